@@ -0,0 +1,80 @@
+//! Load a gerber directory, run DRC and print the results as JSON
+//!
+//! Runs without the GUI:
+//!
+//!     cargo run -p copperforge-core --no-default-features --example headless_drc -- path/to/gerbers
+//!
+//! `--drc-report out.json` also writes the CI report, same as the DRC panel's export.
+//! `--rules profile.json` checks against a rule profile exported from the DRC panel.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use copperforge_core::api;
+
+fn main() -> ExitCode {
+    let mut dir = None;
+    let mut report_path = None;
+    let mut profile_path = None;
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--drc-report" {
+            report_path = args.next().map(PathBuf::from);
+        } else if arg == "--rules" {
+            profile_path = args.next().map(PathBuf::from);
+        } else {
+            dir = Some(PathBuf::from(arg));
+        }
+    }
+    let Some(dir) = dir else {
+        eprintln!("Usage: headless_drc <gerber directory> [--rules profile.json] [--drc-report out.json]");
+        return ExitCode::FAILURE;
+    };
+
+    let mut world = api::setup_ecs_world();
+    let load = match api::load_gerber_directory(&mut world, &dir) {
+        Ok(load) => load,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let profile = match profile_path.map(|path| api::load_profile_file(&path)).transpose() {
+        Ok(profile) => profile.map(|(profile, missing)| {
+            if !missing.is_empty() {
+                eprintln!("Rule profile {} has no {}; using defaults", profile.name, missing.join(", "));
+            }
+            profile
+        }),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let rules = profile.as_ref().map(|profile| profile.rules.clone()).unwrap_or_default();
+    let results = match api::run_drc(&mut world, &rules) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(report_path) = report_path {
+        if let Err(e) = api::write_drc_report(&report_path, &results.report(&rules, profile.as_ref().map(|profile| profile.name.as_str()), Some(&dir))) {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let output = serde_json::json!({
+        "load": load,
+        "board_bounds": api::board_bounds(&world),
+        "layers": api::layer_summaries(&world),
+        "violations": results.violations,
+        "mask_warnings": results.mask_warnings,
+    });
+    println!("{}", serde_json::to_string_pretty(&output).expect("results serialize"));
+
+    if results.violations.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(2) }
+}
@@ -0,0 +1,33 @@
+//! Load a gerber directory and print per-layer trace, flash and region counts
+//!
+//! Runs without the GUI:
+//!
+//!     cargo run -p copperforge-core --no-default-features --example trace_counts -- path/to/gerbers
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use copperforge_core::api;
+
+fn main() -> ExitCode {
+    let Some(dir) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("Usage: trace_counts <gerber directory>");
+        return ExitCode::FAILURE;
+    };
+
+    let mut world = api::setup_ecs_world();
+    if let Err(e) = api::load_gerber_directory(&mut world, &dir) {
+        eprintln!("{}", e);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{:<28} {:>8} {:>12} {:>8} {:>8}", "Layer", "Traces", "Length (mm)", "Flashes", "Regions");
+    for layer in api::layers(&world) {
+        let stats = layer.stats();
+        println!(
+            "{:<28} {:>8} {:>12.1} {:>8} {:>8}",
+            layer.name, stats.traces, stats.trace_length, stats.flashes, stats.regions
+        );
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,255 @@
+//! GUI-free entry points for loading gerbers, querying layers and running DRC
+//!
+//! Everything here is available without the `gui` feature, so scripts and CI
+//! jobs can use CopperForge without eframe or a window. Colors are returned as
+//! plain unmultiplied RGBA.
+//!
+//! Loading and DRC take `&mut World`; the layer queries only read the world and
+//! take `&World`, so a loaded world can be shared with other code while it is
+//! inspected.
+
+use std::path::Path;
+
+use bevy_ecs::world::{EntityRef, World};
+use serde::Serialize;
+
+use crate::drc_operations::types::TraceQualityIssue;
+use crate::drc_operations::{DrcManager, collect_drc_layers, drill, mask};
+use crate::ecs::components::{GerberData, LayerInfo, RenderProperties, Visibility};
+use crate::ecs::LayerPrimitives;
+
+pub use crate::drc_operations::profiles::{DrcRuleProfile, builtin_profiles, load_profile_file};
+pub use crate::drc_operations::report::{DrcReport, write_drc_report};
+pub use crate::drc_operations::types::{DrcCheckConfig, DrcRules, DrcViolation};
+pub use crate::ecs::{ApertureShape, LayerProvenance, LayerType, Polarity, PrimitiveShape, ProvenanceSummary, Side, TaggedPrimitive, setup_ecs_world};
+
+/// Result of loading a gerber directory
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadSummary {
+    pub loaded_layers: usize,
+    /// Gerber files whose layer type could not be detected from the filename
+    pub unassigned_files: usize,
+    pub drill_holes: usize,
+}
+
+/// Replace the layers in `world` with the gerber and drill files in `dir`
+pub fn load_gerber_directory(world: &mut World, dir: &Path) -> Result<LoadSummary, String> {
+    crate::ecs::clear_all_layers_system(world);
+    let (loaded_layers, unassigned_files) = crate::ecs::load_gerbers_from_directory_system(world, dir)?;
+    Ok(LoadSummary {
+        loaded_layers,
+        unassigned_files,
+        drill_holes: crate::ecs::get_drill_holes(world).len(),
+    })
+}
+
+/// Replace the layers in `world` with the artwork and drills of an ODB++ job directory
+pub fn load_odb_directory(world: &mut World, dir: &Path) -> Result<LoadSummary, String> {
+    let result = crate::import::odb::import(world, dir)?;
+    Ok(LoadSummary {
+        loaded_layers: result.loaded_layers(),
+        unassigned_files: 0,
+        drill_holes: crate::ecs::get_drill_holes(world).len(),
+    })
+}
+
+/// Layer type for a gerber filename, using the built-in naming patterns
+pub fn detect_layer_type(filename: &str) -> Option<LayerType> {
+    crate::ecs::LayerDetector::new().detect_layer_type(filename)
+}
+
+/// Axis-aligned bounds in mm, `[min_x, min_y, max_x, max_y]`
+pub type Bounds = [f64; 4];
+
+fn union_bounds(a: Bounds, b: Bounds) -> Bounds {
+    [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])]
+}
+
+/// Read-only view of one loaded layer, borrowed from the world
+#[derive(Debug, Clone, Copy)]
+pub struct LayerRef<'w> {
+    pub layer_type: LayerType,
+    pub name: &'w str,
+    /// Source gerber, when the layer was loaded from a file
+    pub file_path: Option<&'w Path>,
+    pub visible: bool,
+    /// Unmultiplied RGBA
+    pub color: [u8; 4],
+    pub bounds: Bounds,
+    /// Extracted geometry in gerber coordinates (mm); empty without gerber source
+    pub primitives: &'w [TaggedPrimitive],
+}
+
+/// Counts of a layer's primitives by kind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LayerStats {
+    /// Linear draws (traces, outline segments)
+    pub traces: usize,
+    /// Total length of the draws in mm
+    pub trace_length: f64,
+    /// Aperture flashes (pads, vias)
+    pub flashes: usize,
+    /// Filled regions (pours, polygons)
+    pub regions: usize,
+}
+
+impl<'w> LayerRef<'w> {
+    fn from_entity(entity: EntityRef<'w>) -> Option<Self> {
+        let info = entity.get::<LayerInfo>()?;
+        let bbox = entity.get::<GerberData>()?.0.bounding_box();
+        Some(Self {
+            layer_type: info.layer_type,
+            name: &info.name,
+            file_path: info.file_path.as_deref(),
+            visible: entity.get::<Visibility>().is_some_and(|visibility| visibility.visible),
+            color: entity.get::<RenderProperties>().map(RenderProperties::rgba).unwrap_or_default(),
+            bounds: [bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y],
+            primitives: entity.get::<LayerPrimitives>().map(|p| p.0.as_slice()).unwrap_or_default(),
+        })
+    }
+
+    pub fn stats(&self) -> LayerStats {
+        let mut stats = LayerStats::default();
+        for primitive in self.primitives {
+            match &primitive.shape {
+                PrimitiveShape::Segment { start, end, .. } => {
+                    stats.traces += 1;
+                    stats.trace_length += (end - start).norm();
+                }
+                PrimitiveShape::Flash { .. } => stats.flashes += 1,
+                PrimitiveShape::Region { .. } => stats.regions += 1,
+            }
+        }
+        stats
+    }
+}
+
+/// Every loaded layer, in layer order
+pub fn layers(world: &World) -> Vec<LayerRef<'_>> {
+    let mut layers: Vec<LayerRef> = world.iter_entities().filter_map(LayerRef::from_entity).collect();
+    let order = LayerType::all();
+    layers.sort_by_key(|layer| order.iter().position(|l| *l == layer.layer_type).unwrap_or(order.len()));
+    layers
+}
+
+/// The loaded layer of a type
+pub fn layer(world: &World, layer_type: LayerType) -> Option<LayerRef<'_>> {
+    world.iter_entities()
+        .filter_map(LayerRef::from_entity)
+        .find(|layer| layer.layer_type == layer_type)
+}
+
+/// Types of the loaded layers, in layer order
+pub fn loaded_layer_types(world: &World) -> Vec<LayerType> {
+    layers(world).iter().map(|layer| layer.layer_type).collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerSummary {
+    pub layer_type: LayerType,
+    pub name: String,
+    pub visible: bool,
+    /// Unmultiplied RGBA
+    pub color: [u8; 4],
+    pub bounds: Bounds,
+    pub primitive_count: usize,
+    pub flash_count: usize,
+}
+
+/// One summary per loaded layer, in layer order
+pub fn layer_summaries(world: &World) -> Vec<LayerSummary> {
+    layers(world)
+        .into_iter()
+        .map(|layer| LayerSummary {
+            layer_type: layer.layer_type,
+            name: layer.name.to_string(),
+            visible: layer.visible,
+            color: layer.color,
+            bounds: layer.bounds,
+            primitive_count: layer.primitives.len(),
+            flash_count: layer.stats().flashes,
+        })
+        .collect()
+}
+
+/// Combined bounds of the visible layers, if any are loaded
+pub fn board_bounds(world: &World) -> Option<Bounds> {
+    layers(world)
+        .into_iter()
+        .filter(|layer| layer.visible)
+        .map(|layer| layer.bounds)
+        .reduce(union_bounds)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DrcResults {
+    /// Copper and drill violations
+    pub violations: Vec<DrcViolation>,
+    /// Soldermask warnings, reported apart from the hard violations
+    pub mask_warnings: Vec<DrcViolation>,
+    pub trace_quality_issues: Vec<TraceQualityIssue>,
+    /// Generating software and plot time of each checked gerber
+    pub provenance: ProvenanceSummary,
+}
+
+impl DrcResults {
+    /// CI report of these results, same format as the GUI's JSON export
+    /// `rule_profile` names the profile the rules were loaded from, for traceability
+    pub fn report(&self, rules: &DrcRules, rule_profile: Option<&str>, pcb_path: Option<&Path>) -> DrcReport {
+        let mut report = DrcReport::new(pcb_path, rule_profile, rules, &self.violations, &self.mask_warnings);
+        report.provenance = self.provenance.clone();
+        report
+    }
+}
+
+/// Run the full DRC (copper, drill and mask checks) on the loaded layers
+/// Fails when no mechanical outline is loaded, as the board boundary is required
+pub fn run_drc(world: &mut World, rules: &DrcRules) -> Result<DrcResults, String> {
+    let mut manager = DrcManager::new();
+    manager.update_rules(rules.clone());
+    manager.drill_violations = drill::check_loaded_drills(world, rules, &manager.checks).1;
+
+    let layers = collect_drc_layers(world);
+    let layer_hashes = crate::ecs::get_layer_content_hashes(world);
+    if manager.run_incremental_check(&layers, &layer_hashes, true).missing_outline {
+        return Err("No mechanical outline loaded; DRC needs the board boundary".to_string());
+    }
+
+    Ok(DrcResults {
+        violations: manager.violations,
+        mask_warnings: mask::check_loaded_masks(world, rules).unwrap_or_default(),
+        trace_quality_issues: manager.trace_quality_issues,
+        provenance: crate::ecs::get_provenance_summary(world),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_layer_queries() {
+        let mut world = setup_ecs_world();
+        let content = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.250000*%\nD10*\nX0Y0D02*\nX3000000Y4000000D01*\nX0Y0D03*\nM02*\n";
+        let doc = gerber_viewer::gerber_parser::parse(std::io::BufReader::new(content.as_bytes())).unwrap();
+        let layer_data = gerber_viewer::GerberLayer::new(doc.into_commands());
+        crate::ecs::create_gerber_layer_entity(&mut world, LayerType::Copper(1), layer_data, Some(content.to_string()), Some("top.gbr".into()), true);
+
+        let world = &world;
+        assert_eq!(loaded_layer_types(world), vec![LayerType::Copper(1)]);
+        let top = layer(world, LayerType::Copper(1)).unwrap();
+        assert_eq!(top.file_path, Some(Path::new("top.gbr")));
+        let stats = top.stats();
+        assert_eq!((stats.traces, stats.flashes), (1, 1));
+        assert!((stats.trace_length - 5.0).abs() < 1e-9);
+        assert_eq!(board_bounds(world), Some(top.bounds));
+    }
+
+    #[test]
+    fn test_drc_requires_outline() {
+        let mut world = setup_ecs_world();
+        assert!(layer_summaries(&mut world).is_empty());
+        assert!(board_bounds(&mut world).is_none());
+        assert!(run_drc(&mut world, &DrcRules::default()).is_err());
+    }
+}
@@ -12,8 +12,8 @@ use crate::drc_operations::DrcManager;
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::*; 
 use gerber_viewer::{
-   BoundingBox, GerberLayer, 
-   ViewState, UiState, GerberTransform
+   BoundingBox, 
+   ViewState, UiState
 };
 // Import platform modules
 use crate::platform::parameters::gui::VERSION;
@@ -24,14 +24,28 @@ use crate::ecs;
 use crate::project_manager;
 
 use crate::ui::{Tab, TabKind, TabViewer, initialize_and_show_banner, show_system_info};
+use crate::ui::key_bindings::ShortcutAction;
+use crate::ui::i18n::{tr, tr_args};
 
-use crate::project::{load_demo_gerber, ProjectManager, ProjectState, manager::ProjectConfig};
+use crate::project::{ProjectManager, ProjectState, manager::ProjectConfig};
 use crate::display::GridSettings;
 
+/// Alt+<digit> toggles the Nth loaded layer in the layer list
+const LAYER_TOGGLE_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1, egui::Key::Num2, egui::Key::Num3,
+    egui::Key::Num4, egui::Key::Num5, egui::Key::Num6,
+    egui::Key::Num7, egui::Key::Num8, egui::Key::Num9,
+];
+
+/// Repaint interval while nothing changes, so timers and cross-thread signals still run
+/// Input, animations and background jobs request their own repaints
+const IDLE_REPAINT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Seconds between writes of the dock layout
+const DOCK_SAVE_INTERVAL_SECS: f64 = 30.0;
+
 /// The main application struct
 pub struct DemoLensApp {
-    // Legacy single layer support (for compatibility)
-    pub gerber_layer: GerberLayer,
     pub view_state: ViewState,
     pub ui_state: UiState,
     pub needs_initial_view: bool,
@@ -48,8 +62,23 @@ pub struct DemoLensApp {
     // DRC management
     pub drc_manager: DrcManager,
     
-    // Global units setting
-    pub global_units_mils: bool, // true = mils, false = mm
+    // DRC run in progress on a worker thread
+    pub drc_job: Option<crate::drc_operations::DrcJob>,
+    
+    // DRC sweep over all database projects in progress
+    pub batch_drc_job: Option<crate::project_manager::batch_drc::BatchDrcJob>,
+    
+    // Operation shown in the status bar
+    pub status_bar: ui::status_bar::StatusBarState,
+    
+    // Pending overwrite of the shared workspace file
+    pub workspace_sync: ui::workspace_sync::WorkspaceSyncState,
+    
+    // Primitive under the cursor and the one clicked in the gerber view
+    pub primitive_inspect: ui::primitive_inspect::PrimitiveInspectState,
+    
+    // Global units setting, mirrored into the ECS UnitsResource
+    pub display_unit: ecs::DisplayUnit,
     
     // Grid Settings
     pub grid_settings: GridSettings,
@@ -62,12 +91,18 @@ pub struct DemoLensApp {
 
     // Dock state
     dock_state: DockState<Tab>,
+    // Project whose layout `dock_state` belongs to (None = global default layout)
+    dock_project_id: Option<String>,
     pub config_path: PathBuf,
     
     
     // Zoom window state
     pub zoom_window_start: Option<Pos2>,
     pub zoom_window_dragging: bool,
+    /// The next right-drag draws a DRC region instead of zooming (toggled in the DRC panel)
+    pub drc_region_armed: bool,
+    /// The current right-drag draws a DRC region
+    pub drc_region_drag: bool,
     
     // User preferences
     pub user_timezone: Option<String>,
@@ -88,10 +123,19 @@ pub struct DemoLensApp {
     pub ruler_end: Option<nalgebra::Point2<f64>>,
     pub ruler_dragging: bool,
     pub ruler_drag_start: Option<nalgebra::Point2<f64>>,
+    /// Ruler measures along the board outline instead of a straight line
+    pub ruler_edge_mode: bool,
+    /// Report the longer way round the outline instead of the shorter
+    pub ruler_edge_flip: bool,
     
     // Latched measurement (persists after measurement mode is exited)
     pub latched_measurement_start: Option<nalgebra::Point2<f64>>,
     pub latched_measurement_end: Option<nalgebra::Point2<f64>>,
+    /// Straight measurements taken this session, for recall and CSV export
+    pub measurement_history: ui::measurement_history::MeasurementHistory,
+    
+    // Net highlighting (id into the ECS NetTable)
+    pub highlighted_net: Option<u32>,
     
     
     // BOM panel state
@@ -107,33 +151,140 @@ pub struct DemoLensApp {
     
     // Project manager state
     pub project_manager_state: Option<project_manager::ProjectManagerState>,
+    
+    // Revision B overlay and raster diff
+    pub revision_compare: ui::RevisionCompareState,
+    
+    // Probe point picker (list persisted per project)
+    pub point_picker: ui::PointPickerState,
+    
+    // Review notes, arrows and rectangles (list persisted per project)
+    pub annotations: ui::AnnotationState,
+    
+    // Drill report window and per-diameter symbols on the holes
+    pub drill_report: ui::DrillReportState,
+    
+    // Component placement overlap results (ignored pairs persisted per project)
+    pub assembly_checks: ui::AssemblyCheckState,
+    pub drc_profiles: ui::DrcProfileState,
+    pub view_3d: ui::View3dState,
+    /// Tab to open (or focus) once the dock has been drawn
+    pub open_tab_request: Option<TabKind>,
+    /// Remapped viewer shortcuts, persisted in the project config
+    pub key_bindings: ui::key_bindings::KeyBindings,
+    pub key_binding_editor: ui::key_bindings::KeyBindingEditor,
+    /// Command run after gerber generation, persisted in the project config
+    pub post_gen_hook: crate::project_manager::post_gen_hook::PostGenHook,
+    pub post_gen_hook_state: ui::post_gen_hook::PostGenHookState,
+    pub recent_projects: ui::recent_projects::RecentProjectsState,
+    /// Offer to reload single layers when their gerber file changes on disk
+    pub watch_gerber_files: bool,
+    pub layer_watch: ui::layer_watch::LayerWatchState,
+    /// Language of the interface text, persisted in the project config
+    pub language: ui::i18n::Language,
+    pub netlist_checks: ui::NetlistCheckState,
+    
+    // Project notes: edit, rendered Markdown preview, or both
+    pub notes_view: ui::project_panel::NotesView,
+    
+    // PNG export dialog (settings remembered in the project config)
+    pub show_png_export_dialog: bool,
+    /// "Gerber Parse Problems" window is open
+    pub show_parse_problems: bool,
+    pub png_export_settings: crate::export::PngExportSettings,
+    
+    // DRC marker accessibility options (remembered in the project config)
+    pub drc_marker_settings: ui::drc_markers::DrcMarkerSettings,
+    
+    // Layer color schemes: built-in presets plus any imported this session
+    pub color_schemes: Vec<crate::display::ColorScheme>,
+    pub selected_color_scheme: usize,
+    
+    // Project auto-save (interval remembered in the project config, 0 = off)
+    pub autosave_interval_secs: u32,
+    last_autosave_time: f64,
+    last_dock_save_time: f64,
+    
+    // Layer registration details popup (opened from the layer panel warning icon)
+    pub show_registration_details: bool,
+    
+    // Pinch zoom / two-finger pan (can be turned off in Settings, remembered in the project config)
+    pub touch_gestures_enabled: bool,
+    pub gestures: ui::gestures::GestureState,
+    
+    // Board overview inset in the gerber view (settings remembered in the project config)
+    pub minimap_settings: ui::minimap::MinimapSettings,
+    pub minimap: ui::minimap::MinimapState,
+    pub dro_settings: ui::dro::DroSettings,
+    pub dro: ui::dro::DroState,
+    
+    // Eased rotation / flip transitions (can be turned off in Settings, remembered in the project config)
+    pub view_animations_enabled: bool,
+    pub view_animation: display::ViewAnimation,
+    
+    // DRC violation tree selection and click-to-focus (ignore list lives in drc_manager)
+    pub drc_violation_list: ui::drc_violations::DrcViolationListState,
+    
+    // Reuse extracted primitives of unchanged gerbers between launches (remembered in the project config)
+    pub use_parse_cache: bool,
+    
+    // Coordinate format override window opened from the layer list
+    pub format_override_editor: Option<ui::format_override::FormatOverrideEditor>,
+    
+    // Active layer alignment panel (the nudges themselves live on the layer transforms)
+    pub layer_nudge: ui::layer_nudge::LayerNudgeState,
+    /// Layer list filter, kept for the session
+    pub layer_list_filter: ui::layer_controls::LayerListFilter,
+    pub unassigned_selection: ui::layer_controls::UnassignedSelection,
+    /// Gerber viewport and pending "Copy view" screenshot
+    pub view_clipboard: ui::view_clipboard::ViewClipboardState,
+    
+    // Project list tag filter from the last session, handed to the project manager when it opens
+    pub project_tag_filter: project_manager::TagFilter,
+    
+    // Outline measurements and fab cost rates (rates remembered in the project config)
+    pub board_size: ui::board_size::BoardSizeState,
+    pub cost_settings: project_manager::cost::CostSettings,
 }
 
 impl Drop for DemoLensApp {
     fn drop(&mut self) {
         // Save dock state when application closes
         self.save_dock_state();
+        // Write any project changes the auto-save hasn't picked up yet
+        if let Some(state) = self.project_manager_state.as_mut() {
+            if let Err(e) = state.save_current_project() {
+                eprintln!("Failed to save project: {}", e);
+            }
+        }
         // Save project config with time settings
         self.save_settings();
     }
 }
 
 impl DemoLensApp {
-    /// Sync units between legacy global_units_mils and ECS UnitsResource
+    /// Parse cache directory under the config dir
+    pub fn parse_cache(&self) -> ecs::ParseCache {
+        ecs::ParseCache::new(ecs::parse_cache_dir(&self.config_path))
+    }
+    
+    /// Make gerber loads use (or stop using) the parse cache
+    pub fn apply_parse_cache_setting(&mut self) {
+        let cache = self.use_parse_cache.then(|| self.parse_cache());
+        ecs::set_parse_cache(&mut self.ecs_world, cache);
+    }
+    
+    /// Sync units from the app setting to the ECS UnitsResource
     pub fn sync_units_to_ecs(&mut self) {
         if let Some(mut units_resource) = self.ecs_world.get_resource_mut::<ecs::UnitsResource>() {
-            if self.global_units_mils {
-                units_resource.set_mils();
-            } else {
-                units_resource.set_mm();
-            }
+            units_resource.display_unit = self.display_unit;
         }
     }
     
-    /// Sync units from ECS UnitsResource to legacy global_units_mils
+    /// Sync units from the ECS UnitsResource to the app setting
     pub fn sync_units_from_ecs(&mut self) {
         if let Some(units_resource) = self.ecs_world.get_resource::<ecs::UnitsResource>() {
-            self.global_units_mils = units_resource.is_mils();
+            self.display_unit = units_resource.display_unit;
         }
     }
     
@@ -154,6 +305,247 @@ impl DemoLensApp {
         }
     }
     
+    /// Set the board rotation, wrapping into [0, 360)
+    /// Marks coordinates dirty instead of resetting the view so the current origin stays put
+    pub fn set_rotation_degrees(&mut self, degrees: f32) {
+        self.rotation_degrees = crate::display::wrap_rotation_degrees(degrees);
+        self.view_animation.stop_rotation();
+        crate::ecs::mark_coordinates_dirty_ecs(&mut self.ecs_world);
+    }
+    
+    /// Set the board rotation with an eased transition from the angle currently on screen
+    pub fn animate_rotation_to(&mut self, degrees: f32) {
+        let from = self.displayed_rotation_degrees();
+        self.set_rotation_degrees(degrees);
+        if self.view_animations_enabled {
+            self.view_animation.start_rotation(from, self.rotation_degrees);
+        }
+    }
+    
+    /// Rotation drawn this frame; differs from `rotation_degrees` only while a transition runs
+    pub fn displayed_rotation_degrees(&self) -> f32 {
+        self.view_animation.rotation_degrees(self.rotation_degrees)
+    }
+    
+    /// Switch between the top and bottom view, showing the layers of the new side
+    pub fn flip_board_view(&mut self) {
+        self.display_manager.flip_view();
+        let showing_top = self.display_manager.showing_top;
+        
+        // Auto-toggle layer visibility based on flip state using ECS
+        for layer_type in crate::ecs::LayerType::all() {
+            let visible = match layer_type {
+                crate::ecs::LayerType::Copper(1) |
+                crate::ecs::LayerType::Silkscreen(crate::ecs::Side::Top) |
+                crate::ecs::LayerType::Soldermask(crate::ecs::Side::Top) |
+                crate::ecs::LayerType::Paste(crate::ecs::Side::Top) => showing_top,
+                crate::ecs::LayerType::Copper(2) |
+                crate::ecs::LayerType::Silkscreen(crate::ecs::Side::Bottom) |
+                crate::ecs::LayerType::Soldermask(crate::ecs::Side::Bottom) |
+                crate::ecs::LayerType::Paste(crate::ecs::Side::Bottom) => !showing_top,
+                crate::ecs::LayerType::Copper(_) |
+                crate::ecs::LayerType::MechanicalOutline => {
+                    // Leave inner copper and outline visibility unchanged, get current state from ECS
+                    crate::ecs::get_layer_visibility(&mut self.ecs_world, layer_type)
+                }
+            };
+            crate::ecs::set_layer_visibility(&mut self.ecs_world, layer_type, visible);
+        }
+        
+        if self.view_animations_enabled {
+            self.view_animation.start_flip();
+        }
+        // Mark coordinates as dirty since view changed
+        crate::ecs::mark_coordinates_dirty_ecs(&mut self.ecs_world);
+    }
+    
+    /// Apply a project template's DRC rules, grid settings and layer colors to the live app
+    pub fn apply_project_template(&mut self, template: &project_manager::templates::ProjectTemplate) {
+        self.drc_manager.update_rules(template.drc_rules.clone());
+        self.grid_settings = template.grid.clone();
+        for layer_color in &template.layer_colors {
+            let [r, g, b] = layer_color.rgb;
+            ecs::update_layer_render_properties(&mut self.ecs_world, layer_color.layer, egui::Color32::from_rgb(r, g, b));
+        }
+    }
+    
+    /// Capture the current DRC rules, grid settings and loaded layer colors as a template
+    pub fn capture_project_template(&mut self, name: &str) -> project_manager::templates::ProjectTemplate {
+        let layer_colors = ecs::LayerType::all()
+            .into_iter()
+            .filter_map(|layer_type| {
+                ecs::get_layer_render_properties(&mut self.ecs_world, layer_type).map(|props| {
+                    project_manager::templates::TemplateLayerColor {
+                        layer: layer_type,
+                        rgb: [props.color.r(), props.color.g(), props.color.b()],
+                    }
+                })
+            })
+            .collect();
+        
+        // Tags and description boilerplate come from the open project, if any
+        let (tags, description) = self.project_manager_state
+            .as_ref()
+            .and_then(|state| state.current_project.as_ref())
+            .map(|project| (project.metadata.tags.clone(), project.metadata.description.clone()))
+            .unwrap_or_default();
+        
+        project_manager::templates::ProjectTemplate {
+            name: name.to_string(),
+            tags,
+            description,
+            drc_rules: self.drc_manager.rules.clone(),
+            layer_colors,
+            grid: self.grid_settings.clone(),
+        }
+    }
+    
+    /// Write timezone, clock, units, grid, DRC profiles, layer colors and key bindings to one JSON file
+    pub fn export_settings(&mut self, path: &std::path::Path) -> Result<(), String> {
+        use crate::project::settings_bundle::*;
+        use crate::drc_operations::profiles;
+        
+        let (all_profiles, _) = profiles::load_profiles(&profiles::profiles_dir(&self.config_path));
+        let user_profiles = all_profiles.into_iter().filter(|profile| !profiles::is_builtin_profile(&profile.name)).collect();
+        let scheme_name = self.color_schemes
+            .get(self.selected_color_scheme)
+            .map(|scheme| scheme.name.clone())
+            .unwrap_or_else(|| "Custom".to_string());
+        
+        let mut bundle = SettingsBundle::default();
+        bundle.insert(TIME_SECTION, &TimeSettings {
+            timezone: self.user_timezone.clone(),
+            use_24_hour_clock: self.use_24_hour_clock,
+        })?;
+        bundle.insert(UNITS_SECTION, &UnitsSettings::new(self.display_unit))?;
+        bundle.insert(GRID_SECTION, &self.grid_settings)?;
+        bundle.insert(DRC_PROFILES_SECTION, &DrcProfileSettings {
+            profiles: user_profiles,
+            active: self.drc_manager.current_ruleset.clone(),
+            rules: self.drc_manager.rules.clone(),
+        })?;
+        bundle.insert(COLOR_SCHEME_SECTION, &crate::display::ColorScheme::from_world(&scheme_name, &mut self.ecs_world))?;
+        bundle.insert(KEY_BINDINGS_SECTION, &self.key_bindings)?;
+        bundle.save_to_file(path)
+    }
+    
+    /// Apply a file written by `export_settings`
+    /// Sections from a newer version, with invalid data or unknown to this build are skipped and reported;
+    /// an unreadable file or one that is not a settings file applies nothing
+    pub fn import_settings(&mut self, path: &std::path::Path) -> Result<project::settings_bundle::ImportReport, String> {
+        use crate::project::settings_bundle::*;
+        use crate::drc_operations::profiles;
+        
+        let bundle = SettingsBundle::load_from_file(path)?;
+        let mut report = ImportReport::default();
+        
+        if let Some(time) = bundle.section::<TimeSettings>(TIME_SECTION, &mut report) {
+            match &time.timezone {
+                Some(name) if name.parse::<chrono_tz::Tz>().is_err() => {
+                    report.skip(TIME_SECTION, format!("unknown timezone \"{}\"", name));
+                }
+                _ => {
+                    self.user_timezone = time.timezone;
+                    self.use_24_hour_clock = time.use_24_hour_clock;
+                    report.applied.push(TIME_SECTION.to_string());
+                }
+            }
+        }
+        if let Some(units) = bundle.section::<UnitsSettings>(UNITS_SECTION, &mut report) {
+            self.display_unit = units.display_unit();
+            self.sync_units_to_ecs();
+            report.applied.push(UNITS_SECTION.to_string());
+        }
+        if let Some(grid) = bundle.section::<GridSettings>(GRID_SECTION, &mut report) {
+            self.grid_settings = grid;
+            report.applied.push(GRID_SECTION.to_string());
+        }
+        if let Some(drc) = bundle.section::<DrcProfileSettings>(DRC_PROFILES_SECTION, &mut report) {
+            let dir = profiles::profiles_dir(&self.config_path);
+            let failed: Vec<String> = drc.profiles.iter()
+                .filter_map(|profile| profiles::save_profile(profile, &dir).err())
+                .collect();
+            self.drc_manager.update_rules(drc.rules);
+            self.drc_manager.current_ruleset = drc.active;
+            self.drc_profiles.invalidate();
+            if failed.is_empty() {
+                report.applied.push(DRC_PROFILES_SECTION.to_string());
+            } else {
+                report.skip(DRC_PROFILES_SECTION, format!("rules applied, but some profiles were not saved: {}", failed.join("; ")));
+            }
+        }
+        if let Some(scheme) = bundle.section::<crate::display::ColorScheme>(COLOR_SCHEME_SECTION, &mut report) {
+            scheme.apply(&mut self.ecs_world);
+            match self.color_schemes.iter().position(|existing| existing.name == scheme.name) {
+                Some(i) => {
+                    self.color_schemes[i] = scheme;
+                    self.selected_color_scheme = i;
+                }
+                None => {
+                    self.color_schemes.push(scheme);
+                    self.selected_color_scheme = self.color_schemes.len() - 1;
+                }
+            }
+            report.applied.push(COLOR_SCHEME_SECTION.to_string());
+        }
+        if let Some(bindings) = bundle.section::<ui::key_bindings::KeyBindings>(KEY_BINDINGS_SECTION, &mut report) {
+            self.key_bindings = bindings;
+            report.applied.push(KEY_BINDINGS_SECTION.to_string());
+        }
+        for name in bundle.unknown_sections() {
+            report.skip(&name, format!("not supported by this version (file from {})", bundle.app_version));
+        }
+        
+        self.save_settings();
+        Ok(report)
+    }
+    
+    /// Shared settings of the open board for its workspace file (see `project::workspace`)
+    pub fn workspace_settings(&mut self) -> project::workspace::WorkspaceSettings {
+        let scheme_name = self.color_schemes
+            .get(self.selected_color_scheme)
+            .map(|scheme| scheme.name.clone())
+            .unwrap_or_else(|| "Workspace".to_string());
+        let origin_set = self.display_manager.design_offset.x != 0.0 || self.display_manager.design_offset.y != 0.0;
+        project::workspace::WorkspaceSettings {
+            layer_colors: Some(crate::display::ColorScheme::from_world(&scheme_name, &mut self.ecs_world)),
+            drc_profile: self.drc_manager.current_ruleset.clone(),
+            drc_rules: Some(self.drc_manager.rules.clone()),
+            grid: Some(self.grid_settings.clone()),
+            origin: origin_set.then(|| self.display_manager.design_offset.clone()),
+            ..Default::default()
+        }
+    }
+    
+    /// Override the personal values with the sections a workspace file lists
+    /// Layer colors only reach loaded layers, so this runs after the gerbers are loaded
+    pub fn apply_workspace_settings(&mut self, workspace: project::workspace::WorkspaceSettings) -> Vec<&'static str> {
+        let mut applied = Vec::new();
+        if let Some(scheme) = workspace.layer_colors {
+            scheme.apply(&mut self.ecs_world);
+            applied.push("layer colors");
+        }
+        if let Some(rules) = workspace.drc_rules {
+            // The file carries no display unit; keep the personal one
+            let display_unit = self.drc_manager.rules.display_unit;
+            self.drc_manager.update_rules(crate::drc_operations::types::DrcRules { display_unit, ..rules });
+            self.drc_manager.current_ruleset = workspace.drc_profile;
+            applied.push("DRC rules");
+        }
+        if let Some(grid) = workspace.grid {
+            self.grid_settings = grid;
+            applied.push("grid");
+        }
+        if let Some(origin) = workspace.origin {
+            self.display_manager.design_offset = origin;
+            self.origin_has_been_set = true;
+            self.needs_initial_view = true;
+            crate::ecs::mark_coordinates_dirty_ecs(&mut self.ecs_world);
+            applied.push("origin");
+        }
+        applied
+    }
+    
     /// Render layers using ECS system
     pub fn render_layers_ecs(&mut self, painter: &egui::Painter) {
         // Update view state resource
@@ -162,8 +554,15 @@ impl DemoLensApp {
             view_mode: ecs::ViewMode::Normal, // Will be updated based on display manager
         });
         
-        // Run ECS systems to update entity states
-        ecs::run_ecs_systems(&mut self.ecs_world, &self.display_manager, self.rotation_degrees);
+        // Run ECS systems to update entity states, turning about the center of what is loaded
+        self.display_manager.rotation_center = ecs::get_rotation_center(&mut self.ecs_world).coords.into();
+        ecs::run_ecs_systems(&mut self.ecs_world, &self.display_manager, self.displayed_rotation_degrees());
+        
+        // Swipe compare replaces the normal multi-layer render with two clipped layers
+        if self.display_manager.swipe_compare.enabled {
+            self.render_swipe_compare(painter);
+            return;
+        }
         
         // Use the new ECS render system
         ecs::execute_render_system(
@@ -172,12 +571,33 @@ impl DemoLensApp {
             self.view_state,
             &self.display_manager,
             true, // Use enhanced rendering with quadrant support
+            self.highlighted_net,
+            !self.revision_compare.enabled, // Revision overlays need flat layer colors
         );
     }
 
+    /// Render layer A left of the swipe divider and layer B right of it
+    fn render_swipe_compare(&mut self, painter: &egui::Painter) {
+        let viewport = painter.clip_rect();
+        let swipe = self.display_manager.swipe_compare.clone();
+        let divider_x = swipe.divider_x(&viewport);
+        
+        let left = Rect::from_min_max(viewport.min, egui::pos2(divider_x, viewport.max.y));
+        let right = Rect::from_min_max(egui::pos2(divider_x, viewport.min.y), viewport.max);
+        
+        for (layer_type, clip) in [(swipe.layer_a, left), (swipe.layer_b, right)] {
+            ecs::render_single_layer_system(
+                &mut self.ecs_world,
+                &painter.with_clip_rect(clip),
+                self.view_state,
+                &self.display_manager,
+                layer_type,
+            );
+        }
+    }
+
     pub fn new() -> Self {
 
-        let gerber_layer = load_demo_gerber();
         let display_manager = DisplayManager::new();
         
         // Force initial view setup to center gerber at origin
@@ -194,7 +614,6 @@ impl DemoLensApp {
         let ecs_world = ecs::setup_ecs_world();
         
         let mut app = Self {
-            gerber_layer,
             view_state: ViewState::default(),
             ui_state: UiState::default(),
             needs_initial_view: true,
@@ -203,16 +622,24 @@ impl DemoLensApp {
             log_colors,
             display_manager,
             drc_manager: DrcManager::new(),
-            global_units_mils: false, // Default to mm
+            drc_job: None,
+            batch_drc_job: None,
+            status_bar: ui::status_bar::StatusBarState::default(),
+            workspace_sync: ui::workspace_sync::WorkspaceSyncState::default(),
+            primitive_inspect: ui::primitive_inspect::PrimitiveInspectState::default(),
+            display_unit: ecs::DisplayUnit::Millimeters,
             grid_settings: GridSettings::default(),
             project_manager: ProjectManager::new(),
             ecs_world,
             dock_state,
+            dock_project_id: None,
             config_path: dirs::config_dir()
                 .map(|d| d.join("copperforge"))
                 .unwrap_or_default(),
             zoom_window_start: None,
             zoom_window_dragging: false,
+            drc_region_armed: false,
+            drc_region_drag: false,
             user_timezone: None,
             use_24_hour_clock: false, // Default to 12-hour format
             show_about_modal: false,
@@ -223,33 +650,101 @@ impl DemoLensApp {
             ruler_end: None,
             ruler_dragging: false,
             ruler_drag_start: None,
+            ruler_edge_mode: false,
+            ruler_edge_flip: false,
             latched_measurement_start: None,
             latched_measurement_end: None,
+            measurement_history: ui::measurement_history::MeasurementHistory::default(),
+            highlighted_net: None,
             bom_state: None,
             pending_bom_components: None,
             cross_probe_slot: None,
             cross_probe_slot_started: false,
             pending_cross_probe: egui_mobius::types::Value::new(None),
             project_manager_state: None,
+            revision_compare: ui::RevisionCompareState::default(),
+            point_picker: ui::PointPickerState::default(),
+            annotations: ui::AnnotationState::default(),
+            drill_report: ui::DrillReportState::default(),
+            assembly_checks: ui::AssemblyCheckState::default(),
+            drc_profiles: ui::DrcProfileState::default(),
+            view_3d: ui::View3dState::default(),
+            open_tab_request: None,
+            key_bindings: ui::key_bindings::KeyBindings::default(),
+            key_binding_editor: ui::key_bindings::KeyBindingEditor::default(),
+            post_gen_hook: crate::project_manager::post_gen_hook::PostGenHook::default(),
+            post_gen_hook_state: ui::post_gen_hook::PostGenHookState::default(),
+            recent_projects: ui::recent_projects::RecentProjectsState::default(),
+            watch_gerber_files: false,
+            layer_watch: ui::layer_watch::LayerWatchState::default(),
+            language: ui::i18n::Language::default(),
+            netlist_checks: ui::NetlistCheckState::default(),
+            notes_view: ui::project_panel::NotesView::default(),
+            show_png_export_dialog: false,
+            show_parse_problems: false,
+            png_export_settings: crate::export::PngExportSettings::default(),
+            drc_marker_settings: ui::drc_markers::DrcMarkerSettings::default(),
+            color_schemes: crate::display::ColorScheme::presets(),
+            selected_color_scheme: 0,
+            autosave_interval_secs: crate::project::manager::DEFAULT_AUTOSAVE_INTERVAL_SECS,
+            last_autosave_time: 0.0,
+            last_dock_save_time: 0.0,
+            show_registration_details: false,
+            touch_gestures_enabled: true,
+            gestures: ui::gestures::GestureState::default(),
+            minimap_settings: ui::minimap::MinimapSettings::default(),
+            minimap: ui::minimap::MinimapState::default(),
+            dro_settings: ui::dro::DroSettings::default(),
+            dro: ui::dro::DroState::default(),
+            view_animations_enabled: true,
+            view_animation: display::ViewAnimation::default(),
+            drc_violation_list: ui::drc_violations::DrcViolationListState::default(),
+            use_parse_cache: true,
+            format_override_editor: None,
+            layer_nudge: ui::layer_nudge::LayerNudgeState::default(),
+            layer_list_filter: ui::layer_controls::LayerListFilter::default(),
+            unassigned_selection: ui::layer_controls::UnassignedSelection::default(),
+            view_clipboard: ui::view_clipboard::ViewClipboardState::default(),
+            project_tag_filter: project_manager::TagFilter::default(),
+            board_size: ui::board_size::BoardSizeState::default(),
+            cost_settings: project_manager::cost::CostSettings::default(),
         };
         
         if let Ok(project_config) = ProjectConfig::load_from_file(&app.config_path) {
             // Load time settings from saved config
             app.user_timezone = project_config.user_timezone.clone();
             app.use_24_hour_clock = project_config.use_24_hour_clock;
-            app.global_units_mils = project_config.global_units_mils;
+            app.display_unit = project_config.display_unit();
+            app.png_export_settings = project_config.png_export;
+            app.drc_marker_settings = project_config.drc_markers;
+            app.autosave_interval_secs = project_config.autosave_interval_secs;
+            app.touch_gestures_enabled = project_config.touch_gestures;
+            app.minimap_settings = project_config.minimap;
+            app.dro_settings = project_config.dro;
+            app.view_animations_enabled = project_config.view_animations;
+            app.drc_manager.ignored = project_config.drc_ignores.clone();
+            app.key_bindings = project_config.key_bindings.clone();
+            app.post_gen_hook = project_config.post_gen_hook.clone();
+            app.ecs_world.resource_mut::<ecs::LayerRenderCache>().lod = project_config.lod;
+            app.ecs_world.resource_mut::<ecs::LayerRenderCache>().show_overlay = project_config.render_stats_overlay;
+            app.ecs_world.insert_resource(project_config.panel);
+            app.use_parse_cache = project_config.use_parse_cache;
+            app.watch_gerber_files = project_config.watch_gerber_files;
+            app.language = project_config.language;
+            ui::i18n::set_language(app.language);
+            app.project_tag_filter = project_config.project_tag_filter.clone();
+            app.cost_settings = project_config.cost_settings.clone();
+            if let Some(profile) = &project_config.drc_profile {
+                ui::drc_profiles::restore_profile(&mut app, profile);
+            }
             
             // Sync units with ECS resource
-            if let Some(mut units_resource) = app.ecs_world.get_resource_mut::<ecs::UnitsResource>() {
-                if app.global_units_mils {
-                    units_resource.set_mils();
-                } else {
-                    units_resource.set_mm();
-                }
-            }
+            app.sync_units_to_ecs();
             
             app.project_manager = ProjectManager::from_config(project_config);
         }
+        app.apply_parse_cache_setting();
+        app.recent_projects = ui::recent_projects::RecentProjectsState::load(&app.config_path);
         
         let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
         initialize_and_show_banner(&logger);
@@ -276,13 +771,24 @@ impl DemoLensApp {
     }
 
     pub fn reset_view(&mut self, viewport: Rect) {
-        // Find bounding box from all loaded layers using ECS
-        let combined_bbox = crate::ecs::get_combined_bounding_box(&mut self.ecs_world);
+        // Find bounding box from all loaded layers using ECS (the whole panel when it is shown)
+        let combined_bbox = crate::ecs::get_view_bounding_box(&mut self.ecs_world);
         
-        // Fall back to demo gerber if no layers loaded
-        let bbox = combined_bbox.unwrap_or_else(|| self.gerber_layer.bounding_box().clone());
-        let content_width = bbox.width();
-        let content_height = bbox.height();
+        // With every layer hidden, frame the loaded layers; with nothing loaded, center the origin
+        let Some(bbox) = combined_bbox.or_else(|| crate::ecs::get_loaded_bounding_box(&mut self.ecs_world)) else {
+            self.view_state.translation = viewport.center().to_vec2();
+            self.view_state.scale = 1.0;
+            if let Some(mut zoom_resource) = self.ecs_world.get_resource_mut::<ecs::ZoomResource>() {
+                zoom_resource.set_scale(1.0);
+                zoom_resource.set_fit_to_view_scale(1.0);
+                zoom_resource.set_center(self.view_state.translation.x, self.view_state.translation.y);
+            }
+            self.needs_initial_view = false;
+            return;
+        };
+        
+        // Fit the rotated extent so non-orthogonal angles don't clip the board
+        let (content_width, content_height) = crate::display::rotated_extent(bbox.width(), bbox.height(), self.rotation_degrees);
         
         // Calculate scale to fit the content (100% zoom)
         let scale = f32::min(
@@ -323,16 +829,9 @@ impl DemoLensApp {
                 y: -gerber_center.y,
             };
 
-            // Create the transform that will be used during rendering
-            let origin: nalgebra::Vector2<f64> = self.display_manager.center_offset.clone().into();
-            let offset: nalgebra::Vector2<f64> = self.display_manager.design_offset.clone().into();
-            let transform = GerberTransform {
-                rotation: self.rotation_degrees.to_radians(),
-                mirroring: self.display_manager.mirroring.clone().into(),
-                origin: origin - offset,
-                offset,
-                scale: 1.0,
-            };
+            // The transform used during rendering
+            self.display_manager.rotation_center = ecs::get_rotation_center(&mut self.ecs_world).coords.into();
+            let transform = display::build_layer_transform(&self.display_manager, self.rotation_degrees, None);
 
             // Compute transformed bounding box
             let outline_vertices: Vec<_> = bbox
@@ -455,7 +954,22 @@ impl DemoLensApp {
 }
 
 impl DemoLensApp {
+    /// Focus the first tab of `kind`, or add one to the focused leaf if it was closed
+    fn open_tab(&mut self, kind: TabKind) {
+        if let Some(location) = self.dock_state.find_tab_from(|tab| tab.kind == kind) {
+            self.dock_state.set_active_tab(location);
+        } else {
+            self.dock_state.push_to_focused_leaf(Tab::new(kind, SurfaceIndex::main(), NodeIndex(0)));
+        }
+    }
+    
+    /// Save the dock layout to the active project, or to the global default when no project is open
     fn save_dock_state(&self) {
+        if let Some(project_id) = &self.dock_project_id {
+            self.save_project_dock_state(project_id);
+            return;
+        }
+        
         if let Some(config_dir) = dirs::config_dir() {
             let copperforge_dir = config_dir.join("copperforge");
             if let Err(e) = fs::create_dir_all(&copperforge_dir) {
@@ -496,12 +1010,135 @@ impl DemoLensApp {
         None
     }
     
+    fn save_project_dock_state(&self, project_id: &str) {
+        let Some(state) = &self.project_manager_state else {
+            return;
+        };
+        // Skip projects deleted while open so no orphaned layout is written
+        let Some(database) = state.database.as_ref()
+            .filter(|_| state.project_list.iter().any(|project| project.id == project_id)) else {
+            return;
+        };
+        match serde_json::to_string(&self.dock_state) {
+            Ok(json) => {
+                if let Err(e) = database.save_dock_layout(project_id, &json) {
+                    eprintln!("Failed to save project dock state: {}", e);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to serialize dock state: {}", e);
+            }
+        }
+    }
+
+    fn load_project_dock_state(&self, project_id: &str) -> Option<DockState<Tab>> {
+        let database = self.project_manager_state.as_ref()?.database.as_ref()?;
+        let json = database.load_dock_layout(project_id).ok()??;
+        match serde_json::from_str::<DockState<Tab>>(&json) {
+            Ok(dock_state) => Some(dock_state),
+            Err(e) => {
+                eprintln!("Failed to deserialize project dock state: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Swap the dock layout when the active project changes
+    /// The outgoing layout is saved first; projects without a saved layout start from the global default
+    fn sync_project_dock_state(&mut self) {
+        let current_id = self.project_manager_state.as_ref()
+            .and_then(|state| state.current_project.as_ref())
+            .map(|project| project.metadata.id.clone());
+        if current_id == self.dock_project_id {
+            return;
+        }
+        
+        self.save_dock_state();
+        self.dock_state = current_id.as_deref()
+            .and_then(|project_id| self.load_project_dock_state(project_id))
+            .unwrap_or_else(Self::create_default_dock_state);
+        self.dock_project_id = current_id;
+    }
+    
+    /// Flip a layer's visibility from a keyboard shortcut and log it
+    fn toggle_layer_visibility(&mut self, layer_type: crate::ecs::LayerType, shortcut: &str) {
+        let Some(visible) = crate::ecs::toggle_layer_visibility(&mut self.ecs_world, layer_type) else {
+            return;
+        };
+        
+        let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
+        logger.log_info(&format!(
+            "{} layer {} ({})",
+            layer_type.display_name(),
+            if visible { "shown" } else { "hidden" },
+            shortcut
+        ));
+    }
+    
+    /// Save the current project every `autosave_interval_secs` if it has unsaved changes
+    fn autosave_project(&mut self, ctx: &egui::Context) {
+        if self.autosave_interval_secs == 0 {
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        if now - self.last_autosave_time < self.autosave_interval_secs as f64 {
+            return;
+        }
+        // Don't save half-typed text; retry once the field loses focus
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        self.last_autosave_time = now;
+        
+        let Some(state) = self.project_manager_state.as_mut() else {
+            return;
+        };
+        // A BOM still waiting to be loaded into the panel is the project's own
+        if self.pending_bom_components.is_none() {
+            if let Some(ref bom_state) = self.bom_state {
+                state.set_project_bom(bom_state.components.lock().unwrap().clone());
+            }
+        }
+        
+        let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
+        match state.save_current_project() {
+            Ok(true) => {
+                let name = state.current_project.as_ref().map(|p| p.metadata.name.as_str()).unwrap_or_default();
+                logger.log_custom(project::constants::LOG_TYPE_AUTOSAVE, &format!("Auto-saved project {}", name));
+            }
+            Ok(false) => {}
+            Err(e) => logger.log_error(&format!("Auto-save failed: {}", e)),
+        }
+    }
+    
     fn save_settings(&self) {
         let mut config = self.project_manager.config.clone();
         config.state = self.project_manager.state.clone(); // Save current project state!
         config.user_timezone = self.user_timezone.clone();
         config.use_24_hour_clock = self.use_24_hour_clock;
-        config.global_units_mils = self.global_units_mils;
+        config.global_units_mils = self.display_unit.is_imperial();
+        config.display_unit = Some(self.display_unit);
+        config.png_export = self.png_export_settings;
+        config.drc_markers = self.drc_marker_settings;
+        config.autosave_interval_secs = self.autosave_interval_secs;
+        config.touch_gestures = self.touch_gestures_enabled;
+        config.minimap = self.minimap_settings;
+        config.dro = self.dro_settings;
+        config.view_animations = self.view_animations_enabled;
+        config.drc_ignores = self.drc_manager.ignored.clone();
+        config.key_bindings = self.key_bindings.clone();
+        config.post_gen_hook = self.post_gen_hook.clone();
+        config.lod = self.ecs_world.resource::<ecs::LayerRenderCache>().lod;
+        config.render_stats_overlay = self.ecs_world.resource::<ecs::LayerRenderCache>().show_overlay;
+        config.panel = *self.ecs_world.resource::<ecs::PanelSettings>();
+        config.use_parse_cache = self.use_parse_cache;
+        config.watch_gerber_files = self.watch_gerber_files;
+        config.language = self.language;
+        config.project_tag_filter = self.project_manager_state.as_ref()
+            .map(|state| state.tag_filter.clone())
+            .unwrap_or_else(|| self.project_tag_filter.clone());
+        config.cost_settings = self.cost_settings.clone();
+        config.drc_profile = self.drc_manager.current_ruleset.clone();
         
         if let Err(e) = config.save_to_file(&self.config_path) {
             eprintln!("Failed to save settings: {}", e);
@@ -520,14 +1157,18 @@ impl DemoLensApp {
         let gerber_tab = Tab::new(TabKind::GerberView, SurfaceIndex::main(), NodeIndex(4));
         let log_tab = Tab::new(TabKind::EventLog, SurfaceIndex::main(), NodeIndex(5));
         let bom_tab = Tab::new(TabKind::BOM, SurfaceIndex::main(), NodeIndex(6));
+        let analysis_tab = Tab::new(TabKind::Analysis, SurfaceIndex::main(), NodeIndex(7));
+        let points_tab = Tab::new(TabKind::Points, SurfaceIndex::main(), NodeIndex(8));
+        let view_3d_tab = Tab::new(TabKind::View3D, SurfaceIndex::main(), NodeIndex(9));
+        let annotations_tab = Tab::new(TabKind::Annotations, SurfaceIndex::main(), NodeIndex(10));
         
-        let mut dock_state = DockState::new(vec![gerber_tab]);
+        let mut dock_state = DockState::new(vec![gerber_tab, view_3d_tab]);
         let surface = dock_state.main_surface_mut();
         
         let [left, _right] = surface.split_left(
             NodeIndex::root(),
             0.3,
-            vec![view_settings_tab, drc_tab, project_tab, settings_tab, bom_tab],
+            vec![view_settings_tab, drc_tab, project_tab, settings_tab, bom_tab, analysis_tab, points_tab, annotations_tab],
         );
         
         surface.split_below(left, 0.7, vec![log_tab]);
@@ -568,6 +1209,24 @@ impl eframe::App for DemoLensApp {
             crate::ecs::update_coordinates_from_display(&mut self.ecs_world, &self.display_manager);
         }
         
+        // Run work queued for the UI thread now that the status bar shows it
+        ui::status_bar::run_deferred(self, ctx);
+        
+        // Merge results from a background DRC run once it completes
+        ui::drc_panel::poll_drc_job(self, ctx);
+        ui::batch_drc::poll_batch_drc_job(self, ctx);
+        ui::post_gen_hook::poll_post_gen_hook(self, ctx);
+        ui::recent_projects::track_recent_project(self);
+        ui::layer_watch::poll_layer_watch(self, ctx);
+        
+        // Step view transitions; a pending recenter jumps them to the end so it fits the final view
+        if self.needs_initial_view {
+            self.view_animation.finish();
+        }
+        if self.view_animation.advance(ctx.input(|i| i.stable_dt)) {
+            ctx.request_repaint();
+        }
+        
         // Process cross-probe signals from BOM component selection
         if let Some(ref mut cross_probe_slot) = self.cross_probe_slot {
             // Check if slot is not started yet
@@ -606,82 +1265,55 @@ impl eframe::App for DemoLensApp {
         
         // No longer need legacy sync - UI uses ECS directly
         
-        // Handle hotkeys first (but only if no text field has focus)
+        // Handle hotkeys first (but only if no text field has focus or a shortcut is being recorded)
         let text_input_active = ctx.memory(|mem| mem.focused().is_some());
+        let recording_shortcut = self.key_binding_editor.recording.is_some();
         
-        if !text_input_active {
+        let mut copy_view_requested = false;
+        let mut save_view_requested = false;
+        if !text_input_active && !recording_shortcut {
+            let bindings = self.key_bindings.clone();
             ctx.input(|i| {
-                // F key - flip board view (top/bottom)
-                if i.key_pressed(egui::Key::F) {
-                self.display_manager.showing_top = !self.display_manager.showing_top;
-                
-                // Auto-toggle layer visibility based on flip state using ECS
-                for layer_type in crate::ecs::LayerType::all() {
-                    let visible = match layer_type {
-                        crate::ecs::LayerType::Copper(1) |
-                        crate::ecs::LayerType::Silkscreen(crate::ecs::Side::Top) |
-                        crate::ecs::LayerType::Soldermask(crate::ecs::Side::Top) |
-                        crate::ecs::LayerType::Paste(crate::ecs::Side::Top) => {
-                            self.display_manager.showing_top
-                        },
-                        crate::ecs::LayerType::Copper(_) => {
-                            !self.display_manager.showing_top
-                        },
-                        crate::ecs::LayerType::Silkscreen(crate::ecs::Side::Bottom) |
-                        crate::ecs::LayerType::Soldermask(crate::ecs::Side::Bottom) |
-                        crate::ecs::LayerType::Paste(crate::ecs::Side::Bottom) => {
-                            !self.display_manager.showing_top
-                        },
-                        crate::ecs::LayerType::MechanicalOutline => {
-                            // Leave outline visibility unchanged, get current state from ECS
-                            crate::ecs::get_layer_visibility(&mut self.ecs_world, layer_type)
-                        }
-                    };
-                    crate::ecs::set_layer_visibility(&mut self.ecs_world, layer_type, visible);
-                }
+                // Flip board view (top/bottom), F by default
+                if bindings.pressed(ShortcutAction::FlipView, i) {
+                self.flip_board_view();
                 
                 let view_name = if self.display_manager.showing_top { "top" } else { "bottom" };
                 let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
-                logger.log_info(&format!("Flipped to {} view (F key)", view_name));
-                // Mark coordinates as dirty since view changed
-                crate::ecs::mark_coordinates_dirty_ecs(&mut self.ecs_world);
+                logger.log_info(&format!("Flipped to {} view ({})", view_name, bindings.get(ShortcutAction::FlipView)));
             }
             
-            // U key - toggle units (mm/mils)
-            if i.key_pressed(egui::Key::U) {
-                self.global_units_mils = !self.global_units_mils;
+            // Cycle units (mm → mils → in → µm), U by default
+            if bindings.pressed(ShortcutAction::ToggleUnits, i) {
+                self.display_unit = self.display_unit.next();
                 self.sync_units_to_ecs(); // Sync to ECS units system
-                let units_name = if self.global_units_mils { "mils" } else { "mm" };
+                let units_name = self.display_unit.suffix();
                 let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
-                logger.log_info(&format!("Toggled units to {} (U key)", units_name));
+                logger.log_info(&format!("Toggled units to {} ({})", units_name, bindings.get(ShortcutAction::ToggleUnits)));
             }
             
-            // R key - rotate board 90 degrees clockwise
-            if i.key_pressed(egui::Key::R) {
-                // Update rotation
-                self.rotation_degrees = (self.rotation_degrees + 90.0) % 360.0;
-                
-                // Don't reset view - just mark coordinates as dirty to update rotation
-                // This keeps the view centered on the current origin
-                crate::ecs::mark_coordinates_dirty_ecs(&mut self.ecs_world);
+            // Rotate board 90 degrees clockwise, R by default
+            if bindings.pressed(ShortcutAction::RotateView, i) {
+                // Update rotation; keeps the view centered on the current origin
+                self.animate_rotation_to(self.rotation_degrees + 90.0);
                 
                 let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
                 logger.log_custom(
                     project::constants::LOG_TYPE_ROTATION,
-                    &format!("Rotated board to {:.0}° (R key)", self.rotation_degrees)
+                    &format!("Rotated board to {:.1}° ({})", self.rotation_degrees, bindings.get(ShortcutAction::RotateView))
                 );
                 }
             
-            // A key - align view to grid
-            if i.key_pressed(egui::Key::A) {
+            // Align view to grid, A by default
+            if bindings.pressed(ShortcutAction::AlignToGrid, i) {
                 display::align_to_grid(&mut self.view_state, &self.grid_settings);
                 
                 let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
-                logger.log_info("Aligned view to grid (A key)");
+                logger.log_info(&format!("Aligned view to grid ({})", bindings.get(ShortcutAction::AlignToGrid)));
                 }
             
-            // M key - toggle ruler mode with latched measurement support
-            if i.key_pressed(egui::Key::M) {
+            // Toggle ruler mode with latched measurement support, M by default
+            if bindings.pressed(ShortcutAction::ToggleRuler, i) {
                 if self.ruler_active {
                     // Exiting measurement mode - latch the current measurement if complete
                     if self.ruler_start.is_some() && self.ruler_end.is_some() {
@@ -696,7 +1328,7 @@ impl eframe::App for DemoLensApp {
                     self.ruler_dragging = false;
                     
                     let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
-                    logger.log_info("Ruler mode deactivated (M key) - measurement latched");
+                    logger.log_info(&format!("Ruler mode deactivated ({}) - measurement latched", bindings.get(ShortcutAction::ToggleRuler)));
                 } else {
                     // Starting new measurement mode - clear previous latched measurement
                     self.latched_measurement_start = None;
@@ -705,10 +1337,18 @@ impl eframe::App for DemoLensApp {
                     self.ruler_active = true;
                     
                     let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
-                    logger.log_info("Ruler mode activated (M key) - previous measurement cleared");
+                    logger.log_info(&format!("Ruler mode activated ({}) - previous measurement cleared", bindings.get(ShortcutAction::ToggleRuler)));
                 }
                 }
             
+            // ESC key - clear net highlight (measurement mode takes priority)
+            if i.key_pressed(egui::Key::Escape) && !self.ruler_active && self.highlighted_net.is_some() {
+                self.highlighted_net = None;
+                
+                let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
+                logger.log_info("Net highlight cleared (ESC key)");
+            }
+            
             // ESC key - cancel measurement mode with latching support
             if i.key_pressed(egui::Key::Escape) && self.ruler_active {
                 // Latch the current measurement if complete
@@ -736,9 +1376,64 @@ impl eframe::App for DemoLensApp {
                 let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
                 logger.log_info("Ruler mode cancelled (ESC key) - measurement latched");
                 }
+            
+            // Alt+1..9 - toggle visibility of the Nth layer in the layer list
+            // (Alt keeps plain number keys free for view presets)
+            if i.modifiers.alt {
+                for (index, key) in LAYER_TOGGLE_KEYS.iter().enumerate() {
+                    if i.key_pressed(*key) {
+                        if let Some(layer_type) = crate::ecs::layer_for_toggle_key(&mut self.ecs_world, index + 1) {
+                            self.toggle_layer_visibility(layer_type, &format!("Alt+{}", index + 1));
+                        }
+                    }
+                }
+            }
+            
+            // Toggle visibility of the active layer, V by default
+            if bindings.pressed(ShortcutAction::ToggleActiveLayer, i) {
+                let shortcut = bindings.get(ShortcutAction::ToggleActiveLayer).to_string();
+                let active_layer = crate::ecs::get_active_layer(&self.ecs_world);
+                if crate::ecs::get_layer_by_type(&mut self.ecs_world, active_layer).is_some() {
+                    self.toggle_layer_visibility(active_layer, &shortcut);
+                } else {
+                    let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
+                    logger.log_warning(&format!("Active layer {} is not loaded ({})", active_layer.display_name(), shortcut));
+                }
+            }
+            
+            // Arrow keys - nudge the active layer (enabled in the Align Active Layer panel)
+            if self.layer_nudge.arrow_keys {
+                if let Some(delta) = ui::layer_nudge::nudge_from_keys(i, self.layer_nudge.step) {
+                    let active_layer = crate::ecs::get_active_layer(&self.ecs_world);
+                    ui::layer_nudge::apply_nudge_delta(self, active_layer, delta);
+                }
+            }
+            
+            // Copy the gerber view to the clipboard as an image, Ctrl+Shift+C by default
+            if bindings.pressed(ShortcutAction::CopyView, i) {
+                copy_view_requested = true;
+            }
+            
+            // Save the gerber view as a PNG file, Ctrl+Shift+P by default
+            if bindings.pressed(ShortcutAction::SaveViewPng, i) {
+                save_view_requested = true;
+            }
             });
         }
         
+        // Screenshot commands can't be sent while the input is locked
+        {
+            let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
+            if copy_view_requested {
+                ui::view_clipboard::request_view_image_copy(ctx, self, &logger);
+            }
+            if save_view_requested {
+                ui::view_clipboard::request_view_image_save(ctx, self, &logger);
+            }
+            ui::view_clipboard::poll_view_screenshot(ctx, self, &logger);
+        }
+        ui::parse_problems::show_parse_problems_window(ctx, self);
+        
         // Project Ribbon at the top
         egui::TopBottomPanel::top("project_ribbon").show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -747,11 +1442,11 @@ impl eframe::App for DemoLensApp {
                 // Project Ribbon with file selection
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label("📁 KiCad PCB File:");
+                        ui.label(tr("ribbon.pcb_file"));
                         
                         // Show current file or placeholder
                         let current_file_text = match &self.project_manager.state {
-                            ProjectState::NoProject => "No file selected".to_string(),
+                            ProjectState::NoProject => tr("ribbon.no_file").to_string(),
                             ProjectState::Ready { pcb_path, .. } |
                             ProjectState::PcbSelected { pcb_path } |
                             ProjectState::GeneratingGerbers { pcb_path } |
@@ -759,107 +1454,102 @@ impl eframe::App for DemoLensApp {
                             ProjectState::LoadingGerbers { pcb_path, .. } => {
                                 pcb_path.file_name()
                                     .map(|n| n.to_string_lossy().to_string())
-                                    .unwrap_or_else(|| "Unknown file".to_string())
+                                    .unwrap_or_else(|| tr("ribbon.unknown_file").to_string())
                             }
                         };
                         
                         ui.label(egui::RichText::new(current_file_text).strong());
                         
-                        if ui.button("Browse...").clicked() {
+                        if ui.button(tr("ribbon.browse")).clicked() {
                             self.project_manager.open_file_dialog();
                         }
                         
+                        let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
+                        ui::recent_projects::show_recent_menu(ui, self, &logger);
+                        
                         // Handle file dialog
                         if let Some(path_buf) = self.project_manager.update_file_dialog(ui.ctx()) {
                             self.project_manager.state = ProjectState::PcbSelected { pcb_path: path_buf.clone() };
-                            let logger = ReactiveEventLogger::with_colors(&self.logger_state, &self.log_colors);
                             logger.log_info(&format!("Selected PCB file: {}", path_buf.display()));
                         }
                     });
                 });
                 
+                ui::parse_problems::show_problems_indicator(ui, self);
+                
                 // Hotkeys menu
-                ui.menu_button("📋 Hotkeys", |ui| {
-                    ui.heading("Keyboard Shortcuts");
+                ui.menu_button(tr("hotkeys.menu"), |ui| {
+                    ui.heading(tr("hotkeys.keyboard"));
                     ui.separator();
                     
-                    ui.horizontal(|ui| {
-                        ui.label("F");
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Flip Top/Bottom view");
-                        });
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label("R");
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Rotate 90° clockwise");
-                        });
-                    });
-                    
-                    ui.horizontal(|ui| {
-                        ui.label("U");
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Toggle units (mm/mils)");
+                    for action in ShortcutAction::ALL {
+                        ui.horizontal(|ui| {
+                            ui.label(self.key_bindings.get(action).to_string());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(action.display_label());
+                            });
                         });
-                    });
+                    }
                     
                     ui.horizontal(|ui| {
-                        ui.label("A");
+                        ui.label("ESC");
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Align view to grid");
+                            ui.label(tr("hotkeys.cancel_measurement"));
                         });
                     });
+                    ui.label(egui::RichText::new(tr("hotkeys.remap")).small().color(egui::Color32::GRAY));
                     
-                    ui.horizontal(|ui| {
-                        ui.label("M");
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Toggle ruler/measurement mode");
-                        });
-                    });
+                    ui.separator();
+                    ui.heading(tr("hotkeys.layer_toggles"));
                     
-                    ui.horizontal(|ui| {
-                        ui.label("ESC");
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Cancel measurement mode");
+                    let layers = crate::ecs::get_loaded_layer_types(&mut self.ecs_world);
+                    if layers.is_empty() {
+                        ui.label(tr("hotkeys.layer_toggles_empty"));
+                    }
+                    for (index, layer_type) in layers.iter().take(LAYER_TOGGLE_KEYS.len()).enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("Alt+{}", index + 1));
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(tr_args("hotkeys.toggle_layer", &[("layer", &layer_type.display_name())]));
+                            });
                         });
-                    });
+                    }
                     
                     ui.separator();
-                    ui.heading("Mouse Controls");
+                    ui.heading(tr("hotkeys.mouse"));
                     
                     ui.horizontal(|ui| {
-                        ui.label("Double-click");
+                        ui.label(tr("hotkeys.double_click"));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Center view");
+                            ui.label(tr("hotkeys.center_view"));
                         });
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Right-click + drag");
+                        ui.label(tr("hotkeys.right_drag"));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Zoom to selection");
+                            ui.label(tr("hotkeys.zoom_selection"));
                         });
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Scroll wheel");
+                        ui.label(tr("hotkeys.scroll"));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Zoom in/out");
+                            ui.label(tr("hotkeys.zoom"));
                         });
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Left-click + drag");
+                        ui.label(tr("hotkeys.left_drag"));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Pan view");
+                            ui.label(tr("hotkeys.pan"));
                         });
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Escape");
+                        ui.label(tr("hotkeys.escape"));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.label("Cancel zoom selection / measurement mode");
+                            ui.label(tr("hotkeys.cancel_all"));
                         });
                     });
                 });
@@ -871,7 +1561,17 @@ impl eframe::App for DemoLensApp {
             });
         });
         
+        // Status bar at the bottom; panels go before the dock area, which fills the rest
+        ui::status_bar::show_status_bar(ctx, self);
+        
         // Main dock area below the ribbon
+        self.sync_project_dock_state();
+        ui::point_picker::sync_point_picker_project(self);
+        ui::annotations::sync_annotation_project(self);
+        ui::assembly_checks::sync_assembly_check_project(self);
+        ui::drc_panel::sync_drc_checks_project(self);
+        ui::layer_nudge::sync_layer_nudge_project(self);
+        ui::tabs::sync_quadrant_layout_project(self);
         let mut dock_state = self.dock_state.clone();
         let mut tab_viewer = TabViewer { app: self };
         let mut style = Style::from_egui(ctx.style().as_ref());
@@ -885,10 +1585,14 @@ impl eframe::App for DemoLensApp {
             .show(ctx, &mut tab_viewer);
             
         self.dock_state = dock_state;
+        if let Some(kind) = self.open_tab_request.take() {
+            self.open_tab(kind);
+        }
         
         // Show About modal if requested
         if self.show_about_modal {
-            egui::Window::new("About CopperForge")
+            egui::Window::new(tr("about.title"))
+                .id(egui::Id::new("about_window"))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
@@ -898,7 +1602,7 @@ impl eframe::App for DemoLensApp {
                     ui.add_space(20.0);
                     ui.horizontal(|ui| {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.button("Close").clicked() {
+                            if ui.button(tr("about.close")).clicked() {
                                 self.show_about_modal = false;
                             }
                         });
@@ -906,10 +1610,15 @@ impl eframe::App for DemoLensApp {
                 });
         }
         
-        // Save dock state to disk periodically
-        if ctx.input(|i| i.time) % 30.0 < 0.1 {
+        // Save dock state to disk periodically; frames are no longer continuous, so go by elapsed time
+        let now = ctx.input(|i| i.time);
+        if now - self.last_dock_save_time >= DOCK_SAVE_INTERVAL_SECS {
+            self.last_dock_save_time = now;
             self.save_dock_state();
         }
+        
+        self.autosave_project(ctx);
+        ctx.request_repaint_after(IDLE_REPAINT_INTERVAL);
     }
 }
 
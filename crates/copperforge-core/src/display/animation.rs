@@ -0,0 +1,115 @@
+//! Eased view transitions for board rotation and top/bottom flips
+//!
+//! The target state (rotation, showing_top) changes immediately; the animation
+//! only affects what is drawn while it runs, so tools and exports never see an
+//! in-between value.
+
+/// Length of a rotation transition in seconds
+pub const ROTATION_ANIMATION_SECS: f32 = 0.25;
+/// Length of the flip fade-in in seconds
+pub const FLIP_ANIMATION_SECS: f32 = 0.3;
+
+fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RotationTween {
+    from: f32,
+    /// Signed turn from `from`, the shortest way round
+    delta: f32,
+    elapsed: f32,
+}
+
+/// Running view transitions, stepped once per frame
+#[derive(Debug, Clone, Default)]
+pub struct ViewAnimation {
+    rotation: Option<RotationTween>,
+    flip_elapsed: Option<f32>,
+}
+
+impl ViewAnimation {
+    /// Animate from the currently drawn rotation to the new target
+    pub fn start_rotation(&mut self, from_degrees: f32, to_degrees: f32) {
+        let delta = (to_degrees - from_degrees + 180.0).rem_euclid(360.0) - 180.0;
+        self.rotation = (delta.abs() > f32::EPSILON).then_some(RotationTween { from: from_degrees, delta, elapsed: 0.0 });
+    }
+
+    pub fn start_flip(&mut self) {
+        self.flip_elapsed = Some(0.0);
+    }
+
+    /// Jump to the end state of every running transition
+    pub fn finish(&mut self) {
+        self.rotation = None;
+        self.flip_elapsed = None;
+    }
+
+    pub fn stop_rotation(&mut self) {
+        self.rotation = None;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.rotation.is_some() || self.flip_elapsed.is_some()
+    }
+
+    /// Step by the frame time; returns true while something is still animating
+    pub fn advance(&mut self, dt: f32) -> bool {
+        if let Some(tween) = &mut self.rotation {
+            tween.elapsed += dt;
+            if tween.elapsed >= ROTATION_ANIMATION_SECS {
+                self.rotation = None;
+            }
+        }
+        if let Some(elapsed) = &mut self.flip_elapsed {
+            *elapsed += dt;
+            if *elapsed >= FLIP_ANIMATION_SECS {
+                self.flip_elapsed = None;
+            }
+        }
+        self.is_active()
+    }
+
+    /// Rotation to draw this frame, in [0, 360)
+    pub fn rotation_degrees(&self, target_degrees: f32) -> f32 {
+        match self.rotation {
+            Some(tween) => {
+                let t = ease_in_out_cubic((tween.elapsed / ROTATION_ANIMATION_SECS).clamp(0.0, 1.0));
+                (tween.from + tween.delta * t).rem_euclid(360.0)
+            }
+            None => target_degrees,
+        }
+    }
+
+    /// Opacity (0..1) of the background veil drawn over the freshly flipped board
+    pub fn flip_veil(&self) -> Option<f32> {
+        self.flip_elapsed
+            .map(|elapsed| 1.0 - ease_in_out_cubic((elapsed / FLIP_ANIMATION_SECS).clamp(0.0, 1.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_takes_shortest_path_and_ends_on_target() {
+        let mut animation = ViewAnimation::default();
+        animation.start_rotation(270.0, 0.0);
+        animation.advance(ROTATION_ANIMATION_SECS / 2.0);
+        // Halfway from 270 to 360, not back through 180
+        assert!((animation.rotation_degrees(0.0) - 315.0).abs() < 1e-3);
+
+        assert!(!animation.advance(ROTATION_ANIMATION_SECS));
+        assert_eq!(animation.rotation_degrees(0.0), 0.0);
+
+        animation.start_flip();
+        assert_eq!(animation.flip_veil(), Some(1.0));
+        animation.finish();
+        assert!(!animation.is_active() && animation.flip_veil().is_none());
+    }
+}
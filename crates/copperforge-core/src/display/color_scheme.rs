@@ -0,0 +1,230 @@
+//! Layer color schemes applied in bulk to every loaded layer
+//!
+//! Schemes are shared as JSON: a name plus a list of layer/colour entries with
+//! colours written as unmultiplied `#RRGGBBAA` hex so they are easy to edit.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use bevy_ecs::world::World;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::{LayerType, Side};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ColorSchemeFile", into = "ColorSchemeFile")]
+pub struct ColorScheme {
+    pub name: String,
+    pub colors: HashMap<LayerType, Color32>,
+}
+
+/// JSON form of a scheme (map keys must be strings, so layers are listed)
+#[derive(Serialize, Deserialize)]
+struct ColorSchemeFile {
+    name: String,
+    layers: Vec<LayerColorEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LayerColorEntry {
+    layer: LayerType,
+    color: String,
+}
+
+impl TryFrom<ColorSchemeFile> for ColorScheme {
+    type Error = String;
+
+    fn try_from(file: ColorSchemeFile) -> Result<Self, Self::Error> {
+        let colors = file.layers
+            .into_iter()
+            .map(|entry| parse_hex_color(&entry.color).map(|color| (entry.layer, color)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(Self { name: file.name, colors })
+    }
+}
+
+impl From<ColorScheme> for ColorSchemeFile {
+    fn from(scheme: ColorScheme) -> Self {
+        // Keep the file in layer-list order so exports diff cleanly
+        let mut layers: Vec<LayerColorEntry> = LayerType::all()
+            .into_iter()
+            .filter_map(|layer| scheme.colors.get(&layer).map(|color| LayerColorEntry { layer, color: to_hex_color(*color) }))
+            .collect();
+        let mut extra: Vec<_> = scheme.colors
+            .iter()
+            .filter(|(layer, _)| !LayerType::all().contains(layer))
+            .map(|(layer, color)| LayerColorEntry { layer: *layer, color: to_hex_color(*color) })
+            .collect();
+        extra.sort_by_key(|entry| format!("{:?}", entry.layer));
+        layers.extend(extra);
+        Self { name: scheme.name, layers }
+    }
+}
+
+fn to_hex_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+}
+
+/// Parse `#RRGGBB` or `#RRGGBBAA`
+fn parse_hex_color(text: &str) -> Result<Color32, String> {
+    let hex = text.trim().trim_start_matches('#');
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid color '{}', expected #RRGGBB or #RRGGBBAA", text));
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(255);
+    let alpha = if hex.len() == 8 { channel(6) } else { 255 };
+    Ok(Color32::from_rgba_unmultiplied(channel(0), channel(2), channel(4), alpha))
+}
+
+impl ColorScheme {
+    fn from_rgba(name: &str, colors: [(LayerType, [u8; 4]); 9]) -> Self {
+        Self {
+            name: name.to_string(),
+            colors: colors
+                .into_iter()
+                .map(|(layer, [r, g, b, a])| (layer, Color32::from_rgba_unmultiplied(r, g, b, a)))
+                .collect(),
+        }
+    }
+
+    /// The built-in per-layer colors
+    pub fn copperforge() -> Self {
+        Self {
+            name: "CopperForge".to_string(),
+            colors: LayerType::all().into_iter().map(|layer| (layer, layer.color())).collect(),
+        }
+    }
+
+    pub fn kicad() -> Self {
+        Self::from_rgba("KiCad", [
+            (LayerType::Copper(1), [200, 52, 52, 220]),
+            (LayerType::Copper(2), [77, 127, 196, 220]),
+            (LayerType::Silkscreen(Side::Top), [242, 237, 161, 250]),
+            (LayerType::Silkscreen(Side::Bottom), [232, 178, 167, 250]),
+            (LayerType::Soldermask(Side::Top), [216, 100, 255, 110]),
+            (LayerType::Soldermask(Side::Bottom), [2, 255, 238, 110]),
+            (LayerType::Paste(Side::Top), [180, 160, 154, 200]),
+            (LayerType::Paste(Side::Bottom), [0, 194, 194, 200]),
+            (LayerType::MechanicalOutline, [208, 210, 205, 250]),
+        ])
+    }
+
+    pub fn altium() -> Self {
+        Self::from_rgba("Altium", [
+            (LayerType::Copper(1), [255, 0, 0, 220]),
+            (LayerType::Copper(2), [0, 0, 255, 220]),
+            (LayerType::Silkscreen(Side::Top), [255, 255, 0, 250]),
+            (LayerType::Silkscreen(Side::Bottom), [128, 128, 0, 250]),
+            (LayerType::Soldermask(Side::Top), [128, 0, 128, 140]),
+            (LayerType::Soldermask(Side::Bottom), [255, 0, 255, 140]),
+            (LayerType::Paste(Side::Top), [128, 128, 128, 200]),
+            (LayerType::Paste(Side::Bottom), [128, 0, 0, 200]),
+            (LayerType::MechanicalOutline, [255, 0, 255, 250]),
+        ])
+    }
+
+    pub fn high_contrast() -> Self {
+        Self::from_rgba("High contrast", [
+            (LayerType::Copper(1), [255, 170, 0, 255]),
+            (LayerType::Copper(2), [0, 220, 255, 255]),
+            (LayerType::Silkscreen(Side::Top), [255, 255, 255, 255]),
+            (LayerType::Silkscreen(Side::Bottom), [255, 255, 255, 255]),
+            (LayerType::Soldermask(Side::Top), [0, 255, 0, 90]),
+            (LayerType::Soldermask(Side::Bottom), [0, 120, 255, 90]),
+            (LayerType::Paste(Side::Top), [200, 200, 200, 255]),
+            (LayerType::Paste(Side::Bottom), [160, 160, 160, 255]),
+            (LayerType::MechanicalOutline, [255, 255, 0, 255]),
+        ])
+    }
+
+    /// Okabe-Ito palette, distinguishable with the common color vision deficiencies
+    pub fn color_blind_safe() -> Self {
+        Self::from_rgba("Color-blind safe", [
+            (LayerType::Copper(1), [230, 159, 0, 220]),
+            (LayerType::Copper(2), [86, 180, 233, 220]),
+            (LayerType::Silkscreen(Side::Top), [240, 228, 66, 250]),
+            (LayerType::Silkscreen(Side::Bottom), [204, 121, 167, 250]),
+            (LayerType::Soldermask(Side::Top), [0, 158, 115, 140]),
+            (LayerType::Soldermask(Side::Bottom), [0, 114, 178, 140]),
+            (LayerType::Paste(Side::Top), [200, 200, 200, 200]),
+            (LayerType::Paste(Side::Bottom), [150, 150, 150, 200]),
+            (LayerType::MechanicalOutline, [213, 94, 0, 250]),
+        ])
+    }
+
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self::copperforge(),
+            Self::kicad(),
+            Self::altium(),
+            Self::high_contrast(),
+            Self::color_blind_safe(),
+        ]
+    }
+
+    /// Set the render color of every loaded layer the scheme covers
+    /// Returns the number of layers updated
+    pub fn apply(&self, world: &mut World) -> usize {
+        self.colors
+            .iter()
+            .filter(|(layer, color)| crate::ecs::update_layer_render_properties(world, **layer, **color))
+            .count()
+    }
+
+    /// Capture the current colors of the loaded layers as a scheme
+    pub fn from_world(name: &str, world: &mut World) -> Self {
+        let colors = crate::ecs::get_loaded_layer_types(world)
+            .into_iter()
+            .filter_map(|layer| {
+                crate::ecs::get_layer_render_properties(world, layer).map(|props| (layer, props.color))
+            })
+            .collect();
+        Self { name: name.to_string(), colors }
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize color scheme: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid color scheme: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_json_round_trip() {
+        // Opaque colors survive the unmultiplied hex form exactly
+        let scheme = ColorScheme {
+            name: "Shared".to_string(),
+            colors: HashMap::from([
+                (LayerType::Copper(1), Color32::from_rgb(230, 159, 0)),
+                (LayerType::Copper(3), Color32::from_rgb(0, 114, 178)),
+                (LayerType::MechanicalOutline, Color32::from_rgb(213, 94, 0)),
+            ]),
+        };
+        let json = serde_json::to_string(&scheme).expect("scheme serializes");
+        let loaded: ColorScheme = serde_json::from_str(&json).expect("scheme deserializes");
+
+        assert_eq!(loaded, scheme);
+        assert!(json.contains("\"#E69F00FF\""));
+    }
+
+    #[test]
+    fn test_invalid_color_is_rejected() {
+        let json = r##"{"name":"Bad","layers":[{"layer":"MechanicalOutline","color":"#12345"}]}"##;
+        let error = serde_json::from_str::<ColorScheme>(json).unwrap_err().to_string();
+        assert!(error.contains("#12345"));
+
+        let json = r##"{"name":"Short","layers":[{"layer":{"Copper":1},"color":"#FF8000"}]}"##;
+        let scheme: ColorScheme = serde_json::from_str(json).expect("6-digit colors are opaque");
+        assert_eq!(scheme.colors[&LayerType::Copper(1)], Color32::from_rgb(255, 128, 0));
+    }
+}
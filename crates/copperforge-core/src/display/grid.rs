@@ -1,13 +1,47 @@
-use eframe::emath::Rect;
-use eframe::epaint::Color32;
+use egui::emath::Rect;
+use egui::Color32;
 use gerber_viewer::ViewState;
 use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
 
+/// How the grid is drawn (and what snap-to-grid snaps to)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GridStyle {
+    /// Dot at every intersection
+    #[default]
+    Dots,
+    /// Fine lines with a coarser major grid on top
+    Lines,
+    /// Concentric rings and radial lines around the origin
+    Polar,
+}
+
+impl GridStyle {
+    pub const ALL: [GridStyle; 3] = [Self::Dots, Self::Lines, Self::Polar];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dots => "Dots",
+            Self::Lines => "Major/minor lines",
+            Self::Polar => "Polar",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GridSettings {
     pub enabled: bool,
     pub spacing_mm: f32,  // Always store in mm internally
     pub dot_size: f32,
     pub snap_enabled: bool,  // Enterprise feature: snap to grid
+    /// Snap ruler and origin picks to nearby endpoints and pad/hole centers
+    pub object_snap_enabled: bool,
+    pub style: GridStyle,
+    /// Every n-th line (or ring) is drawn as a major one
+    pub major_every: u32,
+    /// Angle between radial lines of the polar grid
+    pub polar_angle_deg: f32,
 }
 
 impl Default for GridSettings {
@@ -17,21 +51,59 @@ impl Default for GridSettings {
             spacing_mm: 2.54,  // 100 mils = 2.54 mm
             dot_size: 1.0,
             snap_enabled: false,  // Default off for existing users
+            object_snap_enabled: true,
+            style: GridStyle::Dots,
+            major_every: 5,
+            polar_angle_deg: 15.0,
         }
     }
 }
 
+const MINOR_LINE_COLOR: Color32 = Color32::from_rgba_premultiplied(45, 45, 45, 45);
+const MAJOR_LINE_COLOR: Color32 = Color32::from_rgba_premultiplied(80, 80, 80, 80);
+/// Lines or rings closer than this on screen are not drawn
+const MIN_LINE_SPACING_PX: f64 = 5.0;
+/// Upper bound on lines or rings per frame
+const MAX_LINES: i64 = 2000;
+
 /// Draw grid on the viewport
+/// `origin` (view coordinates) is the center of the polar grid
 pub fn draw_grid(
     painter: &egui::Painter,
     viewport: &Rect,
     view_state: &ViewState,
     settings: &GridSettings,
+    origin: Point2<f64>,
 ) {
     if !settings.enabled {
         return;
     }
     
+    match settings.style {
+        GridStyle::Dots => draw_dot_grid(painter, viewport, view_state, settings),
+        GridStyle::Lines => draw_line_grid(painter, viewport, view_state, settings),
+        GridStyle::Polar => draw_polar_grid(painter, viewport, view_state, settings, origin),
+    }
+}
+
+/// Visible area in view coordinates as (min, max)
+fn visible_bounds(viewport: &Rect, view_state: &ViewState) -> (Point2<f64>, Point2<f64>) {
+    let top_left = view_state.screen_to_gerber_coords(viewport.min);
+    let bottom_right = view_state.screen_to_gerber_coords(viewport.max);
+    
+    // Due to Y inversion, we need to get proper min/max
+    (
+        Point2::new(top_left.x.min(bottom_right.x), top_left.y.min(bottom_right.y)),
+        Point2::new(top_left.x.max(bottom_right.x), top_left.y.max(bottom_right.y)),
+    )
+}
+
+fn draw_dot_grid(
+    painter: &egui::Painter,
+    viewport: &Rect,
+    view_state: &ViewState,
+    settings: &GridSettings,
+) {
     // Grid spacing is stored in mm
     let grid_spacing_gerber = settings.spacing_mm as f64;
     
@@ -39,7 +111,7 @@ pub fn draw_grid(
     let grid_spacing_screen = grid_spacing_gerber * view_state.scale as f64;
     
     // Skip if grid spacing is too small to be visible (less than 5 pixels)
-    if grid_spacing_screen < 5.0 {
+    if grid_spacing_screen < MIN_LINE_SPACING_PX {
         return;
     }
     
@@ -48,21 +120,13 @@ pub fn draw_grid(
         return;
     }
     
-    // Convert viewport bounds to gerber coordinates
-    let top_left = view_state.screen_to_gerber_coords(viewport.min);
-    let bottom_right = view_state.screen_to_gerber_coords(viewport.max);
-    
-    // Due to Y inversion, we need to get proper min/max
-    let min_x = top_left.x.min(bottom_right.x);
-    let max_x = top_left.x.max(bottom_right.x);
-    let min_y = top_left.y.min(bottom_right.y);
-    let max_y = top_left.y.max(bottom_right.y);
+    let (min, max) = visible_bounds(viewport, view_state);
     
     // Calculate grid start/end indices
-    let start_x = (min_x / grid_spacing_gerber).floor() as i32 - 1;
-    let end_x = (max_x / grid_spacing_gerber).ceil() as i32 + 1;
-    let start_y = (min_y / grid_spacing_gerber).floor() as i32 - 1;
-    let end_y = (max_y / grid_spacing_gerber).ceil() as i32 + 1;
+    let start_x = (min.x / grid_spacing_gerber).floor() as i32 - 1;
+    let end_x = (max.x / grid_spacing_gerber).ceil() as i32 + 1;
+    let start_y = (min.y / grid_spacing_gerber).floor() as i32 - 1;
+    let end_y = (max.y / grid_spacing_gerber).ceil() as i32 + 1;
     
     // Limit the number of grid points to prevent performance issues
     let max_points = 10000;
@@ -91,6 +155,96 @@ pub fn draw_grid(
     }
 }
 
+/// Minor lines at the grid spacing and major lines every `major_every` of them
+/// The minor lines drop out when zoomed out, leaving the major grid
+fn draw_line_grid(
+    painter: &egui::Painter,
+    viewport: &Rect,
+    view_state: &ViewState,
+    settings: &GridSettings,
+) {
+    let spacing = settings.spacing_mm as f64;
+    let major_every = settings.major_every.max(1) as i64;
+    let (min, max) = visible_bounds(viewport, view_state);
+    let scale = view_state.scale as f64;
+    
+    // Major lines first so minor ones never hide them
+    for (step, color) in [(spacing * major_every as f64, MAJOR_LINE_COLOR), (spacing, MINOR_LINE_COLOR)] {
+        if step * scale < MIN_LINE_SPACING_PX {
+            continue;
+        }
+        let (start_x, end_x) = ((min.x / step).floor() as i64, (max.x / step).ceil() as i64);
+        let (start_y, end_y) = ((min.y / step).floor() as i64, (max.y / step).ceil() as i64);
+        if (end_x - start_x) + (end_y - start_y) > MAX_LINES {
+            continue;
+        }
+        let is_minor = step == spacing && major_every > 1;
+        let stroke = egui::Stroke::new(1.0, color);
+        for index in start_x..=end_x {
+            if is_minor && index % major_every == 0 {
+                continue;
+            }
+            let x = view_state.gerber_to_screen_coords(Point2::new(index as f64 * step, 0.0)).x;
+            painter.vline(x, viewport.y_range(), stroke);
+        }
+        for index in start_y..=end_y {
+            if is_minor && index % major_every == 0 {
+                continue;
+            }
+            let y = view_state.gerber_to_screen_coords(Point2::new(0.0, index as f64 * step)).y;
+            painter.hline(viewport.x_range(), y, stroke);
+        }
+    }
+}
+
+/// Rings every grid spacing around `origin` and radial lines every `polar_angle_deg`
+fn draw_polar_grid(
+    painter: &egui::Painter,
+    viewport: &Rect,
+    view_state: &ViewState,
+    settings: &GridSettings,
+    origin: Point2<f64>,
+) {
+    let spacing = settings.spacing_mm as f64;
+    let scale = view_state.scale as f64;
+    if spacing * scale < MIN_LINE_SPACING_PX {
+        return;
+    }
+    
+    // Radius range of the visible area around the origin
+    let (min, max) = visible_bounds(viewport, view_state);
+    let nearest = Point2::new(origin.x.clamp(min.x, max.x), origin.y.clamp(min.y, max.y));
+    let min_radius = (nearest - origin).norm();
+    let max_radius = [min, max, Point2::new(min.x, max.y), Point2::new(max.x, min.y)]
+        .iter()
+        .map(|corner| (corner - origin).norm())
+        .fold(0.0, f64::max);
+    
+    let first_ring = ((min_radius / spacing).floor() as i64).max(1);
+    let last_ring = (max_radius / spacing).ceil() as i64;
+    if last_ring - first_ring > MAX_LINES {
+        return;
+    }
+    
+    let center = view_state.gerber_to_screen_coords(origin);
+    let major_every = settings.major_every.max(1) as i64;
+    for ring in first_ring..=last_ring {
+        let color = if ring % major_every == 0 { MAJOR_LINE_COLOR } else { MINOR_LINE_COLOR };
+        painter.circle_stroke(center, (ring as f64 * spacing * scale) as f32, egui::Stroke::new(1.0, color));
+    }
+    
+    let angle_step = (settings.polar_angle_deg as f64).clamp(1.0, 180.0);
+    let spokes = (360.0 / angle_step).round() as i64;
+    let length = (max_radius * scale) as f32;
+    for spoke in 0..spokes {
+        let angle = (spoke as f64 * angle_step).to_radians();
+        // Screen y points down
+        let direction = egui::Vec2::new(angle.cos() as f32, -angle.sin() as f32);
+        let color = if spoke % (spokes / 4).max(1) == 0 { MAJOR_LINE_COLOR } else { MINOR_LINE_COLOR };
+        painter.line_segment([center, center + direction * length], egui::Stroke::new(1.0, color));
+    }
+}
+
 /// Get grid visibility status message
 pub fn get_grid_status(view_state: &ViewState, grid_spacing_mm: f32) -> GridStatus {
     let grid_spacing_gerber = grid_spacing_mm as f64;
@@ -113,13 +267,22 @@ pub enum GridStatus {
 
 /// Enterprise feature: Snap a point to the nearest grid intersection
 /// Returns the snapped position in gerber coordinates
-pub fn snap_to_grid(point: Point2<f64>, grid_settings: &GridSettings) -> Point2<f64> {
+/// The polar grid snaps to the nearest ring/radial-line crossing around `origin`
+pub fn snap_to_grid(point: Point2<f64>, grid_settings: &GridSettings, origin: Point2<f64>) -> Point2<f64> {
     if !grid_settings.snap_enabled {
         return point;
     }
     
     let grid_spacing = grid_settings.spacing_mm as f64;
     
+    if grid_settings.style == GridStyle::Polar {
+        let offset = point - origin;
+        let radius = (offset.norm() / grid_spacing).round() * grid_spacing;
+        let angle_step = (grid_settings.polar_angle_deg as f64).clamp(1.0, 180.0).to_radians();
+        let angle = (offset.y.atan2(offset.x) / angle_step).round() * angle_step;
+        return Point2::new(origin.x + radius * angle.cos(), origin.y + radius * angle.sin());
+    }
+    
     // Snap X coordinate
     let snapped_x = (point.x / grid_spacing).round() * grid_spacing;
     
@@ -169,3 +332,27 @@ pub fn align_to_grid(view_state: &mut gerber_viewer::ViewState, grid_settings: &
     view_state.translation.y += snap_y;
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polar_snap_lands_on_ring_and_spoke() {
+        let settings = GridSettings {
+            snap_enabled: true,
+            style: GridStyle::Polar,
+            spacing_mm: 1.0,
+            polar_angle_deg: 45.0,
+            ..GridSettings::default()
+        };
+        let origin = Point2::new(10.0, 10.0);
+        let snapped = snap_to_grid(Point2::new(12.2, 11.9), &settings, origin);
+        let expected = origin + nalgebra::Vector2::new(1.0, 1.0).normalize() * 3.0;
+        assert!((snapped - expected).norm() < 1e-9);
+
+        // Rectangular styles ignore the origin
+        let square = GridSettings { style: GridStyle::Lines, ..settings };
+        assert_eq!(snap_to_grid(Point2::new(12.2, 11.9), &square, origin), Point2::new(12.0, 12.0));
+    }
+}
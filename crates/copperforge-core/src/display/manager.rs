@@ -27,7 +27,7 @@ impl From<gerber_viewer::Mirroring> for MirroringSettings {
 }
 
 /// Serializable vector for offsets
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VectorOffset {
     pub x: f64,
     pub y: f64,
@@ -48,6 +48,255 @@ impl From<nalgebra::Vector2<f64>> for VectorOffset {
     }
 }
 
+/// Settings for the two-layer swipe comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwipeCompareSettings {
+    pub enabled: bool,
+    /// Layer shown left of the divider
+    pub layer_a: crate::ecs::LayerType,
+    /// Layer shown right of the divider
+    pub layer_b: crate::ecs::LayerType,
+    /// Divider position as a fraction of the viewport width (0.0 - 1.0)
+    pub divider_fraction: f32,
+}
+
+impl Default for SwipeCompareSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            layer_a: crate::ecs::LayerType::TOP_COPPER,
+            layer_b: crate::ecs::LayerType::BOTTOM_COPPER,
+            divider_fraction: 0.5,
+        }
+    }
+}
+
+impl SwipeCompareSettings {
+    /// Screen x of the divider within `viewport`
+    pub fn divider_x(&self, viewport: &egui::Rect) -> f32 {
+        viewport.left() + viewport.width() * self.divider_fraction.clamp(0.0, 1.0)
+    }
+    
+    /// Move the divider to screen x, kept inside `viewport`
+    pub fn set_divider_x(&mut self, x: f32, viewport: &egui::Rect) {
+        if viewport.width() > 0.0 {
+            self.divider_fraction = ((x - viewport.left()) / viewport.width()).clamp(0.0, 1.0);
+        }
+    }
+}
+
+/// How drill holes are shown in the flat layer view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DrillHoleStyle {
+    /// Holes punch through copper and soldermask, as on the fabricated board
+    #[default]
+    Knockout,
+    /// Hole outlines over intact layers, to inspect pad-to-hole registration
+    Outline,
+}
+
+/// Cell of the 2×2 quadrant view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quadrant {
+    UpperLeft,
+    UpperRight,
+    LowerLeft,
+    LowerRight,
+}
+
+impl Quadrant {
+    pub const ALL: [Quadrant; 4] = [Quadrant::UpperLeft, Quadrant::UpperRight, Quadrant::LowerLeft, Quadrant::LowerRight];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Quadrant::UpperLeft => "Upper left",
+            Quadrant::UpperRight => "Upper right",
+            Quadrant::LowerLeft => "Lower left",
+            Quadrant::LowerRight => "Lower right",
+        }
+    }
+
+    /// Prefix of exported PNG file names
+    pub fn file_prefix(&self) -> &'static str {
+        match self {
+            Quadrant::UpperLeft => "upper_left",
+            Quadrant::UpperRight => "upper_right",
+            Quadrant::LowerLeft => "lower_left",
+            Quadrant::LowerRight => "lower_right",
+        }
+    }
+
+    /// Cell of the quadrant in a 2×2 arrangement
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|quadrant| quadrant == self).unwrap_or(0)
+    }
+}
+
+/// How the cells of the quadrant layout are placed
+/// Cells are filled row by row from the upper left, which stays at the origin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QuadrantArrangement {
+    #[default]
+    Grid2x2,
+    /// Four cells side by side
+    Row,
+    /// Four cells stacked top to bottom
+    Column,
+    Grid { columns: usize, rows: usize },
+}
+
+impl QuadrantArrangement {
+    /// Largest custom grid in either direction
+    pub const MAX_GRID: usize = 4;
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuadrantArrangement::Grid2x2 => "2×2",
+            QuadrantArrangement::Row => "Single row",
+            QuadrantArrangement::Column => "Single column",
+            QuadrantArrangement::Grid { .. } => "Custom grid",
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        match self {
+            QuadrantArrangement::Grid2x2 => 2,
+            QuadrantArrangement::Row => 4,
+            QuadrantArrangement::Column => 1,
+            QuadrantArrangement::Grid { columns, .. } => (*columns).clamp(1, Self::MAX_GRID),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        match self {
+            QuadrantArrangement::Grid2x2 => 2,
+            QuadrantArrangement::Row => 1,
+            QuadrantArrangement::Column => 4,
+            QuadrantArrangement::Grid { rows, .. } => (*rows).clamp(1, Self::MAX_GRID),
+        }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.columns() * self.rows()
+    }
+
+    /// Offset of a cell in multiples of the quadrant spacing
+    fn cell_offset(&self, index: usize) -> (f64, f64) {
+        let columns = self.columns();
+        ((index % columns) as f64, -((index / columns) as f64))
+    }
+}
+
+/// Which layer each cell of the quadrant view shows
+/// With `custom` off the built-in layout is used: copper, silkscreen and
+/// soldermask fill the first three cells of the arrangement, paste hidden
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuadrantLayout {
+    pub custom: bool,
+    pub arrangement: QuadrantArrangement,
+    /// Layer per cell, row by row; `Quadrant::ALL` order for the 2×2 arrangement
+    pub assignments: Vec<Option<crate::ecs::LayerType>>,
+}
+
+impl Default for QuadrantLayout {
+    fn default() -> Self {
+        use crate::ecs::{LayerType, Side};
+        Self {
+            custom: false,
+            arrangement: QuadrantArrangement::Grid2x2,
+            assignments: vec![
+                Some(LayerType::TOP_COPPER),
+                Some(LayerType::BOTTOM_COPPER),
+                Some(LayerType::Soldermask(Side::Top)),
+                Some(LayerType::MechanicalOutline),
+            ],
+        }
+    }
+}
+
+impl QuadrantLayout {
+    pub fn layer(&self, quadrant: Quadrant) -> Option<crate::ecs::LayerType> {
+        self.cell_layer(quadrant.index())
+    }
+
+    pub fn cell_layer(&self, index: usize) -> Option<crate::ecs::LayerType> {
+        self.assignments.get(index).copied().flatten()
+    }
+
+    /// Quadrant a layer is assigned to
+    pub fn quadrant_of(&self, layer_type: crate::ecs::LayerType) -> Option<Quadrant> {
+        Quadrant::ALL.into_iter().find(|quadrant| self.layer(*quadrant) == Some(layer_type))
+    }
+
+    /// Cell a layer is assigned to
+    pub fn cell_of(&self, layer_type: crate::ecs::LayerType) -> Option<usize> {
+        (0..self.arrangement.cell_count()).find(|index| self.cell_layer(*index) == Some(layer_type))
+    }
+
+    /// Cell a layer is drawn in: its assigned cell with `custom` on, otherwise its category's cell
+    /// The built-in layout has no cell for the outline and paste, or for categories past the last cell
+    pub fn layer_cell(&self, layer_type: crate::ecs::LayerType) -> Option<usize> {
+        use crate::ecs::LayerType;
+        if self.custom {
+            return self.cell_of(layer_type);
+        }
+        let index = match layer_type {
+            LayerType::Copper(_) => 0,
+            LayerType::Silkscreen(_) => 1,
+            LayerType::Soldermask(_) => 2,
+            LayerType::Paste(_) | LayerType::MechanicalOutline => return None,
+        };
+        (index < self.arrangement.cell_count()).then_some(index)
+    }
+
+    /// Offset of a layer's cell in multiples of the quadrant spacing; layers without a cell stay at the origin
+    pub fn cell_offset_of(&self, layer_type: crate::ecs::LayerType) -> (f64, f64) {
+        self.layer_cell(layer_type).map_or((0.0, 0.0), |index| self.arrangement.cell_offset(index))
+    }
+
+    /// Hover name of a cell
+    pub fn cell_name(&self, index: usize) -> String {
+        match self.arrangement {
+            QuadrantArrangement::Grid2x2 => Quadrant::ALL[index].name().to_string(),
+            arrangement => format!("Row {}, column {}", index / arrangement.columns() + 1, index % arrangement.columns() + 1),
+        }
+    }
+
+    /// Prefix of exported PNG file names for a cell
+    pub fn file_prefix(&self, index: usize) -> String {
+        match self.arrangement {
+            QuadrantArrangement::Grid2x2 => Quadrant::ALL[index].file_prefix().to_string(),
+            arrangement => format!("r{}_c{}", index / arrangement.columns() + 1, index % arrangement.columns() + 1),
+        }
+    }
+
+    /// Change the arrangement; cells keep their layers in order and layers past the last cell are dropped
+    pub fn set_arrangement(&mut self, arrangement: QuadrantArrangement) {
+        self.arrangement = arrangement;
+        self.assignments.resize(arrangement.cell_count(), None);
+    }
+
+    /// Put a layer in a quadrant, taking it out of any other one (a layer has one position)
+    pub fn assign(&mut self, quadrant: Quadrant, layer_type: Option<crate::ecs::LayerType>) {
+        self.assign_cell(quadrant.index(), layer_type);
+    }
+
+    /// Put a layer in a cell, taking it out of any other one
+    pub fn assign_cell(&mut self, index: usize, layer_type: Option<crate::ecs::LayerType>) {
+        if let Some(layer_type) = layer_type {
+            for slot in self.assignments.iter_mut().filter(|slot| **slot == Some(layer_type)) {
+                *slot = None;
+            }
+        }
+        if self.assignments.len() <= index {
+            self.assignments.resize(index + 1, None);
+        }
+        self.assignments[index] = layer_type;
+    }
+}
+
 /// Manager for all display-related properties and settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisplayManager {
@@ -63,6 +312,10 @@ pub struct DisplayManager {
     /// Center offset for the display
     pub center_offset: VectorOffset,
     
+    /// Point the view rotates and mirrors about: the center of the loaded layers, updated every frame
+    #[serde(skip)]
+    pub rotation_center: VectorOffset,
+    
     /// Design offset for positioning (calculated from mechanical centroid + user delta)
     pub design_offset: VectorOffset,
     
@@ -77,6 +330,33 @@ pub struct DisplayManager {
     
     /// Offset magnitude for quadrant view (in mm)
     pub quadrant_offset_magnitude: f64,
+    
+    /// Layer shown in each quadrant of the quadrant view (stored with the project)
+    #[serde(default)]
+    pub quadrant_layout: QuadrantLayout,
+    
+    /// Project the quadrant layout was loaded from / is saved to
+    #[serde(skip)]
+    pub quadrant_layout_project: Option<String>,
+    
+    /// Outline flashed pads/vias on copper layers
+    #[serde(default)]
+    pub highlight_flashes: bool,
+    
+    /// Draw loaded drill holes, plated and non-plated styled apart
+    #[serde(default)]
+    pub show_drill_holes: bool,
+    
+    #[serde(default)]
+    pub drill_hole_style: DrillHoleStyle,
+    
+    /// A/B swipe comparison between two layers
+    #[serde(default)]
+    pub swipe_compare: SwipeCompareSettings,
+    
+    /// Composite "as fabricated" view of the shown side
+    #[serde(default)]
+    pub realistic: super::RealisticRenderSettings,
 }
 
 impl DisplayManager {
@@ -87,14 +367,32 @@ impl DisplayManager {
             enable_polygon_numbering: false, // Matches ENABLE_POLYGON_NUMBERING constant
             mirroring: MirroringSettings { x: false, y: false },
             center_offset: VectorOffset { x: 0.0, y: 0.0 },
+            rotation_center: VectorOffset { x: 0.0, y: 0.0 },
             design_offset: VectorOffset { x: 0.0, y: 0.0 },
             user_delta_offset: VectorOffset { x: 0.0, y: 0.0 },
             showing_top: true,
             quadrant_view_enabled: false,
             quadrant_offset_magnitude: 141.42, // Default ~100mil in x and y (sqrt(100^2 + 100^2) * 0.0254)
+            quadrant_layout: QuadrantLayout::default(),
+            quadrant_layout_project: None,
+            highlight_flashes: false,
+            show_drill_holes: true,
+            drill_hole_style: DrillHoleStyle::Knockout,
+            swipe_compare: SwipeCompareSettings::default(),
+            realistic: super::RealisticRenderSettings::default(),
         }
     }
     
+    /// Drill holes to knock out of copper and soldermask, if the flat view shows them so
+    pub fn knocks_out_drill_holes(&self) -> bool {
+        self.show_drill_holes && self.drill_hole_style == DrillHoleStyle::Knockout
+    }
+    
+    /// Realistic view is on and not overridden; quadrant view always draws layers flat
+    pub fn realistic_active(&self) -> bool {
+        self.realistic.enabled && !self.quadrant_view_enabled
+    }
+    
     /// Toggle between top and bottom view
     pub fn flip_view(&mut self) {
         self.showing_top = !self.showing_top;
@@ -107,6 +405,10 @@ impl DisplayManager {
         self.user_delta_offset = VectorOffset { x: 0.0, y: 0.0 };
     }
     
+    /// User-set origin in view coordinates ((0, 0) when none is set)
+    pub fn design_origin(&self) -> nalgebra::Point2<f64> {
+        nalgebra::Point2::new(self.design_offset.x, self.design_offset.y)
+    }
     
     /// Toggle X-axis mirroring
     pub fn toggle_x_mirror(&mut self) {
@@ -148,8 +450,7 @@ impl DisplayManager {
     }
     
     /// Get the quadrant offset for a specific layer type
-    /// Returns (x_offset, y_offset) in mm
-    /// Now implements linear horizontal layout instead of quadrant view
+    /// Returns (x_offset, y_offset) in mm of the layer's cell in the quadrant layout
     pub fn get_quadrant_offset(&self, layer_type: &crate::ecs::LayerType) -> VectorOffset {
         // Use the quadrant_offset_magnitude directly as the spacing value
         let spacing = self.quadrant_offset_magnitude.max(1.0); // Minimum 1mm spacing
@@ -157,43 +458,26 @@ impl DisplayManager {
     }
     
     /// Get the quadrant offset with explicit spacing
-    /// Returns (x_offset, y_offset) in mm
-    /// Now implements linear horizontal layout instead of quadrant view
+    /// Returns (x_offset, y_offset) in mm of the layer's cell in the quadrant layout
     pub fn get_quadrant_offset_with_spacing(&self, layer_type: &crate::ecs::LayerType, spacing: f64) -> VectorOffset {
         if !self.quadrant_view_enabled {
             return VectorOffset { x: 0.0, y: 0.0 };
         }
         
-        // Linear horizontal layout using simple spacing:
-        // - Copper at origin (0,0) 
-        // - Silkscreen at spacing
-        // - Soldermask at spacing * 2
-        // - Paste layers hidden (not shown)
-        
-        use crate::ecs::LayerType;
-        
-        let x_offset = match layer_type {
-            // Copper layers - Stay at origin (0,0)
-            LayerType::Copper(_) => 0.0,
-            
-            // Silkscreen layers - at spacing
-            LayerType::Silkscreen(_) => spacing,
-            
-            // Soldermask layers - at spacing * 2
-            LayerType::Soldermask(_) => spacing * 2.0,
-            
-            // Paste layers - hidden (positioned far off-screen)
-            LayerType::Paste(_) => -9999.0,
-            
-            // Mechanical outline should not be displayed in quadrant view
-            // (it will be rendered separately with each layer)
-            LayerType::MechanicalOutline => 0.0,
-        };
-        
-        VectorOffset {
-            x: x_offset,
-            y: 0.0, // All layers at the same Y level (horizontal layout)
+        // Paste stays hidden far off-screen in the built-in layout
+        if !self.quadrant_layout.custom && matches!(layer_type, crate::ecs::LayerType::Paste(_)) {
+            return VectorOffset { x: -9999.0, y: 0.0 };
         }
+        
+        // Layers without a cell are not drawn (the built-in outline is drawn with every layer at the origin)
+        let (x, y) = self.quadrant_layout.cell_offset_of(*layer_type);
+        VectorOffset { x: x * spacing, y: y * spacing }
+    }
+    
+    /// Whether a layer is drawn on its own in quadrant view
+    /// The built-in layout draws the outline with every layer instead and hides paste
+    pub fn quadrant_shows(&self, layer_type: crate::ecs::LayerType) -> bool {
+        self.quadrant_layout.layer_cell(layer_type).is_some()
     }
     
     /// Set the quadrant offset magnitude in mm
@@ -211,90 +495,6 @@ impl DisplayManager {
             self.set_quadrant_offset_magnitude(magnitude_mm);
         }
     }
-    
-    /* REMOVED: LayerManager-dependent functions
-    /// Update all layer positions based on quadrant view settings
-    /// This properly positions layers in traditional geometry space
-    pub fn update_layer_positions(&self, layer_manager: &mut LayerManager) {
-        // Always mark coordinates as updated when we run this
-        let _should_update = layer_manager.coordinates_need_update() || 
-                           self.quadrant_view_enabled; // Always update in quadrant mode
-        // First, get the mechanical outline to determine the base size
-        let mechanical_size = if let Some(mechanical_layer) = layer_manager.get_layer(&LayerType::MechanicalOutline) {
-            if let Some(ref coords) = mechanical_layer.coordinates {
-                (coords.x_width, coords.y_height)
-            } else {
-                (100.0, 100.0) // Default size if no coordinates
-            }
-        } else {
-            (100.0, 100.0) // Default size if no mechanical outline
-        };
-        
-        // Calculate spacing between quadrants
-        let spacing = if self.quadrant_view_enabled {
-            self.quadrant_offset_magnitude.max(1.0)
-        } else {
-            0.0 // No spacing if quadrant view disabled
-        };
-        
-        // Update each layer's position
-        for (layer_type, layer_info) in layer_manager.layers.iter_mut() {
-            if let Some(ref _coords) = layer_info.coordinates {
-                let (screen_upper_left, screen_lower_right) = if self.quadrant_view_enabled {
-                    self.calculate_quadrant_position(layer_type, mechanical_size.0, mechanical_size.1, spacing)
-                } else {
-                    // All layers centered at origin when quadrant view is disabled
-                    let half_width = mechanical_size.0 / 2.0;
-                    let half_height = mechanical_size.1 / 2.0;
-                    (
-                        (-half_width, half_height),   // Upper left in traditional coords
-                        (half_width, -half_height)     // Lower right in traditional coords
-                    )
-                };
-                
-                layer_info.update_screen_position(screen_upper_left, screen_lower_right);
-            }
-        }
-    }
-    
-    /// Calculate the positioned bounds for a layer in linear horizontal layout
-    fn calculate_quadrant_position(
-        &self,
-        layer_type: &LayerType,
-        width: f32,
-        height: f32,
-        spacing: f64,
-    ) -> ((f32, f32), (f32, f32)) {
-        let half_width = width / 2.0;
-        let half_height = height / 2.0;
-        // Calculate horizontal positions using simple spacing:
-        // All layers at Y=0 (horizontal layout)  
-        let spacing = spacing as f32; // Use the provided spacing parameter
-        let center_x = match layer_type {
-            // Copper layers at origin (0,0)
-            LayerType::TopCopper | LayerType::BottomCopper => 0.0,
-            
-            // Silkscreen layers at spacing
-            LayerType::TopSilk | LayerType::BottomSilk => spacing,
-            
-            // Soldermask layers at spacing * 2
-            LayerType::TopSoldermask | LayerType::BottomSoldermask => spacing * 2.0,
-            
-            // Paste layers - hidden (positioned far off-screen)
-            LayerType::TopPaste | LayerType::BottomPaste => -9999.0,
-            
-            // Mechanical outline centered at origin
-            LayerType::MechanicalOutline => 0.0,
-        };
-        
-        let center_y = 0.0; // All layers at same Y level
-        
-        (
-            (center_x - half_width, center_y + half_height),   // Upper left
-            (center_x + half_width, center_y - half_height)    // Lower right
-        )
-    }
-    */
 }
 
 impl Default for DisplayManager {
@@ -312,4 +512,120 @@ impl ToPosition for nalgebra::Vector2<f64> {
     fn to_position(self) -> crate::drc_operations::types::Position {
         crate::drc_operations::types::Position { x: self.x, y: self.y }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{LayerType, Side};
+
+    #[test]
+    fn test_custom_quadrant_layout_places_and_hides_layers() {
+        let mut display_manager = DisplayManager::new();
+        display_manager.quadrant_view_enabled = true;
+        display_manager.set_quadrant_offset_magnitude(50.0);
+
+        // Built-in layout is unchanged: silkscreen next to copper, paste hidden
+        assert_eq!(display_manager.get_quadrant_offset(&LayerType::Silkscreen(Side::Top)).x, 50.0);
+        assert!(!display_manager.quadrant_shows(LayerType::Paste(Side::Top)));
+
+        display_manager.quadrant_layout.custom = true;
+        let bottom = display_manager.get_quadrant_offset(&LayerType::BOTTOM_COPPER);
+        assert_eq!((bottom.x, bottom.y), (50.0, 0.0));
+        let mask = display_manager.get_quadrant_offset(&LayerType::Soldermask(Side::Top));
+        assert_eq!((mask.x, mask.y), (0.0, -50.0));
+        assert!(display_manager.quadrant_shows(LayerType::MechanicalOutline));
+        assert!(!display_manager.quadrant_shows(LayerType::Silkscreen(Side::Top)));
+
+        // Moving a layer frees its old quadrant
+        display_manager.quadrant_layout.assign(Quadrant::LowerRight, Some(LayerType::TOP_COPPER));
+        assert_eq!(display_manager.quadrant_layout.layer(Quadrant::UpperLeft), None);
+        assert_eq!(display_manager.quadrant_layout.quadrant_of(LayerType::TOP_COPPER), Some(Quadrant::LowerRight));
+        assert!(!display_manager.quadrant_shows(LayerType::MechanicalOutline));
+
+        // Top and bottom copper side by side, then stacked; the cells keep their layers
+        display_manager.quadrant_layout.assign(Quadrant::UpperRight, Some(LayerType::TOP_COPPER));
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Row);
+        let top = display_manager.get_quadrant_offset(&LayerType::TOP_COPPER);
+        assert_eq!((top.x, top.y), (50.0, 0.0));
+        assert_eq!(display_manager.quadrant_layout.cell_of(LayerType::Soldermask(Side::Top)), Some(2));
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Column);
+        let mask = display_manager.get_quadrant_offset(&LayerType::Soldermask(Side::Top));
+        assert_eq!((mask.x, mask.y), (0.0, -100.0));
+
+        // A 3×2 grid wraps after the third cell; shrinking it drops the layers past the end
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Grid { columns: 3, rows: 2 });
+        display_manager.quadrant_layout.assign_cell(5, Some(LayerType::BOTTOM_COPPER));
+        let bottom = display_manager.get_quadrant_offset(&LayerType::BOTTOM_COPPER);
+        assert_eq!((bottom.x, bottom.y), (100.0, -50.0));
+        assert_eq!(display_manager.quadrant_layout.file_prefix(5), "r2_c3");
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Grid { columns: 2, rows: 1 });
+        assert!(!display_manager.quadrant_shows(LayerType::BOTTOM_COPPER));
+    }
+
+    #[test]
+    fn test_swipe_divider_follows_the_pointer_inside_the_viewport() {
+        let viewport = egui::Rect::from_min_size(egui::pos2(100.0, 0.0), egui::vec2(400.0, 300.0));
+        let mut swipe = SwipeCompareSettings::default();
+        assert_eq!(swipe.divider_x(&viewport), 300.0);
+
+        swipe.set_divider_x(200.0, &viewport);
+        assert_eq!(swipe.divider_fraction, 0.25);
+        assert_eq!(swipe.divider_x(&viewport), 200.0);
+
+        // Dragged past either edge, the divider stops there
+        swipe.set_divider_x(900.0, &viewport);
+        assert_eq!(swipe.divider_x(&viewport), 500.0);
+        swipe.set_divider_x(-50.0, &viewport);
+        assert_eq!(swipe.divider_x(&viewport), 100.0);
+
+        // A collapsed viewport leaves the divider where it was
+        swipe.set_divider_x(200.0, &viewport);
+        swipe.set_divider_x(150.0, &egui::Rect::from_min_size(egui::pos2(100.0, 0.0), egui::Vec2::ZERO));
+        assert_eq!(swipe.divider_fraction, 0.25);
+    }
+
+    #[test]
+    fn test_builtin_quadrant_layout_follows_arrangement() {
+        let mut display_manager = DisplayManager::new();
+        display_manager.quadrant_view_enabled = true;
+        display_manager.set_quadrant_offset_magnitude(50.0);
+        let offset = |display_manager: &DisplayManager, layer_type: LayerType| {
+            let offset = display_manager.get_quadrant_offset(&layer_type);
+            (offset.x, offset.y)
+        };
+
+        // 2×2: copper and silkscreen on top, soldermask below copper; the outline stays with every layer
+        assert_eq!(offset(&display_manager, LayerType::BOTTOM_COPPER), (0.0, 0.0));
+        assert_eq!(offset(&display_manager, LayerType::Silkscreen(Side::Top)), (50.0, 0.0));
+        assert_eq!(offset(&display_manager, LayerType::Soldermask(Side::Bottom)), (0.0, -50.0));
+        assert_eq!(offset(&display_manager, LayerType::MechanicalOutline), (0.0, 0.0));
+        assert!(!display_manager.quadrant_shows(LayerType::MechanicalOutline));
+
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Row);
+        assert_eq!(offset(&display_manager, LayerType::Soldermask(Side::Top)), (100.0, 0.0));
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Column);
+        assert_eq!(offset(&display_manager, LayerType::Silkscreen(Side::Top)), (0.0, -50.0));
+        assert_eq!(offset(&display_manager, LayerType::Soldermask(Side::Top)), (0.0, -100.0));
+
+        // Paste is hidden in every arrangement, and categories past the last cell are not drawn
+        assert!(!display_manager.quadrant_shows(LayerType::Paste(Side::Top)));
+        assert_eq!(offset(&display_manager, LayerType::Paste(Side::Top)).0, -9999.0);
+        display_manager.quadrant_layout.set_arrangement(QuadrantArrangement::Grid { columns: 2, rows: 1 });
+        assert!(display_manager.quadrant_shows(LayerType::Silkscreen(Side::Top)));
+        assert!(!display_manager.quadrant_shows(LayerType::Soldermask(Side::Top)));
+    }
+
+    #[test]
+    fn test_drill_holes_knocked_out_only_in_knockout_style() {
+        let mut display_manager = DisplayManager::new();
+        display_manager.show_drill_holes = true;
+        assert!(display_manager.knocks_out_drill_holes());
+
+        // Outlines leave the layers intact, and hidden holes knock nothing out
+        display_manager.drill_hole_style = DrillHoleStyle::Outline;
+        assert!(!display_manager.knocks_out_drill_holes());
+        display_manager.drill_hole_style = DrillHoleStyle::Knockout;
+        display_manager.show_drill_holes = false;
+        assert!(!display_manager.knocks_out_drill_holes());
+    }
+}
@@ -1,6 +1,15 @@
 pub mod manager;
 pub mod grid;
+pub mod color_scheme;
+pub mod animation;
+pub mod realistic;
+pub mod view3d;
+pub mod transform;
 
 // Re-export the main types for easy access
-pub use manager::{DisplayManager, VectorOffset};
-pub use grid::{GridSettings, draw_grid, snap_to_grid, align_to_grid};
\ No newline at end of file
+pub use manager::{DisplayManager, DrillHoleStyle, Quadrant, QuadrantArrangement, QuadrantLayout, SwipeCompareSettings, VectorOffset};
+pub use color_scheme::ColorScheme;
+pub use animation::ViewAnimation;
+pub use realistic::{MaskColor, RealisticRenderSettings, SurfaceFinish};
+pub use grid::{GridSettings, GridStyle, draw_grid, snap_to_grid, align_to_grid};
+pub use transform::{build_layer_transform, design_origin_transform, rotated_extent, wrap_rotation_degrees};
\ No newline at end of file
@@ -0,0 +1,165 @@
+//! Colours for the "realistic" composite view
+//!
+//! The realistic view paints the shown side of the board the way it looks when
+//! fabricated: substrate, copper seen through a translucent soldermask film,
+//! exposed pads in the surface finish colour and white silkscreen. The
+//! renderer only needs solid colours, so the film is mixed into the colours
+//! here instead of relying on painter blending.
+
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+/// Bare FR-4 laminate
+pub const SUBSTRATE_COLOR: Color32 = Color32::from_rgb(196, 176, 120);
+
+/// Copper under the mask, before the film is mixed in
+pub const COPPER_COLOR: Color32 = Color32::from_rgb(184, 115, 51);
+
+/// Inside of plated and non-plated holes
+pub const DRILL_HOLE_COLOR: Color32 = Color32::from_rgb(20, 20, 20);
+
+/// Plating exposed through soldermask openings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SurfaceFinish {
+    Hasl,
+    Enig,
+    /// Bare copper with an organic preservative
+    Osp,
+}
+
+impl SurfaceFinish {
+    pub const ALL: [SurfaceFinish; 3] = [SurfaceFinish::Hasl, SurfaceFinish::Enig, SurfaceFinish::Osp];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SurfaceFinish::Hasl => "HASL (tin)",
+            SurfaceFinish::Enig => "ENIG (gold)",
+            SurfaceFinish::Osp => "OSP (copper)",
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            SurfaceFinish::Hasl => Color32::from_rgb(205, 205, 210),
+            SurfaceFinish::Enig => Color32::from_rgb(218, 178, 62),
+            SurfaceFinish::Osp => Color32::from_rgb(214, 140, 80),
+        }
+    }
+}
+
+/// Soldermask colours offered by most fabs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MaskColor {
+    Green,
+    Red,
+    Blue,
+    Black,
+    White,
+    Purple,
+}
+
+impl MaskColor {
+    pub const ALL: [MaskColor; 6] = [
+        MaskColor::Green,
+        MaskColor::Red,
+        MaskColor::Blue,
+        MaskColor::Black,
+        MaskColor::White,
+        MaskColor::Purple,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaskColor::Green => "Green",
+            MaskColor::Red => "Red",
+            MaskColor::Blue => "Blue",
+            MaskColor::Black => "Black",
+            MaskColor::White => "White",
+            MaskColor::Purple => "Purple",
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            MaskColor::Green => Color32::from_rgb(0, 100, 45),
+            MaskColor::Red => Color32::from_rgb(150, 20, 20),
+            MaskColor::Blue => Color32::from_rgb(10, 50, 140),
+            MaskColor::Black => Color32::from_rgb(20, 20, 22),
+            MaskColor::White => Color32::from_rgb(235, 235, 235),
+            MaskColor::Purple => Color32::from_rgb(80, 30, 120),
+        }
+    }
+
+    /// How much of what is underneath shows through the film
+    fn translucency(&self) -> f32 {
+        match self {
+            MaskColor::Black | MaskColor::White => 0.1,
+            _ => 0.3,
+        }
+    }
+}
+
+/// Mix `under` into `over` by `amount` (0 = only `over`)
+pub fn mix(over: Color32, under: Color32, amount: f32) -> Color32 {
+    let amount = amount.clamp(0.0, 1.0);
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount).round() as u8;
+    Color32::from_rgb(channel(over.r(), under.r()), channel(over.g(), under.g()), channel(over.b(), under.b()))
+}
+
+/// Realistic composite view settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RealisticRenderSettings {
+    pub enabled: bool,
+    pub finish: SurfaceFinish,
+    pub mask_color: MaskColor,
+}
+
+impl Default for RealisticRenderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            finish: SurfaceFinish::Hasl,
+            mask_color: MaskColor::Green,
+        }
+    }
+}
+
+impl RealisticRenderSettings {
+    /// Bare board seen through the mask
+    pub fn masked_substrate_color(&self) -> Color32 {
+        mix(self.mask_color.color(), SUBSTRATE_COLOR, self.mask_color.translucency())
+    }
+
+    /// Copper seen through the mask; lighter than the bare board around it
+    pub fn masked_copper_color(&self) -> Color32 {
+        mix(self.mask_color.color(), COPPER_COLOR, self.mask_color.translucency() + 0.15)
+    }
+
+    pub fn finish_color(&self) -> Color32 {
+        self.finish.color()
+    }
+
+    /// White ink, or black on white mask so it stays readable
+    pub fn silkscreen_color(&self) -> Color32 {
+        if self.mask_color == MaskColor::White { Color32::from_rgb(25, 25, 25) } else { Color32::from_rgb(245, 245, 245) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_film_mixes_toward_what_is_underneath() {
+        assert_eq!(mix(Color32::from_rgb(0, 100, 0), Color32::from_rgb(200, 0, 100), 0.5), Color32::from_rgb(100, 50, 50));
+
+        let settings = RealisticRenderSettings::default();
+        let mask = settings.mask_color.color();
+        let (substrate, copper) = (settings.masked_substrate_color(), settings.masked_copper_color());
+        // Traces stand out from the board under green mask
+        assert!(copper.r() > substrate.r() && copper.r() > mask.r());
+        assert_ne!(substrate, mask);
+        assert_eq!(RealisticRenderSettings { mask_color: MaskColor::White, ..settings }.silkscreen_color(), Color32::from_rgb(25, 25, 25));
+    }
+}
@@ -0,0 +1,125 @@
+//! The view transform from gerber coordinates to view coordinates
+//!
+//! Every path that places geometry on screen (layer rendering, overlay markers,
+//! hit-testing) or takes it back off (export) starts from `build_layer_transform`,
+//! so a point lands in the same place whichever path draws it. Per-layer parts
+//! (alignment nudge, image transform) are composed on top in the ECS.
+
+use gerber_viewer::GerberTransform;
+use nalgebra::Vector2;
+
+use super::DisplayManager;
+use crate::ecs::LayerType;
+
+/// Rotation and mirroring about the board center, plus the quadrant offset of `layer_type`
+/// `None` gives the board-level transform used by overlays, without quadrant spreading
+pub fn build_layer_transform(display: &DisplayManager, rotation_degrees: f32, layer_type: Option<LayerType>) -> GerberTransform {
+    let offset = layer_type.map_or_else(Vector2::zeros, |layer_type| display.get_quadrant_offset(&layer_type).into());
+    GerberTransform {
+        rotation: rotation_degrees.to_radians(),
+        mirroring: display.mirroring.clone().into(),
+        origin: display.rotation_center.clone().into(),
+        offset,
+        scale: 1.0,
+    }
+}
+
+/// Transform from view coordinates to exported board coordinates: the design origin becomes (0, 0)
+pub fn design_origin_transform(display: &DisplayManager) -> nalgebra::Matrix3<f64> {
+    nalgebra::Matrix3::new_translation(&-Vector2::from(display.design_offset.clone()))
+}
+
+/// Board rotation wrapped into [0, 360)
+pub fn wrap_rotation_degrees(degrees: f32) -> f32 {
+    let wrapped = degrees.rem_euclid(360.0);
+    // rem_euclid can round up to exactly 360.0 for tiny negative inputs
+    if wrapped >= 360.0 { 0.0 } else { wrapped }
+}
+
+/// Size of the axis-aligned box around a `width` × `height` extent turned by `rotation_degrees`
+/// Used to fit the view so non-orthogonal angles don't clip the board
+pub fn rotated_extent(width: f64, height: f64, rotation_degrees: f32) -> (f64, f64) {
+    let (sin, cos) = (rotation_degrees as f64).to_radians().sin_cos();
+    (width * cos.abs() + height * sin.abs(), width * sin.abs() + height * cos.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Point2;
+
+    #[test]
+    fn test_board_transform_turns_about_center_and_ignores_quadrants() {
+        let mut display = DisplayManager::new();
+        display.rotation_center = super::super::VectorOffset { x: 10.0, y: 5.0 };
+        display.quadrant_view_enabled = true;
+        display.set_quadrant_offset_magnitude(50.0);
+
+        let board = build_layer_transform(&display, 90.0, None);
+        assert!((board.apply_to_position(Point2::new(10.0, 5.0)) - Point2::new(10.0, 5.0)).norm() < 1e-9);
+        assert!((board.apply_to_position(Point2::new(12.0, 5.0)) - Point2::new(10.0, 7.0)).norm() < 1e-9);
+
+        let silk = build_layer_transform(&display, 90.0, Some(LayerType::Silkscreen(crate::ecs::Side::Top)));
+        assert!((silk.apply_to_position(Point2::new(12.0, 5.0)) - Point2::new(60.0, 7.0)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_rotation_wraps_and_fits_arbitrary_angles() {
+        assert_eq!(wrap_rotation_degrees(450.0), 90.0);
+        assert!((wrap_rotation_degrees(-0.1) - 359.9).abs() < 1e-4);
+        assert_eq!(wrap_rotation_degrees(-1e-9), 0.0);
+        assert_eq!(wrap_rotation_degrees(360.0), 0.0);
+
+        let (width, height) = rotated_extent(30.0, 20.0, 90.0);
+        assert!((width - 20.0).abs() < 1e-9 && (height - 30.0).abs() < 1e-9);
+        // At 45° both sides span (30 + 20) / √2
+        let (width, height) = rotated_extent(30.0, 20.0, 45.0);
+        let diagonal = 50.0 / 2f64.sqrt();
+        assert!((width - diagonal).abs() < 1e-9 && (height - diagonal).abs() < 1e-9);
+        let (width, height) = rotated_extent(30.0, 20.0, 180.0);
+        assert!((width - 30.0).abs() < 1e-9 && (height - 20.0).abs() < 1e-9);
+    }
+
+    /// A point lands on the same screen position through the layer render path,
+    /// the overlay marker path and (after undoing origin and quadrant) the export path
+    #[test]
+    fn test_render_overlay_and_export_paths_agree() {
+        let layer_type = LayerType::TOP_SILK;
+        let mut world = crate::ecs::setup_ecs_world();
+        crate::ecs::create_gerber_layer_entity(&mut world, layer_type, gerber_viewer::GerberLayer::new(Vec::new()), None, None, true);
+        let view_state = gerber_viewer::ViewState { scale: 3.0, base_scale: 3.0, translation: egui::Vec2::new(400.0, 300.0) };
+        let point = Point2::new(7.5, 2.0);
+        let screen = |position: Point2<f64>| view_state.gerber_to_screen_coords(position);
+
+        for rotation_degrees in [0.0f32, 90.0, 33.0] {
+            for (mirror_x, mirror_y) in [(false, false), (true, false), (false, true)] {
+                for design_offset in [(0.0, 0.0), (3.0, -4.0)] {
+                    for quadrants in [false, true] {
+                        let mut display = DisplayManager::new();
+                        display.rotation_center = super::super::VectorOffset { x: 10.0, y: 5.0 };
+                        display.mirroring.x = mirror_x;
+                        display.mirroring.y = mirror_y;
+                        display.design_offset = super::super::VectorOffset { x: design_offset.0, y: design_offset.1 };
+                        display.quadrant_view_enabled = quadrants;
+                        display.set_quadrant_offset_magnitude(40.0);
+                        crate::ecs::run_ecs_systems(&mut world, &display, rotation_degrees);
+                        let case = format!("{}° mirror {:?} origin {:?} quadrants {}", rotation_degrees, (mirror_x, mirror_y), design_offset, quadrants);
+
+                        let rendered = crate::ecs::get_layer_render_transform(&mut world, &display, layer_type).unwrap().apply_to_position(point);
+                        let overlay = build_layer_transform(&display, rotation_degrees, Some(layer_type)).apply_to_position(point);
+                        assert!(screen(rendered).distance(screen(overlay)) < 1e-3, "{}", case);
+
+                        let board = crate::ecs::get_board_view_transform(&mut world, &display).unwrap().apply_to_position(point);
+                        let marker = build_layer_transform(&display, rotation_degrees, None).apply_to_position(point);
+                        assert!(screen(board).distance(screen(marker)) < 1e-3, "{}", case);
+
+                        let exported = crate::ecs::get_layer_export_transform(&mut world, &display, layer_type).unwrap().apply_to_position(point);
+                        let quadrant: Vector2<f64> = display.get_quadrant_offset(&layer_type).into();
+                        let restored = exported + Vector2::from(display.design_offset.clone()) + quadrant;
+                        assert!(screen(restored).distance(screen(rendered)) < 1e-3, "{}", case);
+                    }
+                }
+            }
+        }
+    }
+}
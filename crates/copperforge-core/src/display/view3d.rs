@@ -0,0 +1,328 @@
+//! Meshes and orbit camera for the 3D board view
+//!
+//! Copper, mask, silk and paste are only tens of µm thick, so each layer
+//! becomes a flat sheet of triangles at its outer surface height from the
+//! `Stackup`; only the board core is extruded with side walls. Clear-polarity
+//! primitives are not subtracted from the sheets. The view is drawn as
+//! depth-sorted egui triangles, so it needs no GPU pipeline of its own and
+//! works on every eframe backend.
+
+use egui::{Color32, Pos2, Rect};
+use nalgebra::{Point2, Point3, Vector3};
+
+use crate::ecs::{ApertureShape, Polarity, PrimitiveShape, TaggedPrimitive};
+
+/// Segments used for a full circle; caps and pads use a share of this
+const CIRCLE_STEPS: usize = 16;
+
+/// Direction of the fixed light used for flat shading
+const LIGHT_DIRECTION: Vector3<f32> = Vector3::new(0.3, -0.4, 0.87);
+
+/// Triangle mesh in board coordinates (mm, z up from the bottom of the core)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh3d {
+    pub positions: Vec<Point3<f32>>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl Mesh3d {
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// Fill a simple polygon at height `z`, wound to face up (or down when `facing_down`)
+    fn push_polygon(&mut self, outline: &[Point2<f64>], z: f64, facing_down: bool) {
+        let base = self.positions.len() as u32;
+        self.positions.extend(outline.iter().map(|p| Point3::new(p.x as f32, p.y as f32, z as f32)));
+        for [a, b, c] in triangulate(outline) {
+            let (a, b, c) = (base + a as u32, base + b as u32, base + c as u32);
+            self.triangles.push(if facing_down { [a, c, b] } else { [a, b, c] });
+        }
+    }
+
+    /// Axis-aligned bounds (min, max), None for an empty mesh
+    pub fn bounds(&self) -> Option<(Point3<f32>, Point3<f32>)> {
+        let first = *self.positions.first()?;
+        Some(self.positions.iter().fold((first, first), |(min, max), p| {
+            (min.inf(p), max.sup(p))
+        }))
+    }
+}
+
+fn circle(center: Point2<f64>, radius: f64) -> Vec<Point2<f64>> {
+    (0..CIRCLE_STEPS)
+        .map(|i| {
+            let angle = i as f64 / CIRCLE_STEPS as f64 * std::f64::consts::TAU;
+            center + nalgebra::Vector2::new(angle.cos(), angle.sin()) * radius
+        })
+        .collect()
+}
+
+/// Stadium shape around the segment a-b, counter-clockwise
+fn capsule(a: Point2<f64>, b: Point2<f64>, radius: f64) -> Vec<Point2<f64>> {
+    let direction = b - a;
+    if direction.norm() < 1e-9 {
+        return circle(a, radius);
+    }
+    let heading = direction.y.atan2(direction.x);
+    let half = CIRCLE_STEPS / 2;
+    let arc = |center: Point2<f64>, start: f64| {
+        (0..=half).map(move |i| {
+            let angle = start + i as f64 / half as f64 * std::f64::consts::PI;
+            center + nalgebra::Vector2::new(angle.cos(), angle.sin()) * radius
+        })
+    };
+    arc(b, heading - std::f64::consts::FRAC_PI_2)
+        .chain(arc(a, heading + std::f64::consts::FRAC_PI_2))
+        .collect()
+}
+
+fn rectangle(center: Point2<f64>, width: f64, height: f64) -> Vec<Point2<f64>> {
+    let (w, h) = (width / 2.0, height / 2.0);
+    vec![
+        Point2::new(center.x - w, center.y - h),
+        Point2::new(center.x + w, center.y - h),
+        Point2::new(center.x + w, center.y + h),
+        Point2::new(center.x - w, center.y + h),
+    ]
+}
+
+/// Outline of a primitive's copper as a simple polygon
+pub fn primitive_outline(shape: &PrimitiveShape) -> Vec<Point2<f64>> {
+    match shape {
+        PrimitiveShape::Segment { start, end, width } if *width > 0.0 => capsule(*start, *end, width / 2.0),
+        PrimitiveShape::Segment { .. } => Vec::new(),
+        PrimitiveShape::Flash { center, width, height, aperture, .. } => match aperture {
+            ApertureShape::Rectangle | ApertureShape::Other => rectangle(*center, *width, *height),
+            ApertureShape::Obround => {
+                let radius = width.min(*height) / 2.0;
+                let axis = if width >= height {
+                    nalgebra::Vector2::new(width / 2.0 - radius, 0.0)
+                } else {
+                    nalgebra::Vector2::new(0.0, height / 2.0 - radius)
+                };
+                capsule(center - axis, center + axis, radius)
+            }
+            ApertureShape::Circle | ApertureShape::Polygon => circle(*center, width / 2.0),
+        },
+        PrimitiveShape::Region { points } => points.clone(),
+    }
+}
+
+fn point_in_triangle(p: Point2<f64>, a: Point2<f64>, b: Point2<f64>, c: Point2<f64>) -> bool {
+    let d1 = (b - a).perp(&(p - a));
+    let d2 = (c - b).perp(&(p - b));
+    let d3 = (a - c).perp(&(p - c));
+    (d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0) || (d1 <= 0.0 && d2 <= 0.0 && d3 <= 0.0)
+}
+
+/// Ear-clipping triangulation of a simple polygon, as counter-clockwise index triples
+/// Self-intersecting leftovers are fanned rather than dropped
+pub fn triangulate(points: &[Point2<f64>]) -> Vec<[usize; 3]> {
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    if indices.len() > 3 && points.first() == points.last() {
+        indices.pop();
+    }
+    if indices.len() < 3 {
+        return Vec::new();
+    }
+    let n = indices.len();
+    let area: f64 = (0..n).map(|i| points[indices[i]].coords.perp(&points[indices[(i + 1) % n]].coords)).sum();
+    if area < 0.0 {
+        indices.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(indices.len() - 2);
+    let mut i = 0;
+    let mut misses = 0;
+    while indices.len() > 3 {
+        let n = indices.len();
+        if misses >= n {
+            for k in 1..n - 1 {
+                triangles.push([indices[0], indices[k], indices[k + 1]]);
+            }
+            return triangles;
+        }
+        i %= n;
+        let (prev, current, next) = (indices[(i + n - 1) % n], indices[i], indices[(i + 1) % n]);
+        let (a, b, c) = (points[prev], points[current], points[next]);
+        let turn = (b - a).perp(&(c - b));
+        if turn.abs() < 1e-12 {
+            // Collinear or repeated vertex: drop it without a triangle
+            indices.remove(i);
+            misses = 0;
+            continue;
+        }
+        let is_ear = turn > 0.0 && !indices.iter()
+            .any(|&k| k != prev && k != current && k != next && point_in_triangle(points[k], a, b, c));
+        if is_ear {
+            triangles.push([prev, current, next]);
+            indices.remove(i);
+            misses = 0;
+        } else {
+            i += 1;
+            misses += 1;
+        }
+    }
+    triangles.push([indices[0], indices[1], indices[2]]);
+    triangles
+}
+
+/// Sheet of a layer's dark primitives at height `z`
+pub fn layer_mesh(primitives: &[TaggedPrimitive], z: f64, facing_down: bool) -> Mesh3d {
+    let mut mesh = Mesh3d::default();
+    for primitive in primitives.iter().filter(|p| p.polarity == Polarity::Dark) {
+        mesh.push_polygon(&primitive_outline(&primitive.shape), z, facing_down);
+    }
+    mesh
+}
+
+/// Board core extruded between `bottom` and `top` over its outline
+pub fn board_mesh(outline: &[Point2<f64>], bottom: f64, top: f64) -> Mesh3d {
+    let mut mesh = Mesh3d::default();
+    if outline.len() < 3 {
+        return mesh;
+    }
+    mesh.push_polygon(outline, top, false);
+    mesh.push_polygon(outline, bottom, true);
+
+    let n = outline.len();
+    let ccw = (0..n).map(|i| outline[i].coords.perp(&outline[(i + 1) % n].coords)).sum::<f64>() > 0.0;
+    for i in 0..n {
+        let (a, b) = if ccw { (outline[i], outline[(i + 1) % n]) } else { (outline[(i + 1) % n], outline[i]) };
+        let base = mesh.positions.len() as u32;
+        mesh.positions.extend([
+            Point3::new(a.x as f32, a.y as f32, bottom as f32),
+            Point3::new(b.x as f32, b.y as f32, bottom as f32),
+            Point3::new(b.x as f32, b.y as f32, top as f32),
+            Point3::new(a.x as f32, a.y as f32, top as f32),
+        ]);
+        mesh.triangles.push([base, base + 1, base + 2]);
+        mesh.triangles.push([base, base + 2, base + 3]);
+    }
+    mesh
+}
+
+/// Flat shading of a face color by its normal
+pub fn shade(color: Color32, normal: Vector3<f32>) -> Color32 {
+    let light = normal.try_normalize(1e-12)
+        .map_or(1.0, |normal| normal.dot(&LIGHT_DIRECTION.normalize()).abs());
+    let factor = 0.35 + 0.65 * light;
+    let scale = |channel: u8| (channel as f32 * factor).round() as u8;
+    Color32::from_rgba_premultiplied(scale(color.r()), scale(color.g()), scale(color.b()), color.a())
+}
+
+/// Camera orbiting a target point; z is the board normal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCamera {
+    pub target: Point3<f32>,
+    /// Rotation about the board normal (radians)
+    pub yaw: f32,
+    /// Elevation above the board plane (radians), kept short of the poles
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            target: Point3::origin(),
+            yaw: -std::f32::consts::FRAC_PI_2,
+            pitch: 0.8,
+            distance: 100.0,
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// Vertical field of view (radians)
+    pub const FOV_Y: f32 = 0.8;
+    const MAX_PITCH: f32 = 1.55;
+
+    pub fn eye(&self) -> Point3<f32> {
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        self.target + Vector3::new(cos_pitch * cos_yaw, cos_pitch * sin_yaw, sin_pitch) * self.distance
+    }
+
+    /// Right, up and forward unit vectors of the view
+    fn basis(&self) -> (Vector3<f32>, Vector3<f32>, Vector3<f32>) {
+        let forward = (self.target - self.eye()).normalize();
+        let right = forward.cross(&Vector3::z()).try_normalize(1e-6).unwrap_or_else(Vector3::x);
+        (right, right.cross(&forward), forward)
+    }
+
+    /// Rotate by screen drag (radians)
+    pub fn orbit(&mut self, yaw_delta: f32, pitch_delta: f32) {
+        self.yaw -= yaw_delta;
+        self.pitch = (self.pitch + pitch_delta).clamp(-Self::MAX_PITCH, Self::MAX_PITCH);
+    }
+
+    /// Move the target by a screen drag in pixels
+    pub fn pan(&mut self, delta: egui::Vec2, viewport_height: f32) {
+        let (right, up, _) = self.basis();
+        let mm_per_px = 2.0 * self.distance * (Self::FOV_Y / 2.0).tan() / viewport_height.max(1.0);
+        self.target += (-right * delta.x + up * delta.y) * mm_per_px;
+    }
+
+    /// Scale the distance to the target (< 1 moves closer)
+    pub fn zoom(&mut self, factor: f32) {
+        self.distance = (self.distance * factor).clamp(0.5, 5000.0);
+    }
+
+    /// Center on the bounds and back off until they fit
+    pub fn fit(&mut self, min: Point3<f32>, max: Point3<f32>) {
+        self.target = nalgebra::center(&min, &max);
+        let radius = ((max - min).norm() / 2.0).max(1.0);
+        self.distance = radius / (Self::FOV_Y / 2.0).tan() * 1.1;
+    }
+
+    /// Screen position and view depth of a point; None when behind the camera
+    pub fn project(&self, point: Point3<f32>, viewport: Rect) -> Option<(Pos2, f32)> {
+        let (right, up, forward) = self.basis();
+        let offset = point - self.eye();
+        let depth = offset.dot(&forward);
+        if depth < self.distance * 0.01 {
+            return None;
+        }
+        let focal = viewport.height() / 2.0 / (Self::FOV_Y / 2.0).tan();
+        let center = viewport.center();
+        Some((
+            Pos2::new(center.x + offset.dot(&right) / depth * focal, center.y - offset.dot(&up) / depth * focal),
+            depth,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concave_outline_triangulates_and_camera_centers_target() {
+        // L-shaped board, clockwise, with a collinear point on the long edge
+        let outline = [
+            Point2::new(0.0, 0.0), Point2::new(0.0, 20.0), Point2::new(10.0, 20.0),
+            Point2::new(10.0, 10.0), Point2::new(30.0, 10.0), Point2::new(30.0, 0.0), Point2::new(15.0, 0.0),
+        ];
+        let triangles = triangulate(&outline);
+        let area: f64 = triangles.iter()
+            .map(|[a, b, c]| (outline[*b] - outline[*a]).perp(&(outline[*c] - outline[*a])) / 2.0)
+            .sum();
+        assert_eq!(triangles.len(), 4);
+        assert!((area - 400.0).abs() < 1e-9);
+
+        let board = board_mesh(&outline, 0.0, 1.6);
+        let (min, max) = board.bounds().unwrap();
+        assert_eq!((min.z, max.z), (0.0, 1.6));
+
+        let mut camera = OrbitCamera::default();
+        camera.fit(min, max);
+        let viewport = Rect::from_min_size(Pos2::ZERO, egui::vec2(800.0, 600.0));
+        let (center, _) = camera.project(camera.target, viewport).unwrap();
+        assert!((center - viewport.center()).length() < 1e-3);
+        for corner in [min, max] {
+            assert!(viewport.contains(camera.project(corner, viewport).unwrap().0));
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Component placement overlap check from pick-and-place data
+//!
+//! Each component's outline is approximated from its footprint name using a
+//! small table of common package sizes, falling back to the extents of the
+//! silkscreen drawn around it. Rotated outlines are reduced to their
+//! axis-aligned bounds, so parts at odd angles may be reported a little early.
+
+use std::sync::LazyLock;
+
+use nalgebra::Point2;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::Side;
+
+/// Silkscreen further than this from a component center is never part of its outline (mm)
+pub const SILKSCREEN_SEARCH_RADIUS: f64 = 4.0;
+
+static CHIP_SIZE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:^|[_\-])(0201|0402|0603|0805|1206|1210|2512)(?:[_\-]|$)").unwrap());
+static BODY_DIMENSIONS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"_(\d+(?:\.\d+)?)X(\d+(?:\.\d+)?)MM").unwrap());
+
+/// A placed component, in KiCad board coordinates (mm, y down)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedComponent {
+    pub reference: String,
+    pub footprint: String,
+    pub side: Side,
+    pub x: f64,
+    pub y: f64,
+    /// Degrees, counter-clockwise
+    pub rotation: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutlineSource {
+    PackageTable,
+    Silkscreen,
+}
+
+/// Axis-aligned outline of a component in board coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComponentOutline {
+    pub min: Point2<f64>,
+    pub max: Point2<f64>,
+    pub source: OutlineSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssemblyIssue {
+    pub ref_a: String,
+    pub ref_b: String,
+    pub side: Side,
+    /// How far the outlines overlap (mm); 0 when they only sit too close
+    pub overlap: f64,
+    /// Edge-to-edge spacing (mm); 0 when the outlines overlap
+    pub spacing: f64,
+    /// Midpoint between the two components, board coordinates
+    pub x: f64,
+    pub y: f64,
+}
+
+impl AssemblyIssue {
+    /// Key used to remember an acknowledged pair, independent of order
+    pub fn pair_key(&self) -> String {
+        if self.ref_a <= self.ref_b {
+            format!("{}|{}", self.ref_a, self.ref_b)
+        } else {
+            format!("{}|{}", self.ref_b, self.ref_a)
+        }
+    }
+
+    pub fn description(&self, clearance: f64) -> String {
+        if self.overlap > 0.0 {
+            format!("{} and {} overlap by {:.3}mm", self.ref_a, self.ref_b, self.overlap)
+        } else {
+            format!("{} and {} are {:.3}mm apart (min {:.3}mm)", self.ref_a, self.ref_b, self.spacing, clearance)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AssemblyCheckResult {
+    pub issues: Vec<AssemblyIssue>,
+    /// Components with neither a known package nor nearby silkscreen
+    pub unknown_size: Vec<String>,
+}
+
+/// Body size (x, y) at 0° including terminals, from the footprint name
+/// Understands KiCad names such as `Resistor_SMD:R_0603_1608Metric` or
+/// `Package_SO:SOIC-8_3.9x4.9mm_P1.27mm`
+pub fn package_body_size(footprint: &str) -> Option<(f64, f64)> {
+    let name = footprint.rsplit(':').next().unwrap_or(footprint).to_uppercase();
+
+    if let Some(captures) = CHIP_SIZE.captures(&name) {
+        return Some(match &captures[1] {
+            "0201" => (0.6, 0.3),
+            "0402" => (1.0, 0.5),
+            "0603" => (1.6, 0.8),
+            "0805" => (2.0, 1.25),
+            "1206" => (3.2, 1.6),
+            "1210" => (3.2, 2.5),
+            _ => (6.3, 3.2),
+        });
+    }
+
+    // Body dimensions spelled out in the name, e.g. "_3.9x4.9mm"
+    let body = BODY_DIMENSIONS
+        .captures(&name)
+        .and_then(|captures| Some((captures[1].parse::<f64>().ok()?, captures[2].parse::<f64>().ok()?)));
+
+    // Leads stick out past the body on gull-wing packages
+    if name.starts_with("SOIC") || name.starts_with("SOP") || name.starts_with("TSSOP") {
+        let (width, length) = body.unwrap_or((3.9, 4.9));
+        return Some((width + 2.1, length));
+    }
+    if name.starts_with("QFP") || name.starts_with("LQFP") || name.starts_with("TQFP") {
+        let (width, length) = body.unwrap_or((7.0, 7.0));
+        return Some((width + 2.0, length + 2.0));
+    }
+    if name.starts_with("QFN") || name.starts_with("DFN") {
+        return Some(body.unwrap_or((5.0, 5.0)));
+    }
+    if name.starts_with("SOT-23") {
+        return Some((2.9, 2.4));
+    }
+    body
+}
+
+/// Outline of a component from the package table, rotated into place
+pub fn package_outline(component: &PlacedComponent) -> Option<ComponentOutline> {
+    let (width, length) = package_body_size(&component.footprint)?;
+    let (sin, cos) = component.rotation.to_radians().sin_cos();
+    let half_x = (width * cos.abs() + length * sin.abs()) / 2.0;
+    let half_y = (width * sin.abs() + length * cos.abs()) / 2.0;
+    Some(ComponentOutline {
+        min: Point2::new(component.x - half_x, component.y - half_y),
+        max: Point2::new(component.x + half_x, component.y + half_y),
+        source: OutlineSource::PackageTable,
+    })
+}
+
+/// Outline of a component from the silkscreen bounds that sit closer to it than
+/// to any other component on its side. `silkscreen` holds (min, max) bounds in
+/// board coordinates.
+pub fn silkscreen_outline(
+    component: &PlacedComponent,
+    neighbours: &[PlacedComponent],
+    silkscreen: &[(Point2<f64>, Point2<f64>)],
+) -> Option<ComponentOutline> {
+    let center = Point2::new(component.x, component.y);
+    let mut outline: Option<(Point2<f64>, Point2<f64>)> = None;
+    for (min, max) in silkscreen {
+        let mid = nalgebra::center(min, max);
+        let distance = (mid - center).norm();
+        if distance > SILKSCREEN_SEARCH_RADIUS {
+            continue;
+        }
+        let closer_to_other = neighbours.iter().any(|other| {
+            other.reference != component.reference
+                && other.side == component.side
+                && (mid - Point2::new(other.x, other.y)).norm() < distance
+        });
+        if closer_to_other {
+            continue;
+        }
+        outline = Some(match outline {
+            Some((lo, hi)) => (
+                Point2::new(lo.x.min(min.x), lo.y.min(min.y)),
+                Point2::new(hi.x.max(max.x), hi.y.max(max.y)),
+            ),
+            None => (*min, *max),
+        });
+    }
+    outline.map(|(min, max)| ComponentOutline { min, max, source: OutlineSource::Silkscreen })
+}
+
+/// (overlap, spacing) between two outlines, see `AssemblyIssue`
+fn outline_separation(a: &ComponentOutline, b: &ComponentOutline) -> (f64, f64) {
+    let gap_x = (a.min.x - b.max.x).max(b.min.x - a.max.x);
+    let gap_y = (a.min.y - b.max.y).max(b.min.y - a.max.y);
+    if gap_x < 0.0 && gap_y < 0.0 {
+        ((-gap_x).min(-gap_y), 0.0)
+    } else {
+        (0.0, gap_x.max(0.0).hypot(gap_y.max(0.0)))
+    }
+}
+
+/// Flag same-side component pairs that overlap or sit closer than `clearance`
+/// `silkscreen` maps each side to its silkscreen bounds, used for unknown packages
+pub fn check_component_overlaps(
+    components: &[PlacedComponent],
+    clearance: f64,
+    silkscreen: &mut dyn FnMut(Side) -> Vec<(Point2<f64>, Point2<f64>)>,
+) -> AssemblyCheckResult {
+    let mut result = AssemblyCheckResult::default();
+    let mut outlines: Vec<(&PlacedComponent, ComponentOutline)> = Vec::new();
+
+    for side in [Side::Top, Side::Bottom] {
+        let on_side: Vec<PlacedComponent> = components.iter().filter(|c| c.side == side).cloned().collect();
+        let mut side_silkscreen = None;
+        for component in components.iter().filter(|c| c.side == side) {
+            let outline = package_outline(component).or_else(|| {
+                let bounds = side_silkscreen.get_or_insert_with(|| silkscreen(side));
+                silkscreen_outline(component, &on_side, bounds)
+            });
+            match outline {
+                Some(outline) => outlines.push((component, outline)),
+                None => result.unknown_size.push(component.reference.clone()),
+            }
+        }
+    }
+
+    for (i, (a, outline_a)) in outlines.iter().enumerate() {
+        for (b, outline_b) in &outlines[i + 1..] {
+            if a.side != b.side {
+                continue;
+            }
+            let (overlap, spacing) = outline_separation(outline_a, outline_b);
+            if overlap > 0.0 || spacing < clearance {
+                result.issues.push(AssemblyIssue {
+                    ref_a: a.reference.clone(),
+                    ref_b: b.reference.clone(),
+                    side: a.side,
+                    overlap,
+                    spacing,
+                    x: (a.x + b.x) / 2.0,
+                    y: (a.y + b.y) / 2.0,
+                });
+            }
+        }
+    }
+
+    result.issues.sort_by(|a, b| b.overlap.total_cmp(&a.overlap).then(a.spacing.total_cmp(&b.spacing)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(reference: &str, footprint: &str, side: Side, x: f64, rotation: f64) -> PlacedComponent {
+        PlacedComponent {
+            reference: reference.to_string(),
+            footprint: footprint.to_string(),
+            side,
+            x,
+            y: 10.0,
+            rotation,
+        }
+    }
+
+    #[test]
+    fn test_package_sizes_from_footprint_names() {
+        assert_eq!(package_body_size("Resistor_SMD:R_0603_1608Metric"), Some((1.6, 0.8)));
+        assert_eq!(package_body_size("C_0402"), Some((1.0, 0.5)));
+        assert_eq!(package_body_size("Package_SO:SOIC-8_3.9x4.9mm_P1.27mm"), Some((6.0, 4.9)));
+        assert_eq!(package_body_size("Package_DFN_QFN:QFN-32-1EP_5x5mm_P0.5mm"), Some((5.0, 5.0)));
+        assert_eq!(package_body_size("Connector:Weird_Thing"), None);
+    }
+
+    #[test]
+    fn test_overlap_and_clearance_on_same_side_only() {
+        let mut no_silkscreen = |_| Vec::new();
+        let components = [
+            part("R1", "R_0805_2012Metric", Side::Top, 0.0, 0.0),
+            // 0.5mm overlap with R1
+            part("R2", "R_0805_2012Metric", Side::Top, 1.5, 0.0),
+            // Same place as R1 but on the other side
+            part("R3", "R_0805_2012Metric", Side::Bottom, 0.0, 0.0),
+            // Rotated, 0.2mm clear of R2's right edge
+            part("C1", "C_0603_1608Metric", Side::Top, 3.1, 90.0),
+        ];
+        let result = check_component_overlaps(&components, 0.25, &mut no_silkscreen);
+        assert_eq!(result.issues.len(), 2);
+        assert_eq!((result.issues[0].ref_a.as_str(), result.issues[0].ref_b.as_str()), ("R1", "R2"));
+        assert!((result.issues[0].overlap - 0.5).abs() < 1e-9);
+        assert_eq!(result.issues[1].pair_key(), "C1|R2");
+        assert!((result.issues[1].spacing - 0.2).abs() < 1e-9);
+
+        assert!(check_component_overlaps(&components[2..], 0.25, &mut no_silkscreen).issues.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_package_uses_nearby_silkscreen() {
+        let components = [
+            part("J1", "Connector:Weird_Thing", Side::Top, 0.0, 0.0),
+            part("J2", "Connector:Other_Thing", Side::Top, 20.0, 0.0),
+        ];
+        let silkscreen = [
+            (Point2::new(-2.0, 9.0), Point2::new(2.0, 11.0)),
+            (Point2::new(-1.0, 8.0), Point2::new(1.0, 12.0)),
+        ];
+        let outline = silkscreen_outline(&components[0], &components, &silkscreen).unwrap();
+        assert_eq!((outline.min, outline.max), (Point2::new(-2.0, 8.0), Point2::new(2.0, 12.0)));
+
+        let result = check_component_overlaps(&components, 0.25, &mut |_| silkscreen.to_vec());
+        assert_eq!(result.unknown_size, vec!["J2".to_string()]);
+    }
+}
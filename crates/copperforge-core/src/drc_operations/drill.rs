@@ -0,0 +1,349 @@
+//! Drill DRC: hole size, drill-to-drill spacing, drill-to-copper clearance
+//! and the annular ring of plated holes
+//!
+//! Copper is taken from the extracted layer primitives (dark polarity only).
+//! Copper touching a hole is treated as its own pad/land, and everything on
+//! that pad's net is exempt from the clearance check.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy_ecs::world::World;
+
+use super::types::{DrcCheckConfig, DrcRules, DrcViolation};
+use crate::ecs::{DrillHole, LayerType, Polarity, PrimitiveShape, TaggedPrimitive};
+
+pub const RULE_MIN_DRILL_DIAMETER: &str = "Minimum Drill Diameter";
+pub const RULE_DRILL_SPACING: &str = "Drill To Drill Spacing";
+pub const RULE_DRILL_TO_COPPER: &str = "Drill To Copper Clearance";
+pub const RULE_ANNULAR_RING: &str = "Minimum Annular Ring";
+
+/// Size of the spatial hash cells used to find primitives near a point (mm)
+const COPPER_GRID_CELL_MM: f64 = 2.0;
+
+/// Primitives spanning more cells than this are checked against every hole
+const MAX_INDEXED_CELLS: i64 = 256;
+
+/// True for violations produced by the drill checks
+pub fn is_drill_rule(rule_name: &str) -> bool {
+    matches!(rule_name, RULE_MIN_DRILL_DIAMETER | RULE_DRILL_SPACING | RULE_DRILL_TO_COPPER | RULE_ANNULAR_RING)
+}
+
+/// Run the enabled drill checks against the loaded holes and copper layers
+/// The hole size check is cheap and always runs
+pub fn run_drill_drc_check(
+    holes: &[DrillHole],
+    copper_layers: &[(LayerType, Vec<TaggedPrimitive>)],
+    rules: &DrcRules,
+    checks: &DrcCheckConfig,
+) -> Vec<DrcViolation> {
+    let mut violations = check_drill_diameter(holes, rules.min_drill_diameter as f64);
+    if checks.check_spacing {
+        violations.extend(check_drill_spacing(holes, rules.min_drill_spacing as f64));
+    }
+    for (layer_type, primitives) in copper_layers {
+        if checks.check_spacing {
+            violations.extend(check_drill_to_copper(holes, *layer_type, primitives, rules.min_drill_to_copper as f64));
+        }
+        if checks.check_annular {
+            violations.extend(check_annular_ring(holes, *layer_type, primitives, rules.min_annular_ring as f64));
+        }
+    }
+    violations
+}
+
+/// Run the drill checks against the holes and copper layers loaded in `world`
+/// Returns the number of holes checked alongside the violations
+pub fn check_loaded_drills(world: &mut World, rules: &DrcRules, checks: &DrcCheckConfig) -> (usize, Vec<DrcViolation>) {
+    let holes = crate::ecs::get_drill_holes(world);
+    if holes.is_empty() {
+        return (0, Vec::new());
+    }
+    if !checks.check_spacing && !checks.check_annular {
+        return (holes.len(), run_drill_drc_check(&holes, &[], rules, checks));
+    }
+    
+    let copper_layers: Vec<(LayerType, Vec<TaggedPrimitive>)> = LayerType::all()
+        .into_iter()
+        .filter(|layer_type| layer_type.is_copper())
+        .map(|layer_type| (layer_type, crate::ecs::get_layer_primitives(world, layer_type)))
+        .filter(|(_, primitives)| !primitives.is_empty())
+        .collect();
+    
+    (holes.len(), run_drill_drc_check(&holes, &copper_layers, rules, checks))
+}
+
+fn drill_layer_name(hole: &DrillHole) -> String {
+    if hole.plated { "Drill (PTH)" } else { "Drill (NPTH)" }.to_string()
+}
+
+/// Every hole must be at least the minimum drill size
+pub fn check_drill_diameter(holes: &[DrillHole], min_diameter: f64) -> Vec<DrcViolation> {
+    holes
+        .iter()
+        .filter(|hole| hole.diameter < min_diameter)
+        .map(|hole| DrcViolation {
+            rule_name: RULE_MIN_DRILL_DIAMETER.to_string(),
+            description: format!("Hole {:.3}mm below minimum drill size", hole.diameter),
+            layer: drill_layer_name(hole),
+            measured_value: hole.diameter as f32,
+            required_value: min_diameter as f32,
+            x: hole.position.x as f32,
+            y: hole.position.y as f32,
+        })
+        .collect()
+}
+
+/// Hole centers closer than the minimum spacing risk breaking the drill bit
+pub fn check_drill_spacing(holes: &[DrillHole], min_spacing: f64) -> Vec<DrcViolation> {
+    let mut sorted: Vec<&DrillHole> = holes.iter().collect();
+    sorted.sort_by(|a, b| a.position.x.total_cmp(&b.position.x));
+
+    let mut violations = Vec::new();
+    for (i, hole) in sorted.iter().enumerate() {
+        for other in sorted[i + 1..].iter().take_while(|other| other.position.x - hole.position.x < min_spacing) {
+            let distance = (other.position - hole.position).norm();
+            if distance < min_spacing {
+                violations.push(DrcViolation {
+                    rule_name: RULE_DRILL_SPACING.to_string(),
+                    description: format!(
+                        "Hole centers {:.3}mm apart (next hole at {:.2}, {:.2})",
+                        distance, other.position.x, other.position.y
+                    ),
+                    layer: drill_layer_name(hole),
+                    measured_value: distance as f32,
+                    required_value: min_spacing as f32,
+                    x: hole.position.x as f32,
+                    y: hole.position.y as f32,
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Clearance from each hole's edge to copper that does not belong to the hole
+/// One violation is reported per hole and layer, at the closest offending copper
+pub fn check_drill_to_copper(
+    holes: &[DrillHole],
+    layer_type: LayerType,
+    primitives: &[TaggedPrimitive],
+    min_clearance: f64,
+) -> Vec<DrcViolation> {
+    let copper: Vec<&TaggedPrimitive> = primitives.iter().filter(|p| p.polarity == Polarity::Dark).collect();
+    let index = PrimitiveIndex::new(&copper);
+
+    let mut violations = Vec::new();
+    for hole in holes {
+        let radius = hole.diameter / 2.0;
+        let nearby = index.query(hole.position, radius + min_clearance);
+
+        // Copper overlapping a plated hole is its pad, land or a trace ending on it
+        let mut own_nets = HashSet::new();
+        let mut own = HashSet::new();
+        if hole.plated {
+            for &i in &nearby {
+                if copper[i].shape.distance_to(hole.position) <= radius {
+                    own.insert(i);
+                    if let Some(net) = &copper[i].net {
+                        own_nets.insert(net.as_str());
+                    }
+                }
+            }
+        }
+
+        let closest = nearby
+            .iter()
+            .filter(|i| !own.contains(*i))
+            .filter(|i| copper[**i].net.as_deref().is_none_or(|net| !own_nets.contains(net)))
+            .map(|i| (copper[*i].shape.distance_to(hole.position) - radius).max(0.0))
+            .fold(f64::INFINITY, f64::min);
+
+        if closest < min_clearance {
+            violations.push(DrcViolation {
+                rule_name: RULE_DRILL_TO_COPPER.to_string(),
+                description: format!("{} copper {:.3}mm from hole edge", drill_layer_name(hole), closest),
+                layer: layer_type.display_name(),
+                measured_value: closest as f32,
+                required_value: min_clearance as f32,
+                x: hole.position.x as f32,
+                y: hole.position.y as f32,
+            });
+        }
+    }
+    violations
+}
+
+/// Copper left around each plated hole by the flashed pad it sits in
+/// Measured on the pad's narrow axis; holes whose pad is not a flash (e.g. a
+/// region) are not checked on that layer
+pub fn check_annular_ring(
+    holes: &[DrillHole],
+    layer_type: LayerType,
+    primitives: &[TaggedPrimitive],
+    min_ring: f64,
+) -> Vec<DrcViolation> {
+    let pads: Vec<&TaggedPrimitive> = primitives.iter()
+        .filter(|p| p.polarity == Polarity::Dark)
+        .filter(|p| matches!(p.shape, PrimitiveShape::Flash { .. }))
+        .collect();
+    let index = PrimitiveIndex::new(&pads);
+
+    let mut violations = Vec::new();
+    for hole in holes.iter().filter(|hole| hole.plated) {
+        let radius = hole.diameter / 2.0;
+        // The widest ring among pads covering the hole center is the land
+        let ring = index.query(hole.position, 0.0).into_iter()
+            .filter_map(|i| match pads[i].shape {
+                PrimitiveShape::Flash { center, width, height, .. } => {
+                    let offset = (hole.position - center).norm();
+                    let half = width.min(height) / 2.0;
+                    (offset < half).then(|| half - offset - radius)
+                }
+                _ => None,
+            })
+            .fold(None, |best: Option<f64>, ring| Some(best.map_or(ring, |best| best.max(ring))));
+
+        if let Some(ring) = ring.filter(|ring| *ring < min_ring) {
+            violations.push(DrcViolation {
+                rule_name: RULE_ANNULAR_RING.to_string(),
+                description: format!("Annular ring {:.3}mm around {:.3}mm hole", ring.max(0.0), hole.diameter),
+                layer: layer_type.display_name(),
+                measured_value: ring.max(0.0) as f32,
+                required_value: min_ring as f32,
+                x: hole.position.x as f32,
+                y: hole.position.y as f32,
+            });
+        }
+    }
+    violations
+}
+
+/// Uniform grid over primitive bounds so each hole only visits nearby copper
+/// (also used by the edge clearance check to find nearby outline edges)
+pub(super) struct PrimitiveIndex {
+    cells: HashMap<(i64, i64), Vec<usize>>,
+    /// Primitives too large to index (e.g. pours); always candidates
+    large: Vec<usize>,
+}
+
+impl PrimitiveIndex {
+    pub(super) fn new(copper: &[&TaggedPrimitive]) -> Self {
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        let mut large = Vec::new();
+        for (i, primitive) in copper.iter().enumerate() {
+            let (min, max) = primitive.shape.bounds();
+            let (x0, y0) = Self::cell(min.x, min.y);
+            let (x1, y1) = Self::cell(max.x, max.y);
+            if (x1 - x0 + 1) * (y1 - y0 + 1) > MAX_INDEXED_CELLS {
+                large.push(i);
+                continue;
+            }
+            for cx in x0..=x1 {
+                for cy in y0..=y1 {
+                    cells.entry((cx, cy)).or_default().push(i);
+                }
+            }
+        }
+        Self { cells, large }
+    }
+
+    fn cell(x: f64, y: f64) -> (i64, i64) {
+        ((x / COPPER_GRID_CELL_MM).floor() as i64, (y / COPPER_GRID_CELL_MM).floor() as i64)
+    }
+
+    /// Indices of primitives whose cells intersect the square of `reach` around `center`
+    pub(super) fn query(&self, center: nalgebra::Point2<f64>, reach: f64) -> Vec<usize> {
+        let (x0, y0) = Self::cell(center.x - reach, center.y - reach);
+        let (x1, y1) = Self::cell(center.x + reach, center.y + reach);
+        let mut found: Vec<usize> = self.large.clone();
+        for cx in x0..=x1 {
+            for cy in y0..=y1 {
+                if let Some(indices) = self.cells.get(&(cx, cy)) {
+                    found.extend(indices);
+                }
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{ApertureShape, PrimitiveShape};
+    use nalgebra::Point2;
+
+    fn hole(x: f64, y: f64, diameter: f64) -> DrillHole {
+        DrillHole { position: Point2::new(x, y), diameter, plated: true }
+    }
+
+    fn pad(x: f64, y: f64, diameter: f64, net: &str) -> TaggedPrimitive {
+        TaggedPrimitive {
+            net: Some(net.to_string()),
+            polarity: Polarity::Dark,
+            shape: PrimitiveShape::Flash {
+                center: Point2::new(x, y),
+                width: diameter,
+                height: diameter,
+                aperture: ApertureShape::Circle,
+                outline: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_drill_size_and_spacing() {
+        let holes = [hole(0.0, 0.0, 0.3), hole(0.4, 0.0, 0.15), hole(5.0, 0.0, 0.3)];
+
+        let small = check_drill_diameter(&holes, 0.2);
+        assert_eq!(small.len(), 1);
+        assert_eq!(small[0].rule_name, RULE_MIN_DRILL_DIAMETER);
+        assert_eq!((small[0].x, small[0].y), (0.4, 0.0));
+
+        let spacing = check_drill_spacing(&holes, 0.5);
+        assert_eq!(spacing.len(), 1);
+        assert!((spacing[0].measured_value - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_drill_to_copper_ignores_own_pad_and_net() {
+        let holes = [hole(0.0, 0.0, 0.4)];
+        let own_pad = pad(0.0, 0.0, 0.8, "GND");
+        let own_trace = TaggedPrimitive {
+            net: Some("GND".to_string()),
+            polarity: Polarity::Dark,
+            shape: PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(0.0, 0.5), width: 0.2 },
+        };
+        // Edge of the foreign pad sits 0.1mm from the hole edge
+        let foreign_pad = pad(0.5, 0.0, 0.4, "VCC");
+
+        let clear = check_drill_to_copper(&holes, LayerType::Copper(1), &[own_pad.clone(), own_trace.clone()], 0.2);
+        assert!(clear.is_empty());
+
+        let violations = check_drill_to_copper(&holes, LayerType::Copper(1), &[own_pad, own_trace, foreign_pad], 0.2);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, RULE_DRILL_TO_COPPER);
+        assert!((violations[0].measured_value - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_annular_ring_on_plated_holes_only() {
+        let pads = [pad(0.0, 0.0, 0.6, "GND"), pad(5.0, 0.0, 1.0, "VCC")];
+        let npth = DrillHole { plated: false, ..hole(0.0, 0.0, 0.4) };
+
+        // 0.1mm ring on the first pad, 0.3mm on the second
+        let holes = [hole(0.0, 0.0, 0.4), hole(5.0, 0.0, 0.4), npth];
+        let violations = check_annular_ring(&holes, LayerType::Copper(1), &pads, 0.15);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, RULE_ANNULAR_RING);
+        assert_eq!((violations[0].x, violations[0].y), (0.0, 0.0));
+        assert!((violations[0].measured_value - 0.1).abs() < 1e-6);
+
+        let checks = DrcCheckConfig { check_annular: false, ..DrcCheckConfig::default() };
+        let rules = DrcRules { min_annular_ring: 0.15, ..DrcRules::default() };
+        let all = run_drill_drc_check(&holes, &[(LayerType::Copper(1), pads.to_vec())], &rules, &checks);
+        assert!(all.iter().all(|violation| violation.rule_name != RULE_ANNULAR_RING));
+    }
+}
@@ -0,0 +1,267 @@
+//! Board edge clearance: copper too close to the routed outline
+//!
+//! Each copper primitive is broken into strokes (a centerline with a half
+//! width: traces, round and obround flashes, the circles of macro pads) or
+//! zero-width edges (rectangle flashes, regions, the polygons of macro and
+//! polygon pads), and the outline into straight edges indexed on a grid,
+//! so a primitive is only measured against the outline nearby. Primitives
+//! whose center lies outside the outline or on it, i.e. with half or more of
+//! them past the edge, are taken as intentional (castellated pads, edge
+//! fingers) and skipped; others can be ignored one by one in the DRC list.
+
+use nalgebra::{Point2, Vector2};
+
+use super::drill::PrimitiveIndex;
+use super::types::DrcViolation;
+use crate::ecs::{ApertureShape, LayerType, MacroShape, Polarity, PrimitiveShape, TaggedPrimitive};
+
+pub const RULE_EDGE_CLEARANCE: &str = "Board Edge Clearance";
+
+/// Centers closer than this to the outline count as on the edge (mm)
+const ON_EDGE_TOLERANCE_MM: f64 = 0.01;
+
+/// Centerline and half width of a piece of copper
+struct Stroke {
+    start: Point2<f64>,
+    end: Point2<f64>,
+    half_width: f64,
+}
+
+impl Stroke {
+    fn edge(start: Point2<f64>, end: Point2<f64>) -> Self {
+        Self { start, end, half_width: 0.0 }
+    }
+}
+
+/// Strokes covering the outer extent of a primitive
+fn strokes(shape: &PrimitiveShape) -> Vec<Stroke> {
+    let polygon = |points: &[Point2<f64>]| -> Vec<Stroke> {
+        (0..points.len()).map(|i| Stroke::edge(points[i], points[(i + 1) % points.len()])).collect()
+    };
+    match shape {
+        PrimitiveShape::Segment { start, end, width } => vec![Stroke { start: *start, end: *end, half_width: width / 2.0 }],
+        // Cut-outs never reach past the exposed primitives, so only those are measured
+        PrimitiveShape::Flash { center, outline: Some(outline), .. } => outline.shapes.iter()
+            .filter(|shape| shape.is_dark())
+            .flat_map(|shape| match shape {
+                MacroShape::Circle { center: offset, diameter, .. } => {
+                    let at = center + offset.coords;
+                    vec![Stroke { start: at, end: at, half_width: diameter / 2.0 }]
+                }
+                MacroShape::Polygon { points, .. } => polygon(&points.iter().map(|point| center + point.coords).collect::<Vec<_>>()),
+            })
+            .collect(),
+        PrimitiveShape::Flash { center, width, height, aperture, .. } => match aperture {
+            ApertureShape::Circle | ApertureShape::Polygon | ApertureShape::Obround => {
+                let radius = width.min(*height) / 2.0;
+                let axis = if width >= height { Vector2::new(width / 2.0 - radius, 0.0) } else { Vector2::new(0.0, height / 2.0 - radius) };
+                vec![Stroke { start: center - axis, end: center + axis, half_width: radius }]
+            }
+            ApertureShape::Rectangle | ApertureShape::Other => {
+                let (w, h) = (width / 2.0, height / 2.0);
+                polygon(&[
+                    Point2::new(center.x - w, center.y - h),
+                    Point2::new(center.x + w, center.y - h),
+                    Point2::new(center.x + w, center.y + h),
+                    Point2::new(center.x - w, center.y + h),
+                ])
+            }
+        },
+        PrimitiveShape::Region { points } => polygon(points),
+    }
+}
+
+/// Straight edges of the outline as zero-width segments, for the index
+fn outline_edges(outline: &[TaggedPrimitive]) -> Vec<TaggedPrimitive> {
+    let edge = |start: Point2<f64>, end: Point2<f64>| TaggedPrimitive {
+        net: None,
+        polarity: Polarity::Dark,
+        shape: PrimitiveShape::Segment { start, end, width: 0.0 },
+    };
+    let mut edges = Vec::new();
+    for primitive in outline {
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, .. } => edges.push(edge(*start, *end)),
+            PrimitiveShape::Region { points } => {
+                edges.extend((0..points.len()).map(|i| edge(points[i], points[(i + 1) % points.len()])));
+            }
+            PrimitiveShape::Flash { .. } => {}
+        }
+    }
+    edges
+}
+
+fn segment_ends(primitive: &TaggedPrimitive) -> (Point2<f64>, Point2<f64>) {
+    match primitive.shape {
+        PrimitiveShape::Segment { start, end, .. } => (start, end),
+        _ => unreachable!("outline edges are segments"),
+    }
+}
+
+/// Point of segment `a`-`b` closest to `point`
+fn closest_on_segment(point: Point2<f64>, a: Point2<f64>, b: Point2<f64>) -> Point2<f64> {
+    let segment = b - a;
+    let length_sq = segment.norm_squared();
+    let t = if length_sq > 0.0 { ((point - a).dot(&segment) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+    a + segment * t
+}
+
+/// Distance between segments `a` and `b`, with the closest point on `b`
+fn segment_distance(a0: Point2<f64>, a1: Point2<f64>, b0: Point2<f64>, b1: Point2<f64>) -> (f64, Point2<f64>) {
+    let cross = |u: Vector2<f64>, v: Vector2<f64>| u.x * v.y - u.y * v.x;
+    let (da, db) = (a1 - a0, b1 - b0);
+    let denominator = cross(da, db);
+    if denominator.abs() > f64::EPSILON {
+        let t = cross(b0 - a0, db) / denominator;
+        let u = cross(b0 - a0, da) / denominator;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            return (0.0, b0 + db * u);
+        }
+    }
+    [
+        (a0, closest_on_segment(a0, b0, b1)),
+        (a1, closest_on_segment(a1, b0, b1)),
+        (closest_on_segment(b0, a0, a1), b0),
+        (closest_on_segment(b1, a0, a1), b1),
+    ]
+    .into_iter()
+    .map(|(on_a, on_b)| ((on_a - on_b).norm(), on_b))
+    .min_by(|x, y| x.0.total_cmp(&y.0))
+    .expect("four candidates")
+}
+
+/// Even-odd test against the outline edges, so cutouts count as outside
+fn inside_outline(point: Point2<f64>, edges: &[TaggedPrimitive]) -> bool {
+    let mut inside = false;
+    for edge in edges {
+        let (a, b) = segment_ends(edge);
+        if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Copper of one layer closer to the board outline than `min_clearance`
+/// One violation per primitive, located at the closest point of the outline
+pub fn check_edge_clearance(
+    layer_type: LayerType,
+    copper: &[TaggedPrimitive],
+    outline: &[TaggedPrimitive],
+    min_clearance: f64,
+) -> Vec<DrcViolation> {
+    let edges = outline_edges(outline);
+    if edges.is_empty() {
+        return Vec::new();
+    }
+    let edge_refs: Vec<&TaggedPrimitive> = edges.iter().collect();
+    let index = PrimitiveIndex::new(&edge_refs);
+
+    let mut violations = Vec::new();
+    for primitive in copper.iter().filter(|p| p.polarity == Polarity::Dark) {
+        let mut closest: Option<(f64, Point2<f64>)> = None;
+        for stroke in strokes(&primitive.shape) {
+            let middle = nalgebra::center(&stroke.start, &stroke.end);
+            let reach = (stroke.end - stroke.start).norm() / 2.0 + stroke.half_width + min_clearance;
+            for i in index.query(middle, reach) {
+                let (b0, b1) = segment_ends(&edges[i]);
+                let (distance, at) = segment_distance(stroke.start, stroke.end, b0, b1);
+                let clearance = (distance - stroke.half_width).max(0.0);
+                if closest.is_none_or(|(best, _)| clearance < best) {
+                    closest = Some((clearance, at));
+                }
+            }
+        }
+        let Some((clearance, at)) = closest.filter(|(clearance, _)| *clearance < min_clearance) else {
+            continue;
+        };
+
+        // Half or more past the edge: meant to cross it
+        let (min, max) = primitive.shape.bounds();
+        let center = nalgebra::center(&min, &max);
+        let center_on_edge = edges.iter()
+            .map(|edge| {
+                let (a, b) = segment_ends(edge);
+                (center - closest_on_segment(center, a, b)).norm()
+            })
+            .any(|distance| distance <= ON_EDGE_TOLERANCE_MM);
+        if center_on_edge || !inside_outline(center, &edges) {
+            continue;
+        }
+
+        violations.push(DrcViolation {
+            rule_name: RULE_EDGE_CLEARANCE.to_string(),
+            description: format!("Copper {:.3}mm from the board edge", clearance),
+            layer: layer_type.display_name(),
+            measured_value: clearance as f32,
+            required_value: min_clearance as f32,
+            x: at.x as f32,
+            y: at.y as f32,
+        });
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drc_operations::test_primitives::{flash, primitive};
+
+    #[test]
+    fn test_edge_clearance_skips_copper_crossing_the_edge() {
+        let corners = [(0.0, 0.0), (20.0, 0.0), (20.0, 10.0), (0.0, 10.0), (0.0, 0.0)];
+        let outline: Vec<TaggedPrimitive> = corners.windows(2)
+            .map(|pair| primitive(PrimitiveShape::Segment { start: Point2::new(pair[0].0, pair[0].1), end: Point2::new(pair[1].0, pair[1].1), width: 0.1 }))
+            .collect();
+        let copper = [
+            // Trace 0.2mm from the bottom edge
+            primitive(PrimitiveShape::Segment { start: Point2::new(5.0, 0.3), end: Point2::new(8.0, 0.3), width: 0.2 }),
+            // Rectangle pad 0.1mm from the right edge
+            flash(19.4, 5.0, 1.0, 1.0, ApertureShape::Rectangle),
+            // Castellated pad centered on the left edge, and one mostly outside
+            flash(0.0, 5.0, 1.0, 1.0, ApertureShape::Circle),
+            flash(20.3, 8.0, 1.0, 1.0, ApertureShape::Circle),
+            // Well inside
+            flash(10.0, 5.0, 1.0, 1.0, ApertureShape::Circle),
+        ];
+
+        let violations = check_edge_clearance(LayerType::Copper(1), &copper, &outline, 0.3);
+        assert_eq!(violations.len(), 2);
+        assert!((violations[0].measured_value - 0.2).abs() < 1e-6);
+        assert_eq!((violations[0].x, violations[0].y), (5.0, 0.0));
+        assert!((violations[1].measured_value - 0.1).abs() < 1e-6);
+        assert!((violations[1].x - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_edge_clearance_measures_macro_pads_against_a_region_outline() {
+        // Board drawn as one region with a notch cut into the top edge
+        let outline = [primitive(PrimitiveShape::Region {
+            points: [(0.0, 0.0), (20.0, 0.0), (20.0, 10.0), (12.0, 10.0), (12.0, 6.0), (8.0, 6.0), (8.0, 10.0), (0.0, 10.0)]
+                .map(|(x, y)| Point2::new(x, y))
+                .to_vec(),
+        })];
+        // Bar with a round end sticking out to the right: reaches 0.8 mm right of its flash point
+        let body = vec!["21,1,1.0,0.6,0,0,0".to_string(), "1,1,0.6,0.5,0".to_string()];
+        let pad = |x: f64, y: f64| {
+            let outline = crate::ecs::ApertureOutline::from_macro("Bar", &body, "", 1.0).unwrap();
+            let (width, height) = outline.centered_size();
+            primitive(PrimitiveShape::Flash {
+                center: Point2::new(x, y),
+                width,
+                height,
+                aperture: ApertureShape::Other,
+                outline: Some(std::sync::Arc::new(outline)),
+            })
+        };
+        // Round end 0.2 mm from the right edge; one well inside; one under the notch, 0.1 mm below it
+        let copper = [pad(19.0, 2.0), pad(4.0, 4.0), pad(10.0, 5.6)];
+
+        let violations = check_edge_clearance(LayerType::Copper(1), &copper, &outline, 0.3);
+        assert_eq!(violations.len(), 2);
+        assert!((violations[0].measured_value - 0.2).abs() < 1e-6);
+        assert!((violations[0].x - 20.0).abs() < 1e-6 && (violations[0].y - 2.0).abs() < 1e-6);
+        assert!((violations[1].measured_value - 0.1).abs() < 1e-6);
+        assert!((violations[1].y - 6.0).abs() < 1e-6);
+    }
+}
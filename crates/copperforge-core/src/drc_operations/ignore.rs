@@ -0,0 +1,107 @@
+//! User-suppressed DRC violations
+//!
+//! Violations are matched by a hash of rule, layer and position rounded to
+//! `IGNORE_POSITION_GRID_MM`, so an ignored item stays ignored when DRC is re-run
+//! on the same design. The hash is FNV-1a rather than `DefaultHasher`, whose output
+//! is not guaranteed to stay the same between Rust releases.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::types::DrcViolation;
+
+/// Positions are rounded to this grid (mm) before hashing
+pub const IGNORE_POSITION_GRID_MM: f32 = 0.01;
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Stable key of a violation, as 16 hex digits
+pub fn violation_key(violation: &DrcViolation) -> String {
+    let x = (violation.x / IGNORE_POSITION_GRID_MM).round() as i64;
+    let y = (violation.y / IGNORE_POSITION_GRID_MM).round() as i64;
+    let text = format!("{}\u{1f}{}\u{1f}{}\u{1f}{}", violation.rule_name, violation.layer, x, y);
+    format!("{:016x}", fnv1a(text.as_bytes()))
+}
+
+/// Why a violation was ignored, with its message from the run it was ignored in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IgnoredViolation {
+    pub reason: String,
+    pub message: String,
+}
+
+/// Ignored violations keyed by `violation_key`, remembered in the project config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DrcIgnoreList {
+    entries: BTreeMap<String, IgnoredViolation>,
+}
+
+impl DrcIgnoreList {
+    pub fn get(&self, violation: &DrcViolation) -> Option<&IgnoredViolation> {
+        self.entries.get(&violation_key(violation))
+    }
+
+    pub fn is_ignored(&self, violation: &DrcViolation) -> bool {
+        self.get(violation).is_some()
+    }
+
+    pub fn ignore(&mut self, violation: &DrcViolation, reason: &str) {
+        self.entries.insert(violation_key(violation), IgnoredViolation {
+            reason: reason.trim().to_string(),
+            message: violation.format_message(),
+        });
+    }
+
+    /// Stop ignoring; returns false if the key was not in the list
+    pub fn restore(&mut self, key: &str) -> bool {
+        self.entries.remove(key).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries, including ones the current results no longer contain
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &IgnoredViolation)> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(x: f32, y: f32) -> DrcViolation {
+        DrcViolation {
+            rule_name: "Min Spacing".to_string(),
+            description: "Copper too close".to_string(),
+            layer: "Top Copper".to_string(),
+            measured_value: 0.1,
+            required_value: 0.15,
+            x,
+            y,
+        }
+    }
+
+    #[test]
+    fn test_ignore_survives_rerun_within_rounding() {
+        let mut ignores = DrcIgnoreList::default();
+        ignores.ignore(&violation(10.0, 5.0), " fab approved ");
+
+        // Same spot from a fresh run, off by float noise
+        let rerun = violation(10.0001, 4.9999);
+        assert_eq!(ignores.get(&rerun).map(|i| i.reason.as_str()), Some("fab approved"));
+        assert!(!ignores.is_ignored(&violation(10.5, 5.0)));
+
+        let key = violation_key(&rerun);
+        assert_eq!(key.len(), 16);
+        assert!(ignores.restore(&key) && ignores.is_empty());
+    }
+}
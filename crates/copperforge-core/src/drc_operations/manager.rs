@@ -1,6 +1,41 @@
 use serde::{Deserialize, Serialize};
-use super::types::{DrcRules, DrcViolation, TraceQualityIssue, CornerOverlayShape};
+use std::collections::HashMap;
+use super::types::{DrcCheckConfig, DrcRules, DrcViolation, TraceQualityIssue, CornerOverlayShape};
 use super::types::GerberPrimitive;
+use crate::ecs::LayerType;
+
+/// Cached DRC results for a single layer
+#[derive(Debug, Clone)]
+pub struct LayerDrcCache {
+    /// Content hash of the layer when the results were computed
+    pub content_hash: u64,
+    
+    /// Violations found on this layer
+    pub violations: Vec<DrcViolation>,
+    
+    /// Trace quality issues found on this layer
+    pub trace_quality_issues: Vec<TraceQualityIssue>,
+}
+
+/// Layers to re-check (snapshotted) and layers served from the cache
+#[derive(Debug, Clone)]
+pub struct DrcPlan {
+    pub snapshot: super::worker::DrcSnapshot,
+    pub cached_layers: Vec<LayerType>,
+}
+
+/// Summary of an incremental DRC run
+#[derive(Debug, Clone, Default)]
+pub struct DrcRunSummary {
+    /// Layers whose checks were re-run
+    pub rechecked_layers: Vec<LayerType>,
+    
+    /// Layers whose cached results were reused
+    pub cached_layers: Vec<LayerType>,
+    
+    /// True if the run aborted because no mechanical outline is loaded
+    pub missing_outline: bool,
+}
 
 /// Manager for all DRC (Design Rule Check) related functionality
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +46,14 @@ pub struct DrcManager {
     /// DRC rules configuration
     pub rules: DrcRules,
     
+    /// Checks to run; disabled checks are skipped (stored with the project)
+    #[serde(default)]
+    pub checks: DrcCheckConfig,
+    
+    /// Project the checks were loaded from / are saved to
+    #[serde(skip)]
+    pub checks_project: Option<String>,
+    
     /// List of detected DRC violations
     pub violations: Vec<DrcViolation>,
     
@@ -24,6 +67,34 @@ pub struct DrcManager {
     /// Corner overlay shapes for visualization
     #[serde(skip)] // Skip serialization as CornerOverlayShape contains non-serializable Position
     pub corner_overlay_shapes: Vec<CornerOverlayShape>,
+    
+    /// Per-layer DRC results keyed by layer, reused while the layer content is unchanged
+    #[serde(skip)]
+    pub layer_cache: HashMap<LayerType, LayerDrcCache>,
+    
+    /// Rules, enabled checks and outline hash the cache was computed with; any change invalidates it
+    #[serde(skip)]
+    cache_context: Option<(DrcRules, DrcCheckConfig, u64)>,
+    
+    /// Results of the drill checks, recomputed on every run and merged into `violations`
+    #[serde(skip)]
+    pub drill_violations: Vec<DrcViolation>,
+    
+    /// Soldermask sliver/registration warnings, kept apart from the hard violations
+    #[serde(skip)]
+    pub mask_warnings: Vec<DrcViolation>,
+    
+    /// True once a run has completed and its results not been cleared since
+    #[serde(skip)]
+    pub has_results: bool,
+    
+    /// Violations the user chose to ignore (stored in the project config)
+    #[serde(skip)]
+    pub ignored: super::ignore::DrcIgnoreList,
+    
+    /// Region of the last run when it was regional; None after a full run or once cleared
+    #[serde(skip)]
+    pub region: Option<super::region::DrcRegion>,
 }
 
 impl DrcManager {
@@ -32,19 +103,32 @@ impl DrcManager {
         Self {
             current_ruleset: None,
             rules: DrcRules::default(),
+            checks: DrcCheckConfig::default(),
+            checks_project: None,
             violations: Vec::new(),
             trace_quality_issues: Vec::new(),
             rounded_corner_primitives: Vec::new(),
             corner_overlay_shapes: Vec::new(),
+            layer_cache: HashMap::new(),
+            cache_context: None,
+            drill_violations: Vec::new(),
+            mask_warnings: Vec::new(),
+            has_results: false,
+            ignored: super::ignore::DrcIgnoreList::default(),
+            region: None,
         }
     }
     
     /// Clear all DRC violations and issues
     pub fn clear_violations(&mut self) {
         self.violations.clear();
+        self.drill_violations.clear();
+        self.mask_warnings.clear();
         self.trace_quality_issues.clear();
+        self.has_results = false;
         self.corner_overlay_shapes.clear();
         self.rounded_corner_primitives.clear();
+        self.region = None;
     }
     
     /// Add a new DRC violation
@@ -72,6 +156,11 @@ impl DrcManager {
         self.violations.len()
     }
     
+    /// Violations not on the ignore list, with their index in `violations`
+    pub fn active_violations(&self) -> impl Iterator<Item = (usize, &DrcViolation)> {
+        self.violations.iter().enumerate().filter(|(_, violation)| !self.ignored.is_ignored(violation))
+    }
+    
     /// Get the total number of trace quality issues
     pub fn trace_quality_issue_count(&self) -> usize {
         self.trace_quality_issues.len()
@@ -106,10 +195,212 @@ impl DrcManager {
     pub fn update_rules(&mut self, rules: DrcRules) {
         self.rules = rules;
     }
+    
+    /// Drop all cached per-layer results so the next run re-checks everything
+    pub fn invalidate_cache(&mut self) {
+        self.layer_cache.clear();
+        self.cache_context = None;
+    }
+    
+    /// Run DRC, re-checking only layers whose content hash changed since the last run
+    /// Cached results are reused for unchanged layers unless `force_full` is set
+    pub fn run_incremental_check(
+        &mut self,
+        layers: &HashMap<LayerType, super::types::LayerInfo>,
+        layer_hashes: &HashMap<LayerType, u64>,
+        force_full: bool,
+    ) -> DrcRunSummary {
+        let Some(plan) = self.prepare_incremental_check(layers, layer_hashes, force_full) else {
+            return DrcRunSummary {
+                missing_outline: true,
+                ..DrcRunSummary::default()
+            };
+        };
+        
+        let never_cancel = std::sync::atomic::AtomicBool::new(false);
+        let results = super::worker::run_drc_snapshot(&plan.snapshot, &never_cancel, |_, _| {}, |_| {})
+            .unwrap_or_default();
+        let rechecked_layers = results.iter().map(|result| result.layer_type).collect();
+        self.apply_layer_results(results);
+        
+        DrcRunSummary {
+            rechecked_layers,
+            cached_layers: plan.cached_layers,
+            missing_outline: false,
+        }
+    }
+    
+    /// Work out which layers need re-checking and snapshot them for a DRC run
+    /// Returns None (and clears all results) when no mechanical outline is loaded
+    pub fn prepare_incremental_check(
+        &mut self,
+        layers: &HashMap<LayerType, super::types::LayerInfo>,
+        layer_hashes: &HashMap<LayerType, u64>,
+        force_full: bool,
+    ) -> Option<DrcPlan> {
+        let Some(boundary) = super::types::get_pcb_boundary(layers) else {
+            self.violations.clear();
+            self.drill_violations.clear();
+            self.mask_warnings.clear();
+            self.trace_quality_issues.clear();
+            self.has_results = false;
+            self.invalidate_cache();
+            return None;
+        };
+        
+        // The outline, rules and enabled checks affect every layer's results
+        let outline_hash = layer_hashes.get(&LayerType::MechanicalOutline).copied().unwrap_or(0);
+        let context = (self.rules.clone(), self.checks, outline_hash);
+        if force_full || self.cache_context.as_ref() != Some(&context) {
+            self.invalidate_cache();
+        }
+        self.cache_context = Some(context);
+        
+        // Forget layers that are no longer loaded
+        self.layer_cache.retain(|layer_type, _| layers.contains_key(layer_type));
+        
+        let mut dirty_layers = Vec::new();
+        let mut cached_layers = Vec::new();
+        for (layer_type, layer_info) in layers {
+            if !super::types::has_layer_checks(layer_type) {
+                continue;
+            }
+            
+            let content_hash = layer_hashes.get(layer_type).copied().unwrap_or(0);
+            let cache_hit = self.layer_cache.get(layer_type)
+                .map(|cached| cached.content_hash == content_hash)
+                .unwrap_or(false);
+            
+            if cache_hit {
+                cached_layers.push(*layer_type);
+            } else {
+                dirty_layers.push((layer_info.clone(), content_hash));
+            }
+        }
+        
+        Some(DrcPlan {
+            snapshot: super::worker::DrcSnapshot {
+                layers: dirty_layers,
+                boundary,
+                rules: self.rules.clone(),
+                checks: self.checks,
+                outline: layers.get(&crate::ecs::LayerType::MechanicalOutline).and_then(|outline| outline.primitives.clone()),
+            },
+            cached_layers,
+        })
+    }
+    
+    /// Store freshly computed layer results in the cache and rebuild the violation lists
+    /// The combined list is de-duplicated and sorted by layer, then y, then x
+    pub fn apply_layer_results(&mut self, results: Vec<super::worker::LayerDrcResult>) {
+        for result in results {
+            self.layer_cache.insert(result.layer_type, LayerDrcCache {
+                content_hash: result.content_hash,
+                violations: result.violations,
+                trace_quality_issues: result.trace_quality_issues,
+            });
+        }
+        
+        self.rebuild_violations();
+        self.has_results = true;
+        self.region = None;
+    }
+    
+    /// Forget the results of layers a cancelled run did not get to; they were queued because
+    /// their cached results were outdated, and must not be listed next to the freshly checked layers
+    pub fn clear_layer_results(&mut self, layers: &[LayerType]) {
+        for layer_type in layers {
+            self.layer_cache.remove(layer_type);
+        }
+        self.rebuild_violations();
+        self.region = None;
+    }
+    
+    /// Combined violation list of every cached layer and the drill checks
+    fn rebuild_violations(&mut self) {
+        self.violations.clear();
+        self.trace_quality_issues.clear();
+        for cached in self.layer_cache.values() {
+            self.violations.extend(cached.violations.iter().cloned());
+            self.trace_quality_issues.extend(cached.trace_quality_issues.iter().cloned());
+        }
+        self.violations.extend(self.drill_violations.iter().cloned());
+        
+        // Layers come out of a HashMap: sort and merge repeats so runs list the same violations in the same order
+        self.violations = super::types::dedup_violations(std::mem::take(&mut self.violations), super::types::DUPLICATE_TOLERANCE_MM);
+    }
+    
+    /// Show the results of a regional run in place of the board results, cut to the
+    /// region's bounds in each layer's source coordinates
+    /// The per-layer cache is left alone, so the next full run still reuses it
+    pub fn apply_regional_results(
+        &mut self,
+        results: Vec<super::worker::LayerDrcResult>,
+        region: super::region::DrcRegion,
+        bounds: &HashMap<LayerType, (nalgebra::Point2<f64>, nalgebra::Point2<f64>)>,
+    ) {
+        self.violations.clear();
+        self.trace_quality_issues.clear();
+        for mut result in results {
+            if let Some((min, max)) = bounds.get(&result.layer_type) {
+                super::region::retain_in_bounds(&mut result, *min, *max);
+            }
+            self.violations.extend(result.violations);
+            self.trace_quality_issues.extend(result.trace_quality_issues);
+        }
+        self.violations.extend(self.drill_violations.iter().cloned());
+        self.violations = super::types::dedup_violations(std::mem::take(&mut self.violations), super::types::DUPLICATE_TOLERANCE_MM);
+        self.has_results = true;
+        self.region = Some(region);
+    }
+    
+    /// Report of the current results for CI, in mm whatever the display unit
+    pub fn build_report(&self, pcb_path: Option<&std::path::Path>) -> super::report::DrcReport {
+        super::report::DrcReport::new(pcb_path, self.current_ruleset.as_deref(), &self.rules, &self.violations, &self.mask_warnings)
+    }
+    
+    /// Write the current results to `path` as a JSON report
+    pub fn write_drc_report(&self, path: &std::path::Path, pcb_path: Option<&std::path::Path>) -> Result<(), String> {
+        super::report::write_drc_report(path, &self.build_report(pcb_path))
+    }
 }
 
 impl Default for DrcManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::worker::LayerDrcResult;
+
+    fn result(layer_type: LayerType, rule: &str) -> LayerDrcResult {
+        let violation = DrcViolation {
+            rule_name: rule.to_string(),
+            description: String::new(),
+            layer: layer_type.display_name(),
+            measured_value: 0.1,
+            required_value: 0.2,
+            x: 1.0,
+            y: 2.0,
+        };
+        LayerDrcResult { layer_type, content_hash: 1, violations: vec![violation], trace_quality_issues: Vec::new() }
+    }
+
+    #[test]
+    fn test_cancelled_run_drops_outdated_layers() {
+        let mut manager = DrcManager::new();
+        manager.apply_layer_results(vec![result(LayerType::TOP_COPPER, "Old"), result(LayerType::BOTTOM_COPPER, "Old")]);
+
+        // A new run gets through top copper before it is cancelled
+        manager.apply_layer_results(vec![result(LayerType::TOP_COPPER, "New")]);
+        manager.clear_layer_results(&[LayerType::BOTTOM_COPPER]);
+
+        let rules: Vec<&str> = manager.violations.iter().map(|violation| violation.rule_name.as_str()).collect();
+        assert_eq!(rules, ["New"]);
+        assert!(!manager.layer_cache.contains_key(&LayerType::BOTTOM_COPPER));
+        assert!(manager.has_results);
+    }
+}
@@ -0,0 +1,315 @@
+//! Soldermask checks: slivers between openings and mask-to-copper registration
+//!
+//! Results are warnings rather than hard violations: a thin sliver usually just
+//! flakes off (the fab removes it or gangs the openings), and a misregistered
+//! opening mostly points at stale or mismatched gerbers.
+
+use bevy_ecs::world::World;
+use nalgebra::{Point2, Vector2};
+
+use super::types::{DrcRules, DrcViolation};
+use crate::ecs::{ApertureShape, DrillHole, LayerType, Polarity, PrimitiveShape, Side, TaggedPrimitive};
+
+pub const RULE_MASK_SLIVER: &str = "Soldermask Sliver";
+pub const RULE_MASK_REGISTRATION: &str = "Soldermask Registration";
+
+/// Gaps smaller than this are treated as touching openings (merged, no sliver)
+const TOUCH_TOLERANCE_MM: f64 = 1e-4;
+
+/// True for warnings produced by the mask checks
+pub fn is_mask_rule(rule_name: &str) -> bool {
+    matches!(rule_name, RULE_MASK_SLIVER | RULE_MASK_REGISTRATION)
+}
+
+/// Run the enabled mask checks for one side
+/// `mask` is the soldermask layer of that side, `copper` the outer copper on the same side
+pub fn run_mask_checks(
+    mask_layer: LayerType,
+    mask: &[TaggedPrimitive],
+    copper: &[TaggedPrimitive],
+    holes: &[DrillHole],
+    rules: &DrcRules,
+) -> Vec<DrcViolation> {
+    let mut warnings = Vec::new();
+    if rules.mask_sliver_check {
+        warnings.extend(check_mask_slivers(mask_layer, mask, rules.min_mask_sliver as f64));
+    }
+    if rules.mask_registration_check {
+        warnings.extend(check_mask_registration(mask_layer, mask, copper, holes, rules.max_mask_expansion as f64));
+    }
+    warnings
+}
+
+/// Run the enabled mask checks on each loaded soldermask layer against the outer copper of its side
+/// Returns None when no soldermask layer is loaded
+pub fn check_loaded_masks(world: &mut World, rules: &DrcRules) -> Option<Vec<DrcViolation>> {
+    let holes = crate::ecs::get_drill_holes(world);
+    let bottom_copper = crate::ecs::get_loaded_layer_types(world)
+        .into_iter()
+        .filter_map(|layer_type| layer_type.copper_layer_number())
+        .max()
+        .filter(|n| *n > 1)
+        .map(LayerType::Copper);
+    
+    let mut warnings = None;
+    for (side, copper_layer) in [(Side::Top, Some(LayerType::Copper(1))), (Side::Bottom, bottom_copper)] {
+        let mask_layer = LayerType::Soldermask(side);
+        let mask = crate::ecs::get_layer_primitives(world, mask_layer);
+        if mask.is_empty() {
+            continue;
+        }
+        let copper = copper_layer
+            .map(|layer_type| crate::ecs::get_layer_primitives(world, layer_type))
+            .unwrap_or_default();
+        warnings.get_or_insert_with(Vec::new).extend(run_mask_checks(mask_layer, &mask, &copper, &holes, rules));
+    }
+    warnings
+}
+
+/// Report pairs of openings whose edge-to-edge gap leaves a strip of mask thinner than `min_sliver`
+pub fn check_mask_slivers(mask_layer: LayerType, mask: &[TaggedPrimitive], min_sliver: f64) -> Vec<DrcViolation> {
+    let mut openings: Vec<(Outline, (Point2<f64>, Point2<f64>))> = mask
+        .iter()
+        .filter(|p| p.polarity == Polarity::Dark)
+        .map(|p| (Outline::from_shape(&p.shape), p.shape.bounds()))
+        .collect();
+    openings.sort_by(|a, b| a.1.0.x.total_cmp(&b.1.0.x));
+
+    let mut warnings = Vec::new();
+    for (i, (outline, (min, max))) in openings.iter().enumerate() {
+        let candidates = openings[i + 1..].iter().take_while(|(_, (other_min, _))| other_min.x < max.x + min_sliver);
+        for (other, (other_min, other_max)) in candidates {
+            if other_min.y >= max.y + min_sliver || other_max.y + min_sliver <= min.y {
+                continue;
+            }
+            let (gap, midpoint) = outline.gap_to(other);
+            if gap > TOUCH_TOLERANCE_MM && gap < min_sliver {
+                warnings.push(DrcViolation {
+                    rule_name: RULE_MASK_SLIVER.to_string(),
+                    description: format!("Mask sliver {:.3}mm wide between openings", gap),
+                    layer: mask_layer.display_name(),
+                    measured_value: gap as f32,
+                    required_value: min_sliver as f32,
+                    x: midpoint.x as f32,
+                    y: midpoint.y as f32,
+                });
+            }
+        }
+    }
+    warnings
+}
+
+/// Report flashed openings that are not centered on a copper pad within `max_expansion`
+/// Openings over a non-plated hole are expected to have no copper and are skipped
+pub fn check_mask_registration(
+    mask_layer: LayerType,
+    mask: &[TaggedPrimitive],
+    copper: &[TaggedPrimitive],
+    holes: &[DrillHole],
+    max_expansion: f64,
+) -> Vec<DrcViolation> {
+    let pads: Vec<(Point2<f64>, (Point2<f64>, Point2<f64>))> = copper
+        .iter()
+        .filter(|p| p.polarity == Polarity::Dark)
+        .filter_map(|p| match p.shape {
+            PrimitiveShape::Flash { center, .. } => Some((center, p.shape.bounds())),
+            _ => None,
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    for opening in mask.iter().filter(|p| p.polarity == Polarity::Dark) {
+        let PrimitiveShape::Flash { center, .. } = opening.shape else {
+            continue;
+        };
+        let (min, max) = opening.shape.bounds();
+
+        // Nearest pad whose bounds overlap the opening
+        let offset = pads
+            .iter()
+            .filter(|(_, (pad_min, pad_max))| pad_min.x < max.x && pad_max.x > min.x && pad_min.y < max.y && pad_max.y > min.y)
+            .map(|(pad_center, _)| (pad_center - center).norm())
+            .min_by(f64::total_cmp);
+
+        let description = match offset {
+            Some(offset) if offset <= max_expansion => continue,
+            Some(offset) => format!("Mask opening offset {:.3}mm from its copper pad", offset),
+            None if holes.iter().any(|hole| !hole.plated && opening.shape.distance_to(hole.position) == 0.0) => continue,
+            None => "Mask opening over no copper pad (stale or misaligned gerbers?)".to_string(),
+        };
+        warnings.push(DrcViolation {
+            rule_name: RULE_MASK_REGISTRATION.to_string(),
+            description,
+            layer: mask_layer.display_name(),
+            measured_value: offset.unwrap_or(0.0) as f32,
+            required_value: max_expansion as f32,
+            x: center.x as f32,
+            y: center.y as f32,
+        });
+    }
+    warnings
+}
+
+/// An opening as a core point, segment or polygon grown by `radius`
+struct Outline {
+    core: Vec<Point2<f64>>,
+    radius: f64,
+}
+
+impl Outline {
+    /// Polygon apertures are treated as circles and macros as their bounding box
+    fn from_shape(shape: &PrimitiveShape) -> Self {
+        match shape {
+            PrimitiveShape::Segment { start, end, width } => Self { core: vec![*start, *end], radius: width / 2.0 },
+            PrimitiveShape::Flash { center, width, height, aperture, .. } => match aperture {
+                ApertureShape::Circle | ApertureShape::Polygon => Self { core: vec![*center], radius: width / 2.0 },
+                ApertureShape::Rectangle | ApertureShape::Other => {
+                    let (hw, hh) = (width / 2.0, height / 2.0);
+                    Self {
+                        core: vec![
+                            center + Vector2::new(-hw, -hh),
+                            center + Vector2::new(hw, -hh),
+                            center + Vector2::new(hw, hh),
+                            center + Vector2::new(-hw, hh),
+                        ],
+                        radius: 0.0,
+                    }
+                }
+                ApertureShape::Obround => {
+                    let radius = width.min(*height) / 2.0;
+                    let axis = if width >= height {
+                        Vector2::new(width / 2.0 - radius, 0.0)
+                    } else {
+                        Vector2::new(0.0, height / 2.0 - radius)
+                    };
+                    Self { core: vec![center - axis, center + axis], radius }
+                }
+            },
+            PrimitiveShape::Region { points } => Self { core: points.clone(), radius: 0.0 },
+        }
+    }
+
+    /// Edges of the core; a point is a zero-length edge and polygons are closed
+    fn edges(&self) -> Vec<(Point2<f64>, Point2<f64>)> {
+        match self.core.len() {
+            0 => Vec::new(),
+            1 => vec![(self.core[0], self.core[0])],
+            2 => vec![(self.core[0], self.core[1])],
+            n => (0..n).map(|i| (self.core[i], self.core[(i + 1) % n])).collect(),
+        }
+    }
+
+    fn contains(&self, point: Point2<f64>) -> bool {
+        self.core.len() >= 3
+            && PrimitiveShape::Region { points: self.core.clone() }.distance_to(point) == 0.0
+    }
+
+    /// Edge-to-edge gap to another outline and the midpoint of the gap
+    /// Overlapping outlines have a gap of 0
+    fn gap_to(&self, other: &Outline) -> (f64, Point2<f64>) {
+        let (Some(&first), Some(&other_first)) = (self.core.first(), other.core.first()) else {
+            return (f64::INFINITY, Point2::origin());
+        };
+        if self.contains(other_first) || other.contains(first) {
+            return (0.0, other_first);
+        }
+
+        // Candidate closest pairs of points between the two cores
+        let mut pairs = Vec::new();
+        for (a0, a1) in self.edges() {
+            for (b0, b1) in other.edges() {
+                if segments_cross(a0, a1, b0, b1) {
+                    return (0.0, a0);
+                }
+                pairs.extend([
+                    (a0, closest_on_segment(a0, b0, b1)),
+                    (a1, closest_on_segment(a1, b0, b1)),
+                    (closest_on_segment(b0, a0, a1), b0),
+                    (closest_on_segment(b1, a0, a1), b1),
+                ]);
+            }
+        }
+
+        let core_distance = pairs.iter().map(|(p, q)| (q - p).norm()).fold(f64::INFINITY, f64::min);
+        let gap = core_distance - self.radius - other.radius;
+        if gap <= 0.0 {
+            return (0.0, first);
+        }
+
+        // Parallel edges tie along their overlap; average the tied pairs to land mid-span
+        let tied: Vec<(Point2<f64>, Point2<f64>)> = pairs
+            .into_iter()
+            .filter(|(p, q)| (q - p).norm() - core_distance < TOUCH_TOLERANCE_MM)
+            .collect();
+        let mut midpoint = Vector2::zeros();
+        for (p, q) in &tied {
+            // Step out from each core by its radius to land on the opening edges
+            let direction = (q - p) / core_distance;
+            let edge_a = p + direction * self.radius;
+            let edge_b = q - direction * other.radius;
+            midpoint += nalgebra::center(&edge_a, &edge_b).coords;
+        }
+        (gap, Point2::from(midpoint / tied.len() as f64))
+    }
+}
+
+fn closest_on_segment(point: Point2<f64>, a: Point2<f64>, b: Point2<f64>) -> Point2<f64> {
+    let segment = b - a;
+    let length_sq = segment.norm_squared();
+    let t = if length_sq > 0.0 { ((point - a).dot(&segment) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+    a + segment * t
+}
+
+/// True if the segments properly intersect
+fn segments_cross(a0: Point2<f64>, a1: Point2<f64>, b0: Point2<f64>, b1: Point2<f64>) -> bool {
+    let orient = |p: Point2<f64>, q: Point2<f64>, r: Point2<f64>| (q - p).perp(&(r - p));
+    let (d1, d2) = (orient(b0, b1, a0), orient(b0, b1, a1));
+    let (d3, d4) = (orient(a0, a1, b0), orient(a0, a1, b1));
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drc_operations::test_primitives::flash;
+
+    #[test]
+    fn test_sliver_between_adjacent_pads() {
+        let layer = LayerType::Soldermask(crate::ecs::Side::Top);
+        // 0.55mm openings at 0.6mm pitch leave a 0.05mm strip of mask
+        let mask = [
+            flash(0.0, 0.0, 0.55, 1.5, ApertureShape::Rectangle),
+            flash(0.6, 0.0, 0.55, 1.5, ApertureShape::Rectangle),
+            flash(3.0, 0.0, 1.0, 1.0, ApertureShape::Circle),
+            // Touching openings merge into one and leave no sliver
+            flash(4.0, 0.0, 1.0, 1.0, ApertureShape::Circle),
+        ];
+
+        let slivers = check_mask_slivers(layer, &mask, 0.1);
+        assert_eq!(slivers.len(), 1);
+        assert!((slivers[0].measured_value - 0.05).abs() < 1e-6);
+        assert!((slivers[0].x - 0.3).abs() < 1e-6);
+        assert_eq!(slivers[0].y, 0.0);
+    }
+
+    #[test]
+    fn test_registration_flags_shifted_and_orphan_openings() {
+        let layer = LayerType::Soldermask(crate::ecs::Side::Top);
+        let copper = [
+            flash(0.0, 0.0, 1.0, 1.0, ApertureShape::Rectangle),
+            flash(5.0, 0.0, 1.0, 1.0, ApertureShape::Rectangle),
+        ];
+        let mask = [
+            flash(0.02, 0.0, 1.1, 1.1, ApertureShape::Rectangle),
+            flash(5.3, 0.0, 1.1, 1.1, ApertureShape::Rectangle),
+            flash(10.0, 0.0, 1.0, 1.0, ApertureShape::Circle),
+            flash(20.0, 0.0, 3.2, 3.2, ApertureShape::Circle),
+        ];
+        let npth = DrillHole { position: Point2::new(20.0, 0.0), diameter: 3.0, plated: false };
+
+        let warnings = check_mask_registration(layer, &mask, &copper, &[npth], 0.1);
+        assert_eq!(warnings.len(), 2);
+        assert!((warnings[0].measured_value - 0.3).abs() < 1e-6);
+        assert_eq!((warnings[1].x, warnings[1].y), (10.0, 0.0));
+    }
+}
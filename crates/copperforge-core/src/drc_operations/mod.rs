@@ -1,6 +1,25 @@
 pub mod types;
 pub mod manager;
+pub mod worker;
+pub mod drill;
+pub mod mask;
+pub mod report;
+pub mod assembly;
+pub mod ignore;
+pub mod profiles;
+pub mod thermal;
+pub mod tenting;
+pub mod region;
+pub mod silkscreen;
+pub mod edge;
+#[cfg(test)]
+mod test_primitives;
 
 // Re-export the main types for easy access
-pub use types::{TraceQualityType, DrcSimple, run_simple_drc_check};
-pub use manager::DrcManager;
\ No newline at end of file
+pub use types::{TraceQualityType, DrcSimple, DrcCheckConfig, LayerInfo, collect_drc_layers, run_simple_drc_check};
+pub use manager::{DrcManager, DrcPlan, DrcRunSummary, LayerDrcCache};
+pub use worker::{DrcJob, DrcJobOutcome, DrcSnapshot, LayerDrcResult};
+pub use report::{DrcReport, write_drc_report};
+pub use ignore::{DrcIgnoreList, IgnoredViolation, violation_key};
+pub use region::DrcRegion;
+pub use profiles::{DrcRuleProfile, builtin_profiles, is_builtin_profile, load_profiles, profiles_dir};
@@ -0,0 +1,181 @@
+//! Named DRC rule profiles shared as JSON files
+//!
+//! User profiles live as `<name>.json` in the `drc_profiles` directory under
+//! the config dir. Two built-in profiles ship with the app; they can be saved
+//! under a new name but never overwritten. Profiles written by older versions
+//! lack the thresholds of newer checks: those fall back to the defaults and
+//! are listed so the caller can warn. Per-layer rule overrides are part of the
+//! rules and are saved with them.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::DrcRules;
+
+/// Directory name for profile files under the config dir
+pub const PROFILES_DIR_NAME: &str = "drc_profiles";
+
+pub const JLC_STANDARD_PROFILE: &str = "JLC 1oz standard";
+pub const CONSERVATIVE_PROFILE: &str = "Conservative 8mil/8mil";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcRuleProfile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub rules: DrcRules,
+}
+
+/// Profiles that ship with the app, read-only
+pub fn builtin_profiles() -> Vec<DrcRuleProfile> {
+    vec![
+        DrcRuleProfile {
+            name: JLC_STANDARD_PROFILE.to_string(),
+            description: "JLCPCB 2-layer, 1oz copper: 6/6 mil trace/space, 0.3 mm vias".to_string(),
+            rules: DrcRules {
+                min_trace_width: 0.15,
+                min_via_diameter: 0.3,
+                min_drill_diameter: 0.2,
+                min_spacing: 0.15,
+                min_annular_ring: 0.1,
+                min_drill_spacing: 0.5,
+                min_drill_to_copper: 0.2,
+                ..DrcRules::default()
+            },
+        },
+        DrcRuleProfile {
+            name: CONSERVATIVE_PROFILE.to_string(),
+            description: "Margin over most fabs: 8/8 mil trace/space, 0.4 mm vias".to_string(),
+            rules: DrcRules {
+                min_trace_width: 0.2,
+                min_via_diameter: 0.4,
+                min_drill_diameter: 0.25,
+                min_spacing: 0.2,
+                min_annular_ring: 0.15,
+                min_drill_spacing: 0.6,
+                min_drill_to_copper: 0.25,
+                ..DrcRules::default()
+            },
+        },
+    ]
+}
+
+pub fn is_builtin_profile(name: &str) -> bool {
+    builtin_profiles().iter().any(|profile| profile.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Profiles directory under the application config dir
+pub fn profiles_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join(PROFILES_DIR_NAME)
+}
+
+/// Rule fields the JSON `rules` object does not set, i.e. filled from the defaults
+fn missing_rule_fields(rules: Option<&serde_json::Value>) -> Vec<String> {
+    let serde_json::Value::Object(defaults) = serde_json::to_value(DrcRules::default()).unwrap_or_default() else {
+        return Vec::new();
+    };
+    let present = rules.and_then(|rules| rules.as_object());
+    defaults.keys()
+        .filter(|key| !present.is_some_and(|present| present.contains_key(key.as_str())))
+        .cloned()
+        .collect()
+}
+
+/// Parse a profile, returning it with the rule fields that were missing
+pub fn parse_profile(json: &str) -> Result<(DrcRuleProfile, Vec<String>), String> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|e| format!("Invalid profile JSON: {}", e))?;
+    let missing = missing_rule_fields(value.get("rules"));
+    let profile: DrcRuleProfile = serde_json::from_value(value).map_err(|e| format!("Invalid rule profile: {}", e))?;
+    if profile.name.trim().is_empty() {
+        return Err("Rule profile has no name".to_string());
+    }
+    Ok((profile, missing))
+}
+
+pub fn load_profile_file(path: &Path) -> Result<(DrcRuleProfile, Vec<String>), String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_profile(&json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Write a profile to any path, e.g. for sharing
+pub fn export_profile(profile: &DrcRuleProfile, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(profile).map_err(|e| format!("Failed to serialize profile: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Store a user profile as `<name>.json` in `dir`; built-in names are refused
+pub fn save_profile(profile: &DrcRuleProfile, dir: &Path) -> Result<PathBuf, String> {
+    if is_builtin_profile(&profile.name) {
+        return Err(format!("\"{}\" is a built-in profile; save it under another name", profile.name.trim()));
+    }
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("{}.json", profile_file_stem(&profile.name)));
+    export_profile(profile, &path)?;
+    Ok(path)
+}
+
+/// Built-in profiles followed by the user profiles in `dir`, sorted by name
+/// Files that fail to parse, and profiles filled with defaults, are reported next to the list
+pub fn load_profiles(dir: &Path) -> (Vec<DrcRuleProfile>, Vec<String>) {
+    let mut user_profiles = Vec::new();
+    let mut problems = Vec::new();
+    let paths = std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    for path in paths.into_iter().filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json")) {
+        match load_profile_file(&path) {
+            Ok((profile, missing)) if !is_builtin_profile(&profile.name) => {
+                if !missing.is_empty() {
+                    problems.push(missing_fields_warning(&profile.name, &missing));
+                }
+                user_profiles.push(profile);
+            }
+            Ok(_) => {}
+            Err(e) => problems.push(e),
+        }
+    }
+    user_profiles.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    let mut profiles = builtin_profiles();
+    profiles.extend(user_profiles);
+    (profiles, problems)
+}
+
+/// Warning for a profile saved before some rule fields existed
+pub fn missing_fields_warning(name: &str, missing: &[String]) -> String {
+    format!("Rule profile \"{}\" predates {}; using the defaults", name, missing.join(", "))
+}
+
+/// File-system safe stem for a profile name
+fn profile_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if stem.is_empty() { "profile".to_string() } else { stem }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_profile_fills_defaults_and_builtins_are_read_only() {
+        let json = r#"{ "name": "Team 2L", "rules": { "min_trace_width": 0.127, "min_spacing": 0.127 } }"#;
+        let (profile, missing) = parse_profile(json).expect("older profile should load");
+        assert_eq!(profile.rules.min_trace_width, 0.127);
+        assert_eq!(profile.rules.min_component_clearance, DrcRules::default().min_component_clearance);
+        assert!(missing.contains(&"min_component_clearance".to_string()));
+        assert!(!missing.contains(&"min_spacing".to_string()));
+
+        let (round_trip, missing) = parse_profile(&serde_json::to_string(&profile).unwrap()).unwrap();
+        assert_eq!(round_trip, profile);
+        assert!(missing.is_empty());
+
+        let builtin = DrcRuleProfile { name: "jlc 1oz STANDARD ".to_string(), ..builtin_profiles()[0].clone() };
+        assert!(save_profile(&builtin, Path::new("/nonexistent")).is_err());
+    }
+}
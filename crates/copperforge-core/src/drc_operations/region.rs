@@ -0,0 +1,146 @@
+//! Regional DRC: checks limited to a rectangle drawn in the gerber view
+//!
+//! The rectangle is kept in view coordinates, i.e. as drawn on screen after the
+//! layers' rotation, mirroring and offsets. For each checked layer it is taken
+//! back into the layer's source coordinates, the primitives touching it are
+//! written to a temporary gerber and the normal layer checks run on that. A
+//! primitive that crosses the edge is checked whole, so the results are then
+//! cut to the region and nothing outside it is reported.
+
+use gerber_viewer::GerberTransform;
+use nalgebra::{Matrix3, Point2};
+
+use super::types::{DrcViolation, LayerInfo};
+use super::worker::LayerDrcResult;
+use crate::ecs::TaggedPrimitive;
+
+/// Rectangle of a regional DRC run, in view coordinates (mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrcRegion {
+    pub min: Point2<f64>,
+    pub max: Point2<f64>,
+}
+
+impl DrcRegion {
+    pub fn from_corners(a: Point2<f64>, b: Point2<f64>) -> Self {
+        Self {
+            min: Point2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Corners in view coordinates, counter-clockwise from the minimum
+    pub fn corners(&self) -> [Point2<f64>; 4] {
+        [self.min, Point2::new(self.max.x, self.min.y), self.max, Point2::new(self.min.x, self.max.y)]
+    }
+
+    /// Axis-aligned bounds of the region in a layer's source coordinates
+    /// `to_source` is the inverse of the layer's render transform; for rotations
+    /// other than quarter turns the bounds enclose the rotated rectangle
+    pub fn source_bounds(&self, to_source: &Matrix3<f64>) -> (Point2<f64>, Point2<f64>) {
+        let corners = self.corners().map(|corner| to_source.transform_point(&corner));
+        let min = corners.iter().fold(Point2::new(f64::MAX, f64::MAX), |min, p| Point2::new(min.x.min(p.x), min.y.min(p.y)));
+        let max = corners.iter().fold(Point2::new(f64::MIN, f64::MIN), |max, p| Point2::new(max.x.max(p.x), max.y.max(p.y)));
+        (min, max)
+    }
+}
+
+/// Primitives whose bounds overlap `min`..`max`
+pub fn primitives_in_region(primitives: &[TaggedPrimitive], min: Point2<f64>, max: Point2<f64>) -> Vec<TaggedPrimitive> {
+    primitives.iter()
+        .filter(|primitive| {
+            let (p_min, p_max) = primitive.shape.bounds();
+            p_min.x <= max.x && p_max.x >= min.x && p_min.y <= max.y && p_max.y >= min.y
+        })
+        .cloned()
+        .collect()
+}
+
+/// DRC snapshot of a layer holding only the primitives inside the source bounds
+/// None when nothing of the layer lies in the region
+pub fn regional_layer(layer: &LayerInfo, primitives: &[TaggedPrimitive], min: Point2<f64>, max: Point2<f64>) -> Option<LayerInfo> {
+    let inside = primitives_in_region(primitives, min, max);
+    if inside.is_empty() {
+        return None;
+    }
+    let identity = GerberTransform::from_matrix(&Matrix3::identity());
+    let gerber = crate::export::gerber::write_transformed_gerber(&inside, &identity, None);
+    let gerber_layer = crate::ecs::parse_gerber(&gerber.content).0?;
+    Some(LayerInfo {
+        gerber_layer: Some(std::sync::Arc::new(gerber_layer)),
+        raw_gerber_data: None,
+        primitives: Some(std::sync::Arc::new(inside)),
+        ..layer.clone()
+    })
+}
+
+/// Whether a violation location lies within source bounds
+pub fn violation_in_bounds(violation: &DrcViolation, min: Point2<f64>, max: Point2<f64>) -> bool {
+    location_in_bounds((violation.x, violation.y), min, max)
+}
+
+/// Drop the violations and trace quality issues of a layer's result that lie outside source bounds
+pub fn retain_in_bounds(result: &mut LayerDrcResult, min: Point2<f64>, max: Point2<f64>) {
+    result.violations.retain(|violation| violation_in_bounds(violation, min, max));
+    result.trace_quality_issues.retain(|issue| location_in_bounds(issue.location, min, max));
+}
+
+fn location_in_bounds((x, y): (f32, f32), min: Point2<f64>, max: Point2<f64>) -> bool {
+    let (x, y) = (x as f64, y as f64);
+    x >= min.x && x <= max.x && y >= min.y && y <= max.y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Polarity, PrimitiveShape};
+
+    fn segment(start: (f64, f64), end: (f64, f64)) -> TaggedPrimitive {
+        TaggedPrimitive {
+            net: None,
+            polarity: Polarity::Dark,
+            shape: PrimitiveShape::Segment { start: Point2::new(start.0, start.1), end: Point2::new(end.0, end.1), width: 0.2 },
+        }
+    }
+
+    #[test]
+    fn test_region_maps_through_rotation_and_filters_primitives() {
+        // Drawn over x 10..20, y 0..5 of a view rotated a quarter turn and mirrored in x
+        let region = DrcRegion::from_corners(Point2::new(20.0, 5.0), Point2::new(10.0, 0.0));
+        let to_view = Matrix3::new(0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0) * Matrix3::new(-1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0);
+        let (min, max) = region.source_bounds(&to_view.try_inverse().unwrap());
+        assert!((min - Point2::new(-5.0, -20.0)).norm() < 1e-9 && (max - Point2::new(0.0, -10.0)).norm() < 1e-9);
+
+        let primitives = vec![
+            segment((-2.0, -12.0), (-2.0, -18.0)),
+            // Crosses into the region from outside
+            segment((-10.0, -15.0), (-4.0, -15.0)),
+            segment((5.0, -12.0), (8.0, -12.0)),
+        ];
+        let inside = primitives_in_region(&primitives, min, max);
+        assert_eq!(inside, primitives[..2].to_vec());
+    }
+
+    #[test]
+    fn test_results_are_cut_to_the_region() {
+        let violation = |x: f32| DrcViolation {
+            rule_name: "Min Spacing".to_string(),
+            description: String::new(),
+            layer: "Top Copper".to_string(),
+            measured_value: 0.1,
+            required_value: 0.15,
+            x,
+            y: 1.0,
+        };
+        // One long trace crosses the region; only its violation inside is kept
+        let mut result = LayerDrcResult {
+            layer_type: crate::ecs::LayerType::Copper(1),
+            content_hash: 0,
+            violations: vec![violation(2.0), violation(40.0)],
+            trace_quality_issues: Vec::new(),
+        };
+        retain_in_bounds(&mut result, Point2::new(0.0, 0.0), Point2::new(5.0, 5.0));
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].x, 2.0);
+    }
+}
@@ -0,0 +1,175 @@
+//! Machine-readable DRC report for CI
+//!
+//! All lengths and coordinates are written in mm, whatever unit the panel
+//! displays. Groups and entries are sorted so identical runs produce
+//! identical files; bump `DRC_REPORT_SCHEMA_VERSION` on any layout change.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{DrcRules, DrcViolation};
+use crate::ecs::ProvenanceSummary;
+
+pub const DRC_REPORT_SCHEMA_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcReport {
+    pub schema_version: u32,
+    pub tool: String,
+    pub tool_version: String,
+    /// RFC 3339, UTC
+    pub generated_at: String,
+    pub pcb_path: Option<String>,
+    /// Generating software and plot time of each checked gerber
+    #[serde(default)]
+    pub provenance: ProvenanceSummary,
+    /// Unit of every length and coordinate in the report
+    pub units: String,
+    /// Name of the rule profile the rules came from, if any
+    pub rule_profile: Option<String>,
+    pub rules: DrcRules,
+    pub totals: DrcReportTotals,
+    pub violations: Vec<DrcReportGroup>,
+    /// Soldermask warnings, kept apart from the hard violations
+    pub warnings: Vec<DrcReportGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcReportTotals {
+    pub violations: usize,
+    pub warnings: usize,
+    /// Number of distinct rules with at least one violation
+    pub rules_violated: usize,
+}
+
+/// All results for one rule on one layer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcReportGroup {
+    pub rule_name: String,
+    pub layer: String,
+    pub count: usize,
+    pub items: Vec<DrcReportItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcReportItem {
+    pub description: String,
+    pub x: f32,
+    pub y: f32,
+    pub measured_value: f32,
+    pub required_value: f32,
+}
+
+impl DrcReport {
+    /// Build a report stamped with the current time
+    pub fn new(
+        pcb_path: Option<&Path>,
+        rule_profile: Option<&str>,
+        rules: &DrcRules,
+        violations: &[DrcViolation],
+        warnings: &[DrcViolation],
+    ) -> Self {
+        let violation_groups = group_violations(violations);
+        let mut rules_violated: Vec<&str> = violation_groups.iter().map(|group| group.rule_name.as_str()).collect();
+        rules_violated.dedup();
+
+        Self {
+            schema_version: DRC_REPORT_SCHEMA_VERSION,
+            tool: "CopperForge".to_string(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            pcb_path: pcb_path.map(|path| path.display().to_string()),
+            provenance: ProvenanceSummary::default(),
+            units: "mm".to_string(),
+            rule_profile: rule_profile.map(str::to_string),
+            rules: rules.clone(),
+            totals: DrcReportTotals {
+                violations: violations.len(),
+                warnings: warnings.len(),
+                rules_violated: rules_violated.len(),
+            },
+            violations: violation_groups,
+            warnings: group_violations(warnings),
+        }
+    }
+}
+
+/// Group by rule then layer, with items in position order
+fn group_violations(violations: &[DrcViolation]) -> Vec<DrcReportGroup> {
+    let mut sorted: Vec<&DrcViolation> = violations.iter().collect();
+    sorted.sort_by(|a, b| {
+        (&a.rule_name, &a.layer)
+            .cmp(&(&b.rule_name, &b.layer))
+            .then(a.x.total_cmp(&b.x))
+            .then(a.y.total_cmp(&b.y))
+    });
+
+    let mut groups: Vec<DrcReportGroup> = Vec::new();
+    for violation in sorted {
+        let item = DrcReportItem {
+            description: violation.description.clone(),
+            x: violation.x,
+            y: violation.y,
+            measured_value: violation.measured_value,
+            required_value: violation.required_value,
+        };
+        match groups.last_mut() {
+            Some(group) if group.rule_name == violation.rule_name && group.layer == violation.layer => {
+                group.items.push(item);
+                group.count += 1;
+            }
+            _ => groups.push(DrcReportGroup {
+                rule_name: violation.rule_name.clone(),
+                layer: violation.layer.clone(),
+                count: 1,
+                items: vec![item],
+            }),
+        }
+    }
+    groups
+}
+
+/// Write a report as pretty-printed JSON
+pub fn write_drc_report(path: &Path, report: &DrcReport) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize DRC report: {}", e))?;
+    std::fs::write(path, json + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(rule_name: &str, layer: &str, x: f32, measured_value: f32) -> DrcViolation {
+        DrcViolation {
+            rule_name: rule_name.to_string(),
+            description: format!("{} at {}", rule_name, x),
+            layer: layer.to_string(),
+            measured_value,
+            required_value: 0.15,
+            x,
+            y: 2.5,
+        }
+    }
+
+    #[test]
+    fn test_report_matches_golden() {
+        let violations = [
+            violation("Minimum Trace Width", "Top Copper", 12.5, 0.1),
+            violation("Minimum Spacing", "Top Copper", 3.0, 0.125),
+            violation("Minimum Trace Width", "Top Copper", 1.0, 0.125),
+            violation("Minimum Trace Width", "Bottom Copper", 4.0, 0.1),
+        ];
+        let warnings = [violation("Mask Sliver", "Top Soldermask", 7.0, 0.05)];
+        let mut report = DrcReport::new(Some(Path::new("boards/demo")), Some("JLC 1oz standard"), &DrcRules::default(), &violations, &warnings);
+        // Only the header fields that change between runs are pinned
+        report.tool_version = "0.0.0".to_string();
+        report.generated_at = "2024-01-01T00:00:00Z".to_string();
+
+        let json = serde_json::to_string(&report).expect("report serializes");
+        let actual: serde_json::Value = serde_json::from_str(&json).expect("report is valid JSON");
+        let golden: serde_json::Value = serde_json::from_str(include_str!("testdata/drc_report.golden.json"))
+            .expect("golden file is valid JSON");
+        assert_eq!(actual, golden);
+    }
+}
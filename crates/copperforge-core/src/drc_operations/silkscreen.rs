@@ -0,0 +1,279 @@
+//! Silkscreen checks: minimum line width and minimum text height
+//!
+//! Gerbers carry silkscreen text as plain strokes, so text is found by shape:
+//! short strokes sharing an endpoint form a glyph, and glyphs close to each
+//! other form a label. A label's height is its extent across the direction its
+//! glyphs run in (the longer side for a lone glyph). Strokes longer than
+//! `MAX_GLYPH_STROKE_MM` belong to outlines and are never taken for text.
+//! Thin strokes are reported once per connected group, not per segment, so a
+//! thin arc or glyph gives a single violation.
+
+use std::collections::HashMap;
+
+use nalgebra::Point2;
+
+use super::types::{DrcRules, DrcViolation};
+use crate::ecs::{LayerType, Polarity, PrimitiveShape, TaggedPrimitive};
+
+pub const RULE_SILK_LINE_WIDTH: &str = "Silkscreen Line Width";
+pub const RULE_SILK_TEXT_HEIGHT: &str = "Silkscreen Text Height";
+
+/// Endpoints closer than this join strokes into one group (mm)
+const JOIN_TOLERANCE_MM: f64 = 0.001;
+/// Longer strokes are outlines, not text (mm)
+const MAX_GLYPH_STROKE_MM: f64 = 3.0;
+/// Glyphs less than this share of their size apart belong to the same label
+const GLYPH_GAP_FACTOR: f64 = 0.6;
+
+struct Stroke {
+    start: Point2<f64>,
+    end: Point2<f64>,
+    width: f64,
+}
+
+/// Axis-aligned bounds of stroke centerlines
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min: Point2<f64>,
+    max: Point2<f64>,
+}
+
+impl Bounds {
+    fn of_stroke(stroke: &Stroke) -> Self {
+        Self {
+            min: Point2::new(stroke.start.x.min(stroke.end.x), stroke.start.y.min(stroke.end.y)),
+            max: Point2::new(stroke.start.x.max(stroke.end.x), stroke.start.y.max(stroke.end.y)),
+        }
+    }
+
+    fn union(&self, other: &Bounds) -> Self {
+        Self {
+            min: Point2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Point2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    fn size(&self) -> f64 {
+        self.width().max(self.height())
+    }
+
+    fn center(&self) -> Point2<f64> {
+        nalgebra::center(&self.min, &self.max)
+    }
+
+    /// Largest of the x and y gaps between the bounds, 0 when they overlap
+    fn gap_to(&self, other: &Bounds) -> f64 {
+        let dx = (other.min.x - self.max.x).max(self.min.x - other.max.x).max(0.0);
+        let dy = (other.min.y - self.max.y).max(self.min.y - other.max.y).max(0.0);
+        dx.max(dy)
+    }
+}
+
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find(parents, a), find(parents, b));
+    if a != b {
+        parents[b] = a;
+    }
+}
+
+/// Indices of the strokes grouped by shared endpoints
+fn connected_strokes(strokes: &[Stroke]) -> Vec<Vec<usize>> {
+    let mut parents: Vec<usize> = (0..strokes.len()).collect();
+    let key = |p: &Point2<f64>| ((p.x / JOIN_TOLERANCE_MM).round() as i64, (p.y / JOIN_TOLERANCE_MM).round() as i64);
+    let mut first_at: HashMap<(i64, i64), usize> = HashMap::new();
+    for (i, stroke) in strokes.iter().enumerate() {
+        for point in [&stroke.start, &stroke.end] {
+            let first = *first_at.entry(key(point)).or_insert(i);
+            union(&mut parents, first, i);
+        }
+    }
+    collect_groups(&mut parents)
+}
+
+fn collect_groups(parents: &mut [usize]) -> Vec<Vec<usize>> {
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..parents.len() {
+        let root = find(parents, i);
+        groups.entry(root).or_default().push(i);
+    }
+    let mut groups: Vec<Vec<usize>> = groups.into_values().collect();
+    groups.sort_by_key(|group| group[0]);
+    groups
+}
+
+/// Glyph bounds grouped into labels: glyphs closer than `GLYPH_GAP_FACTOR` of their size
+fn group_labels(glyphs: &[Bounds]) -> Vec<Vec<usize>> {
+    let mut parents: Vec<usize> = (0..glyphs.len()).collect();
+    let mut order: Vec<usize> = (0..glyphs.len()).collect();
+    order.sort_by(|a, b| glyphs[*a].min.x.total_cmp(&glyphs[*b].min.x));
+    for (position, &i) in order.iter().enumerate() {
+        let reach = glyphs[i].size() * GLYPH_GAP_FACTOR;
+        for &j in &order[position + 1..] {
+            if glyphs[j].min.x > glyphs[i].max.x + reach {
+                break;
+            }
+            let gap = glyphs[i].gap_to(&glyphs[j]);
+            if gap <= reach.min(glyphs[j].size() * GLYPH_GAP_FACTOR) {
+                union(&mut parents, i, j);
+            }
+        }
+    }
+    collect_groups(&mut parents)
+}
+
+/// Text height of a label: its extent across the run of glyph centers
+fn label_height(glyphs: &[Bounds], label: &[usize]) -> f64 {
+    let bounds = label.iter().map(|i| glyphs[*i]).reduce(|a, b| a.union(&b)).expect("labels are not empty");
+    if label.len() == 1 {
+        return bounds.size();
+    }
+    let centers: Vec<Point2<f64>> = label.iter().map(|i| glyphs[*i].center()).collect();
+    let spread = |axis: fn(&Point2<f64>) -> f64| {
+        let values = centers.iter().map(axis);
+        values.clone().fold(f64::MIN, f64::max) - values.fold(f64::MAX, f64::min)
+    };
+    if spread(|p| p.x) >= spread(|p| p.y) { bounds.height() } else { bounds.width() }
+}
+
+/// Run the enabled silkscreen checks on one silkscreen layer with that layer's rules
+pub fn check_silkscreen(layer: LayerType, primitives: &[TaggedPrimitive], rules: &DrcRules) -> Vec<DrcViolation> {
+    let strokes: Vec<Stroke> = primitives.iter()
+        .filter(|primitive| primitive.polarity == Polarity::Dark)
+        .filter_map(|primitive| match primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => Some(Stroke { start, end, width }),
+            _ => None,
+        })
+        .collect();
+    let groups = connected_strokes(&strokes);
+    let min_width = rules.min_silk_line_width as f64;
+    let min_height = rules.min_silk_text_height as f64;
+    let mut violations = Vec::new();
+
+    for group in &groups {
+        let Some(thinnest) = group.iter().map(|i| &strokes[*i]).min_by(|a, b| a.width.total_cmp(&b.width)) else {
+            continue;
+        };
+        if thinnest.width < min_width {
+            let at = nalgebra::center(&thinnest.start, &thinnest.end);
+            violations.push(DrcViolation {
+                rule_name: RULE_SILK_LINE_WIDTH.to_string(),
+                description: format!("Silkscreen line {:.3}mm wide below minimum", thinnest.width),
+                layer: layer.display_name(),
+                measured_value: thinnest.width as f32,
+                required_value: rules.min_silk_line_width,
+                x: at.x as f32,
+                y: at.y as f32,
+            });
+        }
+    }
+
+    let glyphs: Vec<Bounds> = groups.iter()
+        .filter(|group| group.iter().all(|i| (strokes[*i].end - strokes[*i].start).norm() <= MAX_GLYPH_STROKE_MM))
+        .map(|group| group.iter().map(|i| Bounds::of_stroke(&strokes[*i])).reduce(|a, b| a.union(&b)).expect("groups are not empty"))
+        .collect();
+    for label in group_labels(&glyphs) {
+        // A lone dash or dot says nothing about the text size
+        if label.len() == 1 && glyphs[label[0]].width().min(glyphs[label[0]].height()) <= JOIN_TOLERANCE_MM {
+            continue;
+        }
+        let height = label_height(&glyphs, &label);
+        if height < min_height {
+            let bounds = label.iter().map(|i| glyphs[*i]).reduce(|a, b| a.union(&b)).expect("labels are not empty");
+            let at = bounds.center();
+            violations.push(DrcViolation {
+                rule_name: RULE_SILK_TEXT_HEIGHT.to_string(),
+                description: format!("Silkscreen text {:.2}mm high below minimum", height),
+                layer: layer.display_name(),
+                measured_value: height as f32,
+                required_value: rules.min_silk_text_height,
+                x: at.x as f32,
+                y: at.y as f32,
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::Side;
+
+    fn stroke(points: &[(f64, f64)], width: f64) -> Vec<TaggedPrimitive> {
+        points.windows(2)
+            .map(|pair| TaggedPrimitive {
+                net: None,
+                polarity: Polarity::Dark,
+                shape: PrimitiveShape::Segment { start: Point2::new(pair[0].0, pair[0].1), end: Point2::new(pair[1].0, pair[1].1), width },
+            })
+            .collect()
+    }
+
+    /// "L" and "T"-ish glyphs of the given height, side by side from `x`
+    fn label(x: f64, y: f64, height: f64, width: f64) -> Vec<TaggedPrimitive> {
+        let mut primitives = stroke(&[(x, y + height), (x, y), (x + height * 0.6, y)], width);
+        let x = x + height * 0.8;
+        primitives.extend(stroke(&[(x, y + height), (x + height * 0.6, y + height)], width));
+        primitives.extend(stroke(&[(x + height * 0.3, y + height), (x + height * 0.3, y)], width));
+        primitives
+    }
+
+    #[test]
+    fn test_silkscreen_line_width_and_text_height() {
+        let rules = DrcRules { min_silk_line_width: 0.15, min_silk_text_height: 0.8, ..DrcRules::default() };
+        let mut primitives = label(0.0, 0.0, 1.0, 0.15);
+        primitives.extend(label(10.0, 0.0, 0.5, 0.15));
+        // A long thin outline: one line width violation, not taken for text
+        primitives.extend(stroke(&[(0.0, 10.0), (20.0, 10.0), (20.0, 14.0)], 0.1));
+
+        let violations = check_silkscreen(LayerType::Silkscreen(Side::Top), &primitives, &rules);
+        let rules_hit: Vec<&str> = violations.iter().map(|violation| violation.rule_name.as_str()).collect();
+        assert_eq!(rules_hit, [RULE_SILK_LINE_WIDTH, RULE_SILK_TEXT_HEIGHT]);
+        assert!((violations[0].measured_value - 0.1).abs() < 1e-6);
+        assert!((violations[1].measured_value - 0.5).abs() < 1e-6 && violations[1].x > 10.0);
+
+        // A layer override applies its own thresholds
+        let mut overridden = rules.clone();
+        overridden.add_layer_override(LayerType::Silkscreen(Side::Top)).min_silk_text_height = Some(0.4);
+        let violations = check_silkscreen(LayerType::Silkscreen(Side::Top), &primitives, &overridden.for_layer(&LayerType::Silkscreen(Side::Top)));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(overridden.for_layer(&LayerType::Silkscreen(Side::Bottom)).min_silk_text_height, 0.8);
+
+        // Thresholds the layer does not override follow later edits to the global rules
+        overridden.min_silk_line_width = 0.05;
+        overridden.min_silk_text_height = 0.3;
+        let top = overridden.for_layer(&LayerType::Silkscreen(Side::Top));
+        assert_eq!((top.min_silk_line_width, top.min_silk_text_height), (0.05, 0.4));
+    }
+
+    #[test]
+    fn test_layer_overrides_round_trip_and_read_old_profiles() {
+        let mut rules = DrcRules::default();
+        rules.add_layer_override(LayerType::Copper(1)).min_trace_width = Some(0.1);
+        let json = serde_json::to_string(&rules).unwrap();
+        assert!(json.contains(r#""layer_overrides":[{"layer":{"Copper":1},"rules":{"min_trace_width":0.1}}]"#), "{}", json);
+        assert_eq!(serde_json::from_str::<DrcRules>(&json).unwrap(), rules);
+
+        // Older profiles stored a full rule set per layer
+        let old = r#"{"layer_overrides":[{"layer":{"Copper":1},"rules":{"min_trace_width":0.1,"min_spacing":0.2}}]}"#;
+        let rules: DrcRules = serde_json::from_str(old).unwrap();
+        assert_eq!(rules.layer_overrides[&LayerType::Copper(1)].min_trace_width, Some(0.1));
+        assert_eq!(rules.for_layer(&LayerType::Copper(1)).min_spacing, DrcRules::default().min_spacing);
+    }
+}
@@ -0,0 +1,195 @@
+//! Via tenting from the soldermask openings over the drill hits
+//!
+//! A via is a plated hole no larger than `via_max_drill` that does not sit in a
+//! component pad: a rectangular, oblong or macro pad, or a round pad that is off
+//! the drill center or wider than a via's own pad would be. Each side is
+//! exposed when a mask opening matches the via: a flashed opening whose center
+//! is within `via_mask_tolerance` of the drill center, or any opening that
+//! covers the drill center (a via in a pad opening). Like the thermal check,
+//! the result is a review list rather than a rule violation.
+
+use bevy_ecs::world::World;
+use nalgebra::Point2;
+
+use super::types::DrcRules;
+use crate::ecs::{ApertureShape, DrillHole, LayerType, Polarity, PrimitiveShape, Side, TaggedPrimitive};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tenting {
+    /// Mask covers the via on both sides
+    Tented,
+    ExposedTop,
+    ExposedBottom,
+    ExposedBoth,
+}
+
+impl Tenting {
+    pub const ALL: [Tenting; 4] = [Self::Tented, Self::ExposedTop, Self::ExposedBottom, Self::ExposedBoth];
+
+    fn from_sides(top: bool, bottom: bool) -> Self {
+        match (top, bottom) {
+            (false, false) => Self::Tented,
+            (true, false) => Self::ExposedTop,
+            (false, true) => Self::ExposedBottom,
+            (true, true) => Self::ExposedBoth,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tented => "tented",
+            Self::ExposedTop => "exposed top",
+            Self::ExposedBottom => "exposed bottom",
+            Self::ExposedBoth => "exposed both sides",
+        }
+    }
+
+    pub fn is_exposed(&self) -> bool {
+        *self != Self::Tented
+    }
+}
+
+/// A via and how the soldermask treats it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViaTenting {
+    pub position: Point2<f64>,
+    pub drill: f64,
+    pub tenting: Tenting,
+}
+
+/// Round pads centred on a via's drill and up to this many times `via_max_drill` across are its own pad
+const VIA_PAD_DRILL_RATIO: f64 = 2.0;
+
+/// Plated hole sitting in a component pad rather than its own via pad, i.e. a component pin
+fn in_component_pad(hole: &DrillHole, copper: &[TaggedPrimitive], rules: &DrcRules) -> bool {
+    let max_via_pad = rules.via_max_drill as f64 * VIA_PAD_DRILL_RATIO;
+    copper.iter()
+        .filter(|primitive| primitive.polarity == Polarity::Dark && primitive.shape.distance_to(hole.position) == 0.0)
+        .any(|primitive| match primitive.shape {
+            PrimitiveShape::Flash { center, width, aperture: ApertureShape::Circle | ApertureShape::Polygon, .. } => {
+                width > max_via_pad + 1e-6 || (center - hole.position).norm() > rules.via_mask_tolerance as f64
+            }
+            PrimitiveShape::Flash { .. } => true,
+            _ => false,
+        })
+}
+
+/// A mask opening on `mask` matches the via at `center`
+fn has_opening(mask: &[TaggedPrimitive], center: Point2<f64>, tolerance: f64) -> bool {
+    mask.iter().filter(|opening| opening.polarity == Polarity::Dark).any(|opening| match opening.shape {
+        PrimitiveShape::Flash { center: opening_center, .. } if (opening_center - center).norm() <= tolerance => true,
+        _ => opening.shape.distance_to(center) == 0.0,
+    })
+}
+
+/// Classify the vias among `holes`; `copper` is the outer copper of both sides
+pub fn classify_vias(
+    holes: &[DrillHole],
+    top_mask: &[TaggedPrimitive],
+    bottom_mask: &[TaggedPrimitive],
+    copper: &[TaggedPrimitive],
+    rules: &DrcRules,
+) -> Vec<ViaTenting> {
+    let tolerance = rules.via_mask_tolerance as f64;
+    holes
+        .iter()
+        .filter(|hole| hole.plated && hole.diameter <= rules.via_max_drill as f64 + 1e-6)
+        .filter(|hole| !in_component_pad(hole, copper, rules))
+        .map(|hole| ViaTenting {
+            position: hole.position,
+            drill: hole.diameter,
+            tenting: Tenting::from_sides(
+                has_opening(top_mask, hole.position, tolerance),
+                has_opening(bottom_mask, hole.position, tolerance),
+            ),
+        })
+        .collect()
+}
+
+/// Classify the vias of the loaded drill files against both soldermask layers
+pub fn check_loaded_vias(world: &mut World, rules: &DrcRules) -> Result<Vec<ViaTenting>, String> {
+    let holes = crate::ecs::get_drill_holes(world);
+    if holes.is_empty() {
+        return Err("No drill data loaded".to_string());
+    }
+    let top_mask = crate::ecs::get_layer_primitives(world, LayerType::Soldermask(Side::Top));
+    let bottom_mask = crate::ecs::get_layer_primitives(world, LayerType::Soldermask(Side::Bottom));
+    if top_mask.is_empty() || bottom_mask.is_empty() {
+        return Err("Tenting needs both soldermask layers loaded".to_string());
+    }
+    let bottom_copper = crate::ecs::get_loaded_layer_types(world)
+        .into_iter()
+        .filter_map(|layer_type| layer_type.copper_layer_number())
+        .max()
+        .filter(|n| *n > 1)
+        .map(LayerType::Copper);
+    let mut copper = crate::ecs::get_layer_primitives(world, LayerType::Copper(1));
+    if let Some(bottom_copper) = bottom_copper {
+        copper.extend(crate::ecs::get_layer_primitives(world, bottom_copper));
+    }
+    Ok(classify_vias(&holes, &top_mask, &bottom_mask, &copper, rules))
+}
+
+/// CSV of the exposed vias in gerber coordinates (mm), for review outside the viewer
+pub fn exposed_vias_csv(vias: &[ViaTenting]) -> String {
+    let mut csv = "index,x_mm,y_mm,drill_mm,exposed\n".to_string();
+    for (i, via) in vias.iter().filter(|via| via.tenting.is_exposed()).enumerate() {
+        csv.push_str(&format!("{},{:.4},{:.4},{:.4},{}\n", i + 1, via.position.x, via.position.y, via.drill, via.tenting.label()));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drc_operations::test_primitives::flash;
+
+    fn hole(x: f64, diameter: f64) -> DrillHole {
+        DrillHole { position: Point2::new(x, 0.0), diameter, plated: true }
+    }
+
+    #[test]
+    fn test_vias_are_classified_per_side_and_pins_skipped() {
+        let rules = DrcRules { via_max_drill: 0.4, via_mask_tolerance: 0.05, ..DrcRules::default() };
+        // Vias at x = 0, 10, 20 and 30, a pin in a square pad at 40 and a large hole at 50
+        let holes = [hole(0.0, 0.3), hole(10.0, 0.3), hole(20.0, 0.3), hole(30.0, 0.3), hole(40.0, 0.3), hole(50.0, 1.0)];
+        // Top opening at 10 is 0.04 off center (within tolerance); the one near 30 is 0.3 off and misses it
+        let top_mask = [flash(10.04, 0.0, 0.05, 0.05, ApertureShape::Circle), flash(30.3, 0.0, 0.2, 0.2, ApertureShape::Circle), flash(20.0, 0.0, 0.6, 0.6, ApertureShape::Circle)];
+        let bottom_mask = [flash(20.0, 0.0, 0.6, 0.6, ApertureShape::Circle), flash(40.0, 0.0, 1.8, 1.8, ApertureShape::Rectangle)];
+        let copper = [flash(40.0, 0.0, 1.7, 1.7, ApertureShape::Rectangle)];
+
+        let vias = classify_vias(&holes, &top_mask, &bottom_mask, &copper, &rules);
+        let tenting: Vec<Tenting> = vias.iter().map(|via| via.tenting).collect();
+        assert_eq!(tenting, vec![Tenting::Tented, Tenting::ExposedTop, Tenting::ExposedBoth, Tenting::Tented]);
+
+        let csv = exposed_vias_csv(&vias);
+        assert_eq!(csv.lines().count(), 3);
+        assert_eq!(csv.lines().nth(2).unwrap(), "2,20.0000,0.0000,0.3000,exposed both sides");
+    }
+
+    #[test]
+    fn test_round_and_macro_pins_are_not_vias() {
+        let rules = DrcRules { via_max_drill: 0.4, via_mask_tolerance: 0.05, ..DrcRules::default() };
+        let holes = [hole(0.0, 0.3), hole(10.0, 0.3), hole(20.0, 0.3), hole(30.0, 0.3)];
+        // Rounded rectangle pad of a fine-pitch connector, as KiCad writes it
+        let body = vec!["21,1,1.0,0.6,0,0,0".to_string(), "21,1,0.8,0.8,0,0,0".to_string()];
+        let outline = crate::ecs::ApertureOutline::from_macro("RoundRect", &body, "", 1.0).unwrap();
+        let (width, height) = outline.centered_size();
+        let macro_pad = TaggedPrimitive {
+            net: None,
+            polarity: Polarity::Dark,
+            shape: PrimitiveShape::Flash { center: Point2::new(30.0, 0.0), width, height, aperture: ApertureShape::Other, outline: Some(std::sync::Arc::new(outline)) },
+        };
+        // Via pad at 0; a round pin pad wider than 2 × 0.4 at 10; a small round pad off center at 20
+        let copper = [
+            flash(0.0, 0.0, 0.6, 0.6, ApertureShape::Circle),
+            flash(10.0, 0.0, 1.2, 1.2, ApertureShape::Circle),
+            flash(20.2, 0.0, 0.7, 0.7, ApertureShape::Circle),
+            macro_pad,
+        ];
+
+        let vias = classify_vias(&holes, &[], &[], &copper, &rules);
+        let positions: Vec<f64> = vias.iter().map(|via| via.position.x).collect();
+        assert_eq!(positions, vec![0.0]);
+    }
+}
@@ -0,0 +1,15 @@
+//! Primitive builders shared by the DRC check tests
+
+use nalgebra::Point2;
+
+use crate::ecs::{ApertureShape, Polarity, PrimitiveShape, TaggedPrimitive};
+
+/// Dark primitive without a net
+pub fn primitive(shape: PrimitiveShape) -> TaggedPrimitive {
+    TaggedPrimitive { net: None, polarity: Polarity::Dark, shape }
+}
+
+/// Dark flash at (x, y), sized like its aperture
+pub fn flash(x: f64, y: f64, width: f64, height: f64, aperture: ApertureShape) -> TaggedPrimitive {
+    primitive(PrimitiveShape::Flash { center: Point2::new(x, y), width, height, aperture, outline: None })
+}
@@ -0,0 +1,273 @@
+//! Direct-connected pad heuristic for copper pours
+//!
+//! Flashed pads inside a large filled region are classified from the pour
+//! coverage on a ring `thermal_ring_distance` outside the pad edge. Coverage all
+//! round means the pad is joined to the pour directly, partial coverage means
+//! spokes across a clearance gap (thermal relief), and little or none means the
+//! pour keeps clear of the pad (isolated, usually another net).
+//!
+//! Direct-connected through-hole pads are reported: the pour sinks the heat of
+//! a hand iron. Without drill data every direct-connected pad is reported. The
+//! result is a list of candidates for manual review, not a rule check. Clear
+//! primitives cut copper out of the pours drawn before them, in file order, so
+//! an island poured inside a void counts again.
+
+use bevy_ecs::world::World;
+use nalgebra::{Point2, Vector2};
+
+use super::types::DrcRules;
+use crate::ecs::{ApertureShape, DrillHole, LayerType, Polarity, PrimitiveShape, TaggedPrimitive};
+
+/// Regions smaller than this are pads or small shapes rather than pours (mm²)
+const MIN_POUR_AREA_MM2: f64 = 4.0;
+/// Points sampled on the ring around each pad
+const RING_SAMPLES: usize = 72;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadConnection {
+    Direct,
+    ThermalRelief,
+    Isolated,
+}
+
+impl PadConnection {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Direct => "direct connect",
+            Self::ThermalRelief => "thermal relief",
+            Self::Isolated => "isolated",
+        }
+    }
+}
+
+/// How a pad inside a pour is joined to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PadThermal {
+    pub layer: LayerType,
+    pub position: Point2<f64>,
+    pub width: f64,
+    pub height: f64,
+    /// Share of the ring around the pad covered by the pour (0..=1)
+    pub coverage: f64,
+    pub connection: PadConnection,
+    /// A plated hole sits in the pad
+    pub through_hole: bool,
+}
+
+impl PadThermal {
+    pub fn description(&self) -> String {
+        let kind = if self.through_hole { "Through-hole pad" } else { "Pad" };
+        format!("{} {} to the pour ({:.0}% of the ring covered)", kind, self.connection.label(), self.coverage * 100.0)
+    }
+}
+
+/// Filled region (dark) or clear primitive (void) with its bounds
+struct Pour<'a> {
+    shape: &'a PrimitiveShape,
+    min: Point2<f64>,
+    max: Point2<f64>,
+    dark: bool,
+}
+
+impl Pour<'_> {
+    fn covers(&self, point: Point2<f64>) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y
+            && self.shape.distance_to(point) == 0.0
+    }
+}
+
+fn region_area(points: &[Point2<f64>]) -> f64 {
+    let twice: f64 = (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+    twice.abs() / 2.0
+}
+
+/// Points on the pad outline grown by `distance`: a circle for round pads, else a rectangle
+fn ring_points(center: Point2<f64>, width: f64, height: f64, aperture: ApertureShape, distance: f64) -> Vec<Point2<f64>> {
+    if matches!(aperture, ApertureShape::Circle | ApertureShape::Polygon) {
+        let radius = width / 2.0 + distance;
+        return (0..RING_SAMPLES)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / RING_SAMPLES as f64;
+                center + Vector2::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect();
+    }
+    let (half_w, half_h) = (width / 2.0 + distance, height / 2.0 + distance);
+    let perimeter = 4.0 * (half_w + half_h);
+    (0..RING_SAMPLES)
+        .map(|i| {
+            // Walk the rectangle counter-clockwise from its bottom-left corner
+            let mut along = perimeter * i as f64 / RING_SAMPLES as f64;
+            let sides = [(2.0 * half_w, Vector2::new(1.0, 0.0)), (2.0 * half_h, Vector2::new(0.0, 1.0)), (2.0 * half_w, Vector2::new(-1.0, 0.0)), (2.0 * half_h, Vector2::new(0.0, -1.0))];
+            let mut point = center + Vector2::new(-half_w, -half_h);
+            for (length, direction) in sides {
+                let step = along.min(length);
+                point += direction * step;
+                along -= step;
+                if along <= 0.0 {
+                    break;
+                }
+            }
+            point
+        })
+        .collect()
+}
+
+fn classify(coverage: f64, rules: &DrcRules) -> PadConnection {
+    if coverage >= rules.thermal_direct_coverage as f64 {
+        PadConnection::Direct
+    } else if coverage >= rules.thermal_min_coverage as f64 {
+        PadConnection::ThermalRelief
+    } else {
+        PadConnection::Isolated
+    }
+}
+
+/// Classify every flashed pad of a copper layer that lies within a large region
+pub fn classify_pads(layer: LayerType, primitives: &[TaggedPrimitive], holes: &[DrillHole], rules: &DrcRules) -> Vec<PadThermal> {
+    // Pours and the clear primitives (pour voids, clearances) cutting them, in file order
+    let pours: Vec<Pour> = primitives
+        .iter()
+        .filter(|primitive| match (&primitive.shape, primitive.polarity) {
+            (_, Polarity::Clear) => true,
+            (PrimitiveShape::Region { points }, Polarity::Dark) => region_area(points) >= MIN_POUR_AREA_MM2,
+            _ => false,
+        })
+        .map(|primitive| {
+            let (min, max) = primitive.shape.bounds();
+            Pour { shape: &primitive.shape, min, max, dark: primitive.polarity == Polarity::Dark }
+        })
+        .collect();
+    if !pours.iter().any(|pour| pour.dark) {
+        return Vec::new();
+    }
+
+    let distance = rules.thermal_ring_distance as f64;
+    let mut pads = Vec::new();
+    for primitive in primitives.iter().filter(|primitive| primitive.polarity == Polarity::Dark) {
+        let PrimitiveShape::Flash { center, width, height, aperture, .. } = primitive.shape else {
+            continue;
+        };
+        let (min, max) = primitive.shape.bounds();
+        let nearby: Vec<&Pour> = pours
+            .iter()
+            .filter(|pour| pour.min.x < max.x + distance && pour.max.x > min.x - distance && pour.min.y < max.y + distance && pour.max.y > min.y - distance)
+            .collect();
+        if !nearby.iter().any(|pour| pour.dark) {
+            continue;
+        }
+
+        // A point is copper when the last pour or void drawn over it is a pour
+        let ring = ring_points(center, width, height, aperture, distance);
+        let covered = ring.iter()
+            .filter(|point| nearby.iter().rev().find(|pour| pour.covers(**point)).is_some_and(|pour| pour.dark))
+            .count();
+        let coverage = covered as f64 / ring.len() as f64;
+        let through_hole = holes.iter().any(|hole| hole.plated && (hole.position - center).norm() < width.min(height) / 2.0);
+        pads.push(PadThermal {
+            layer,
+            position: center,
+            width,
+            height,
+            coverage,
+            connection: classify(coverage, rules),
+            through_hole,
+        });
+    }
+    pads
+}
+
+/// Pads worth a manual look: direct-connected through-hole pads, or every direct-connected pad without drill data
+pub fn direct_connect_warnings(pads: Vec<PadThermal>, have_drill_data: bool) -> Vec<PadThermal> {
+    pads.into_iter()
+        .filter(|pad| pad.connection == PadConnection::Direct && (pad.through_hole || !have_drill_data))
+        .collect()
+}
+
+/// Run the heuristic on every loaded copper layer
+pub fn check_loaded_copper(world: &mut World, rules: &DrcRules) -> Vec<PadThermal> {
+    let holes = crate::ecs::get_drill_holes(world);
+    let mut warnings = Vec::new();
+    for layer in crate::ecs::get_loaded_layer_types(world).into_iter().filter(|layer| layer.copper_layer_number().is_some()) {
+        let primitives = crate::ecs::get_layer_primitives(world, layer);
+        warnings.extend(direct_connect_warnings(classify_pads(layer, &primitives, &holes, rules), !holes.is_empty()));
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(min: (f64, f64), max: (f64, f64)) -> TaggedPrimitive {
+        let points = vec![Point2::new(min.0, min.1), Point2::new(max.0, min.1), Point2::new(max.0, max.1), Point2::new(min.0, max.1)];
+        TaggedPrimitive { net: None, polarity: Polarity::Dark, shape: PrimitiveShape::Region { points } }
+    }
+
+    fn pad() -> TaggedPrimitive {
+        let shape = PrimitiveShape::Flash { center: Point2::new(0.0, 0.0), width: 1.6, height: 1.6, aperture: ApertureShape::Circle, outline: None };
+        TaggedPrimitive { net: None, polarity: Polarity::Dark, shape }
+    }
+
+    /// Pour around the origin with a square clearance of half-size `gap`, cut in through a slit as fills are exported
+    fn pour_with_clearance(gap: f64) -> Vec<TaggedPrimitive> {
+        let points = [(-5.0, -5.0), (5.0, -5.0), (5.0, 5.0), (-5.0, 5.0), (-5.0, 0.0), (-gap, 0.0), (-gap, gap), (gap, gap), (gap, -gap), (-gap, -gap), (-gap, 0.0), (-5.0, 0.0)];
+        let points = points.iter().map(|(x, y)| Point2::new(*x, *y)).collect();
+        vec![TaggedPrimitive { net: None, polarity: Polarity::Dark, shape: PrimitiveShape::Region { points } }]
+    }
+
+    #[test]
+    fn test_pads_are_classified_by_ring_coverage() {
+        let rules = DrcRules::default();
+        let hole = DrillHole { position: Point2::new(0.0, 0.0), diameter: 0.8, plated: true };
+
+        let direct = vec![region((-5.0, -5.0), (5.0, 5.0)), pad()];
+        let pads = classify_pads(LayerType::Copper(1), &direct, &[hole], &rules);
+        assert_eq!(pads.len(), 1);
+        assert_eq!(pads[0].connection, PadConnection::Direct);
+        assert!(pads[0].through_hole);
+        assert_eq!(direct_connect_warnings(pads, true).len(), 1);
+
+        let mut isolated = pour_with_clearance(1.5);
+        isolated.push(pad());
+        let pads = classify_pads(LayerType::Copper(1), &isolated, &[hole], &rules);
+        assert_eq!(pads[0].connection, PadConnection::Isolated);
+
+        // Four 0.5 mm spokes across the clearance
+        let mut relief = isolated;
+        relief.extend([region((-0.25, -5.0), (0.25, 5.0)), region((-5.0, -0.25), (5.0, 0.25))]);
+        let pads = classify_pads(LayerType::Copper(1), &relief, &[hole], &rules);
+        assert_eq!(pads[0].connection, PadConnection::ThermalRelief, "coverage {}", pads[0].coverage);
+        assert!(direct_connect_warnings(pads, true).is_empty());
+
+        // Without drill data a direct SMD-looking pad is still a candidate
+        let pads = classify_pads(LayerType::Copper(1), &direct, &[], &rules);
+        assert!(!pads[0].through_hole);
+        assert_eq!(direct_connect_warnings(pads.clone(), false).len(), 1);
+        assert!(direct_connect_warnings(pads, true).is_empty());
+    }
+
+    #[test]
+    fn test_clear_primitives_only_cut_what_was_drawn_before_them() {
+        let rules = DrcRules::default();
+        let void = |min: (f64, f64), max: (f64, f64)| TaggedPrimitive { polarity: Polarity::Clear, ..region(min, max) };
+
+        // A void over the pad's surroundings isolates it
+        let voided = vec![region((-5.0, -5.0), (5.0, 5.0)), void((-3.0, -3.0), (3.0, 3.0)), pad()];
+        assert_eq!(classify_pads(LayerType::Copper(1), &voided, &[], &rules)[0].connection, PadConnection::Isolated);
+
+        // An island poured inside the void afterwards joins the pad again
+        let mut island = voided.clone();
+        island.insert(2, region((-2.5, -2.5), (2.5, 2.5)));
+        assert_eq!(classify_pads(LayerType::Copper(1), &island, &[], &rules)[0].connection, PadConnection::Direct);
+
+        // The same void drawn after the pour and island cuts both
+        let late_void = vec![region((-5.0, -5.0), (5.0, 5.0)), region((-2.5, -2.5), (2.5, 2.5)), void((-3.0, -3.0), (3.0, 3.0)), pad()];
+        assert_eq!(classify_pads(LayerType::Copper(1), &late_void, &[], &rules)[0].connection, PadConnection::Isolated);
+    }
+}
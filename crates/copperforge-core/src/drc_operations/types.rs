@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use gerber_viewer::{GerberLayer, BoundingBox};
 
 // Temporary Position struct until we figure out the correct gerber_types API
@@ -80,6 +82,8 @@ pub struct DrcSimple {
     pub min_spacing: f32,       // mm
     pub lines_only: bool,       // Only analyze Line primitives (skip rectangles)
     pub min_trace_length: f32,  // mm - minimum length to be considered a trace
+    pub check_corners: bool,    // Look for sharp corners in trace quality analysis
+    pub check_jogs: bool,       // Look for unnecessary jogs in trace quality analysis
 }
 
 impl Default for DrcSimple {
@@ -91,6 +95,8 @@ impl Default for DrcSimple {
             min_spacing: 0.15,       // 6 mil
             lines_only: false,       // By default, analyze both lines and rectangles
             min_trace_length: 2.0,   // 2mm - filter out short pad/via connections
+            check_corners: true,
+            check_jogs: true,
         }
     }
 }
@@ -144,15 +150,107 @@ pub struct CornerOverlayShape {
     pub trace_width: f32,
 }
 
+/// Layer snapshot handed to the DRC checks (replaces layer_operations::LayerInfo)
+#[derive(Debug, Clone)]
+pub struct LayerInfo {
+    pub layer_type: crate::ecs::LayerType,
+    pub gerber_layer: Option<Arc<GerberLayer>>,
+    pub raw_gerber_data: Option<String>,
+    pub visible: bool,
+    pub color: egui::Color32,
+    /// Extracted primitives, for the checks that need exact geometry
+    /// (silkscreen, board edge clearance on copper and the outline)
+    pub primitives: Option<Arc<Vec<crate::ecs::TaggedPrimitive>>>,
+}
+
+impl LayerInfo {
+    pub fn new(layer_type: crate::ecs::LayerType, gerber_layer: Option<Arc<GerberLayer>>, raw_gerber_data: Option<String>, visible: bool) -> Self {
+        Self {
+            layer_type,
+            gerber_layer,
+            raw_gerber_data,
+            visible,
+            color: layer_type.color(),
+            primitives: None,
+        }
+    }
+}
+
+/// Snapshot every loaded ECS layer in the form the DRC checks take
+pub fn collect_drc_layers(world: &mut bevy_ecs::world::World) -> HashMap<crate::ecs::LayerType, LayerInfo> {
+    let mut layers = HashMap::new();
+    
+    for layer_type in crate::ecs::LayerType::all() {
+        if let Some((_entity, layer_info, gerber_data, visibility)) = crate::ecs::get_layer_data(world, layer_type) {
+            // Share the entity's layer; a snapshot for the worker thread copies no geometry
+            let mut drc_layer = LayerInfo::new(
+                layer_info.layer_type,
+                Some(gerber_data.0.clone()),
+                None, // Raw gerber data not needed for DRC
+                visibility.visible,
+            );
+            
+            // Get color from ECS render properties
+            if let Some(render_props) = crate::ecs::get_layer_render_properties(world, layer_type) {
+                drc_layer.color = render_props.color;
+            }
+            if layer_type.is_copper() || matches!(layer_type, crate::ecs::LayerType::Silkscreen(_) | crate::ecs::LayerType::MechanicalOutline) {
+                drc_layer.primitives = Some(Arc::new(crate::ecs::get_layer_primitives(world, layer_type)));
+            }
+            
+            layers.insert(layer_type, drc_layer);
+        }
+    }
+    
+    layers
+}
+
 /// DRC Rules structure with unit conversion support
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct DrcRules {
     pub min_trace_width: f32,      // mm
     pub min_via_diameter: f32,     // mm  
     pub min_drill_diameter: f32,   // mm
     pub min_spacing: f32,          // mm
     pub min_annular_ring: f32,     // mm
-    pub use_mils: bool,            // true = display in mils, false = mm
+    /// Unit the rule fields are shown in; rules saved before this read `use_mils`
+    #[serde(alias = "use_mils", deserialize_with = "deserialize_rule_unit")]
+    pub display_unit: crate::ecs::DisplayUnit,
+    pub min_paste_aperture_area: f32, // mm², smaller paste openings risk insufficient solder
+    pub min_drill_spacing: f32,    // mm, hole center to hole center
+    pub min_drill_to_copper: f32,  // mm, hole edge to unrelated copper
+    pub mask_sliver_check: bool,
+    pub min_mask_sliver: f32,      // mm, narrowest mask strip between openings
+    pub mask_registration_check: bool,
+    pub max_mask_expansion: f32,   // mm, expected opening growth around a pad
+    pub min_component_clearance: f32, // mm, edge to edge between component bodies
+    pub thermal_ring_distance: f32, // mm, outside the pad edge where pour coverage is sampled
+    pub thermal_direct_coverage: f32, // ring share covered at or above which a pad is direct connected
+    pub thermal_min_coverage: f32, // ring share below which a pad is isolated from the pour
+    pub via_max_drill: f32,        // mm, plated holes up to this size are vias rather than pins
+    pub via_mask_tolerance: f32,   // mm, mask opening center to drill center for a match
+    pub min_silk_line_width: f32,  // mm, thinner silkscreen strokes may not print
+    pub min_silk_text_height: f32, // mm, smaller silkscreen text is not legible
+    pub min_edge_clearance: f32,   // mm, copper to the board outline
+    /// Thresholds replacing these on single layers, consulted by the per-layer checks
+    /// (trace width, edge clearance, silkscreen)
+    #[serde(skip_serializing_if = "HashMap::is_empty", with = "layer_override_list")]
+    pub layer_overrides: HashMap<crate::ecs::LayerType, LayerRuleOverride>,
+}
+
+/// Per-layer thresholds; None follows the global rule, including later edits to it
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LayerRuleOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_trace_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_edge_clearance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_silk_line_width: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_silk_text_height: Option<f32>,
 }
 
 impl Default for DrcRules {
@@ -163,7 +261,24 @@ impl Default for DrcRules {
             min_drill_diameter: 0.2,  // 0.2mm = ~8 mil
             min_spacing: 0.15,        // 0.15mm = ~6 mil
             min_annular_ring: 0.1,    // 0.1mm = ~4 mil
-            use_mils: false,          // Default to mm
+            display_unit: crate::ecs::DisplayUnit::Millimeters,
+            min_paste_aperture_area: 0.2, // 0.2mm², below this paste release gets unreliable
+            min_drill_spacing: 0.5,   // 0.5mm center to center
+            min_drill_to_copper: 0.2, // 0.2mm = ~8 mil
+            mask_sliver_check: true,
+            min_mask_sliver: 0.1,     // 0.1mm = ~4 mil, typical minimum mask web
+            mask_registration_check: true,
+            max_mask_expansion: 0.1,  // 0.1mm = ~4 mil
+            min_component_clearance: 0.25, // 0.25mm, enough for pick-and-place nozzles
+            thermal_ring_distance: 0.2, // 0.2mm, inside a typical 0.5mm thermal gap
+            thermal_direct_coverage: 0.85,
+            thermal_min_coverage: 0.05,
+            via_max_drill: 0.5,       // 0.5mm, pins of through-hole parts are usually larger
+            via_mask_tolerance: 0.05, // 0.05mm, covers rounding and small export offsets
+            min_silk_line_width: 0.15, // 0.15mm = ~6 mil
+            min_silk_text_height: 0.8, // 0.8mm, smallest text most fabs print legibly
+            min_edge_clearance: 0.3,  // 0.3mm, keeps copper clear of the router and V-score
+            layer_overrides: HashMap::new(),
         }
     }
 }
@@ -179,27 +294,157 @@ impl DrcRules {
         crate::ecs::nm_to_mm(crate::ecs::mils_to_nm(mils))
     }
     
-    /// Get display value (convert to mils if use_mils is true)
+    /// Get display value (convert to the rules' display unit)
     pub fn get_display_value(&self, mm_value: f32) -> f32 {
-        if self.use_mils {
-            Self::mm_to_mils(mm_value)
-        } else {
-            mm_value
-        }
+        self.display_unit.from_mm(mm_value as f64) as f32
     }
     
-    /// Set value from display (convert from mils if use_mils is true)
+    /// Set value from display (convert from the rules' display unit)
     pub fn set_from_display(&self, display_value: f32) -> f32 {
-        if self.use_mils {
-            Self::mils_to_mm(display_value)
-        } else {
-            display_value
-        }
+        self.display_unit.to_mm(display_value as f64) as f32
+    }
+    
+    /// Units the rule fields are shown in
+    pub fn display_unit(&self) -> crate::ecs::DisplayUnit {
+        self.display_unit
     }
     
     /// Get unit suffix
     pub fn unit_suffix(&self) -> &str {
-        if self.use_mils { " mils" } else { " mm" }
+        self.display_unit.suffix()
+    }
+    
+    /// Rules the per-layer checks use on `layer_type`: these rules with the layer's overridden thresholds
+    pub fn for_layer(&self, layer_type: &crate::ecs::LayerType) -> std::borrow::Cow<'_, DrcRules> {
+        let Some(layer) = self.layer_overrides.get(layer_type) else {
+            return std::borrow::Cow::Borrowed(self);
+        };
+        std::borrow::Cow::Owned(DrcRules {
+            min_trace_width: layer.min_trace_width.unwrap_or(self.min_trace_width),
+            min_edge_clearance: layer.min_edge_clearance.unwrap_or(self.min_edge_clearance),
+            min_silk_line_width: layer.min_silk_line_width.unwrap_or(self.min_silk_line_width),
+            min_silk_text_height: layer.min_silk_text_height.unwrap_or(self.min_silk_text_height),
+            layer_overrides: HashMap::new(),
+            ..self.clone()
+        })
+    }
+    
+    /// Start overriding thresholds on a layer; nothing is overridden until a field is set
+    pub fn add_layer_override(&mut self, layer_type: crate::ecs::LayerType) -> &mut LayerRuleOverride {
+        self.layer_overrides.entry(layer_type).or_default()
+    }
+}
+
+/// Layer overrides as a list of layer/rules entries (JSON map keys must be strings)
+mod layer_override_list {
+    use std::collections::HashMap;
+    
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    
+    use super::LayerRuleOverride;
+    use crate::ecs::LayerType;
+    
+    #[derive(Serialize)]
+    struct Entry<'a> {
+        layer: LayerType,
+        rules: &'a LayerRuleOverride,
+    }
+    
+    /// Profiles saved before the per-field overrides hold a full rule set here;
+    /// its threshold fields read as overrides and the rest is ignored
+    #[derive(Deserialize)]
+    struct OwnedEntry {
+        layer: LayerType,
+        rules: LayerRuleOverride,
+    }
+    
+    pub fn serialize<S: Serializer>(overrides: &HashMap<LayerType, LayerRuleOverride>, serializer: S) -> Result<S::Ok, S::Error> {
+        // Layer-list order, so saved profiles diff cleanly
+        let order = LayerType::all();
+        let mut entries: Vec<Entry> = overrides.iter().map(|(layer, rules)| Entry { layer: *layer, rules }).collect();
+        entries.sort_by_key(|entry| order.iter().position(|layer| *layer == entry.layer).unwrap_or(usize::MAX));
+        entries.serialize(serializer)
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<LayerType, LayerRuleOverride>, D::Error> {
+        let entries = Vec::<OwnedEntry>::deserialize(deserializer)?;
+        Ok(entries.into_iter().map(|entry| (entry.layer, entry.rules)).collect())
+    }
+}
+
+/// Rule unit from a `DisplayUnit` or from the `use_mils` flag of older rule files
+fn deserialize_rule_unit<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<crate::ecs::DisplayUnit, D::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum RuleUnit {
+        Unit(crate::ecs::DisplayUnit),
+        LegacyMils(bool),
+    }
+    Ok(match <RuleUnit as serde::Deserialize>::deserialize(deserializer)? {
+        RuleUnit::Unit(unit) => unit,
+        RuleUnit::LegacyMils(mils) => crate::ecs::DisplayUnit::from_legacy_mils(mils),
+    })
+}
+
+/// Which DRC checks run; disabled checks are skipped entirely, not filtered afterwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DrcCheckConfig {
+    /// Minimum trace width on the copper layers
+    pub check_trace_width: bool,
+    /// Drill to drill spacing and drill to copper clearance
+    pub check_spacing: bool,
+    /// Sharp trace corners (trace quality)
+    pub check_corners: bool,
+    /// Unnecessary trace jogs (trace quality)
+    pub check_jogs: bool,
+    /// Copper to board edge clearance
+    pub check_edge_clearance: bool,
+    /// Copper ring around plated holes
+    pub check_annular: bool,
+    /// Silkscreen line width and text height
+    pub check_silkscreen: bool,
+}
+
+impl Default for DrcCheckConfig {
+    fn default() -> Self {
+        Self {
+            check_trace_width: true,
+            check_spacing: true,
+            check_corners: true,
+            check_jogs: true,
+            check_edge_clearance: true,
+            check_annular: true,
+            check_silkscreen: true,
+        }
+    }
+}
+
+impl DrcCheckConfig {
+    /// Cheap checks for quick iteration: no trace quality analysis
+    pub fn fast() -> Self {
+        Self {
+            check_corners: false,
+            check_jogs: false,
+            ..Self::default()
+        }
+    }
+    
+    /// Only the trace quality analysis (corners and jogs)
+    pub fn trace_quality_only() -> Self {
+        Self {
+            check_trace_width: false,
+            check_spacing: false,
+            check_corners: true,
+            check_jogs: true,
+            check_edge_clearance: false,
+            check_annular: false,
+            check_silkscreen: false,
+        }
+    }
+    
+    pub fn trace_quality(&self) -> bool {
+        self.check_corners || self.check_jogs
     }
 }
 
@@ -367,13 +612,17 @@ impl DrcSimple {
                 }
                 
                 // Check for unnecessary jogs by examining nearby lines
-                if let Some(jog_issue) = self.detect_unnecessary_jog(primitive, primitives, i) {
-                    quality_issues.push(jog_issue);
+                if self.check_jogs {
+                    if let Some(jog_issue) = self.detect_unnecessary_jog(primitive, primitives, i) {
+                        quality_issues.push(jog_issue);
+                    }
                 }
                 
                 // Check for sharp corners that could be rounded
-                if let Some(corner_issue) = self.detect_sharp_corner(primitive, primitives, i) {
-                    quality_issues.push(corner_issue);
+                if self.check_corners {
+                    if let Some(corner_issue) = self.detect_sharp_corner(primitive, primitives, i) {
+                        quality_issues.push(corner_issue);
+                    }
                 }
             }
         }
@@ -816,6 +1065,15 @@ mod tests {
         assert_eq!(drc.lines_only, false);
     }
     
+    #[test]
+    fn test_rules_read_the_legacy_mils_flag() {
+        let rules: DrcRules = serde_json::from_str(r#"{"min_trace_width": 0.2, "use_mils": true}"#).unwrap();
+        assert_eq!(rules.display_unit, crate::ecs::DisplayUnit::Mils);
+        let rules: DrcRules = serde_json::from_str(r#"{"display_unit": "Inches"}"#).unwrap();
+        assert_eq!(rules.display_unit, crate::ecs::DisplayUnit::Inches);
+        assert_eq!(serde_json::from_str::<DrcRules>("{}").unwrap().display_unit, crate::ecs::DisplayUnit::Millimeters);
+    }
+    
     #[test]
     fn test_find_trace_width_violations() {
         let drc = DrcSimple::default();
@@ -844,6 +1102,39 @@ mod tests {
         assert_eq!(violations[0].required_width, 0.15);
     }
     
+    #[test]
+    fn test_dedup_merges_primitive_and_raw_reports_in_stable_order() {
+        let violation = |rule: &str, layer: &str, measured: f32, x: f32, y: f32| DrcViolation {
+            rule_name: rule.to_string(),
+            description: String::new(),
+            layer: layer.to_string(),
+            measured_value: measured,
+            required_value: 0.15,
+            x,
+            y,
+        };
+        let found = vec![
+            violation("Minimum Trace Width", "Top Copper (L1)", 0.12, 10.2, 5.0),
+            violation("Drill To Drill Spacing", "Drill (PTH)", 0.3, 1.0, 1.0),
+            violation("Primitive Trace Width", "Top Copper (L1)", 0.1, 10.0, 5.0),
+            violation("Primitive Trace Width", "Bottom Copper (L2)", 0.1, 10.0, 5.0),
+            violation("Primitive Trace Width", "Top Copper (L1)", 0.1, 2.0, 5.0),
+        ];
+        
+        let unique = dedup_violations(found.clone(), DUPLICATE_TOLERANCE_MM);
+        let summary: Vec<(&str, f32, f32)> = unique.iter().map(|v| (v.layer.as_str(), v.x, v.measured_value)).collect();
+        assert_eq!(summary, vec![
+            ("Bottom Copper (L2)", 10.0, 0.1),
+            ("Drill (PTH)", 1.0, 0.3),
+            ("Top Copper (L1)", 2.0, 0.1),
+            ("Top Copper (L1)", 10.0, 0.1),
+        ]);
+        
+        let reversed: Vec<DrcViolation> = found.into_iter().rev().collect();
+        let again = dedup_violations(reversed, DUPLICATE_TOLERANCE_MM);
+        assert_eq!(again.iter().map(|v| v.format_message()).collect::<Vec<_>>(), unique.iter().map(|v| v.format_message()).collect::<Vec<_>>());
+    }
+    
     #[test]
     fn test_lines_only_mode() {
         let drc = DrcSimple {
@@ -906,17 +1197,14 @@ pub fn extract_coordinates_from_command(command_str: &str) -> (f32, f32) {
     (x, y)
 }
 
-/// Cluster DRC violations by trace  
-pub fn cluster_violations_per_trace(violations: &[DrcViolation]) -> Vec<DrcViolation> {
-    if violations.is_empty() {
-        return Vec::new();
-    }
-    
-    // Group violations by proximity (traces are continuous)
+/// Violations closer than this on the same layer and rule are one defect (mm)
+pub const DUPLICATE_TOLERANCE_MM: f32 = 0.5;
+
+/// Group violations into chains whose members lie within `cluster_distance` of each other
+fn cluster_by_distance<'a>(violations: &[&'a DrcViolation], cluster_distance: f32) -> Vec<Vec<&'a DrcViolation>> {
     let mut clusters: Vec<Vec<&DrcViolation>> = Vec::new();
-    let cluster_distance = 5.0; // mm - violations within 5mm are likely same trace
     
-    for violation in violations {
+    for &violation in violations {
         let mut added_to_cluster = false;
         
         for cluster in &mut clusters {
@@ -977,18 +1265,69 @@ pub fn cluster_violations_per_trace(violations: &[DrcViolation]) -> Vec<DrcViola
         }
     }
     
+    clusters
+}
+
+/// The violation with the smallest measured value (worst case) of a cluster
+fn worst_violation(cluster: Vec<&DrcViolation>) -> DrcViolation {
+    cluster.into_iter()
+        .min_by(|a, b| a.measured_value.total_cmp(&b.measured_value))
+        .expect("clusters are never empty")
+        .clone()
+}
+
+/// Cluster DRC violations by trace  
+pub fn cluster_violations_per_trace(violations: &[DrcViolation]) -> Vec<DrcViolation> {
+    if violations.is_empty() {
+        return Vec::new();
+    }
+    
+    // Group violations by proximity (traces are continuous)
+    let cluster_distance = 5.0; // mm - violations within 5mm are likely same trace
+    let clusters = cluster_by_distance(&violations.iter().collect::<Vec<_>>(), cluster_distance);
+    
     println!("Clustered {} violations into {} traces", violations.len(), clusters.len());
     
     // Return one representative violation per cluster (trace)
-    clusters.into_iter()
-        .map(|cluster| {
-            // Find the violation with the smallest width (worst case)
-            cluster.into_iter()
-                .min_by(|a, b| a.measured_value.partial_cmp(&b.measured_value).unwrap())
-                .unwrap()
-                .clone()
-        })
-        .collect()
+    clusters.into_iter().map(worst_violation).collect()
+}
+
+/// Rule used to match duplicates: primitive and raw gerber analysis report the same trace width defect
+fn duplicate_rule_key(rule_name: &str) -> &str {
+    match rule_name {
+        "Primitive Trace Width" | "Minimum Trace Width" => "Trace Width",
+        other => other,
+    }
+}
+
+/// Order violations by layer, then y, then x (rule name breaks ties)
+pub fn sort_violations(violations: &mut [DrcViolation]) {
+    violations.sort_by(|a, b| {
+        a.layer.cmp(&b.layer)
+            .then(a.y.total_cmp(&b.y))
+            .then(a.x.total_cmp(&b.x))
+            .then_with(|| a.rule_name.cmp(&b.rule_name))
+    });
+}
+
+/// Merge violations of the same rule on the same layer lying within `tolerance` mm,
+/// keeping the worst of each, and return them in `sort_violations` order
+pub fn dedup_violations(mut violations: Vec<DrcViolation>, tolerance: f32) -> Vec<DrcViolation> {
+    sort_violations(&mut violations);
+    
+    let mut groups: std::collections::BTreeMap<(&str, &str), Vec<&DrcViolation>> = std::collections::BTreeMap::new();
+    for violation in &violations {
+        groups.entry((violation.layer.as_str(), duplicate_rule_key(&violation.rule_name)))
+            .or_default()
+            .push(violation);
+    }
+    
+    let mut unique: Vec<DrcViolation> = groups.values()
+        .flat_map(|group| cluster_by_distance(group, tolerance))
+        .map(worst_violation)
+        .collect();
+    sort_violations(&mut unique);
+    unique
 }
 
 /// Check trace width in gerber data
@@ -1108,58 +1447,96 @@ pub fn check_trace_width_in_gerber_data(
 
 /// Main DRC check function - runs all configured DRC checks
 pub fn run_simple_drc_check(
-    layers: &HashMap<crate::ecs::LayerType, crate::ui::drc_panel::LayerInfo>,
+    layers: &HashMap<crate::ecs::LayerType, LayerInfo>,
     drc_rules: &DrcRules,
+    checks: &DrcCheckConfig,
     trace_quality_issues: &mut Vec<TraceQualityIssue>
 ) -> Vec<DrcViolation> {
-    use crate::ecs::LayerType;
-    
     let mut violations = Vec::new();
     
     // Clear previous quality issues
     trace_quality_issues.clear();
     
     // Get PCB boundary from mechanical outline layer
-    let pcb_boundary = if let Some(outline_info) = layers.get(&LayerType::MechanicalOutline) {
-        outline_info.gerber_layer.as_ref().map(|layer| layer.bounding_box())
-    } else {
-        None
+    let boundary = match get_pcb_boundary(layers) {
+        Some(boundary) => boundary,
+        None => {
+            println!("Warning: No mechanical outline found - cannot determine PCB boundary for DRC");
+            return violations;
+        }
     };
     
-    if pcb_boundary.is_none() {
-        println!("Warning: No mechanical outline found - cannot determine PCB boundary for DRC");
-        return violations;
-    }
-    
-    let boundary = pcb_boundary.unwrap();
     println!("DRC boundary check: PCB area is {:.1} x {:.1} mm", boundary.width(), boundary.height());
     
     // Check each copper layer for trace width violations
+    let outline = layers.get(&crate::ecs::LayerType::MechanicalOutline).and_then(|outline| outline.primitives.as_deref());
     for (layer_type, layer_info) in layers {
-        // Only check copper layers
-        if !layer_type.is_copper() {
-            continue;
+        let (layer_violations, quality_issues) = run_layer_drc_check(layer_type, layer_info, &drc_rules.for_layer(layer_type), checks, &boundary, outline.map(Vec::as_slice));
+        violations.extend(layer_violations);
+        trace_quality_issues.extend(quality_issues);
+    }
+    
+    violations
+}
+
+/// Layers with per-layer checks: copper and silkscreen
+pub fn has_layer_checks(layer_type: &crate::ecs::LayerType) -> bool {
+    layer_type.is_copper() || matches!(layer_type, crate::ecs::LayerType::Silkscreen(_))
+}
+
+/// Get the PCB boundary from the mechanical outline layer, if one is loaded
+pub fn get_pcb_boundary(
+    layers: &HashMap<crate::ecs::LayerType, LayerInfo>,
+) -> Option<BoundingBox> {
+    layers.get(&crate::ecs::LayerType::MechanicalOutline)
+        .and_then(|outline| outline.gerber_layer.as_ref())
+        .map(|layer| layer.bounding_box().clone())
+}
+
+/// Run the DRC checks for a single layer against a known PCB boundary
+/// Returns the violations and trace quality issues found on that layer only;
+/// `drc_rules` are the layer's rules, see `DrcRules::for_layer`; `outline` holds
+/// the outline primitives for the board edge clearance check
+pub fn run_layer_drc_check(
+    layer_type: &crate::ecs::LayerType,
+    layer_info: &LayerInfo,
+    drc_rules: &DrcRules,
+    checks: &DrcCheckConfig,
+    boundary: &BoundingBox,
+    outline: Option<&[crate::ecs::TaggedPrimitive]>,
+) -> (Vec<DrcViolation>, Vec<TraceQualityIssue>) {
+    let mut violations = Vec::new();
+    let mut trace_quality_issues = Vec::new();
+    
+    if let crate::ecs::LayerType::Silkscreen(_) = layer_type {
+        if let Some(primitives) = layer_info.primitives.as_ref().filter(|_| checks.check_silkscreen) {
+            violations = super::silkscreen::check_silkscreen(*layer_type, primitives, drc_rules);
         }
+        return (violations, trace_quality_issues);
+    }
+    
+    // Everything else only checks copper layers
+    if !layer_type.is_copper() {
+        return (violations, trace_quality_issues);
+    }
+    
+    if let (Some(copper), Some(outline)) = (layer_info.primitives.as_ref().filter(|_| checks.check_edge_clearance), outline) {
+        violations.extend(super::edge::check_edge_clearance(*layer_type, copper, outline, drc_rules.min_edge_clearance as f64));
+    }
+    
+    // Use primitive-based DRC analysis
+    if let Some(gerber_layer) = &layer_info.gerber_layer {
+        let drc = DrcSimple {
+            min_trace_width: drc_rules.min_trace_width,
+            lines_only: true,  // Only check Line primitives to avoid copper pour false positives
+            min_trace_length: 1.0,  // Only lines >= 1mm are considered traces (not pad connections)
+            check_corners: checks.check_corners,
+            check_jogs: checks.check_jogs,
+            ..DrcSimple::default()
+        };
         
-        // Use primitive-based DRC analysis
-        if let Some(gerber_layer) = &layer_info.gerber_layer {
-            println!("Running primitive-based trace detection on {}", layer_type.display_name());
-            
-            let drc = DrcSimple {
-                min_trace_width: drc_rules.min_trace_width,
-                lines_only: true,  // Only check Line primitives to avoid copper pour false positives
-                min_trace_length: 1.0,  // Only lines >= 1mm are considered traces (not pad connections)
-                ..DrcSimple::default()
-            };
-            
-            // Get mechanical outline bounds for filtering
-            let pcb_bounds = layers.get(&LayerType::MechanicalOutline)
-                .and_then(|outline| outline.gerber_layer.as_ref())
-                .map(|layer| layer.bounding_box());
-                
-            let primitive_violations = drc.run_trace_width_drc_with_bounds(gerber_layer, pcb_bounds);
-            
-            // Also analyze trace quality (corners, jogs, etc.)
+        // Analyze trace quality (corners, jogs, etc.)
+        if checks.trace_quality() {
             let quality_issues = drc.analyze_trace_quality(gerber_layer);
             println!("Found {} trace quality issues on {}", quality_issues.len(), layer_type.display_name());
             
@@ -1170,17 +1547,19 @@ pub fn run_simple_drc_check(
                 }
             }
             
-            // Store quality issues for this layer (extend the existing vector)
             trace_quality_issues.extend(quality_issues);
+        }
+        
+        if checks.check_trace_width {
+            println!("Running primitive-based trace detection on {}", layer_type.display_name());
+            let primitive_violations = drc.run_trace_width_drc_with_bounds(gerber_layer, Some(boundary));
             
-            if let Some(bounds) = &pcb_bounds {
-                println!("PCB bounds: ({:.2}, {:.2}) to ({:.2}, {:.2})", 
-                    bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y);
-            }
+            println!("PCB bounds: ({:.2}, {:.2}) to ({:.2}, {:.2})", 
+                boundary.min.x, boundary.min.y, boundary.max.x, boundary.max.y);
             
-            // Convert to DrcViolation format
+            // Debug first few violations
             for (i, violation) in primitive_violations.iter().enumerate() {
-                if i < 3 { // Debug first few violations
+                if i < 3 {
                     println!("Violation {}: trace at ({:.2}, {:.2}), width {:.3}mm", 
                         i, violation.trace.center_x, violation.trace.center_y, violation.trace.width);
                 }
@@ -1199,24 +1578,24 @@ pub fn run_simple_drc_check(
                 });
             }
         }
+    }
+    
+    // Also check using raw gerber data analysis
+    if let Some(raw_data) = layer_info.raw_gerber_data.as_ref().filter(|_| checks.check_trace_width) {
+        let raw_violations = check_trace_width_in_gerber_data(
+            raw_data, 
+            &layer_type.display_name(), 
+            drc_rules.min_trace_width,
+            boundary
+        );
         
-        // Also check using raw gerber data analysis
-        if let Some(raw_data) = &layer_info.raw_gerber_data {
-            let raw_violations = check_trace_width_in_gerber_data(
-                raw_data, 
-                &layer_type.display_name(), 
-                drc_rules.min_trace_width,
-                &boundary
-            );
-            
-            // Cluster violations to reduce duplicates
-            let clustered = cluster_violations_per_trace(&raw_violations);
-            println!("Raw gerber analysis found {} violations ({} traces) on {}", 
-                raw_violations.len(), clustered.len(), layer_type.display_name());
-            
-            violations.extend(clustered);
-        }
+        // Cluster violations to reduce duplicates
+        let clustered = cluster_violations_per_trace(&raw_violations);
+        println!("Raw gerber analysis found {} violations ({} traces) on {}", 
+            raw_violations.len(), clustered.len(), layer_type.display_name());
+        
+        violations.extend(clustered);
     }
     
-    violations
-}
\ No newline at end of file
+    (violations, trace_quality_issues)
+}
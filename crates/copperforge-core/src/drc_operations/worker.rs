@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use gerber_viewer::BoundingBox;
+use nalgebra::Point2;
+
+use super::types::{run_layer_drc_check, DrcCheckConfig, DrcRules, DrcViolation, LayerInfo, TraceQualityIssue};
+use crate::ecs::{LayerType, TaggedPrimitive};
+
+/// DRC results for one layer, tagged with the content hash they were computed from
+#[derive(Debug, Clone)]
+pub struct LayerDrcResult {
+    pub layer_type: LayerType,
+    pub content_hash: u64,
+    pub violations: Vec<DrcViolation>,
+    pub trace_quality_issues: Vec<TraceQualityIssue>,
+}
+
+/// Snapshot of everything a DRC run needs, so it can run off the UI thread
+#[derive(Debug, Clone)]
+pub struct DrcSnapshot {
+    /// Layers to check with their content hashes
+    pub layers: Vec<(LayerInfo, u64)>,
+    pub boundary: BoundingBox,
+    pub rules: DrcRules,
+    pub checks: DrcCheckConfig,
+    /// Board outline primitives for the edge clearance check
+    pub outline: Option<Arc<Vec<TaggedPrimitive>>>,
+}
+
+/// Messages sent from the DRC worker to the UI
+/// A plain channel, not an egui_mobius signal: DRC also runs headless without the
+/// `gui` feature, and the UI drains it once a frame in `DrcJob::poll`
+#[derive(Debug)]
+pub enum DrcProgress {
+    /// A layer is about to be checked; percent is the fraction of layers done (0.0..=1.0)
+    Layer { layer_name: String, percent: f32 },
+    /// A layer was checked; its results can be shown before the run completes
+    LayerDone(LayerDrcResult),
+    /// All layers checked
+    Finished(Vec<LayerDrcResult>),
+    /// The run was cancelled before completing
+    Cancelled,
+}
+
+/// Final outcome of a background DRC job
+#[derive(Debug)]
+pub enum DrcJobOutcome {
+    Finished(Vec<LayerDrcResult>),
+    Cancelled,
+    /// The worker exited without reporting a result (e.g. panicked)
+    Failed(String),
+}
+
+/// Run the DRC checks of a snapshot layer by layer
+/// `on_layer` is called before each layer and `on_result` after it;
+/// the cancel flag is checked between layers; returns None when cancelled
+pub fn run_drc_snapshot(
+    snapshot: &DrcSnapshot,
+    cancel: &AtomicBool,
+    mut on_layer: impl FnMut(&str, f32),
+    mut on_result: impl FnMut(&LayerDrcResult),
+) -> Option<Vec<LayerDrcResult>> {
+    let total = snapshot.layers.len().max(1) as f32;
+    let mut results = Vec::with_capacity(snapshot.layers.len());
+
+    for (index, (layer_info, content_hash)) in snapshot.layers.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        on_layer(&layer_info.layer_type.display_name(), index as f32 / total);
+
+        let (violations, trace_quality_issues) = run_layer_drc_check(
+            &layer_info.layer_type,
+            layer_info,
+            &snapshot.rules.for_layer(&layer_info.layer_type),
+            &snapshot.checks,
+            &snapshot.boundary,
+            snapshot.outline.as_deref().map(Vec::as_slice),
+        );
+        let result = LayerDrcResult {
+            layer_type: layer_info.layer_type,
+            content_hash: *content_hash,
+            violations,
+            trace_quality_issues,
+        };
+        on_result(&result);
+        results.push(result);
+    }
+
+    if cancel.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    Some(results)
+}
+
+/// Handle to a DRC run executing on a worker thread
+pub struct DrcJob {
+    receiver: Receiver<DrcProgress>,
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+
+    /// PCB file the run was started for; results are discarded if the project changes
+    pub project_key: Option<PathBuf>,
+
+    /// Layers whose cached results are reused (reported when the run completes)
+    pub cached_layers: Vec<LayerType>,
+
+    /// Name of the layer currently being checked
+    pub current_layer: String,
+
+    /// Fraction of layers completed (0.0..=1.0)
+    pub progress: f32,
+
+    /// Only trace quality is checked; the results replace the quality issues, not the DRC results
+    pub trace_quality_only: bool,
+
+    /// Region of a regional run; its results bypass the per-layer cache
+    pub region: Option<super::region::DrcRegion>,
+
+    /// The region in each checked layer's source coordinates, which its results are cut to
+    pub region_bounds: HashMap<LayerType, (Point2<f64>, Point2<f64>)>,
+
+    /// Layers finished since the last `take_completed`
+    completed: Vec<LayerDrcResult>,
+
+    /// Layers of the snapshot not reported finished yet
+    unfinished: Vec<LayerType>,
+}
+
+impl DrcJob {
+    /// Start checking a snapshot on a background thread
+    pub fn spawn(snapshot: DrcSnapshot, project_key: Option<PathBuf>, cached_layers: Vec<LayerType>) -> Self {
+        let unfinished = snapshot.layers.iter().map(|(layer_info, _)| layer_info.layer_type).collect();
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_cancel = cancel.clone();
+
+        let handle = std::thread::spawn(move || {
+            let progress_sender = sender.clone();
+            let result_sender = sender.clone();
+            let result = run_drc_snapshot(&snapshot, &worker_cancel, |layer_name, percent| {
+                let _ = progress_sender.send(DrcProgress::Layer {
+                    layer_name: layer_name.to_string(),
+                    percent,
+                });
+            }, |result| {
+                let _ = result_sender.send(DrcProgress::LayerDone(result.clone()));
+            });
+            let _ = sender.send(match result {
+                Some(results) => DrcProgress::Finished(results),
+                None => DrcProgress::Cancelled,
+            });
+        });
+
+        Self {
+            receiver,
+            cancel,
+            handle: Some(handle),
+            project_key,
+            cached_layers,
+            current_layer: String::new(),
+            progress: 0.0,
+            trace_quality_only: false,
+            region: None,
+            region_bounds: HashMap::new(),
+            completed: Vec::new(),
+            unfinished,
+        }
+    }
+
+    /// Results of the layers finished since the last call, for showing them while the run continues
+    pub fn take_completed(&mut self) -> Vec<LayerDrcResult> {
+        std::mem::take(&mut self.completed)
+    }
+
+    /// Layers the run was to check but has not finished; after a cancel their old results are outdated
+    pub fn unfinished_layers(&self) -> &[LayerType] {
+        &self.unfinished
+    }
+
+    /// Whether finished layers are shown while the run continues: full runs for `project_key` only
+    pub fn shows_layers_as_they_finish(&self, project_key: Option<&PathBuf>) -> bool {
+        !self.trace_quality_only && self.region.is_none() && self.project_key.as_ref() == project_key
+    }
+
+    /// Ask the worker to stop before the next layer
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelling(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Drain progress messages; returns the outcome once the worker is done
+    pub fn poll(&mut self) -> Option<DrcJobOutcome> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(DrcProgress::Layer { layer_name, percent }) => {
+                    self.current_layer = layer_name;
+                    self.progress = percent;
+                }
+                Ok(DrcProgress::LayerDone(result)) => {
+                    self.unfinished.retain(|layer_type| *layer_type != result.layer_type);
+                    self.completed.push(result);
+                }
+                Ok(DrcProgress::Finished(results)) => {
+                    self.progress = 1.0;
+                    self.join();
+                    return Some(DrcJobOutcome::Finished(results));
+                }
+                Ok(DrcProgress::Cancelled) => {
+                    self.join();
+                    return Some(DrcJobOutcome::Cancelled);
+                }
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    self.join();
+                    return Some(DrcJobOutcome::Failed("DRC worker stopped unexpectedly".to_string()));
+                }
+            }
+        }
+    }
+
+    fn join(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for DrcJob {
+    fn drop(&mut self) {
+        // Let a still-running worker exit at the next layer boundary
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+    use gerber_viewer::gerber_parser::parse;
+    use gerber_viewer::GerberLayer;
+
+    fn fixture_layer(layer_type: LayerType, content: &str) -> LayerInfo {
+        let doc = parse(BufReader::new(content.as_bytes())).expect("fixture gerber should parse");
+        LayerInfo::new(layer_type, Some(Arc::new(GerberLayer::new(doc.into_commands()))), Some(content.to_string()), true)
+    }
+
+    fn fixture_layers() -> HashMap<LayerType, LayerInfo> {
+        let mut layers = HashMap::new();
+        layers.insert(LayerType::Copper(1), fixture_layer(LayerType::Copper(1), include_str!("../../../../assets/cmod_s7-F_Cu.gbr")));
+        layers.insert(LayerType::Copper(2), fixture_layer(LayerType::Copper(2), include_str!("../../../../assets/cmod_s7-B_Cu.gbr")));
+        layers.insert(LayerType::MechanicalOutline, fixture_layer(LayerType::MechanicalOutline, include_str!("../../../../assets/cmod_s7-Edge_Cuts.gbr")));
+        layers
+    }
+
+    fn snapshot_of(layers: &HashMap<LayerType, LayerInfo>, rules: &DrcRules) -> DrcSnapshot {
+        DrcSnapshot {
+            layers: layers.values().map(|info| (info.clone(), 0)).collect(),
+            boundary: super::super::types::get_pcb_boundary(layers).expect("fixture has an outline"),
+            rules: rules.clone(),
+            checks: DrcCheckConfig::default(),
+            outline: None,
+        }
+    }
+
+    #[test]
+    fn test_async_drc_matches_sync_path() {
+        let layers = fixture_layers();
+        let rules = DrcRules { min_trace_width: 0.5, ..DrcRules::default() };
+
+        let mut sync_issues = Vec::new();
+        let sync_violations = super::super::run_simple_drc_check(&layers, &rules, &DrcCheckConfig::default(), &mut sync_issues);
+
+        let mut job = DrcJob::spawn(snapshot_of(&layers, &rules), None, Vec::new());
+        let mut completed_layers = 0;
+        let outcome = loop {
+            let outcome = job.poll();
+            completed_layers += job.take_completed().len();
+            if let Some(outcome) = outcome {
+                break outcome;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+
+        let results = match outcome {
+            DrcJobOutcome::Finished(results) => results,
+            other => panic!("expected finished DRC job, got {:?}", other),
+        };
+        // Every layer was also reported on its own as it finished
+        assert_eq!(completed_layers, results.len());
+
+        let mut async_violations: Vec<String> = results.iter()
+            .flat_map(|r| r.violations.iter().map(|v| v.format_message()))
+            .collect();
+        let mut expected: Vec<String> = sync_violations.iter().map(|v| v.format_message()).collect();
+        async_violations.sort();
+        expected.sort();
+        assert_eq!(async_violations, expected);
+
+        let async_issue_count: usize = results.iter().map(|r| r.trace_quality_issues.len()).sum();
+        assert_eq!(async_issue_count, sync_issues.len());
+    }
+
+    #[test]
+    fn test_drc_cancellation_stops_between_layers() {
+        let layers = fixture_layers();
+        let snapshot = snapshot_of(&layers, &DrcRules::default());
+        let cancel = AtomicBool::new(false);
+        let mut layers_started = 0;
+
+        let result = run_drc_snapshot(&snapshot, &cancel, |_, _| {
+            layers_started += 1;
+            cancel.store(true, Ordering::Relaxed);
+        }, |_| {});
+
+        assert!(result.is_none());
+        assert_eq!(layers_started, 1);
+    }
+
+    #[test]
+    fn test_cancelled_job_reports_the_layers_it_did_not_finish() {
+        let layers = fixture_layers();
+        let mut job = DrcJob::spawn(snapshot_of(&layers, &DrcRules::default()), None, Vec::new());
+        assert_eq!(job.unfinished_layers().len(), layers.len());
+        job.cancel();
+
+        let mut finished: Vec<LayerType> = Vec::new();
+        let outcome = loop {
+            let outcome = job.poll();
+            finished.extend(job.take_completed().iter().map(|result| result.layer_type));
+            if let Some(outcome) = outcome {
+                break outcome;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+
+        // Usually stopped before the first layer; whatever finished is not reported unfinished
+        assert!(matches!(outcome, DrcJobOutcome::Cancelled), "{:?}", outcome);
+        assert!(!job.unfinished_layers().is_empty());
+        assert!(finished.iter().all(|layer_type| !job.unfinished_layers().contains(layer_type)));
+        assert_eq!(finished.len() + job.unfinished_layers().len(), layers.len());
+    }
+}
@@ -0,0 +1,545 @@
+//! Aperture macros (%AM)
+//!
+//! KiCad writes rounded-rectangle and custom pads as aperture macros. A macro
+//! body is a list of primitive statements whose modifiers are arithmetic
+//! expressions over the `$n` parameters of the %ADD definition that uses it.
+//! Instantiating a macro evaluates those expressions into circles and polygons
+//! relative to the flash point, which give the pad its real outline and size.
+//! The body and parameters are kept as written so export can pass them through.
+//! Regular polygon apertures (P) get an outline the same way.
+//!
+//! Moiré (6) and thermal (7) primitives are reduced to their outer circle.
+
+use std::collections::HashMap;
+
+use nalgebra::{Matrix3, Point2, Vector2};
+use serde::{Deserialize, Serialize};
+
+/// Segments a circle is cut into when a macro shape is needed as a polygon
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// One evaluated macro primitive, in mm relative to the flash point
+#[derive(Debug, Clone, PartialEq)]
+pub enum MacroShape {
+    Circle { center: Point2<f64>, diameter: f64, dark: bool },
+    /// Closed polygon (closing edge implied)
+    Polygon { points: Vec<Point2<f64>>, dark: bool },
+}
+
+impl MacroShape {
+    /// Exposure on: the shape adds to the pad rather than cutting into it
+    pub fn is_dark(&self) -> bool {
+        match self {
+            Self::Circle { dark, .. } | Self::Polygon { dark, .. } => *dark,
+        }
+    }
+
+    /// Axis-aligned bounds (min, max)
+    pub fn bounds(&self) -> (Point2<f64>, Point2<f64>) {
+        match self {
+            Self::Circle { center, diameter, .. } => {
+                let r = Vector2::new(diameter / 2.0, diameter / 2.0);
+                (center - r, center + r)
+            }
+            Self::Polygon { points, .. } => points.iter().fold(
+                (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+                |(min, max), p| (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y))),
+            ),
+        }
+    }
+
+    /// Outline as a polygon; circles are cut into `CIRCLE_SEGMENTS` chords
+    pub fn to_polygon(&self) -> Vec<Point2<f64>> {
+        match self {
+            Self::Circle { center, diameter, .. } => (0..CIRCLE_SEGMENTS)
+                .map(|i| {
+                    let angle = std::f64::consts::TAU * i as f64 / CIRCLE_SEGMENTS as f64;
+                    center + Vector2::new(angle.cos(), angle.sin()) * (diameter / 2.0)
+                })
+                .collect(),
+            Self::Polygon { points, .. } => points.clone(),
+        }
+    }
+
+    /// How far a ray from `origin` along the unit `direction` runs inside the shape;
+    /// 0 when it does not start inside
+    fn depth_along(&self, origin: Point2<f64>, direction: Vector2<f64>) -> f64 {
+        let start = origin + direction * 1e-9;
+        if self.distance_to(start) > 0.0 {
+            return 0.0;
+        }
+        match self {
+            Self::Circle { center, diameter, .. } => {
+                let offset = start - center;
+                let along = offset.dot(&direction);
+                let discriminant = along * along - offset.norm_squared() + (diameter / 2.0).powi(2);
+                (-along + discriminant.max(0.0).sqrt()).max(0.0)
+            }
+            Self::Polygon { points, .. } => (0..points.len())
+                .filter_map(|i| {
+                    let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                    let edge = b - a;
+                    let cross = direction.perp(&edge);
+                    if cross.abs() < 1e-12 {
+                        return None;
+                    }
+                    let t = (a - start).perp(&edge) / cross;
+                    let u = (a - start).perp(&direction) / cross;
+                    (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+                })
+                .fold(f64::INFINITY, f64::min),
+        }
+    }
+
+    /// Distance from `point` (relative to the flash point) to the shape; 0 inside
+    pub fn distance_to(&self, point: Point2<f64>) -> f64 {
+        match self {
+            Self::Circle { center, diameter, .. } => ((point - center).norm() - diameter / 2.0).max(0.0),
+            Self::Polygon { points, .. } => {
+                let mut inside = false;
+                let mut distance = f64::INFINITY;
+                for i in 0..points.len() {
+                    let (a, b) = (points[i], points[(i + 1) % points.len()]);
+                    if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+                        inside = !inside;
+                    }
+                    let edge = b - a;
+                    let length_sq = edge.norm_squared();
+                    let t = if length_sq > 0.0 { ((point - a).dot(&edge) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+                    distance = distance.min((point - (a + edge * t)).norm());
+                }
+                if inside { 0.0 } else { distance }
+            }
+        }
+    }
+}
+
+/// A macro definition and the parameters an aperture instantiates it with, as written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroDefinition {
+    pub name: String,
+    /// Primitive and variable statements of the %AM block
+    pub body: Vec<String>,
+    /// Modifiers of the %ADD definition (e.g. "0.25X-0.45X0.4")
+    pub modifiers: String,
+    /// mm per unit of the source file, which body and modifiers are written in
+    pub unit_scale: f64,
+}
+
+/// Exact shape of a macro or polygon aperture, which a flash's width and height only bound
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApertureOutline {
+    /// Primitives in mm relative to the flash point, in definition order
+    pub shapes: Vec<MacroShape>,
+    /// Macro the shapes were evaluated from; None for polygon apertures
+    pub definition: Option<MacroDefinition>,
+}
+
+impl ApertureOutline {
+    /// Evaluate a macro body with the parameters in `modifiers`; None when nothing is exposed
+    pub fn from_macro(name: &str, body: &[String], modifiers: &str, unit_scale: f64) -> Option<Self> {
+        let mut variables: HashMap<u32, f64> = modifiers
+            .split('X')
+            .enumerate()
+            .filter_map(|(i, value)| value.trim().parse::<f64>().ok().map(|value| (i as u32 + 1, value)))
+            .collect();
+        let mut shapes = Vec::new();
+        for statement in body {
+            // Primitive 0 is a comment, whatever follows it
+            let code: String = statement.trim().chars().take_while(char::is_ascii_digit).collect();
+            if code == "0" {
+                continue;
+            }
+            let statement: String = statement.chars().filter(|c| !c.is_whitespace()).collect();
+            if let Some((variable, expression)) = statement.strip_prefix('$').and_then(|rest| rest.split_once('=')) {
+                if let (Ok(index), Some(value)) = (variable.parse::<u32>(), evaluate(expression, &variables)) {
+                    variables.insert(index, value);
+                }
+                continue;
+            }
+            let values: Option<Vec<f64>> = statement.split(',').map(|term| evaluate(term, &variables)).collect();
+            if let Some(shape) = values.and_then(|values| primitive_shape(&values)) {
+                shapes.push(scale_shape(shape, unit_scale));
+            }
+        }
+        shapes.iter().any(MacroShape::is_dark).then(|| Self {
+            shapes,
+            definition: Some(MacroDefinition {
+                name: name.to_string(),
+                body: body.to_vec(),
+                modifiers: modifiers.to_string(),
+                unit_scale,
+            }),
+        })
+    }
+
+    /// Regular polygon aperture (P) of circumscribed `diameter`, first vertex at `rotation` degrees
+    pub fn regular_polygon(diameter: f64, vertices: usize, rotation: f64) -> Self {
+        let shape = primitive_shape(&[5.0, 1.0, vertices as f64, 0.0, 0.0, diameter, rotation])
+            .expect("polygon primitive always has a shape");
+        Self { shapes: vec![shape], definition: None }
+    }
+
+    /// Bounds (min, max) of the exposed primitives, relative to the flash point
+    pub fn bounds(&self) -> (Point2<f64>, Point2<f64>) {
+        self.shapes.iter().filter(|shape| shape.is_dark()).map(MacroShape::bounds).fold(
+            (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |(min, max), (a, b)| (Point2::new(min.x.min(a.x), min.y.min(a.y)), Point2::new(max.x.max(b.x), max.y.max(b.y))),
+        )
+    }
+
+    /// Width and height of a box centred on the flash point that holds every exposed primitive
+    pub fn centered_size(&self) -> (f64, f64) {
+        let (min, max) = self.bounds();
+        (2.0 * min.x.abs().max(max.x.abs()), 2.0 * min.y.abs().max(max.y.abs()))
+    }
+
+    /// Outline with the linear part of `matrix` (rotation, mirroring, scale) applied
+    /// The result no longer matches the macro text, so it carries no definition
+    pub fn transformed(&self, matrix: &Matrix3<f64>) -> Self {
+        let scale = matrix.fixed_view::<2, 2>(0, 0).determinant().abs().sqrt();
+        let apply = |point: &Point2<f64>| Point2::from(matrix.transform_vector(&point.coords));
+        let shapes = self.shapes.iter().map(|shape| match shape {
+            MacroShape::Circle { center, diameter, dark } => MacroShape::Circle { center: apply(center), diameter: diameter * scale, dark: *dark },
+            MacroShape::Polygon { points, dark } => MacroShape::Polygon { points: points.iter().map(apply).collect(), dark: *dark },
+        }).collect();
+        Self { shapes, definition: None }
+    }
+
+    /// Whether `point` (relative to the flash point) is exposed: the last primitive
+    /// covering it decides, so cut-outs remove what earlier primitives exposed
+    pub fn contains(&self, point: Point2<f64>) -> bool {
+        self.shapes.iter().fold(false, |inside, shape| if shape.distance_to(point) == 0.0 { shape.is_dark() } else { inside })
+    }
+
+    /// Outline with every exposed primitive grown by `distance` and every cut-out shrunk
+    /// by it (the reverse when negative); None when no exposed primitive is left
+    /// A shrinking polygon edge that another primitive of the same exposure covers
+    /// moves only as far as it reaches past that cover, so the edge strokes and
+    /// corner circles of a rounded rectangle inset into the exact smaller pad.
+    pub fn grown(&self, distance: f64) -> Option<Self> {
+        let shapes: Vec<MacroShape> = self.shapes.iter().enumerate().filter_map(|(index, shape)| {
+            let distance = if shape.is_dark() { distance } else { -distance };
+            match shape {
+                MacroShape::Circle { center, diameter, dark } => {
+                    let diameter = diameter + 2.0 * distance;
+                    (diameter > 0.0).then_some(MacroShape::Circle { center: *center, diameter, dark: *dark })
+                }
+                MacroShape::Polygon { points, dark } => offset_edges(points, |midpoint, outward| {
+                    if distance >= 0.0 {
+                        return distance;
+                    }
+                    let cover = self.shapes.iter().enumerate()
+                        .filter(|(other, shape)| *other != index && shape.is_dark() == *dark)
+                        .map(|(_, shape)| shape.depth_along(midpoint, outward))
+                        .fold(0.0, f64::max);
+                    (distance + cover).min(0.0)
+                })
+                .map(|points| MacroShape::Polygon { points, dark: *dark }),
+            }
+        }).collect();
+        shapes.iter().any(MacroShape::is_dark).then_some(Self { shapes, definition: None })
+    }
+
+    /// Distance from `point` (relative to the flash point) to the nearest exposed primitive
+    /// Cut-outs (exposure off) are ignored, so the distance is never overestimated
+    pub fn distance_to(&self, point: Point2<f64>) -> f64 {
+        self.shapes
+            .iter()
+            .filter(|shape| shape.is_dark())
+            .map(|shape| shape.distance_to(point))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+fn scale_shape(shape: MacroShape, scale: f64) -> MacroShape {
+    match shape {
+        MacroShape::Circle { center, diameter, dark } => MacroShape::Circle { center: center * scale, diameter: diameter * scale, dark },
+        MacroShape::Polygon { points, dark } => MacroShape::Polygon { points: points.into_iter().map(|p| p * scale).collect(), dark },
+    }
+}
+
+/// Polygon with every edge moved outward by `distance` (inward when negative),
+/// mitred where neighbouring edges meet; None when an edge turns over or the
+/// polygon collapses
+pub fn offset_polygon(points: &[Point2<f64>], distance: f64) -> Option<Vec<Point2<f64>>> {
+    offset_edges(points, |_, _| distance)
+}
+
+/// `offset_polygon` with the distance of each edge given by `distance(midpoint, outward normal)`
+fn offset_edges(points: &[Point2<f64>], distance: impl Fn(Point2<f64>, Vector2<f64>) -> f64) -> Option<Vec<Point2<f64>>> {
+    let mut points: Vec<Point2<f64>> = points.to_vec();
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    let area = signed_area(&points);
+    if n < 3 || area == 0.0 {
+        return None;
+    }
+    // Outward is to the right of each edge on a counter-clockwise polygon
+    let edges: Vec<(Point2<f64>, Vector2<f64>)> = (0..n).map(|i| {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        let direction = (b - a).normalize();
+        let outward = Vector2::new(direction.y, -direction.x) * area.signum();
+        (a + outward * distance(nalgebra::center(&a, &b), outward), direction)
+    }).collect();
+    let offset: Vec<Point2<f64>> = (0..n).map(|i| {
+        let (previous_start, previous_direction) = edges[(i + n - 1) % n];
+        let (start, direction) = edges[i];
+        let cross = previous_direction.perp(&direction);
+        if cross.abs() < 1e-12 {
+            return start;
+        }
+        previous_start + previous_direction * ((start - previous_start).perp(&direction) / cross)
+    }).collect();
+    let turned_over = (0..n).any(|i| (offset[(i + 1) % n] - offset[i]).dot(&edges[i].1) <= 0.0);
+    (!turned_over && signed_area(&offset) * area > 0.0).then_some(offset)
+}
+
+/// Signed polygon area (positive when counter-clockwise)
+fn signed_area(points: &[Point2<f64>]) -> f64 {
+    let n = points.len();
+    (0..n).map(|i| points[i].coords.perp(&points[(i + 1) % n].coords)).sum::<f64>() / 2.0
+}
+
+fn rotate(point: Point2<f64>, degrees: f64) -> Point2<f64> {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    Point2::new(point.x * cos - point.y * sin, point.x * sin + point.y * cos)
+}
+
+/// Corners of a w × h rectangle centred on `center`, before rotation
+fn rectangle(center: Point2<f64>, width: f64, height: f64) -> Vec<Point2<f64>> {
+    let (hw, hh) = (width / 2.0, height / 2.0);
+    vec![
+        center + Vector2::new(-hw, -hh),
+        center + Vector2::new(hw, -hh),
+        center + Vector2::new(hw, hh),
+        center + Vector2::new(-hw, hh),
+    ]
+}
+
+/// Shape of one primitive statement from its evaluated modifiers (code first)
+/// Rotations are about the macro origin, as the Gerber specification defines them
+fn primitive_shape(values: &[f64]) -> Option<MacroShape> {
+    let code = *values.first()? as u32;
+    let modifier = |i: usize| values.get(i).copied().unwrap_or(0.0);
+    let dark = modifier(1) != 0.0;
+    let polygon = |points: Vec<Point2<f64>>, rotation: f64, dark: bool| MacroShape::Polygon {
+        points: points.into_iter().map(|p| rotate(p, rotation)).collect(),
+        dark,
+    };
+    match code {
+        1 => Some(MacroShape::Circle {
+            center: rotate(Point2::new(modifier(3), modifier(4)), modifier(5)),
+            diameter: modifier(2),
+            dark,
+        }),
+        // Vector line: a stroke with square ends
+        2 | 20 => {
+            let (start, end) = (Point2::new(modifier(3), modifier(4)), Point2::new(modifier(5), modifier(6)));
+            let direction = end - start;
+            let length = direction.norm();
+            if length <= 0.0 {
+                return None;
+            }
+            let normal = Vector2::new(-direction.y, direction.x) / length * (modifier(2) / 2.0);
+            Some(polygon(vec![start + normal, end + normal, end - normal, start - normal], modifier(7), dark))
+        }
+        21 => Some(polygon(rectangle(Point2::new(modifier(4), modifier(5)), modifier(2), modifier(3)), modifier(6), dark)),
+        // Lower-left line (deprecated): corner instead of centre
+        22 => {
+            let (width, height) = (modifier(2), modifier(3));
+            let center = Point2::new(modifier(4) + width / 2.0, modifier(5) + height / 2.0);
+            Some(polygon(rectangle(center, width, height), modifier(6), dark))
+        }
+        4 => {
+            let vertices = modifier(2) as usize;
+            let coordinates = values.get(3..3 + 2 * (vertices + 1))?;
+            let mut points: Vec<Point2<f64>> = coordinates.chunks(2).map(|xy| Point2::new(xy[0], xy[1])).collect();
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            (points.len() >= 3).then(|| polygon(points, modifier(3 + 2 * (vertices + 1)), dark))
+        }
+        5 => {
+            let vertices = (modifier(2) as usize).max(3);
+            let center = Point2::new(modifier(3), modifier(4));
+            let radius = modifier(5) / 2.0;
+            let points = (0..vertices)
+                .map(|i| {
+                    let angle = std::f64::consts::TAU * i as f64 / vertices as f64;
+                    center + Vector2::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect();
+            Some(polygon(points, modifier(6), dark))
+        }
+        // Moiré and thermal have no exposure modifier: centre, outer diameter, ..., rotation last
+        6 | 7 => Some(MacroShape::Circle {
+            center: rotate(Point2::new(modifier(1), modifier(2)), values.last().copied().unwrap_or(0.0)),
+            diameter: modifier(3),
+            dark: true,
+        }),
+        _ => None,
+    }
+}
+
+/// Evaluate a macro arithmetic expression: numbers, `$n`, + - x / and parentheses
+/// Undefined variables read as 0, as the specification requires
+pub fn evaluate(expression: &str, variables: &HashMap<u32, f64>) -> Option<f64> {
+    let tokens: Vec<char> = expression.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut position = 0;
+    let value = parse_sum(&tokens, &mut position, variables)?;
+    (position == tokens.len()).then_some(value)
+}
+
+fn parse_sum(tokens: &[char], position: &mut usize, variables: &HashMap<u32, f64>) -> Option<f64> {
+    let mut value = parse_product(tokens, position, variables)?;
+    while let Some(&operator) = tokens.get(*position) {
+        if operator != '+' && operator != '-' {
+            break;
+        }
+        *position += 1;
+        let rhs = parse_product(tokens, position, variables)?;
+        value = if operator == '+' { value + rhs } else { value - rhs };
+    }
+    Some(value)
+}
+
+fn parse_product(tokens: &[char], position: &mut usize, variables: &HashMap<u32, f64>) -> Option<f64> {
+    let mut value = parse_factor(tokens, position, variables)?;
+    while let Some(&operator) = tokens.get(*position) {
+        if !matches!(operator, 'x' | 'X' | '/') {
+            break;
+        }
+        *position += 1;
+        let rhs = parse_factor(tokens, position, variables)?;
+        value = if operator == '/' { value / rhs } else { value * rhs };
+    }
+    Some(value)
+}
+
+fn parse_factor(tokens: &[char], position: &mut usize, variables: &HashMap<u32, f64>) -> Option<f64> {
+    match *tokens.get(*position)? {
+        '-' => {
+            *position += 1;
+            Some(-parse_factor(tokens, position, variables)?)
+        }
+        '+' => {
+            *position += 1;
+            parse_factor(tokens, position, variables)
+        }
+        '(' => {
+            *position += 1;
+            let value = parse_sum(tokens, position, variables)?;
+            if tokens.get(*position) != Some(&')') {
+                return None;
+            }
+            *position += 1;
+            Some(value)
+        }
+        '$' => {
+            *position += 1;
+            let start = *position;
+            while tokens.get(*position).is_some_and(|c| c.is_ascii_digit()) {
+                *position += 1;
+            }
+            let index: String = tokens[start..*position].iter().collect();
+            Some(variables.get(&index.parse::<u32>().ok()?).copied().unwrap_or(0.0))
+        }
+        _ => {
+            let start = *position;
+            while tokens.get(*position).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+                *position += 1;
+            }
+            let number: String = tokens[start..*position].iter().collect();
+            number.parse::<f64>().ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expressions_follow_precedence_and_variables() {
+        let variables = HashMap::from([(1, 0.5), (2, 3.0)]);
+        assert_eq!(evaluate("$1+$1", &variables), Some(1.0));
+        assert_eq!(evaluate("1+$2x2", &variables), Some(7.0));
+        assert_eq!(evaluate("(1+$2)x2", &variables), Some(8.0));
+        assert_eq!(evaluate("-$2/2", &variables), Some(-1.5));
+        assert_eq!(evaluate("$9", &variables), Some(0.0));
+        assert_eq!(evaluate("1+", &variables), None);
+    }
+
+    #[test]
+    fn test_macro_with_variables_and_rotation() {
+        let body = vec![
+            "0 centre line rotated a quarter turn".to_string(),
+            "$3=$1x2".to_string(),
+            "21,1,$3,$2,0,0,90".to_string(),
+            "1,0,0.2,0,0".to_string(),
+        ];
+        let outline = ApertureOutline::from_macro("Bar", &body, "1X0.5", 1.0).unwrap();
+        assert_eq!(outline.shapes.len(), 2);
+        assert!(!outline.shapes[1].is_dark());
+
+        // 2 × 0.5 turned by 90° stands 0.5 wide and 2 tall; the cut-out does not change the size
+        let (width, height) = outline.centered_size();
+        assert!((width - 0.5).abs() < 1e-9 && (height - 2.0).abs() < 1e-9);
+        assert!((outline.distance_to(Point2::new(0.0, 1.5)) - 0.5).abs() < 1e-9);
+        assert!(outline.contains(Point2::new(0.0, 0.5)));
+        assert!(!outline.contains(Point2::new(0.0, 0.05)));
+
+        assert!(ApertureOutline::from_macro("Empty", &["1,0,1,0,0".to_string()], "", 1.0).is_none());
+    }
+
+    #[test]
+    fn test_inset_moves_every_edge_and_grows_cutouts() {
+        // L-shape: the inner corner moves diagonally, every edge stays parallel
+        let l_shape = [
+            Point2::new(0.0, 0.0), Point2::new(2.0, 0.0), Point2::new(2.0, 1.0),
+            Point2::new(1.0, 1.0), Point2::new(1.0, 2.0), Point2::new(0.0, 2.0),
+        ];
+        let inset = offset_polygon(&l_shape, -0.1).unwrap();
+        assert!((inset[0] - Point2::new(0.1, 0.1)).norm() < 1e-9);
+        assert!((inset[3] - Point2::new(0.9, 0.9)).norm() < 1e-9);
+        assert!(offset_polygon(&l_shape, -0.6).is_none());
+
+        // Rounded rectangle as KiCad writes it: the inset keeps the corners round
+        let body: Vec<String> = [
+            "4,1,4,$2,$3,$4,$5,$6,$7,$8,$9,$2,$3,0", "1,1,$1+$1,$2,$3", "1,1,$1+$1,$4,$5", "1,1,$1+$1,$6,$7",
+            "1,1,$1+$1,$8,$9", "20,1,$1+$1,$2,$3,$4,$5,0", "20,1,$1+$1,$4,$5,$6,$7,0",
+            "20,1,$1+$1,$6,$7,$8,$9,0", "20,1,$1+$1,$8,$9,$2,$3,0",
+        ].map(String::from).to_vec();
+        let pad = ApertureOutline::from_macro("RoundRect", &body, "0.25X-0.45X-0.35X0.45X-0.35X0.45X0.35X-0.45X0.35", 1.0).unwrap();
+        let inset = pad.grown(-0.1).unwrap();
+        let (width, height) = inset.centered_size();
+        assert!((width - 1.2).abs() < 1e-9 && (height - 1.0).abs() < 1e-9);
+        assert!(inset.contains(Point2::new(0.59, 0.0)) && inset.contains(Point2::new(0.45, 0.49)));
+        assert!(!inset.contains(Point2::new(0.59, 0.49)));
+        // Past the corner radius only the sharp-cornered core is left, 0.05 in from the corners
+        let (width, height) = pad.grown(-0.3).unwrap().centered_size();
+        assert!((width - 0.8).abs() < 1e-9 && (height - 0.6).abs() < 1e-9);
+
+        let body = vec!["21,1,1,1,0,0,0".to_string(), "1,0,0.2,0,0".to_string()];
+        let outline = ApertureOutline::from_macro("Washer", &body, "", 1.0).unwrap().grown(-0.1).unwrap();
+        assert!(outline.definition.is_none());
+        let (width, height) = outline.centered_size();
+        assert!((width - 0.8).abs() < 1e-9 && (height - 0.8).abs() < 1e-9);
+        assert!(!outline.contains(Point2::new(0.15, 0.0)));
+        assert!(outline.contains(Point2::new(0.25, 0.0)));
+        assert!(outline.grown(-0.5).is_none());
+    }
+
+    #[test]
+    fn test_regular_polygon_first_vertex_follows_rotation() {
+        let outline = ApertureOutline::regular_polygon(2.0, 4, 45.0);
+        let MacroShape::Polygon { points, dark: true } = &outline.shapes[0] else { unreachable!() };
+        assert_eq!(points.len(), 4);
+        let expected = Point2::new(std::f64::consts::FRAC_1_SQRT_2, std::f64::consts::FRAC_1_SQRT_2);
+        assert!((points[0] - expected).norm() < 1e-9);
+    }
+}
@@ -4,15 +4,50 @@ use crate::display::manager::MirroringSettings;
 use crate::display::VectorOffset;
 use egui::Color32;
 use std::path::PathBuf;
+use std::sync::Arc;
 use super::LayerType; // Import LayerType from types module
 
 // Note: kicad-ecs components are for individual PCB components (R1, C2, etc.)
 // while KiForge works with entire layers. We might use kicad-ecs later for
 // component-level analysis, but for now we need layer-level components.
 
-// Core gerber data wrapper
+// Core gerber data wrapper, shared so DRC snapshots can take the layer without copying it
 #[derive(Component)]
-pub struct GerberData(pub GerberLayer);
+pub struct GerberData(pub Arc<GerberLayer>);
+
+// Content hash of the layer's gerber source, used to skip unchanged layers in DRC
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LayerContentHash(pub u64);
+
+impl LayerContentHash {
+    /// Hash raw gerber source text
+    pub fn from_content(content: &str) -> Self {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+    
+    /// Fallback hash when no raw source is available: the parsed layer's full debug
+    /// form, so any change to its commands changes the hash, not just to its extents
+    pub fn from_layer(gerber_layer: &GerberLayer) -> Self {
+        use std::fmt::Write;
+        use std::hash::Hasher;
+
+        // Streams the formatted layer into the hasher instead of building the string
+        struct HashWriter(std::collections::hash_map::DefaultHasher);
+        impl Write for HashWriter {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                self.0.write(s.as_bytes());
+                Ok(())
+            }
+        }
+
+        let mut writer = HashWriter(std::collections::hash_map::DefaultHasher::new());
+        let _ = write!(writer, "{:?}", gerber_layer);
+        Self(writer.0.finish())
+    }
+}
 
 // Layer identification
 #[derive(Component, Clone, Debug)]
@@ -30,6 +65,8 @@ pub struct Transform {
     pub scale: f64,
     pub mirroring: MirroringSettings,
     pub origin: VectorOffset,
+    /// Per-layer alignment added on top of the view position and rotation
+    pub nudge: super::LayerNudge,
 }
 
 // Gerber image transform component for legacy transformations
@@ -54,6 +91,7 @@ impl Default for Transform {
             scale: 1.0,
             mirroring: MirroringSettings { x: false, y: false },
             origin: VectorOffset { x: 0.0, y: 0.0 },
+            nudge: super::LayerNudge::default(),
         }
     }
 }
@@ -82,6 +120,13 @@ pub struct RenderProperties {
     pub z_order: i32,
 }
 
+impl RenderProperties {
+    /// Layer color as plain unmultiplied RGBA, for callers that don't use egui
+    pub fn rgba(&self) -> [u8; 4] {
+        self.color.to_srgba_unmultiplied()
+    }
+}
+
 // Bounding box cache
 #[derive(Component, Clone, Debug)]
 pub struct BoundingBoxCache {
@@ -94,4 +139,22 @@ pub struct RequiresDrc;
 
 // Marker for selected layers
 #[derive(Component)]
-pub struct Selected;
\ No newline at end of file
+pub struct Selected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_hash_sees_edits_inside_the_extents() {
+        let layer = |content: &str| crate::ecs::parse_gerber(content).0.expect("gerber parses");
+        let header = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.200000*%\nD10*\n";
+        // Same 10 x 10 mm extents, the inner trace moved
+        let before = layer(&format!("{}X0Y0D02*\nX10000000Y10000000D01*\nX2000000Y5000000D02*\nX4000000Y5000000D01*\nM02*\n", header));
+        let after = layer(&format!("{}X0Y0D02*\nX10000000Y10000000D01*\nX2000000Y6000000D02*\nX4000000Y6000000D01*\nM02*\n", header));
+        let (a, b) = (before.bounding_box(), after.bounding_box());
+        assert_eq!((a.min, a.max), (b.min, b.max));
+        assert_ne!(LayerContentHash::from_layer(&before), LayerContentHash::from_layer(&after));
+        assert_eq!(LayerContentHash::from_layer(&before), LayerContentHash::from_layer(&before.clone()));
+    }
+}
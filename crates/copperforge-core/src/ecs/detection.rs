@@ -1,6 +1,9 @@
 use regex::Regex;
 use std::collections::HashMap;
-use super::{LayerType, Side}; // Use LayerType and Side from ECS types module
+use std::sync::LazyLock;
+use super::{LayerType, Side, MAX_INNER_COPPER_LAYERS}; // Use LayerType and Side from ECS types module
+
+static FILE_FUNCTION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"%TF\.FileFunction,([^*]*)\*%").unwrap());
 
 /// Common layer name patterns found across different PCB design tools
 #[derive(Debug)]
@@ -42,26 +45,20 @@ impl LayerDetector {
             Regex::new(r"(?i)[-_\.]l2\.gbr$").unwrap(),
         ]);
         
-        // Inner layer patterns (for multi-layer boards)
-        // Layer 3
-        patterns.insert(LayerType::Copper(3), vec![
-            Regex::new(r"(?i)[-_\.]In1[-_\.]?Cu\.gbr$").unwrap(),
-            Regex::new(r"(?i)[-_\.]inner1\.gbr$").unwrap(),
-            Regex::new(r"(?i)[-_\.]layer3\.gbr$").unwrap(),
-            Regex::new(r"(?i)[-_\.]l3\.gbr$").unwrap(),
-            Regex::new(r"(?i)\.g1$").unwrap(), // Gerber inner 1
-        ]);
-        
-        // Layer 4
-        patterns.insert(LayerType::Copper(4), vec![
-            Regex::new(r"(?i)[-_\.]In2[-_\.]?Cu\.gbr$").unwrap(),
-            Regex::new(r"(?i)[-_\.]inner2\.gbr$").unwrap(),
-            Regex::new(r"(?i)[-_\.]layer4\.gbr$").unwrap(),
-            Regex::new(r"(?i)[-_\.]l4\.gbr$").unwrap(),
-            Regex::new(r"(?i)\.g2$").unwrap(), // Gerber inner 2
-        ]);
-        
-        // Can add more inner layers as needed...
+        // Inner layer patterns (for multi-layer boards): In1.Cu -> Copper(3), In2.Cu -> Copper(4), ...
+        for inner in 1..=MAX_INNER_COPPER_LAYERS {
+            let mut inner_patterns = vec![
+                Regex::new(&format!(r"(?i)[-_\.]In{}[-_\.]?Cu\.gbr$", inner)).unwrap(),
+                Regex::new(&format!(r"(?i)[-_\.]inner{}\.gbr$", inner)).unwrap(),
+                Regex::new(&format!(r"(?i)\.g{}$", inner)).unwrap(), // Gerber inner N
+            ];
+            // layer3/l3 and layer4/l4 have always meant the first two inner layers
+            if inner <= 2 {
+                inner_patterns.push(Regex::new(&format!(r"(?i)[-_\.]layer{}\.gbr$", inner + 2)).unwrap());
+                inner_patterns.push(Regex::new(&format!(r"(?i)[-_\.]l{}\.gbr$", inner + 2)).unwrap());
+            }
+            patterns.insert(LayerType::inner_copper(inner), inner_patterns);
+        }
         
         // Top Silkscreen patterns
         patterns.insert(LayerType::Silkscreen(Side::Top), vec![
@@ -152,6 +149,38 @@ impl LayerDetector {
         None
     }
     
+    /// Layer type from the Gerber X2 `%TF.FileFunction` attribute, if the file has one
+    /// Copper layers are numbered from the top, so `Copper,L2,Inr` is In1.Cu
+    pub fn detect_from_attributes(&self, content: &str) -> Option<LayerType> {
+        let captures = FILE_FUNCTION.captures(content)?;
+        let fields: Vec<&str> = captures[1].split(',').map(str::trim).collect();
+        let side = |field: Option<&&str>| match field.map(|f| f.to_ascii_lowercase()).as_deref() {
+            Some("top") => Some(Side::Top),
+            Some("bot") => Some(Side::Bottom),
+            _ => None,
+        };
+        match fields.first().map(|f| f.to_ascii_lowercase()).as_deref()? {
+            "copper" => {
+                let position = fields.get(2).map(|f| f.to_ascii_lowercase());
+                match position.as_deref()? {
+                    "top" => Some(LayerType::Copper(1)),
+                    "bot" => Some(LayerType::Copper(2)),
+                    "inr" => {
+                        let number: u8 = fields.get(1)?.trim_start_matches(['L', 'l']).parse().ok()?;
+                        let inner = number.checked_sub(1).filter(|inner| (1..=MAX_INNER_COPPER_LAYERS).contains(inner))?;
+                        Some(LayerType::inner_copper(inner))
+                    }
+                    _ => None,
+                }
+            }
+            "legend" => side(fields.get(1)).map(LayerType::Silkscreen),
+            "soldermask" => side(fields.get(1)).map(LayerType::Soldermask),
+            "paste" => side(fields.get(1)).map(LayerType::Paste),
+            "profile" => Some(LayerType::MechanicalOutline),
+            _ => None,
+        }
+    }
+    
     /// Get all patterns for a specific layer type (for display/debugging)
     pub fn get_patterns_for_layer(&self, layer_type: LayerType) -> Vec<String> {
         self.patterns.get(&layer_type)
@@ -162,10 +191,188 @@ impl LayerDetector {
     }
 }
 
+/// Bin size used when grouping paste aperture areas (mm²)
+pub const PASTE_HISTOGRAM_BIN_MM2: f64 = 0.001;
+
+/// Group the flashed apertures of a paste layer by opening area
+/// Returns (area in mm², count) pairs sorted by area. gerber_viewer's GerberLayer
+/// does not expose its primitives, so this works on the layer's flash table
+/// (see `get_flash_primitives`). Macro apertures have no known area and are skipped.
+pub fn paste_aperture_histogram(paste: &[super::FlashPrimitive]) -> Vec<(f64, usize)> {
+    let mut bins: HashMap<i64, usize> = HashMap::new();
+    for flash in paste {
+        let area = flash.area();
+        if area <= 0.0 {
+            continue;
+        }
+        *bins.entry((area / PASTE_HISTOGRAM_BIN_MM2).round() as i64).or_insert(0) += 1;
+    }
+
+    let mut histogram: Vec<(f64, usize)> = bins
+        .into_iter()
+        .map(|(bin, count)| (bin as f64 * PASTE_HISTOGRAM_BIN_MM2, count))
+        .collect();
+    histogram.sort_by(|a, b| a.0.total_cmp(&b.0));
+    histogram
+}
+
+/// Holes of one drill diameter, the plated and non-plated ones counted apart
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrillReportRow {
+    /// mm, rounded to the micron
+    pub diameter: f64,
+    pub plated: usize,
+    pub non_plated: usize,
+    /// Index of the symbol marking these holes, see `drill_symbol_style`
+    pub symbol: usize,
+}
+
+impl DrillReportRow {
+    pub fn count(&self) -> usize {
+        self.plated + self.non_plated
+    }
+}
+
+/// Drill report: one row per unique hole diameter, smallest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DrillReport {
+    pub rows: Vec<DrillReportRow>,
+}
+
+/// Kinds of drill symbol shapes
+pub const DRILL_SYMBOL_SHAPES: usize = 8;
+
+/// Gap between the rings around a drill symbol, as a fraction of its radius
+pub const DRILL_SYMBOL_RING_SPACING: f64 = 0.35;
+
+/// How a drill symbol is drawn: (shape, filled, rings around it)
+/// The shapes come outlined, then filled, then again with one more ring each
+/// round, so every size has a symbol of its own however many there are
+pub fn drill_symbol_style(symbol: usize) -> (usize, bool, usize) {
+    let round = symbol / DRILL_SYMBOL_SHAPES;
+    (symbol % DRILL_SYMBOL_SHAPES, round % 2 == 1, round / 2)
+}
+
+impl DrillReport {
+    pub fn plated(&self) -> usize {
+        self.rows.iter().map(|row| row.plated).sum()
+    }
+
+    pub fn non_plated(&self) -> usize {
+        self.rows.iter().map(|row| row.non_plated).sum()
+    }
+
+    pub fn total(&self) -> usize {
+        self.plated() + self.non_plated()
+    }
+
+    /// Symbol of the row a hole of `diameter` falls in
+    pub fn symbol_for(&self, diameter: f64) -> Option<usize> {
+        let microns = drill_microns(diameter);
+        self.rows.iter().find(|row| drill_microns(row.diameter) == microns).map(|row| row.symbol)
+    }
+
+    /// Rows with the diameter in mm and inches, then a total row
+    pub fn to_csv(&self) -> String {
+        let mut csv = "symbol,diameter_mm,diameter_in,plated,non_plated,total\n".to_string();
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{:.3},{:.4},{},{},{}\n",
+                row.symbol + 1, row.diameter, row.diameter / 25.4, row.plated, row.non_plated, row.count()
+            ));
+        }
+        csv.push_str(&format!("total,,,{},{},{}\n", self.plated(), self.non_plated(), self.total()));
+        csv
+    }
+}
+
+fn drill_microns(diameter: f64) -> i64 {
+    (diameter * 1000.0).round() as i64
+}
+
+/// Group parsed Excellon holes by diameter (to the micron), each size with its own symbol
+pub fn drill_report(drills: &[super::DrillHole]) -> DrillReport {
+    let mut sizes: std::collections::BTreeMap<i64, (usize, usize)> = std::collections::BTreeMap::new();
+    for hole in drills {
+        let (plated, non_plated) = sizes.entry(drill_microns(hole.diameter)).or_default();
+        if hole.plated {
+            *plated += 1;
+        } else {
+            *non_plated += 1;
+        }
+    }
+    let rows = sizes.into_iter()
+        .enumerate()
+        .map(|(symbol, (microns, (plated, non_plated)))| DrillReportRow { diameter: microns as f64 / 1000.0, plated, non_plated, symbol })
+        .collect();
+    DrillReport { rows }
+}
+
 /// Represents unassigned gerber files that couldn't be automatically detected
 #[derive(Debug, Clone)]
 pub struct UnassignedGerber {
     pub filename: String,
     pub content: String,
     pub parsed_layer: gerber_viewer::GerberLayer,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{build_flash_primitives, extract_primitives};
+
+    #[test]
+    fn test_inner_copper_detected_from_filename_and_x2() {
+        let detector = LayerDetector::new();
+        assert_eq!(detector.detect_layer_type("board-In1_Cu.gbr"), Some(LayerType::inner_copper(1)));
+        assert_eq!(detector.detect_layer_type("board-In4_Cu.gbr"), Some(LayerType::inner_copper(4)));
+        assert_eq!(detector.detect_layer_type("board-B_Cu.gbr"), Some(LayerType::Copper(2)));
+
+        let header = |function: &str| format!("%TF.GenerationSoftware,KiCad*%\n%TF.FileFunction,{}*%\n%FSLAX46Y46*%\n", function);
+        assert_eq!(detector.detect_from_attributes(&header("Copper,L1,Top")), Some(LayerType::Copper(1)));
+        assert_eq!(detector.detect_from_attributes(&header("Copper,L3,Inr")), Some(LayerType::inner_copper(2)));
+        assert_eq!(detector.detect_from_attributes(&header("Copper,L4,Bot")), Some(LayerType::Copper(2)));
+        assert_eq!(detector.detect_from_attributes(&header("Legend,Bot")), Some(LayerType::Silkscreen(Side::Bottom)));
+        assert_eq!(detector.detect_from_attributes(&header("Profile,NP")), Some(LayerType::MechanicalOutline));
+        assert_eq!(detector.detect_from_attributes("%FSLAX46Y46*%\n"), None);
+    }
+
+    #[test]
+    fn test_paste_aperture_histogram_bins_by_area() {
+        let gerber = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10R,0.500000X0.400000*%\n%ADD11R,1.000000X1.000000*%\n\
+            D10*\nX0Y0D03*\nX1000000Y0D03*\nX2000000Y0D03*\nD11*\nX0Y5000000D03*\nM02*\n";
+
+        let flashes = build_flash_primitives(&extract_primitives(gerber));
+        let histogram = paste_aperture_histogram(&flashes.0);
+
+        assert_eq!(histogram.len(), 2);
+        assert!((histogram[0].0 - 0.2).abs() < 1e-9);
+        assert_eq!(histogram[0].1, 3);
+        assert!((histogram[1].0 - 1.0).abs() < 1e-9);
+        assert_eq!(histogram[1].1, 1);
+    }
+
+    #[test]
+    fn test_drill_report_counts_per_diameter() {
+        let hole = |diameter: f64, plated: bool| crate::ecs::DrillHole { position: nalgebra::Point2::origin(), diameter, plated };
+        let holes = [hole(1.0, true), hole(0.3, true), hole(1.0, false), hole(0.3, true), hole(0.2999, true), hole(3.2, false)];
+        let report = drill_report(&holes);
+
+        assert_eq!(report.rows, vec![
+            DrillReportRow { diameter: 0.3, plated: 3, non_plated: 0, symbol: 0 },
+            DrillReportRow { diameter: 1.0, plated: 1, non_plated: 1, symbol: 1 },
+            DrillReportRow { diameter: 3.2, plated: 0, non_plated: 1, symbol: 2 },
+        ]);
+        assert_eq!((report.plated(), report.non_plated(), report.total()), (4, 2, 6));
+        assert_eq!(report.symbol_for(0.29995), Some(0));
+        assert_eq!(report.symbol_for(0.8), None);
+
+        let styles: std::collections::HashSet<_> = (0..100).map(drill_symbol_style).collect();
+        assert_eq!(styles.len(), 100);
+        assert_eq!(drill_symbol_style(DRILL_SYMBOL_SHAPES * 2 + 1), (1, false, 1));
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[2], "2,1.000,0.0394,1,1,2");
+        assert_eq!(lines[4], "total,,,4,2,6");
+    }
+}
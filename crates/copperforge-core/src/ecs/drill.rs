@@ -0,0 +1,249 @@
+//! Excellon drill file parsing
+//!
+//! Only what DRC needs is read: tool diameters, plating and hole positions.
+//! Routed slots (G85) are recorded as a hole at their start point.
+//!
+//! Plating comes from the X2-style attributes KiCad writes into drill files
+//! (`TF.FileFunction` for the file, `TA.AperFunction` before each tool) and
+//! otherwise from the filename, since separate PTH/NPTH files carry none.
+
+use bevy_ecs::prelude::*;
+use nalgebra::Point2;
+use std::collections::HashMap;
+
+/// A single drilled hole in gerber (mm) coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrillHole {
+    pub position: Point2<f64>,
+    pub diameter: f64,
+    /// False for non-plated holes (NPTH)
+    pub plated: bool,
+}
+
+// Holes from every drill file loaded with the gerbers
+#[derive(Resource, Clone, Debug, Default)]
+pub struct DrillData {
+    pub holes: Vec<DrillHole>,
+    pub filenames: Vec<String>,
+}
+
+impl DrillData {
+    pub fn clear(&mut self) {
+        self.holes.clear();
+        self.filenames.clear();
+    }
+}
+
+/// True if the filename looks like an Excellon drill file
+pub fn is_drill_file(filename: &str) -> bool {
+    let lower = filename.to_lowercase();
+    lower.ends_with(".drl") || lower.ends_with(".xln") || lower.ends_with(".exc")
+}
+
+/// True if the drill filename marks non-plated holes (e.g. KiCad's `-NPTH.drl`)
+pub fn is_non_plated_drill_file(filename: &str) -> bool {
+    filename.to_uppercase().contains("NPTH")
+}
+
+/// Plating named by a `TF.FileFunction` or `TA.AperFunction` attribute comment,
+/// e.g. `; #@! TA.AperFunction,NonPlated,NPTH,ComponentDrill`
+/// `None` for other comments and for `MixedPlating` files
+fn attribute_plating(comment: &str) -> Option<bool> {
+    let attribute = comment.trim_start_matches(';').trim().strip_prefix("#@!")?.trim();
+    let mut fields = attribute.split(',');
+    if !matches!(fields.next(), Some("TF.FileFunction" | "TA.AperFunction")) {
+        return None;
+    }
+    match fields.next()? {
+        "Plated" => Some(true),
+        "NonPlated" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse Excellon drill data into holes
+/// `plated` applies to tools without a plating attribute (see the module docs)
+/// Coordinates without a decimal point use the header's zero suppression and
+/// the default 3.3 (metric) / 2.4 (inch) format
+pub fn parse_excellon(content: &str, plated: bool) -> Result<Vec<DrillHole>, String> {
+    let mut holes = Vec::new();
+    // Diameter and plating per tool
+    let mut tools: HashMap<u32, (f64, bool)> = HashMap::new();
+    let mut current_tool: Option<(f64, bool)> = None;
+    let mut file_plated = plated;
+    let mut next_tool_plated: Option<bool> = None;
+    let mut unit_scale = 25.4; // mm per file unit; Excellon defaults to inch
+    let mut decimal_digits = 4u32;
+    let mut integer_digits = 2u32;
+    let mut leading_zeros_kept = true;
+    let mut x = 0.0;
+    let mut y = 0.0;
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line.starts_with(';') {
+            if let Some(attribute_plated) = attribute_plating(line) {
+                if line.contains("TF.FileFunction") {
+                    file_plated = attribute_plated;
+                } else {
+                    next_tool_plated = Some(attribute_plated);
+                }
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("METRIC") || line.starts_with("INCH") {
+            let metric = line.starts_with("METRIC");
+            unit_scale = if metric { 1.0 } else { 25.4 };
+            (integer_digits, decimal_digits) = if metric { (3, 3) } else { (2, 4) };
+            if line.contains("TZ") {
+                // Trailing zeros kept, leading zeros suppressed
+                leading_zeros_kept = false;
+            } else if line.contains("LZ") {
+                leading_zeros_kept = true;
+            }
+            if let Some(format) = line.split(',').find_map(|part| part.split_once('.')) {
+                if let (Ok(int), Ok(dec)) = (format.0.len().try_into(), format.1.len().try_into()) {
+                    (integer_digits, decimal_digits) = (int, dec);
+                }
+            }
+            continue;
+        }
+        if line == "M71" {
+            unit_scale = 1.0;
+            continue;
+        }
+        if line == "M72" {
+            unit_scale = 25.4;
+            continue;
+        }
+
+        if let Some(tool) = line.strip_prefix('T') {
+            let code_end = tool.find(|c: char| !c.is_ascii_digit()).unwrap_or(tool.len());
+            let Ok(code) = tool[..code_end].parse::<u32>() else {
+                continue;
+            };
+            let rest = &tool[code_end..];
+            if let Some(diameter) = rest.strip_prefix('C') {
+                // Tool definition (header), e.g. T1C0.0220
+                let value: String = diameter.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+                let diameter = value.parse::<f64>()
+                    .map_err(|_| format!("Invalid tool diameter: {}", line))?;
+                let tool_plated = next_tool_plated.take().unwrap_or(file_plated);
+                tools.insert(code, (diameter * unit_scale, tool_plated));
+            } else {
+                // Tool selection; T0 unloads the tool
+                current_tool = tools.get(&code).copied();
+            }
+            continue;
+        }
+
+        if !(line.starts_with('X') || line.starts_with('Y')) {
+            continue;
+        }
+
+        // Slots (X..Y..G85X..Y..) are recorded at their start point
+        let hit = line.split("G85").next().unwrap_or(line);
+        let parse = |axis: char| {
+            parse_excellon_coordinate(hit, axis, integer_digits, decimal_digits, leading_zeros_kept)
+                .map(|v| v * unit_scale)
+        };
+        x = parse('X').unwrap_or(x);
+        y = parse('Y').unwrap_or(y);
+
+        let (diameter, plated) = current_tool.ok_or_else(|| format!("Hole without a tool: {}", line))?;
+        holes.push(DrillHole {
+            position: Point2::new(x, y),
+            diameter,
+            plated,
+        });
+    }
+
+    Ok(holes)
+}
+
+/// Parse the value following `axis`, e.g. X3.532 or X03532
+fn parse_excellon_coordinate(
+    data: &str,
+    axis: char,
+    integer_digits: u32,
+    decimal_digits: u32,
+    leading_zeros_kept: bool,
+) -> Option<f64> {
+    let start = data.find(axis)? + 1;
+    let value: String = data[start..]
+        .chars()
+        .enumerate()
+        .take_while(|(i, c)| c.is_ascii_digit() || *c == '.' || (*i == 0 && (*c == '-' || *c == '+')))
+        .map(|(_, c)| c)
+        .collect();
+
+    if value.contains('.') {
+        return value.parse::<f64>().ok();
+    }
+
+    let negative = value.starts_with('-');
+    let digits = value.trim_start_matches(['-', '+']);
+    let raw = if leading_zeros_kept {
+        // Trailing zeros were suppressed: pad on the right to the full width
+        let width = (integer_digits + decimal_digits) as usize;
+        format!("{:0<width$}", digits, width = width).parse::<i64>().ok()?
+    } else {
+        digits.parse::<i64>().ok()?
+    };
+    let magnitude = raw as f64 / 10f64.powi(decimal_digits as i32);
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// Get every loaded drill hole
+pub fn get_drill_holes(world: &World) -> Vec<DrillHole> {
+    world.get_resource::<DrillData>()
+        .map(|drill_data| drill_data.holes.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kicad_inch_drill_file() {
+        let holes = parse_excellon(include_str!("../../../../assets/cmod_s7-PTH.drl"), true)
+            .expect("fixture drill file should parse");
+
+        assert_eq!(holes.len(), 112);
+        let first = holes[0];
+        assert!((first.position.x - 3.532 * 25.4).abs() < 1e-9);
+        assert!((first.position.y + 1.673 * 25.4).abs() < 1e-9);
+        assert!((first.diameter - 0.022 * 25.4).abs() < 1e-9);
+        assert!(first.plated);
+    }
+
+    #[test]
+    fn test_parse_metric_suppressed_zeros() {
+        let drill = "M48\nMETRIC,TZ\nT1C0.8\n%\nT1\nX10000Y-2500\nM30\n";
+        let holes = parse_excellon(drill, false).expect("drill should parse");
+
+        assert_eq!(holes.len(), 1);
+        assert_eq!(holes[0].position, Point2::new(10.0, -2.5));
+        assert_eq!(holes[0].diameter, 0.8);
+        assert!(!holes[0].plated);
+    }
+
+    #[test]
+    fn test_plating_attributes_override_the_filename() {
+        let drill = "M48\n; #@! TF.FileFunction,MixedPlating,1,2\nMETRIC\n\
+            ; #@! TA.AperFunction,Plated,PTH,ViaDrill\nT1C0.300\n\
+            ; #@! TA.AperFunction,NonPlated,NPTH,ComponentDrill\nT2C3.200\n%\n\
+            T1\nX1.0Y1.0\nT2\nX5.0Y5.0\nM30\n";
+        let holes = parse_excellon(drill, true).expect("drill should parse");
+        assert_eq!(holes.iter().map(|hole| hole.plated).collect::<Vec<_>>(), vec![true, false]);
+
+        // A file-level attribute wins over the plating guessed from the filename
+        let drill = "M48\n; #@! TF.FileFunction,NonPlated,1,2,NPTH\nMETRIC\nT1C1.0\n%\nT1\nX0.0Y0.0\nM30\n";
+        assert!(!parse_excellon(drill, true).expect("drill should parse")[0].plated);
+    }
+}
@@ -0,0 +1,285 @@
+//! Distances measured along the board outline
+//!
+//! The outline is chained into closed loops (the board edge and any cutouts)
+//! in view coordinates. A point is located on a loop by its arc-length position
+//! from the loop's first vertex, so the path between two points can be walked
+//! either way round. Arcs are re-tessellated from the outline source at a much
+//! finer step than the one used for display, keeping the measured length within
+//! a few ppm of the true arc length.
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+use nalgebra::{Matrix3, Point2};
+
+use crate::display::DisplayManager;
+use super::components::{LayerContentHash, LayerInfo};
+use super::outline::{chain_outline_loops, OUTLINE_JOIN_TOLERANCE};
+use super::primitives::{extract_primitives_with_arc_step, LayerPrimitives};
+use super::LayerType;
+
+/// Largest angle swept by one segment of an outline arc when measuring along it
+pub const EDGE_ARC_STEP_DEGREES: f64 = 0.5;
+
+/// One closed outline loop with the arc length up to each vertex
+#[derive(Debug, Clone)]
+pub struct OutlinePath {
+    points: Vec<Point2<f64>>,
+    /// `cumulative[i]` is the length from the first vertex to vertex `i`; the last entry is the loop length
+    cumulative: Vec<f64>,
+}
+
+impl OutlinePath {
+    pub fn new(points: Vec<Point2<f64>>) -> Self {
+        let mut cumulative = Vec::with_capacity(points.len() + 1);
+        let mut length = 0.0;
+        cumulative.push(0.0);
+        for i in 0..points.len() {
+            length += (points[(i + 1) % points.len()] - points[i]).norm();
+            cumulative.push(length);
+        }
+        Self { points, cumulative }
+    }
+
+    pub fn length(&self) -> f64 {
+        *self.cumulative.last().unwrap_or(&0.0)
+    }
+
+    pub fn points(&self) -> &[Point2<f64>] {
+        &self.points
+    }
+
+    fn edge(&self, index: usize) -> (Point2<f64>, Point2<f64>) {
+        (self.points[index], self.points[(index + 1) % self.points.len()])
+    }
+
+    /// Closest point of the loop to `target`: (position along the loop, point, distance)
+    pub fn project(&self, target: Point2<f64>) -> Option<(f64, Point2<f64>, f64)> {
+        (0..self.points.len())
+            .map(|index| {
+                let (a, b) = self.edge(index);
+                let edge = b - a;
+                let t = if edge.norm_squared() > 0.0 {
+                    ((target - a).dot(&edge) / edge.norm_squared()).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let point = a + edge * t;
+                (self.cumulative[index] + edge.norm() * t, point, (target - point).norm())
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+
+    /// Point at a position along the loop, wrapping past the end
+    pub fn point_at(&self, position: f64) -> Point2<f64> {
+        let length = self.length();
+        if length <= 0.0 {
+            return self.points[0];
+        }
+        let position = position.rem_euclid(length);
+        let index = (self.cumulative.partition_point(|&s| s <= position) - 1).min(self.points.len() - 1);
+        let (a, b) = self.edge(index);
+        let edge_length = self.cumulative[index + 1] - self.cumulative[index];
+        let t = if edge_length > 0.0 { (position - self.cumulative[index]) / edge_length } else { 0.0 };
+        a + (b - a) * t
+    }
+
+    /// Length walked from `from` to `to` in the direction of increasing position
+    pub fn forward_distance(&self, from: f64, to: f64) -> f64 {
+        let length = self.length();
+        if length <= 0.0 { 0.0 } else { (to - from).rem_euclid(length) }
+    }
+
+    /// Polyline from `from` to `to`, walking forward or backward round the loop
+    pub fn path(&self, from: f64, to: f64, forward: bool) -> Vec<Point2<f64>> {
+        if !forward {
+            let mut points = self.path(to, from, true);
+            points.reverse();
+            return points;
+        }
+        let distance = self.forward_distance(from, to);
+        let mut vertices: Vec<(f64, Point2<f64>)> = self.points.iter()
+            .zip(&self.cumulative)
+            .map(|(point, s)| (self.forward_distance(from, *s), *point))
+            .filter(|(offset, _)| *offset > 0.0 && *offset < distance)
+            .collect();
+        vertices.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut points = Vec::with_capacity(vertices.len() + 2);
+        points.push(self.point_at(from));
+        points.extend(vertices.into_iter().map(|(_, point)| point));
+        points.push(self.point_at(to));
+        points
+    }
+}
+
+/// A point snapped onto the outline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgePoint {
+    pub loop_index: usize,
+    /// Position along the loop from its first vertex
+    pub position: f64,
+    pub point: Point2<f64>,
+    /// Distance from the picked point to the outline
+    pub distance: f64,
+}
+
+/// Lengths between two points of the same loop
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeMeasurement {
+    pub start: EdgePoint,
+    pub end: EdgePoint,
+    /// Along the loop in the direction of increasing position
+    pub forward: f64,
+    /// Along the loop the other way round
+    pub backward: f64,
+    /// Straight line between the two points
+    pub chord: f64,
+}
+
+impl EdgeMeasurement {
+    /// Direction of the reported path: the shorter one, or the longer when flipped
+    pub fn goes_forward(&self, flip: bool) -> bool {
+        (self.forward <= self.backward) != flip
+    }
+
+    /// Length of the reported path
+    pub fn along(&self, flip: bool) -> f64 {
+        if self.goes_forward(flip) { self.forward } else { self.backward }
+    }
+
+    /// Length of the path not reported
+    pub fn other_way(&self, flip: bool) -> f64 {
+        if self.goes_forward(flip) { self.backward } else { self.forward }
+    }
+}
+
+/// Every closed loop of the board outline, in view coordinates
+#[derive(Debug, Clone, Default)]
+pub struct OutlinePaths {
+    pub loops: Vec<OutlinePath>,
+}
+
+impl OutlinePaths {
+    /// Closest outline point to `target` within `max_distance`, optionally on one loop only
+    pub fn nearest(&self, target: Point2<f64>, max_distance: f64, loop_index: Option<usize>) -> Option<EdgePoint> {
+        self.loops.iter()
+            .enumerate()
+            .filter(|(index, _)| loop_index.is_none_or(|wanted| wanted == *index))
+            .filter_map(|(index, path)| {
+                path.project(target).map(|(position, point, distance)| EdgePoint { loop_index: index, position, point, distance })
+            })
+            .filter(|edge_point| edge_point.distance <= max_distance)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Measure from the outline point nearest `start` to the point nearest `end` on the same loop
+    pub fn measure(&self, start: Point2<f64>, end: Point2<f64>) -> Option<EdgeMeasurement> {
+        let start = self.nearest(start, f64::INFINITY, None)?;
+        let end = self.nearest(end, f64::INFINITY, Some(start.loop_index))?;
+        let path = &self.loops[start.loop_index];
+        let forward = path.forward_distance(start.position, end.position);
+        Some(EdgeMeasurement {
+            start,
+            end,
+            forward,
+            backward: if forward > 0.0 { path.length() - forward } else { 0.0 },
+            chord: (end.point - start.point).norm(),
+        })
+    }
+
+    /// Outline path of a measurement in its reported direction
+    pub fn measured_path(&self, measurement: &EdgeMeasurement, flip: bool) -> Vec<Point2<f64>> {
+        self.loops[measurement.start.loop_index].path(
+            measurement.start.position,
+            measurement.end.position,
+            measurement.goes_forward(flip),
+        )
+    }
+}
+
+/// Outline loops built for the current outline source and view transform
+#[derive(Resource, Default)]
+pub struct OutlinePathCache {
+    key: Option<(u64, Matrix3<f64>)>,
+    paths: Option<Arc<OutlinePaths>>,
+}
+
+/// Outline primitives with finely tessellated arcs, re-read from the outline file
+/// Falls back to the loaded primitives when the file is gone or has changed since loading
+fn fine_outline_primitives(world: &mut World) -> Option<(u64, Vec<super::TaggedPrimitive>)> {
+    let mut query = world.query::<(&LayerInfo, &LayerContentHash, &LayerPrimitives)>();
+    let (info, hash, primitives) = query.iter(world).find(|(info, _, _)| info.layer_type == LayerType::MechanicalOutline)?;
+    let source = info.file_path.as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .filter(|content| LayerContentHash::from_content(content) == *hash);
+    let primitives = match source {
+        Some(content) => extract_primitives_with_arc_step(&content, EDGE_ARC_STEP_DEGREES),
+        None => primitives.0.clone(),
+    };
+    Some((hash.0, primitives))
+}
+
+/// Closed loops of the mechanical outline as drawn in the view, or None without an outline
+pub fn get_outline_paths(world: &mut World, display_manager: &DisplayManager) -> Option<Arc<OutlinePaths>> {
+    let hash = {
+        let mut query = world.query::<(&LayerInfo, &LayerContentHash)>();
+        query.iter(world).find(|(info, _)| info.layer_type == LayerType::MechanicalOutline).map(|(_, hash)| hash.0)?
+    };
+    let matrix = super::get_layer_render_transform(world, display_manager, LayerType::MechanicalOutline)?.to_matrix();
+    let key = Some((hash, matrix));
+    if let Some(cache) = world.get_resource::<OutlinePathCache>().filter(|cache| cache.key == key) {
+        return cache.paths.clone();
+    }
+
+    let paths = fine_outline_primitives(world).and_then(|(_, primitives)| {
+        let (loops, _) = chain_outline_loops(&primitives, OUTLINE_JOIN_TOLERANCE);
+        let loops: Vec<OutlinePath> = loops.into_iter()
+            .filter(|points| points.len() >= 3)
+            .map(|points| OutlinePath::new(points.iter().map(|point| matrix.transform_point(point)).collect()))
+            .collect();
+        (!loops.is_empty()).then(|| Arc::new(OutlinePaths { loops }))
+    });
+    world.insert_resource(OutlinePathCache { key, paths: paths.clone() });
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_measure_along_arc_outline_both_ways() {
+        // Circle of radius 10 around the origin plus a 2 x 2 cutout
+        let gerber = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.100000*%\nD10*\nX10000000Y0D02*\nG75*\nG03X10000000Y0I-10000000J0D01*\nG01*\n\
+                      X0Y0D02*\nX2000000Y0D01*\nX2000000Y2000000D01*\nX0Y2000000D01*\nX0Y0D01*\nM02*\n";
+        let primitives = extract_primitives_with_arc_step(gerber, EDGE_ARC_STEP_DEGREES);
+        let (loops, open_chains) = chain_outline_loops(&primitives, OUTLINE_JOIN_TOLERANCE);
+        assert_eq!(open_chains, 0);
+        let paths = OutlinePaths { loops: loops.into_iter().map(OutlinePath::new).collect() };
+
+        let circle = paths.loops.iter().position(|path| path.length() > 20.0).unwrap();
+        assert!((paths.loops[circle].length() - 20.0 * PI).abs() < 1e-3);
+
+        // Quarter turn from (10, 0) to (0, 10), picked slightly off the edge
+        let measurement = paths.measure(Point2::new(10.3, 0.0), Point2::new(0.0, 9.8)).unwrap();
+        assert_eq!(measurement.start.loop_index, circle);
+        assert!((measurement.along(false) - 5.0 * PI).abs() < 1e-3);
+        assert!((measurement.other_way(false) - 15.0 * PI).abs() < 1e-3);
+        assert!((measurement.along(true) - 15.0 * PI).abs() < 1e-3);
+        assert!((measurement.chord - 10.0 * 2f64.sqrt()).abs() < 1e-3);
+
+        let path = paths.measured_path(&measurement, false);
+        assert!((path[0] - Point2::new(10.0, 0.0)).norm() < 1e-6);
+        assert!((path.last().unwrap() - Point2::new(0.0, 10.0)).norm() < 1e-6);
+        assert!(path.iter().all(|point| point.x >= -1e-9 && point.y >= -1e-9));
+
+        // The end point stays on the loop the measurement started on
+        let cutout = paths.measure(Point2::new(1.0, -0.1), Point2::new(9.0, 0.5)).unwrap();
+        assert_ne!(cutout.start.loop_index, circle);
+        assert!((cutout.end.point - Point2::new(2.0, 0.5)).norm() < 1e-9);
+        assert!((cutout.along(false) - 1.5).abs() < 1e-9 && (cutout.other_way(false) - 6.5).abs() < 1e-9);
+        assert!(paths.nearest(Point2::new(5.0, 5.0), 0.5, None).is_none());
+    }
+}
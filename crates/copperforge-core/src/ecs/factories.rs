@@ -1,8 +1,14 @@
 use bevy_ecs::prelude::*;
 use gerber_viewer::GerberLayer;
-use super::{LayerType, Side};
+use super::LayerType;
 use crate::ecs::components::*;
+use crate::ecs::nets::{NetTable, build_net_geometry};
+use crate::ecs::primitives::{extract_primitives, build_flash_primitives, LayerPrimitives, TaggedPrimitive};
+use crate::ecs::registration::GerberFormat;
+use crate::ecs::provenance::LayerProvenance;
+use crate::ecs::polarity::LayerPolarity;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Entity Factory Pattern for creating layer entities
 /// These functions encapsulate the "recipe" for creating different types of layer entities
@@ -13,20 +19,57 @@ pub fn create_gerber_layer_entity(
     world: &mut World,
     layer_type: LayerType,
     gerber_layer: GerberLayer,
-    _raw_gerber_data: Option<String>,
+    raw_gerber_data: Option<String>,
+    file_path: Option<PathBuf>,
+    visible: bool,
+) -> Entity {
+    create_gerber_layer_entity_with_primitives(world, layer_type, gerber_layer, raw_gerber_data, file_path, visible, None)
+}
+
+/// Like `create_gerber_layer_entity`, reusing already extracted primitives (e.g. from the parse cache)
+pub fn create_gerber_layer_entity_with_primitives(
+    world: &mut World,
+    layer_type: LayerType,
+    gerber_layer: GerberLayer,
+    raw_gerber_data: Option<String>,
     file_path: Option<PathBuf>,
     visible: bool,
+    primitives: Option<Vec<TaggedPrimitive>>,
 ) -> Entity {
     let bounds = gerber_layer.bounding_box().clone();
+    let content_hash = match &raw_gerber_data {
+        Some(content) => LayerContentHash::from_content(content),
+        None => LayerContentHash::from_layer(&gerber_layer),
+    };
     
-    world.spawn((
-        GerberData(gerber_layer),
+    // Extract primitives, net geometry and flashed pads/vias when the source is available
+    let primitive_tables = raw_gerber_data.as_ref().map(|content| {
+        let primitives = primitives.unwrap_or_else(|| extract_primitives(content));
+        let mut net_table = world.get_resource_or_insert_with(NetTable::default);
+        let net_geometry = build_net_geometry(&mut net_table, &primitives);
+        let flash_primitives = build_flash_primitives(&primitives);
+        let polarity = LayerPolarity::new(content, &primitives);
+        (net_geometry, flash_primitives, LayerPrimitives(primitives), GerberFormat::from_content(content), LayerProvenance::from_content(content), polarity)
+    });
+    
+    // Re-apply an alignment nudge saved for this file and attach its parse problems
+    let transform = Transform {
+        nudge: crate::ecs::saved_layer_nudge(world, file_path.as_deref()),
+        ..Transform::default()
+    };
+    let diagnostics = file_path.as_ref()
+        .and_then(|path| path.file_name())
+        .and_then(|name| crate::ecs::saved_parse_diagnostics(world, &name.to_string_lossy()));
+    
+    let entity = world.spawn((
+        GerberData(Arc::new(gerber_layer)),
+        content_hash,
         LayerInfo {
             layer_type,
             name: layer_type.display_name().to_string(),
             file_path,
         },
-        Transform::default(),
+        transform,
         ImageTransform::default(),
         Visibility {
             visible,
@@ -38,40 +81,17 @@ pub fn create_gerber_layer_entity(
             z_order: layer_type_to_z_order(&layer_type),
         },
         BoundingBoxCache { bounds },
-    )).id()
-}
-
-/* DEPRECATED: LayerManager migration function (no longer needed)
-/// Factory for creating a layer entity from existing LayerInfo
-pub fn create_layer_from_info(
-    world: &mut World,
-    layer_info: &LayerInfoOrig,
-    gerber_layer: GerberLayer,
-) -> Entity {
-    let bounds = gerber_layer.bounding_box().clone();
+    )).id();
     
-    world.spawn((
-        GerberData(gerber_layer),
-        LayerInfo {
-            layer_type: layer_info.layer_type,
-            name: layer_info.layer_type.display_name().to_string(),
-            file_path: None, // TODO: Extract from layer_info if needed
-        },
-        Transform::default(),
-        ImageTransform::default(),
-        Visibility {
-            visible: layer_info.visible,
-            opacity: 1.0,
-        },
-        RenderProperties {
-            color: layer_info.color,
-            highlight_color: None,
-            z_order: layer_type_to_z_order(&layer_info.layer_type),
-        },
-        BoundingBoxCache { bounds },
-    )).id()
+    if let Some(primitive_tables) = primitive_tables {
+        world.entity_mut(entity).insert(primitive_tables);
+    }
+    if let Some(diagnostics) = diagnostics {
+        world.entity_mut(entity).insert(diagnostics);
+    }
+    
+    entity
 }
-*/
 
 /// Factory for creating a mechanical outline layer entity
 pub fn create_mechanical_outline_entity(
@@ -134,37 +154,8 @@ pub fn create_layer_entity(
 
 /// Utility function to determine z-order for layer rendering
 fn layer_type_to_z_order(layer_type: &LayerType) -> i32 {
-    match layer_type {
-        LayerType::Paste(Side::Top) => 90,
-        LayerType::Silkscreen(Side::Top) => 80,
-        LayerType::Soldermask(Side::Top) => 70,
-        LayerType::Copper(1) => 60,  // Top copper
-        LayerType::Copper(n) => 50 - (*n as i32),  // All other copper layers (inner/bottom)
-        LayerType::Soldermask(Side::Bottom) => 40,
-        LayerType::Silkscreen(Side::Bottom) => 30,
-        LayerType::Paste(Side::Bottom) => 20,
-        LayerType::MechanicalOutline => 10,
-    }
-}
-
-/* DEPRECATED: LayerManager factory (no longer needed)
-/// Bulk factory for creating multiple layer entities from a LayerManager (deprecated)
-pub fn create_layers_from_manager(
-    world: &mut World,
-    layer_manager: &crate::layer_operations::LayerManager,
-) -> Vec<Entity> {
-    let mut entities = Vec::new();
-    
-    for (_layer_type, layer_info) in &layer_manager.layers {
-        if let Some(ref gerber_layer) = layer_info.gerber_layer {
-            let entity = create_layer_from_info(world, layer_info, gerber_layer.clone());
-            entities.push(entity);
-        }
-    }
-    
-    entities
+    layer_type.z_order()
 }
-*/
 
 /// Factory for creating a layer entity with custom transform
 pub fn create_layer_with_transform(
@@ -175,9 +166,11 @@ pub fn create_layer_with_transform(
     visible: bool,
 ) -> Entity {
     let bounds = gerber_layer.bounding_box().clone();
+    let content_hash = LayerContentHash::from_layer(&gerber_layer);
     
     world.spawn((
-        GerberData(gerber_layer),
+        GerberData(Arc::new(gerber_layer)),
+        content_hash,
         LayerInfo {
             layer_type,
             name: layer_type.display_name().to_string(),
@@ -206,9 +199,11 @@ pub fn create_layer_with_color(
     visible: bool,
 ) -> Entity {
     let bounds = gerber_layer.bounding_box().clone();
+    let content_hash = LayerContentHash::from_layer(&gerber_layer);
     
     world.spawn((
-        GerberData(gerber_layer),
+        GerberData(Arc::new(gerber_layer)),
+        content_hash,
         LayerInfo {
             layer_type,
             name: layer_type.display_name().to_string(),
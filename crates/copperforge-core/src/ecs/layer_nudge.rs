@@ -0,0 +1,247 @@
+//! Per-layer alignment offsets for gerbers exported with a different origin
+//!
+//! A nudge is stored on the layer's `Transform` and added on top of the view
+//! transform (quadrant offset, board rotation) every frame. Nudges of layers
+//! loaded from a file are also kept in `LayerNudges`, keyed by file name, so they
+//! survive reloads and are saved with the open project.
+//!
+//! Auto-align estimates the offset of a paste, mask or other copper layer from
+//! its flashes: the shift that lands the most of them on a pad of the copper
+//! layer on the same side wins, refined by the mean residual of the matches.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bevy_ecs::prelude::*;
+use nalgebra::{Point2, Vector2};
+use serde::{Deserialize, Serialize};
+
+use super::components::{LayerInfo, Transform};
+use super::{LayerType, Side};
+
+/// Arrow-key step of the nudge panel in board units (Shift moves ten times as far)
+pub const DEFAULT_NUDGE_STEP: f64 = 0.01;
+/// Alt+arrow rotation step in degrees
+pub const DEFAULT_NUDGE_ROTATION_STEP: f32 = 0.1;
+
+/// Offset and rotation applied to one layer on top of the view transform
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayerNudge {
+    pub x: f64,
+    pub y: f64,
+    /// Counter-clockwise, about the same center as the board rotation
+    pub rotation_degrees: f32,
+}
+
+impl LayerNudge {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The offset in view coordinates: rotated and mirrored with the board
+    /// (same order as the overlay code), so the layer stays aligned when the
+    /// view is turned or flipped
+    pub fn view_offset(&self, view_rotation_degrees: f32, mirror_x: bool, mirror_y: bool) -> (f64, f64) {
+        let (sin, cos) = (view_rotation_degrees as f64).to_radians().sin_cos();
+        let x = self.x * cos - self.y * sin;
+        let y = self.x * sin + self.y * cos;
+        (if mirror_x { -x } else { x }, if mirror_y { -y } else { y })
+    }
+}
+
+/// Saved nudges by source file name
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LayerNudges(pub BTreeMap<String, LayerNudge>);
+
+fn layer_file_name(info: &LayerInfo) -> Option<String> {
+    info.file_path
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// Saved nudge for a layer about to be created from `file_path`
+pub fn saved_layer_nudge(world: &World, file_path: Option<&std::path::Path>) -> LayerNudge {
+    let file_name = file_path.and_then(|path| path.file_name()).map(|name| name.to_string_lossy().to_string());
+    file_name
+        .and_then(|name| world.get_resource::<LayerNudges>()?.0.get(&name).copied())
+        .unwrap_or_default()
+}
+
+/// Current nudge of a loaded layer
+pub fn get_layer_nudge(world: &mut World, layer_type: LayerType) -> Option<LayerNudge> {
+    let mut query = world.query::<(&Transform, &LayerInfo)>();
+    query.iter(world)
+        .find(|(_, info)| info.layer_type == layer_type)
+        .map(|(transform, _)| transform.nudge)
+}
+
+/// Set a loaded layer's nudge and remember it for its file; identity forgets it
+pub fn set_layer_nudge(world: &mut World, layer_type: LayerType, nudge: LayerNudge) -> bool {
+    let mut query = world.query::<(&mut Transform, &LayerInfo)>();
+    let Some((mut transform, info)) = query.iter_mut(world).find(|(_, info)| info.layer_type == layer_type) else {
+        return false;
+    };
+    transform.nudge = nudge;
+    let file_name = layer_file_name(info);
+
+    if let Some(file_name) = file_name {
+        let mut nudges = world.get_resource_or_insert_with(LayerNudges::default);
+        if nudge.is_identity() {
+            nudges.0.remove(&file_name);
+        } else {
+            nudges.0.insert(file_name, nudge);
+        }
+    }
+    super::mark_coordinates_dirty(world);
+    true
+}
+
+/// Size of the offset bins voted on by flash pairs (mm)
+const ALIGN_BIN: f64 = 0.05;
+/// A flash within this distance of a shifted reference flash matches it (mm)
+const ALIGN_MATCH_TOLERANCE: f64 = 0.1;
+/// Flashes of the layer being aligned that take part in the vote; keeps the pair count bounded
+const ALIGN_MAX_SAMPLES: usize = 400;
+/// Share of the layer's flashes that must match for an estimate to be trusted
+const ALIGN_MIN_MATCH_RATIO: f64 = 0.5;
+
+/// Copper layer whose pads a layer is aligned to: the copper on its side, the top copper for other copper layers
+pub fn alignment_reference(layer_type: LayerType) -> Option<LayerType> {
+    match layer_type {
+        LayerType::Copper(1) | LayerType::MechanicalOutline => None,
+        LayerType::Copper(_) => Some(LayerType::TOP_COPPER),
+        LayerType::Paste(side) | LayerType::Soldermask(side) | LayerType::Silkscreen(side) => Some(match side {
+            Side::Top => LayerType::TOP_COPPER,
+            Side::Bottom => LayerType::BOTTOM_COPPER,
+        }),
+    }
+}
+
+/// Shift that moves `flashes` onto `reference`, with the number of flashes it matches
+/// `None` when fewer than half of the flashes (or fewer than three) find a partner
+pub fn estimate_flash_offset(flashes: &[Point2<f64>], reference: &[Point2<f64>]) -> Option<(Vector2<f64>, usize)> {
+    if flashes.is_empty() || reference.is_empty() {
+        return None;
+    }
+    let step = flashes.len().div_ceil(ALIGN_MAX_SAMPLES);
+    let mut votes: HashMap<(i64, i64), usize> = HashMap::new();
+    for flash in flashes.iter().step_by(step) {
+        for target in reference {
+            let delta = target - flash;
+            let bin = ((delta.x / ALIGN_BIN).round() as i64, (delta.y / ALIGN_BIN).round() as i64);
+            *votes.entry(bin).or_default() += 1;
+        }
+    }
+    // Ties go to the smaller shift so a symmetric pad grid does not jump a whole pitch
+    let (bin, _) = votes.into_iter().max_by(|(a, a_votes), (b, b_votes)| {
+        a_votes.cmp(b_votes).then_with(|| (b.0.pow(2) + b.1.pow(2)).cmp(&(a.0.pow(2) + a.1.pow(2))))
+    })?;
+    let coarse = Vector2::new(bin.0 as f64, bin.1 as f64) * ALIGN_BIN;
+
+    let residuals: Vec<Vector2<f64>> = flashes.iter()
+        .filter_map(|flash| {
+            let shifted = flash + coarse;
+            reference.iter()
+                .map(|target| target - shifted)
+                .filter(|residual| residual.norm() <= ALIGN_MATCH_TOLERANCE)
+                .min_by(|a, b| a.norm().total_cmp(&b.norm()))
+        })
+        .collect();
+    let needed = ((flashes.len() as f64 * ALIGN_MIN_MATCH_RATIO).ceil() as usize).max(3.min(flashes.len()));
+    if residuals.len() < needed {
+        return None;
+    }
+    let mean = residuals.iter().sum::<Vector2<f64>>() / residuals.len() as f64;
+    Some((coarse + mean, residuals.len()))
+}
+
+/// Nudge that lines a layer's flashes up with the pads of its reference copper layer
+/// The copper layer's own nudge is kept, so the layer follows it; any rotation is taken from it too
+/// Returns the nudge with the number of flashes matched and the layer's flash count
+pub fn auto_align_nudge(world: &mut World, layer_type: LayerType) -> Result<(LayerNudge, usize, usize), String> {
+    let reference = alignment_reference(layer_type)
+        .ok_or_else(|| format!("{} has no copper layer to align to", layer_type.display_name()))?;
+    let reference_nudge = get_layer_nudge(world, reference)
+        .ok_or_else(|| format!("{} is not loaded", reference.display_name()))?;
+    let positions = |world: &mut World, layer_type| -> Vec<Point2<f64>> {
+        super::get_flash_primitives(world, layer_type).into_iter().map(|flash| flash.position).collect()
+    };
+    let flashes = positions(world, layer_type);
+    if flashes.is_empty() {
+        return Err(format!("{} has no flashed pads or openings to match", layer_type.display_name()));
+    }
+    let (offset, matched) = estimate_flash_offset(&flashes, &positions(world, reference))
+        .ok_or_else(|| format!("No consistent offset between {} and {}", layer_type.display_name(), reference.display_name()))?;
+    let nudge = LayerNudge {
+        x: reference_nudge.x + offset.x,
+        y: reference_nudge.y + offset.y,
+        rotation_degrees: reference_nudge.rotation_degrees,
+    };
+    Ok((nudge, matched, flashes.len()))
+}
+
+/// Saved nudges, for the project database
+pub fn get_layer_nudges(world: &World) -> LayerNudges {
+    world.get_resource::<LayerNudges>().cloned().unwrap_or_default()
+}
+
+/// Restore a project's saved nudges; applies to layers loaded afterwards
+pub fn set_layer_nudges(world: &mut World, nudges: LayerNudges) {
+    world.insert_resource(nudges);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{create_gerber_layer_entity, setup_ecs_world};
+
+    fn load_layer(world: &mut World, layer_type: LayerType, file: &str) {
+        let layer = gerber_viewer::GerberLayer::new(Vec::new());
+        create_gerber_layer_entity(world, layer_type, layer, None, Some(file.into()), true);
+    }
+
+    #[test]
+    fn test_view_offset_turns_with_board() {
+        let nudge = LayerNudge { x: 1.0, y: 0.0, rotation_degrees: 0.0 };
+        let (x, y) = nudge.view_offset(90.0, false, false);
+        assert!(x.abs() < 1e-9 && (y - 1.0).abs() < 1e-9);
+        let (x, y) = nudge.view_offset(0.0, true, false);
+        assert_eq!((x, y), (-1.0, 0.0));
+    }
+
+    #[test]
+    fn test_nudge_survives_reload_of_same_file() {
+        let mut world = setup_ecs_world();
+        load_layer(&mut world, LayerType::Copper(1), "board-F_Cu.gbr");
+        let nudge = LayerNudge { x: 0.25, y: -0.1, rotation_degrees: 0.5 };
+        assert!(set_layer_nudge(&mut world, LayerType::Copper(1), nudge));
+        assert!(!set_layer_nudge(&mut world, LayerType::Copper(2), nudge));
+
+        crate::ecs::clear_all_layers_system(&mut world);
+        load_layer(&mut world, LayerType::Copper(1), "board-F_Cu.gbr");
+        assert_eq!(get_layer_nudge(&mut world, LayerType::Copper(1)), Some(nudge));
+
+        assert!(set_layer_nudge(&mut world, LayerType::Copper(1), LayerNudge::default()));
+        assert!(get_layer_nudges(&world).0.is_empty());
+    }
+
+    #[test]
+    fn test_flash_offset_found_on_a_pad_grid() {
+        // A 1.27 mm pin header plus a few scattered pads; paste exported 12.3/-4.05 mm off
+        let mut pads: Vec<Point2<f64>> = (0..10).map(|i| Point2::new(i as f64 * 1.27, 0.0)).collect();
+        pads.extend([Point2::new(20.0, 7.5), Point2::new(-3.0, 11.0), Point2::new(8.2, -6.4)]);
+        let paste: Vec<Point2<f64>> = pads.iter()
+            .filter(|pad| pad.y != 11.0)
+            .map(|pad| pad - Vector2::new(12.3, -4.05) + Vector2::new(0.004, -0.003))
+            .collect();
+
+        let (offset, matched) = estimate_flash_offset(&paste, &pads).expect("offset should be found");
+        assert_eq!(matched, paste.len());
+        assert!((offset - Vector2::new(12.3, -4.05)).norm() < 0.01, "{:?}", offset);
+
+        assert!(estimate_flash_offset(&[Point2::new(0.0, 0.0)], &[]).is_none());
+        let unrelated = [Point2::new(0.0, 0.0), Point2::new(3.0, 0.1), Point2::new(0.2, 7.0), Point2::new(9.0, 9.0)];
+        assert!(estimate_flash_offset(&unrelated, &pads[..3]).is_none());
+    }
+}
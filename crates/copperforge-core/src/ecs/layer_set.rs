@@ -0,0 +1,190 @@
+//! Board presence check: which required layers are missing
+//!
+//! Run before DRC and exports and after a project load so a missing outline or
+//! one-sided mask is reported up front instead of silently skipping checks.
+//! Only `Blocking` issues stop an operation, and only the operations listed in
+//! `LayerSetIssue::blocks`; everything else continues with a warning.
+
+use bevy_ecs::prelude::*;
+
+use super::components::{LayerInfo, Visibility};
+use super::{LayerType, Side};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LayerSetSeverity {
+    Info,
+    Warning,
+    Blocking,
+}
+
+impl LayerSetSeverity {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            LayerSetSeverity::Info => "ℹ",
+            LayerSetSeverity::Warning => "⚠",
+            LayerSetSeverity::Blocking => "⛔",
+        }
+    }
+}
+
+/// Operations gated by the layer set check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerSetOperation {
+    Drc,
+    Export,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerSetIssue {
+    NoLayers,
+    NoVisibleLayers,
+    MissingOutline,
+    MissingTopCopper,
+    /// Mask loaded for one side only; the side present
+    SingleSidedMask(Side),
+    /// Copper present but no soldermask at all
+    MissingMask,
+}
+
+impl LayerSetIssue {
+    pub fn severity(&self) -> LayerSetSeverity {
+        match self {
+            LayerSetIssue::NoLayers | LayerSetIssue::MissingOutline => LayerSetSeverity::Blocking,
+            LayerSetIssue::NoVisibleLayers | LayerSetIssue::MissingTopCopper | LayerSetIssue::SingleSidedMask(_) => LayerSetSeverity::Warning,
+            LayerSetIssue::MissingMask => LayerSetSeverity::Info,
+        }
+    }
+
+    /// Whether this issue stops `operation` rather than just warning
+    /// DRC and exports need the outline for the board boundary; exports also need something to draw
+    pub fn blocks(&self, operation: LayerSetOperation) -> bool {
+        match operation {
+            LayerSetOperation::Drc => matches!(self, LayerSetIssue::NoLayers | LayerSetIssue::MissingOutline),
+            LayerSetOperation::Export => matches!(self, LayerSetIssue::NoLayers | LayerSetIssue::NoVisibleLayers | LayerSetIssue::MissingOutline),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            LayerSetIssue::NoLayers => "No gerber layers loaded".to_string(),
+            LayerSetIssue::NoVisibleLayers => "Every layer is hidden".to_string(),
+            LayerSetIssue::MissingOutline => "No mechanical outline: DRC and exports cannot find the board boundary".to_string(),
+            LayerSetIssue::MissingTopCopper => "No top copper layer loaded".to_string(),
+            LayerSetIssue::SingleSidedMask(present) => {
+                let (present, missing) = match present {
+                    Side::Top => ("top", "bottom"),
+                    Side::Bottom => ("bottom", "top"),
+                };
+                format!("Only the {} soldermask is loaded; {} mask checks are skipped", present, missing)
+            }
+            LayerSetIssue::MissingMask => "No soldermask layers; mask checks are skipped".to_string(),
+        }
+    }
+
+    /// What to do about it
+    pub fn hint(&self) -> String {
+        match self {
+            LayerSetIssue::NoLayers => "Open a KiCad PCB or load a gerber folder from the gerber view or the Project panel".to_string(),
+            LayerSetIssue::NoVisibleLayers => "Turn layers back on in View Settings".to_string(),
+            LayerSetIssue::MissingOutline => Self::assign_hint(LayerType::MechanicalOutline),
+            LayerSetIssue::MissingTopCopper => Self::assign_hint(LayerType::Copper(1)),
+            LayerSetIssue::SingleSidedMask(Side::Top) => Self::assign_hint(LayerType::Soldermask(Side::Bottom)),
+            LayerSetIssue::SingleSidedMask(Side::Bottom) => Self::assign_hint(LayerType::Soldermask(Side::Top)),
+            LayerSetIssue::MissingMask => "Regenerate gerbers with the mask layers enabled in the plot settings".to_string(),
+        }
+    }
+
+    fn assign_hint(layer_type: LayerType) -> String {
+        format!("Assign a file to {} under Unassigned Gerber Files in View Settings", layer_type.display_name())
+    }
+
+    pub fn format_message(&self) -> String {
+        format!("{} — {}", self.message(), self.hint())
+    }
+}
+
+/// Result of the last layer set check, shown above the gerber view
+#[derive(Resource, Default)]
+pub struct LayerSetIssues(pub Vec<LayerSetIssue>);
+
+impl LayerSetIssues {
+    /// First issue stopping `operation`, if any
+    pub fn blocking(&self, operation: LayerSetOperation) -> Option<LayerSetIssue> {
+        self.0.iter().copied().find(|issue| issue.blocks(operation))
+    }
+}
+
+/// Issues for a set of loaded layers and their visibility, most severe first
+pub fn check_layer_set(layers: &[(LayerType, bool)]) -> Vec<LayerSetIssue> {
+    if layers.is_empty() {
+        return vec![LayerSetIssue::NoLayers];
+    }
+    let has = |layer_type: LayerType| layers.iter().any(|(loaded, _)| *loaded == layer_type);
+
+    let mut issues = Vec::new();
+    if layers.iter().all(|(_, visible)| !visible) {
+        issues.push(LayerSetIssue::NoVisibleLayers);
+    }
+    if !has(LayerType::MechanicalOutline) {
+        issues.push(LayerSetIssue::MissingOutline);
+    }
+    if !has(LayerType::Copper(1)) {
+        issues.push(LayerSetIssue::MissingTopCopper);
+    }
+    let has_copper = layers.iter().any(|(layer_type, _)| matches!(layer_type, LayerType::Copper(_)));
+    match (has(LayerType::Soldermask(Side::Top)), has(LayerType::Soldermask(Side::Bottom))) {
+        (true, false) if has(LayerType::Copper(2)) => issues.push(LayerSetIssue::SingleSidedMask(Side::Top)),
+        (false, true) => issues.push(LayerSetIssue::SingleSidedMask(Side::Bottom)),
+        (false, false) if has_copper => issues.push(LayerSetIssue::MissingMask),
+        _ => {}
+    }
+
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity()));
+    issues
+}
+
+/// Check the loaded layers and store the result in `LayerSetIssues`
+pub fn validate_layer_set(world: &mut World) -> Vec<LayerSetIssue> {
+    let layers: Vec<(LayerType, bool)> = {
+        let mut query = world.query::<(&LayerInfo, &Visibility)>();
+        query.iter(world).map(|(info, visibility)| (info.layer_type, visibility.visible)).collect()
+    };
+    let issues = check_layer_set(&layers);
+    world.insert_resource(LayerSetIssues(issues.clone()));
+    issues
+}
+
+/// Issues found by the last check
+pub fn get_layer_set_issues(world: &World) -> Vec<LayerSetIssue> {
+    world.get_resource::<LayerSetIssues>().map(|issues| issues.0.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_set_issues() {
+        assert_eq!(check_layer_set(&[]), vec![LayerSetIssue::NoLayers]);
+
+        let complete = [
+            (LayerType::Copper(1), true),
+            (LayerType::Copper(2), true),
+            (LayerType::Soldermask(Side::Top), true),
+            (LayerType::Soldermask(Side::Bottom), false),
+            (LayerType::MechanicalOutline, true),
+        ];
+        assert!(check_layer_set(&complete).is_empty());
+
+        // Two-sided copper with only the top mask, no outline
+        let issues = check_layer_set(&complete[..3]);
+        assert_eq!(issues, vec![LayerSetIssue::MissingOutline, LayerSetIssue::SingleSidedMask(Side::Top)]);
+        assert!(issues[0].blocks(LayerSetOperation::Drc));
+        assert!(issues[0].blocks(LayerSetOperation::Export));
+        assert!(!issues[1].blocks(LayerSetOperation::Export));
+
+        // A single-sided board only has the top mask
+        let single_sided = [(LayerType::Copper(1), false), (LayerType::Soldermask(Side::Top), false), (LayerType::MechanicalOutline, false)];
+        assert_eq!(check_layer_set(&single_sided), vec![LayerSetIssue::NoVisibleLayers]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Simplified layer drawing when zoomed far out
+//!
+//! Once a layer's typical primitive is smaller on screen than the threshold,
+//! drawing it in full detail only produces aliased mush at a large cost. The
+//! layer is then drawn as its coverage of a grid of threshold-sized screen
+//! cells: each cell touched by a draw, flash or region is filled, and filled
+//! cells are merged into row spans. The mesh size depends on the layer's size
+//! on screen, not on its primitive count. Clear (negative) primitives are
+//! ignored at this level of detail.
+
+use egui::epaint::Mesh;
+use egui::{Color32, Pos2, Rect, Vec2};
+use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
+
+use super::outline::contains_point;
+use super::primitives::{Polarity, PrimitiveShape, TaggedPrimitive};
+
+/// Coverage grids larger than this fall back to full detail
+const MAX_LOD_CELLS: usize = 4_000_000;
+
+/// When layers switch to the simplified drawing, saved with the app settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LodSettings {
+    pub enabled: bool,
+    /// Layers whose typical primitive is smaller than this on screen are simplified
+    pub threshold_px: f32,
+}
+
+impl Default for LodSettings {
+    fn default() -> Self {
+        Self { enabled: true, threshold_px: 2.0 }
+    }
+}
+
+impl LodSettings {
+    /// Whether a layer with primitives of `typical_size` mm is simplified at `px_per_mm`
+    pub fn applies(&self, typical_size: Option<f64>, px_per_mm: f32) -> bool {
+        self.enabled && typical_size.is_some_and(|size| (size as f32) * px_per_mm < self.threshold_px)
+    }
+}
+
+/// Median of the larger bounds dimension of the dark primitives, in mm
+pub fn typical_primitive_size(primitives: &[TaggedPrimitive]) -> Option<f64> {
+    let mut sizes: Vec<f64> = primitives.iter()
+        .filter(|primitive| primitive.polarity == Polarity::Dark)
+        .map(|primitive| {
+            let (min, max) = primitive.shape.bounds();
+            (max.x - min.x).max(max.y - min.y)
+        })
+        .collect();
+    if sizes.is_empty() {
+        return None;
+    }
+    let middle = sizes.len() / 2;
+    sizes.select_nth_unstable_by(middle, f64::total_cmp);
+    Some(sizes[middle])
+}
+
+/// Cells of `cell_px` screen pixels, counted from the layer origin
+struct CoverageGrid {
+    cell_px: f32,
+    min: (i64, i64),
+    columns: usize,
+    rows: usize,
+    filled: Vec<bool>,
+}
+
+impl CoverageGrid {
+    fn cell_of(&self, offset: Vec2) -> (i64, i64) {
+        ((offset.x / self.cell_px).floor() as i64, (offset.y / self.cell_px).floor() as i64)
+    }
+
+    fn fill(&mut self, (column, row): (i64, i64)) {
+        let (column, row) = (column - self.min.0, row - self.min.1);
+        if column >= 0 && row >= 0 && (column as usize) < self.columns && (row as usize) < self.rows {
+            self.filled[row as usize * self.columns + column as usize] = true;
+        }
+    }
+
+    fn fill_rect(&mut self, min: Vec2, max: Vec2) {
+        let (first, last) = (self.cell_of(min), self.cell_of(max));
+        for row in first.1..=last.1 {
+            for column in first.0..=last.0 {
+                self.fill((column, row));
+            }
+        }
+    }
+
+    fn fill_line(&mut self, start: Vec2, end: Vec2) {
+        let steps = ((end - start).length() / (self.cell_px * 0.5)).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            self.fill(self.cell_of(start + (end - start) * (step as f32 / steps as f32)));
+        }
+    }
+
+    /// Cells whose center lies inside the polygon, plus the cells of its vertices
+    fn fill_polygon(&mut self, polygon: &[Vec2]) {
+        let (min, max) = screen_bounds(polygon.iter().copied());
+        let (first, last) = (self.cell_of(min), self.cell_of(max));
+        let contour: Vec<Point2<f64>> = polygon.iter().map(|p| Point2::new(p.x as f64, p.y as f64)).collect();
+        for row in first.1..=last.1 {
+            for column in first.0..=last.0 {
+                let center = Point2::new((column as f64 + 0.5) * self.cell_px as f64, (row as f64 + 0.5) * self.cell_px as f64);
+                if contains_point(&contour, center) {
+                    self.fill((column, row));
+                }
+            }
+        }
+        for point in polygon {
+            self.fill(self.cell_of(*point));
+        }
+    }
+}
+
+fn screen_bounds(points: impl Iterator<Item = Vec2>) -> (Vec2, Vec2) {
+    points.fold((Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)), |(min, max), p| (min.min(p), max.max(p)))
+}
+
+/// Mesh of the cells covered by the dark primitives, placed relative to `origin`
+/// `to_screen` maps a layer point to its screen offset from the layer origin.
+/// Returns None when the layer is too large on screen for a coverage grid.
+pub fn build_lod_mesh(
+    primitives: &[TaggedPrimitive],
+    origin: Pos2,
+    to_screen: impl Fn(Point2<f64>) -> Vec2,
+    cell_px: f32,
+    color: Color32,
+) -> Option<Mesh> {
+    let cell_px = cell_px.max(0.5);
+    let dark: Vec<&TaggedPrimitive> = primitives.iter().filter(|primitive| primitive.polarity == Polarity::Dark).collect();
+    let corners = |primitive: &TaggedPrimitive| {
+        let (min, max) = primitive.shape.bounds();
+        [Point2::new(min.x, min.y), Point2::new(max.x, min.y), Point2::new(max.x, max.y), Point2::new(min.x, max.y)].map(&to_screen)
+    };
+    let (min, max) = screen_bounds(dark.iter().flat_map(|primitive| corners(primitive)));
+    if !(min.x.is_finite() && max.x.is_finite()) {
+        return Some(Mesh::default());
+    }
+
+    let first = ((min.x / cell_px).floor() as i64, (min.y / cell_px).floor() as i64);
+    let last = ((max.x / cell_px).floor() as i64, (max.y / cell_px).floor() as i64);
+    let (columns, rows) = ((last.0 - first.0 + 1) as usize, (last.1 - first.1 + 1) as usize);
+    if columns.saturating_mul(rows) > MAX_LOD_CELLS {
+        return None;
+    }
+    let mut grid = CoverageGrid { cell_px, min: first, columns, rows, filled: vec![false; columns * rows] };
+
+    for primitive in dark {
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, .. } => grid.fill_line(to_screen(*start), to_screen(*end)),
+            PrimitiveShape::Flash { .. } => {
+                let (min, max) = screen_bounds(corners(primitive).into_iter());
+                grid.fill_rect(min, max);
+            }
+            PrimitiveShape::Region { points } => {
+                let polygon: Vec<Vec2> = points.iter().map(|point| to_screen(*point)).collect();
+                grid.fill_polygon(&polygon);
+            }
+        }
+    }
+
+    // One rectangle per run of filled cells in a row
+    let mut mesh = Mesh::default();
+    for row in 0..rows {
+        let cells = &grid.filled[row * columns..(row + 1) * columns];
+        let mut column = 0;
+        while column < columns {
+            if !cells[column] {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < columns && cells[column] {
+                column += 1;
+            }
+            let top_left = Vec2::new((first.0 + start as i64) as f32, (first.1 + row as i64) as f32) * cell_px;
+            let size = Vec2::new((column - start) as f32, 1.0) * cell_px;
+            mesh.add_colored_rect(Rect::from_min_size(origin + top_left, size), color);
+        }
+    }
+    Some(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dark(shape: PrimitiveShape) -> TaggedPrimitive {
+        TaggedPrimitive { net: None, polarity: Polarity::Dark, shape }
+    }
+
+    #[test]
+    fn test_lod_mesh_merges_covered_cells_into_row_spans() {
+        // A 10 mm trace along y = 0 and a square pour at 1 px per mm, on 2 px cells
+        let mut primitives = vec![
+            dark(PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(9.9, 0.0), width: 0.1 }),
+            dark(PrimitiveShape::Region {
+                points: vec![Point2::new(0.5, 4.5), Point2::new(3.5, 4.5), Point2::new(3.5, 7.5), Point2::new(0.5, 7.5)],
+            }),
+        ];
+        primitives.extend((0..1000).map(|i| TaggedPrimitive {
+            net: None,
+            polarity: Polarity::Clear,
+            shape: PrimitiveShape::Segment { start: Point2::new(i as f64, 50.0), end: Point2::new(i as f64, 60.0), width: 0.1 },
+        }));
+        let to_screen = |point: Point2<f64>| Vec2::new(point.x as f32, point.y as f32);
+        let mesh = build_lod_mesh(&primitives, Pos2::new(100.0, 100.0), to_screen, 2.0, Color32::RED).unwrap();
+
+        // The trace fills one row of cells, the pour two; clear primitives add nothing
+        let rects = mesh.indices.len() / 6;
+        assert_eq!(rects, 1 + 2);
+        assert!(mesh.vertices.iter().all(|vertex| vertex.pos.y >= 100.0 && vertex.pos.x >= 100.0));
+
+        assert!((typical_primitive_size(&primitives[..1]).unwrap() - 10.0).abs() < 1e-9);
+        let settings = LodSettings::default();
+        assert!(settings.applies(Some(0.2), 5.0));
+        assert!(!settings.applies(Some(0.2), 20.0));
+        assert!(!LodSettings { enabled: false, ..settings }.applies(Some(0.2), 5.0));
+    }
+}
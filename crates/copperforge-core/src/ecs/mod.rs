@@ -6,6 +6,25 @@ pub mod systems;
 pub mod factories;
 pub mod detection;
 pub mod units;
+pub mod nets;
+pub mod primitives;
+pub mod aperture_macro;
+pub mod drill;
+pub mod registration;
+pub mod snap;
+pub mod outline;
+pub mod parse_cache;
+pub mod layer_nudge;
+pub mod parse_diagnostics;
+pub mod layer_set;
+pub mod provenance;
+pub mod render_cache;
+pub mod edge_measure;
+pub mod lod;
+pub mod polarity;
+pub mod panel;
+pub mod picking;
+pub mod paste_adjust;
 
 pub use types::*;
 pub use components::*;
@@ -14,6 +33,25 @@ pub use systems::*;
 pub use factories::*;
 pub use detection::*;
 pub use units::*;
+pub use nets::*;
+pub use primitives::*;
+pub use aperture_macro::*;
+pub use drill::*;
+pub use registration::*;
+pub use snap::*;
+pub use outline::*;
+pub use parse_cache::*;
+pub use layer_nudge::*;
+pub use parse_diagnostics::*;
+pub use layer_set::*;
+pub use provenance::*;
+pub use render_cache::*;
+pub use edge_measure::*;
+pub use lod::*;
+pub use polarity::*;
+pub use panel::*;
+pub use picking::*;
+pub use paste_adjust::*;
 
 use bevy_ecs::prelude::*;
 // All types now local to ECS module - no more layer_operations dependency
@@ -32,32 +70,17 @@ pub fn setup_ecs_world() -> World {
     world.insert_resource(CoordinateUpdateTracker::default());
     world.insert_resource(UnitsResource::default());
     world.insert_resource(ZoomResource::default());
+    world.insert_resource(NetTable::default());
+    world.insert_resource(DrillData::default());
+    world.insert_resource(RegistrationIssues::default());
+    world.insert_resource(LayerSetIssues::default());
+    world.insert_resource(LayerRenderCache::default());
+    world.insert_resource(PanelSettings::default());
+    world.insert_resource(PasteAdjustSettings::default());
     
     world
 }
 
-/* DEPRECATED: LayerManager compatibility functions
-// Deprecated: Use factories::create_layer_from_info instead
-pub fn spawn_layer(
-    world: &mut World,
-    layer_info: &LayerInfoOrig,
-    gerber_layer: GerberLayer,
-) -> Entity {
-    create_layer_from_info(world, layer_info, gerber_layer)
-}
-*/
-
-/* DEPRECATED: LayerManager migration (no longer needed)
-// Migration function to populate ECS world from LayerManager data (deprecated)
-pub fn migrate_layers_to_ecs(world: &mut World, layer_manager: &crate::layer_operations::LayerManager) {
-    // Use the bulk factory to create all layer entities
-    create_layers_from_manager(world, layer_manager);
-    
-    // Set active layer
-    world.insert_resource(ActiveLayer(layer_manager.active_layer));
-}
-*/
-
 // Query functions for LayerManager facade
 pub fn get_layer_entities(world: &mut World) -> Vec<Entity> {
     let mut query = world.query::<Entity>();
@@ -95,6 +118,14 @@ pub fn set_layer_visibility(world: &mut World, layer_type: LayerType, visible: b
     }
 }
 
+// Flip a loaded layer's visibility; returns the new state, None when the layer is not loaded
+pub fn toggle_layer_visibility(world: &mut World, layer_type: LayerType) -> Option<bool> {
+    let entity = get_layer_by_type(world, layer_type)?;
+    let mut visibility = world.get_mut::<Visibility>(entity)?;
+    visibility.visible = !visibility.visible;
+    Some(visibility.visible)
+}
+
 pub fn get_layer_count(world: &mut World) -> usize {
     let mut query = world.query::<Entity>();
     query.iter(world).count()
@@ -156,6 +187,13 @@ pub fn detect_layer_type(world: &World, filename: &str) -> Option<LayerType> {
         .and_then(|detector| detector.0.detect_layer_type(filename))
 }
 
+// Detect a layer from its X2 FileFunction attribute, falling back to the filename
+pub fn detect_layer_type_with_content(world: &World, filename: &str, content: &str) -> Option<LayerType> {
+    world.get_resource::<LayerDetectorResource>().and_then(|detector| {
+        detector.0.detect_from_attributes(content).or_else(|| detector.0.detect_layer_type(filename))
+    })
+}
+
 pub fn mark_coordinates_dirty(world: &mut World) {
     if let Some(mut tracker) = world.get_resource_mut::<CoordinateUpdateTracker>() {
         tracker.dirty = true;
@@ -182,6 +220,14 @@ pub fn get_layer_data(world: &mut World, layer_type: LayerType) -> Option<(Entit
         .find(|(_, layer_info, _, _)| layer_info.layer_type == layer_type)
 }
 
+// Get the content hash of every loaded layer (used by incremental DRC)
+pub fn get_layer_content_hashes(world: &mut World) -> std::collections::HashMap<LayerType, u64> {
+    let mut query = world.query::<(&components::LayerInfo, &components::LayerContentHash)>();
+    query.iter(world)
+        .map(|(layer_info, content_hash)| (layer_info.layer_type, content_hash.0))
+        .collect()
+}
+
 // Get layer render properties (replaces LayerManager::get_layer_render_properties_ecs)
 pub fn get_layer_render_properties(world: &mut World, layer_type: LayerType) -> Option<&components::RenderProperties> {
     if let Some(entity) = get_layer_by_type_readonly(world, layer_type) {
@@ -225,6 +271,20 @@ pub fn get_layer_assignments(world: &World) -> std::collections::HashMap<String,
 
 // Get combined bounding box from all visible layers (replaces LayerManager::get_combined_bounding_box_ecs)
 pub fn get_combined_bounding_box(world: &mut World) -> Option<gerber_viewer::BoundingBox> {
+    layers_bounding_box(world, true)
+}
+
+// Check if any gerber layer is loaded; false is the empty state of a fresh start
+pub fn has_loaded_layers(world: &mut World) -> bool {
+    world.query::<(&components::LayerInfo, &components::GerberData)>().iter(world).next().is_some()
+}
+
+// Get combined bounding box of every loaded layer, visible or not
+pub fn get_loaded_bounding_box(world: &mut World) -> Option<gerber_viewer::BoundingBox> {
+    layers_bounding_box(world, false)
+}
+
+fn layers_bounding_box(world: &mut World, visible_only: bool) -> Option<gerber_viewer::BoundingBox> {
     use gerber_viewer::BoundingBox;
     use nalgebra::Point2;
     
@@ -232,7 +292,7 @@ pub fn get_combined_bounding_box(world: &mut World) -> Option<gerber_viewer::Bou
     let mut combined_bbox: Option<BoundingBox> = None;
     
     for (_, gerber_data, visibility) in query.iter(world) {
-        if visibility.visible {
+        if visibility.visible || !visible_only {
             let layer_bbox = gerber_data.0.bounding_box();
             combined_bbox = match combined_bbox {
                 Some(existing) => Some(BoundingBox {
@@ -267,9 +327,13 @@ pub fn mark_coordinates_dirty_ecs(world: &mut World) {
 pub use systems::{
     assign_gerber_to_layer_system,
     auto_assign_gerbers_system,
+    bulk_assign_gerbers_system,
     clear_all_layers_system,
     add_unassigned_gerbers_system,
     load_gerbers_from_directory_system,
+    reload_layer_from_file_system,
+    reparse_layer_with_format,
+    reparse_unassigned_with_format,
 };
 
 // Get layer visibility (replaces LayerManager::get_layer_visibility)
@@ -283,6 +347,31 @@ pub fn get_layer_visibility(world: &mut World, layer_type: LayerType) -> bool {
     }
 }
 
+// Get the active layer (replaces LayerManager::active_layer)
+pub fn get_active_layer(world: &World) -> LayerType {
+    world.get_resource::<ActiveLayer>()
+        .map(|active| active.0)
+        .unwrap_or(LayerType::Copper(1))
+}
+
+// Set the active layer
+pub fn set_active_layer(world: &mut World, layer_type: LayerType) {
+    world.insert_resource(ActiveLayer(layer_type));
+}
+
+// Loaded layers in layer-list order (the order Alt+1..9 refer to)
+pub fn get_loaded_layer_types(world: &mut World) -> Vec<LayerType> {
+    LayerType::all()
+        .into_iter()
+        .filter(|layer_type| get_layer_by_type(world, *layer_type).is_some())
+        .collect()
+}
+
+// Layer toggled by Alt+<number>: the Nth loaded layer in layer-list order, counting from 1
+pub fn layer_for_toggle_key(world: &mut World, number: usize) -> Option<LayerType> {
+    get_loaded_layer_types(world).get(number.checked_sub(1)?).copied()
+}
+
 // Update coordinates from display manager (replaces LayerManager::update_coordinates_from_display_ecs)
 pub fn update_coordinates_from_display(world: &mut World, display_manager: &crate::display::DisplayManager) {
     // TODO: Implement proper transform updates from display manager
@@ -296,4 +385,48 @@ pub fn update_coordinates_from_display(world: &mut World, display_manager: &crat
     
     // Mark coordinates as updated
     mark_coordinates_updated(world);
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_toggle_keys_follow_the_layer_list() {
+        let mut world = setup_ecs_world();
+        for layer_type in [LayerType::MechanicalOutline, LayerType::TOP_SILK, LayerType::BOTTOM_COPPER, LayerType::TOP_COPPER] {
+            create_gerber_layer_entity(&mut world, layer_type, gerber_viewer::GerberLayer::new(Vec::new()), None, None, true);
+        }
+
+        // Copper top to bottom, then the other layers, whatever order they were loaded in
+        assert_eq!(layer_for_toggle_key(&mut world, 1), Some(LayerType::TOP_COPPER));
+        assert_eq!(layer_for_toggle_key(&mut world, 2), Some(LayerType::BOTTOM_COPPER));
+        assert_eq!(layer_for_toggle_key(&mut world, 3), Some(LayerType::TOP_SILK));
+        assert_eq!(layer_for_toggle_key(&mut world, 4), Some(LayerType::MechanicalOutline));
+        assert_eq!(layer_for_toggle_key(&mut world, 5), None);
+        assert_eq!(layer_for_toggle_key(&mut world, 0), None);
+
+        assert_eq!(toggle_layer_visibility(&mut world, LayerType::BOTTOM_COPPER), Some(false));
+        assert!(!get_layer_visibility(&mut world, LayerType::BOTTOM_COPPER));
+        assert!(get_layer_visibility(&mut world, LayerType::TOP_COPPER));
+        assert_eq!(toggle_layer_visibility(&mut world, LayerType::BOTTOM_COPPER), Some(true));
+        assert_eq!(toggle_layer_visibility(&mut world, LayerType::Paste(Side::Top)), None);
+    }
+
+    #[test]
+    fn test_empty_state_until_a_layer_is_loaded() {
+        let mut world = setup_ecs_world();
+        assert!(!has_loaded_layers(&mut world));
+
+        // A loaded layer keeps its source; a single pad is enough to give it bounds
+        let source = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,1.000000*%\nD10*\nX1000000Y2000000D03*\nM02*\n";
+        let gerber = parse_gerber(source).0.expect("gerber parses");
+        create_gerber_layer_entity(&mut world, LayerType::TOP_COPPER, gerber, Some(source.to_string()), None, true);
+        assert!(has_loaded_layers(&mut world));
+        assert!(get_combined_bounding_box(&mut world).is_some());
+        let primitive_counts: Vec<usize> = world.query::<&LayerPrimitives>().iter(&world).map(|primitives| primitives.0.len()).collect();
+        assert!(matches!(primitive_counts[..], [count] if count > 0), "{:?}", primitive_counts);
+
+        clear_all_layers_system(&mut world);
+        assert!(!has_loaded_layers(&mut world));
+    }
+}
@@ -0,0 +1,111 @@
+//! Net extraction from Gerber X2 object attributes (%TO.N,<net>*%)
+//!
+//! Net names come from the primitives parsed in `primitives.rs`; only
+//! primitives carrying a net attribute are kept.
+
+use bevy_ecs::prelude::*;
+use std::collections::HashMap;
+use super::primitives::{PrimitiveShape, TaggedPrimitive};
+
+/// A primitive belonging to a net, in gerber (mm) coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetPrimitive {
+    pub net_id: u32,
+    pub shape: PrimitiveShape,
+}
+
+// Net-tagged primitives of a layer
+#[derive(Component, Clone, Debug, Default)]
+pub struct NetGeometry(pub Vec<NetPrimitive>);
+
+// Net names shared by all layers; the index in `names` is the net id
+#[derive(Resource, Clone, Debug, Default)]
+pub struct NetTable {
+    pub names: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+impl NetTable {
+    /// Get the id for a net name, registering it if unknown
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len() as u32;
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    pub fn name(&self, net_id: u32) -> Option<&str> {
+        self.names.get(net_id as usize).map(|s| s.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.ids.clear();
+    }
+}
+
+/// Build the net geometry for a layer, registering net names in the shared table
+pub fn build_net_geometry(net_table: &mut NetTable, primitives: &[TaggedPrimitive]) -> NetGeometry {
+    let net_primitives = primitives
+        .iter()
+        .filter_map(|primitive| {
+            primitive.net.as_ref().map(|net| NetPrimitive {
+                net_id: net_table.intern(net),
+                shape: primitive.shape.clone(),
+            })
+        })
+        .collect();
+    NetGeometry(net_primitives)
+}
+
+/// Get all known net names, sorted, with their ids
+pub fn get_net_list(world: &World) -> Vec<(u32, String)> {
+    let mut nets: Vec<(u32, String)> = world.get_resource::<NetTable>()
+        .map(|table| table.names.iter().enumerate().map(|(id, name)| (id as u32, name.clone())).collect())
+        .unwrap_or_default();
+    nets.sort_by(|a, b| a.1.cmp(&b.1));
+    nets
+}
+
+/// Get the name of a net by id
+pub fn get_net_name(world: &World, net_id: u32) -> Option<String> {
+    world.get_resource::<NetTable>()
+        .and_then(|table| table.name(net_id).map(|s| s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::primitives::{extract_primitives, ApertureShape};
+    use nalgebra::Point2;
+
+    #[test]
+    fn test_build_net_geometry() {
+        let gerber = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.250000*%\n%ADD11R,1.000000X0.500000*%\n\
+            %TO.N,GND*%\nD10*\nX1000000Y2000000D02*\nX3000000Y2000000D01*\n%TD*%\n\
+            %TO.N,VCC*%\nD11*\nX5000000Y-1000000D03*\n%TD*%\nD10*\nX0Y0D02*\nX1000000Y0D01*\nM02*\n";
+
+        let mut net_table = NetTable::default();
+        let geometry = build_net_geometry(&mut net_table, &extract_primitives(gerber));
+        assert_eq!(geometry.0.len(), 2);
+
+        assert_eq!(net_table.name(geometry.0[0].net_id), Some("GND"));
+        assert_eq!(geometry.0[0].shape, PrimitiveShape::Segment {
+            start: Point2::new(1.0, 2.0),
+            end: Point2::new(3.0, 2.0),
+            width: 0.25,
+        });
+
+        assert_eq!(net_table.name(geometry.0[1].net_id), Some("VCC"));
+        assert_eq!(geometry.0[1].shape, PrimitiveShape::Flash {
+            center: Point2::new(5.0, -1.0),
+            width: 1.0,
+            height: 0.5,
+            aperture: ApertureShape::Rectangle,
+            outline: None,
+        });
+    }
+}
@@ -0,0 +1,440 @@
+//! Board size from the mechanical outline
+//!
+//! Outline draws are chained end to end into loops. The largest closed loop is
+//! the board edge and closed loops inside it are cutouts, subtracted from the
+//! area. Draws that do not join up into a loop are counted so the caller can
+//! warn instead of falling back to the bounding box.
+//!
+//! Partial gerber sets without an outline get one inferred from the bounding box
+//! of the visible layers, so framing and export still work.
+
+use bevy_ecs::prelude::*;
+use gerber_viewer::BoundingBox;
+use nalgebra::{Point2, Vector2};
+
+use super::LayerType;
+use crate::display::DisplayManager;
+use super::primitives::{Polarity, PrimitiveShape, TaggedPrimitive};
+
+/// Endpoints closer than this (mm) are treated as joined
+pub const OUTLINE_JOIN_TOLERANCE: f64 = 0.01;
+
+/// Smallest rectangle enclosing the board at any rotation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotatedRect {
+    pub width: f64,
+    pub height: f64,
+    /// Rotation of the rectangle's width side from the X axis
+    pub angle_degrees: f64,
+}
+
+/// Measurements of the board outline, all in mm / mm²
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardGeometry {
+    /// Enclosed area with cutouts removed
+    pub area: f64,
+    /// Length of the outer board edge
+    pub perimeter: f64,
+    /// Edge length of all cutouts
+    pub cutout_perimeter: f64,
+    pub cutouts: usize,
+    /// Axis-aligned size (width, height)
+    pub bounding_size: (f64, f64),
+    pub min_rect: RotatedRect,
+    /// Chains of outline draws that do not close on themselves
+    pub open_chains: usize,
+}
+
+/// Signed polygon area (positive when counter-clockwise)
+pub(crate) fn signed_area(points: &[Point2<f64>]) -> f64 {
+    let n = points.len();
+    (0..n).map(|i| {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        a.x * b.y - b.x * a.y
+    }).sum::<f64>() / 2.0
+}
+
+fn loop_length(points: &[Point2<f64>]) -> f64 {
+    let n = points.len();
+    (0..n).map(|i| (points[(i + 1) % n] - points[i]).norm()).sum()
+}
+
+pub(crate) fn contains_point(polygon: &[Point2<f64>], point: Point2<f64>) -> bool {
+    let mut inside = false;
+    for i in 0..polygon.len() {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Chain outline draws into loops; returns (closed loops, number of open chains)
+/// Regions are already closed and become loops as they are
+pub fn chain_outline_loops(primitives: &[TaggedPrimitive], tolerance: f64) -> (Vec<Vec<Point2<f64>>>, usize) {
+    let mut loops = Vec::new();
+    let mut edges: Vec<(Point2<f64>, Point2<f64>)> = Vec::new();
+    for primitive in primitives {
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, .. } if (end - start).norm() > tolerance => edges.push((*start, *end)),
+            PrimitiveShape::Region { points } => loops.push(points.clone()),
+            _ => {}
+        }
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut open_chains = 0;
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let mut chain = vec![edges[first].0, edges[first].1];
+
+        // Extend the chain from its end, and once that stalls, from its start
+        for reverse in [false, true] {
+            loop {
+                let tip = if reverse { chain[0] } else { *chain.last().unwrap() };
+                if chain.len() > 2 && (tip - if reverse { *chain.last().unwrap() } else { chain[0] }).norm() <= tolerance {
+                    break;
+                }
+                let next = (0..edges.len()).filter(|&i| !used[i]).find_map(|i| {
+                    let (a, b) = edges[i];
+                    if (a - tip).norm() <= tolerance {
+                        Some((i, b))
+                    } else if (b - tip).norm() <= tolerance {
+                        Some((i, a))
+                    } else {
+                        None
+                    }
+                });
+                let Some((index, point)) = next else {
+                    break;
+                };
+                used[index] = true;
+                if reverse {
+                    chain.insert(0, point);
+                } else {
+                    chain.push(point);
+                }
+            }
+        }
+
+        if chain.len() > 3 && (chain[0] - *chain.last().unwrap()).norm() <= tolerance {
+            chain.pop();
+            loops.push(chain);
+        } else {
+            open_chains += 1;
+        }
+    }
+    (loops, open_chains)
+}
+
+/// Convex hull (Andrew's monotone chain), counter-clockwise
+fn convex_hull(points: &[Point2<f64>]) -> Vec<Point2<f64>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let cross = |o: Point2<f64>, a: Point2<f64>, b: Point2<f64>| (a - o).perp(&(b - o));
+    let mut hull: Vec<Point2<f64>> = Vec::with_capacity(sorted.len() * 2);
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let base = hull.len();
+        for point in pass {
+            while hull.len() >= base + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0 {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+/// Minimum-area enclosing rectangle; one of its sides lies along a hull edge
+fn min_area_rect(points: &[Point2<f64>]) -> RotatedRect {
+    let hull = convex_hull(points);
+    let mut best = RotatedRect { width: 0.0, height: 0.0, angle_degrees: 0.0 };
+    let mut best_area = f64::INFINITY;
+    for i in 0..hull.len() {
+        let edge = hull[(i + 1) % hull.len()] - hull[i];
+        if edge.norm() <= f64::EPSILON {
+            continue;
+        }
+        let axis = edge.normalize();
+        let normal = Vector2::new(-axis.y, axis.x);
+        let (mut min_u, mut max_u, mut min_v, mut max_v) = (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY);
+        for point in &hull {
+            let (u, v) = (point.coords.dot(&axis), point.coords.dot(&normal));
+            min_u = min_u.min(u);
+            max_u = max_u.max(u);
+            min_v = min_v.min(v);
+            max_v = max_v.max(v);
+        }
+        let (width, height) = (max_u - min_u, max_v - min_v);
+        if width * height < best_area - 1e-9 {
+            best_area = width * height;
+            // Report the longer side as the width, angle folded into [0, 180)
+            let angle = axis.y.atan2(axis.x).to_degrees();
+            best = if width >= height {
+                RotatedRect { width, height, angle_degrees: angle.rem_euclid(180.0) }
+            } else {
+                RotatedRect { width: height, height: width, angle_degrees: (angle + 90.0).rem_euclid(180.0) }
+            };
+        }
+    }
+    best
+}
+
+/// Area, perimeter and sizes of the board described by outline primitives
+/// Fails when no outline draw closes into a loop
+pub fn measure_board_outline(primitives: &[TaggedPrimitive]) -> Result<BoardGeometry, String> {
+    let (loops, open_chains) = chain_outline_loops(primitives, OUTLINE_JOIN_TOLERANCE);
+    let Some(outer) = loops.iter().max_by(|a, b| signed_area(a).abs().total_cmp(&signed_area(b).abs())) else {
+        return Err(if open_chains > 0 {
+            format!("Outline does not form a closed loop ({} open chain(s))", open_chains)
+        } else {
+            "No outline loaded".to_string()
+        });
+    };
+
+    let inner: Vec<&Vec<Point2<f64>>> = loops.iter()
+        .filter(|candidate| !std::ptr::eq(*candidate, outer) && contains_point(outer, candidate[0]))
+        .collect();
+
+    let (min, max) = outer.iter().fold(
+        (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(min, max), p| (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y))),
+    );
+
+    Ok(BoardGeometry {
+        area: signed_area(outer).abs() - inner.iter().map(|cutout| signed_area(cutout).abs()).sum::<f64>(),
+        perimeter: loop_length(outer),
+        cutout_perimeter: inner.iter().map(|cutout| loop_length(cutout)).sum(),
+        cutouts: inner.len(),
+        bounding_size: (max.x - min.x, max.y - min.y),
+        min_rect: min_area_rect(outer),
+        open_chains,
+    })
+}
+
+/// Measure the loaded mechanical outline
+pub fn get_board_geometry(world: &mut World) -> Result<BoardGeometry, String> {
+    measure_board_outline(&super::get_layer_primitives(world, LayerType::MechanicalOutline))
+}
+
+/// Convex area to fill as the board: the hull of the largest closed outline loop,
+/// or of every outline point if nothing closes
+pub fn board_fill_polygon(primitives: &[TaggedPrimitive]) -> Vec<Point2<f64>> {
+    let (loops, _) = chain_outline_loops(primitives, OUTLINE_JOIN_TOLERANCE);
+    if let Some(outer) = loops.iter().max_by(|a, b| signed_area(a).abs().total_cmp(&signed_area(b).abs())) {
+        return convex_hull(outer);
+    }
+    let points: Vec<Point2<f64>> = primitives.iter()
+        .flat_map(|primitive| match &primitive.shape {
+            PrimitiveShape::Segment { start, end, .. } => vec![*start, *end],
+            PrimitiveShape::Region { points } => points.clone(),
+            PrimitiveShape::Flash { center, .. } => vec![*center],
+        })
+        .collect();
+    convex_hull(&points)
+}
+
+/// Board edge and cutouts as drawn, for exported profiles: the largest closed
+/// outline loop and the closed loops inside it, or the convex hull of every
+/// outline point (and no cutouts) if nothing closes
+pub fn board_profile(primitives: &[TaggedPrimitive]) -> (Vec<Point2<f64>>, Vec<Vec<Point2<f64>>>) {
+    let (loops, _) = chain_outline_loops(primitives, OUTLINE_JOIN_TOLERANCE);
+    let Some(outer) = loops.iter().max_by(|a, b| signed_area(a).abs().total_cmp(&signed_area(b).abs())) else {
+        return (board_fill_polygon(primitives), Vec::new());
+    };
+    let cutouts = loops.iter()
+        .filter(|candidate| !std::ptr::eq(*candidate, outer) && contains_point(outer, candidate[0]))
+        .cloned()
+        .collect();
+    (outer.clone(), cutouts)
+}
+
+/// Board points the design origin can be set to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineReference {
+    /// Bottom-left corner of the outline's bounding box
+    BottomLeft,
+    /// Center of the bounding box
+    Center,
+    /// Area centroid of the board, cutouts removed
+    Centroid,
+}
+
+impl OutlineReference {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::BottomLeft => "outline bottom-left",
+            Self::Center => "outline center",
+            Self::Centroid => "outline centroid",
+        }
+    }
+}
+
+/// Area centroid of closed loops: the largest loop is the board, the others cutouts
+pub fn outline_centroid(loops: &[&[Point2<f64>]]) -> Option<Point2<f64>> {
+    let outer = loops.iter().map(|points| signed_area(points).abs()).enumerate().max_by(|a, b| a.1.total_cmp(&b.1))?.0;
+    let (mut area, mut moment) = (0.0, Vector2::zeros());
+    for (index, points) in loops.iter().enumerate() {
+        let loop_area = signed_area(points);
+        if loop_area == 0.0 {
+            continue;
+        }
+        // Centroid of one loop, weighted by its area (negative for cutouts)
+        let n = points.len();
+        let sum = (0..n).fold(Vector2::zeros(), |sum, i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            sum + (a.coords + b.coords) * (a.x * b.y - b.x * a.y)
+        });
+        let centroid = sum / (6.0 * loop_area);
+        let weight = if index == outer { loop_area.abs() } else { -loop_area.abs() };
+        area += weight;
+        moment += centroid * weight;
+    }
+    (area > 0.0).then(|| Point2::from(moment / area))
+}
+
+/// Reference point of the outline loops, in the loops' coordinates
+pub fn outline_reference_point(loops: &[&[Point2<f64>]], reference: OutlineReference) -> Option<Point2<f64>> {
+    let points = loops.iter().flat_map(|points| points.iter());
+    let (min, max) = points.fold(
+        (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(min, max), p| (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y))),
+    );
+    if !min.x.is_finite() {
+        return None;
+    }
+    match reference {
+        OutlineReference::BottomLeft => Some(min),
+        OutlineReference::Center => Some(nalgebra::center(&min, &max)),
+        OutlineReference::Centroid => outline_centroid(loops),
+    }
+}
+
+/// Reference point of the mechanical outline as drawn in the view, or None without a closed outline
+pub fn get_outline_reference_point(world: &mut World, display_manager: &DisplayManager, reference: OutlineReference) -> Option<Point2<f64>> {
+    let paths = super::get_outline_paths(world, display_manager)?;
+    let loops: Vec<&[Point2<f64>]> = paths.loops.iter().map(|path| path.points()).collect();
+    outline_reference_point(&loops, reference)
+}
+
+/// Rectangle of hairline draws around `bbox`, standing in for a missing outline
+pub fn bounding_outline(bbox: &BoundingBox) -> Vec<TaggedPrimitive> {
+    let corners = [
+        Point2::new(bbox.min.x, bbox.min.y),
+        Point2::new(bbox.max.x, bbox.min.y),
+        Point2::new(bbox.max.x, bbox.max.y),
+        Point2::new(bbox.min.x, bbox.max.y),
+    ];
+    (0..4).map(|i| TaggedPrimitive {
+        net: None,
+        polarity: Polarity::Dark,
+        shape: PrimitiveShape::Segment { start: corners[i], end: corners[(i + 1) % 4], width: 0.0 },
+    }).collect()
+}
+
+/// Outline primitives of the board, and whether they were inferred
+/// Without a mechanical outline layer, the bounding box of the visible layers is used
+pub fn get_board_outline_primitives(world: &mut World) -> Option<(Vec<TaggedPrimitive>, bool)> {
+    if super::get_layer_by_type(world, LayerType::MechanicalOutline).is_some() {
+        return Some((super::get_layer_primitives(world, LayerType::MechanicalOutline), false));
+    }
+    super::get_combined_bounding_box(world).map(|bbox| (bounding_outline(&bbox), true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(a: (f64, f64), b: (f64, f64)) -> TaggedPrimitive {
+        TaggedPrimitive {
+            net: None,
+            polarity: Polarity::Dark,
+            shape: PrimitiveShape::Segment { start: Point2::new(a.0, a.1), end: Point2::new(b.0, b.1), width: 0.05 },
+        }
+    }
+
+    fn square(center: (f64, f64), half: f64, angle: f64) -> Vec<TaggedPrimitive> {
+        let corners: Vec<(f64, f64)> = (0..4).map(|i| {
+            let a = angle.to_radians() + std::f64::consts::FRAC_PI_4 + i as f64 * std::f64::consts::FRAC_PI_2;
+            (center.0 + half * std::f64::consts::SQRT_2 * a.cos(), center.1 + half * std::f64::consts::SQRT_2 * a.sin())
+        }).collect();
+        (0..4).map(|i| segment(corners[i], corners[(i + 1) % 4])).collect()
+    }
+
+    #[test]
+    fn test_board_profile_keeps_concave_edge_and_cutouts() {
+        // L-shaped board with a 2 x 2 cutout in its wide leg
+        let corners = [(0.0, 0.0), (20.0, 0.0), (20.0, 8.0), (8.0, 8.0), (8.0, 20.0), (0.0, 20.0)];
+        let mut primitives: Vec<TaggedPrimitive> = (0..corners.len())
+            .map(|i| segment(corners[i], corners[(i + 1) % corners.len()]))
+            .collect();
+        primitives.extend(square((14.0, 4.0), 1.0, 0.0));
+
+        let (outer, cutouts) = board_profile(&primitives);
+        assert_eq!(outer.len(), 6);
+        assert!(outer.iter().any(|p| (p.x - 8.0).abs() < 1e-9 && (p.y - 8.0).abs() < 1e-9), "inner corner kept");
+        assert!((signed_area(&outer).abs() - 256.0).abs() < 1e-6);
+        assert_eq!(cutouts.len(), 1);
+        assert!((signed_area(&cutouts[0]).abs() - 4.0).abs() < 1e-6);
+
+        // Nothing closes: fall back to the hull
+        let (outer, cutouts) = board_profile(&primitives[..2]);
+        assert_eq!(outer.len(), 3);
+        assert!(cutouts.is_empty());
+    }
+
+    #[test]
+    fn test_board_area_subtracts_cutouts_and_finds_rotated_rect() {
+        // 20 x 20 board turned 30 degrees, drawn out of order, with a 4 x 4 cutout
+        let mut primitives = square((0.0, 0.0), 10.0, 30.0);
+        primitives.swap(0, 2);
+        primitives.extend(square((2.0, 1.0), 2.0, 0.0));
+        let geometry = measure_board_outline(&primitives).unwrap();
+
+        assert!((geometry.area - (400.0 - 16.0)).abs() < 1e-6);
+        assert!((geometry.perimeter - 80.0).abs() < 1e-6);
+        assert_eq!((geometry.cutouts, geometry.open_chains), (1, 0));
+        assert!((geometry.min_rect.width - 20.0).abs() < 1e-6 && (geometry.min_rect.height - 20.0).abs() < 1e-6);
+        assert!(geometry.bounding_size.0 > 27.0);
+
+        // An outline with a gap is reported, not measured
+        let open = vec![segment((0.0, 0.0), (10.0, 0.0)), segment((10.0, 0.0), (10.0, 10.0)), segment((10.0, 10.0), (0.0, 10.0))];
+        assert!(measure_board_outline(&open).unwrap_err().contains("closed loop"));
+    }
+
+    #[test]
+    fn test_outline_reference_points_with_cutout() {
+        let rect = |min: (f64, f64), max: (f64, f64)| vec![Point2::new(min.0, min.1), Point2::new(max.0, min.1), Point2::new(max.0, max.1), Point2::new(min.0, max.1)];
+        let board = rect((0.0, 0.0), (20.0, 20.0));
+        let mut cutout = rect((12.0, 12.0), (16.0, 16.0));
+        cutout.reverse();
+        let loops = [board.as_slice(), cutout.as_slice()];
+
+        assert_eq!(outline_reference_point(&loops, OutlineReference::BottomLeft), Some(Point2::new(0.0, 0.0)));
+        assert_eq!(outline_reference_point(&loops, OutlineReference::Center), Some(Point2::new(10.0, 10.0)));
+        // (400 * 10 - 16 * 14) / 384 on both axes
+        let centroid = outline_reference_point(&loops, OutlineReference::Centroid).unwrap();
+        assert!((centroid.x - 3776.0 / 384.0).abs() < 1e-9 && (centroid.y - centroid.x).abs() < 1e-9);
+        assert_eq!(outline_reference_point(&[], OutlineReference::Center), None);
+    }
+
+    #[test]
+    fn test_bounding_outline_is_a_closed_board() {
+        let bbox = BoundingBox { min: Point2::new(-5.0, 2.0), max: Point2::new(15.0, 12.0) };
+        let geometry = measure_board_outline(&bounding_outline(&bbox)).unwrap();
+        assert!((geometry.area - 200.0).abs() < 1e-9);
+        assert_eq!(geometry.bounding_size, (20.0, 10.0));
+        assert_eq!(geometry.open_chains, 0);
+    }
+}
@@ -0,0 +1,208 @@
+//! Panel preview: copies of the loaded board in a grid with rails
+//!
+//! Copies are laid out in the boards' own (source) coordinates, one board
+//! pitch (board size plus spacing) apart, starting from the loaded board at
+//! the bottom left. Each copy is drawn by shifting the layer's render transform,
+//! so rotation and mirroring apply to the whole panel. Rails run along the
+//! bottom and top (and optionally the sides), `rail_gap` away from the boards.
+
+use bevy_ecs::prelude::*;
+use egui::{Color32, Painter, Pos2, Shape, Stroke};
+use gerber_viewer::{BoundingBox, GerberTransform, ViewState};
+use nalgebra::{Matrix3, Point2, Vector2};
+use serde::{Deserialize, Serialize};
+
+use super::outline::bounding_outline;
+use super::primitives::{PrimitiveShape, TaggedPrimitive};
+use super::LayerType;
+
+/// Rails are drawn as bare board material
+const RAIL_FILL_COLOR: Color32 = Color32::from_rgba_premultiplied(40, 70, 40, 140);
+const RAIL_STROKE_COLOR: Color32 = Color32::from_rgb(90, 130, 90);
+
+/// Panel arrangement, saved with the app settings
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelSettings {
+    pub enabled: bool,
+    pub columns: u32,
+    pub rows: u32,
+    /// Gap between neighbouring boards (mm)
+    pub spacing_x: f64,
+    pub spacing_y: f64,
+    /// Width of each rail (mm); 0 for none
+    pub rail_width: f64,
+    /// Gap between the boards and the rails (mm)
+    pub rail_gap: f64,
+    /// Rails on the left and right as well as the top and bottom
+    pub side_rails: bool,
+}
+
+impl Default for PanelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            columns: 2,
+            rows: 2,
+            spacing_x: 2.0,
+            spacing_y: 2.0,
+            rail_width: 5.0,
+            rail_gap: 2.0,
+            side_rails: false,
+        }
+    }
+}
+
+/// Board copies and rails of a panel, in board source coordinates
+#[derive(Debug, Clone)]
+pub struct PanelLayout {
+    /// Offset of every copy from the loaded board, the loaded board first
+    pub offsets: Vec<Vector2<f64>>,
+    /// Rail rectangles (min, max)
+    pub rails: Vec<(Point2<f64>, Point2<f64>)>,
+    /// Extent of all copies and rails
+    pub bounds: BoundingBox,
+}
+
+impl PanelLayout {
+    pub fn new(settings: &PanelSettings, board: &BoundingBox) -> Self {
+        let (columns, rows) = (settings.columns.max(1), settings.rows.max(1));
+        let pitch = Vector2::new(board.width() + settings.spacing_x.max(0.0), board.height() + settings.spacing_y.max(0.0));
+        let offsets: Vec<Vector2<f64>> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| Vector2::new(column as f64 * pitch.x, row as f64 * pitch.y)))
+            .collect();
+
+        let boards_max = board.max + Vector2::new((columns - 1) as f64 * pitch.x, (rows - 1) as f64 * pitch.y);
+        let mut rails = Vec::new();
+        let (mut min, mut max) = (board.min, boards_max);
+        if settings.rail_width > 0.0 {
+            let (width, gap) = (settings.rail_width, settings.rail_gap.max(0.0));
+            let (left, right) = if settings.side_rails {
+                (board.min.x - gap - width, boards_max.x + gap + width)
+            } else {
+                (board.min.x, boards_max.x)
+            };
+            rails.push((Point2::new(left, board.min.y - gap - width), Point2::new(right, board.min.y - gap)));
+            rails.push((Point2::new(left, boards_max.y + gap), Point2::new(right, boards_max.y + gap + width)));
+            if settings.side_rails {
+                rails.push((Point2::new(left, board.min.y - gap), Point2::new(board.min.x - gap, boards_max.y + gap)));
+                rails.push((Point2::new(boards_max.x + gap, board.min.y - gap), Point2::new(right, boards_max.y + gap)));
+            }
+            min = Point2::new(left, board.min.y - gap - width);
+            max = Point2::new(right, boards_max.y + gap + width);
+        }
+
+        Self { offsets, rails, bounds: BoundingBox { min, max } }
+    }
+
+    /// Offsets of the copies beyond the loaded board
+    pub fn copies(&self) -> &[Vector2<f64>] {
+        &self.offsets[1..]
+    }
+
+    /// Outline of the whole panel: every board outline plus the rails' outer frame
+    pub fn panel_outline(&self, board_outline: &[TaggedPrimitive]) -> Vec<TaggedPrimitive> {
+        let mut outline = panelize_primitives(board_outline, &self.offsets);
+        if !self.rails.is_empty() {
+            outline.extend(bounding_outline(&self.bounds));
+        }
+        outline
+    }
+}
+
+/// Copy of `primitive` moved by `offset`
+fn offset_primitive(primitive: &TaggedPrimitive, offset: Vector2<f64>) -> TaggedPrimitive {
+    let shape = match &primitive.shape {
+        PrimitiveShape::Segment { start, end, width } => PrimitiveShape::Segment { start: start + offset, end: end + offset, width: *width },
+        PrimitiveShape::Flash { center, width, height, aperture, outline } => {
+            PrimitiveShape::Flash { center: center + offset, width: *width, height: *height, aperture: *aperture, outline: outline.clone() }
+        }
+        PrimitiveShape::Region { points } => PrimitiveShape::Region { points: points.iter().map(|point| point + offset).collect() },
+    };
+    TaggedPrimitive { net: primitive.net.clone(), polarity: primitive.polarity, shape }
+}
+
+/// One copy of the primitives per offset, copy by copy
+pub fn panelize_primitives(primitives: &[TaggedPrimitive], offsets: &[Vector2<f64>]) -> Vec<TaggedPrimitive> {
+    offsets.iter()
+        .flat_map(|offset| primitives.iter().map(move |primitive| offset_primitive(primitive, *offset)))
+        .collect()
+}
+
+/// Render transform of the copy at `offset`: the offset is applied before the layer transform
+pub fn instance_transform(transform: &GerberTransform, offset: Vector2<f64>) -> GerberTransform {
+    GerberTransform::from_matrix(&(transform.to_matrix() * Matrix3::new_translation(&offset)))
+}
+
+/// Paint the rails of `layout` under the layers, with the outline's render transform
+pub fn paint_panel_rails(painter: &Painter, view_state: &ViewState, layout: &PanelLayout, transform: &GerberTransform) {
+    let matrix = transform.to_matrix();
+    let to_screen = |x: f64, y: f64| view_state.gerber_to_screen_coords(matrix.transform_point(&Point2::new(x, y)));
+    for (min, max) in &layout.rails {
+        let corners: Vec<Pos2> = vec![to_screen(min.x, min.y), to_screen(max.x, min.y), to_screen(max.x, max.y), to_screen(min.x, max.y)];
+        painter.add(Shape::convex_polygon(corners, RAIL_FILL_COLOR, Stroke::new(1.0, RAIL_STROKE_COLOR)));
+    }
+}
+
+/// Size of one board in source coordinates: the outline extents, else every loaded layer
+fn board_bounds(world: &mut World) -> Option<BoundingBox> {
+    if super::get_layer_by_type(world, LayerType::MechanicalOutline).is_some() {
+        let primitives = super::get_layer_primitives(world, LayerType::MechanicalOutline);
+        let bounds = primitives.iter().map(|primitive| primitive.shape.bounds()).reduce(|(min_a, max_a), (min_b, max_b)| {
+            (Point2::new(min_a.x.min(min_b.x), min_a.y.min(min_b.y)), Point2::new(max_a.x.max(max_b.x), max_a.y.max(max_b.y)))
+        });
+        if let Some((min, max)) = bounds {
+            return Some(BoundingBox { min, max });
+        }
+    }
+    super::get_loaded_bounding_box(world)
+}
+
+/// Layout of the enabled panel, or None when the panel is off or nothing is loaded
+pub fn get_panel_layout(world: &mut World) -> Option<PanelLayout> {
+    let settings = *world.get_resource::<PanelSettings>().filter(|settings| settings.enabled)?;
+    board_bounds(world).map(|board| PanelLayout::new(&settings, &board))
+}
+
+/// Bounding box to frame in the view: the visible layers, grown to the whole panel when it is on
+pub fn get_view_bounding_box(world: &mut World) -> Option<BoundingBox> {
+    let combined = super::get_combined_bounding_box(world)?;
+    let Some(panel) = get_panel_layout(world) else {
+        return Some(combined);
+    };
+    let mut bounds = combined.clone();
+    bounds.expand(&panel.bounds);
+    let far_copy = panel.offsets.last().copied().unwrap_or_default();
+    bounds.expand(&BoundingBox { min: combined.min + far_copy, max: combined.max + far_copy });
+    Some(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panel_layout_offsets_rails_and_bounds() {
+        let board = BoundingBox { min: Point2::new(0.0, 0.0), max: Point2::new(20.0, 10.0) };
+        let settings = PanelSettings { enabled: true, columns: 3, rows: 2, spacing_x: 2.0, spacing_y: 1.0, rail_width: 5.0, rail_gap: 1.5, side_rails: false };
+        let layout = PanelLayout::new(&settings, &board);
+
+        assert_eq!(layout.offsets.len(), 6);
+        assert_eq!(layout.offsets[0], Vector2::zeros());
+        assert_eq!(layout.offsets[5], Vector2::new(44.0, 11.0));
+        assert_eq!(layout.copies().len(), 5);
+        assert_eq!(layout.rails.len(), 2);
+        assert_eq!(layout.bounds.min, Point2::new(0.0, -6.5));
+        assert_eq!(layout.bounds.max, Point2::new(64.0, 27.5));
+
+        let sides = PanelLayout::new(&PanelSettings { side_rails: true, ..settings }, &board);
+        assert_eq!(sides.rails.len(), 4);
+        assert_eq!(sides.bounds.min, Point2::new(-6.5, -6.5));
+
+        // Export copies every board outline and adds the frame
+        let outline = bounding_outline(&board);
+        assert_eq!(layout.panel_outline(&outline).len(), 6 * 4 + 4);
+        let moved = panelize_primitives(&outline, &layout.offsets[5..]);
+        assert_eq!(moved[0].shape.bounds().0, Point2::new(44.0, 11.0));
+    }
+}
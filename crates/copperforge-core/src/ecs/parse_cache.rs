@@ -0,0 +1,303 @@
+//! On-disk cache of extracted layer primitives
+//!
+//! Primitive extraction (see `primitives`) is the slow part of loading a large
+//! layer. After extracting, the primitives are written with bincode next to the
+//! source file's size, mtime and content hash; the next load reuses them only if
+//! all three and the cache format version still match. The gerber_viewer layer
+//! itself is still parsed on every load since its commands cannot be serialized.
+//!
+//! The cache is used when a `ParseCache` resource is present in the world.
+
+use bevy_ecs::prelude::*;
+use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use super::LayerContentHash;
+use super::aperture_macro::{ApertureOutline, MacroDefinition, MacroShape};
+use super::primitives::{ApertureShape, Polarity, PrimitiveShape, TaggedPrimitive, extract_primitives};
+
+/// Bump whenever the cached representation or the extraction changes meaning
+pub const PARSE_CACHE_VERSION: u32 = 2;
+
+const PARSE_CACHE_DIR_NAME: &str = "parse_cache";
+const CACHE_FILE_EXTENSION: &str = "bin";
+
+/// Cache directory under the app config directory
+pub fn parse_cache_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join(PARSE_CACHE_DIR_NAME)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum CachedShape {
+    Segment { start: [f64; 2], end: [f64; 2], width: f64 },
+    Flash { center: [f64; 2], width: f64, height: f64, aperture: u8, outline: Option<CachedOutline> },
+    Region { points: Vec<[f64; 2]> },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum CachedMacroShape {
+    Circle { center: [f64; 2], diameter: f64, dark: bool },
+    Polygon { points: Vec<[f64; 2]>, dark: bool },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedOutline {
+    shapes: Vec<CachedMacroShape>,
+    definition: Option<MacroDefinition>,
+}
+
+impl From<&ApertureOutline> for CachedOutline {
+    fn from(outline: &ApertureOutline) -> Self {
+        let shapes = outline.shapes.iter().map(|shape| match shape {
+            MacroShape::Circle { center, diameter, dark } => CachedMacroShape::Circle { center: to_array(center), diameter: *diameter, dark: *dark },
+            MacroShape::Polygon { points, dark } => CachedMacroShape::Polygon { points: points.iter().map(to_array).collect(), dark: *dark },
+        }).collect();
+        Self { shapes, definition: outline.definition.clone() }
+    }
+}
+
+impl From<CachedOutline> for ApertureOutline {
+    fn from(cached: CachedOutline) -> Self {
+        let shapes = cached.shapes.into_iter().map(|shape| match shape {
+            CachedMacroShape::Circle { center, diameter, dark } => MacroShape::Circle { center: to_point(&center), diameter, dark },
+            CachedMacroShape::Polygon { points, dark } => MacroShape::Polygon { points: points.iter().map(to_point).collect(), dark },
+        }).collect();
+        Self { shapes, definition: cached.definition }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedPrimitive {
+    net: Option<String>,
+    clear: bool,
+    shape: CachedShape,
+}
+
+/// Source file identity the cached primitives were extracted from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SourceStamp {
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+    content_hash: u64,
+}
+
+impl SourceStamp {
+    fn new(path: &Path, content: &str) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+            content_hash: LayerContentHash::from_content(content).0,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    stamp: SourceStamp,
+    /// How long the extraction took, to report the time a cache hit saves
+    extract_micros: u64,
+    primitives: Vec<CachedPrimitive>,
+}
+
+fn to_array(point: &Point2<f64>) -> [f64; 2] {
+    [point.x, point.y]
+}
+
+fn to_point(array: &[f64; 2]) -> Point2<f64> {
+    Point2::new(array[0], array[1])
+}
+
+const APERTURE_SHAPES: [ApertureShape; 5] = [
+    ApertureShape::Circle,
+    ApertureShape::Rectangle,
+    ApertureShape::Obround,
+    ApertureShape::Polygon,
+    ApertureShape::Other,
+];
+
+impl From<&TaggedPrimitive> for CachedPrimitive {
+    fn from(primitive: &TaggedPrimitive) -> Self {
+        let shape = match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => CachedShape::Segment { start: to_array(start), end: to_array(end), width: *width },
+            PrimitiveShape::Flash { center, width, height, aperture, outline } => CachedShape::Flash {
+                center: to_array(center),
+                width: *width,
+                height: *height,
+                aperture: APERTURE_SHAPES.iter().position(|shape| shape == aperture).unwrap_or(4) as u8,
+                outline: outline.as_deref().map(CachedOutline::from),
+            },
+            PrimitiveShape::Region { points } => CachedShape::Region { points: points.iter().map(to_array).collect() },
+        };
+        Self { net: primitive.net.clone(), clear: primitive.polarity == Polarity::Clear, shape }
+    }
+}
+
+impl From<CachedPrimitive> for TaggedPrimitive {
+    fn from(cached: CachedPrimitive) -> Self {
+        let shape = match cached.shape {
+            CachedShape::Segment { start, end, width } => PrimitiveShape::Segment { start: to_point(&start), end: to_point(&end), width },
+            CachedShape::Flash { center, width, height, aperture, outline } => PrimitiveShape::Flash {
+                center: to_point(&center),
+                width,
+                height,
+                aperture: APERTURE_SHAPES.get(aperture as usize).copied().unwrap_or(ApertureShape::Other),
+                outline: outline.map(|outline| Arc::new(ApertureOutline::from(outline))),
+            },
+            CachedShape::Region { points } => PrimitiveShape::Region { points: points.iter().map(to_point).collect() },
+        };
+        Self { net: cached.net, polarity: if cached.clear { Polarity::Clear } else { Polarity::Dark }, shape }
+    }
+}
+
+/// How one layer's primitives were obtained during a load
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseCacheOutcome {
+    /// Reused from the cache; `saved` is the extraction time minus the cache read
+    Hit { saved: Duration },
+    /// Extracted and written to the cache
+    Miss { extract_time: Duration },
+    /// Extracted, but the cache could not be written
+    WriteFailed(String),
+}
+
+/// Per-file outcomes of the last directory load, for logging
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ParseCacheReport {
+    pub layers: Vec<(String, ParseCacheOutcome)>,
+}
+
+/// Primitive cache directory; loads use it while this resource is in the world
+#[derive(Resource, Debug, Clone)]
+pub struct ParseCache {
+    dir: PathBuf,
+}
+
+impl ParseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, source: &Path) -> PathBuf {
+        let source = source.canonicalize().unwrap_or_else(|_| source.to_path_buf());
+        let key = LayerContentHash::from_content(&source.to_string_lossy()).0;
+        self.dir.join(format!("{:016x}.{}", key, CACHE_FILE_EXTENSION))
+    }
+
+    /// Cached primitives for `source`, or None when missing, stale or from another format version
+    fn read(&self, source: &Path, stamp: &SourceStamp) -> Option<(Vec<TaggedPrimitive>, Duration)> {
+        let bytes = std::fs::read(self.entry_path(source)).ok()?;
+        let entry: CacheEntry = bincode::deserialize(&bytes).ok()?;
+        if entry.version != PARSE_CACHE_VERSION || entry.stamp != *stamp {
+            return None;
+        }
+        let primitives = entry.primitives.into_iter().map(TaggedPrimitive::from).collect();
+        Some((primitives, Duration::from_micros(entry.extract_micros)))
+    }
+
+    fn write(&self, source: &Path, stamp: SourceStamp, primitives: &[TaggedPrimitive], extract_time: Duration) -> Result<(), String> {
+        let entry = CacheEntry {
+            version: PARSE_CACHE_VERSION,
+            stamp,
+            extract_micros: extract_time.as_micros() as u64,
+            primitives: primitives.iter().map(CachedPrimitive::from).collect(),
+        };
+        let bytes = bincode::serialize(&entry).map_err(|e| format!("Failed to encode parse cache entry: {}", e))?;
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("Failed to create parse cache directory: {}", e))?;
+        std::fs::write(self.entry_path(source), bytes).map_err(|e| format!("Failed to write parse cache entry: {}", e))
+    }
+
+    /// Primitives of `source` (whose text is `content`), from the cache when it is still valid
+    pub fn load_or_extract(&self, source: &Path, content: &str) -> (Vec<TaggedPrimitive>, ParseCacheOutcome) {
+        let started = Instant::now();
+        let stamp = SourceStamp::new(source, content);
+        if let Some((primitives, extract_time)) = stamp.as_ref().and_then(|stamp| self.read(source, stamp)) {
+            return (primitives, ParseCacheOutcome::Hit { saved: extract_time.saturating_sub(started.elapsed()) });
+        }
+
+        let started = Instant::now();
+        let primitives = extract_primitives(content);
+        let extract_time = started.elapsed();
+        let outcome = match stamp.map(|stamp| self.write(source, stamp, &primitives, extract_time)) {
+            Some(Ok(())) => ParseCacheOutcome::Miss { extract_time },
+            Some(Err(e)) => ParseCacheOutcome::WriteFailed(e),
+            None => ParseCacheOutcome::WriteFailed(format!("Cannot read file metadata of {}", source.display())),
+        };
+        (primitives, outcome)
+    }
+
+    /// Total size in bytes of the cache entries
+    pub fn size_on_disk(&self) -> u64 {
+        self.entries().filter_map(|path| std::fs::metadata(path).ok()).map(|metadata| metadata.len()).sum()
+    }
+
+    /// Delete every cache entry; returns how many were removed
+    pub fn clear(&self) -> Result<usize, String> {
+        let mut removed = 0;
+        for path in self.entries() {
+            std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn entries(&self) -> impl Iterator<Item = PathBuf> {
+        std::fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(CACHE_FILE_EXTENSION))
+    }
+}
+
+/// Turn the parse cache on (with its directory) or off for later loads
+pub fn set_parse_cache(world: &mut World, cache: Option<ParseCache>) {
+    match cache {
+        Some(cache) => world.insert_resource(cache),
+        None => {
+            world.remove_resource::<ParseCache>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_cache_hit_until_source_changes() {
+        let dir = TempDir::new("parse_cache");
+        let cache = ParseCache::new(dir.path().join("cache"));
+        let source = dir.path().join("top.gbr");
+        let content = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.250000*%\nD10*\nX0Y0D02*\nX1000000Y0D01*\nX2000000Y0D03*\nM02*\n";
+        std::fs::write(&source, content).unwrap();
+
+        let (extracted, outcome) = cache.load_or_extract(&source, content);
+        assert!(matches!(outcome, ParseCacheOutcome::Miss { .. }), "{:?}", outcome);
+        let (cached, outcome) = cache.load_or_extract(&source, content);
+        assert!(matches!(outcome, ParseCacheOutcome::Hit { .. }), "{:?}", outcome);
+        assert_eq!(cached, extracted);
+
+        // A changed file falls back to extracting
+        let changed = content.replace("X1000000", "X1500000");
+        std::fs::write(&source, &changed).unwrap();
+        let (_, outcome) = cache.load_or_extract(&source, &changed);
+        assert!(matches!(outcome, ParseCacheOutcome::Miss { .. }));
+
+        assert!(cache.size_on_disk() > 0);
+        assert_eq!(cache.clear().unwrap(), 1);
+    }
+}
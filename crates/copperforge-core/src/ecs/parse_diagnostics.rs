@@ -0,0 +1,227 @@
+//! Parser warnings and errors per gerber file
+//!
+//! gerber_parser reports commands it could not parse as errors inside the
+//! document, and unsupported or suspicious input through the `log` crate.
+//! `parse_gerber` collects both: log records from the parser are captured on
+//! the parsing thread by `ParserLogCapture`, which the binary installs in front
+//! of its normal logger. Whatever did parse is still returned, so a partially
+//! broken file renders what was recoverable.
+//!
+//! Problems of the last directory load are kept in `LoadDiagnostics` by file
+//! name; layers created from those files get a `ParseDiagnostics` component.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::BufReader;
+
+use bevy_ecs::prelude::*;
+use gerber_viewer::GerberLayer;
+use gerber_viewer::gerber_parser::parse;
+
+use super::LayerType;
+use super::components::LayerInfo;
+
+/// Messages kept per file; a broken file can otherwise report one per line
+pub const MAX_PARSE_MESSAGES: usize = 100;
+
+const PARSER_LOG_TARGET: &str = "gerber_parser";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseMessage {
+    pub severity: DiagnosticSeverity,
+    pub text: String,
+}
+
+/// Problems reported while parsing one file
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct ParseDiagnostics {
+    pub messages: Vec<ParseMessage>,
+    /// Messages dropped after `MAX_PARSE_MESSAGES`
+    pub truncated: usize,
+}
+
+impl ParseDiagnostics {
+    fn push(&mut self, severity: DiagnosticSeverity, text: String) {
+        if self.messages.len() < MAX_PARSE_MESSAGES {
+            self.messages.push(ParseMessage { severity, text });
+        } else {
+            self.truncated += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.messages.len() + self.truncated
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.messages.iter().any(|message| message.severity == DiagnosticSeverity::Error)
+    }
+
+    /// One message per line, for hover text
+    pub fn details(&self) -> String {
+        let mut lines: Vec<String> = self.messages.iter()
+            .map(|message| match message.severity {
+                DiagnosticSeverity::Warning => format!("Warning: {}", message.text),
+                DiagnosticSeverity::Error => format!("Error: {}", message.text),
+            })
+            .collect();
+        if self.truncated > 0 {
+            lines.push(format!("... and {} more", self.truncated));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Problems of the files read by the last directory load, by file name
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LoadDiagnostics(pub BTreeMap<String, ParseDiagnostics>);
+
+impl LoadDiagnostics {
+    /// Messages over all files
+    pub fn total(&self) -> usize {
+        self.0.values().map(ParseDiagnostics::count).sum()
+    }
+}
+
+thread_local! {
+    /// Parser log records of the `parse_gerber` call running on this thread
+    static CAPTURED: RefCell<Option<ParseDiagnostics>> = const { RefCell::new(None) };
+}
+
+/// Logger that hands gerber_parser warnings to `parse_gerber` and everything
+/// else (including parser records outside a parse) to the wrapped logger
+pub struct ParserLogCapture<L> {
+    inner: L,
+}
+
+impl<L: log::Log> ParserLogCapture<L> {
+    pub fn new(inner: L) -> Self {
+        Self { inner }
+    }
+}
+
+impl<L: log::Log> log::Log for ParserLogCapture<L> {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with(PARSER_LOG_TARGET) || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.target().starts_with(PARSER_LOG_TARGET) && record.level() <= log::Level::Warn {
+            let captured = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+                Some(diagnostics) => {
+                    let severity = if record.level() == log::Level::Error {
+                        DiagnosticSeverity::Error
+                    } else {
+                        DiagnosticSeverity::Warning
+                    };
+                    diagnostics.push(severity, record.args().to_string());
+                    true
+                }
+                None => false,
+            });
+            if captured {
+                return;
+            }
+        }
+        if self.inner.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Parse gerber source, collecting the parser's warnings and errors
+/// Commands that fail to parse are skipped; the layer is None only if nothing could be read
+pub fn parse_gerber(content: &str) -> (Option<GerberLayer>, ParseDiagnostics) {
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(ParseDiagnostics::default()));
+    let result = parse(BufReader::new(content.as_bytes()));
+    let mut diagnostics = CAPTURED.with(|captured| captured.borrow_mut().take()).unwrap_or_default();
+
+    let layer = match result {
+        Ok(doc) => {
+            for error in doc.commands.iter().filter_map(|command| command.as_ref().err()) {
+                diagnostics.push(DiagnosticSeverity::Warning, format!("Skipped command: {}", error));
+            }
+            Some(GerberLayer::new(doc.into_commands()))
+        }
+        Err(e) => {
+            diagnostics.push(DiagnosticSeverity::Error, format!("File could not be parsed: {:?}", e));
+            None
+        }
+    };
+    (layer, diagnostics)
+}
+
+/// Problems recorded for `file_name` by the last load, for a layer being created from it
+pub fn saved_parse_diagnostics(world: &World, file_name: &str) -> Option<ParseDiagnostics> {
+    world.get_resource::<LoadDiagnostics>()?.0.get(file_name).cloned()
+}
+
+/// Record (or clear, when empty) a file's problems and update the layer loaded from it
+pub fn set_file_diagnostics(world: &mut World, file_name: &str, diagnostics: ParseDiagnostics) {
+    let mut query = world.query::<(Entity, &LayerInfo)>();
+    let entity = query.iter(world)
+        .find(|(_, info)| info.file_path.as_ref().and_then(|path| path.file_name()).is_some_and(|name| name == file_name))
+        .map(|(entity, _)| entity);
+    if let Some(entity) = entity {
+        if diagnostics.is_empty() {
+            world.entity_mut(entity).remove::<ParseDiagnostics>();
+        } else {
+            world.entity_mut(entity).insert(diagnostics.clone());
+        }
+    }
+
+    let mut load_diagnostics = world.get_resource_or_insert_with(LoadDiagnostics::default);
+    if diagnostics.is_empty() {
+        load_diagnostics.0.remove(file_name);
+    } else {
+        load_diagnostics.0.insert(file_name.to_string(), diagnostics);
+    }
+}
+
+/// Parse problems of a loaded layer
+pub fn get_layer_diagnostics(world: &mut World, layer_type: LayerType) -> Option<ParseDiagnostics> {
+    let mut query = world.query::<(&LayerInfo, &ParseDiagnostics)>();
+    query.iter(world)
+        .find(|(info, _)| info.layer_type == layer_type)
+        .map(|(_, diagnostics)| diagnostics.clone())
+}
+
+/// Parse problems of every file read by the last load
+pub fn get_load_diagnostics(world: &World) -> LoadDiagnostics {
+    world.get_resource::<LoadDiagnostics>().cloned().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_file_has_no_diagnostics_and_messages_are_capped() {
+        let content = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.250000*%\nD10*\nX0Y0D02*\nX1000000Y0D01*\nM02*\n";
+        let (layer, diagnostics) = parse_gerber(content);
+        assert!(layer.is_some());
+        assert!(diagnostics.is_empty(), "{}", diagnostics.details());
+
+        let mut diagnostics = ParseDiagnostics::default();
+        for i in 0..MAX_PARSE_MESSAGES + 5 {
+            diagnostics.push(DiagnosticSeverity::Warning, format!("line {}", i));
+        }
+        assert_eq!((diagnostics.messages.len(), diagnostics.count()), (MAX_PARSE_MESSAGES, MAX_PARSE_MESSAGES + 5));
+        assert!(diagnostics.details().ends_with("... and 5 more"));
+        assert!(!diagnostics.has_errors());
+    }
+}
@@ -0,0 +1,337 @@
+//! Solder paste aperture reduction preview
+//!
+//! Paste layers can be shown with their apertures shrunk (or grown) before a
+//! stencil is ordered, without touching the loaded geometry. The amount applies
+//! per side: a percentage scales each aperture dimension about its center, an
+//! absolute value moves every edge in by that much (negative values expand).
+//! Flashes keep their center and shape and draws lose width. Macro and polygon
+//! pads have their outline scaled or inset (cut-outs grow as the pad shrinks),
+//! and regions are scaled about the center of their bounds or have their edges
+//! moved in. Apertures the reduction would make non-positive are clamped to
+//! `MIN_APERTURE_MM` and reported with their location.
+//!
+//! The adjusted primitives are kept on the paste layer entity and painted in
+//! place of the layer (through the render cache, keyed by `AdjustedPaste::key`),
+//! with the original apertures outlined underneath.
+
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+use egui::{Color32, Painter, Stroke};
+use gerber_viewer::{GerberTransform, ViewState};
+use nalgebra::{Matrix3, Point2, Vector2};
+use serde::{Deserialize, Serialize};
+
+use super::aperture_macro::{offset_polygon, ApertureOutline, MacroShape};
+use super::components::{LayerContentHash, LayerInfo};
+use super::primitives::{ApertureShape, LayerPrimitives, Polarity, PrimitiveShape, TaggedPrimitive};
+use super::LayerType;
+
+/// Smallest aperture dimension left after a clamped reduction (mm)
+pub const MIN_APERTURE_MM: f64 = 0.01;
+
+/// Opacity of the original aperture outlines under the preview
+const ORIGINAL_OUTLINE_ALPHA: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PasteReduction {
+    /// Percentage of each dimension removed, half from each side
+    Percent,
+    /// Distance each edge moves in (mm)
+    PerSide,
+}
+
+/// Paste preview settings, saved with the project
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasteAdjustSettings {
+    pub enabled: bool,
+    pub reduction: PasteReduction,
+    /// Percent or mm depending on `reduction`; negative values expand
+    pub amount: f64,
+}
+
+impl Default for PasteAdjustSettings {
+    fn default() -> Self {
+        Self { enabled: false, reduction: PasteReduction::Percent, amount: 10.0 }
+    }
+}
+
+impl PasteAdjustSettings {
+    /// New size of one aperture dimension, and whether it had to be clamped
+    fn adjust(&self, size: f64) -> (f64, bool) {
+        let adjusted = match self.reduction {
+            PasteReduction::Percent => size * (1.0 - self.amount / 100.0),
+            PasteReduction::PerSide => size - 2.0 * self.amount,
+        };
+        if adjusted < MIN_APERTURE_MM { (MIN_APERTURE_MM.min(size), true) } else { (adjusted, false) }
+    }
+
+    /// Outline of a macro or polygon pad after the adjustment, and whether it had to be clamped
+    fn adjust_outline(&self, outline: &ApertureOutline) -> (ApertureOutline, bool) {
+        let adjusted = match self.reduction {
+            PasteReduction::Percent => {
+                let factor = 1.0 - self.amount / 100.0;
+                (factor > 0.0).then(|| outline.transformed(&Matrix3::new_nonuniform_scaling(&Vector2::repeat(factor))))
+            }
+            PasteReduction::PerSide => outline.grown(-self.amount),
+        };
+        match adjusted {
+            Some(adjusted) if smallest_dimension(adjusted.bounds()) >= MIN_APERTURE_MM => (adjusted, false),
+            _ => {
+                let smallest = smallest_dimension(outline.bounds());
+                let factor = MIN_APERTURE_MM.min(smallest) / smallest;
+                (outline.transformed(&Matrix3::new_nonuniform_scaling(&Vector2::repeat(factor))), true)
+            }
+        }
+    }
+
+    /// Contour of a region after the adjustment, and whether it had to be clamped
+    fn adjust_region(&self, points: &[Point2<f64>]) -> (Vec<Point2<f64>>, bool) {
+        let (min, max) = bounds(points);
+        let (width, height) = (max.x - min.x, max.y - min.y);
+        let ((adjusted_width, clamped_x), (adjusted_height, clamped_y)) = match self.reduction {
+            PasteReduction::Percent => (self.adjust(width), self.adjust(height)),
+            PasteReduction::PerSide => match offset_polygon(points, -self.amount) {
+                Some(inset) if smallest_dimension(bounds(&inset)) >= MIN_APERTURE_MM => return (inset, false),
+                _ => {
+                    let smallest = width.min(height);
+                    let factor = MIN_APERTURE_MM.min(smallest) / smallest;
+                    ((width * factor, true), (height * factor, true))
+                }
+            },
+        };
+        let center = nalgebra::center(&min, &max);
+        let scale_x = if width > 0.0 { adjusted_width / width } else { 1.0 };
+        let scale_y = if height > 0.0 { adjusted_height / height } else { 1.0 };
+        let points = points.iter()
+            .map(|p| Point2::new(center.x + (p.x - center.x) * scale_x, center.y + (p.y - center.y) * scale_y))
+            .collect();
+        (points, clamped_x || clamped_y)
+    }
+
+    fn key(&self, content_hash: u64, primitive_count: usize) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content_hash.hash(&mut hasher);
+        primitive_count.hash(&mut hasher);
+        self.reduction.hash(&mut hasher);
+        self.amount.to_bits().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Adjusted primitives of a paste layer, with the totals of the summary
+#[derive(Component, Debug, Clone, Default)]
+pub struct AdjustedPaste {
+    /// Layer content and settings the primitives were computed for
+    pub key: u64,
+    pub primitives: Vec<TaggedPrimitive>,
+    /// Total aperture area before and after the adjustment (mm²)
+    pub original_area: f64,
+    pub adjusted_area: f64,
+    /// Centers of the apertures that were clamped
+    pub clamped: Vec<Point2<f64>>,
+}
+
+/// Area of a primitive in mm²; polygons are approximated by their circumcircle,
+/// macro flashes by their bounds and regions by their contour
+pub fn primitive_area(shape: &PrimitiveShape) -> f64 {
+    match shape {
+        PrimitiveShape::Segment { start, end, width } => (end - start).norm() * width + std::f64::consts::PI * (width / 2.0).powi(2),
+        PrimitiveShape::Flash { width, height, aperture, .. } => match aperture {
+            ApertureShape::Circle | ApertureShape::Polygon => std::f64::consts::PI * (width / 2.0).powi(2),
+            ApertureShape::Rectangle | ApertureShape::Other => width * height,
+            ApertureShape::Obround => {
+                let radius = width.min(*height) / 2.0;
+                width * height - (4.0 - std::f64::consts::PI) * radius * radius
+            }
+        },
+        PrimitiveShape::Region { points } => {
+            let twice_area: f64 = points.iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(a, b)| a.x * b.y - b.x * a.y)
+                .sum();
+            twice_area.abs() / 2.0
+        }
+    }
+}
+
+/// Apply the reduction to every primitive of a paste layer
+pub fn adjust_paste(primitives: &[TaggedPrimitive], settings: &PasteAdjustSettings) -> AdjustedPaste {
+    let mut result = AdjustedPaste::default();
+    for primitive in primitives {
+        let (shape, clamped) = match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => {
+                let (width, clamped) = settings.adjust(*width);
+                (PrimitiveShape::Segment { start: *start, end: *end, width }, clamped)
+            }
+            PrimitiveShape::Flash { center, aperture, outline: Some(outline), .. } => {
+                let (outline, clamped) = settings.adjust_outline(outline);
+                let (width, height) = match aperture {
+                    // Polygon apertures are sized by their circumscribed diameter
+                    ApertureShape::Polygon => {
+                        let radius = outline.shapes.iter()
+                            .flat_map(MacroShape::to_polygon)
+                            .map(|point| point.coords.norm())
+                            .fold(0.0, f64::max);
+                        (2.0 * radius, 2.0 * radius)
+                    }
+                    _ => outline.centered_size(),
+                };
+                let outline = Some(Arc::new(outline));
+                (PrimitiveShape::Flash { center: *center, width, height, aperture: *aperture, outline }, clamped)
+            }
+            PrimitiveShape::Flash { center, width, height, aperture, .. } => {
+                let (width, clamped_x) = settings.adjust(*width);
+                let (height, clamped_y) = settings.adjust(*height);
+                (PrimitiveShape::Flash { center: *center, width, height, aperture: *aperture, outline: None }, clamped_x || clamped_y)
+            }
+            PrimitiveShape::Region { points } => {
+                let (points, clamped) = settings.adjust_region(points);
+                (PrimitiveShape::Region { points }, clamped)
+            }
+        };
+        if clamped {
+            let (min, max) = primitive.shape.bounds();
+            result.clamped.push(nalgebra::center(&min, &max));
+        }
+        // Clear primitives take their area out of the apertures drawn before them
+        let sign = if primitive.polarity == Polarity::Clear { -1.0 } else { 1.0 };
+        result.original_area += sign * primitive_area(&primitive.shape);
+        result.adjusted_area += sign * primitive_area(&shape);
+        result.primitives.push(TaggedPrimitive { net: primitive.net.clone(), polarity: primitive.polarity, shape });
+    }
+    result
+}
+
+fn bounds(points: &[Point2<f64>]) -> (Point2<f64>, Point2<f64>) {
+    points.iter().fold(
+        (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(min, max), p| (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y))),
+    )
+}
+
+fn smallest_dimension((min, max): (Point2<f64>, Point2<f64>)) -> f64 {
+    (max.x - min.x).min(max.y - min.y)
+}
+
+/// Recompute the adjusted primitives of the paste layers whose content or settings changed
+/// Called before the layers are drawn; does nothing while the preview is off
+pub fn update_paste_preview(world: &mut World) {
+    let Some(settings) = world.get_resource::<PasteAdjustSettings>().copied().filter(|settings| settings.enabled) else {
+        return;
+    };
+    let mut query = world.query::<(Entity, &LayerInfo, &LayerPrimitives, Option<&LayerContentHash>, Option<&AdjustedPaste>)>();
+    let stale: Vec<(Entity, u64, AdjustedPaste)> = query.iter(world)
+        .filter(|(_, layer_info, _, _, _)| matches!(layer_info.layer_type, LayerType::Paste(_)))
+        .filter_map(|(entity, _, primitives, content_hash, adjusted)| {
+            let key = settings.key(content_hash.map_or(0, |hash| hash.0), primitives.0.len());
+            (adjusted.map(|adjusted| adjusted.key) != Some(key)).then(|| (entity, key, adjust_paste(&primitives.0, &settings)))
+        })
+        .collect();
+    for (entity, key, adjusted) in stale {
+        world.entity_mut(entity).insert(AdjustedPaste { key, ..adjusted });
+    }
+}
+
+/// Adjusted primitives of a loaded paste layer, for the summary and the export
+pub fn get_adjusted_paste(world: &mut World, side: super::Side) -> Option<AdjustedPaste> {
+    let mut query = world.query::<(&LayerInfo, &AdjustedPaste)>();
+    query.iter(world)
+        .find(|(layer_info, _)| layer_info.layer_type == LayerType::Paste(side))
+        .map(|(_, adjusted)| adjusted.clone())
+}
+
+/// Faint outlines of the original apertures, drawn under the adjusted ones
+pub fn paint_original_paste_outlines(
+    painter: &Painter,
+    view_state: &ViewState,
+    primitives: &[TaggedPrimitive],
+    transform: &GerberTransform,
+    color: Color32,
+) {
+    let matrix = transform.to_matrix();
+    let stroke = Stroke::new(1.0, color.gamma_multiply(ORIGINAL_OUTLINE_ALPHA));
+    for primitive in primitives {
+        let points = super::picking::primitive_outline(&primitive.shape)
+            .iter()
+            .map(|point| view_state.gerber_to_screen_coords(matrix.transform_point(point)))
+            .collect();
+        painter.add(egui::Shape::closed_line(points, stroke));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primitive(shape: PrimitiveShape) -> TaggedPrimitive {
+        TaggedPrimitive { net: None, polarity: Polarity::Dark, shape }
+    }
+
+    #[test]
+    fn test_paste_reduction_scales_apertures_and_clamps() {
+        let pad = primitive(PrimitiveShape::Flash { center: Point2::new(5.0, 5.0), width: 1.0, height: 0.6, aperture: ApertureShape::Rectangle, outline: None });
+        let small = primitive(PrimitiveShape::Flash { center: Point2::new(9.0, 1.0), width: 0.15, height: 0.15, aperture: ApertureShape::Circle, outline: None });
+        let region = primitive(PrimitiveShape::Region {
+            points: vec![Point2::new(0.0, 0.0), Point2::new(2.0, 0.0), Point2::new(2.0, 1.0), Point2::new(0.0, 1.0)],
+        });
+
+        let percent = PasteAdjustSettings { enabled: true, reduction: PasteReduction::Percent, amount: 10.0 };
+        let adjusted = adjust_paste(&[pad.clone(), region.clone()], &percent);
+        assert!((adjusted.original_area - 2.6).abs() < 1e-9);
+        assert!((adjusted.adjusted_area - 2.6 * 0.81).abs() < 1e-9);
+        assert!(adjusted.clamped.is_empty());
+
+        // 0.1 mm per side leaves the 0.15 mm circle at a negative size
+        let per_side = PasteAdjustSettings { enabled: true, reduction: PasteReduction::PerSide, amount: 0.1 };
+        let adjusted = adjust_paste(&[pad, small, region], &per_side);
+        let PrimitiveShape::Flash { width, height, .. } = adjusted.primitives[0].shape else { panic!("pad stays a flash") };
+        assert!((width - 0.8).abs() < 1e-9 && (height - 0.4).abs() < 1e-9);
+        let PrimitiveShape::Flash { width, .. } = adjusted.primitives[1].shape else { panic!("circle stays a flash") };
+        assert_eq!(width, MIN_APERTURE_MM);
+        assert_eq!(adjusted.clamped, vec![Point2::new(9.0, 1.0)]);
+        let (min, max) = adjusted.primitives[2].shape.bounds();
+        assert!((min - Point2::new(0.1, 0.1)).norm() < 1e-9 && (max - Point2::new(1.9, 0.9)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_macro_pads_and_concave_regions_are_inset() {
+        // KiCad RoundRect, 1.4 × 1.2 mm with 0.25 mm corners
+        let body: Vec<String> = [
+            "4,1,4,$2,$3,$4,$5,$6,$7,$8,$9,$2,$3,0", "1,1,$1+$1,$2,$3", "1,1,$1+$1,$4,$5", "1,1,$1+$1,$6,$7",
+            "1,1,$1+$1,$8,$9", "20,1,$1+$1,$2,$3,$4,$5,0", "20,1,$1+$1,$4,$5,$6,$7,0",
+            "20,1,$1+$1,$6,$7,$8,$9,0", "20,1,$1+$1,$8,$9,$2,$3,0",
+        ].map(String::from).to_vec();
+        let outline = ApertureOutline::from_macro("RoundRect", &body, "0.25X-0.45X-0.35X0.45X-0.35X0.45X0.35X-0.45X0.35", 1.0).unwrap();
+        let pad = primitive(PrimitiveShape::Flash {
+            center: Point2::new(3.0, 3.0), width: 1.4, height: 1.2, aperture: ApertureShape::Other, outline: Some(Arc::new(outline)),
+        });
+        // L-shaped pour: every edge moves in, the inner corner included
+        let region = primitive(PrimitiveShape::Region {
+            points: vec![
+                Point2::new(0.0, 0.0), Point2::new(2.0, 0.0), Point2::new(2.0, 1.0),
+                Point2::new(1.0, 1.0), Point2::new(1.0, 2.0), Point2::new(0.0, 2.0),
+            ],
+        });
+
+        let per_side = PasteAdjustSettings { enabled: true, reduction: PasteReduction::PerSide, amount: 0.1 };
+        let adjusted = adjust_paste(&[pad.clone(), region], &per_side);
+        assert!(adjusted.clamped.is_empty());
+        let PrimitiveShape::Flash { width, height, outline: Some(outline), .. } = &adjusted.primitives[0].shape else {
+            panic!("pad keeps its outline")
+        };
+        assert!((width - 1.2).abs() < 1e-9 && (height - 1.0).abs() < 1e-9);
+        assert!(outline.contains(Point2::new(0.59, 0.0)) && !outline.contains(Point2::new(0.59, 0.49)));
+        let PrimitiveShape::Region { points } = &adjusted.primitives[1].shape else { panic!("region stays a region") };
+        assert!((points[3] - Point2::new(0.9, 0.9)).norm() < 1e-9);
+        assert!((points[4] - Point2::new(0.9, 1.9)).norm() < 1e-9);
+
+        let percent = PasteAdjustSettings { enabled: true, reduction: PasteReduction::Percent, amount: 50.0 };
+        let PrimitiveShape::Flash { width, height, .. } = adjust_paste(&[pad], &percent).primitives[0].shape else {
+            panic!("pad stays a flash")
+        };
+        assert!((width - 0.7).abs() < 1e-9 && (height - 0.6).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,149 @@
+//! Hit testing of single gerber primitives for hover inspection and selection
+//!
+//! The cursor is taken back into each layer's source gerber coordinates, so the
+//! test uses the exact primitive shapes (`PrimitiveShape::distance_to`). Layers
+//! are searched from the top down and the first layer with a hit wins; on that
+//! layer the nearest primitive is picked, with draws and flashes ahead of the
+//! regions (pours) they sit on.
+
+use bevy_ecs::prelude::*;
+use gerber_viewer::ViewState;
+use nalgebra::Point2;
+
+use super::components::{LayerInfo, RenderProperties, Visibility};
+use super::primitives::{LayerPrimitives, Polarity, PrimitiveShape, TaggedPrimitive};
+use super::LayerType;
+use crate::display::DisplayManager;
+
+/// A primitive under the cursor, in source gerber coordinates of its layer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickedPrimitive {
+    pub layer_type: LayerType,
+    pub primitive: TaggedPrimitive,
+}
+
+/// Index of the primitive nearest `point` within `max_distance`; clear primitives are skipped
+pub fn nearest_primitive(primitives: &[TaggedPrimitive], point: Point2<f64>, max_distance: f64) -> Option<usize> {
+    primitives.iter()
+        .enumerate()
+        .filter(|(_, primitive)| primitive.polarity == Polarity::Dark)
+        .map(|(index, primitive)| (index, primitive.shape.distance_to(point), matches!(primitive.shape, PrimitiveShape::Region { .. })))
+        .filter(|(_, distance, _)| *distance <= max_distance)
+        .min_by(|(_, a, a_region), (_, b, b_region)| a.total_cmp(b).then_with(|| a_region.cmp(b_region)))
+        .map(|(index, _, _)| index)
+}
+
+/// Primitive under a screen position on the visible layers, topmost layer first
+pub fn pick_primitive_at_screen_pos(
+    world: &mut World,
+    view_state: &ViewState,
+    display_manager: &DisplayManager,
+    screen_pos: egui::Pos2,
+    tolerance_px: f32,
+) -> Option<PickedPrimitive> {
+    let view_point = view_state.screen_to_gerber_coords(screen_pos);
+    let max_distance = (tolerance_px / view_state.scale) as f64;
+
+    let mut query = world.query::<(&LayerInfo, &Visibility, &RenderProperties, &LayerPrimitives)>();
+    let mut layers: Vec<(LayerType, i32)> = query.iter(world)
+        .filter(|(_, visibility, _, _)| visibility.visible)
+        .filter(|(layer_info, _, _, _)| !display_manager.quadrant_view_enabled || display_manager.quadrant_shows(layer_info.layer_type))
+        .map(|(layer_info, _, props, _)| (layer_info.layer_type, props.z_order))
+        .collect();
+    layers.sort_by_key(|(_, z_order)| -z_order);
+
+    // Cursor in each layer's source coordinates
+    let targets: Vec<(LayerType, Point2<f64>)> = layers.into_iter()
+        .filter_map(|(layer_type, _)| {
+            let to_source = super::get_layer_render_transform(world, display_manager, layer_type)?.to_matrix().try_inverse()?;
+            Some((layer_type, to_source.transform_point(&view_point)))
+        })
+        .collect();
+
+    for (layer_type, point) in targets {
+        let Some((_, _, _, primitives)) = query.iter(world).find(|(layer_info, _, _, _)| layer_info.layer_type == layer_type) else {
+            continue;
+        };
+        if let Some(index) = nearest_primitive(&primitives.0, point, max_distance) {
+            return Some(PickedPrimitive { layer_type, primitive: primitives.0[index].clone() });
+        }
+    }
+    None
+}
+
+/// Outline of a primitive as a closed polygon in source gerber coordinates, for highlighting
+pub fn primitive_outline(shape: &PrimitiveShape) -> Vec<Point2<f64>> {
+    const ARC_STEPS: usize = 12;
+    // Half circle of `radius` around `center`, starting at `start_angle`
+    let cap = |center: Point2<f64>, radius: f64, start_angle: f64| {
+        (0..=ARC_STEPS).map(move |i| {
+            let angle = start_angle + std::f64::consts::PI * i as f64 / ARC_STEPS as f64;
+            Point2::new(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+    };
+    let capsule = |start: Point2<f64>, end: Point2<f64>, radius: f64| {
+        let direction = end - start;
+        let angle = direction.y.atan2(direction.x);
+        let half_turn = std::f64::consts::FRAC_PI_2;
+        cap(end, radius, angle - half_turn).chain(cap(start, radius, angle + half_turn)).collect::<Vec<_>>()
+    };
+
+    match shape {
+        PrimitiveShape::Segment { start, end, width } => capsule(*start, *end, width / 2.0),
+        PrimitiveShape::Flash { center, width, height, aperture, .. } => match aperture {
+            super::ApertureShape::Rectangle => vec![
+                Point2::new(center.x - width / 2.0, center.y - height / 2.0),
+                Point2::new(center.x + width / 2.0, center.y - height / 2.0),
+                Point2::new(center.x + width / 2.0, center.y + height / 2.0),
+                Point2::new(center.x - width / 2.0, center.y + height / 2.0),
+            ],
+            super::ApertureShape::Obround => {
+                let radius = width.min(*height) / 2.0;
+                let axis = if width >= height {
+                    nalgebra::Vector2::new(width / 2.0 - radius, 0.0)
+                } else {
+                    nalgebra::Vector2::new(0.0, height / 2.0 - radius)
+                };
+                capsule(center - axis, center + axis, radius)
+            }
+            _ => capsule(*center, *center, width / 2.0),
+        },
+        PrimitiveShape::Region { points } => points.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::ApertureShape;
+
+    fn primitive(shape: PrimitiveShape) -> TaggedPrimitive {
+        TaggedPrimitive { net: None, polarity: Polarity::Dark, shape }
+    }
+
+    #[test]
+    fn test_nearest_primitive_prefers_traces_over_pours() {
+        let pour = Point2::new(-10.0, -10.0);
+        let primitives = vec![
+            primitive(PrimitiveShape::Region { points: vec![pour, Point2::new(10.0, -10.0), Point2::new(10.0, 10.0), Point2::new(-10.0, 10.0)] }),
+            primitive(PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(5.0, 0.0), width: 0.25 }),
+            primitive(PrimitiveShape::Flash { center: Point2::new(20.0, 0.0), width: 1.0, height: 1.0, aperture: ApertureShape::Circle, outline: None }),
+            TaggedPrimitive { net: None, polarity: Polarity::Clear, shape: PrimitiveShape::Flash { center: Point2::new(30.0, 0.0), width: 1.0, height: 1.0, aperture: ApertureShape::Circle, outline: None } },
+        ];
+
+        // On the trace, which lies inside the pour
+        assert_eq!(nearest_primitive(&primitives, Point2::new(2.0, 0.1), 0.05), Some(1));
+        // Pour only
+        assert_eq!(nearest_primitive(&primitives, Point2::new(2.0, 5.0), 0.05), Some(0));
+        // Just off the pad edge, within the tolerance
+        assert_eq!(nearest_primitive(&primitives, Point2::new(20.55, 0.0), 0.1), Some(2));
+        // Clear primitives cannot be picked
+        assert_eq!(nearest_primitive(&primitives, Point2::new(30.0, 0.0), 0.1), None);
+
+        // A trace outline closes around both end caps
+        let outline = primitive_outline(&primitives[1].shape);
+        let (min, max) = primitives[1].shape.bounds();
+        assert!(outline.iter().all(|p| p.x >= min.x - 1e-9 && p.x <= max.x + 1e-9 && p.y >= min.y - 1e-9 && p.y <= max.y + 1e-9));
+        assert_eq!(outline.len(), 26);
+    }
+}
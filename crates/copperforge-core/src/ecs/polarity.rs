@@ -0,0 +1,245 @@
+//! Clear (LPC) primitives and negative layers
+//!
+//! gerber_viewer fills clear primitives like dark ones, so layers with any
+//! clear primitive, or declared negative with `%TF.FilePolarity,Negative*%`,
+//! are composited from the extracted primitives instead. Each such layer is
+//! rasterized on its own at screen resolution (see `export::raster`), in file
+//! order with clear primitives uncovering what the layer drew before them,
+//! and painted as an image whose uncovered pixels are transparent. A clearance
+//! therefore shows the layers underneath, never erases them. Apertures keep
+//! their exact shapes: macro and polygon outlines, rotated rectangles and
+//! obrounds, concave regions. A negative layer starts from its image area (the
+//! board outline loops, else the layer extents) and its dark primitives uncover.
+
+use std::sync::LazyLock;
+
+use bevy_ecs::prelude::*;
+use egui::{Color32, Painter, Pos2, Rect, TextureHandle};
+use gerber_viewer::{BoundingBox, GerberTransform, ViewState};
+use nalgebra::{Matrix3, Point2};
+use regex::Regex;
+
+use crate::export::raster::{fill_contours, paint_primitives, primitives_bounds, transform_primitives, CoverageMask, RasterGrid};
+use super::primitives::{Polarity, TaggedPrimitive};
+use super::LayerType;
+
+/// Largest side of a composited layer image, in pixels
+const MAX_COMPOSITE_PIXELS: u32 = 4096;
+
+static FILE_POLARITY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"TF\.FilePolarity,\s*(Positive|Negative)\s*\*").unwrap());
+
+/// Polarity information of a layer
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayerPolarity {
+    /// The file declares itself negative: dark primitives are clearances in a filled plane
+    pub negative: bool,
+    /// Some primitives are drawn with %LPC
+    pub has_clear: bool,
+}
+
+impl LayerPolarity {
+    pub fn new(content: &str, primitives: &[TaggedPrimitive]) -> Self {
+        Self {
+            negative: FILE_POLARITY.captures(content).is_some_and(|captures| &captures[1] == "Negative"),
+            has_clear: primitives.iter().any(|primitive| primitive.polarity == Polarity::Clear),
+        }
+    }
+
+    /// The layer must be painted from its primitives to show correctly
+    pub fn needs_compositing(&self) -> bool {
+        self.negative || self.has_clear
+    }
+}
+
+/// Primitives of a layer and how to composite them
+pub struct CompositeLayer<'a> {
+    pub primitives: &'a [TaggedPrimitive],
+    pub polarity: LayerPolarity,
+    /// Closed loops filled first on a negative layer (cut-outs inside the board are holes), in layer coordinates
+    pub image_area: Option<&'a [Vec<Point2<f64>>]>,
+}
+
+/// Image a composited layer was painted with
+pub struct CompositedImage {
+    /// Keeps the texture alive while the image is repainted
+    pub texture: TextureHandle,
+    /// Screen rectangle the image covers
+    pub rect: Rect,
+    /// Screen rectangle of the whole layer, of which the image may show only part
+    pub layer_rect: Rect,
+}
+
+impl CompositedImage {
+    /// Whether the image, zoomed by `factor` about `origin` and then moved by `delta`,
+    /// shows every part of the layer inside `needed`
+    pub fn covers(&self, needed: Rect, origin: Pos2, factor: f32, delta: egui::Vec2) -> bool {
+        let place = |rect: Rect| zoom_rect(rect, origin, factor).translate(delta);
+        let needed = needed.intersect(place(self.layer_rect));
+        !needed.is_positive() || place(self.rect).contains_rect(needed)
+    }
+
+    /// Zoom the placement by `factor` about `origin`, like the cached meshes
+    pub fn scale_about(&mut self, origin: Pos2, factor: f32) {
+        self.rect = zoom_rect(self.rect, origin, factor);
+        self.layer_rect = zoom_rect(self.layer_rect, origin, factor);
+    }
+}
+
+fn zoom_rect(rect: Rect, origin: Pos2, factor: f32) -> Rect {
+    Rect::from_min_max(origin + (rect.min - origin) * factor, origin + (rect.max - origin) * factor)
+}
+
+/// Rectangle around every primitive, standing in for a missing board outline
+fn extents_polygon(primitives: &[TaggedPrimitive]) -> Vec<Point2<f64>> {
+    let Some(bounds) = primitives_bounds(primitives) else {
+        return Vec::new();
+    };
+    let (min, max) = (bounds.min, bounds.max);
+    vec![min, Point2::new(max.x, min.y), max, Point2::new(min.x, max.y)]
+}
+
+/// Coverage of a layer after `matrix`, on a grid of `pixels_per_mm` over the part inside `visible`
+/// Returns the mask and the bounds of the whole layer; None when nothing of it is visible
+pub fn composite_mask(
+    layer: &CompositeLayer,
+    matrix: &Matrix3<f64>,
+    visible: &BoundingBox,
+    pixels_per_mm: f64,
+) -> Option<(CoverageMask, BoundingBox)> {
+    let negative = layer.polarity.negative;
+    let primitives = transform_primitives(layer.primitives, matrix);
+    let area: Vec<Vec<Point2<f64>>> = match layer.image_area {
+        _ if !negative => Vec::new(),
+        Some(loops) if !loops.is_empty() => {
+            loops.iter().map(|contour| contour.iter().map(|point| matrix.transform_point(point)).collect()).collect()
+        }
+        _ => vec![extents_polygon(&primitives)],
+    };
+
+    let mut points: Vec<Point2<f64>> = area.iter().flatten().copied().collect();
+    if let Some(bounds) = primitives_bounds(&primitives) {
+        points.extend([bounds.min, bounds.max]);
+    }
+    if points.is_empty() {
+        return None;
+    }
+    let bounds = BoundingBox::from_points(&points);
+    let min = Point2::new(bounds.min.x.max(visible.min.x), bounds.min.y.max(visible.min.y));
+    let max = Point2::new(bounds.max.x.min(visible.max.x), bounds.max.y.min(visible.max.y));
+    if min.x >= max.x || min.y >= max.y {
+        return None;
+    }
+
+    let grid = RasterGrid::covering(&BoundingBox::from_points(&[min, max]), pixels_per_mm, MAX_COMPOSITE_PIXELS);
+    let mut mask = CoverageMask::new(grid);
+    fill_contours(&mut mask, &area, true);
+    paint_primitives(&mut mask, &primitives, negative);
+    Some((mask, bounds))
+}
+
+/// Paint a layer composited from its primitives, covering at least the `visible` screen rectangle
+/// Returns the image painted, None when the layer is outside `visible`
+pub fn paint_composited_layer(
+    painter: &Painter,
+    view_state: &ViewState,
+    layer: &CompositeLayer,
+    color: Color32,
+    transform: &GerberTransform,
+    visible: Rect,
+) -> Option<CompositedImage> {
+    let scale = view_state.scale as f64;
+    let to_gerber = |pos: Pos2| {
+        Point2::new((pos.x - view_state.translation.x) as f64 / scale, (view_state.translation.y - pos.y) as f64 / scale)
+    };
+    let to_screen = |point: Point2<f64>| view_state.gerber_to_screen_coords(point);
+    let visible_gerber = BoundingBox::from_points(&[to_gerber(visible.min), to_gerber(visible.max)]);
+    let pixels_per_point = painter.ctx().pixels_per_point();
+
+    let (mask, bounds) = composite_mask(layer, &transform.to_matrix(), &visible_gerber, scale * pixels_per_point as f64)?;
+    let grid = mask.grid;
+    let pixels: Vec<u8> = mask.pixels.iter()
+        .flat_map(|covered| if *covered { color } else { Color32::TRANSPARENT }.to_array())
+        .collect();
+    let image = egui::ColorImage::from_rgba_premultiplied([grid.width as usize, grid.height as usize], &pixels);
+    let texture = painter.ctx().load_texture("composited_layer", image, egui::TextureOptions::LINEAR);
+
+    let size = egui::Vec2::new(grid.width as f32, grid.height as f32) * (scale / grid.pixels_per_mm) as f32;
+    let rect = Rect::from_min_size(to_screen(grid.top_left), size);
+    let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+    painter.image(texture.id(), rect, uv, Color32::WHITE);
+
+    let layer_rect = Rect::from_two_pos(to_screen(bounds.min), to_screen(bounds.max));
+    Some(CompositedImage { texture, rect, layer_rect })
+}
+
+/// Image area for negative layers: the closed loops of the board outline, when any negative layer is loaded
+pub fn negative_image_area(world: &mut World) -> Option<Vec<Vec<Point2<f64>>>> {
+    let mut query = world.query::<&LayerPolarity>();
+    if !query.iter(world).any(|polarity| polarity.negative) {
+        return None;
+    }
+    super::get_layer_by_type(world, LayerType::MechanicalOutline)?;
+    let primitives = super::get_layer_primitives(world, LayerType::MechanicalOutline);
+    let (loops, _) = super::chain_outline_loops(&primitives, super::OUTLINE_JOIN_TOLERANCE);
+    (!loops.is_empty()).then_some(loops)
+}
+
+/// Polarity of a loaded layer, when its source was available
+pub fn get_layer_polarity(world: &mut World, layer_type: LayerType) -> Option<LayerPolarity> {
+    let entity = super::get_layer_by_type(world, layer_type)?;
+    world.get::<LayerPolarity>(entity).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polarity_detection() {
+        let negative = "%FSLAX46Y46*%\n%TF.FilePolarity,Negative*%\n%MOMM*%\n%ADD10C,0.5*%\nD10*\nX0Y0D03*\nM02*\n";
+        let primitives = crate::ecs::extract_primitives(negative);
+        let polarity = LayerPolarity::new(negative, &primitives);
+        assert_eq!(polarity, LayerPolarity { negative: true, has_clear: false });
+        assert!(polarity.needs_compositing());
+
+        let with_clear = "%FSLAX46Y46*%\n%TF.FilePolarity,Positive*%\n%MOMM*%\n%ADD10C,0.5*%\nD10*\nX0Y0D03*\n%LPC*%\nX0Y0D03*\nM02*\n";
+        let primitives = crate::ecs::extract_primitives(with_clear);
+        assert_eq!(LayerPolarity::new(with_clear, &primitives), LayerPolarity { negative: false, has_clear: true });
+        assert!(!LayerPolarity::new("%FSLAX46Y46*%\n", &[]).needs_compositing());
+    }
+
+    #[test]
+    fn test_clear_uncovers_only_this_layer_and_negative_fills_concave_outline() {
+        let everything = BoundingBox::from_points(&[Point2::new(-100.0, -100.0), Point2::new(100.0, 100.0)]);
+        let identity = Matrix3::identity();
+
+        // 4 x 4 mm pour with a clear 1 mm pad in the middle: the pad is uncovered, not painted
+        let pour = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,1.0*%\nG36*\nX0Y0D02*\nX4000000Y0D01*\nX4000000Y4000000D01*\nX0Y4000000D01*\nX0Y0D01*\nG37*\n\
+            %LPC*%\nD10*\nX2000000Y2000000D03*\nM02*\n";
+        let primitives = crate::ecs::extract_primitives(pour);
+        let layer = CompositeLayer { primitives: &primitives, polarity: LayerPolarity::new(pour, &primitives), image_area: None };
+        let (mask, _) = composite_mask(&layer, &identity, &everything, 10.0).unwrap();
+        assert!(!mask.get(20, 20));
+        assert!(mask.get(5, 5));
+
+        // L-shaped board: the notch stays outside the negative image area
+        let outline = vec![vec![
+            Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), Point2::new(4.0, 2.0),
+            Point2::new(2.0, 2.0), Point2::new(2.0, 4.0), Point2::new(0.0, 4.0),
+        ]];
+        let negative = "%FSLAX46Y46*%\n%TF.FilePolarity,Negative*%\n%MOMM*%\n%ADD10C,0.5*%\nD10*\nX1000000Y1000000D03*\nM02*\n";
+        let primitives = crate::ecs::extract_primitives(negative);
+        let layer = CompositeLayer { primitives: &primitives, polarity: LayerPolarity::new(negative, &primitives), image_area: Some(&outline) };
+        let (mask, bounds) = composite_mask(&layer, &identity, &everything, 10.0).unwrap();
+        assert!((bounds.max.x - 4.0).abs() < 1e-9);
+        let pixel = |x: f64, y: f64| {
+            let grid = mask.grid;
+            mask.get(((x - grid.top_left.x) * grid.pixels_per_mm) as u32, ((grid.top_left.y - y) * grid.pixels_per_mm) as u32)
+        };
+        assert!(pixel(3.0, 1.0));
+        assert!(!pixel(3.0, 3.0));
+        // The dark flash is a clearance in the plane
+        assert!(!pixel(1.0, 1.0));
+        assert!(pixel(1.5, 1.5));
+    }
+}
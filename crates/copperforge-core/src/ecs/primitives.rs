@@ -0,0 +1,612 @@
+//! Primitive extraction from raw gerber source
+//!
+//! gerber_viewer does not expose its primitives, so draws, flashes and regions
+//! are pulled straight from the gerber text. Circular draws (G02/G03, multi-quadrant)
+//! are tessellated into short segments. Flashes of macro and polygon apertures
+//! carry their evaluated outline (see `aperture_macro`). Load mirroring, rotation
+//! and scaling (%LM, %LR, %LS) are applied to outlines in full; standard apertures
+//! only follow scaling and quarter-turn rotations, which their size can express.
+
+use bevy_ecs::prelude::*;
+use nalgebra::{Matrix3, Point2};
+use std::collections::HashMap;
+use std::sync::Arc;
+use super::LayerType;
+use super::aperture_macro::ApertureOutline;
+use super::components::LayerInfo;
+
+/// Largest angle swept by one segment of a tessellated arc
+pub const ARC_STEP_DEGREES: f64 = 5.0;
+
+/// Geometry of a single drawn or flashed primitive, in gerber (mm) coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrimitiveShape {
+    /// Linear draw (D01)
+    Segment {
+        start: Point2<f64>,
+        end: Point2<f64>,
+        width: f64,
+    },
+    /// Aperture flash (D03)
+    Flash {
+        center: Point2<f64>,
+        width: f64,
+        height: f64,
+        aperture: ApertureShape,
+        /// Exact shape of macro and polygon apertures; width and height bound it
+        outline: Option<Arc<ApertureOutline>>,
+    },
+    /// Filled G36/G37 region contour (closing edge implied)
+    Region {
+        points: Vec<Point2<f64>>,
+    },
+}
+
+impl PrimitiveShape {
+    /// Axis-aligned bounds (min, max) including stroke width and aperture size
+    pub fn bounds(&self) -> (Point2<f64>, Point2<f64>) {
+        match self {
+            Self::Segment { start, end, width } => {
+                let r = width / 2.0;
+                (
+                    Point2::new(start.x.min(end.x) - r, start.y.min(end.y) - r),
+                    Point2::new(start.x.max(end.x) + r, start.y.max(end.y) + r),
+                )
+            }
+            Self::Flash { center, width, height, .. } => (
+                Point2::new(center.x - width / 2.0, center.y - height / 2.0),
+                Point2::new(center.x + width / 2.0, center.y + height / 2.0),
+            ),
+            Self::Region { points } => points.iter().fold(
+                (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+                |(min, max), p| (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y))),
+            ),
+        }
+    }
+
+    /// Distance from `point` to the edge of the copper; 0 when the point is covered
+    pub fn distance_to(&self, point: Point2<f64>) -> f64 {
+        match self {
+            Self::Segment { start, end, width } => (distance_to_segment(point, *start, *end) - width / 2.0).max(0.0),
+            Self::Flash { center, outline: Some(outline), .. } => outline.distance_to(Point2::from(point - center)),
+            Self::Flash { center, width, height, aperture, .. } => match aperture {
+                ApertureShape::Circle | ApertureShape::Polygon => ((point - center).norm() - width / 2.0).max(0.0),
+                ApertureShape::Rectangle | ApertureShape::Other => {
+                    let dx = ((point.x - center.x).abs() - width / 2.0).max(0.0);
+                    let dy = ((point.y - center.y).abs() - height / 2.0).max(0.0);
+                    dx.hypot(dy)
+                }
+                ApertureShape::Obround => {
+                    let radius = width.min(*height) / 2.0;
+                    let axis = if width >= height {
+                        nalgebra::Vector2::new(width / 2.0 - radius, 0.0)
+                    } else {
+                        nalgebra::Vector2::new(0.0, height / 2.0 - radius)
+                    };
+                    (distance_to_segment(point, center - axis, center + axis) - radius).max(0.0)
+                }
+            },
+            Self::Region { points } => {
+                if points.len() < 3 {
+                    return f64::INFINITY;
+                }
+                let mut inside = false;
+                let mut distance = f64::INFINITY;
+                for i in 0..points.len() {
+                    let a = points[i];
+                    let b = points[(i + 1) % points.len()];
+                    if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+                        inside = !inside;
+                    }
+                    distance = distance.min(distance_to_segment(point, a, b));
+                }
+                if inside { 0.0 } else { distance }
+            }
+        }
+    }
+}
+
+/// Distance from a point to the segment a..b
+fn distance_to_segment(point: Point2<f64>, a: Point2<f64>, b: Point2<f64>) -> f64 {
+    let segment = b - a;
+    let length_sq = segment.norm_squared();
+    let t = if length_sq > 0.0 { ((point - a).dot(&segment) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+    (point - (a + segment * t)).norm()
+}
+
+/// Load polarity (%LPD / %LPC) a primitive was drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Polarity {
+    #[default]
+    Dark,
+    Clear,
+}
+
+/// Aperture template of a flash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApertureShape {
+    Circle,
+    Rectangle,
+    Obround,
+    /// Regular polygon; width is the circumscribed diameter
+    Polygon,
+    /// Aperture macro or unknown template; width and height bound its exposed primitives
+    Other,
+}
+
+/// A primitive with the X2 net attribute (%TO.N) active when it was drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedPrimitive {
+    pub net: Option<String>,
+    pub polarity: Polarity,
+    pub shape: PrimitiveShape,
+}
+
+// All primitives of a layer in file order, kept for export
+#[derive(Component, Clone, Debug, Default)]
+pub struct LayerPrimitives(pub Vec<TaggedPrimitive>);
+
+/// A flashed feature (pad or via) on a layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlashPrimitive {
+    pub position: Point2<f64>,
+    /// Largest aperture dimension; the pad diameter for round apertures
+    pub diameter: f64,
+    pub width: f64,
+    pub height: f64,
+    pub aperture: ApertureShape,
+}
+
+impl FlashPrimitive {
+    /// Copper/opening area of the flash in mm²; polygons are approximated by their circumcircle
+    /// and macros by their bounding box
+    pub fn area(&self) -> f64 {
+        match self.aperture {
+            ApertureShape::Circle | ApertureShape::Polygon => std::f64::consts::PI * (self.diameter / 2.0).powi(2),
+            ApertureShape::Rectangle => self.width * self.height,
+            ApertureShape::Obround => {
+                let radius = self.width.min(self.height) / 2.0;
+                self.width * self.height - (4.0 - std::f64::consts::PI) * radius * radius
+            }
+            ApertureShape::Other => self.width * self.height,
+        }
+    }
+}
+
+// Flashed (D03) features of a layer, i.e. pads and vias
+#[derive(Component, Clone, Debug, Default)]
+pub struct FlashPrimitives(pub Vec<FlashPrimitive>);
+
+/// Collect the flashed primitives into the per-layer flash table
+pub fn build_flash_primitives(primitives: &[TaggedPrimitive]) -> FlashPrimitives {
+    let flashes = primitives
+        .iter()
+        .filter_map(|primitive| match primitive.shape {
+            PrimitiveShape::Flash { center, width, height, aperture, .. } if primitive.polarity == Polarity::Dark => Some(FlashPrimitive {
+                position: center,
+                diameter: width.max(height),
+                width,
+                height,
+                aperture,
+            }),
+            _ => None,
+        })
+        .collect();
+    FlashPrimitives(flashes)
+}
+
+/// Get the flashed pads/vias of a layer (positions and diameters in mm)
+pub fn get_flash_primitives(world: &mut World, layer_type: LayerType) -> Vec<FlashPrimitive> {
+    let mut query = world.query::<(&LayerInfo, &FlashPrimitives)>();
+    query.iter(world)
+        .find(|(layer_info, _)| layer_info.layer_type == layer_type)
+        .map(|(_, flashes)| flashes.0.clone())
+        .unwrap_or_default()
+}
+
+/// Get every extracted primitive of a layer (draws, flashes and regions)
+pub fn get_layer_primitives(world: &mut World, layer_type: LayerType) -> Vec<TaggedPrimitive> {
+    let mut query = world.query::<(&LayerInfo, &LayerPrimitives)>();
+    query.iter(world)
+        .find(|(layer_info, _)| layer_info.layer_type == layer_type)
+        .map(|(_, primitives)| primitives.0.clone())
+        .unwrap_or_default()
+}
+
+/// Count the flashed pads/vias of a layer
+pub fn get_flash_count(world: &mut World, layer_type: LayerType) -> usize {
+    let mut query = world.query::<(&LayerInfo, &FlashPrimitives)>();
+    query.iter(world)
+        .find(|(layer_info, _)| layer_info.layer_type == layer_type)
+        .map(|(_, flashes)| flashes.0.len())
+        .unwrap_or(0)
+}
+
+/// Extract draw (D01), flash (D03) and region (G36/G37) primitives from raw gerber source
+/// Each primitive carries the net attribute and polarity active when it was drawn
+pub fn extract_primitives(content: &str) -> Vec<TaggedPrimitive> {
+    extract_primitives_with_arc_step(content, ARC_STEP_DEGREES)
+}
+
+/// Like `extract_primitives`, with arcs cut into segments of at most `arc_step_degrees`
+pub fn extract_primitives_with_arc_step(content: &str, arc_step_degrees: f64) -> Vec<TaggedPrimitive> {
+    let mut shapes = Vec::new();
+
+    let mut decimal_digits_x = 6u32;
+    let mut decimal_digits_y = 6u32;
+    let mut unit_scale = 1.0; // mm per file unit
+    let mut apertures: HashMap<u32, Aperture> = HashMap::new();
+    let mut current_aperture: Option<Aperture> = None;
+    let mut macros: HashMap<String, Vec<String>> = HashMap::new();
+    // %AM block being read: macro name and the statements so far
+    let mut open_macro: Option<(String, Vec<String>)> = None;
+    let mut current_net: Option<String> = None;
+    let mut polarity = Polarity::Dark;
+    let mut load = LoadTransform::IDENTITY;
+    let mut in_region = false;
+    let mut contour: Vec<Point2<f64>> = Vec::new();
+    // None for linear interpolation, Some(clockwise) for circular
+    let mut circular: Option<bool> = None;
+    let mut x = 0.0;
+    let mut y = 0.0;
+
+    for raw_block in content.split('*') {
+        // The '%' that ends an extended command starts the next block ("%\n%MOMM")
+        let closes_macro = raw_block.trim_start().starts_with('%');
+        if let Some((name, body)) = open_macro.take_if(|_| closes_macro) {
+            macros.insert(name, body);
+        }
+        let block = raw_block.trim_matches(|c: char| c == '%' || c.is_whitespace());
+        if block.is_empty() {
+            continue;
+        }
+        if let Some((_, body)) = &mut open_macro {
+            body.push(block.to_string());
+            continue;
+        }
+
+        // KiCad writes X2 attributes as comments when X2 output is disabled
+        let block = block.strip_prefix("G04 #@! ").unwrap_or(block);
+
+        if let Some(format) = block.strip_prefix("FS") {
+            // e.g. FSLAX46Y46
+            if let Some(x_pos) = format.find('X') {
+                let digits: Vec<u32> = format[x_pos + 1..].chars().take(2).filter_map(|c| c.to_digit(10)).collect();
+                if digits.len() == 2 {
+                    decimal_digits_x = digits[1];
+                }
+            }
+            if let Some(y_pos) = format.find('Y') {
+                let digits: Vec<u32> = format[y_pos + 1..].chars().take(2).filter_map(|c| c.to_digit(10)).collect();
+                if digits.len() == 2 {
+                    decimal_digits_y = digits[1];
+                }
+            }
+        } else if block.starts_with("MOIN") {
+            unit_scale = 25.4;
+        } else if block.starts_with("MOMM") {
+            unit_scale = 1.0;
+        } else if let Some(name) = block.strip_prefix("AM") {
+            open_macro = Some((name.to_string(), Vec::new()));
+        } else if let Some(definition) = block.strip_prefix("ADD") {
+            if let Some((code, aperture)) = parse_aperture_definition(definition, unit_scale, &macros) {
+                apertures.insert(code, aperture);
+            }
+        } else if let Some(net) = block.strip_prefix("TO.N,") {
+            let net = net.split(',').next().unwrap_or("").trim();
+            current_net = if net.is_empty() || net == "N/C" { None } else { Some(net.to_string()) };
+        } else if block == "TD" || block == "TD.N" {
+            current_net = None;
+        } else if block == "LPD" {
+            polarity = Polarity::Dark;
+        } else if block == "LPC" {
+            polarity = Polarity::Clear;
+        } else if let Some(mirroring) = block.strip_prefix("LM") {
+            load.mirror_x = mirroring.contains('X');
+            load.mirror_y = mirroring.contains('Y');
+        } else if let Some(rotation) = block.strip_prefix("LR") {
+            load.rotation = rotation.trim().parse().unwrap_or(0.0);
+        } else if let Some(scale) = block.strip_prefix("LS") {
+            load.scale = scale.trim().parse().unwrap_or(1.0);
+        } else if block.starts_with("G36") {
+            in_region = true;
+            contour.clear();
+        } else if block.starts_with("G37") {
+            flush_contour(&mut contour, &mut shapes, &current_net, polarity);
+            in_region = false;
+        } else if block.starts_with("G04") || block.starts_with("TF") || block.starts_with("TA") {
+            // Comments and file/aperture attributes carry no geometry
+        } else {
+            // Strip modal G codes preceding coordinates or aperture selection (G01X.., G54D10)
+            let mut data = block;
+            while data.starts_with('G') {
+                let end = data[1..].find(|c: char| !c.is_ascii_digit()).map(|i| i + 1).unwrap_or(data.len());
+                match data[1..end].parse::<u32>() {
+                    Ok(1) => circular = None,
+                    Ok(2) => circular = Some(true),
+                    Ok(3) => circular = Some(false),
+                    _ => {}
+                }
+                data = &data[end..];
+            }
+
+            if let Some(code) = data.strip_prefix('D').and_then(|d| d.parse::<u32>().ok()) {
+                if code >= 10 {
+                    current_aperture = apertures.get(&code).cloned();
+                }
+                continue;
+            }
+
+            if !(data.starts_with('X') || data.starts_with('Y')) {
+                continue;
+            }
+
+            let new_x = parse_coordinate(data, 'X', decimal_digits_x).map(|v| v * unit_scale).unwrap_or(x);
+            let new_y = parse_coordinate(data, 'Y', decimal_digits_y).map(|v| v * unit_scale).unwrap_or(y);
+            let operation = data.rfind('D').and_then(|pos| data[pos + 1..].parse::<u32>().ok());
+
+            // Points a D01 draws through, after the current point
+            let path = match circular {
+                Some(clockwise) if operation == Some(1) => {
+                    let i = parse_coordinate(data, 'I', decimal_digits_x).map(|v| v * unit_scale).unwrap_or(0.0);
+                    let j = parse_coordinate(data, 'J', decimal_digits_y).map(|v| v * unit_scale).unwrap_or(0.0);
+                    tessellate_arc(Point2::new(x, y), Point2::new(new_x, new_y), Point2::new(x + i, y + j), clockwise, arc_step_degrees)
+                }
+                _ => vec![Point2::new(new_x, new_y)],
+            };
+
+            if in_region {
+                match operation {
+                    Some(1) => {
+                        if contour.is_empty() {
+                            contour.push(Point2::new(x, y));
+                        }
+                        contour.extend(path);
+                    }
+                    Some(2) => flush_contour(&mut contour, &mut shapes, &current_net, polarity),
+                    _ => {}
+                }
+                x = new_x;
+                y = new_y;
+                continue;
+            }
+
+            let Aperture { width, height, shape: aperture, outline } = current_aperture.as_ref().unwrap_or(&Aperture::UNKNOWN).loaded(&load);
+            let new_shapes: Vec<PrimitiveShape> = match operation {
+                Some(1) => std::iter::once(Point2::new(x, y))
+                    .chain(path.iter().copied())
+                    .zip(path.iter().copied())
+                    .map(|(start, end)| PrimitiveShape::Segment { start, end, width })
+                    .collect(),
+                Some(3) => vec![PrimitiveShape::Flash {
+                    center: Point2::new(new_x, new_y),
+                    width,
+                    height,
+                    aperture,
+                    outline,
+                }],
+                _ => Vec::new(),
+            };
+            shapes.extend(new_shapes.into_iter().map(|shape| TaggedPrimitive {
+                net: current_net.clone(),
+                polarity,
+                shape,
+            }));
+
+            x = new_x;
+            y = new_y;
+        }
+    }
+
+    shapes
+}
+
+/// Points along a circular arc from `start` to `end` around `center`, excluding `start`
+/// Coincident start and end points make a full circle
+fn tessellate_arc(start: Point2<f64>, end: Point2<f64>, center: Point2<f64>, clockwise: bool, step_degrees: f64) -> Vec<Point2<f64>> {
+    let radius = (start - center).norm();
+    if radius <= f64::EPSILON {
+        return vec![end];
+    }
+    let start_angle = (start.y - center.y).atan2(start.x - center.x);
+    let end_angle = (end.y - center.y).atan2(end.x - center.x);
+    let full_turn = std::f64::consts::TAU;
+    let mut sweep = if clockwise { start_angle - end_angle } else { end_angle - start_angle };
+    sweep = sweep.rem_euclid(full_turn);
+    if sweep <= 1e-9 {
+        sweep = full_turn;
+    }
+
+    // A quarter circle is 18 steps of 5° even when float noise makes its sweep a hair longer
+    let steps = (sweep.to_degrees() / step_degrees - 1e-9).ceil().max(1.0) as usize;
+    let direction = if clockwise { -1.0 } else { 1.0 };
+    let mut points: Vec<Point2<f64>> = (1..steps)
+        .map(|step| {
+            let angle = start_angle + direction * sweep * step as f64 / steps as f64;
+            Point2::new(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect();
+    points.push(end);
+    points
+}
+
+/// Close the region contour being collected, keeping it if it encloses an area
+fn flush_contour(contour: &mut Vec<Point2<f64>>, shapes: &mut Vec<TaggedPrimitive>, net: &Option<String>, polarity: Polarity) {
+    let mut points = std::mem::take(contour);
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() >= 3 {
+        shapes.push(TaggedPrimitive {
+            net: net.clone(),
+            polarity,
+            shape: PrimitiveShape::Region { points },
+        });
+    }
+}
+
+/// Aperture selected by a D code, sizes in mm
+#[derive(Debug, Clone)]
+struct Aperture {
+    width: f64,
+    height: f64,
+    shape: ApertureShape,
+    outline: Option<Arc<ApertureOutline>>,
+}
+
+impl Aperture {
+    /// Undefined D code or unknown template
+    const UNKNOWN: Self = Self { width: 0.0, height: 0.0, shape: ApertureShape::Other, outline: None };
+
+    /// The aperture as the current %LM/%LR/%LS state flashes or draws it
+    fn loaded(&self, load: &LoadTransform) -> Self {
+        if load.is_identity() {
+            return self.clone();
+        }
+        if let Some(outline) = &self.outline {
+            let outline = outline.transformed(&load.matrix());
+            let (width, height) = match self.shape {
+                ApertureShape::Other => outline.centered_size(),
+                _ => (self.width * load.scale, self.height * load.scale),
+            };
+            return Self { width, height, shape: self.shape, outline: Some(Arc::new(outline)) };
+        }
+        let quarter_turns = (load.rotation / 90.0).round();
+        let swapped = (load.rotation - quarter_turns * 90.0).abs() < 1e-6 && quarter_turns.rem_euclid(2.0) == 1.0;
+        let (width, height) = if swapped { (self.height, self.width) } else { (self.width, self.height) };
+        Self { width: width * load.scale, height: height * load.scale, shape: self.shape, outline: None }
+    }
+}
+
+/// Load mirroring, rotation (degrees, counter-clockwise) and scaling of the graphics state
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LoadTransform {
+    mirror_x: bool,
+    mirror_y: bool,
+    rotation: f64,
+    scale: f64,
+}
+
+impl LoadTransform {
+    const IDENTITY: Self = Self { mirror_x: false, mirror_y: false, rotation: 0.0, scale: 1.0 };
+
+    fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Mirroring first, then rotation, then scaling, as the specification orders them
+    fn matrix(&self) -> Matrix3<f64> {
+        let mirror = Matrix3::new_nonuniform_scaling(&nalgebra::Vector2::new(
+            if self.mirror_x { -1.0 } else { 1.0 },
+            if self.mirror_y { -1.0 } else { 1.0 },
+        ));
+        Matrix3::new_scaling(self.scale) * Matrix3::new_rotation(self.rotation.to_radians()) * mirror
+    }
+}
+
+/// Parse "10C,0.25" / "11R,1.2X0.8" / "12RoundRect,0.25X..." into (code, aperture in mm)
+/// Macros are evaluated with the definition's parameters; unknown templates get zero size
+fn parse_aperture_definition(definition: &str, unit_scale: f64, macros: &HashMap<String, Vec<String>>) -> Option<(u32, Aperture)> {
+    let code_end = definition.find(|c: char| !c.is_ascii_digit())?;
+    let code = definition[..code_end].parse::<u32>().ok()?;
+    let rest = &definition[code_end..];
+    let (template, params) = rest.split_once(',').unwrap_or((rest, ""));
+    let values: Vec<f64> = params.split('X').filter_map(|p| p.trim().parse::<f64>().ok()).collect();
+
+    let standard = |width: f64, height: f64, shape| Aperture { width: width * unit_scale, height: height * unit_scale, shape, outline: None };
+    let aperture = match (template, values.as_slice()) {
+        ("C", [d, ..]) => standard(*d, *d, ApertureShape::Circle),
+        ("R", [w, h, ..]) => standard(*w, *h, ApertureShape::Rectangle),
+        ("O", [w, h, ..]) => standard(*w, *h, ApertureShape::Obround),
+        ("P", [d, vertices, rest @ ..]) => {
+            let rotation = rest.first().copied().unwrap_or(0.0);
+            let outline = ApertureOutline::regular_polygon(d * unit_scale, *vertices as usize, rotation);
+            Aperture { outline: Some(Arc::new(outline)), ..standard(*d, *d, ApertureShape::Polygon) }
+        }
+        ("P", [d, ..]) => standard(*d, *d, ApertureShape::Polygon),
+        _ => match macros.get(template).and_then(|body| ApertureOutline::from_macro(template, body, params, unit_scale)) {
+            Some(outline) => {
+                let (width, height) = outline.centered_size();
+                Aperture { width, height, shape: ApertureShape::Other, outline: Some(Arc::new(outline)) }
+            }
+            None => Aperture::UNKNOWN,
+        },
+    };
+    Some((code, aperture))
+}
+
+/// Parse a fixed-point coordinate value following `axis` (e.g. X-1234567)
+fn parse_coordinate(data: &str, axis: char, decimal_digits: u32) -> Option<f64> {
+    let start = data.find(axis)? + 1;
+    let value: String = data[start..]
+        .chars()
+        .enumerate()
+        .take_while(|(i, c)| c.is_ascii_digit() || (*i == 0 && (*c == '-' || *c == '+')))
+        .map(|(_, c)| c)
+        .collect();
+    let raw = value.parse::<i64>().ok()?;
+    Some(raw as f64 / 10f64.powi(decimal_digits as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_flash_primitives_keeps_only_flashes() {
+        let gerber = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.600000*%\n%ADD11R,1.500000X0.800000*%\n\
+            D10*\nX0Y0D02*\nX1000000Y0D01*\nX2000000Y3000000D03*\nD11*\nX-1000000Y500000D03*\nM02*\n";
+
+        let flashes = build_flash_primitives(&extract_primitives(gerber));
+        assert_eq!(flashes.0.len(), 2);
+
+        assert_eq!(flashes.0[0].position, Point2::new(2.0, 3.0));
+        assert!((flashes.0[0].diameter - 0.6).abs() < 1e-9);
+
+        assert_eq!(flashes.0[1].position, Point2::new(-1.0, 0.5));
+        assert!((flashes.0[1].diameter - 1.5).abs() < 1e-9);
+        assert!((flashes.0[1].height - 0.8).abs() < 1e-9);
+        assert_eq!(flashes.0[1].aperture, ApertureShape::Rectangle);
+        assert!((flashes.0[1].area() - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_macro_flash_gets_its_outline_and_size() {
+        // KiCad RoundRect: corners at ±0.45, ±0.35 rounded by 0.25, so the pad is 1.4 × 1.2 mm
+        let gerber = "%FSLAX46Y46*%\n%MOIN*%\n%MOMM*%\n%AMRoundRect*\n0 Rectangle with rounded corners*\n\
+            4,1,4,$2,$3,$4,$5,$6,$7,$8,$9,$2,$3,0*\n1,1,$1+$1,$2,$3*\n1,1,$1+$1,$4,$5*\n1,1,$1+$1,$6,$7*\n1,1,$1+$1,$8,$9*\n\
+            20,1,$1+$1,$2,$3,$4,$5,0*\n20,1,$1+$1,$4,$5,$6,$7,0*\n20,1,$1+$1,$6,$7,$8,$9,0*\n20,1,$1+$1,$8,$9,$2,$3,0*%\n\
+            %ADD10RoundRect,0.250000X-0.450000X-0.350000X0.450000X-0.350000X0.450000X0.350000X-0.450000X0.350000*%\n\
+            D10*\nX1000000Y2000000D03*\nM02*\n";
+        let primitives = extract_primitives(gerber);
+        assert_eq!(primitives.len(), 1);
+        let PrimitiveShape::Flash { center, width, height, aperture, outline } = &primitives[0].shape else { unreachable!() };
+        assert_eq!((*center, *aperture), (Point2::new(1.0, 2.0), ApertureShape::Other));
+        assert!((width - 1.4).abs() < 1e-9 && (height - 1.2).abs() < 1e-9, "{} × {}", width, height);
+        assert_eq!(outline.as_ref().and_then(|outline| outline.definition.as_ref()).map(|definition| definition.name.as_str()), Some("RoundRect"));
+
+        // Straight edge, then the rounded corner
+        let shape = &primitives[0].shape;
+        assert!((shape.distance_to(Point2::new(1.8, 2.0)) - 0.1).abs() < 1e-9);
+        let corner = shape.distance_to(Point2::new(1.7, 2.6));
+        assert!((corner - (0.25f64.hypot(0.25) - 0.25)).abs() < 1e-9);
+        assert_eq!(shape.distance_to(Point2::new(1.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn test_circular_draw_is_tessellated() {
+        // Counter-clockwise half circle of radius 1 from (1, 0) to (-1, 0) around the origin
+        let gerber = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.100000*%\nD10*\nX1000000Y0D02*\nG75*\nG03X-1000000Y0I-1000000J0D01*\nG01*\nX-1000000Y-1000000D01*\nM02*\n";
+        let primitives = extract_primitives(gerber);
+        assert_eq!(primitives.len(), 180 / ARC_STEP_DEGREES as usize + 1);
+
+        for primitive in &primitives[..primitives.len() - 1] {
+            let PrimitiveShape::Segment { start, end, .. } = primitive.shape else { unreachable!() };
+            assert!((start.coords.norm() - 1.0).abs() < 1e-9 && (end.coords.norm() - 1.0).abs() < 1e-9);
+            assert!(end.y >= -1e-9);
+        }
+        // Back to linear after G01
+        let PrimitiveShape::Segment { start, end, .. } = primitives.last().unwrap().shape else { unreachable!() };
+        assert_eq!((start, end), (Point2::new(-1.0, 0.0), Point2::new(-1.0, -1.0)));
+    }
+}
@@ -0,0 +1,195 @@
+//! Where each gerber file came from
+//!
+//! Reads the `TF.GenerationSoftware` and `TF.CreationDate` file attributes
+//! (either as `%TF...*%` or the `G04 #@! TF...*` comment form older KiCad
+//! versions write) and the first plain G04 comment as a fallback. Files
+//! plotted minutes apart or by different tool versions usually mean a stale
+//! layer left over from an earlier revision.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use bevy_ecs::prelude::*;
+use chrono::{DateTime, FixedOffset};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::components::LayerInfo;
+use super::LayerType;
+
+/// Files plotted further apart than this are flagged
+pub const PROVENANCE_TIME_SPREAD_MINUTES: i64 = 5;
+
+static GENERATION_SOFTWARE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"TF\.GenerationSoftware,([^,*]*)(?:,([^,*]*))?(?:,([^*]*))?\*").unwrap());
+static CREATION_DATE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"TF\.CreationDate,([^*]*)\*").unwrap());
+static PLAIN_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^G04\s*([^#*\s][^*]*)\*").unwrap());
+
+/// Generating tool and plot time of one gerber file, as declared in its header
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerProvenance {
+    /// Vendor and application, e.g. "KiCad Pcbnew"
+    pub software: Option<String>,
+    pub version: Option<String>,
+    /// Creation timestamp as written in the file (ISO 8601)
+    pub creation_date: Option<String>,
+    /// First ordinary G04 comment, often the only hint in older files
+    pub comment: Option<String>,
+}
+
+impl LayerProvenance {
+    pub fn from_content(content: &str) -> Self {
+        let software = GENERATION_SOFTWARE.captures(content);
+        let field = |index: usize| {
+            software.as_ref()
+                .and_then(|captures| captures.get(index))
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|text| !text.is_empty())
+        };
+        let application = [field(1), field(2)].into_iter().flatten().collect::<Vec<_>>().join(" ");
+
+        Self {
+            software: Some(application).filter(|text| !text.is_empty()),
+            version: field(3),
+            creation_date: CREATION_DATE.captures(content).map(|captures| captures[1].trim().to_string()),
+            comment: PLAIN_COMMENT.captures(content).map(|captures| captures[1].trim().to_string()),
+        }
+    }
+
+    pub fn created_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.creation_date.as_deref().and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+    }
+
+    /// Software and version as one label, e.g. "KiCad Pcbnew 8.0.1"
+    pub fn tool(&self) -> Option<String> {
+        match (&self.software, &self.version) {
+            (Some(software), Some(version)) => Some(format!("{} {}", software, version)),
+            (Some(software), None) => Some(software.clone()),
+            (None, _) => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Provenance of one loaded layer, as listed in the panel and the DRC report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerProvenanceEntry {
+    pub layer: String,
+    pub file: Option<String>,
+    #[serde(flatten)]
+    pub provenance: LayerProvenance,
+    /// Differs from the rest of the set in tool or plot time
+    pub mismatch: bool,
+}
+
+/// Provenance across the loaded layers with the mismatches found
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceSummary {
+    pub layers: Vec<LayerProvenanceEntry>,
+    pub warnings: Vec<String>,
+}
+
+impl ProvenanceSummary {
+    /// Compare the layers: the most common tool is taken as the reference and
+    /// plot times are measured back from the newest file
+    pub fn from_layers(layers: Vec<(LayerType, Option<String>, LayerProvenance)>) -> Self {
+        let mut tool_counts: HashMap<String, usize> = HashMap::new();
+        for (_, _, provenance) in &layers {
+            if let Some(tool) = provenance.tool() {
+                *tool_counts.entry(tool).or_default() += 1;
+            }
+        }
+        let mut tools: Vec<(String, usize)> = tool_counts.into_iter().collect();
+        tools.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let reference_tool = tools.first().map(|(tool, _)| tool.clone());
+
+        let times: Vec<DateTime<FixedOffset>> = layers.iter().filter_map(|(_, _, provenance)| provenance.created_at()).collect();
+        let newest = times.iter().max().copied();
+        let spread_minutes = newest.zip(times.iter().min()).map(|(newest, oldest)| (newest - *oldest).num_minutes());
+
+        let mut warnings = Vec::new();
+        if tools.len() > 1 {
+            let names: Vec<&str> = tools.iter().map(|(tool, _)| tool.as_str()).collect();
+            warnings.push(format!("Layers were generated by different software: {}", names.join(", ")));
+        }
+        if let Some(spread) = spread_minutes.filter(|spread| *spread > PROVENANCE_TIME_SPREAD_MINUTES) {
+            warnings.push(format!("Layers were plotted {} apart; some may be stale", format_minutes(spread)));
+        }
+
+        let entries = layers
+            .into_iter()
+            .map(|(layer_type, file, provenance)| {
+                let other_tool = provenance.tool().is_some() && provenance.tool() != reference_tool;
+                let stale = provenance.created_at()
+                    .zip(newest)
+                    .is_some_and(|(created, newest)| (newest - created).num_minutes() > PROVENANCE_TIME_SPREAD_MINUTES);
+                LayerProvenanceEntry {
+                    layer: layer_type.display_name().to_string(),
+                    file,
+                    provenance,
+                    mismatch: other_tool || stale,
+                }
+            })
+            .collect();
+
+        Self { layers: entries, warnings }
+    }
+}
+
+fn format_minutes(minutes: i64) -> String {
+    match minutes {
+        m if m < 120 => format!("{} minutes", m),
+        m if m < 48 * 60 => format!("{} hours", m / 60),
+        m => format!("{} days", m / (24 * 60)),
+    }
+}
+
+/// Provenance of every loaded layer, in layer order
+pub fn get_provenance_summary(world: &mut World) -> ProvenanceSummary {
+    let mut layers: Vec<(LayerType, Option<String>, LayerProvenance)> = {
+        let mut query = world.query::<(&LayerInfo, Option<&LayerProvenance>)>();
+        query.iter(world)
+            .map(|(info, provenance)| {
+                let file = info.file_path.as_ref()
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().into_owned());
+                (info.layer_type, file, provenance.cloned().unwrap_or_default())
+            })
+            .collect()
+    };
+    layers.sort_by_key(|(layer_type, _, _)| LayerType::all().iter().position(|other| other == layer_type));
+    ProvenanceSummary::from_layers(layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Side;
+
+    #[test]
+    fn test_provenance_parsing_and_mismatches() {
+        let kicad8 = "G04 #@! TF.GenerationSoftware,KiCad,Pcbnew,8.0.1*\nG04 #@! TF.CreationDate,2024-03-01T10:22:33+01:00*\n%FSLAX46Y46*%\n";
+        let provenance = LayerProvenance::from_content(kicad8);
+        assert_eq!(provenance.tool().as_deref(), Some("KiCad Pcbnew 8.0.1"));
+        assert_eq!(provenance.creation_date.as_deref(), Some("2024-03-01T10:22:33+01:00"));
+        assert_eq!(provenance.comment, None);
+
+        let attribute = LayerProvenance::from_content("%TF.GenerationSoftware,KiCad,Pcbnew,8.0.1*%\n%TF.CreationDate,2024-03-01T10:25:00+01:00*%\n");
+        let stale = LayerProvenance::from_content("G04 Created by hand*\n%TF.GenerationSoftware,KiCad,Pcbnew,7.0.10*%\n%TF.CreationDate,2024-02-20T09:00:00+01:00*%\n");
+        assert_eq!(stale.comment.as_deref(), Some("Created by hand"));
+        assert!(LayerProvenance::from_content("%FSLAX46Y46*%\n").is_empty());
+
+        let summary = ProvenanceSummary::from_layers(vec![
+            (LayerType::Copper(1), Some("top.gbr".to_string()), provenance),
+            (LayerType::Copper(2), None, attribute),
+            (LayerType::Soldermask(Side::Top), None, stale),
+        ]);
+        let mismatches: Vec<bool> = summary.layers.iter().map(|entry| entry.mismatch).collect();
+        assert_eq!(mismatches, vec![false, false, true]);
+        assert_eq!(summary.warnings.len(), 2);
+        assert!(summary.warnings[1].contains("9 days"));
+    }
+}
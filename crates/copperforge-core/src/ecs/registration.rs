@@ -0,0 +1,437 @@
+//! Registration sanity check across the loaded gerber files
+//!
+//! Catches stale files from an older revision: layers that spill past the
+//! mechanical outline or sit far from its center, and files whose header
+//! declares different units or coordinate precision from the rest. A layer
+//! whose extents are an order of magnitude off from the others is flagged as a
+//! likely coordinate format mismatch; `FormatOverride` re-parses it with a
+//! corrected header.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use bevy_ecs::prelude::*;
+use gerber_viewer::BoundingBox;
+use regex::Regex;
+
+use super::components::{GerberData, LayerInfo};
+use super::LayerType;
+
+/// How far a layer may extend past the outline before it is flagged (board units)
+pub const OUTLINE_OVERHANG_TOLERANCE: f64 = 0.5;
+
+/// Layer centers further than this fraction of the outline's shorter side are flagged
+pub const MAX_CENTER_OFFSET_FRACTION: f64 = 0.25;
+
+/// A layer whose bounding box diagonal differs from the median of the others by
+/// more than this factor (either way) is flagged as a format mismatch
+pub const SCALE_MISMATCH_RATIO: f64 = 8.0;
+
+static FORMAT_SPEC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"%FS([LTD])?[AI]X(\d)(\d)Y\d\d").unwrap());
+static FORMAT_HEADER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)%FS[^%]*%\r?\n?|%MO(IN|MM)\*%\r?\n?|^G7[01]\*\r?\n?").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GerberUnits {
+    Inch,
+    Millimeters,
+}
+
+impl GerberUnits {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Inch => "inch",
+            Self::Millimeters => "mm",
+        }
+    }
+
+    pub const ALL: [GerberUnits; 2] = [GerberUnits::Millimeters, GerberUnits::Inch];
+}
+
+/// Which zeros the %FS command says are left out of coordinate numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZeroOmission {
+    Leading,
+    Trailing,
+}
+
+impl ZeroOmission {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Leading => "leading zeros omitted",
+            Self::Trailing => "trailing zeros omitted",
+        }
+    }
+
+    pub const ALL: [ZeroOmission; 2] = [ZeroOmission::Leading, ZeroOmission::Trailing];
+}
+
+/// Units and coordinate format declared in a gerber file's header
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GerberFormat {
+    pub units: Option<GerberUnits>,
+    /// Integer and decimal digits from the %FS command
+    pub coordinate_digits: Option<(u8, u8)>,
+    pub zero_omission: Option<ZeroOmission>,
+}
+
+impl GerberFormat {
+    pub fn from_content(content: &str) -> Self {
+        let units = if content.contains("%MOIN") {
+            Some(GerberUnits::Inch)
+        } else if content.contains("%MOMM") {
+            Some(GerberUnits::Millimeters)
+        } else if content.contains("G70") {
+            Some(GerberUnits::Inch)
+        } else if content.contains("G71") {
+            Some(GerberUnits::Millimeters)
+        } else {
+            None
+        };
+
+        let captures = FORMAT_SPEC.captures(content);
+        let coordinate_digits = captures.as_ref().and_then(|captures| {
+            let integer = captures[2].parse().ok()?;
+            let decimal = captures[3].parse().ok()?;
+            Some((integer, decimal))
+        });
+        let zero_omission = captures.as_ref().and_then(|captures| match captures.get(1).map(|m| m.as_str()) {
+            Some("L") => Some(ZeroOmission::Leading),
+            Some("T") => Some(ZeroOmission::Trailing),
+            _ => None,
+        });
+
+        Self { units, coordinate_digits, zero_omission }
+    }
+
+    pub fn describe_digits(&self) -> String {
+        match self.coordinate_digits {
+            Some((integer, decimal)) => format!("{}.{}", integer, decimal),
+            None => "unspecified".to_string(),
+        }
+    }
+
+    /// Short form for the layer list, e.g. "4.6 mm L"
+    pub fn summary(&self) -> String {
+        let units = self.units.map(|units| units.name()).unwrap_or("?");
+        let zeros = match self.zero_omission {
+            Some(ZeroOmission::Leading) => " L",
+            Some(ZeroOmission::Trailing) => " T",
+            None => "",
+        };
+        format!("{} {}{}", self.describe_digits(), units, zeros)
+    }
+
+    /// Every detected field, for hover text
+    pub fn describe(&self) -> String {
+        format!(
+            "Coordinate format {} ({} integer, {} decimal digits)\nUnits: {}\nZeros: {}",
+            self.describe_digits(),
+            self.coordinate_digits.map(|(integer, _)| integer.to_string()).unwrap_or_else(|| "?".to_string()),
+            self.coordinate_digits.map(|(_, decimal)| decimal.to_string()).unwrap_or_else(|| "?".to_string()),
+            self.units.map(|units| units.name()).unwrap_or("unspecified"),
+            self.zero_omission.map(|zeros| zeros.name()).unwrap_or("unspecified"),
+        )
+    }
+}
+
+/// Coordinate format forced on a layer whose header is wrong or missing
+///
+/// Applied by rewriting the %FS/%MO header before parsing, so the gerber_viewer
+/// layer and the extracted primitives both see the corrected format.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOverride {
+    pub units: GerberUnits,
+    pub integer_digits: u8,
+    pub decimal_digits: u8,
+    pub zero_omission: ZeroOmission,
+}
+
+impl FormatOverride {
+    /// Start from what was detected, filling gaps with the common 4.6 mm format
+    pub fn from_detected(format: &GerberFormat) -> Self {
+        let (integer_digits, decimal_digits) = format.coordinate_digits.unwrap_or((4, 6));
+        Self {
+            units: format.units.unwrap_or(GerberUnits::Millimeters),
+            integer_digits,
+            decimal_digits,
+            zero_omission: format.zero_omission.unwrap_or(ZeroOmission::Leading),
+        }
+    }
+
+    /// `content` with its format and unit commands replaced by this override
+    pub fn apply(&self, content: &str) -> String {
+        let zeros = match self.zero_omission {
+            ZeroOmission::Leading => 'L',
+            ZeroOmission::Trailing => 'T',
+        };
+        let units = match self.units {
+            GerberUnits::Inch => "IN",
+            GerberUnits::Millimeters => "MM",
+        };
+        let digits = format!("{}{}", self.integer_digits, self.decimal_digits);
+        format!(
+            "%FS{}AX{}Y{}*%\n%MO{}*%\n{}",
+            zeros, digits, digits, units,
+            FORMAT_HEADER.replace_all(content, "")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistrationIssueKind {
+    /// Layer extends past the outline by `overhang`
+    ExceedsOutline { overhang: f64 },
+    /// Layer center is `offset` away from the outline center
+    CenterOffset { offset: f64 },
+    UnitsMismatch { units: GerberUnits, expected: GerberUnits },
+    PrecisionMismatch { digits: (u8, u8), expected: (u8, u8) },
+    /// Layer's extents are `ratio` times the typical layer's
+    ScaleMismatch { ratio: f64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistrationIssue {
+    pub layer_type: LayerType,
+    pub file_name: Option<String>,
+    pub kind: RegistrationIssueKind,
+}
+
+impl RegistrationIssue {
+    pub fn description(&self) -> String {
+        match &self.kind {
+            RegistrationIssueKind::ExceedsOutline { overhang } => {
+                format!("extends {:.3} past the board outline", overhang)
+            }
+            RegistrationIssueKind::CenterOffset { offset } => {
+                format!("center is {:.3} away from the board outline center", offset)
+            }
+            RegistrationIssueKind::UnitsMismatch { units, expected } => {
+                format!("uses {} units while the other files use {}", units.name(), expected.name())
+            }
+            RegistrationIssueKind::PrecisionMismatch { digits, expected } => format!(
+                "coordinate format {}.{} differs from the other files ({}.{})",
+                digits.0, digits.1, expected.0, expected.1
+            ),
+            RegistrationIssueKind::ScaleMismatch { ratio } if *ratio >= 1.0 => format!(
+                "is {:.0}× larger than the other layers; check its coordinate format",
+                ratio
+            ),
+            RegistrationIssueKind::ScaleMismatch { ratio } => format!(
+                "is {:.0}× smaller than the other layers; check its coordinate format",
+                1.0 / ratio
+            ),
+        }
+    }
+
+    pub fn format_message(&self) -> String {
+        match &self.file_name {
+            Some(file_name) => format!("{} ({}): {}", self.layer_type.display_name(), file_name, self.description()),
+            None => format!("{}: {}", self.layer_type.display_name(), self.description()),
+        }
+    }
+}
+
+/// Result of the last registration check, shown in the layer panel
+#[derive(Resource, Default)]
+pub struct RegistrationIssues(pub Vec<RegistrationIssue>);
+
+/// Compare a layer's extents and center against the outline
+pub fn check_against_outline(outline: &BoundingBox, layer: &BoundingBox) -> Vec<RegistrationIssueKind> {
+    let mut issues = Vec::new();
+
+    let overhang = [
+        outline.min.x - layer.min.x,
+        outline.min.y - layer.min.y,
+        layer.max.x - outline.max.x,
+        layer.max.y - outline.max.y,
+    ]
+    .into_iter()
+    .fold(0.0, f64::max);
+    if overhang > OUTLINE_OVERHANG_TOLERANCE {
+        issues.push(RegistrationIssueKind::ExceedsOutline { overhang });
+    }
+
+    let outline_center = nalgebra::center(&outline.min, &outline.max);
+    let layer_center = nalgebra::center(&layer.min, &layer.max);
+    let offset = (layer_center - outline_center).norm();
+    let shorter_side = (outline.max.x - outline.min.x).min(outline.max.y - outline.min.y);
+    if offset > shorter_side * MAX_CENTER_OFFSET_FRACTION {
+        issues.push(RegistrationIssueKind::CenterOffset { offset });
+    }
+
+    issues
+}
+
+/// Ratio of `diagonal` to the median of `others` when it is off by more than
+/// `SCALE_MISMATCH_RATIO`; needs at least two other layers to compare against
+pub fn scale_outlier(diagonal: f64, others: &[f64]) -> Option<f64> {
+    let mut others: Vec<f64> = others.iter().copied().filter(|d| d.is_finite() && *d > 0.0).collect();
+    if others.len() < 2 || !diagonal.is_finite() || diagonal <= 0.0 {
+        return None;
+    }
+    others.sort_by(f64::total_cmp);
+    let middle = others.len() / 2;
+    let median = if others.len() % 2 == 0 { (others[middle - 1] + others[middle]) / 2.0 } else { others[middle] };
+    let ratio = diagonal / median;
+    (ratio > SCALE_MISMATCH_RATIO || ratio < 1.0 / SCALE_MISMATCH_RATIO).then_some(ratio)
+}
+
+fn diagonal(bounds: &BoundingBox) -> f64 {
+    (bounds.max - bounds.min).norm()
+}
+
+/// Most common value, ties broken towards the first layer in layer order
+fn majority<T: Copy + Eq + std::hash::Hash>(values: &[T]) -> Option<T> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(*value).or_default() += 1;
+    }
+    values.iter().copied().max_by_key(|value| (counts[value], std::cmp::Reverse(values.iter().position(|v| v == value))))
+}
+
+/// Check every loaded layer and store the result in `RegistrationIssues`
+pub fn check_layer_registration(world: &mut World) -> Vec<RegistrationIssue> {
+    let mut layers: Vec<(LayerType, Option<String>, BoundingBox, GerberFormat)> = {
+        let mut query = world.query::<(&LayerInfo, &GerberData, Option<&GerberFormat>)>();
+        query
+            .iter(world)
+            .map(|(info, gerber, format)| {
+                let file_name = info.file_path
+                    .as_ref()
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().to_string());
+                (info.layer_type, file_name, gerber.0.bounding_box().clone(), format.copied().unwrap_or_default())
+            })
+            .collect()
+    };
+    let order = LayerType::all();
+    layers.sort_by_key(|(layer_type, ..)| order.iter().position(|l| l == layer_type).unwrap_or(order.len()));
+
+    let mut issues = Vec::new();
+    let mut push = |layer_type: LayerType, file_name: &Option<String>, kind| {
+        issues.push(RegistrationIssue { layer_type, file_name: file_name.clone(), kind });
+    };
+
+    let units: Vec<GerberUnits> = layers.iter().filter_map(|(.., format)| format.units).collect();
+    let digits: Vec<(u8, u8)> = layers.iter().filter_map(|(.., format)| format.coordinate_digits).collect();
+    let expected_units = majority(&units);
+    let expected_digits = majority(&digits);
+    let outline = layers
+        .iter()
+        .find(|(layer_type, ..)| *layer_type == LayerType::MechanicalOutline)
+        .map(|(.., bounds, _)| bounds.clone());
+
+    let diagonals: Vec<f64> = layers.iter().map(|(_, _, bounds, _)| diagonal(bounds)).collect();
+
+    for (index, (layer_type, file_name, bounds, format)) in layers.iter().enumerate() {
+        if let (Some(units), Some(expected)) = (format.units, expected_units) {
+            if units != expected {
+                push(*layer_type, file_name, RegistrationIssueKind::UnitsMismatch { units, expected });
+            }
+        }
+        if let (Some(digits), Some(expected)) = (format.coordinate_digits, expected_digits) {
+            if digits != expected {
+                push(*layer_type, file_name, RegistrationIssueKind::PrecisionMismatch { digits, expected });
+            }
+        }
+        let others: Vec<f64> = diagonals.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, d)| *d).collect();
+        if let Some(ratio) = scale_outlier(diagonals[index], &others) {
+            push(*layer_type, file_name, RegistrationIssueKind::ScaleMismatch { ratio });
+        }
+        if let Some(outline) = &outline {
+            if *layer_type != LayerType::MechanicalOutline {
+                for kind in check_against_outline(outline, bounds) {
+                    push(*layer_type, file_name, kind);
+                }
+            }
+        }
+    }
+
+    world.insert_resource(RegistrationIssues(issues.clone()));
+    issues
+}
+
+/// Format detected (or forced) for a loaded layer
+pub fn get_layer_format(world: &mut World, layer_type: LayerType) -> Option<GerberFormat> {
+    let entity = super::get_layer_by_type(world, layer_type)?;
+    world.get::<GerberFormat>(entity).copied()
+}
+
+/// Issues found for one layer by the last check
+pub fn get_registration_issues(world: &World, layer_type: LayerType) -> Vec<RegistrationIssue> {
+    world
+        .get_resource::<RegistrationIssues>()
+        .map(|issues| issues.0.iter().filter(|issue| issue.layer_type == layer_type).cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Point2;
+
+    fn bbox(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> BoundingBox {
+        BoundingBox { min: Point2::new(min_x, min_y), max: Point2::new(max_x, max_y) }
+    }
+
+    #[test]
+    fn test_gerber_format_from_header() {
+        let format = GerberFormat::from_content("G04 test*\n%FSLAX46Y46*%\n%MOMM*%\n");
+        assert_eq!(format.units, Some(GerberUnits::Millimeters));
+        assert_eq!(format.coordinate_digits, Some((4, 6)));
+
+        let legacy = GerberFormat::from_content("%FSLAX25Y25*%\nG70*\n");
+        assert_eq!(legacy.units, Some(GerberUnits::Inch));
+        assert_eq!(legacy.coordinate_digits, Some((2, 5)));
+    }
+
+    #[test]
+    fn test_format_override_rewrites_header() {
+        let content = "G04 test*\n%FSTAX25Y25*%\nG70*\n%ADD10C,0.010*%\nX1Y1D02*\nM02*\n";
+        let detected = GerberFormat::from_content(content);
+        assert_eq!(detected.zero_omission, Some(ZeroOmission::Trailing));
+        assert_eq!(detected.summary(), "2.5 inch T");
+
+        let mut corrected = FormatOverride::from_detected(&detected);
+        corrected.units = GerberUnits::Millimeters;
+        corrected.integer_digits = 4;
+        corrected.decimal_digits = 6;
+        corrected.zero_omission = ZeroOmission::Leading;
+        let rewritten = corrected.apply(content);
+        assert!(rewritten.contains("%ADD10C,0.010*%") && !rewritten.contains("G70"));
+        assert_eq!(rewritten.matches("%FS").count(), 1);
+        let format = GerberFormat::from_content(&rewritten);
+        assert_eq!(format.units, Some(GerberUnits::Millimeters));
+        assert_eq!(format.coordinate_digits, Some((4, 6)));
+        assert_eq!(format.zero_omission, Some(ZeroOmission::Leading));
+    }
+
+    #[test]
+    fn test_scale_outlier_flags_order_of_magnitude() {
+        let others = [100.0, 104.0, 98.0];
+        assert_eq!(scale_outlier(101.0, &others), None);
+        // Inch file read as mm: 25.4x too small
+        assert!(scale_outlier(100.0 / 25.4, &others).is_some_and(|ratio| ratio < 1.0));
+        // One decimal digit short: 10x too large
+        assert!(scale_outlier(1000.0, &others).is_some_and(|ratio| ratio > 1.0));
+        assert_eq!(scale_outlier(1000.0, &[100.0]), None);
+    }
+
+    #[test]
+    fn test_layer_checked_against_outline() {
+        let outline = bbox(0.0, 0.0, 100.0, 80.0);
+        assert!(check_against_outline(&outline, &bbox(1.0, 1.0, 99.0, 79.0)).is_empty());
+
+        // Same size, shifted by a stale origin
+        let shifted = check_against_outline(&outline, &bbox(30.0, 1.0, 129.0, 79.0));
+        assert!(matches!(shifted[0], RegistrationIssueKind::ExceedsOutline { overhang } if (overhang - 29.0).abs() < 1e-9));
+        assert!(matches!(shifted[1], RegistrationIssueKind::CenterOffset { .. }));
+    }
+
+    #[test]
+    fn test_majority_prefers_first_on_tie() {
+        assert_eq!(majority(&[GerberUnits::Inch, GerberUnits::Millimeters]), Some(GerberUnits::Inch));
+        assert_eq!(majority(&[(4, 6), (2, 5), (4, 6)]), Some((4, 6)));
+        assert_eq!(majority::<u8>(&[]), None);
+    }
+}
@@ -0,0 +1,611 @@
+//! Tessellated layer meshes reused between frames
+//!
+//! `GerberRenderer::paint_layer` rebuilds and re-tessellates every primitive on
+//! each call, which dominates the frame time on boards with many primitives.
+//! The renderer's output for a layer is captured once, tessellated into meshes
+//! and repainted from the cache until the layer's geometry, color, zoom,
+//! rotation or mirroring change. A pure pan shifts the cached meshes instead of
+//! rebuilding them. While the zoom keeps changing the meshes are scaled about
+//! the layer origin instead (up to `MAX_RESCALE` away from the zoom they were
+//! built at), and rebuilt at full quality in the first frame the zoom holds
+//! still. Entries of layers not drawn in a frame (hidden or removed) are dropped
+//! at the end of it.
+//!
+//! Far enough out, layers are drawn at a lower level of detail (see `lod`) and
+//! the simplified mesh is cached the same way. Layers with clear primitives or
+//! negative polarity are composited into an image (see `polarity`) that covers
+//! the viewport with some margin; it is rebuilt once panning or zooming shows
+//! part of the layer outside it. Panel copies (see `panel`) are cached as
+//! shifted meshes next to the layer's own and moved along with them.
+//!
+//! Cached meshes are shared with the painter behind an `Arc`, so a cache hit
+//! hands out references instead of copying vertex buffers every frame.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+use egui::epaint::{ClippedShape, Mesh, Primitive};
+use egui::{Color32, Painter, Pos2, Rect, Vec2};
+use gerber_viewer::{GerberLayer, GerberRenderer, GerberTransform, RenderConfiguration, ViewState};
+use nalgebra::{Point2, Vector2};
+
+use super::lod::{build_lod_mesh, typical_primitive_size, LodSettings};
+use super::polarity::{paint_composited_layer, CompositeLayer, CompositedImage};
+use super::primitives::TaggedPrimitive;
+
+/// Weight of the newest frame in the smoothed render time
+const FRAME_TIME_SMOOTHING: f32 = 0.1;
+
+/// Largest zoom factor, either way, a mesh is scaled by before it is rebuilt mid-zoom
+const MAX_RESCALE: f32 = 2.0;
+
+/// Share of the viewport size a composited image extends past it on each side, so short pans reuse it
+const COMPOSITE_MARGIN: f32 = 0.25;
+
+/// Screen position of the layer origin and the screen vectors of its unit axes
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScreenFrame {
+    origin: Pos2,
+    x_axis: Vec2,
+    y_axis: Vec2,
+}
+
+impl ScreenFrame {
+    fn new(view_state: &ViewState, transform: &GerberTransform) -> Self {
+        let matrix = transform.to_matrix();
+        let screen = |x: f64, y: f64| view_state.gerber_to_screen_coords(matrix.transform_point(&Point2::new(x, y)));
+        let origin = screen(0.0, 0.0);
+        Self { origin, x_axis: screen(1.0, 0.0) - origin, y_axis: screen(0.0, 1.0) - origin }
+    }
+
+    /// Same zoom, rotation and mirroring; panning only moves the origin
+    /// The tolerance absorbs f32 rounding of the origin subtraction while panning
+    fn same_shape(&self, other: &ScreenFrame) -> bool {
+        let close = |a: Vec2, b: Vec2| (a - b).length() <= 1e-4 * a.length().max(1e-3);
+        close(self.x_axis, other.x_axis) && close(self.y_axis, other.y_axis)
+    }
+
+    /// Factor this frame is zoomed by relative to `other`, when it differs only by zoom and pan
+    fn zoom_from(&self, other: &ScreenFrame) -> Option<f32> {
+        let factor = self.x_axis.length() / other.x_axis.length();
+        let scaled = ScreenFrame { origin: self.origin, x_axis: other.x_axis * factor, y_axis: other.y_axis * factor };
+        (factor.is_finite() && factor > 0.0 && self.same_shape(&scaled)).then_some(factor)
+    }
+}
+
+struct CachedLayer {
+    content_hash: u64,
+    color: Color32,
+    pixels_per_point: f32,
+    /// Frame the meshes were tessellated for
+    built_frame: ScreenFrame,
+    /// Frame the meshes are currently placed for, i.e. the last one drawn
+    frame: ScreenFrame,
+    /// Drawn at the lower level of detail
+    lod: bool,
+    /// Image area a composited layer was painted with
+    composite_key: u64,
+    meshes: Vec<Arc<Mesh>>,
+    /// Screen bounds of `meshes`, placed like them
+    bounds: Rect,
+    /// Layer offsets of the panel copies `copy_meshes` were shifted to
+    copy_offsets: Vec<Vector2<f64>>,
+    /// `meshes` shifted to each panel copy, placed like them
+    copy_meshes: Vec<Vec<Arc<Mesh>>>,
+    /// Image of a composited layer, placed like the meshes
+    composited: Option<CompositedImage>,
+}
+
+impl CachedLayer {
+    /// Meshes of the layer and its copies, for moving them in place
+    /// Unshared by the time the next frame is drawn, so `make_mut` does not copy
+    fn meshes_mut(&mut self) -> impl Iterator<Item = &mut Mesh> {
+        self.meshes.iter_mut()
+            .chain(self.copy_meshes.iter_mut().flatten())
+            .map(Arc::make_mut)
+    }
+
+    /// Factor the meshes are scaled by to be drawn in `frame`, 1.0 at the zoom they were built at
+    /// None when they must be rebuilt: the zoom settled, left the `MAX_RESCALE` range, or stopped being a pure zoom
+    fn rescale_to(&self, frame: &ScreenFrame) -> Option<f32> {
+        if self.built_frame.same_shape(frame) {
+            Some(1.0)
+        } else if self.frame.same_shape(frame) {
+            // The zoom held still since the last frame: rebuild at the new zoom
+            None
+        } else {
+            frame.zoom_from(&self.built_frame)
+                .filter(|total| (1.0 / MAX_RESCALE..=MAX_RESCALE).contains(total))
+                .and_then(|_| frame.zoom_from(&self.frame))
+        }
+    }
+
+    /// Move everything drawn by `delta` on screen (a pan)
+    fn translate(&mut self, delta: Vec2) {
+        for mesh in self.meshes_mut() {
+            mesh.translate(delta);
+        }
+        self.bounds = self.bounds.translate(delta);
+        if let Some(image) = &mut self.composited {
+            image.rect = image.rect.translate(delta);
+            image.layer_rect = image.layer_rect.translate(delta);
+        }
+        self.frame.origin += delta;
+    }
+
+    /// Scale everything drawn about the layer origin (a zoom)
+    fn scale(&mut self, factor: f32) {
+        let origin = self.frame.origin;
+        for mesh in self.meshes_mut() {
+            for vertex in &mut mesh.vertices {
+                vertex.pos = origin + (vertex.pos - origin) * factor;
+            }
+        }
+        self.bounds = Rect::from_min_max(origin + (self.bounds.min - origin) * factor, origin + (self.bounds.max - origin) * factor);
+        if let Some(image) = &mut self.composited {
+            image.scale_about(origin, factor);
+        }
+    }
+
+    /// Rebuild the copy meshes when the panel copies changed
+    fn place_copies(&mut self, copies: &[Vector2<f64>]) {
+        if self.copy_offsets == copies {
+            return;
+        }
+        let frame = self.frame;
+        self.copy_meshes = copies.iter()
+            .map(|offset| {
+                let delta = copy_delta(&frame, *offset);
+                self.meshes.iter()
+                    .map(|mesh| {
+                        let mut copy = Mesh::clone(mesh);
+                        copy.translate(delta);
+                        Arc::new(copy)
+                    })
+                    .collect()
+            })
+            .collect();
+        self.copy_offsets = copies.to_vec();
+    }
+
+    /// Hand the meshes to the painter, skipping copies off screen; returns the number painted
+    fn paint(&self, painter: &Painter) -> usize {
+        let clip = painter.clip_rect();
+        let mut emitted = 0;
+        let groups = std::iter::once((Vec2::ZERO, &self.meshes))
+            .chain(self.copy_offsets.iter().map(|offset| copy_delta(&self.frame, *offset)).zip(&self.copy_meshes));
+        for (delta, meshes) in groups {
+            if delta != Vec2::ZERO && !self.bounds.translate(delta).intersects(clip) {
+                continue;
+            }
+            for mesh in meshes {
+                painter.add(egui::Shape::mesh(Arc::clone(mesh)));
+            }
+            emitted += meshes.len();
+        }
+        emitted
+    }
+}
+
+/// Screen shift of the panel copy at `offset` (layer coordinates)
+fn copy_delta(frame: &ScreenFrame, offset: Vector2<f64>) -> Vec2 {
+    frame.x_axis * offset.x as f32 + frame.y_axis * offset.y as f32
+}
+
+/// Render timings, shown in the view settings and the render stats overlay
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderStats {
+    /// Smoothed time spent painting the layers, in ms
+    pub render_ms: f32,
+    pub layers_drawn: usize,
+    /// Layers painted from the cache this frame
+    pub cache_hits: usize,
+    /// Cache hits whose meshes were scaled to a changing zoom
+    pub rescaled: usize,
+    /// Meshes handed to the painter this frame, panel copies included
+    /// Layers painted directly are not counted
+    pub meshes_emitted: usize,
+    /// Layers drawn at the lower level of detail this frame
+    pub lod_layers: usize,
+}
+
+/// What a layer is painted from
+pub struct LayerSource<'a> {
+    pub layer: &'a GerberLayer,
+    /// Extracted primitives, used for the lower level of detail
+    pub primitives: Option<&'a [TaggedPrimitive]>,
+    /// Set when the layer must be painted from its primitives to erase clear areas
+    pub composite: Option<CompositeLayer<'a>>,
+}
+
+impl LayerSource<'_> {
+    /// Paint the layer; a composited one covers at least `visible` and returns its image
+    #[allow(clippy::too_many_arguments)]
+    fn paint(
+        &self,
+        painter: &Painter,
+        renderer: &GerberRenderer,
+        view_state: ViewState,
+        color: Color32,
+        config: &RenderConfiguration,
+        transform: &GerberTransform,
+        visible: Rect,
+    ) -> Option<CompositedImage> {
+        match &self.composite {
+            Some(composite) => paint_composited_layer(painter, &view_state, composite, color, transform, visible),
+            None => {
+                renderer.paint_layer(painter, view_state, self.layer, color, config, transform);
+                None
+            }
+        }
+    }
+
+    /// Changes whenever a composited layer would paint differently for the same geometry
+    fn composite_key(&self) -> u64 {
+        let Some(composite) = &self.composite else {
+            return 0;
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for contour in composite.image_area.unwrap_or_default() {
+            contour.len().hash(&mut hasher);
+            for point in contour {
+                point.x.to_bits().hash(&mut hasher);
+                point.y.to_bits().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+}
+
+/// Captured layer meshes, by layer entity
+#[derive(Resource)]
+pub struct LayerRenderCache {
+    /// Off paints every layer directly, for comparing frame times
+    pub enabled: bool,
+    pub lod: LodSettings,
+    pub stats: RenderStats,
+    /// Draw `stats` and the frame time over the gerber view
+    pub show_overlay: bool,
+    layers: HashMap<Entity, CachedLayer>,
+    /// Typical primitive size per layer, with the content hash it was measured for
+    detail_sizes: HashMap<Entity, (u64, Option<f64>)>,
+    drawn: HashSet<Entity>,
+}
+
+impl Default for LayerRenderCache {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lod: LodSettings::default(),
+            stats: RenderStats::default(),
+            show_overlay: false,
+            layers: HashMap::new(),
+            detail_sizes: HashMap::new(),
+            drawn: HashSet::new(),
+        }
+    }
+}
+
+impl LayerRenderCache {
+    pub fn begin_frame(&mut self) {
+        self.drawn.clear();
+        self.stats.layers_drawn = 0;
+        self.stats.cache_hits = 0;
+        self.stats.rescaled = 0;
+        self.stats.meshes_emitted = 0;
+        self.stats.lod_layers = 0;
+    }
+
+    /// Drop entries of layers not drawn this frame and record the render time
+    pub fn end_frame(&mut self, elapsed: Duration) {
+        let drawn = &self.drawn;
+        self.layers.retain(|entity, _| drawn.contains(entity));
+        self.detail_sizes.retain(|entity, _| drawn.contains(entity));
+        let ms = elapsed.as_secs_f32() * 1000.0;
+        self.stats.render_ms = if self.stats.render_ms == 0.0 {
+            ms
+        } else {
+            self.stats.render_ms + (ms - self.stats.render_ms) * FRAME_TIME_SMOOTHING
+        };
+    }
+
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Whether the layer is small enough on screen to be drawn at the lower level of detail
+    fn use_lod(&mut self, entity: Entity, content_hash: u64, primitives: &[TaggedPrimitive], frame: &ScreenFrame) -> bool {
+        if !self.lod.enabled {
+            return false;
+        }
+        let size = match self.detail_sizes.get(&entity) {
+            Some((hash, size)) if *hash == content_hash => *size,
+            _ => {
+                let size = typical_primitive_size(primitives);
+                self.detail_sizes.insert(entity, (content_hash, size));
+                size
+            }
+        };
+        let px_per_mm = frame.x_axis.length().max(frame.y_axis.length());
+        self.lod.applies(size, px_per_mm)
+    }
+
+    /// Paint a layer like `GerberRenderer::paint_layer`, from the cache when it is still valid,
+    /// plus a copy shifted by each of `copies` (in layer coordinates)
+    /// Layers without a content hash are painted directly; layers with primitives may be simplified,
+    /// except composited ones, whose clear areas the simplified drawing cannot show
+    #[allow(clippy::too_many_arguments)]
+    pub fn paint_layer(
+        &mut self,
+        painter: &Painter,
+        renderer: &GerberRenderer,
+        view_state: ViewState,
+        entity: Entity,
+        content_hash: Option<u64>,
+        source: &LayerSource,
+        color: Color32,
+        config: &RenderConfiguration,
+        transform: &GerberTransform,
+        copies: &[Vector2<f64>],
+    ) {
+        self.stats.layers_drawn += 1;
+        let clip = painter.clip_rect();
+        let paint_directly = |painter: &Painter| {
+            source.paint(painter, renderer, view_state, color, config, transform, clip);
+            for offset in copies {
+                source.paint(painter, renderer, view_state, color, config, &super::panel::instance_transform(transform, *offset), clip);
+            }
+        };
+        let Some(content_hash) = content_hash else {
+            paint_directly(painter);
+            return;
+        };
+        self.drawn.insert(entity);
+
+        let frame = ScreenFrame::new(&view_state, transform);
+        let lod_primitives = source.primitives
+            .filter(|_| source.composite.is_none())
+            .filter(|primitives| self.use_lod(entity, content_hash, primitives, &frame));
+        let threshold_px = self.lod.threshold_px;
+        let lod_mesh = |primitives: &[TaggedPrimitive]| {
+            let (x_axis, y_axis) = (frame.x_axis, frame.y_axis);
+            let to_screen = move |point: Point2<f64>| x_axis * point.x as f32 + y_axis * point.y as f32;
+            build_lod_mesh(primitives, frame.origin, to_screen, threshold_px, color)
+        };
+
+        if !self.enabled {
+            match lod_primitives.and_then(lod_mesh) {
+                Some(mesh) => {
+                    self.stats.lod_layers += 1;
+                    self.stats.meshes_emitted += paint_uncached_mesh(painter, mesh, &frame, copies);
+                }
+                None => paint_directly(painter),
+            }
+            return;
+        }
+
+        let pixels_per_point = painter.ctx().pixels_per_point();
+        let lod = lod_primitives.is_some();
+        let composite_key = source.composite_key();
+        // Screen area a composited image must cover: the viewport, and where each copy takes its pixels from
+        let needed = copies.iter().fold(clip, |needed, offset| needed.union(clip.translate(-copy_delta(&frame, *offset))));
+        // A composited image, zoomed by `factor` and panned to this frame, still shows all of it that is needed
+        let covers = |cached: &CachedLayer, factor: f32| {
+            cached.composited.as_ref().is_none_or(|image| image.covers(needed, cached.frame.origin, factor, frame.origin - cached.frame.origin))
+        };
+        let reuse = self.layers.get(&entity).and_then(|cached| {
+            let same_content = cached.content_hash == content_hash
+                && cached.color == color
+                && cached.pixels_per_point == pixels_per_point
+                && cached.lod == lod
+                && cached.composite_key == composite_key;
+            if !same_content {
+                None
+            } else {
+                cached.rescale_to(&frame).filter(|factor| covers(cached, *factor))
+            }
+        });
+        match reuse {
+            Some(factor) => {
+                self.stats.cache_hits += 1;
+                if factor != 1.0 {
+                    self.stats.rescaled += 1;
+                    let cached = self.layers.get_mut(&entity).expect("reused layer is cached");
+                    cached.scale(factor);
+                    cached.frame = ScreenFrame { origin: cached.frame.origin, ..frame };
+                }
+            }
+            None => {
+                let visible = needed.expand2(clip.size() * COMPOSITE_MARGIN);
+                let (meshes, composited) = match lod_primitives.and_then(lod_mesh) {
+                    Some(mesh) => (vec![mesh], None),
+                    None => capture_layer(painter, |capture| source.paint(capture, renderer, view_state, color, config, transform, visible)),
+                };
+                let meshes: Vec<Arc<Mesh>> = meshes.into_iter().map(Arc::new).collect();
+                let bounds = meshes.iter().fold(Rect::NOTHING, |bounds, mesh| bounds.union(mesh.calc_bounds()));
+                self.layers.insert(entity, CachedLayer {
+                    content_hash,
+                    color,
+                    pixels_per_point,
+                    built_frame: frame,
+                    frame,
+                    lod,
+                    composite_key,
+                    meshes,
+                    bounds,
+                    copy_offsets: Vec::new(),
+                    copy_meshes: Vec::new(),
+                    composited,
+                });
+            }
+        }
+        if lod {
+            self.stats.lod_layers += 1;
+        }
+
+        let cached = self.layers.get_mut(&entity).expect("layer cached above");
+        let delta = frame.origin - cached.frame.origin;
+        if delta != Vec2::ZERO {
+            cached.translate(delta);
+        }
+        cached.place_copies(copies);
+        self.stats.meshes_emitted += cached.paint(painter);
+    }
+}
+
+/// Paint a mesh built for this frame only, and again shifted to each copy that is on screen; returns the number painted
+fn paint_uncached_mesh(painter: &Painter, mesh: Mesh, frame: &ScreenFrame, copies: &[Vector2<f64>]) -> usize {
+    let bounds = mesh.calc_bounds();
+    let clip = painter.clip_rect();
+    let mesh = Arc::new(mesh);
+    painter.add(egui::Shape::mesh(Arc::clone(&mesh)));
+    let mut emitted = 1;
+    for offset in copies {
+        let delta = copy_delta(frame, *offset);
+        if !bounds.translate(delta).intersects(clip) {
+            continue;
+        }
+        let mut copy = Mesh::clone(&mesh);
+        copy.translate(delta);
+        painter.add(egui::Shape::mesh(copy));
+        emitted += 1;
+    }
+    emitted
+}
+
+/// Paint the layer on an off-screen layer, take the shapes back and tessellate them
+/// Captured unclipped so panning can reveal parts that were outside the viewport
+fn capture_layer<R>(painter: &Painter, paint: impl FnOnce(&Painter) -> R) -> (Vec<Mesh>, R) {
+    let ctx = painter.ctx();
+    let layer_id = egui::LayerId::new(egui::Order::Background, egui::Id::new("layer_render_cache_capture"));
+    let capture = Painter::new(ctx.clone(), layer_id, Rect::EVERYTHING);
+    let painted = paint(&capture);
+
+    let shapes: Vec<ClippedShape> = ctx.graphics_mut(|graphics| {
+        std::mem::take(graphics.entry(layer_id)).all_entries().cloned().collect()
+    });
+    let meshes = ctx.tessellate(shapes, ctx.pixels_per_point())
+        .into_iter()
+        .filter_map(|primitive| match primitive.primitive {
+            Primitive::Mesh(mesh) if !mesh.is_empty() => Some(mesh),
+            _ => None,
+        })
+        .collect();
+    (meshes, painted)
+}
+
+/// Render statistics of the last frames
+pub fn get_render_stats(world: &World) -> RenderStats {
+    world.get_resource::<LayerRenderCache>().map(|cache| cache.stats).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pan_keeps_frame_shape_and_zoom_changes_it() {
+        let transform = GerberTransform {
+            rotation: 30f32.to_radians(),
+            mirroring: crate::display::manager::MirroringSettings { x: true, y: false }.into(),
+            origin: nalgebra::Vector2::new(12.5, -3.25),
+            offset: nalgebra::Vector2::new(-40.0, 7.5),
+            scale: 1.0,
+        };
+        let view = ViewState { scale: 4.0, base_scale: 4.0, translation: Vec2::new(100.0, 200.0) };
+        let frame = ScreenFrame::new(&view, &transform);
+
+        let panned = ScreenFrame::new(&ViewState { translation: Vec2::new(-3512.25, 977.5), ..view }, &transform);
+        assert!(frame.same_shape(&panned));
+        assert!((panned.origin - frame.origin - Vec2::new(-3612.25, 777.5)).length() < 1e-3);
+
+        let zoomed = ScreenFrame::new(&ViewState { scale: 4.1, ..view }, &transform);
+        assert!(!frame.same_shape(&zoomed));
+        assert!((zoomed.zoom_from(&frame).unwrap() - 4.1 / 4.0).abs() < 1e-4);
+
+        // A rotation is not a zoom
+        let rotated = ScreenFrame::new(&view, &GerberTransform { rotation: 45f32.to_radians(), ..transform });
+        assert_eq!(rotated.zoom_from(&frame), None);
+    }
+
+    /// Layer with one two-vertex mesh, built for `frame`
+    fn cached_layer(frame: ScreenFrame) -> CachedLayer {
+        let mut mesh = Mesh::default();
+        mesh.colored_vertex(Pos2::new(100.0, 100.0), Color32::WHITE);
+        mesh.colored_vertex(Pos2::new(110.0, 90.0), Color32::WHITE);
+        let meshes = vec![Arc::new(mesh)];
+        let bounds = meshes[0].calc_bounds();
+        CachedLayer {
+            content_hash: 0,
+            color: Color32::WHITE,
+            pixels_per_point: 1.0,
+            built_frame: frame,
+            frame,
+            lod: false,
+            composite_key: 0,
+            meshes,
+            bounds,
+            copy_offsets: Vec::new(),
+            copy_meshes: Vec::new(),
+            composited: None,
+        }
+    }
+
+    /// `frame` zoomed by `factor` about its origin
+    fn zoomed(frame: ScreenFrame, factor: f32) -> ScreenFrame {
+        ScreenFrame { x_axis: frame.x_axis * factor, y_axis: frame.y_axis * factor, ..frame }
+    }
+
+    #[test]
+    fn test_zooming_rescales_cached_meshes_until_it_settles_or_goes_too_far() {
+        let built = ScreenFrame { origin: Pos2::new(100.0, 100.0), x_axis: Vec2::new(2.0, 0.0), y_axis: Vec2::new(0.0, -2.0) };
+        let mut cached = cached_layer(built);
+        assert_eq!(cached.rescale_to(&built), Some(1.0));
+
+        // Zooming in over two frames to 1.5x is a cache hit each time, scaling the meshes along
+        for total in [1.2, 1.5] {
+            let frame = zoomed(built, total);
+            let factor = cached.rescale_to(&frame).expect("within MAX_RESCALE of the built zoom");
+            cached.scale(factor);
+            cached.frame = frame;
+        }
+        let vertex = cached.meshes[0].vertices[1].pos;
+        assert!((vertex - Pos2::new(115.0, 85.0)).length() < 1e-3, "{vertex:?}");
+
+        // Holding the zoom still for a frame rebuilds at full quality
+        assert_eq!(cached.rescale_to(&zoomed(built, 1.5)), None);
+
+        // 3x is past MAX_RESCALE from the built zoom, in either direction
+        assert_eq!(cached.rescale_to(&zoomed(built, 3.0)), None);
+        assert_eq!(cached.rescale_to(&zoomed(built, 1.0 / 3.0)), None);
+        assert!(cached.rescale_to(&zoomed(built, 0.6)).is_some());
+    }
+
+    #[test]
+    fn test_cached_meshes_and_copies_follow_pan_and_zoom() {
+        let frame = ScreenFrame { origin: Pos2::new(100.0, 100.0), x_axis: Vec2::new(2.0, 0.0), y_axis: Vec2::new(0.0, -2.0) };
+        let mut cached = cached_layer(frame);
+        let copies = [Vector2::new(30.0, 0.0)];
+        cached.place_copies(&copies);
+        let vertex = |meshes: &[Arc<Mesh>], index: usize| meshes[0].vertices[index].pos;
+        assert_eq!(vertex(&cached.copy_meshes[0], 1), Pos2::new(170.0, 90.0));
+
+        // Meshes no longer held by the painter are moved in place
+        let before = Arc::as_ptr(&cached.meshes[0]);
+        cached.translate(Vec2::new(-5.0, 5.0));
+        cached.scale(2.0);
+        cached.frame = ScreenFrame { origin: cached.frame.origin, x_axis: frame.x_axis * 2.0, y_axis: frame.y_axis * 2.0 };
+        assert_eq!(Arc::as_ptr(&cached.meshes[0]), before);
+        assert_eq!(vertex(&cached.meshes, 1), Pos2::new(115.0, 85.0));
+
+        // The copy stays where a freshly shifted copy would be
+        let expected = vertex(&cached.meshes, 1) + copy_delta(&cached.frame, copies[0]);
+        assert_eq!(vertex(&cached.copy_meshes[0], 1), expected);
+        cached.place_copies(&copies);
+        assert_eq!(vertex(&cached.copy_meshes[0], 1), expected);
+        assert_eq!(cached.bounds, Rect::from_min_max(Pos2::new(95.0, 85.0), Pos2::new(115.0, 105.0)));
+    }
+}
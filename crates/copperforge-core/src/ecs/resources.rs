@@ -158,4 +158,50 @@ impl Default for CoordinateUpdateTracker {
             last_updated: std::time::Instant::now(),
         }
     }
-}
\ No newline at end of file
+}
+// Board build-up used by the 3D view (mm); the defaults stand in when it is absent
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct Stackup {
+    pub board_thickness: f64,
+    pub copper_thickness: f64,
+    pub soldermask_thickness: f64,
+    pub silkscreen_thickness: f64,
+    pub paste_thickness: f64,
+}
+
+impl Default for Stackup {
+    fn default() -> Self {
+        Self {
+            board_thickness: 1.6,
+            copper_thickness: 0.035,     // 1oz
+            soldermask_thickness: 0.02,
+            silkscreen_thickness: 0.015,
+            paste_thickness: 0.12,       // typical 5 mil stencil
+        }
+    }
+}
+
+impl Stackup {
+    /// Height of a layer's outer surface above the bottom of the board core
+    /// Inner copper is spread evenly through the core
+    pub fn layer_z(&self, layer_type: LayerType, inner_layers: u8) -> f64 {
+        let top = self.board_thickness;
+        let copper = self.copper_thickness;
+        let mask = copper + self.soldermask_thickness;
+        match layer_type {
+            LayerType::Copper(1) => top + copper,
+            LayerType::Copper(2) => -copper,
+            LayerType::Copper(_) => {
+                let index = layer_type.inner_copper_index().unwrap_or(1) as f64;
+                top * (1.0 - index / (inner_layers.max(1) as f64 + 1.0))
+            }
+            LayerType::Soldermask(super::Side::Top) => top + mask,
+            LayerType::Soldermask(super::Side::Bottom) => -mask,
+            LayerType::Silkscreen(super::Side::Top) => top + mask + self.silkscreen_thickness,
+            LayerType::Silkscreen(super::Side::Bottom) => -(mask + self.silkscreen_thickness),
+            LayerType::Paste(super::Side::Top) => top + copper + self.paste_thickness,
+            LayerType::Paste(super::Side::Bottom) => -(copper + self.paste_thickness),
+            LayerType::MechanicalOutline => top,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Object snapping for the ruler and origin tools
+//!
+//! Snap targets are exact geometric points: draw endpoints and region vertices,
+//! flash (pad) centers and drill hole centers. Distances are compared on screen
+//! so the snap radius stays the same at every zoom level.
+
+use bevy_ecs::prelude::*;
+use gerber_viewer::ViewState;
+use nalgebra::Point2;
+
+use super::components::{LayerInfo, Visibility};
+use super::primitives::{LayerPrimitives, PrimitiveShape, TaggedPrimitive};
+use super::LayerType;
+use crate::display::DisplayManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapKind {
+    /// End of a draw or corner of a region
+    Endpoint,
+    /// Center of a flashed pad or drill hole
+    Center,
+}
+
+/// A snap target, in view coordinates (as reported by `screen_to_gerber_coords`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapPoint {
+    pub position: Point2<f64>,
+    pub kind: SnapKind,
+}
+
+/// Snap targets of a layer's primitives, in source gerber coordinates
+pub fn primitive_snap_points(primitives: &[TaggedPrimitive]) -> Vec<SnapPoint> {
+    let mut points = Vec::new();
+    for primitive in primitives {
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, .. } => {
+                points.push(SnapPoint { position: *start, kind: SnapKind::Endpoint });
+                points.push(SnapPoint { position: *end, kind: SnapKind::Endpoint });
+            }
+            PrimitiveShape::Flash { center, .. } => {
+                points.push(SnapPoint { position: *center, kind: SnapKind::Center });
+            }
+            PrimitiveShape::Region { points: vertices } => {
+                points.extend(vertices.iter().map(|vertex| SnapPoint { position: *vertex, kind: SnapKind::Endpoint }));
+            }
+        }
+    }
+    points
+}
+
+/// Closest candidate within `max_distance` of `target`; centers win ties with endpoints
+pub fn nearest_snap_point(
+    candidates: impl IntoIterator<Item = SnapPoint>,
+    target: Point2<f64>,
+    max_distance: f64,
+) -> Option<SnapPoint> {
+    candidates.into_iter()
+        .map(|candidate| ((candidate.position - target).norm(), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|(a, point_a), (b, point_b)| {
+            a.total_cmp(b).then_with(|| (point_a.kind == SnapKind::Endpoint).cmp(&(point_b.kind == SnapKind::Endpoint)))
+        })
+        .map(|(_, candidate)| candidate)
+}
+
+/// Nearest snap target on the visible layers (and drill holes) within `tolerance_px` of a screen position
+pub fn pick_snap_point_at_screen_pos(
+    world: &mut World,
+    view_state: &ViewState,
+    display_manager: &DisplayManager,
+    screen_pos: egui::Pos2,
+    tolerance_px: f32,
+) -> Option<SnapPoint> {
+    let target = view_state.screen_to_gerber_coords(screen_pos);
+    let max_distance = (tolerance_px / view_state.scale) as f64;
+
+    let mut query = world.query::<(&LayerInfo, &Visibility, &LayerPrimitives)>();
+    let layers: Vec<(LayerType, Vec<SnapPoint>)> = query.iter(world)
+        .filter(|(_, visibility, _)| visibility.visible)
+        .filter(|(layer_info, _, _)| layer_info.layer_type.should_render(display_manager.showing_top)
+            || layer_info.layer_type == LayerType::MechanicalOutline)
+        .map(|(layer_info, _, primitives)| (layer_info.layer_type, primitive_snap_points(&primitives.0)))
+        .collect();
+
+    let mut candidates = Vec::new();
+    for (layer_type, points) in layers {
+        let Some(matrix) = super::get_layer_render_transform(world, display_manager, layer_type)
+            .map(|transform| transform.to_matrix())
+        else {
+            continue;
+        };
+        candidates.extend(points.into_iter().map(|point| SnapPoint {
+            position: matrix.transform_point(&point.position),
+            kind: point.kind,
+        }));
+    }
+
+    // Drill holes are in board coordinates, shared by every layer
+    if let Some(matrix) = super::get_board_view_transform(world, display_manager).map(|transform| transform.to_matrix()) {
+        candidates.extend(super::get_drill_holes(world).into_iter().map(|hole| SnapPoint {
+            position: matrix.transform_point(&hole.position),
+            kind: SnapKind::Center,
+        }));
+    }
+
+    nearest_snap_point(candidates, target, max_distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{ApertureShape, Polarity};
+
+    fn primitive(shape: PrimitiveShape) -> TaggedPrimitive {
+        TaggedPrimitive { net: None, polarity: Polarity::Dark, shape }
+    }
+
+    #[test]
+    fn test_nearest_snap_point_prefers_closest_then_center() {
+        let primitives = vec![
+            primitive(PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(5.0, 0.0), width: 0.2 }),
+            primitive(PrimitiveShape::Flash {
+                center: Point2::new(5.0, 0.0),
+                width: 1.0,
+                height: 1.0,
+                aperture: ApertureShape::Circle,
+                outline: None,
+            }),
+        ];
+        let points = primitive_snap_points(&primitives);
+        assert_eq!(points.len(), 3);
+
+        // Trace end and pad center coincide: the center is reported
+        let snapped = nearest_snap_point(points.clone(), Point2::new(4.9, 0.1), 0.5).unwrap();
+        assert_eq!(snapped, SnapPoint { position: Point2::new(5.0, 0.0), kind: SnapKind::Center });
+
+        let snapped = nearest_snap_point(points.clone(), Point2::new(0.2, -0.1), 0.5).unwrap();
+        assert_eq!(snapped.kind, SnapKind::Endpoint);
+        assert_eq!(snapped.position, Point2::new(0.0, 0.0));
+
+        // Nothing within range
+        assert!(nearest_snap_point(points, Point2::new(2.5, 0.0), 0.5).is_none());
+    }
+}
@@ -5,6 +5,11 @@ use gerber_viewer::{GerberRenderer, RenderConfiguration, GerberTransform, ViewSt
 use egui::Painter;
 use crate::display::DisplayManager;
 use super::{LayerType, UnassignedGerber};
+use super::render_cache::{LayerRenderCache, LayerSource};
+use super::polarity::{negative_image_area, CompositeLayer, LayerPolarity};
+use super::paste_adjust::{paint_original_paste_outlines, update_paste_preview, AdjustedPaste, PasteAdjustSettings};
+use super::primitives::LayerPrimitives;
+use super::panel::{get_panel_layout, paint_panel_rails};
 
 /// ECS-based rendering system for gerber layers
 /// This system queries all layer entities and renders them using gerber-viewer
@@ -13,19 +18,27 @@ pub fn render_layers_system(
     painter: &Painter,
     view_state: ViewState,
     display_manager: &DisplayManager,
+    highlighted_net: Option<u32>,
+    cache: &mut LayerRenderCache,
 ) {
     let config = RenderConfiguration::default();
     let renderer = GerberRenderer::default();
+    let image_area = negative_image_area(world);
+    let background = painter.ctx().style().visuals.extreme_bg_color;
+    let panel = paint_panel_rails_system(world, painter, &view_state, display_manager);
+    let copies = panel.as_ref().map_or(&[][..], |panel| panel.copies());
+    let knockouts = if display_manager.knocks_out_drill_holes() { super::get_drill_holes(world) } else { Vec::new() };
     
     // Query all layer entities including ImageTransform
-    let mut layer_query = world.query::<(&GerberData, &Transform, &ImageTransform, &Visibility, &RenderProperties, &LayerInfo)>();
+    let paste_preview = world.get_resource::<PasteAdjustSettings>().is_some_and(|settings| settings.enabled);
+    let mut layer_query = world.query::<(Entity, &GerberData, &Transform, &ImageTransform, &Visibility, &RenderProperties, &LayerInfo, Option<&NetGeometry>, Option<&FlashPrimitives>, Option<&LayerContentHash>, Option<&LayerPrimitives>, Option<&LayerPolarity>, Option<&AdjustedPaste>)>();
     let mut layers: Vec<_> = layer_query.iter(world).collect();
     
     // Sort layers by z-order for proper rendering depth
-    layers.sort_by_key(|(_, _, _, _, props, _)| props.z_order);
+    layers.sort_by_key(|(_, _, _, _, _, props, _, _, _, _, _, _, _)| props.z_order);
     
     // Render each visible layer
-    for (gerber_data, transform, image_transform, visibility, render_props, _layer_info) in layers {
+    for (entity, gerber_data, transform, image_transform, visibility, render_props, layer_info, net_geometry, flash_primitives, content_hash, primitives, polarity, adjusted_paste) in layers {
         if !visibility.visible {
             continue;
         }
@@ -34,20 +47,88 @@ pub fn render_layers_system(
         // This allows manual layer control overrides regardless of top/bottom view
         
         // Create GerberTransform from ECS Transform and ImageTransform
-        let gerber_transform = create_gerber_transform_composed(transform, image_transform, display_manager);
+        let gerber_transform = create_gerber_transform_composed(transform, image_transform);
+        
+        // Paste layers show their adjusted apertures over the original outlines while the preview is on
+        let adjusted_paste = adjusted_paste.filter(|_| paste_preview);
+        if let (Some(_), Some(primitives)) = (adjusted_paste, primitives) {
+            paint_original_paste_outlines(painter, &view_state, &primitives.0, &gerber_transform, render_props.color);
+        }
         
         // Render the layer
-        renderer.paint_layer(
+        cache.paint_layer(
             painter,
+            &renderer,
             view_state,
-            &gerber_data.0,
+            entity,
+            adjusted_paste.map(|adjusted| adjusted.key).or(content_hash.map(|hash| hash.0)),
+            &layer_source(&gerber_data.0, primitives, polarity, adjusted_paste, image_area.as_deref()),
             render_props.color,
             &config,
             &gerber_transform,
+            copies,
         );
+        
+        // Holes go through copper and soldermask
+        if layer_info.layer_type.is_drilled() {
+            paint_drill_knockouts(painter, &view_state, &knockouts, &gerber_transform, copies, background);
+        }
+        
+        // Tint the highlighted net on top of the layer
+        if let (Some(net_id), Some(net_geometry)) = (highlighted_net, net_geometry) {
+            let color = render_props.highlight_color.unwrap_or(DEFAULT_NET_HIGHLIGHT_COLOR);
+            paint_net_highlight(painter, &view_state, net_geometry, net_id, &gerber_transform, color);
+        }
+        
+        // Outline flashed pads/vias
+        if display_manager.highlight_flashes && matches!(layer_info.layer_type, LayerType::Copper(_)) {
+            if let Some(flash_primitives) = flash_primitives {
+                paint_flash_highlight(painter, &view_state, flash_primitives, &gerber_transform, DEFAULT_FLASH_HIGHLIGHT_COLOR);
+            }
+        }
     }
 }
 
+/// Paint source of a layer; layers with clear primitives or negative polarity are composited,
+/// as are adjusted paste apertures
+fn layer_source<'a>(
+    layer: &'a gerber_viewer::GerberLayer,
+    primitives: Option<&'a LayerPrimitives>,
+    polarity: Option<&LayerPolarity>,
+    adjusted_paste: Option<&'a AdjustedPaste>,
+    image_area: Option<&'a [Vec<nalgebra::Point2<f64>>]>,
+) -> LayerSource<'a> {
+    if let Some(adjusted) = adjusted_paste {
+        let polarity = polarity.copied().unwrap_or_default();
+        let composite = CompositeLayer { primitives: &adjusted.primitives, polarity, image_area };
+        return LayerSource { layer, primitives: None, composite: Some(composite) };
+    }
+    let primitives = primitives.map(|primitives| primitives.0.as_slice());
+    let composite = primitives.zip(polarity.filter(|polarity| polarity.needs_compositing())).map(|(primitives, polarity)| {
+        CompositeLayer { primitives, polarity: *polarity, image_area }
+    });
+    LayerSource { layer, primitives, composite }
+}
+
+/// Paint the rails of the enabled panel, in the outline's frame (else the first layer's)
+/// Returns the layout so the layers can be drawn at every copy
+fn paint_panel_rails_system(
+    world: &mut World,
+    painter: &Painter,
+    view_state: &ViewState,
+    display_manager: &DisplayManager,
+) -> Option<super::PanelLayout> {
+    let panel = get_panel_layout(world)?;
+    let rails_transform = get_layer_render_transform(world, display_manager, LayerType::MechanicalOutline).or_else(|| {
+        let mut query = world.query::<(&Transform, &ImageTransform)>();
+        query.iter(world).next().map(|(transform, image_transform)| create_gerber_transform_composed(transform, image_transform))
+    });
+    if let Some(rails_transform) = rails_transform {
+        paint_panel_rails(painter, view_state, &panel, &rails_transform);
+    }
+    Some(panel)
+}
+
 /// Enhanced ECS-based rendering system with quadrant support
 /// This system supports quadrant view mode and proper layer positioning
 pub fn render_layers_system_enhanced(
@@ -55,9 +136,13 @@ pub fn render_layers_system_enhanced(
     painter: &Painter,
     view_state: ViewState,
     display_manager: &DisplayManager,
+    highlighted_net: Option<u32>,
+    cache: &mut LayerRenderCache,
 ) {
     let config = RenderConfiguration::default();
     let renderer = GerberRenderer::default();
+    let image_area = negative_image_area(world);
+    let background = painter.ctx().style().visuals.extreme_bg_color;
     
     // Get mechanical outline for quadrant view (do this first to avoid borrow issues)
     let mechanical_outline = if display_manager.quadrant_view_enabled {
@@ -66,15 +151,25 @@ pub fn render_layers_system_enhanced(
         None
     };
     
+    // Panel copies are shown outside quadrant view only
+    let panel = if display_manager.quadrant_view_enabled {
+        None
+    } else {
+        paint_panel_rails_system(world, painter, &view_state, display_manager)
+    };
+    let copies = panel.as_ref().map_or(&[][..], |panel| panel.copies());
+    let knockouts = if display_manager.knocks_out_drill_holes() { super::get_drill_holes(world) } else { Vec::new() };
+    
     // Query all layer entities including ImageTransform
-    let mut layer_query = world.query::<(&GerberData, &Transform, &ImageTransform, &Visibility, &RenderProperties, &LayerInfo)>();
+    let paste_preview = world.get_resource::<PasteAdjustSettings>().is_some_and(|settings| settings.enabled);
+    let mut layer_query = world.query::<(Entity, &GerberData, &Transform, &ImageTransform, &Visibility, &RenderProperties, &LayerInfo, Option<&NetGeometry>, Option<&FlashPrimitives>, Option<&LayerContentHash>, Option<&LayerPrimitives>, Option<&LayerPolarity>, Option<&AdjustedPaste>)>();
     let mut layers: Vec<_> = layer_query.iter(world).collect();
     
     // Sort layers by z-order for proper rendering depth
-    layers.sort_by_key(|(_, _, _, _, props, _)| props.z_order);
+    layers.sort_by_key(|(_, _, _, _, _, props, _, _, _, _, _, _, _)| props.z_order);
     
     // Render each visible layer
-    for (gerber_data, transform, image_transform, visibility, render_props, layer_info) in layers {
+    for (entity, gerber_data, transform, image_transform, visibility, render_props, layer_info, net_geometry, flash_primitives, content_hash, primitives, polarity, adjusted_paste) in layers {
         if !visibility.visible {
             continue;
         }
@@ -82,102 +177,225 @@ pub fn render_layers_system_enhanced(
         // Note: We rely solely on visibility.visible to determine if a layer should be shown
         // This allows manual layer control overrides regardless of top/bottom view
         
-        // Skip mechanical outline in quadrant view (it will be rendered with each layer)
-        if display_manager.quadrant_view_enabled && layer_info.layer_type == LayerType::MechanicalOutline {
-            continue;
-        }
-        
-        // Skip paste layers in quadrant view (user doesn't want to see them)
-        if display_manager.quadrant_view_enabled && matches!(layer_info.layer_type, LayerType::Paste(_)) {
+        // Skip layers without a place in quadrant view (the outline is rendered with each layer)
+        if display_manager.quadrant_view_enabled && !display_manager.quadrant_shows(layer_info.layer_type) {
             continue;
         }
         
-        // Calculate quadrant offset if needed
-        let quadrant_offset = if display_manager.quadrant_view_enabled {
-            display_manager.get_quadrant_offset(&layer_info.layer_type)
-        } else {
-            crate::display::VectorOffset { x: 0.0, y: 0.0 }
-        };
+        // The stored transform includes the quadrant offset of the layer
+        let gerber_transform = create_gerber_transform_composed(transform, image_transform);
         
-        // Create GerberTransform with quadrant offset and image transform
-        let gerber_transform = create_gerber_transform_with_offset_composed(transform, image_transform, display_manager, quadrant_offset.clone());
+        // Paste layers show their adjusted apertures over the original outlines while the preview is on
+        let adjusted_paste = adjusted_paste.filter(|_| paste_preview);
+        if let (Some(_), Some(primitives)) = (adjusted_paste, primitives) {
+            paint_original_paste_outlines(painter, &view_state, &primitives.0, &gerber_transform, render_props.color);
+        }
         
         // Render main layer
-        renderer.paint_layer(
+        cache.paint_layer(
             painter,
+            &renderer,
             view_state,
-            &gerber_data.0,
+            entity,
+            adjusted_paste.map(|adjusted| adjusted.key).or(content_hash.map(|hash| hash.0)),
+            &layer_source(&gerber_data.0, primitives, polarity, adjusted_paste, image_area.as_deref()),
             render_props.color,
             &config,
             &gerber_transform,
+            copies,
         );
         
+        // Holes go through copper and soldermask
+        if layer_info.layer_type.is_drilled() {
+            paint_drill_knockouts(painter, &view_state, &knockouts, &gerber_transform, copies, background);
+        }
+        
+        // Tint the highlighted net on top of the layer
+        if let (Some(net_id), Some(net_geometry)) = (highlighted_net, net_geometry) {
+            let color = render_props.highlight_color.unwrap_or(DEFAULT_NET_HIGHLIGHT_COLOR);
+            paint_net_highlight(painter, &view_state, net_geometry, net_id, &gerber_transform, color);
+        }
+        
+        // Outline flashed pads/vias
+        if display_manager.highlight_flashes && matches!(layer_info.layer_type, LayerType::Copper(_)) {
+            if let Some(flash_primitives) = flash_primitives {
+                paint_flash_highlight(painter, &view_state, flash_primitives, &gerber_transform, DEFAULT_FLASH_HIGHLIGHT_COLOR);
+            }
+        }
+        
         // Render mechanical outline in quadrant view
         if display_manager.quadrant_view_enabled {
             if let Some((mechanical_gerber, mechanical_color)) = &mechanical_outline {
                 // Use the same transform as the layer for proper alignment
-                let mechanical_transform = create_gerber_transform_with_offset_composed(
-                    transform,
-                    image_transform,
-                    display_manager,
-                    quadrant_offset,
-                );
-                
                 renderer.paint_layer(
                     painter,
                     view_state,
                     mechanical_gerber,
                     *mechanical_color,
                     &config,
-                    &mechanical_transform,
+                    &gerber_transform,
                 );
             }
         }
     }
 }
 
-/// Helper function to create GerberTransform from ECS Transform
-fn create_gerber_transform(transform: &Transform, _display_manager: &DisplayManager) -> GerberTransform {
-    GerberTransform {
-        rotation: transform.rotation,
-        mirroring: transform.mirroring.clone().into(),
-        origin: transform.origin.clone().into(),
-        offset: transform.position.clone().into(),
-        scale: transform.scale,
+/// Render the shown side as fabricated: board under the soldermask film, copper
+/// seen through the mask, mask openings, finished pads, silkscreen and drill holes
+/// Painter order stands in for blending (colors come from `display::realistic`);
+/// layers are drawn flat in their own frames, without quadrant offsets
+pub fn render_layers_system_realistic(
+    world: &mut World,
+    painter: &Painter,
+    view_state: ViewState,
+    display_manager: &DisplayManager,
+) {
+    let settings = &display_manager.realistic;
+    let config = RenderConfiguration::default();
+    let renderer = GerberRenderer::default();
+    let side = if display_manager.showing_top { super::Side::Top } else { super::Side::Bottom };
+    let copper_type = if display_manager.showing_top { LayerType::TOP_COPPER } else { LayerType::BOTTOM_COPPER };
+    let mask_type = LayerType::Soldermask(side);
+    
+    // Board area, in the outline's frame (or the copper's when the outline is inferred)
+    let board = super::get_board_outline_primitives(world).map(|(primitives, inferred)| {
+        let frame = if inferred { copper_type } else { LayerType::MechanicalOutline };
+        (super::board_fill_polygon(&primitives), get_layer_render_transform(world, display_manager, frame))
+    });
+    let holes = super::get_drill_holes(world);
+    
+    let mut layer_query = world.query::<(&GerberData, &Transform, &ImageTransform, &Visibility, &LayerInfo, Option<&FlashPrimitives>)>();
+    let layers: Vec<_> = layer_query.iter(world)
+        .filter(|(_, _, _, visibility, _, _)| visibility.visible)
+        .collect();
+    let find = |layer_type: LayerType| layers.iter().find(|(_, _, _, _, info, _)| info.layer_type == layer_type);
+    let frame = |transform: &Transform, image_transform: &ImageTransform| {
+        create_gerber_transform_composed(transform, image_transform)
+    };
+    
+    if let Some((outline, Some(outline_transform))) = board {
+        let matrix = outline_transform.to_matrix();
+        let points: Vec<egui::Pos2> = outline.iter()
+            .map(|point| view_state.gerber_to_screen_coords(matrix.transform_point(point)))
+            .collect();
+        if points.len() >= 3 {
+            painter.add(egui::Shape::convex_polygon(points, settings.masked_substrate_color(), egui::Stroke::NONE));
+        }
+    }
+    
+    let copper = find(copper_type);
+    let mask = find(mask_type);
+    if let Some((gerber_data, transform, image_transform, _, _, _)) = copper {
+        // Without a mask layer the whole side is bare
+        let color = if mask.is_some() { settings.masked_copper_color() } else { settings.finish_color() };
+        renderer.paint_layer(painter, view_state, &gerber_data.0, color, &config, &frame(transform, image_transform));
+    }
+    
+    if let Some((gerber_data, transform, image_transform, _, _, mask_flashes)) = mask {
+        renderer.paint_layer(painter, view_state, &gerber_data.0, crate::display::realistic::SUBSTRATE_COLOR, &config, &frame(transform, image_transform));
+        
+        // Pads inside a flashed opening get the finish; tented pads stay under the mask
+        if let Some((_, transform, image_transform, _, _, Some(copper_flashes))) = copper {
+            let openings = SortedOpenings::new(mask_flashes.map(|flashes| flashes.0.as_slice()).unwrap_or_default());
+            let exposed = FlashPrimitives(copper_flashes.0.iter()
+                .filter(|flash| openings.contains(flash.position))
+                .cloned()
+                .collect());
+            paint_flashes_filled(painter, &view_state, &exposed, &frame(transform, image_transform), settings.finish_color());
+        }
+    }
+    
+    if let Some((gerber_data, transform, image_transform, _, _, _)) = find(LayerType::Silkscreen(side)) {
+        renderer.paint_layer(painter, view_state, &gerber_data.0, settings.silkscreen_color(), &config, &frame(transform, image_transform));
+    }
+    
+    // Drill files carry no transform of their own; they follow the copper
+    if let Some((_, transform, image_transform, _, _, _)) = copper {
+        paint_drill_holes(painter, &view_state, &holes, &frame(transform, image_transform), true);
+    }
+    
+    if let Some((gerber_data, transform, image_transform, _, _, _)) = find(LayerType::MechanicalOutline) {
+        renderer.paint_layer(painter, view_state, &gerber_data.0, crate::display::realistic::SUBSTRATE_COLOR, &config, &frame(transform, image_transform));
     }
 }
 
-/// Helper function to create GerberTransform with quadrant offset
-fn create_gerber_transform_with_offset(
-    transform: &Transform,
-    _display_manager: &DisplayManager,
-    quadrant_offset: crate::display::VectorOffset,
-) -> GerberTransform {
-    // Combine transform position with quadrant offset
-    let combined_offset = crate::display::VectorOffset {
-        x: transform.position.x + quadrant_offset.x,
-        y: transform.position.y + quadrant_offset.y,
-    };
+/// Soldermask flash openings sorted by X, for point lookups over many pads
+struct SortedOpenings {
+    /// (center, half width, half height)
+    openings: Vec<(nalgebra::Point2<f64>, f64, f64)>,
+    max_half_width: f64,
+}
+
+impl SortedOpenings {
+    fn new(flashes: &[crate::ecs::FlashPrimitive]) -> Self {
+        let mut openings: Vec<_> = flashes.iter()
+            .map(|flash| (flash.position, flash.width / 2.0, flash.height / 2.0))
+            .collect();
+        openings.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+        let max_half_width = openings.iter().map(|(_, half_width, _)| *half_width).fold(0.0, f64::max);
+        Self { openings, max_half_width }
+    }
     
-    GerberTransform {
-        rotation: transform.rotation,
-        mirroring: transform.mirroring.clone().into(),
-        origin: transform.origin.clone().into(),
-        offset: combined_offset.into(),
-        scale: transform.scale,
+    fn contains(&self, point: nalgebra::Point2<f64>) -> bool {
+        let start = self.openings.partition_point(|(center, _, _)| center.x < point.x - self.max_half_width);
+        self.openings[start..].iter()
+            .take_while(|(center, _, _)| center.x <= point.x + self.max_half_width)
+            .any(|(center, half_width, half_height)| {
+                (point.x - center.x).abs() <= *half_width && (point.y - center.y).abs() <= *half_height
+            })
     }
 }
 
-/// Helper function to create composed GerberTransform from ECS Transform and ImageTransform
-fn create_gerber_transform_composed(
-    transform: &Transform, 
-    image_transform: &ImageTransform, 
-    _display_manager: &DisplayManager
-) -> GerberTransform {
-    // According to gerber-viewer 0.2.0, we need to compose:
-    // matrix = image_transform_matrix * render_transform_matrix
+/// Fill every flash of `flash_primitives` with `color` using the layer's render transform
+fn paint_flashes_filled(
+    painter: &Painter,
+    view_state: &ViewState,
+    flash_primitives: &FlashPrimitives,
+    gerber_transform: &GerberTransform,
+    color: egui::Color32,
+) {
+    let matrix = gerber_transform.to_matrix();
+    for flash in &flash_primitives.0 {
+        let center = view_state.gerber_to_screen_coords(matrix.transform_point(&flash.position));
+        let size = egui::Vec2::new(flash.width as f32, flash.height as f32) * view_state.scale;
+        if matches!(flash.aperture, crate::ecs::ApertureShape::Circle | crate::ecs::ApertureShape::Polygon) {
+            painter.circle_filled(center, size.x / 2.0, color);
+        } else {
+            painter.rect_filled(egui::Rect::from_center_size(center, size), 0.0, color);
+        }
+    }
+}
+
+/// Render one layer in its own color regardless of its visibility
+/// Quadrant offsets are ignored so compared layers stay registered on top of each other
+pub fn render_single_layer_system(
+    world: &mut World,
+    painter: &Painter,
+    view_state: ViewState,
+    display_manager: &DisplayManager,
+    layer_type: LayerType,
+) -> bool {
+    let mut layer_query = world.query::<(&GerberData, &Transform, &ImageTransform, &RenderProperties, &LayerInfo)>();
+    let Some((gerber_data, transform, image_transform, render_props, _)) = layer_query.iter(world)
+        .find(|(_, _, _, _, layer_info)| layer_info.layer_type == layer_type) else {
+        return false;
+    };
     
-    // First create the render transform matrix
+    let gerber_transform = create_gerber_transform_composed(transform, image_transform);
+    GerberRenderer::default().paint_layer(
+        painter,
+        view_state,
+        &gerber_data.0,
+        render_props.color,
+        &RenderConfiguration::default(),
+        &gerber_transform,
+    );
+    true
+}
+
+/// Screen transform of a layer: the view transform stored by `run_ecs_systems`
+/// (rotation, mirroring, quadrant offset and nudge) composed with its image transform
+fn create_gerber_transform_composed(transform: &Transform, image_transform: &ImageTransform) -> GerberTransform {
     let render_transform = GerberTransform {
         rotation: transform.rotation,
         mirroring: transform.mirroring.clone().into(),
@@ -186,52 +404,69 @@ fn create_gerber_transform_composed(
         scale: transform.scale,
     };
     
-    // Get the matrices
-    let image_matrix = image_transform.transform.to_matrix();
-    let render_matrix = render_transform.to_matrix();
-    
-    // Compose: final_matrix = image_matrix * render_matrix
-    let composed_matrix = image_matrix * render_matrix;
-    
-    // Convert back to GerberTransform
-    GerberTransform::from_matrix(&composed_matrix)
+    // According to gerber-viewer 0.2.0: matrix = image_transform_matrix * render_transform_matrix
+    GerberTransform::from_matrix(&(image_transform.transform.to_matrix() * render_transform.to_matrix()))
 }
 
-/// Helper function to create composed GerberTransform with quadrant offset
-fn create_gerber_transform_with_offset_composed(
+/// Board-level transform of a layer: as on screen, without the quadrant offset but keeping its nudge
+fn create_board_transform_composed(
     transform: &Transform,
     image_transform: &ImageTransform,
-    _display_manager: &DisplayManager,
-    quadrant_offset: crate::display::VectorOffset,
+    display_manager: &DisplayManager,
+    layer_type: LayerType,
 ) -> GerberTransform {
-    // Combine transform position with quadrant offset
-    let combined_offset = crate::display::VectorOffset {
-        x: transform.position.x + quadrant_offset.x,
-        y: transform.position.y + quadrant_offset.y,
-    };
-    
-    // Create the render transform with combined offset
-    let render_transform = GerberTransform {
-        rotation: transform.rotation,
-        mirroring: transform.mirroring.clone().into(),
-        origin: transform.origin.clone().into(),
-        offset: combined_offset.into(),
-        scale: transform.scale,
-    };
+    let quadrant = display_manager.get_quadrant_offset(&layer_type);
+    let mut transform = transform.clone();
+    transform.position.x -= quadrant.x;
+    transform.position.y -= quadrant.y;
+    create_gerber_transform_composed(&transform, image_transform)
+}
+
+/// Transform mapping a layer's source coordinates to exported board coordinates
+/// Uses the on-screen rotation, mirroring and image transform without quadrant spreading,
+/// shifted so the user's design origin becomes (0, 0)
+pub fn get_layer_export_transform(
+    world: &mut World,
+    display_manager: &DisplayManager,
+    layer_type: LayerType,
+) -> Option<GerberTransform> {
+    let mut query = world.query::<(&Transform, &ImageTransform, &LayerInfo)>();
+    let (transform, image_transform, _) = query.iter(world)
+        .find(|(_, _, layer_info)| layer_info.layer_type == layer_type)?;
     
-    // Get the matrices
-    let image_matrix = image_transform.transform.to_matrix();
-    let render_matrix = render_transform.to_matrix();
+    let board_transform = create_board_transform_composed(transform, image_transform, display_manager, layer_type);
+    let matrix = crate::display::design_origin_transform(display_manager) * board_transform.to_matrix();
+    Some(GerberTransform::from_matrix(&matrix))
+}
+
+/// Transform from source gerber coordinates to view coordinates for board-level overlays
+/// Taken from the outline (or any loaded layer) without quadrant spreading
+pub fn get_board_view_transform(world: &mut World, display_manager: &DisplayManager) -> Option<GerberTransform> {
+    let mut query = world.query::<(&Transform, &ImageTransform, &LayerInfo)>();
+    let layers: Vec<_> = query.iter(world).collect();
+    let (transform, image_transform, layer_info) = layers.iter()
+        .find(|(_, _, layer_info)| layer_info.layer_type == LayerType::MechanicalOutline)
+        .or_else(|| layers.first())?;
     
-    // Compose: final_matrix = image_matrix * render_matrix
-    let composed_matrix = image_matrix * render_matrix;
+    Some(create_board_transform_composed(transform, image_transform, display_manager, layer_info.layer_type))
+}
+
+/// Get the screen render transform of a layer (including any quadrant offset)
+/// Used to draw overlays that must line up with the rendered layer
+pub fn get_layer_render_transform(
+    world: &mut World,
+    display_manager: &DisplayManager,
+    layer_type: LayerType,
+) -> Option<GerberTransform> {
+    let mut query = world.query::<(&Transform, &ImageTransform, &LayerInfo)>();
+    let (transform, image_transform, _) = query.iter(world)
+        .find(|(_, _, layer_info)| layer_info.layer_type == layer_type)?;
     
-    // Convert back to GerberTransform
-    GerberTransform::from_matrix(&composed_matrix)
+    Some(create_gerber_transform_composed(transform, image_transform))
 }
 
 /// Helper function to get mechanical outline layer for quadrant rendering
-fn get_mechanical_outline_layer(world: &mut World) -> Option<(gerber_viewer::GerberLayer, egui::Color32)> {
+fn get_mechanical_outline_layer(world: &mut World) -> Option<(std::sync::Arc<gerber_viewer::GerberLayer>, egui::Color32)> {
     let mut query = world.query::<(&GerberData, &RenderProperties, &LayerInfo)>();
     
     for (gerber_data, render_props, layer_info) in query.iter(world) {
@@ -251,12 +486,255 @@ pub fn execute_render_system(
     view_state: ViewState,
     display_manager: &DisplayManager,
     use_enhanced_rendering: bool,
+    highlighted_net: Option<u32>,
+    allow_realistic: bool,
 ) {
-    if use_enhanced_rendering {
-        render_layers_system_enhanced(world, painter, view_state, display_manager);
+    let started = std::time::Instant::now();
+    update_paste_preview(world);
+    let mut cache = world.remove_resource::<LayerRenderCache>().unwrap_or_default();
+    cache.begin_frame();
+    
+    if allow_realistic && display_manager.realistic_active() {
+        render_layers_system_realistic(world, painter, view_state, display_manager);
     } else {
-        render_layers_system(world, painter, view_state, display_manager);
+        if use_enhanced_rendering {
+            render_layers_system_enhanced(world, painter, view_state, display_manager, highlighted_net, &mut cache);
+        } else {
+            render_layers_system(world, painter, view_state, display_manager, highlighted_net, &mut cache);
+        }
+        if display_manager.show_drill_holes {
+            render_drill_holes(world, painter, view_state, display_manager);
+        }
     }
+    
+    cache.end_frame(started.elapsed());
+    world.insert_resource(cache);
+}
+
+/// Tint used for highlighted nets when a layer has no highlight color of its own
+pub const DEFAULT_NET_HIGHLIGHT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 255, 0);
+
+/// Outline color for flashed pads/vias when "Highlight pads/vias" is enabled
+pub const DEFAULT_FLASH_HIGHLIGHT_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 255, 255);
+
+/// Barrel plating drawn around plated (PTH) holes
+pub const PLATED_HOLE_RING_COLOR: egui::Color32 = egui::Color32::from_rgb(218, 165, 32);
+
+/// Outline of non-plated (NPTH) holes, which are left unfilled
+pub const NON_PLATED_HOLE_OUTLINE_COLOR: egui::Color32 = egui::Color32::from_rgb(235, 235, 235);
+
+/// Paint drill holes: plated ones with a plating ring, non-plated ones as a bare outline
+/// `filled` paints the inside of plated holes; the flat view leaves it to the knockouts
+fn paint_drill_holes(
+    painter: &Painter,
+    view_state: &ViewState,
+    holes: &[super::DrillHole],
+    gerber_transform: &GerberTransform,
+    filled: bool,
+) {
+    let matrix = gerber_transform.to_matrix();
+    for hole in holes {
+        let center = view_state.gerber_to_screen_coords(matrix.transform_point(&hole.position));
+        let radius = (hole.diameter as f32 * view_state.scale / 2.0).max(1.0);
+        if hole.plated {
+            let fill = if filled { crate::display::realistic::DRILL_HOLE_COLOR } else { egui::Color32::TRANSPARENT };
+            painter.circle(center, radius, fill, egui::Stroke::new(1.5, PLATED_HOLE_RING_COLOR));
+        } else {
+            painter.circle_stroke(center, radius, egui::Stroke::new(1.0, NON_PLATED_HOLE_OUTLINE_COLOR));
+        }
+    }
+}
+
+/// Transform drill holes are drawn with: that of the copper of the shown side
+pub fn get_drill_render_transform(world: &mut World, display_manager: &DisplayManager) -> Option<GerberTransform> {
+    let (shown, other) = if display_manager.showing_top {
+        (LayerType::TOP_COPPER, LayerType::BOTTOM_COPPER)
+    } else {
+        (LayerType::BOTTOM_COPPER, LayerType::TOP_COPPER)
+    };
+    get_layer_render_transform(world, display_manager, shown)
+        .or_else(|| get_layer_render_transform(world, display_manager, other))
+        .or_else(|| get_board_view_transform(world, display_manager))
+}
+
+/// Drill holes on top of the flat layer view, registered with the copper of the shown side
+fn render_drill_holes(world: &mut World, painter: &Painter, view_state: ViewState, display_manager: &DisplayManager) {
+    let holes = super::get_drill_holes(world);
+    if holes.is_empty() {
+        return;
+    }
+    if let Some(transform) = get_drill_render_transform(world, display_manager) {
+        paint_drill_holes(painter, &view_state, &holes, &transform, false);
+    }
+}
+
+/// Knock drill holes out of a layer just painted, by filling them with the background
+/// Done in the layer's own frame (and at every panel copy) so holes punch through each quadrant cell
+fn paint_drill_knockouts(
+    painter: &Painter,
+    view_state: &ViewState,
+    holes: &[super::DrillHole],
+    gerber_transform: &GerberTransform,
+    copies: &[nalgebra::Vector2<f64>],
+    background: egui::Color32,
+) {
+    for (center, radius) in drill_knockout_circles(view_state, holes, gerber_transform, copies) {
+        painter.circle_filled(center, radius, background);
+    }
+}
+
+/// Screen centre and radius of every knockout, for the layer itself and then each panel copy
+/// Radii never drop below a pixel so holes stay visible when zoomed out
+fn drill_knockout_circles(
+    view_state: &ViewState,
+    holes: &[super::DrillHole],
+    gerber_transform: &GerberTransform,
+    copies: &[nalgebra::Vector2<f64>],
+) -> Vec<(egui::Pos2, f32)> {
+    let matrix = gerber_transform.to_matrix();
+    let offsets = std::iter::once(nalgebra::Vector2::zeros()).chain(copies.iter().copied());
+    offsets
+        .flat_map(|offset| holes.iter().map(move |hole| (offset, hole)))
+        .map(|(offset, hole)| {
+            let center = view_state.gerber_to_screen_coords(matrix.transform_point(&(hole.position + offset)));
+            (center, (hole.diameter as f32 * view_state.scale / 2.0).max(1.0))
+        })
+        .collect()
+}
+
+/// Outline every flash (pad/via) of a layer using the layer's render transform
+fn paint_flash_highlight(
+    painter: &Painter,
+    view_state: &ViewState,
+    flash_primitives: &FlashPrimitives,
+    gerber_transform: &GerberTransform,
+    color: egui::Color32,
+) {
+    let matrix = gerber_transform.to_matrix();
+    let stroke = egui::Stroke::new(1.5, color);
+    
+    for flash in &flash_primitives.0 {
+        let center = view_state.gerber_to_screen_coords(matrix.transform_point(&flash.position));
+        let size = egui::Vec2::new(flash.width as f32, flash.height as f32) * view_state.scale;
+        let size = size.max(egui::Vec2::splat(3.0));
+        if matches!(flash.aperture, crate::ecs::ApertureShape::Circle | crate::ecs::ApertureShape::Polygon) {
+            painter.circle_stroke(center, size.x / 2.0, stroke);
+        } else {
+            painter.rect_stroke(egui::Rect::from_center_size(center, size), 0.0, stroke, egui::StrokeKind::Middle);
+        }
+    }
+}
+
+/// Paint every primitive of `net_id` on a layer using the layer's render transform
+fn paint_net_highlight(
+    painter: &Painter,
+    view_state: &ViewState,
+    net_geometry: &NetGeometry,
+    net_id: u32,
+    gerber_transform: &GerberTransform,
+    color: egui::Color32,
+) {
+    let matrix = gerber_transform.to_matrix();
+    let to_screen = |point: &nalgebra::Point2<f64>| view_state.gerber_to_screen_coords(matrix.transform_point(point));
+    
+    for primitive in net_geometry.0.iter().filter(|p| p.net_id == net_id) {
+        match &primitive.shape {
+            crate::ecs::PrimitiveShape::Segment { start, end, width } => {
+                let stroke_width = (*width as f32 * view_state.scale).max(1.5);
+                painter.line_segment([to_screen(start), to_screen(end)], egui::Stroke::new(stroke_width, color));
+            }
+            crate::ecs::PrimitiveShape::Flash { center, width, height, aperture, .. } => {
+                let size = egui::Vec2::new(*width as f32, *height as f32) * view_state.scale;
+                let size = size.max(egui::Vec2::splat(3.0));
+                let center_screen = to_screen(center);
+                if matches!(aperture, crate::ecs::ApertureShape::Circle | crate::ecs::ApertureShape::Polygon) {
+                    painter.circle_filled(center_screen, size.x / 2.0, color);
+                } else {
+                    painter.rect_filled(egui::Rect::from_center_size(center_screen, size), 0.0, color);
+                }
+            }
+            crate::ecs::PrimitiveShape::Region { points } => {
+                let screen_points: Vec<egui::Pos2> = points.iter().map(to_screen).collect();
+                painter.add(egui::Shape::closed_line(screen_points, egui::Stroke::new(1.5, color)));
+            }
+        }
+    }
+}
+
+/// Find the net under a screen position, checking visible layers from top to bottom
+/// Uses the same per-layer transforms as `render_layers_system_enhanced`
+pub fn pick_net_at_screen_pos(
+    world: &mut World,
+    view_state: &ViewState,
+    display_manager: &DisplayManager,
+    screen_pos: egui::Pos2,
+    tolerance_px: f32,
+) -> Option<u32> {
+    let mut layer_query = world.query::<(&Transform, &ImageTransform, &Visibility, &RenderProperties, &LayerInfo, &NetGeometry)>();
+    let mut layers: Vec<_> = layer_query.iter(world).collect();
+    
+    // Topmost layer first
+    layers.sort_by_key(|(_, _, _, props, _, _)| -props.z_order);
+    
+    for (transform, image_transform, visibility, _render_props, layer_info, net_geometry) in layers {
+        if !visibility.visible {
+            continue;
+        }
+        if display_manager.quadrant_view_enabled && !display_manager.quadrant_shows(layer_info.layer_type) {
+            continue;
+        }
+        
+        let gerber_transform = create_gerber_transform_composed(transform, image_transform);
+        let matrix = gerber_transform.to_matrix();
+        let to_screen = |point: &nalgebra::Point2<f64>| view_state.gerber_to_screen_coords(matrix.transform_point(point));
+        
+        for primitive in &net_geometry.0 {
+            let hit = match &primitive.shape {
+                crate::ecs::PrimitiveShape::Segment { start, end, width } => {
+                    let radius = (*width as f32 * view_state.scale / 2.0).max(tolerance_px);
+                    distance_to_segment(screen_pos, to_screen(start), to_screen(end)) <= radius
+                }
+                crate::ecs::PrimitiveShape::Flash { center, width, height, .. } => {
+                    let radius = (width.max(*height) as f32 * view_state.scale / 2.0).max(tolerance_px);
+                    to_screen(center).distance(screen_pos) <= radius
+                }
+                crate::ecs::PrimitiveShape::Region { points } => {
+                    let screen_points: Vec<egui::Pos2> = points.iter().map(to_screen).collect();
+                    point_in_polygon(screen_pos, &screen_points)
+                }
+            };
+            if hit {
+                return Some(primitive.net_id);
+            }
+        }
+    }
+    
+    None
+}
+
+/// Distance from a point to a line segment in screen space
+fn distance_to_segment(point: egui::Pos2, start: egui::Pos2, end: egui::Pos2) -> f32 {
+    let segment = end - start;
+    let length_sq = segment.length_sq();
+    if length_sq == 0.0 {
+        return point.distance(start);
+    }
+    let t = ((point - start).dot(segment) / length_sq).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
+
+/// Even-odd point in polygon test in screen space
+fn point_in_polygon(point: egui::Pos2, polygon: &[egui::Pos2]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > point.y) != (b.y > point.y) && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
 }
 
 /// System to update bounding boxes when transforms change
@@ -300,16 +778,17 @@ pub fn transform_system(
     for (mut transform, layer_info) in &mut query {
         // Update transform based on display manager settings
         
-        // Apply quadrant offset if enabled
+        // Apply quadrant offset if enabled, plus the layer's alignment nudge
+        let nudge = transform.nudge;
         if display_manager.quadrant_view_enabled {
             let quadrant_offset = display_manager.get_quadrant_offset(&layer_info.layer_type);
             transform.position = crate::display::VectorOffset {
-                x: quadrant_offset.x,
-                y: quadrant_offset.y,
+                x: quadrant_offset.x + nudge.x,
+                y: quadrant_offset.y + nudge.y,
             };
         } else {
-            // Reset position for normal view
-            transform.position = crate::display::VectorOffset { x: 0.0, y: 0.0 };
+            // Only the nudge in normal view
+            transform.position = crate::display::VectorOffset { x: nudge.x, y: nudge.y };
         }
         
         // Apply mirroring
@@ -340,23 +819,32 @@ pub fn z_order_system(
 ) {
     for (mut render_props, layer_info) in &mut query {
         // Update z-order based on layer type
-        render_props.z_order = match layer_info.layer_type {
-            LayerType::Paste(crate::ecs::Side::Top) => 90,
-            LayerType::Silkscreen(crate::ecs::Side::Top) => 80,
-            LayerType::Soldermask(crate::ecs::Side::Top) => 70,
-            LayerType::Copper(1) => 60,  // Top copper
-            LayerType::Copper(n) => 50 - (n as i32),  // All other copper layers (inner/bottom)
-            LayerType::Soldermask(crate::ecs::Side::Bottom) => 40,
-            LayerType::Silkscreen(crate::ecs::Side::Bottom) => 30,
-            LayerType::Paste(crate::ecs::Side::Bottom) => 20,
-            LayerType::MechanicalOutline => 10,
-        };
+        render_props.z_order = layer_info.layer_type.z_order();
     }
 }
 
 // Note: view_mode_system has been removed to allow manual layer control
 // Visibility is now controlled entirely through the layer controls UI
 
+/// Center of the combined bounding box of all loaded layers, the point the view rotates and mirrors about
+pub fn get_rotation_center(world: &mut World) -> nalgebra::Point2<f64> {
+    let mut bbox_query = world.query::<&GerberData>();
+    let mut combined_bbox: Option<gerber_viewer::BoundingBox> = None;
+    
+    for gerber_data in bbox_query.iter(world) {
+        let layer_bbox = gerber_data.0.bounding_box();
+        combined_bbox = match combined_bbox {
+            None => Some(layer_bbox.clone()),
+            Some(mut existing) => {
+                existing.expand(layer_bbox);
+                Some(existing)
+            }
+        };
+    }
+    
+    combined_bbox.map(|bbox| bbox.center()).unwrap_or_else(|| nalgebra::Point2::new(0.0, 0.0))
+}
+
 /// Master system runner that executes all ECS systems in the correct order
 /// This function provides a single entry point for running all ECS systems
 pub fn run_ecs_systems(
@@ -364,50 +852,20 @@ pub fn run_ecs_systems(
     display_manager: &DisplayManager,
     rotation_degrees: f32,
 ) {
-    // First, get the combined bounding box to determine the PCB center for mirroring
-    let pcb_center = {
-        let mut bbox_query = world.query::<&GerberData>();
-        let mut combined_bbox: Option<gerber_viewer::BoundingBox> = None;
-        
-        for gerber_data in bbox_query.iter(world) {
-            let layer_bbox = gerber_data.0.bounding_box();
-            combined_bbox = match combined_bbox {
-                None => Some(layer_bbox.clone()),
-                Some(mut existing) => {
-                    existing.expand(layer_bbox);
-                    Some(existing)
-                }
-            };
-        }
-        
-        combined_bbox.map(|bbox| bbox.center()).unwrap_or_else(|| nalgebra::Point2::new(0.0, 0.0))
-    };
     // Update transforms based on display settings
     let mut transform_query = world.query::<(&mut Transform, &LayerInfo)>();
     for (mut transform, layer_info) in transform_query.iter_mut(world) {
-        // Apply quadrant offset if enabled
-        if display_manager.quadrant_view_enabled {
-            let quadrant_offset = display_manager.get_quadrant_offset(&layer_info.layer_type);
-            transform.position = crate::display::VectorOffset {
-                x: quadrant_offset.x,
-                y: quadrant_offset.y,
-            };
-        } else {
-            // Reset position for normal view
-            transform.position = crate::display::VectorOffset { x: 0.0, y: 0.0 };
-        }
-        
-        // Apply mirroring
-        transform.mirroring = display_manager.mirroring.clone();
-        
-        // Apply rotation
-        transform.rotation = rotation_degrees.to_radians();
-        
-        // Set origin to PCB center for proper in-place mirroring and rotation
-        transform.origin = crate::display::VectorOffset {
-            x: pcb_center.x,
-            y: pcb_center.y,
+        // View transform of the layer (quadrant offset included), plus its alignment nudge
+        let nudge = transform.nudge;
+        let view = crate::display::build_layer_transform(display_manager, rotation_degrees + nudge.rotation_degrees, Some(layer_info.layer_type));
+        let (nudge_x, nudge_y) = nudge.view_offset(rotation_degrees, display_manager.mirroring.x, display_manager.mirroring.y);
+        transform.position = crate::display::VectorOffset {
+            x: view.offset.x + nudge_x,
+            y: view.offset.y + nudge_y,
         };
+        transform.mirroring = display_manager.mirroring.clone();
+        transform.rotation = view.rotation;
+        transform.origin = view.origin.into();
     }
     
     // Note: Visibility is now controlled manually through layer controls
@@ -417,17 +875,7 @@ pub fn run_ecs_systems(
     // Update z-order for proper rendering
     let mut z_order_query = world.query::<(&mut RenderProperties, &LayerInfo)>();
     for (mut render_props, layer_info) in z_order_query.iter_mut(world) {
-        render_props.z_order = match layer_info.layer_type {
-            LayerType::Paste(crate::ecs::Side::Top) => 90,
-            LayerType::Silkscreen(crate::ecs::Side::Top) => 80,
-            LayerType::Soldermask(crate::ecs::Side::Top) => 70,
-            LayerType::Copper(1) => 60,  // Top copper
-            LayerType::Copper(n) => 50 - (n as i32),  // All other copper layers (inner/bottom)
-            LayerType::Soldermask(crate::ecs::Side::Bottom) => 40,
-            LayerType::Silkscreen(crate::ecs::Side::Bottom) => 30,
-            LayerType::Paste(crate::ecs::Side::Bottom) => 20,
-            LayerType::MechanicalOutline => 10,
-        };
+        render_props.z_order = layer_info.layer_type.z_order();
     }
     
     // Update bounding boxes when transforms change (for quadrant view)
@@ -514,7 +962,7 @@ pub fn auto_assign_gerbers_system(world: &mut World) -> Vec<(String, LayerType)>
     
     // Try to detect and assign each unassigned gerber
     for unassigned in unassigned_list {
-        if let Some(detected_type) = crate::ecs::detect_layer_type(world, &unassigned.filename) {
+        if let Some(detected_type) = crate::ecs::detect_layer_type_with_content(world, &unassigned.filename, &unassigned.content) {
             // Check if this layer type is already assigned
             if crate::ecs::get_layer_by_type(world, detected_type).is_none() {
                 // Try to assign it
@@ -528,6 +976,53 @@ pub fn auto_assign_gerbers_system(world: &mut World) -> Vec<(String, LayerType)>
     newly_assigned
 }
 
+/// System to assign several unassigned gerbers to one layer type
+/// A layer type holds one file, so for copper the files after the first take the
+/// next free inner layers, ordered by the copper layer detected in each file (files
+/// without one keep their order, last); other types take the first file only.
+/// Returns the assigned (filename, layer_type) pairs and the files left unassigned
+pub fn bulk_assign_gerbers_system(
+    world: &mut World,
+    filenames: &[String],
+    layer_type: LayerType,
+) -> (Vec<(String, LayerType)>, Vec<String>) {
+    let mut candidates = vec![layer_type];
+    if layer_type.is_copper() {
+        candidates.extend((1..=crate::ecs::MAX_INNER_COPPER_LAYERS).map(LayerType::inner_copper).filter(|inner| *inner != layer_type));
+    }
+    
+    let mut filenames = filenames.to_vec();
+    if layer_type.is_copper() {
+        filenames.sort_by_cached_key(|filename| copper_stack_position(world, filename));
+    }
+    
+    let mut assigned = Vec::new();
+    let mut left = Vec::new();
+    for filename in &filenames {
+        let free = candidates.iter().copied().find(|candidate| crate::ecs::get_layer_by_type(world, *candidate).is_none());
+        match free.map(|free| (free, assign_gerber_to_layer_system(world, filename.clone(), free))) {
+            Some((free, Ok(_entity))) => assigned.push((filename.clone(), free)),
+            _ => left.push(filename.clone()),
+        }
+    }
+    (assigned, left)
+}
+
+/// Position of an unassigned copper gerber in the stack (top, In1..In6, bottom) from
+/// its file name or X2 attributes; files without a detected copper layer come last
+fn copper_stack_position(world: &World, filename: &str) -> usize {
+    let content = world.get_resource::<UnassignedGerbers>()
+        .and_then(|unassigned| unassigned.0.iter().find(|gerber| gerber.filename == filename))
+        .map_or("", |gerber| gerber.content.as_str());
+    let bottom = crate::ecs::MAX_INNER_COPPER_LAYERS as usize + 1;
+    match crate::ecs::detect_layer_type_with_content(world, filename, content) {
+        Some(LayerType::Copper(1)) => 0,
+        Some(LayerType::Copper(2)) => bottom,
+        Some(LayerType::Copper(n)) => n as usize - 2,
+        _ => bottom + 1,
+    }
+}
+
 /// System to clear all layers and unassigned gerbers
 /// This is used when loading a new project
 pub fn clear_all_layers_system(world: &mut World) {
@@ -550,6 +1045,24 @@ pub fn clear_all_layers_system(world: &mut World) {
     if let Some(mut assignments_res) = world.get_resource_mut::<LayerAssignments>() {
         assignments_res.0.clear();
     }
+    
+    // Clear net names
+    if let Some(mut net_table) = world.get_resource_mut::<crate::ecs::NetTable>() {
+        net_table.clear();
+    }
+    
+    // Clear drill holes
+    if let Some(mut drill_data) = world.get_resource_mut::<crate::ecs::DrillData>() {
+        drill_data.clear();
+    }
+    
+    // Forget registration issues of the removed layers
+    if let Some(mut issues) = world.get_resource_mut::<crate::ecs::RegistrationIssues>() {
+        issues.0.clear();
+    }
+    
+    // And their parse problems
+    world.remove_resource::<crate::ecs::LoadDiagnostics>();
 }
 
 /// System to add multiple unassigned gerbers
@@ -566,13 +1079,12 @@ pub fn load_gerbers_from_directory_system(
     world: &mut World,
     gerber_dir: &std::path::Path,
 ) -> Result<(usize, usize), String> {
-    use std::io::BufReader;
-    use gerber_viewer::gerber_parser::parse;
-    use gerber_viewer::GerberLayer;
-    
     let mut loaded_count = 0;
     let mut unassigned_count = 0;
     let mut gerbers_to_add = Vec::new();
+    let parse_cache = world.get_resource::<crate::ecs::ParseCache>().cloned();
+    let mut cache_report = crate::ecs::ParseCacheReport::default();
+    let mut load_diagnostics = crate::ecs::LoadDiagnostics::default();
     
     // Read directory and collect all gerber files
     let entries = std::fs::read_dir(gerber_dir)
@@ -580,6 +1092,12 @@ pub fn load_gerbers_from_directory_system(
     
     for entry in entries.flatten() {
         let path = entry.path();
+        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if crate::ecs::is_drill_file(filename) {
+            load_drill_file(world, &path, filename);
+            continue;
+        }
+        
         if path.extension().and_then(|s| s.to_str()) == Some("gbr") {
             let filename = path.file_name()
                 .and_then(|n| n.to_str())
@@ -589,14 +1107,16 @@ pub fn load_gerbers_from_directory_system(
             // Try to load and parse the gerber file
             match std::fs::read_to_string(&path) {
                 Ok(gerber_content) => {
-                    let reader = BufReader::new(gerber_content.as_bytes());
-                    match parse(reader) {
-                        Ok(doc) => {
-                            let commands = doc.into_commands();
-                            let gerber_layer = GerberLayer::new(commands);
+                    // Warnings are kept per file; a file that does not parse at all is skipped
+                    let (gerber_layer, diagnostics) = crate::ecs::parse_gerber(&gerber_content);
+                    if !diagnostics.is_empty() {
+                        load_diagnostics.0.insert(filename.clone(), diagnostics);
+                    }
+                    match gerber_layer {
+                        Some(gerber_layer) => {
                             
                             // Try to detect layer type
-                            if let Some(detected_type) = crate::ecs::detect_layer_type(world, &filename) {
+                            if let Some(detected_type) = crate::ecs::detect_layer_type_with_content(world, &filename, &gerber_content) {
                                 // Check if this layer type is already assigned
                                 let layer_assignments = crate::ecs::get_layer_assignments(world);
                                 if let Some(existing_assignment) = layer_assignments.iter()
@@ -606,8 +1126,13 @@ pub fn load_gerbers_from_directory_system(
                                     gerbers_to_add.push((filename, gerber_content, gerber_layer, None, existing_assignment));
                                     unassigned_count += 1;
                                 } else {
-                                    // Try to assign directly
-                                    gerbers_to_add.push((filename, gerber_content, gerber_layer, Some(detected_type), String::new()));
+                                    // Try to assign directly, reusing cached primitives when the file is unchanged
+                                    let primitives = parse_cache.as_ref().map(|cache| {
+                                        let (primitives, outcome) = cache.load_or_extract(&path, &gerber_content);
+                                        cache_report.layers.push((filename.clone(), outcome));
+                                        primitives
+                                    });
+                                    gerbers_to_add.push((filename, gerber_content, gerber_layer, Some((detected_type, primitives)), String::new()));
                                     loaded_count += 1;
                                 }
                             } else {
@@ -616,10 +1141,7 @@ pub fn load_gerbers_from_directory_system(
                                 unassigned_count += 1;
                             }
                         }
-                        Err(_e) => {
-                            // Parse failed - skip this file
-                            continue;
-                        }
+                        None => continue,
                     }
                 }
                 Err(_e) => {
@@ -630,17 +1152,21 @@ pub fn load_gerbers_from_directory_system(
         }
     }
     
+    // Recorded first so the layers created below pick up their file's diagnostics
+    world.insert_resource(load_diagnostics);
+    
     // Now process all the collected gerbers
     for (filename, gerber_content, gerber_layer, detected_type_opt, _existing_assignment) in gerbers_to_add {
-        if let Some(detected_type) = detected_type_opt {
+        if let Some((detected_type, primitives)) = detected_type_opt {
             // Create layer entity directly
-            let _entity = crate::ecs::create_gerber_layer_entity(
+            let _entity = crate::ecs::create_gerber_layer_entity_with_primitives(
                 world,
                 detected_type,
                 gerber_layer,
                 Some(gerber_content),
                 Some(filename.clone().into()),
                 true, // visible by default
+                primitives,
             );
             
             // Update layer assignments
@@ -658,5 +1184,186 @@ pub fn load_gerbers_from_directory_system(
         }
     }
     
+    if parse_cache.is_some() {
+        world.insert_resource(cache_report);
+    }
+    
     Ok((loaded_count, unassigned_count))
-}
\ No newline at end of file
+}
+
+/// Parse a drill file and add its holes to the DrillData resource
+/// Unreadable or malformed drill files are skipped like unparseable gerbers
+fn load_drill_file(world: &mut World, path: &std::path::Path, filename: &str) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
+    };
+    let plated = !crate::ecs::is_non_plated_drill_file(filename);
+    let Ok(holes) = crate::ecs::parse_excellon(&content, plated) else {
+        return;
+    };
+    
+    if let Some(mut drill_data) = world.get_resource_mut::<crate::ecs::DrillData>() {
+        drill_data.holes.extend(holes);
+        drill_data.filenames.push(filename.to_string());
+    }
+}
+
+/// System to reload a single layer's gerber from disk
+/// Returns Ok(true) if the content changed, Ok(false) if the file is identical
+pub fn reload_layer_from_file_system(
+    world: &mut World,
+    layer_type: LayerType,
+    gerber_dir: &std::path::Path,
+) -> Result<bool, String> {
+    let entity = crate::ecs::get_layer_by_type(world, layer_type)
+        .ok_or_else(|| format!("Layer {:?} is not loaded", layer_type))?;
+    
+    let file_path = world.get::<LayerInfo>(entity)
+        .and_then(|layer_info| layer_info.file_path.clone())
+        .ok_or_else(|| format!("Layer {:?} has no source file", layer_type))?;
+    
+    let mut gerber_content = std::fs::read_to_string(gerber_dir.join(&file_path))
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    if let Some(format_override) = world.get::<crate::ecs::FormatOverride>(entity) {
+        gerber_content = format_override.apply(&gerber_content);
+    }
+    
+    let content_hash = LayerContentHash::from_content(&gerber_content);
+    if world.get::<LayerContentHash>(entity) == Some(&content_hash) {
+        return Ok(false);
+    }
+    
+    let (gerber_layer, diagnostics) = crate::ecs::parse_gerber(&gerber_content);
+    let file_name = file_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    crate::ecs::set_file_diagnostics(world, &file_name, diagnostics.clone());
+    let gerber_layer = gerber_layer
+        .ok_or_else(|| format!("Failed to parse {}: {}", file_path.display(), diagnostics.details()))?;
+    let bounds = gerber_layer.bounding_box().clone();
+    
+    let primitives = match world.get_resource::<crate::ecs::ParseCache>() {
+        Some(cache) => cache.load_or_extract(&gerber_dir.join(&file_path), &gerber_content).0,
+        None => crate::ecs::extract_primitives(&gerber_content),
+    };
+    let net_geometry = {
+        let mut net_table = world.get_resource_or_insert_with(crate::ecs::NetTable::default);
+        crate::ecs::build_net_geometry(&mut net_table, &primitives)
+    };
+    
+    world.entity_mut(entity).insert((
+        GerberData(std::sync::Arc::new(gerber_layer)),
+        content_hash,
+        net_geometry,
+        crate::ecs::build_flash_primitives(&primitives),
+        LayerPolarity::new(&gerber_content, &primitives),
+        crate::ecs::LayerPrimitives(primitives),
+        BoundingBoxCache { bounds },
+        crate::ecs::GerberFormat::from_content(&gerber_content),
+        crate::ecs::LayerProvenance::from_content(&gerber_content),
+    ));
+    
+    Ok(true)
+}
+
+/// Re-parse a loaded layer from disk with a corrected coordinate format
+/// The override stays on the layer, so later reloads of the file keep it
+pub fn reparse_layer_with_format(
+    world: &mut World,
+    layer_type: LayerType,
+    gerber_dir: &std::path::Path,
+    format_override: crate::ecs::FormatOverride,
+) -> Result<bool, String> {
+    let entity = crate::ecs::get_layer_by_type(world, layer_type)
+        .ok_or_else(|| format!("Layer {:?} is not loaded", layer_type))?;
+    world.entity_mut(entity).insert(format_override);
+    reload_layer_from_file_system(world, layer_type, gerber_dir)
+}
+
+/// Re-parse an unassigned gerber with a corrected coordinate format
+/// The rewritten content replaces the original, so assigning it keeps the fix
+pub fn reparse_unassigned_with_format(
+    world: &mut World,
+    filename: &str,
+    format_override: crate::ecs::FormatOverride,
+) -> Result<(), String> {
+    let mut unassigned_res = world.get_resource_mut::<UnassignedGerbers>()
+        .ok_or("UnassignedGerbers resource not found")?;
+    let unassigned = unassigned_res.0.iter_mut()
+        .find(|u| u.filename == filename)
+        .ok_or("Unassigned gerber not found")?;
+    
+    let content = format_override.apply(&unassigned.content);
+    let (gerber_layer, diagnostics) = crate::ecs::parse_gerber(&content);
+    let Some(gerber_layer) = gerber_layer else {
+        return Err(format!("Failed to parse {}: {}", filename, diagnostics.details()));
+    };
+    unassigned.parsed_layer = gerber_layer;
+    unassigned.content = content;
+    crate::ecs::set_file_diagnostics(world, filename, diagnostics);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unassigned(filename: &str, file_function: Option<&str>) -> UnassignedGerber {
+        let attribute = file_function.map(|function| format!("%TF.FileFunction,{}*%\n", function)).unwrap_or_default();
+        let content = format!("{}%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.250000*%\nD10*\nX0Y0D03*\nM02*\n", attribute);
+        let parsed_layer = crate::ecs::parse_gerber(&content).0.expect("gerber parses");
+        UnassignedGerber { filename: filename.to_string(), content, parsed_layer }
+    }
+
+    #[test]
+    fn test_bulk_assign_orders_copper_by_detected_layer() {
+        let mut world = crate::ecs::setup_ecs_world();
+        add_unassigned_gerbers_system(&mut world, vec![
+            unassigned("a_signal.gbr", Some("Copper,L3,Inr")),
+            unassigned("b_misc.gbr", None),
+            unassigned("c_plane.gbr", Some("Copper,L2,Inr")),
+            unassigned("d-In3_Cu.gbr", None),
+        ]);
+        let filenames = ["a_signal.gbr", "b_misc.gbr", "c_plane.gbr", "d-In3_Cu.gbr"].map(String::from);
+
+        let (assigned, left) = bulk_assign_gerbers_system(&mut world, &filenames, LayerType::inner_copper(1));
+        assert!(left.is_empty());
+        assert_eq!(assigned, vec![
+            ("c_plane.gbr".to_string(), LayerType::inner_copper(1)),
+            ("a_signal.gbr".to_string(), LayerType::inner_copper(2)),
+            ("d-In3_Cu.gbr".to_string(), LayerType::inner_copper(3)),
+            ("b_misc.gbr".to_string(), LayerType::inner_copper(4)),
+        ]);
+    }
+
+    #[test]
+    fn test_drill_knockouts_follow_transform_and_panel_copies() {
+        let holes = [
+            crate::ecs::DrillHole { position: nalgebra::Point2::new(1.0, 2.0), diameter: 0.8, plated: true },
+            crate::ecs::DrillHole { position: nalgebra::Point2::new(-3.0, 0.5), diameter: 0.01, plated: false },
+        ];
+        let view = ViewState { scale: 10.0, base_scale: 10.0, translation: egui::Vec2::new(100.0, 200.0) };
+        let transform = GerberTransform {
+            rotation: 0.0,
+            mirroring: crate::display::manager::MirroringSettings { x: false, y: false }.into(),
+            origin: nalgebra::Vector2::zeros(),
+            offset: nalgebra::Vector2::new(5.0, -1.0),
+            scale: 1.0,
+        };
+        let copies = [nalgebra::Vector2::new(20.0, 0.0)];
+
+        let circles = drill_knockout_circles(&view, &holes, &transform, &copies);
+        let expected = |x: f64, y: f64| view.gerber_to_screen_coords(nalgebra::Point2::new(x, y));
+        assert_eq!(circles, vec![
+            (expected(6.0, 1.0), 4.0),
+            (expected(2.0, -0.5), 1.0),
+            (expected(26.0, 1.0), 4.0),
+            (expected(22.0, -0.5), 1.0),
+        ]);
+        assert!(drill_knockout_circles(&view, &[], &transform, &copies).is_empty());
+
+        // Only copper and soldermask are drilled through
+        assert!(LayerType::Copper(1).is_drilled() && LayerType::inner_copper(1).is_drilled());
+        assert!(LayerType::BOTTOM_SOLDERMASK.is_drilled());
+        assert!(!LayerType::TOP_SILK.is_drilled());
+        assert!(!LayerType::MechanicalOutline.is_drilled());
+    }
+}
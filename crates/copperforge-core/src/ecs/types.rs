@@ -3,7 +3,8 @@ use egui::Color32;
 /// Represents different PCB layers - redesigned to support multi-layer PCBs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum LayerType {
-    /// Copper layers by number (1=top, 2,3,4...N=inner, N+1=bottom)
+    /// Copper layers by number: 1=top, 2=bottom, 3.. = inner In1, In2, ...
+    /// (see `inner_copper`), so 2-layer boards keep their original numbering
     Copper(u8),
     /// Silkscreen (text/component outlines) - only top/bottom
     Silkscreen(Side),
@@ -15,6 +16,41 @@ pub enum LayerType {
     MechanicalOutline,
 }
 
+/// Most inner copper layers detected and listed (In1.Cu..In6.Cu)
+pub const MAX_INNER_COPPER_LAYERS: u8 = 6;
+
+/// Default colors of In1..In6, chosen to stay apart from top/bottom copper
+const INNER_COPPER_COLORS: [[u8; 3]; MAX_INNER_COPPER_LAYERS as usize] = [
+    [230, 200, 60],
+    [200, 90, 200],
+    [90, 170, 230],
+    [230, 110, 110],
+    [150, 120, 230],
+    [100, 210, 200],
+];
+
+/// Where a layer sits in the stack, for grouping the layer panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerGroup {
+    Top,
+    Inner,
+    Bottom,
+    Other,
+}
+
+impl LayerGroup {
+    pub const ALL: [LayerGroup; 4] = [LayerGroup::Top, LayerGroup::Inner, LayerGroup::Bottom, LayerGroup::Other];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Top => "Top",
+            Self::Inner => "Inner",
+            Self::Bottom => "Bottom",
+            Self::Other => "Other",
+        }
+    }
+}
+
 /// PCB side designation for non-copper layers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Side {
@@ -53,9 +89,9 @@ impl LayerType {
     pub fn standard_4_layer() -> Vec<Self> {
         vec![
             Self::Copper(1),                    // Top copper
-            Self::Copper(2),                    // Inner 1
-            Self::Copper(3),                    // Inner 2
-            Self::Copper(4),                    // Bottom copper
+            Self::Copper(3),                    // Inner 1
+            Self::Copper(4),                    // Inner 2
+            Self::Copper(2),                    // Bottom copper
             Self::Silkscreen(Side::Top),
             Self::Silkscreen(Side::Bottom),
             Self::Soldermask(Side::Top),
@@ -70,9 +106,13 @@ impl LayerType {
     pub fn for_layer_count(layer_count: u8) -> Vec<Self> {
         let mut layers = Vec::new();
         
-        // Add copper layers (1 to layer_count)
-        for i in 1..=layer_count {
-            layers.push(Self::Copper(i));
+        // Add copper layers top to bottom
+        layers.push(Self::Copper(1));
+        for inner in 1..=layer_count.saturating_sub(2) {
+            layers.push(Self::inner_copper(inner));
+        }
+        if layer_count > 1 {
+            layers.push(Self::Copper(2));
         }
         
         // Add non-copper layers (only top/bottom exist)
@@ -89,16 +129,63 @@ impl LayerType {
         layers
     }
     
-    /// Default to 2-layer for backwards compatibility
+    /// Every layer slot in layer-list order: the top side, inner copper
+    /// In1..In6, the bottom side, then the outline (see `LayerGroup`)
     pub fn all() -> Vec<Self> {
-        Self::standard_2_layer()
+        let slots = Self::for_layer_count(MAX_INNER_COPPER_LAYERS + 2);
+        LayerGroup::ALL
+            .iter()
+            .flat_map(|group| slots.iter().copied().filter(move |layer| layer.group() == *group))
+            .collect()
+    }
+    
+    /// Inner copper layer `index` (1 = In1.Cu)
+    pub fn inner_copper(index: u8) -> Self {
+        Self::Copper(index + 2)
+    }
+    
+    /// Inner layer index (1 = In1.Cu) if this is an inner copper layer
+    pub fn inner_copper_index(&self) -> Option<u8> {
+        match self {
+            Self::Copper(n) if *n >= 3 => Some(n - 2),
+            _ => None,
+        }
+    }
+    
+    pub fn is_inner_copper(&self) -> bool {
+        self.inner_copper_index().is_some()
+    }
+    
+    pub fn group(&self) -> LayerGroup {
+        match self {
+            Self::Copper(1) | Self::Silkscreen(Side::Top) | Self::Soldermask(Side::Top) | Self::Paste(Side::Top) => LayerGroup::Top,
+            Self::Copper(2) | Self::Silkscreen(Side::Bottom) | Self::Soldermask(Side::Bottom) | Self::Paste(Side::Bottom) => LayerGroup::Bottom,
+            Self::Copper(_) => LayerGroup::Inner,
+            Self::MechanicalOutline => LayerGroup::Other,
+        }
+    }
+    
+    /// Default draw order (higher is drawn later): top side over inner copper over bottom side
+    pub fn z_order(&self) -> i32 {
+        match self {
+            Self::Paste(Side::Top) => 90,
+            Self::Silkscreen(Side::Top) => 80,
+            Self::Soldermask(Side::Top) => 70,
+            Self::Copper(1) => 60,
+            Self::Copper(2) => 50,
+            Self::Copper(n) => 60 - (*n as i32 - 2).clamp(1, 9), // In1 = 59, In2 = 58, ... above bottom copper
+            Self::Soldermask(Side::Bottom) => 40,
+            Self::Silkscreen(Side::Bottom) => 30,
+            Self::Paste(Side::Bottom) => 20,
+            Self::MechanicalOutline => 10,
+        }
     }
     
     pub fn display_name(&self) -> String {
         match self {
             Self::Copper(1) => "Top Copper (L1)".to_string(),
             Self::Copper(n) if *n == 2 => "Bottom Copper (L2)".to_string(), // For 2-layer
-            Self::Copper(n) => format!("Inner Copper (L{})", n),
+            Self::Copper(n) => format!("Inner Copper (In{})", n.saturating_sub(2)),
             Self::Silkscreen(Side::Top) => "Top Silkscreen".to_string(),
             Self::Silkscreen(Side::Bottom) => "Bottom Silkscreen".to_string(),
             Self::Soldermask(Side::Top) => "Top Soldermask".to_string(),
@@ -113,8 +200,8 @@ impl LayerType {
     pub fn display_name_with_context(&self, total_copper_layers: u8) -> String {
         match self {
             Self::Copper(1) => "Top Copper (L1)".to_string(),
-            Self::Copper(n) if *n == total_copper_layers => format!("Bottom Copper (L{})", n),
-            Self::Copper(n) => format!("Inner Copper (L{})", n),
+            Self::Copper(2) => format!("Bottom Copper (L{})", total_copper_layers),
+            Self::Copper(n) => format!("Inner Copper (L{})", n - 1),
             Self::Silkscreen(Side::Top) => "Top Silkscreen".to_string(),
             Self::Silkscreen(Side::Bottom) => "Bottom Silkscreen".to_string(),
             Self::Soldermask(Side::Top) => "Top Soldermask".to_string(),
@@ -130,10 +217,8 @@ impl LayerType {
             Self::Copper(1) => Color32::from_rgba_premultiplied(184, 115, 51, 220),     // Top: copper
             Self::Copper(2) => Color32::from_rgba_premultiplied(115, 184, 51, 220),     // Bottom: green copper
             Self::Copper(n) => {
-                // Inner layers: different colors for each
-                let hue = (*n as f32 * 60.0) % 360.0; // Spread colors around hue wheel
-                let (r, g, b) = hsv_to_rgb(hue, 0.7, 0.8);
-                Color32::from_rgba_premultiplied((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, 220)
+                let [r, g, b] = INNER_COPPER_COLORS[(n.saturating_sub(3) as usize) % INNER_COPPER_COLORS.len()];
+                Color32::from_rgba_premultiplied(r, g, b, 220)
             },
             Self::Silkscreen(_) => Color32::from_rgba_premultiplied(255, 255, 255, 250),
             Self::Soldermask(Side::Top) => Color32::from_rgba_premultiplied(0, 132, 80, 180),    // Green
@@ -159,6 +244,11 @@ impl LayerType {
         matches!(self, Self::Copper(_))
     }
     
+    /// Check if drill holes go through this layer (copper and soldermask)
+    pub fn is_drilled(&self) -> bool {
+        matches!(self, Self::Copper(_) | Self::Soldermask(_))
+    }
+    
     /// Get copper layer number (if copper layer)
     pub fn copper_layer_number(&self) -> Option<u8> {
         match self {
@@ -176,35 +266,31 @@ impl LayerType {
         }
     }
     
-    /// Check if this is bottom layer (highest copper or bottom side)
-    pub fn is_bottom(&self, total_copper_layers: u8) -> bool {
+    /// Check if this is bottom layer (L2 copper or bottom side)
+    pub fn is_bottom(&self) -> bool {
         match self {
-            Self::Copper(n) => *n == total_copper_layers,
+            Self::Copper(2) => true,
             Self::Silkscreen(Side::Bottom) | Self::Soldermask(Side::Bottom) | Self::Paste(Side::Bottom) => true,
             _ => false,
         }
     }
 }
 
-/// Helper function to convert HSV to RGB
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
-    let c = v * s;
-    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
-    let m = v - c;
-    
-    let (r, g, b) = if h < 60.0 {
-        (c, x, 0.0)
-    } else if h < 120.0 {
-        (x, c, 0.0)
-    } else if h < 180.0 {
-        (0.0, c, x)
-    } else if h < 240.0 {
-        (0.0, x, c)
-    } else if h < 300.0 {
-        (x, 0.0, c)
-    } else {
-        (c, 0.0, x)
-    };
-    
-    (r + m, g + m, b + m)
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inner_copper_stacks_between_top_and_bottom() {
+        let copper: Vec<LayerType> = LayerType::for_layer_count(4).into_iter().filter(LayerType::is_copper).collect();
+        assert_eq!(copper, vec![LayerType::Copper(1), LayerType::inner_copper(1), LayerType::inner_copper(2), LayerType::Copper(2)]);
+        let z: Vec<i32> = copper.iter().map(LayerType::z_order).collect();
+        assert!(z.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", z);
+        assert!(z[3] > LayerType::Soldermask(Side::Bottom).z_order());
+
+        assert_eq!(LayerType::inner_copper(2).inner_copper_index(), Some(2));
+        assert_eq!(LayerType::inner_copper(2).group(), LayerGroup::Inner);
+        assert_eq!(LayerType::inner_copper(1).display_name_with_context(4), "Inner Copper (L2)");
+        assert_ne!(LayerType::inner_copper(1).color(), LayerType::inner_copper(2).color());
+    }
+}
@@ -1,4 +1,5 @@
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Base unit is nanometer (1e-9 meters) stored as u32
 /// This provides ~4.29 meters of range with nanometer precision
@@ -8,15 +9,27 @@ pub type Nanometer = u32;
 /// Extended precision for calculations that might overflow u32
 pub type NanometerExtended = u64;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Unit lengths are shown and entered in; values are always stored in mm or nm
+/// Every conversion for display goes through `from_mm`/`to_mm`, and readouts through `format_mm`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum DisplayUnit {
+    #[default]
     Millimeters,
     Mils,
+    Inches,
     Micrometers,
     Nanometers,
 }
 
 impl DisplayUnit {
+    /// Units offered in the settings and cycled by the units shortcut
+    pub const CHOICES: [DisplayUnit; 4] = [Self::Millimeters, Self::Mils, Self::Inches, Self::Micrometers];
+    
+    /// Unit of a file saved when the only choice was a mils flag
+    pub fn from_legacy_mils(mils: bool) -> Self {
+        if mils { Self::Mils } else { Self::Millimeters }
+    }
+    
     pub fn is_mils(&self) -> bool {
         matches!(self, DisplayUnit::Mils)
     }
@@ -24,6 +37,80 @@ impl DisplayUnit {
     pub fn is_mm(&self) -> bool {
         matches!(self, DisplayUnit::Millimeters)
     }
+    
+    /// Mils and inches; grid presets and the "other unit" hints follow this
+    pub fn is_imperial(&self) -> bool {
+        matches!(self, DisplayUnit::Mils | DisplayUnit::Inches)
+    }
+    
+    /// The unit after this one in `CHOICES`, wrapping round
+    pub fn next(&self) -> Self {
+        let index = Self::CHOICES.iter().position(|unit| unit == self).map_or(0, |i| i + 1);
+        Self::CHOICES[index % Self::CHOICES.len()]
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisplayUnit::Millimeters => "Millimeters (mm)",
+            DisplayUnit::Mils => "Mils (1/1000 inch)",
+            DisplayUnit::Inches => "Inches (in)",
+            DisplayUnit::Micrometers => "Micrometers (µm)",
+            DisplayUnit::Nanometers => "Nanometers (nm)",
+        }
+    }
+    
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            DisplayUnit::Millimeters => "mm",
+            DisplayUnit::Mils => "mils",
+            DisplayUnit::Inches => "in",
+            DisplayUnit::Micrometers => "µm",
+            DisplayUnit::Nanometers => "nm",
+        }
+    }
+    
+    pub fn mm_per_unit(&self) -> f64 {
+        match self {
+            DisplayUnit::Millimeters => 1.0,
+            DisplayUnit::Mils => NM_PER_MIL / NM_PER_MM,
+            DisplayUnit::Inches => NM_PER_INCH / NM_PER_MM,
+            DisplayUnit::Micrometers => NM_PER_UM / NM_PER_MM,
+            DisplayUnit::Nanometers => 1.0 / NM_PER_MM,
+        }
+    }
+    
+    /// A length in mm expressed in this unit
+    pub fn from_mm(&self, mm: f64) -> f64 {
+        mm / self.mm_per_unit()
+    }
+    
+    /// A length in this unit expressed in mm
+    pub fn to_mm(&self, value: f64) -> f64 {
+        value * self.mm_per_unit()
+    }
+    
+    /// Decimals giving about the resolution `mm_decimals` gives in mm
+    /// (3 in mm is 2 in mils, 5 in inches and 0 in µm)
+    pub fn decimals_for(&self, mm_decimals: usize) -> usize {
+        let shift: i32 = match self {
+            DisplayUnit::Millimeters => 0,
+            DisplayUnit::Mils => -1,
+            DisplayUnit::Inches => 2,
+            DisplayUnit::Micrometers => -3,
+            DisplayUnit::Nanometers => -6,
+        };
+        (mm_decimals as i32 + shift).max(0) as usize
+    }
+    
+    /// A length in mm as a number in this unit, without the suffix
+    pub fn format_number(&self, mm: f64, mm_decimals: usize) -> String {
+        format!("{:.prec$}", self.from_mm(mm), prec = self.decimals_for(mm_decimals))
+    }
+    
+    /// "1.500 mm" / "59.06 mils" / "0.05906 in" for a length in mm, at the resolution `mm_decimals` gives in mm
+    pub fn format_mm(&self, mm: f64, mm_decimals: usize) -> String {
+        format!("{} {}", self.format_number(mm, mm_decimals), self.suffix())
+    }
 }
 
 #[derive(Resource, Debug, Clone)]
@@ -44,12 +131,9 @@ impl UnitsResource {
         Self { display_unit }
     }
     
+    /// Step to the next of `DisplayUnit::CHOICES`
     pub fn toggle(&mut self) {
-        self.display_unit = match self.display_unit {
-            DisplayUnit::Millimeters => DisplayUnit::Mils,
-            DisplayUnit::Mils => DisplayUnit::Millimeters,
-            _ => DisplayUnit::Millimeters,
-        };
+        self.display_unit = self.display_unit.next();
     }
     
     pub fn set_mils(&mut self) {
@@ -70,52 +154,29 @@ impl UnitsResource {
     
     /// Convert from nanometers to display units
     pub fn to_display(&self, nm_value: Nanometer) -> f64 {
-        match self.display_unit {
-            DisplayUnit::Nanometers => nm_value as f64,
-            DisplayUnit::Micrometers => nm_value as f64 / NM_PER_UM,
-            DisplayUnit::Millimeters => nm_value as f64 / NM_PER_MM,
-            DisplayUnit::Mils => nm_value as f64 / NM_PER_MIL,
-        }
+        self.display_unit.from_mm(nm_value as f64 / NM_PER_MM)
     }
     
     /// Convert from display units to nanometers
     pub fn from_display(&self, display_value: f64) -> Nanometer {
-        let nm_value = match self.display_unit {
-            DisplayUnit::Nanometers => display_value,
-            DisplayUnit::Micrometers => display_value * NM_PER_UM,
-            DisplayUnit::Millimeters => display_value * NM_PER_MM,
-            DisplayUnit::Mils => display_value * NM_PER_MIL,
-        };
-        nm_value.round() as Nanometer
+        (self.display_unit.to_mm(display_value) * NM_PER_MM).round() as Nanometer
     }
     
     pub fn format_value(&self, nm_value: Nanometer) -> String {
-        let display_value = self.to_display(nm_value);
-        match self.display_unit {
-            DisplayUnit::Nanometers => format!("{:.0} nm", display_value),
-            DisplayUnit::Micrometers => format!("{:.3} µm", display_value),
-            DisplayUnit::Millimeters => format!("{:.3} mm", display_value),
-            DisplayUnit::Mils => format!("{:.1} mils", display_value),
-        }
+        self.display_unit.format_mm(nm_value as f64 / NM_PER_MM, 3)
     }
     
     pub fn format_value_with_precision(&self, nm_value: Nanometer, precision: usize) -> String {
-        let display_value = self.to_display(nm_value);
-        match self.display_unit {
-            DisplayUnit::Nanometers => format!("{:.prec$} nm", display_value, prec = precision),
-            DisplayUnit::Micrometers => format!("{:.prec$} µm", display_value, prec = precision),
-            DisplayUnit::Millimeters => format!("{:.prec$} mm", display_value, prec = precision),
-            DisplayUnit::Mils => format!("{:.prec$} mils", display_value, prec = precision),
-        }
+        format!("{:.prec$} {}", self.to_display(nm_value), self.unit_suffix(), prec = precision)
     }
     
     pub fn unit_suffix(&self) -> &'static str {
-        match self.display_unit {
-            DisplayUnit::Nanometers => "nm",
-            DisplayUnit::Micrometers => "µm",
-            DisplayUnit::Millimeters => "mm",
-            DisplayUnit::Mils => "mils",
-        }
+        self.display_unit.suffix()
+    }
+    
+    /// A length in mm shown in the display unit (see `DisplayUnit::format_mm`)
+    pub fn format_mm(&self, mm: f64, mm_decimals: usize) -> String {
+        self.display_unit.format_mm(mm, mm_decimals)
     }
 }
 
@@ -219,6 +280,25 @@ mod tests {
         assert!((y_mm - 20.0).abs() < 0.001);
     }
     
+    #[test]
+    fn test_display_units_cycle_and_format() {
+        let mut units = UnitsResource::default();
+        let mut seen = Vec::new();
+        for _ in 0..DisplayUnit::CHOICES.len() {
+            seen.push(units.display_unit);
+            units.toggle();
+        }
+        assert_eq!(seen, DisplayUnit::CHOICES);
+        assert!(units.is_mm());
+        
+        assert_eq!(DisplayUnit::Millimeters.format_mm(1.5, 3), "1.500 mm");
+        assert_eq!(DisplayUnit::Mils.format_mm(1.5, 3), "59.06 mils");
+        assert_eq!(DisplayUnit::Inches.format_mm(25.4, 3), "1.00000 in");
+        assert_eq!(DisplayUnit::Micrometers.format_mm(0.0254, 3), "25 µm");
+        assert_eq!(UnitsResource::new(DisplayUnit::Inches).from_display(0.5), 12_700_000);
+        assert_eq!(DisplayUnit::Nanometers.next(), DisplayUnit::Millimeters);
+    }
+    
     #[test]
     fn test_precision_limits() {
         // Test maximum range with u32
@@ -0,0 +1,140 @@
+//! Raster diff between two revisions of a layer
+//!
+//! Both revisions are rasterized on a shared grid; copper only in B is shown as
+//! added (green), copper only in A as removed (red) and shared copper in grey.
+
+use gerber_viewer::BoundingBox;
+use image::{Rgba, RgbaImage};
+
+use super::raster::{primitives_bounds, rasterize_primitives, RasterGrid};
+use crate::ecs::TaggedPrimitive;
+
+/// Default diff resolution (50 µm pixels)
+pub const DIFF_PIXELS_PER_MM: f64 = 20.0;
+
+/// Longest image side; resolution is reduced for large boards
+pub const DIFF_MAX_DIMENSION: u32 = 4096;
+
+pub const DIFF_ADDED_COLOR: Rgba<u8> = Rgba([0, 200, 0, 255]);
+pub const DIFF_REMOVED_COLOR: Rgba<u8> = Rgba([220, 40, 40, 255]);
+pub const DIFF_UNCHANGED_COLOR: Rgba<u8> = Rgba([110, 110, 110, 255]);
+pub const DIFF_BACKGROUND_COLOR: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+/// Pixel counts of a layer diff
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LayerDiffStats {
+    pub added_pixels: usize,
+    pub removed_pixels: usize,
+    pub unchanged_pixels: usize,
+    /// Area covered by one pixel in mm²
+    pub pixel_area_mm2: f64,
+}
+
+impl LayerDiffStats {
+    pub fn added_area_mm2(&self) -> f64 {
+        self.added_pixels as f64 * self.pixel_area_mm2
+    }
+
+    pub fn removed_area_mm2(&self) -> f64 {
+        self.removed_pixels as f64 * self.pixel_area_mm2
+    }
+
+    pub fn is_identical(&self) -> bool {
+        self.added_pixels == 0 && self.removed_pixels == 0
+    }
+}
+
+/// Render the diff of revision `a` against revision `b` at the default resolution
+pub fn render_layer_diff(a: &[TaggedPrimitive], b: &[TaggedPrimitive]) -> RgbaImage {
+    render_layer_diff_with_stats(a, b, DIFF_PIXELS_PER_MM).0
+}
+
+/// Render the diff of `a` against `b` and count changed pixels
+pub fn render_layer_diff_with_stats(
+    a: &[TaggedPrimitive],
+    b: &[TaggedPrimitive],
+    pixels_per_mm: f64,
+) -> (RgbaImage, LayerDiffStats) {
+    let Some(bounds) = combined_bounds(a, b) else {
+        return (RgbaImage::from_pixel(1, 1, DIFF_BACKGROUND_COLOR), LayerDiffStats::default());
+    };
+
+    diff_on_grid(a, b, RasterGrid::covering(&bounds, pixels_per_mm, DIFF_MAX_DIMENSION))
+}
+
+fn diff_on_grid(a: &[TaggedPrimitive], b: &[TaggedPrimitive], grid: RasterGrid) -> (RgbaImage, LayerDiffStats) {
+    let mask_a = rasterize_primitives(a, grid);
+    let mask_b = rasterize_primitives(b, grid);
+
+    let mut stats = LayerDiffStats {
+        pixel_area_mm2: grid.pixel_area(),
+        ..LayerDiffStats::default()
+    };
+    let image = RgbaImage::from_fn(grid.width, grid.height, |x, y| {
+        match (mask_a.get(x, y), mask_b.get(x, y)) {
+            (true, true) => {
+                stats.unchanged_pixels += 1;
+                DIFF_UNCHANGED_COLOR
+            }
+            (false, true) => {
+                stats.added_pixels += 1;
+                DIFF_ADDED_COLOR
+            }
+            (true, false) => {
+                stats.removed_pixels += 1;
+                DIFF_REMOVED_COLOR
+            }
+            (false, false) => DIFF_BACKGROUND_COLOR,
+        }
+    });
+
+    (image, stats)
+}
+
+/// Bounds shared by both revisions, if either has geometry
+pub fn combined_bounds(a: &[TaggedPrimitive], b: &[TaggedPrimitive]) -> Option<BoundingBox> {
+    match (primitives_bounds(a), primitives_bounds(b)) {
+        (Some(mut bounds_a), Some(bounds_b)) => {
+            bounds_a.expand(&bounds_b);
+            Some(bounds_a)
+        }
+        (bounds, None) | (None, bounds) => bounds,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::extract_primitives;
+
+    const SQUARE_A: &str = "%FSLAX46Y46*%\n%MOMM*%\nG36*\nX0Y0D02*\nX2000000Y0D01*\nX2000000Y2000000D01*\nX0Y2000000D01*\nX0Y0D01*\nG37*\nM02*\n";
+    const SQUARE_B: &str = "%FSLAX46Y46*%\n%MOMM*%\nG36*\nX1000000Y0D02*\nX3000000Y0D01*\nX3000000Y2000000D01*\nX1000000Y2000000D01*\nX1000000Y0D01*\nG37*\nM02*\n";
+
+    #[test]
+    fn test_identical_layers_have_no_changes() {
+        let a = extract_primitives(SQUARE_A);
+        let (image, stats) = render_layer_diff_with_stats(&a, &a, 10.0);
+
+        assert!(stats.is_identical());
+        assert_eq!(stats.unchanged_pixels, 20 * 20);
+        assert!(image.pixels().all(|p| *p == DIFF_UNCHANGED_COLOR || *p == DIFF_BACKGROUND_COLOR));
+    }
+
+    #[test]
+    fn test_shifted_layer_reports_added_and_removed_copper() {
+        let a = extract_primitives(SQUARE_A);
+        let b = extract_primitives(SQUARE_B);
+        let (image, stats) = render_layer_diff_with_stats(&a, &b, 10.0);
+
+        assert_eq!((image.width(), image.height()), (30, 20));
+        assert_eq!(stats.removed_pixels, 10 * 20);
+        assert_eq!(stats.added_pixels, 10 * 20);
+        assert_eq!(stats.unchanged_pixels, 10 * 20);
+        assert!((stats.added_area_mm2() - 2.0).abs() < 1e-9);
+
+        // Left column only in A, right column only in B
+        assert_eq!(*image.get_pixel(0, 10), DIFF_REMOVED_COLOR);
+        assert_eq!(*image.get_pixel(29, 10), DIFF_ADDED_COLOR);
+        assert_eq!(*image.get_pixel(15, 10), DIFF_UNCHANGED_COLOR);
+    }
+}
@@ -0,0 +1,353 @@
+//! Fabrication drawing: one SVG page with the board outline, its overall
+//! dimensions, a drill map with a drill chart, a layer legend and a title block
+//!
+//! The page is laid out in millimetres at 1:1 scale, so it prints to size. The
+//! board is drawn in its source orientation (as in the gerbers, not as rotated
+//! or mirrored in the view). Each drill size gets the symbol the drill report
+//! gives it, which marks its holes in the drill map and its rows in the chart.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use bevy_ecs::world::World;
+use nalgebra::{Matrix3, Point2};
+
+use super::raster::{primitives_bounds, transform_primitives};
+use super::svg::escape;
+use crate::ecs::{drill_symbol_style, DrillHole, DrillReport, LayerType, PrimitiveShape, TaggedPrimitive, DRILL_SYMBOL_RING_SPACING};
+use crate::project_manager::database::ProjectMetadata;
+
+const MARGIN_MM: f64 = 10.0;
+/// Distance of the dimension lines from the board
+const DIMENSION_OFFSET_MM: f64 = 8.0;
+const FONT_MM: f64 = 2.5;
+const ROW_MM: f64 = 5.0;
+const LINE_MM: f64 = 0.25;
+/// Chart columns: symbol, diameter, count, plating
+const CHART_COLUMNS_MM: [f64; 4] = [12.0, 26.0, 16.0, 22.0];
+const TITLE_LABEL_MM: f64 = 28.0;
+const TITLE_VALUE_MM: f64 = 72.0;
+const LEGEND_WIDTH_MM: f64 = 60.0;
+
+/// One row of the drill chart: holes of one diameter and plating
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrillChartRow {
+    pub diameter: f64,
+    pub plated: bool,
+    pub count: usize,
+    /// Index of the drill map symbol, shared by both platings of a size
+    pub symbol: usize,
+}
+
+/// Drill report rows split by plating, plated first for the same size
+pub fn drill_chart(report: &DrillReport) -> Vec<DrillChartRow> {
+    report.rows.iter()
+        .flat_map(|row| [(true, row.plated), (false, row.non_plated)].map(|(plated, count)| DrillChartRow {
+            diameter: row.diameter,
+            plated,
+            count,
+            symbol: row.symbol,
+        }))
+        .filter(|row| row.count > 0)
+        .collect()
+}
+
+/// Project details printed in the title block
+#[derive(Debug, Clone, Default)]
+pub struct TitleBlock {
+    pub project: String,
+    pub description: String,
+    pub version: String,
+    pub file: String,
+    pub date: String,
+}
+
+impl TitleBlock {
+    /// Title block of a project, dated today
+    pub fn from_metadata(metadata: Option<&ProjectMetadata>) -> Self {
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        match metadata {
+            Some(metadata) => Self {
+                project: metadata.name.clone(),
+                description: metadata.description.clone(),
+                version: metadata.version.clone(),
+                file: metadata.pcb_file_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+                date,
+            },
+            None => Self { project: "Untitled".to_string(), date, ..Self::default() },
+        }
+    }
+}
+
+/// What went into an exported drawing
+#[derive(Debug, Clone)]
+pub struct FabDrawingResult {
+    pub width_mm: f64,
+    pub height_mm: f64,
+    pub holes: usize,
+    /// The outline is the bounding box of the layers (no mechanical outline loaded)
+    pub inferred_outline: bool,
+}
+
+/// Drill map symbol centred on `center`, drawn like the drill report overlay (see `drill_symbol_style`)
+fn write_symbol(out: &mut String, symbol: usize, center: Point2<f64>, size: f64) {
+    let (x, y, r) = (center.x, center.y, size / 2.0);
+    let (shape, filled, rings) = drill_symbol_style(symbol);
+    let fill = if filled { "black" } else { "none" };
+    for ring in 1..=rings {
+        let _ = writeln!(out, "    <circle cx=\"{x:.3}\" cy=\"{y:.3}\" r=\"{:.3}\" fill=\"none\"/>", r * (1.0 + ring as f64 * DRILL_SYMBOL_RING_SPACING));
+    }
+    let polygon = |corners: &[(f64, f64)]| {
+        let points: Vec<String> = corners.iter().map(|(dx, dy)| format!("{:.3},{:.3}", x + dx * r, y + dy * r)).collect();
+        format!("<polygon points=\"{}\" fill=\"{}\"/>", points.join(" "), fill)
+    };
+    let element = match shape {
+        0 => format!("<circle cx=\"{x:.3}\" cy=\"{y:.3}\" r=\"{r:.3}\" fill=\"{fill}\"/><path d=\"M{:.3} {y:.3}H{:.3}M{x:.3} {:.3}V{:.3}\"/>", x - r, x + r, y - r, y + r),
+        1 => format!("<path d=\"M{:.3} {:.3}L{:.3} {:.3}M{:.3} {:.3}L{:.3} {:.3}\"/>", x - r, y - r, x + r, y + r, x - r, y + r, x + r, y - r),
+        2 => format!("<rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" fill=\"{fill}\"/>", x - r, y - r, size, size),
+        3 => polygon(&[(0.0, -1.0), (0.866, 0.5), (-0.866, 0.5)]),
+        4 => polygon(&[(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]),
+        5 => format!("<circle cx=\"{x:.3}\" cy=\"{y:.3}\" r=\"{r:.3}\" fill=\"{fill}\"/>"),
+        6 => polygon(&[(1.0, 0.0), (0.5, 0.866), (-0.5, 0.866), (-1.0, 0.0), (-0.5, -0.866), (0.5, -0.866)]),
+        _ => format!("<path d=\"M{:.3} {y:.3}H{:.3}M{x:.3} {:.3}V{:.3}\"/>", x - r, x + r, y - r, y + r),
+    };
+    let _ = writeln!(out, "    {}", element);
+}
+
+fn write_text(out: &mut String, x: f64, y: f64, anchor: &str, text: &str) {
+    let _ = writeln!(out, "    <text x=\"{:.3}\" y=\"{:.3}\" text-anchor=\"{}\">{}</text>", x, y, anchor, escape(text));
+}
+
+/// Outline primitives already in page coordinates, as thin strokes
+fn write_outline(out: &mut String, primitives: &[TaggedPrimitive]) {
+    out.push_str("  <g id=\"outline\" fill=\"none\" stroke=\"black\" stroke-linecap=\"round\">\n");
+    for primitive in primitives {
+        let _ = match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => writeln!(
+                out,
+                "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\" stroke-width=\"{:.3}\"/>",
+                start.x, start.y, end.x, end.y, width.max(LINE_MM)
+            ),
+            PrimitiveShape::Flash { center, width, .. } => writeln!(
+                out,
+                "    <circle cx=\"{:.3}\" cy=\"{:.3}\" r=\"{:.3}\" stroke-width=\"{}\"/>",
+                center.x, center.y, width / 2.0, LINE_MM
+            ),
+            PrimitiveShape::Region { points } => {
+                let points: Vec<String> = points.iter().map(|point| format!("{:.3},{:.3}", point.x, point.y)).collect();
+                writeln!(out, "    <polygon points=\"{}\" stroke-width=\"{}\"/>", points.join(" "), LINE_MM)
+            }
+        };
+    }
+    out.push_str("  </g>\n");
+}
+
+/// Dimension line from `a` to `b` with arrow heads, extension lines from the
+/// board corners `from_a` / `from_b` and the length written at its middle
+fn write_dimension(out: &mut String, a: Point2<f64>, b: Point2<f64>, from_a: Point2<f64>, from_b: Point2<f64>, length: f64, vertical: bool) {
+    let _ = writeln!(out, "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\"/>", from_a.x, from_a.y, a.x, a.y);
+    let _ = writeln!(out, "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\"/>", from_b.x, from_b.y, b.x, b.y);
+    let _ = writeln!(out, "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\"/>", a.x, a.y, b.x, b.y);
+    for (tip, tail) in [(a, b), (b, a)] {
+        if let Some([left, right]) = crate::project_manager::annotation::arrow_head(tail, tip, 1.5) {
+            let _ = writeln!(
+                out,
+                "    <polygon points=\"{:.3},{:.3} {:.3},{:.3} {:.3},{:.3}\" fill=\"black\"/>",
+                tip.x, tip.y, left.x, left.y, right.x, right.y
+            );
+        }
+    }
+    let middle = nalgebra::center(&a, &b);
+    let label = escape(&format!("{:.2} mm", length));
+    let _ = if vertical {
+        writeln!(out, "    <text x=\"{0:.3}\" y=\"{1:.3}\" text-anchor=\"middle\" transform=\"rotate(-90 {0:.3} {1:.3})\">{2}</text>", middle.x - 1.0, middle.y, label)
+    } else {
+        writeln!(out, "    <text x=\"{:.3}\" y=\"{:.3}\" text-anchor=\"middle\">{}</text>", middle.x, middle.y - 1.0, label)
+    };
+}
+
+/// Table with a header row; returns its height
+fn write_table(out: &mut String, x: f64, y: f64, columns: &[f64], rows: &[Vec<String>]) -> f64 {
+    let width: f64 = columns.iter().sum();
+    let height = rows.len() as f64 * ROW_MM;
+    let _ = writeln!(out, "    <rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" fill=\"none\"/>", x, y, width, height);
+    for (index, row) in rows.iter().enumerate() {
+        let top = y + index as f64 * ROW_MM;
+        if index > 0 {
+            let _ = writeln!(out, "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\"/>", x, top, x + width, top);
+        }
+        let mut left = x;
+        for (cell, column) in row.iter().zip(columns) {
+            write_text(out, left + 1.5, top + ROW_MM - 1.5, "start", cell);
+            left += column;
+        }
+    }
+    height
+}
+
+/// SVG fab drawing of an outline and holes in board coordinates (mm)
+/// `layers` are listed in the legend in the order given
+pub fn render_svg(outline: &[TaggedPrimitive], holes: &[DrillHole], layers: &[LayerType], title: &TitleBlock) -> Result<String, String> {
+    let bounds = primitives_bounds(outline).ok_or("The board outline has no geometry")?;
+    let (board_width, board_height) = (bounds.max.x - bounds.min.x, bounds.max.y - bounds.min.y);
+    let report = crate::ecs::drill_report(holes);
+    let chart = drill_chart(&report);
+
+    // Board top-left on the page, leaving room for the dimensions above and left of it
+    let origin = Point2::new(MARGIN_MM + DIMENSION_OFFSET_MM + FONT_MM, MARGIN_MM + DIMENSION_OFFSET_MM + FONT_MM);
+    let to_page = Matrix3::new(
+        1.0, 0.0, origin.x - bounds.min.x,
+        0.0, -1.0, origin.y + bounds.max.y,
+        0.0, 0.0, 1.0,
+    );
+    let chart_width: f64 = CHART_COLUMNS_MM.iter().sum();
+    let title_width = TITLE_LABEL_MM + TITLE_VALUE_MM;
+    let page_width = (origin.x + board_width + MARGIN_MM).max(MARGIN_MM * 3.0 + chart_width.max(LEGEND_WIDTH_MM) + title_width);
+    let tables_top = origin.y + board_height + MARGIN_MM;
+
+    let mut body = String::new();
+    write_outline(&mut body, &transform_primitives(outline, &to_page));
+
+    // Overall dimensions
+    body.push_str("  <g id=\"dimensions\" stroke=\"black\" stroke-width=\"0.18\">\n");
+    let (top_left, top_right) = (origin, Point2::new(origin.x + board_width, origin.y));
+    let bottom_left = Point2::new(origin.x, origin.y + board_height);
+    let above = |p: Point2<f64>| Point2::new(p.x, origin.y - DIMENSION_OFFSET_MM);
+    let left_of = |p: Point2<f64>| Point2::new(origin.x - DIMENSION_OFFSET_MM, p.y);
+    write_dimension(&mut body, above(top_left), above(top_right), top_left, top_right, board_width, false);
+    write_dimension(&mut body, left_of(top_left), left_of(bottom_left), top_left, bottom_left, board_height, true);
+    body.push_str("  </g>\n");
+
+    // Drill map
+    let symbol_size = (board_width.max(board_height) / 60.0).clamp(0.8, 2.5);
+    body.push_str("  <g id=\"drill_map\" fill=\"none\" stroke=\"black\" stroke-width=\"0.15\">\n");
+    for hole in holes {
+        write_symbol(&mut body, report.symbol_for(hole.diameter).unwrap_or(0), to_page.transform_point(&hole.position), symbol_size);
+    }
+    body.push_str("  </g>\n");
+
+    // Drill chart, then the layer legend under it
+    body.push_str("  <g id=\"drill_chart\" fill=\"none\" stroke=\"black\" stroke-width=\"0.18\">\n");
+    let mut rows = vec![vec![String::new(), "Diameter".to_string(), "Count".to_string(), "Plating".to_string()]];
+    rows.extend(chart.iter().map(|row| vec![
+        String::new(),
+        format!("{:.3} mm", row.diameter),
+        row.count.to_string(),
+        if row.plated { "PTH" } else { "NPTH" }.to_string(),
+    ]));
+    rows.push(vec![String::new(), "Total".to_string(), holes.len().to_string(), String::new()]);
+    let chart_height = write_table(&mut body, MARGIN_MM, tables_top, &CHART_COLUMNS_MM, &rows);
+    for (index, row) in chart.iter().enumerate() {
+        let center = Point2::new(MARGIN_MM + CHART_COLUMNS_MM[0] / 2.0, tables_top + (index + 1) as f64 * ROW_MM + ROW_MM / 2.0);
+        write_symbol(&mut body, row.symbol, center, 2.5);
+    }
+    body.push_str("  </g>\n");
+
+    let legend_top = tables_top + chart_height + MARGIN_MM;
+    body.push_str("  <g id=\"layer_legend\" fill=\"none\" stroke=\"black\" stroke-width=\"0.18\">\n");
+    let mut rows = vec![vec![String::new(), "Layer".to_string()]];
+    rows.extend(layers.iter().map(|layer| vec![String::new(), layer.display_name()]));
+    let legend_height = write_table(&mut body, MARGIN_MM, legend_top, &[10.0, LEGEND_WIDTH_MM - 10.0], &rows);
+    for (index, layer) in layers.iter().enumerate() {
+        let [r, g, b, _] = layer.color().to_srgba_unmultiplied();
+        let _ = writeln!(
+            body,
+            "    <rect x=\"{:.3}\" y=\"{:.3}\" width=\"6\" height=\"3\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+            MARGIN_MM + 2.0, legend_top + (index + 1) as f64 * ROW_MM + 1.0, r, g, b
+        );
+    }
+    body.push_str("  </g>\n");
+
+    // Title block in the bottom right corner
+    let title_rows: Vec<Vec<String>> = [
+        ("Project", title.project.clone()),
+        ("Description", title.description.clone()),
+        ("Version", title.version.clone()),
+        ("Board size", format!("{:.2} × {:.2} mm", board_width, board_height)),
+        ("Layers", layers.iter().filter(|layer| layer.is_copper()).count().to_string()),
+        ("Holes", holes.len().to_string()),
+        ("File", title.file.clone()),
+        ("Date", title.date.clone()),
+    ]
+    .into_iter()
+    .map(|(label, value)| vec![label.to_string(), value])
+    .collect();
+    let title_height = title_rows.len() as f64 * ROW_MM;
+    let page_height = tables_top + (chart_height + MARGIN_MM + legend_height).max(title_height) + MARGIN_MM;
+    body.push_str("  <g id=\"title_block\" fill=\"none\" stroke=\"black\" stroke-width=\"0.25\">\n");
+    write_table(&mut body, page_width - MARGIN_MM - title_width, page_height - MARGIN_MM - title_height, &[TITLE_LABEL_MM, TITLE_VALUE_MM], &title_rows);
+    body.push_str("  </g>\n");
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.3}mm\" height=\"{1:.3}mm\" viewBox=\"0 0 {0:.3} {1:.3}\">",
+        page_width, page_height
+    );
+    let _ = writeln!(out, "  <style>text {{ font-family: sans-serif; font-size: {}px; fill: black; stroke: none; }}</style>", FONT_MM);
+    out.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    out.push_str(&body);
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+/// Fab drawing of the loaded board
+pub fn render(world: &mut World, metadata: Option<&ProjectMetadata>) -> Result<(String, FabDrawingResult), String> {
+    let (outline, inferred_outline) = crate::ecs::get_board_outline_primitives(world).ok_or("No board outline or layers loaded")?;
+    let holes = crate::ecs::get_drill_holes(world);
+    let layers: Vec<LayerType> = crate::ecs::get_loaded_layer_types(world)
+        .into_iter()
+        .filter(|layer| *layer != LayerType::MechanicalOutline)
+        .collect();
+    let svg = render_svg(&outline, &holes, &layers, &TitleBlock::from_metadata(metadata))?;
+    let bounds = primitives_bounds(&outline).ok_or("The board outline has no geometry")?;
+    Ok((svg, FabDrawingResult {
+        width_mm: bounds.max.x - bounds.min.x,
+        height_mm: bounds.max.y - bounds.min.y,
+        holes: holes.len(),
+        inferred_outline,
+    }))
+}
+
+/// Write the fab drawing of the loaded board to an SVG file
+pub fn export(world: &mut World, metadata: Option<&ProjectMetadata>, path: &Path) -> Result<FabDrawingResult, String> {
+    let (svg, result) = render(world, metadata)?;
+    std::fs::write(path, svg).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Polarity, Side};
+
+    #[test]
+    fn test_drill_chart_and_drawing() {
+        let hole = |x: f64, diameter: f64, plated: bool| DrillHole { position: Point2::new(x, 5.0), diameter, plated };
+        let holes = [hole(1.0, 0.3, true), hole(2.0, 1.0, true), hole(3.0, 0.3, true), hole(4.0, 1.0, false), hole(5.0, 0.2999, true)];
+        let report = crate::ecs::drill_report(&holes);
+        let chart = drill_chart(&report);
+        // Both platings of 1.0 mm share the symbol the drill report overlay draws
+        assert_eq!(chart, vec![
+            DrillChartRow { diameter: 0.3, plated: true, count: 3, symbol: 0 },
+            DrillChartRow { diameter: 1.0, plated: true, count: 1, symbol: 1 },
+            DrillChartRow { diameter: 1.0, plated: false, count: 1, symbol: 1 },
+        ]);
+        assert_eq!(report.symbol_for(1.0), Some(1));
+
+        let corners = [(0.0, 0.0), (40.0, 0.0), (40.0, 20.0), (0.0, 20.0), (0.0, 0.0)];
+        let outline: Vec<TaggedPrimitive> = corners.windows(2)
+            .map(|pair| TaggedPrimitive {
+                net: None,
+                polarity: Polarity::Dark,
+                shape: PrimitiveShape::Segment { start: Point2::new(pair[0].0, pair[0].1), end: Point2::new(pair[1].0, pair[1].1), width: 0.0 },
+            })
+            .collect();
+        let title = TitleBlock { project: "Probe <rev B>".to_string(), ..TitleBlock::default() };
+        let svg = render_svg(&outline, &holes, &[LayerType::Copper(1), LayerType::Silkscreen(Side::Top)], &title).unwrap();
+        assert!(svg.contains(">40.00 mm<") && svg.contains(">20.00 mm<"));
+        assert!(svg.contains(">NPTH<") && svg.contains(">Total<") && svg.contains(">5<"));
+        assert!(svg.contains("Probe &lt;rev B&gt;"));
+        assert!(render_svg(&[], &holes, &[], &title).is_err());
+    }
+}
@@ -0,0 +1,723 @@
+//! RS-274X writer for transformed layers
+//!
+//! Writes the primitives extracted from a layer (see `ecs::primitives`) with a
+//! GerberTransform applied, so rotated/mirrored/re-origined boards can be handed
+//! to panelization tools. Output is metric 4.6 with standard apertures rebuilt
+//! from the primitive sizes. Macro apertures are passed through as written, with
+//! the transform loaded by %LM/%LR/%LS around their flashes. Chord runs that
+//! match the extractor's arc tessellation are written back as circular arcs.
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use gerber_viewer::GerberTransform;
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::ecs::{ApertureOutline, ApertureShape, LayerType, MacroShape, Polarity, PrimitiveShape, Side, TaggedPrimitive, ARC_STEP_DEGREES};
+
+/// Coordinates are written in 4.6 format, i.e. integer nanometres
+const COORDINATE_SCALE: f64 = 1_000_000.0;
+
+/// Axis-alignment tolerance when deciding whether rotated rectangles stay apertures
+const AXIS_EPSILON: f64 = 1e-9;
+
+/// Largest distance (mm) of a chord end from the circle of its run, to still count as an arc
+const ARC_RADIUS_TOLERANCE: f64 = 1e-5;
+
+/// Largest difference (radians) between the angles swept by the chords of one arc
+const ARC_ANGLE_TOLERANCE: f64 = 1e-4;
+
+/// Result of writing a layer
+#[derive(Debug, Clone)]
+pub struct GerberWriteResult {
+    pub content: String,
+    /// Flashes of undefined apertures, whose shape is unknown and which are not written
+    pub skipped_flashes: usize,
+}
+
+/// Aperture key in nanometres so equal sizes share a D code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ApertureKey {
+    Circle(i64),
+    Rectangle(i64, i64),
+    Obround(i64, i64),
+    /// Diameter, vertex count and rotation in millionths of a degree
+    Polygon(i64, usize, i64),
+    /// Index into `GerberBody::macro_apertures`
+    Macro(usize),
+}
+
+/// A macro aperture: the %AM block it instantiates and its %ADD modifiers
+#[derive(Debug, Clone, PartialEq)]
+struct MacroAperture {
+    /// Index into `GerberBody::macros`
+    macro_index: usize,
+    modifiers: String,
+    /// Aperture transform (%LM, %LR, %LS) to flash it with
+    load: LoadTransform,
+}
+
+/// Load mirroring (of x), rotation in degrees and scaling, as written
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LoadTransform {
+    mirror_x: bool,
+    rotation: f64,
+    scale: f64,
+}
+
+impl LoadTransform {
+    const IDENTITY: Self = Self { mirror_x: false, rotation: 0.0, scale: 1.0 };
+}
+
+/// One step of a written path after the current point
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathStep {
+    Line(Point2<f64>),
+    Arc { end: Point2<f64>, center: Point2<f64>, clockwise: bool },
+}
+
+/// Body writer that tracks modal state (aperture, polarity, net, interpolation, load transform)
+struct GerberBody {
+    matrix: Matrix3<f64>,
+    scale: f64,
+    /// Aperture transform that puts a macro written in source axes into output axes
+    macro_load: LoadTransform,
+    apertures: HashMap<ApertureKey, u32>,
+    aperture_order: Vec<ApertureKey>,
+    /// %AM blocks as written: name and statements
+    macros: Vec<(String, Vec<String>)>,
+    macro_apertures: Vec<MacroAperture>,
+    body: String,
+    current_aperture: Option<u32>,
+    current_polarity: Polarity,
+    current_net: Option<String>,
+    /// None for linear interpolation, Some(clockwise) for circular
+    current_interpolation: Option<bool>,
+    current_load: LoadTransform,
+}
+
+impl GerberBody {
+    fn new(transform: &GerberTransform) -> Self {
+        let matrix = transform.to_matrix();
+        // Uniform scale of the linear part (mirroring flips the determinant sign)
+        let determinant = matrix[(0, 0)] * matrix[(1, 1)] - matrix[(0, 1)] * matrix[(1, 0)];
+        // Mirroring comes first in the aperture transform, so take the rotation after undoing it
+        let mirror_x = determinant < 0.0;
+        let (sign, cosine, sine) = (if mirror_x { -1.0 } else { 1.0 }, matrix[(0, 0)], matrix[(1, 0)]);
+        let rotation = (sign * sine).atan2(sign * cosine).to_degrees().rem_euclid(360.0);
+        Self {
+            matrix,
+            scale: determinant.abs().sqrt(),
+            macro_load: LoadTransform { mirror_x, rotation, scale: determinant.abs().sqrt() },
+            apertures: HashMap::new(),
+            aperture_order: Vec::new(),
+            macros: Vec::new(),
+            macro_apertures: Vec::new(),
+            body: String::new(),
+            current_aperture: None,
+            current_polarity: Polarity::Dark,
+            current_net: None,
+            current_interpolation: None,
+            current_load: LoadTransform::IDENTITY,
+        }
+    }
+
+    fn transform_point(&self, point: &Point2<f64>) -> Point2<f64> {
+        self.matrix.transform_point(point)
+    }
+
+    fn transform_vector(&self, vector: Vector2<f64>) -> Vector2<f64> {
+        self.matrix.transform_vector(&vector)
+    }
+
+    fn size_nm(&self, size_mm: f64) -> i64 {
+        (size_mm * self.scale * COORDINATE_SCALE).round() as i64
+    }
+
+    fn nm(value: f64) -> i64 {
+        (value * COORDINATE_SCALE).round() as i64
+    }
+
+    fn coordinate(point: &Point2<f64>) -> String {
+        format!("X{}Y{}", Self::nm(point.x), Self::nm(point.y))
+    }
+
+    fn definition(&self, key: ApertureKey, code: u32) -> String {
+        let mm = |nm: i64| nm as f64 / COORDINATE_SCALE;
+        match key {
+            ApertureKey::Circle(d) => format!("%ADD{}C,{:.6}*%", code, mm(d)),
+            ApertureKey::Rectangle(w, h) => format!("%ADD{}R,{:.6}X{:.6}*%", code, mm(w), mm(h)),
+            ApertureKey::Obround(w, h) => format!("%ADD{}O,{:.6}X{:.6}*%", code, mm(w), mm(h)),
+            ApertureKey::Polygon(d, vertices, rotation) => {
+                format!("%ADD{}P,{:.6}X{}X{:.6}*%", code, mm(d), vertices, rotation as f64 / COORDINATE_SCALE)
+            }
+            ApertureKey::Macro(index) => {
+                let aperture = &self.macro_apertures[index];
+                let name = &self.macros[aperture.macro_index].0;
+                if aperture.modifiers.is_empty() {
+                    format!("%ADD{}{}*%", code, name)
+                } else {
+                    format!("%ADD{}{},{}*%", code, name, aperture.modifiers)
+                }
+            }
+        }
+    }
+
+    fn set_state(&mut self, primitive: &TaggedPrimitive) {
+        if primitive.polarity != self.current_polarity {
+            let code = match primitive.polarity {
+                Polarity::Dark => "LPD",
+                Polarity::Clear => "LPC",
+            };
+            let _ = writeln!(self.body, "%{}*%", code);
+            self.current_polarity = primitive.polarity;
+        }
+        if primitive.net != self.current_net {
+            match &primitive.net {
+                Some(net) => {
+                    let _ = writeln!(self.body, "%TO.N,{}*%", net);
+                }
+                None => self.body.push_str("%TD*%\n"),
+            }
+            self.current_net = primitive.net.clone();
+        }
+    }
+
+    /// Write whichever of %LM, %LR and %LS differ from the current aperture transform
+    fn set_load(&mut self, load: LoadTransform) {
+        if load.mirror_x != self.current_load.mirror_x {
+            let _ = writeln!(self.body, "%LM{}*%", if load.mirror_x { "X" } else { "N" });
+        }
+        if load.rotation != self.current_load.rotation {
+            let _ = writeln!(self.body, "%LR{:.6}*%", load.rotation);
+        }
+        if load.scale != self.current_load.scale {
+            let _ = writeln!(self.body, "%LS{:.6}*%", load.scale);
+        }
+        self.current_load = load;
+    }
+
+    fn select_aperture(&mut self, key: ApertureKey) {
+        let load = match key {
+            ApertureKey::Macro(index) => self.macro_apertures[index].load,
+            _ => LoadTransform::IDENTITY,
+        };
+        self.set_load(load);
+        let code = match self.apertures.get(&key) {
+            Some(code) => *code,
+            None => {
+                let code = 10 + self.aperture_order.len() as u32;
+                self.apertures.insert(key, code);
+                self.aperture_order.push(key);
+                code
+            }
+        };
+        if self.current_aperture != Some(code) {
+            let _ = writeln!(self.body, "D{}*", code);
+            self.current_aperture = Some(code);
+        }
+    }
+
+    /// Aperture key of a macro flash, adding its %AM block the first time it is used
+    /// A different body under a name already taken is written under a suffixed name
+    fn macro_key(&mut self, name: &str, body: &[String], modifiers: &str, load: LoadTransform) -> ApertureKey {
+        let macro_index = match self.macros.iter().position(|(_, existing)| existing == body) {
+            Some(index) => index,
+            None => {
+                let mut unique = name.to_string();
+                let mut suffix = 1;
+                while self.macros.iter().any(|(taken, _)| *taken == unique) {
+                    suffix += 1;
+                    unique = format!("{}_{}", name, suffix);
+                }
+                self.macros.push((unique, body.to_vec()));
+                self.macros.len() - 1
+            }
+        };
+        let aperture = MacroAperture { macro_index, modifiers: modifiers.to_string(), load };
+        let index = match self.macro_apertures.iter().position(|existing| *existing == aperture) {
+            Some(index) => index,
+            None => {
+                self.macro_apertures.push(aperture);
+                self.macro_apertures.len() - 1
+            }
+        };
+        ApertureKey::Macro(index)
+    }
+
+    /// Macro aperture for an outline: the source macro when it has one, otherwise
+    /// a macro generated from the outline's shapes in output axes
+    fn outline_key(&mut self, outline: &ApertureOutline) -> ApertureKey {
+        if let Some(definition) = &outline.definition {
+            let load = LoadTransform { scale: self.macro_load.scale * definition.unit_scale, ..self.macro_load };
+            return self.macro_key(&definition.name, &definition.body, &definition.modifiers, load);
+        }
+        let body: Vec<String> = outline.transformed(&self.matrix).shapes.iter().map(outline_statement).collect();
+        self.macro_key("OUTLINE", &body, "", LoadTransform::IDENTITY)
+    }
+
+    fn set_interpolation(&mut self, interpolation: Option<bool>) {
+        if interpolation != self.current_interpolation {
+            self.body.push_str(match interpolation {
+                None => "G01*\n",
+                Some(true) => "G02*\n",
+                Some(false) => "G03*\n",
+            });
+            self.current_interpolation = interpolation;
+        }
+    }
+
+    /// D01 through every point after the current one, arcs where the chords allow
+    fn path(&mut self, points: &[Point2<f64>]) {
+        let mut from = points[0];
+        for step in path_steps(points) {
+            match step {
+                PathStep::Line(end) => {
+                    self.set_interpolation(None);
+                    let _ = writeln!(self.body, "{}D01*", Self::coordinate(&end));
+                    from = end;
+                }
+                PathStep::Arc { end, center, clockwise } => {
+                    self.set_interpolation(Some(clockwise));
+                    let _ = writeln!(
+                        self.body,
+                        "{}I{}J{}D01*",
+                        Self::coordinate(&end),
+                        Self::nm(center.x - from.x),
+                        Self::nm(center.y - from.y)
+                    );
+                    from = end;
+                }
+            }
+        }
+    }
+
+    /// Stroke a connected polyline with a round aperture
+    fn stroke(&mut self, points: &[Point2<f64>], diameter_nm: i64) {
+        self.select_aperture(ApertureKey::Circle(diameter_nm));
+        let _ = writeln!(self.body, "{}D02*", Self::coordinate(&points[0]));
+        self.path(points);
+    }
+
+    fn flash(&mut self, center: &Point2<f64>, key: ApertureKey) {
+        self.select_aperture(key);
+        let _ = writeln!(self.body, "{}D03*", Self::coordinate(center));
+    }
+
+    fn region(&mut self, points: &[Point2<f64>]) {
+        self.body.push_str("G36*\n");
+        let _ = writeln!(self.body, "{}D02*", Self::coordinate(&points[0]));
+        let closed: Vec<Point2<f64>> = points.iter().chain(std::iter::once(&points[0])).copied().collect();
+        self.path(&closed);
+        self.body.push_str("G37*\n");
+    }
+
+    /// Where the rotated X axis of an aperture ends up: Some(false) = still along X,
+    /// Some(true) = now along Y, None = not axis aligned
+    fn axis_swap(&self) -> Option<bool> {
+        let x_axis = self.transform_vector(Vector2::new(1.0, 0.0));
+        if x_axis.y.abs() < AXIS_EPSILON {
+            Some(false)
+        } else if x_axis.x.abs() < AXIS_EPSILON {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    /// Write every primitive; returns how many flashes were skipped
+    /// Consecutive connected segments of one width, net and polarity are written as one path
+    fn write_primitives(&mut self, primitives: &[TaggedPrimitive]) -> usize {
+        let mut skipped = 0;
+        let mut index = 0;
+        while index < primitives.len() {
+            let primitive = &primitives[index];
+            if let PrimitiveShape::Segment { start, end, width } = &primitive.shape {
+                let mut points = vec![self.transform_point(start), self.transform_point(end)];
+                let mut last_end = *end;
+                index += 1;
+                while let Some(next) = primitives.get(index) {
+                    match &next.shape {
+                        PrimitiveShape::Segment { start, end, width: next_width }
+                            if *start == last_end && next_width == width && next.net == primitive.net && next.polarity == primitive.polarity =>
+                        {
+                            points.push(self.transform_point(end));
+                            last_end = *end;
+                            index += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                self.set_state(primitive);
+                let diameter = self.size_nm(*width);
+                self.stroke(&points, diameter);
+                continue;
+            }
+            if !self.write_primitive(primitive) {
+                skipped += 1;
+            }
+            index += 1;
+        }
+        skipped
+    }
+
+    fn write_primitive(&mut self, primitive: &TaggedPrimitive) -> bool {
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => {
+                self.set_state(primitive);
+                let points = [self.transform_point(start), self.transform_point(end)];
+                let diameter = self.size_nm(*width);
+                self.stroke(&points, diameter);
+            }
+            PrimitiveShape::Region { points } => {
+                self.set_state(primitive);
+                let points: Vec<Point2<f64>> = points.iter().map(|p| self.transform_point(p)).collect();
+                self.region(&points);
+            }
+            PrimitiveShape::Flash { center, width, height, aperture, outline } => {
+                let transformed_center = self.transform_point(center);
+                match (aperture, outline) {
+                    (ApertureShape::Polygon, Some(outline)) => {
+                        self.set_state(primitive);
+                        let key = match outline.transformed(&self.matrix).shapes.first() {
+                            Some(MacroShape::Polygon { points, .. }) if !points.is_empty() => {
+                                let rotation = points[0].y.atan2(points[0].x).to_degrees().rem_euclid(360.0);
+                                ApertureKey::Polygon(self.size_nm(*width), points.len(), Self::nm(rotation))
+                            }
+                            _ => ApertureKey::Circle(self.size_nm(*width)),
+                        };
+                        self.flash(&transformed_center, key);
+                    }
+                    (ApertureShape::Other, Some(outline)) => {
+                        self.set_state(primitive);
+                        let key = self.outline_key(outline);
+                        self.flash(&transformed_center, key);
+                    }
+                    (ApertureShape::Circle | ApertureShape::Polygon, _) => {
+                        self.set_state(primitive);
+                        let key = ApertureKey::Circle(self.size_nm(*width));
+                        self.flash(&transformed_center, key);
+                    }
+                    (ApertureShape::Rectangle | ApertureShape::Obround, _) => {
+                        self.set_state(primitive);
+                        let is_rectangle = *aperture == ApertureShape::Rectangle;
+                        match self.axis_swap() {
+                            Some(swapped) => {
+                                let (w, h) = if swapped { (*height, *width) } else { (*width, *height) };
+                                let (w, h) = (self.size_nm(w), self.size_nm(h));
+                                let key = if is_rectangle { ApertureKey::Rectangle(w, h) } else { ApertureKey::Obround(w, h) };
+                                self.flash(&transformed_center, key);
+                            }
+                            None if is_rectangle => {
+                                let (hw, hh) = (width / 2.0, height / 2.0);
+                                let corners: Vec<Point2<f64>> = [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+                                    .iter()
+                                    .map(|(dx, dy)| self.transform_point(&Point2::new(center.x + dx, center.y + dy)))
+                                    .collect();
+                                self.region(&corners);
+                            }
+                            None => {
+                                // A rotated obround is exactly a round-ended stroke along its long axis
+                                let diameter = width.min(*height);
+                                let half_length = (width.max(*height) - diameter) / 2.0;
+                                let axis = if width >= height { Vector2::new(half_length, 0.0) } else { Vector2::new(0.0, half_length) };
+                                let start = self.transform_point(&(*center - axis));
+                                let end = self.transform_point(&(*center + axis));
+                                let diameter = self.size_nm(diameter);
+                                self.stroke(&[start, end], diameter);
+                            }
+                        }
+                    }
+                    (ApertureShape::Other, None) => return false,
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Macro primitive statement (circle 1 or outline 4) for an evaluated shape in mm
+fn outline_statement(shape: &MacroShape) -> String {
+    let exposure = u8::from(shape.is_dark());
+    match shape {
+        MacroShape::Circle { center, diameter, .. } => {
+            format!("1,{},{:.6},{:.6},{:.6}", exposure, diameter, center.x, center.y)
+        }
+        MacroShape::Polygon { points, .. } => {
+            let vertices: Vec<String> = points
+                .iter()
+                .chain(points.first())
+                .map(|p| format!("{:.6},{:.6}", p.x, p.y))
+                .collect();
+            format!("4,{},{},{},0", exposure, points.len(), vertices.join(","))
+        }
+    }
+}
+
+/// Lines and arcs through `points` (the first is the current point)
+/// A run of at least two chords is an arc when its points lie on one circle and every
+/// chord sweeps the same angle, between half and one extraction step as tessellation cuts them
+fn path_steps(points: &[Point2<f64>]) -> Vec<PathStep> {
+    let mut steps = Vec::new();
+    let mut index = 0;
+    while index + 1 < points.len() {
+        match arc_run(points, index) {
+            Some((end, center, clockwise)) => {
+                steps.push(PathStep::Arc { end: points[end], center, clockwise });
+                index = end;
+            }
+            None => {
+                steps.push(PathStep::Line(points[index + 1]));
+                index += 1;
+            }
+        }
+    }
+    steps
+}
+
+/// Index of the last point of the arc starting at `start`, its centre and direction
+fn arc_run(points: &[Point2<f64>], start: usize) -> Option<(usize, Point2<f64>, bool)> {
+    let center = circumcenter(points[start], *points.get(start + 1)?, *points.get(start + 2)?)?;
+    let radius = (points[start] - center).norm();
+    let sweep_between = |a: Point2<f64>, b: Point2<f64>| {
+        let (a, b) = (a - center, b - center);
+        (a.x * b.y - a.y * b.x).atan2(a.dot(&b))
+    };
+    let step = sweep_between(points[start], points[start + 1]);
+    let max_step = ARC_STEP_DEGREES.to_radians() + ARC_ANGLE_TOLERANCE;
+    if step.abs() > max_step || step.abs() < max_step / 2.0 - 2.0 * ARC_ANGLE_TOLERANCE {
+        return None;
+    }
+
+    let mut end = start + 1;
+    let mut sweep = step;
+    while let Some(next) = points.get(end + 1) {
+        let next_step = sweep_between(points[end], *next);
+        if ((next - center).norm() - radius).abs() > ARC_RADIUS_TOLERANCE
+            || (next_step - step).abs() > ARC_ANGLE_TOLERANCE
+            || (sweep + next_step).abs() > TAU + ARC_ANGLE_TOLERANCE
+        {
+            break;
+        }
+        sweep += next_step;
+        end += 1;
+    }
+    (end >= start + 2).then_some((end, center, step < 0.0))
+}
+
+/// Centre of the circle through three points; None when they are collinear
+fn circumcenter(a: Point2<f64>, b: Point2<f64>, c: Point2<f64>) -> Option<Point2<f64>> {
+    let (ab, ac) = (b - a, c - a);
+    let cross = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+    if cross.abs() < 1e-12 {
+        return None;
+    }
+    let (ab_sq, ac_sq) = (ab.norm_squared(), ac.norm_squared());
+    Some(a + Vector2::new(ac.y * ab_sq - ab.y * ac_sq, ab.x * ac_sq - ac.x * ab_sq) / cross)
+}
+
+/// Write primitives as an RS-274X file with `transform` applied to every coordinate
+pub fn write_transformed_gerber(
+    primitives: &[TaggedPrimitive],
+    transform: &GerberTransform,
+    file_function: Option<&str>,
+) -> GerberWriteResult {
+    let mut body = GerberBody::new(transform);
+    let skipped_flashes = body.write_primitives(primitives);
+
+    let mut content = String::new();
+    content.push_str("G04 Transformed layer exported by CopperForge*\n");
+    let _ = writeln!(content, "%TF.GenerationSoftware,Atlantix-EDA,CopperForge,{}*%", env!("CARGO_PKG_VERSION"));
+    if let Some(file_function) = file_function {
+        let _ = writeln!(content, "%TF.FileFunction,{}*%", file_function);
+    }
+    content.push_str("%FSLAX46Y46*%\n%MOMM*%\n%LPD*%\nG75*\nG01*\n");
+    for (name, statements) in &body.macros {
+        let _ = writeln!(content, "%AM{}*", name);
+        for statement in statements {
+            let _ = writeln!(content, "{}*", statement);
+        }
+        content.push_str("%\n");
+    }
+    for (index, key) in body.aperture_order.iter().enumerate() {
+        let _ = writeln!(content, "{}", body.definition(*key, 10 + index as u32));
+    }
+    content.push_str(&body.body);
+    if body.current_net.is_some() {
+        content.push_str("%TD*%\n");
+    }
+    content.push_str("M02*\n");
+
+    GerberWriteResult { content, skipped_flashes }
+}
+
+/// KiCad-style file suffix and X2 file function for a layer
+pub fn layer_file_suffix(layer_type: LayerType) -> (String, String) {
+    match layer_type {
+        LayerType::Copper(1) => ("F_Cu".to_string(), "Copper,L1,Top".to_string()),
+        LayerType::Copper(2) => ("B_Cu".to_string(), "Copper,L2,Bot".to_string()),
+        LayerType::Copper(n) => (format!("In{}_Cu", n - 2), format!("Copper,L{},Inr", n - 1)),
+        LayerType::Silkscreen(Side::Top) => ("F_Silkscreen".to_string(), "Legend,Top".to_string()),
+        LayerType::Silkscreen(Side::Bottom) => ("B_Silkscreen".to_string(), "Legend,Bot".to_string()),
+        LayerType::Soldermask(Side::Top) => ("F_Mask".to_string(), "Soldermask,Top".to_string()),
+        LayerType::Soldermask(Side::Bottom) => ("B_Mask".to_string(), "Soldermask,Bot".to_string()),
+        LayerType::Paste(Side::Top) => ("F_Paste".to_string(), "Paste,Top".to_string()),
+        LayerType::Paste(Side::Bottom) => ("B_Paste".to_string(), "Paste,Bot".to_string()),
+        LayerType::MechanicalOutline => ("Edge_Cuts".to_string(), "Profile,NP".to_string()),
+    }
+}
+
+/// Write one transformed gerber per layer into `output_dir` as `<base>-<suffix>.gbr`
+/// Returns the written paths and the total number of skipped flashes of undefined apertures
+pub fn export_transformed_layers(
+    layers: &[(LayerType, Vec<TaggedPrimitive>, GerberTransform)],
+    output_dir: &Path,
+    base_name: &str,
+) -> Result<(Vec<PathBuf>, usize), String> {
+    std::fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let mut written = Vec::new();
+    let mut skipped = 0;
+    for (layer_type, primitives, transform) in layers {
+        let (suffix, file_function) = layer_file_suffix(*layer_type);
+        let result = write_transformed_gerber(primitives, transform, Some(&file_function));
+        let path = output_dir.join(format!("{}-{}.gbr", base_name, suffix));
+        std::fs::write(&path, result.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        skipped += result.skipped_flashes;
+        written.push(path);
+    }
+    Ok((written, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::extract_primitives;
+
+    const TOLERANCE_MM: f64 = 0.001;
+
+    fn assert_close(a: &Point2<f64>, b: &Point2<f64>) {
+        assert!((a - b).norm() <= TOLERANCE_MM, "{:?} != {:?}", a, b);
+    }
+
+    fn test_transform(rotation_degrees: f32) -> GerberTransform {
+        GerberTransform {
+            rotation: rotation_degrees.to_radians(),
+            mirroring: crate::display::manager::MirroringSettings { x: true, y: false }.into(),
+            origin: Vector2::new(12.5, -3.25),
+            offset: Vector2::new(-40.0, 7.5),
+            scale: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_matches_transformed_original() {
+        let source = include_str!("../../../../assets/cmod_s7-F_Cu.gbr");
+        let original = extract_primitives(source);
+        let transform = test_transform(90.0);
+        let matrix = transform.to_matrix();
+
+        let exported = write_transformed_gerber(&original, &transform, None);
+        let reimported = extract_primitives(&exported.content);
+
+        // Macro pads are passed through, so nothing is left out
+        assert_eq!(exported.skipped_flashes, 0);
+        assert!(exported.content.contains("%AMRoundRect*"));
+        assert_eq!(reimported.len(), original.len());
+
+        for (before, after) in original.iter().zip(&reimported) {
+            assert_eq!(before.net, after.net);
+            assert_eq!(before.polarity, after.polarity);
+            match (&before.shape, &after.shape) {
+                (PrimitiveShape::Segment { start, end, width }, PrimitiveShape::Segment { start: s2, end: e2, width: w2 }) => {
+                    assert_close(&matrix.transform_point(start), s2);
+                    assert_close(&matrix.transform_point(end), e2);
+                    assert!((width - w2).abs() <= TOLERANCE_MM);
+                }
+                (
+                    PrimitiveShape::Flash { center, width, height, outline, .. },
+                    PrimitiveShape::Flash { center: c2, width: w2, height: h2, outline: o2, .. },
+                ) => {
+                    assert_close(&matrix.transform_point(center), c2);
+                    // 90° rotation swaps the aperture axes
+                    assert!((width - h2).abs() <= TOLERANCE_MM && (height - w2).abs() <= TOLERANCE_MM);
+                    if let (Some(outline), Some(o2)) = (outline, o2) {
+                        let (min, max) = outline.transformed(&matrix).bounds();
+                        let (min2, max2) = o2.bounds();
+                        assert_close(&min, &min2);
+                        assert_close(&max, &max2);
+                    }
+                    assert_eq!(outline.is_some(), o2.is_some());
+                }
+                (PrimitiveShape::Region { points }, PrimitiveShape::Region { points: p2 }) => {
+                    assert_eq!(points.len(), p2.len());
+                    for (a, b) in points.iter().zip(p2) {
+                        assert_close(&matrix.transform_point(a), b);
+                    }
+                }
+                (a, b) => panic!("primitive kind changed: {:?} -> {:?}", a, b),
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_orthogonal_rectangle_becomes_region() {
+        let source = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10R,2.000000X1.000000*%\n%LPC*%\nD10*\nX1000000Y1000000D03*\nM02*\n";
+        let transform = test_transform(30.0);
+        let exported = write_transformed_gerber(&extract_primitives(source), &transform, None);
+        let reimported = extract_primitives(&exported.content);
+
+        assert_eq!(reimported.len(), 1);
+        assert_eq!(reimported[0].polarity, Polarity::Clear);
+        match &reimported[0].shape {
+            PrimitiveShape::Region { points } => {
+                assert_eq!(points.len(), 4);
+                let matrix = transform.to_matrix();
+                assert_close(&matrix.transform_point(&Point2::new(0.0, 0.5)), &points[0]);
+            }
+            other => panic!("expected region, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_arcs_are_written_as_arcs() {
+        // Clockwise quarter circle of radius 2 followed by a straight draw
+        let source = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.200000*%\nG75*\nD10*\n\
+            X0Y2000000D02*\nG02*\nX2000000Y0I0J-2000000D01*\nG01*\nX4000000Y0D01*\nM02*\n";
+        let original = extract_primitives(source);
+        let transform = test_transform(30.0);
+        let matrix = transform.to_matrix();
+        let exported = write_transformed_gerber(&original, &transform, None);
+
+        // Mirroring turns the clockwise arc counter-clockwise
+        assert!(exported.content.contains("G03*"));
+        assert_eq!(exported.content.matches("D01*").count(), 2);
+
+        let reimported = extract_primitives(&exported.content);
+        assert_eq!(reimported.len(), original.len());
+        for (before, after) in original.iter().zip(&reimported) {
+            match (&before.shape, &after.shape) {
+                (PrimitiveShape::Segment { start, end, .. }, PrimitiveShape::Segment { start: s2, end: e2, .. }) => {
+                    assert_close(&matrix.transform_point(start), s2);
+                    assert_close(&matrix.transform_point(end), e2);
+                }
+                (a, b) => panic!("primitive kind changed: {:?} -> {:?}", a, b),
+            }
+        }
+    }
+
+    #[test]
+    fn test_inch_macro_is_scaled_on_load() {
+        let source = "%FSLAX46Y46*%\n%MOIN*%\n%AMBox*\n21,1,$1,$2,0,0,0*%\n%ADD10Box,0.1X0.05*%\nD10*\nX0Y0D03*\nM02*\n";
+        let identity = GerberTransform::from_matrix(&Matrix3::identity());
+        let exported = write_transformed_gerber(&extract_primitives(source), &identity, None);
+        assert!(exported.content.contains("%ADD10Box,0.1X0.05*%"));
+        assert!(exported.content.contains("%LS25.400000*%"));
+
+        let reimported = extract_primitives(&exported.content);
+        let PrimitiveShape::Flash { width, height, .. } = &reimported[0].shape else { panic!("expected a flash") };
+        assert!((width - 2.54).abs() <= TOLERANCE_MM && (height - 1.27).abs() <= TOLERANCE_MM);
+    }
+}
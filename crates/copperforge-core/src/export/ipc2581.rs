@@ -0,0 +1,391 @@
+//! Minimal IPC-2581 (revision B) writer
+//!
+//! One XML file with the layer stack, the copper, mask, silkscreen and paste
+//! features, the drills and the board profile of the loaded design. Extracted
+//! primitives map to features the same way as in the ODB++ writer: segments are
+//! lines with a round line description, flashes are pads referencing a
+//! standard primitive and regions are contours. Components, nets and macro
+//! aperture flashes are not written.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use bevy_ecs::world::World;
+use nalgebra::Point2;
+
+use crate::ecs::{ApertureShape, DrillHole, LayerType, Polarity, PrimitiveShape, Side, Stackup, TaggedPrimitive};
+
+const STEP_NAME: &str = "pcb";
+
+/// Escape text for use in an XML attribute
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Standard primitive for a flash, sizes in nanometres so equal sizes share an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StandardPrimitive {
+    Circle(i64),
+    Rectangle(i64, i64),
+    Oval(i64, i64),
+}
+
+impl StandardPrimitive {
+    fn element(&self) -> String {
+        let mm = |nm: i64| nm as f64 / 1_000_000.0;
+        match *self {
+            StandardPrimitive::Circle(d) => format!("<Circle diameter=\"{:.6}\"/>", mm(d)),
+            StandardPrimitive::Rectangle(w, h) => format!("<RectCenter width=\"{:.6}\" height=\"{:.6}\"/>", mm(w), mm(h)),
+            StandardPrimitive::Oval(w, h) => format!("<Oval width=\"{:.6}\" height=\"{:.6}\"/>", mm(w), mm(h)),
+        }
+    }
+}
+
+fn nm(mm: f64) -> i64 {
+    (mm * 1_000_000.0).round() as i64
+}
+
+/// IPC-2581 layer name, function and side
+pub fn ipc_layer(layer_type: LayerType) -> (String, &'static str, &'static str) {
+    let side = |side: Side| if side == Side::Top { "TOP" } else { "BOTTOM" };
+    match layer_type {
+        LayerType::Copper(1) => ("TOP".to_string(), "CONDUCTOR", "TOP"),
+        LayerType::Copper(2) => ("BOTTOM".to_string(), "CONDUCTOR", "BOTTOM"),
+        LayerType::Copper(n) => (format!("INNER{}", n - 2), "CONDUCTOR", "INTERNAL"),
+        LayerType::Soldermask(s) => (format!("SOLDERMASK_{}", side(s)), "SOLDERMASK", side(s)),
+        LayerType::Silkscreen(s) => (format!("SILKSCREEN_{}", side(s)), "SILKSCREEN", side(s)),
+        LayerType::Paste(s) => (format!("PASTE_{}", side(s)), "SOLDERPASTE", side(s)),
+        LayerType::MechanicalOutline => ("OUTLINE".to_string(), "BOARD_OUTLINE", "ALL"),
+    }
+}
+
+/// Dictionaries shared by every layer's features
+#[derive(Default)]
+struct Dictionaries {
+    primitives: HashMap<StandardPrimitive, usize>,
+    primitive_order: Vec<StandardPrimitive>,
+    line_widths: HashMap<i64, usize>,
+    line_width_order: Vec<i64>,
+}
+
+impl Dictionaries {
+    fn primitive_id(&mut self, primitive: StandardPrimitive) -> String {
+        let index = *self.primitives.entry(primitive).or_insert_with(|| {
+            self.primitive_order.push(primitive);
+            self.primitive_order.len() - 1
+        });
+        format!("PRIMITIVE_{}", index + 1)
+    }
+
+    fn line_id(&mut self, width_nm: i64) -> String {
+        let index = *self.line_widths.entry(width_nm).or_insert_with(|| {
+            self.line_width_order.push(width_nm);
+            self.line_width_order.len() - 1
+        });
+        format!("LINE_{}", index + 1)
+    }
+
+    fn write(&self, out: &mut String) {
+        out.push_str("    <DictionaryStandard units=\"MILLIMETER\">\n");
+        for (index, primitive) in self.primitive_order.iter().enumerate() {
+            let _ = writeln!(out, "      <EntryStandard id=\"PRIMITIVE_{}\">{}</EntryStandard>", index + 1, primitive.element());
+        }
+        out.push_str("    </DictionaryStandard>\n");
+        out.push_str("    <DictionaryLineDesc units=\"MILLIMETER\">\n");
+        for (index, width) in self.line_width_order.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "      <EntryLineDesc id=\"LINE_{}\"><LineDesc lineEnd=\"ROUND\" lineWidth=\"{:.6}\"/></EntryLineDesc>",
+                index + 1,
+                *width as f64 / 1_000_000.0
+            );
+        }
+        out.push_str("    </DictionaryLineDesc>\n");
+    }
+}
+
+fn polygon(points: &[Point2<f64>], indent: &str) -> String {
+    contour("Polygon", points, indent)
+}
+
+/// Closed contour element (`<Polygon>`, `<Cutout>`) from its vertices
+fn contour(tag: &str, points: &[Point2<f64>], indent: &str) -> String {
+    let mut out = format!("{}<{}>\n", indent, tag);
+    let _ = writeln!(out, "{}  <PolyBegin x=\"{:.6}\" y=\"{:.6}\"/>", indent, points[0].x, points[0].y);
+    for point in points.iter().skip(1).chain(std::iter::once(&points[0])) {
+        let _ = writeln!(out, "{}  <PolyStepSegment x=\"{:.6}\" y=\"{:.6}\"/>", indent, point.x, point.y);
+    }
+    let _ = writeln!(out, "{}</{}>", indent, tag);
+    out
+}
+
+/// `<Set>` for one primitive; None for macro flashes, whose shape is unknown
+fn feature_set(primitive: &TaggedPrimitive, dictionaries: &mut Dictionaries) -> Option<String> {
+    let polarity = match primitive.polarity {
+        Polarity::Dark => "POSITIVE",
+        Polarity::Clear => "NEGATIVE",
+    };
+    let body = match &primitive.shape {
+        PrimitiveShape::Segment { start, end, width } => format!(
+            "<Features><Line startX=\"{:.6}\" startY=\"{:.6}\" endX=\"{:.6}\" endY=\"{:.6}\"><LineDescRef id=\"{}\"/></Line></Features>",
+            start.x,
+            start.y,
+            end.x,
+            end.y,
+            dictionaries.line_id(nm(*width))
+        ),
+        PrimitiveShape::Flash { center, width, height, aperture, .. } => {
+            let standard = match aperture {
+                ApertureShape::Circle | ApertureShape::Polygon => StandardPrimitive::Circle(nm(*width)),
+                ApertureShape::Rectangle => StandardPrimitive::Rectangle(nm(*width), nm(*height)),
+                ApertureShape::Obround => StandardPrimitive::Oval(nm(*width), nm(*height)),
+                ApertureShape::Other => return None,
+            };
+            format!(
+                "<Pad><Location x=\"{:.6}\" y=\"{:.6}\"/><StandardPrimitiveRef id=\"{}\"/></Pad>",
+                center.x,
+                center.y,
+                dictionaries.primitive_id(standard)
+            )
+        }
+        PrimitiveShape::Region { points } if points.len() >= 3 => {
+            format!("<Features><Contour>\n{}          </Contour></Features>", polygon(points, "            "))
+        }
+        PrimitiveShape::Region { .. } => return None,
+    };
+    Some(format!("        <Set polarity=\"{}\">{}</Set>\n", polarity, body))
+}
+
+/// `<LayerFeature>` for a layer and the number of skipped macro flashes
+fn layer_feature(name: &str, primitives: &[TaggedPrimitive], dictionaries: &mut Dictionaries) -> (String, usize) {
+    let mut out = format!("      <LayerFeature layerRef=\"{}\">\n", name);
+    let mut skipped = 0;
+    for primitive in primitives {
+        match feature_set(primitive, dictionaries) {
+            Some(set) => out.push_str(&set),
+            None => skipped += 1,
+        }
+    }
+    out.push_str("      </LayerFeature>\n");
+    (out, skipped)
+}
+
+fn drill_feature(name: &str, holes: &[DrillHole]) -> String {
+    let mut out = format!("      <LayerFeature layerRef=\"{}\">\n", name);
+    for (index, hole) in holes.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "        <Set><Hole name=\"H{}\" diameter=\"{:.6}\" platingStatus=\"{}\" plusTol=\"0\" minusTol=\"0\" x=\"{:.6}\" y=\"{:.6}\"/></Set>",
+            index + 1,
+            hole.diameter,
+            if hole.plated { "PLATED" } else { "NONPLATED" },
+            hole.position.x,
+            hole.position.y
+        );
+    }
+    out.push_str("      </LayerFeature>\n");
+    out
+}
+
+/// Layer stack top to bottom as (layer name, thickness in mm)
+/// The core is split evenly between the loaded inner copper layers
+fn stackup_layers(loaded: &[LayerType], stackup: &Stackup) -> Vec<(String, f64)> {
+    let inner: Vec<LayerType> = loaded.iter().copied().filter(LayerType::is_inner_copper).collect();
+    let dielectric = stackup.board_thickness / (inner.len() + 1) as f64;
+    let outer = |layer_type: LayerType, thickness: f64| loaded.contains(&layer_type).then(|| (ipc_layer(layer_type).0, thickness));
+
+    let mut rows: Vec<(String, f64)> = [
+        outer(LayerType::Soldermask(Side::Top), stackup.soldermask_thickness),
+        outer(LayerType::TOP_COPPER, stackup.copper_thickness),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    for (index, layer_type) in inner.iter().enumerate() {
+        rows.push((format!("DIELECTRIC_{}", index + 1), dielectric));
+        rows.push((ipc_layer(*layer_type).0, stackup.copper_thickness));
+    }
+    rows.push((format!("DIELECTRIC_{}", inner.len() + 1), dielectric));
+    rows.extend([
+        outer(LayerType::BOTTOM_COPPER, stackup.copper_thickness),
+        outer(LayerType::Soldermask(Side::Bottom), stackup.soldermask_thickness),
+    ]
+    .into_iter()
+    .flatten());
+    rows
+}
+
+/// Result of an IPC-2581 export
+#[derive(Debug, Clone)]
+pub struct Ipc2581ExportResult {
+    pub layers: usize,
+    pub holes: usize,
+    /// Macro aperture flashes that could not be written
+    pub skipped_flashes: usize,
+}
+
+/// Build the IPC-2581 document for the loaded design
+pub fn write_document(world: &mut World, design_name: &str) -> Result<(String, Ipc2581ExportResult), String> {
+    let mut loaded: Vec<LayerType> = LayerType::all()
+        .into_iter()
+        .filter(|layer_type| crate::ecs::get_layer_by_type(world, *layer_type).is_some())
+        .collect();
+    if loaded.is_empty() {
+        return Err("No layers loaded".to_string());
+    }
+    loaded.sort_by_key(|layer_type| super::odb::stack_order(*layer_type));
+    let stackup = world.get_resource::<Stackup>().copied().unwrap_or_default();
+    let holes = crate::ecs::get_drill_holes(world);
+    let design_name = escape(design_name);
+
+    let mut dictionaries = Dictionaries::default();
+    let mut layer_features = String::new();
+    let mut skipped_flashes = 0;
+    for layer_type in &loaded {
+        let (name, ..) = ipc_layer(*layer_type);
+        let (feature, skipped) = layer_feature(&name, &crate::ecs::get_layer_primitives(world, *layer_type), &mut dictionaries);
+        layer_features.push_str(&feature);
+        skipped_flashes += skipped;
+    }
+    let drill_layers: Vec<(&str, Vec<DrillHole>)> = [("DRILL_PTH", true), ("DRILL_NPTH", false)]
+        .into_iter()
+        .map(|(name, plated)| (name, holes.iter().filter(|hole| hole.plated == plated).copied().collect::<Vec<_>>()))
+        .filter(|(_, holes)| !holes.is_empty())
+        .collect();
+    for (name, holes) in &drill_layers {
+        layer_features.push_str(&drill_feature(name, holes));
+    }
+
+    let stack = stackup_layers(&loaded, &stackup);
+    let mut layer_defs: Vec<(String, &str, &str)> = loaded.iter().map(|layer_type| ipc_layer(*layer_type)).collect();
+    layer_defs.extend(stack.iter()
+        .filter(|(name, _)| name.starts_with("DIELECTRIC_"))
+        .map(|(name, _)| (name.clone(), "DIELCORE", "INTERNAL")));
+    layer_defs.extend(drill_layers.iter().map(|(name, _)| (name.to_string(), "DRILL", "ALL")));
+
+    let now = chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<IPC-2581 revision=\"B\" xmlns=\"http://webstds.ipc.org/2581\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n");
+    xml.push_str("  <Content roleRef=\"Owner\">\n    <FunctionMode mode=\"FABRICATION\"/>\n");
+    let _ = writeln!(xml, "    <StepRef name=\"{}\"/>", STEP_NAME);
+    for (name, ..) in &layer_defs {
+        let _ = writeln!(xml, "    <LayerRef name=\"{}\"/>", name);
+    }
+    dictionaries.write(&mut xml);
+    xml.push_str("  </Content>\n");
+    xml.push_str("  <LogisticHeader>\n    <Role id=\"Owner\" roleFunction=\"SENDER\"/>\n");
+    xml.push_str("    <Enterprise id=\"CopperForge\" code=\"NONE\"/>\n");
+    xml.push_str("    <Person name=\"CopperForge\" enterpriseRef=\"CopperForge\" roleRef=\"Owner\"/>\n  </LogisticHeader>\n");
+    let _ = write!(
+        xml,
+        "  <HistoryRecord number=\"1\" origination=\"{now}\" software=\"CopperForge\" lastChange=\"{now}\">\n    <FileRevision fileRevisionId=\"1\" comment=\"\">\n      <SoftwarePackage name=\"CopperForge\" revision=\"{}\" vendor=\"Atlantix-EDA\"><Certification certificationStatus=\"SELFTEST\"/></SoftwarePackage>\n    </FileRevision>\n  </HistoryRecord>\n",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let _ = writeln!(xml, "  <Ecad name=\"{}\">", design_name);
+    xml.push_str("    <CadHeader units=\"MILLIMETER\"/>\n");
+    xml.push_str("    <CadData>\n");
+    for (name, function, side) in &layer_defs {
+        let _ = writeln!(xml, "      <Layer name=\"{}\" layerFunction=\"{}\" side=\"{}\" polarity=\"POSITIVE\"/>", name, function, side);
+    }
+    let overall: f64 = stack.iter().map(|(_, thickness)| thickness).sum();
+    let _ = writeln!(xml, "      <Stackup name=\"{}\" overallThickness=\"{:.6}\" tolPlus=\"0\" tolMinus=\"0\" whereMeasured=\"METAL\">", design_name, overall);
+    let _ = writeln!(xml, "        <StackupGroup name=\"GROUP_1\" thickness=\"{:.6}\" tolPlus=\"0\" tolMinus=\"0\">", overall);
+    for (index, (name, thickness)) in stack.iter().enumerate() {
+        let _ = writeln!(
+            xml,
+            "          <StackupLayer layerOrGroupRef=\"{}\" thickness=\"{:.6}\" tolPlus=\"0\" tolMinus=\"0\" sequence=\"{}\"/>",
+            name,
+            thickness,
+            index + 1
+        );
+    }
+    xml.push_str("        </StackupGroup>\n      </Stackup>\n");
+
+    let _ = writeln!(xml, "      <Step name=\"{}\">", STEP_NAME);
+    xml.push_str("        <Datum x=\"0\" y=\"0\"/>\n");
+    if let Some((primitives, _)) = crate::ecs::get_board_outline_primitives(world) {
+        xml.push_str(&profile(&primitives));
+    }
+    xml.push_str(&layer_features.lines().map(|line| format!("  {}\n", line)).collect::<String>());
+    xml.push_str("      </Step>\n    </CadData>\n  </Ecad>\n</IPC-2581>\n");
+
+    let result = Ipc2581ExportResult { layers: layer_defs.len(), holes: holes.len(), skipped_flashes };
+    Ok((xml, result))
+}
+
+/// `<Profile>`: the board edge as drawn, with its cutouts; empty without a usable outline
+fn profile(outline: &[TaggedPrimitive]) -> String {
+    let (outer, cutouts) = crate::ecs::board_profile(outline);
+    if outer.len() < 3 {
+        return String::new();
+    }
+    let mut out = format!("        <Profile>\n{}", polygon(&outer, "          "));
+    for cutout in cutouts.iter().filter(|cutout| cutout.len() >= 3) {
+        out.push_str(&contour("Cutout", cutout, "          "));
+    }
+    out.push_str("        </Profile>\n");
+    out
+}
+
+/// Write the loaded design as an IPC-2581 file
+pub fn export(world: &mut World, path: &Path) -> Result<Ipc2581ExportResult, String> {
+    let design_name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_else(|| "board".to_string());
+    let (xml, result) = write_document(world, &design_name)?;
+    std::fs::write(path, xml).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stackup_and_features() {
+        let loaded = [LayerType::TOP_COPPER, LayerType::Copper(3), LayerType::BOTTOM_COPPER, LayerType::Soldermask(Side::Top)];
+        let stack = stackup_layers(&loaded, &Stackup::default());
+        let names: Vec<&str> = stack.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["SOLDERMASK_TOP", "TOP", "DIELECTRIC_1", "INNER1", "DIELECTRIC_2", "BOTTOM"]);
+        let dielectric: f64 = stack.iter().filter(|(name, _)| name.starts_with("DIELECTRIC")).map(|(_, t)| t).sum();
+        assert!((dielectric - 1.6).abs() < 1e-9);
+
+        let mut dictionaries = Dictionaries::default();
+        let segment = TaggedPrimitive {
+            net: None,
+            polarity: Polarity::Clear,
+            shape: PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(1.0, 0.0), width: 0.25 },
+        };
+        let set = feature_set(&segment, &mut dictionaries).expect("segments are written");
+        assert!(set.contains("polarity=\"NEGATIVE\"") && set.contains("<LineDescRef id=\"LINE_1\"/>"));
+        assert_eq!(dictionaries.line_width_order, vec![250_000]);
+        assert_eq!(escape("A&B <\"rev\">"), "A&amp;B &lt;&quot;rev&quot;&gt;");
+    }
+
+    #[test]
+    fn test_profile_follows_concave_outline_and_cutouts() {
+        let segment = |a: (f64, f64), b: (f64, f64)| TaggedPrimitive {
+            net: None,
+            polarity: Polarity::Dark,
+            shape: PrimitiveShape::Segment { start: Point2::new(a.0, a.1), end: Point2::new(b.0, b.1), width: 0.1 },
+        };
+        let mut outline = Vec::new();
+        for corners in [
+            &[(0.0, 0.0), (20.0, 0.0), (20.0, 8.0), (8.0, 8.0), (8.0, 20.0), (0.0, 20.0)][..],
+            &[(13.0, 3.0), (15.0, 3.0), (15.0, 5.0), (13.0, 5.0)][..],
+        ] {
+            outline.extend((0..corners.len()).map(|i| segment(corners[i], corners[(i + 1) % corners.len()])));
+        }
+
+        let xml = profile(&outline);
+        let polygon = &xml[xml.find("<Polygon>").unwrap()..xml.find("</Polygon>").unwrap()];
+        // Begin plus a step to every vertex and back to the start
+        assert_eq!(polygon.matches("<PolyStepSegment").count(), 6);
+        assert!(polygon.contains("x=\"8.000000\" y=\"8.000000\""), "concave corner kept: {}", polygon);
+        assert_eq!(xml.matches("<Cutout>").count(), 1);
+        assert!(profile(&outline[..1]).is_empty());
+    }
+}
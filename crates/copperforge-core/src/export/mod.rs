@@ -1,64 +1,197 @@
-use std::path::PathBuf;
+pub mod gerber;
+pub mod raster;
+pub mod diff;
+pub mod svg;
+pub mod odb;
+pub mod ipc2581;
+pub mod fab_drawing;
+
+use std::path::{Path, PathBuf};
 use image::{ImageBuffer, Rgba, RgbaImage};
-use eframe::emath::{Rect, Vec2};
+use serde::{Deserialize, Serialize};
+use bevy_ecs::world::World;
+use egui::emath::{Rect, Vec2};
 use egui::Pos2;
-use gerber_viewer::{ViewState, BoundingBox, GerberTransform};
-use crate::{DemoLensApp, ecs::LayerType};
-use crate::display::VectorOffset;
-use nalgebra::{Vector2, Point2};
+use gerber_viewer::{ViewState, BoundingBox};
+use crate::display::DisplayManager;
+use crate::ecs::{ApertureShape, LayerType, Polarity, PrimitiveShape, TaggedPrimitive};
+use crate::project_manager::annotation::{arrow_head, Annotation, AnnotationKind};
+use nalgebra::{Matrix3, Point2};
+use raster::{primitives_bounds, rasterize_primitives, transform_primitives, CoverageMask, RasterGrid};
+
+/// Largest width or height accepted for an exported PNG
+pub const MAX_PNG_DIMENSION: u32 = 16384;
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// Stroke width, note marker diameter and arrow head length of exported annotations (pixels)
+const ANNOTATION_STROKE_PX: f64 = 3.0;
+const ANNOTATION_NOTE_PX: f64 = 12.0;
+const ANNOTATION_ARROW_HEAD_PX: f64 = 16.0;
+
+/// How the pixel size of an exported PNG is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PngResolution {
+    /// Fixed image size; the board is fitted inside
+    Pixels { width: u32, height: u32 },
+    /// Image size follows the physical board size at this many dots per inch
+    Dpi(f32),
+}
+
+impl PngResolution {
+    /// Image size for the given board bounding box (mm), rejecting absurd sizes
+    pub fn image_size(&self, bbox: &BoundingBox) -> Result<(u32, u32), String> {
+        let (width, height) = match *self {
+            PngResolution::Pixels { width, height } => (width as f64, height as f64),
+            PngResolution::Dpi(dpi) => {
+                if dpi <= 0.0 {
+                    return Err("DPI must be greater than zero".to_string());
+                }
+                let pixels_per_mm = dpi as f64 / MM_PER_INCH;
+                ((bbox.width() * pixels_per_mm).round(), (bbox.height() * pixels_per_mm).round())
+            }
+        };
+
+        if width < 1.0 || height < 1.0 {
+            return Err("Image size must be at least 1×1 pixels".to_string());
+        }
+        if width > MAX_PNG_DIMENSION as f64 || height > MAX_PNG_DIMENSION as f64 {
+            return Err(format!(
+                "Image would be {:.0}×{:.0} px; the limit is {} px per side. Lower the DPI or size.",
+                width, height, MAX_PNG_DIMENSION
+            ));
+        }
+        Ok((width as u32, height as u32))
+    }
+}
+
+/// PNG export options, remembered in the project config
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PngExportSettings {
+    pub resolution: PngResolution,
+}
+
+impl Default for PngExportSettings {
+    fn default() -> Self {
+        Self {
+            resolution: PngResolution::Pixels { width: 2048, height: 2048 },
+        }
+    }
+}
+
+/// Save an image as PNG with pHYs metadata so documents size it physically
+pub fn save_png_with_dpi(img: &RgbaImage, path: &Path, dpi: f64) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), img.width(), img.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let pixels_per_meter = (dpi / MM_PER_INCH * 1000.0).round() as u32;
+    encoder.set_pixel_dims(Some(png::PixelDimensions {
+        xppu: pixels_per_meter,
+        yppu: pixels_per_meter,
+        unit: png::Unit::Meter,
+    }));
+
+    let mut writer = encoder.write_header().map_err(|e| format!("Failed to write PNG header: {}", e))?;
+    writer.write_image_data(img.as_raw()).map_err(|e| format!("Failed to save PNG: {}", e))
+}
 
 #[allow(dead_code)]
 pub struct PngExporter;
 
+/// A layer prepared for export, with geometry already in view coordinates
+struct LayerExport {
+    layer_type: LayerType,
+    primitives: Vec<TaggedPrimitive>,
+    /// Board outline drawn with the layer's own transform for reference
+    outline: Vec<TaggedPrimitive>,
+    annotations: Vec<AnnotationExport>,
+}
+
+/// Annotation shape in view coordinates; text is not rasterized, notes become markers
+struct AnnotationExport {
+    kind: AnnotationKind,
+    /// As `Annotation::outline`
+    points: Vec<Point2<f64>>,
+    color: egui::Color32,
+}
+
+impl AnnotationExport {
+    /// Strokes and markers sized in pixels of a grid with `pixels_per_mm`
+    fn primitives(&self, pixels_per_mm: f64) -> Vec<TaggedPrimitive> {
+        let width = ANNOTATION_STROKE_PX / pixels_per_mm;
+        let dark = |shape| TaggedPrimitive { net: None, polarity: Polarity::Dark, shape };
+        let segment = |start: Point2<f64>, end: Point2<f64>| dark(PrimitiveShape::Segment { start, end, width });
+        match (self.kind, self.points.as_slice()) {
+            (AnnotationKind::Note, [center]) => {
+                let diameter = ANNOTATION_NOTE_PX / pixels_per_mm;
+                vec![dark(PrimitiveShape::Flash { center: *center, width: diameter, height: diameter, aperture: ApertureShape::Circle, outline: None })]
+            }
+            (AnnotationKind::Arrow, [tail, tip]) => {
+                let mut strokes = vec![segment(*tail, *tip)];
+                if let Some(head) = arrow_head(*tail, *tip, ANNOTATION_ARROW_HEAD_PX / pixels_per_mm) {
+                    strokes.extend(head.map(|end| segment(*tip, end)));
+                }
+                strokes
+            }
+            (_, points) => points.iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(start, end)| segment(*start, *end))
+                .collect(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 impl PngExporter {
     /// Export each layer in quadrant view as a separate PNG file
-    pub fn export_quadrant_layers(app: &mut DemoLensApp, output_dir: &PathBuf, width: u32, height: u32) -> Result<Vec<PathBuf>, String> {
-        if !app.display_manager.quadrant_view_enabled {
+    /// Layers are rasterized from their geometry with the same transform used on screen;
+    /// `annotations` are drawn over every layer (empty to leave them out)
+    pub fn export_quadrant_layers(
+        world: &mut World,
+        display_manager: &DisplayManager,
+        output_dir: &PathBuf,
+        resolution: PngResolution,
+        annotations: &[Annotation],
+    ) -> Result<Vec<PathBuf>, String> {
+        if !display_manager.quadrant_view_enabled {
             return Err("Quadrant view must be enabled for layer export".to_string());
         }
 
-        std::fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create output directory: {}", e))?;
-        
-        // Get mechanical outline layer using ECS - this defines the consistent bounding box for all exports
-        let (mechanical_outline_gerber, master_bbox) = {
-            let mechanical_outline_data = crate::ecs::get_layer_data(&mut app.ecs_world, LayerType::MechanicalOutline)
-                .ok_or("Mechanical outline layer is required for consistent PNG export boundaries")?;
-            let gerber_layer = mechanical_outline_data.2.0.clone();
-            let bbox = Self::calculate_master_bounding_box(app, &gerber_layer)?;
-            (gerber_layer, bbox)
-        };
-        
-        let mut exported_files = Vec::new();
-        
         // Collect visible layers data first to avoid borrowing conflicts
-        let mut layers_to_export = Vec::new();
-        for layer_type in LayerType::all() {
-            if let Some((_entity, _layer_info, gerber_data, visibility)) = crate::ecs::get_layer_data(&mut app.ecs_world, layer_type) {
-                if visibility.visible && layer_type != LayerType::MechanicalOutline {
-                    // Skip if layer shouldn't render for current view
-                    if layer_type.should_render(app.display_manager.showing_top) {
-                        layers_to_export.push((layer_type, gerber_data.0.clone()));
-                    }
-                }
+        let mut layers_to_export = Self::collect_export_layers(world, display_manager)?;
+        for layer in &mut layers_to_export {
+            if let Some(matrix) = Self::layer_render_matrix(world, display_manager, layer.layer_type) {
+                layer.annotations = annotations.iter()
+                    .map(|annotation| AnnotationExport {
+                        kind: annotation.kind,
+                        points: annotation.outline(&matrix),
+                        color: egui::Color32::from_rgb(annotation.color[0], annotation.color[1], annotation.color[2]),
+                    })
+                    .collect();
             }
         }
         
+        // Every image covers all spread-out layers so they share one frame
+        let master_bbox = Self::calculate_master_bounding_box(&layers_to_export)?;
+        let (width, height) = resolution.image_size(&master_bbox)?;
+
+        std::fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create output directory: {}", e))?;
+        
+        let mut exported_files = Vec::new();
+        
         // Now export each layer without borrowing conflicts
-        for (layer_type, gerber_layer) in layers_to_export {
-            let filename = format!("{}.png", layer_type.display_name().replace(" ", "_").to_lowercase());
+        for layer in layers_to_export {
+            let layer_name = layer.layer_type.display_name().replace(" ", "_").to_lowercase();
+            let filename = match display_manager.quadrant_layout.cell_of(layer.layer_type) {
+                Some(cell) if display_manager.quadrant_layout.custom => format!("{}_{}.png", display_manager.quadrant_layout.file_prefix(cell), layer_name),
+                _ => format!("{}.png", layer_name),
+            };
             let output_path = output_dir.join(&filename);
             
-            Self::export_single_layer_with_bbox(
-                app,
-                &gerber_layer,
-                &layer_type,
-                Some(&mechanical_outline_gerber),
-                &master_bbox,
-                &output_path,
-                width,
-                height,
-            )?;
+            Self::export_single_layer_with_bbox(&layer, &master_bbox, &output_path, width, height)?;
             
             exported_files.push(output_path);
         }
@@ -66,14 +199,98 @@ impl PngExporter {
         Ok(exported_files)
     }
     
-    /// Calculate the master bounding box from mechanical outline layer (defines size for all exports)
-    fn calculate_master_bounding_box(
-        app: &DemoLensApp,
-        mechanical_outline: &gerber_viewer::GerberLayer,
-    ) -> Result<BoundingBox, String> {
-        // The mechanical outline defines the board boundary and should be used as the 
-        // consistent bounding box for all layer exports
-        let outline_bbox = Self::calculate_transformed_bounding_box(app, mechanical_outline, &LayerType::MechanicalOutline)?;
+    /// Visible layers to export, transformed as on screen
+    /// The board outline is drawn with every layer; without a mechanical outline
+    /// layer it is inferred from the bounding box of the visible layers
+    fn collect_export_layers(world: &mut World, display_manager: &DisplayManager) -> Result<Vec<LayerExport>, String> {
+        let Some((outline, _inferred)) = crate::ecs::get_board_outline_primitives(world) else {
+            return Err("No visible layers to export".to_string());
+        };
+        
+        let mut layers = Vec::new();
+        for layer_type in LayerType::all() {
+            let visible = crate::ecs::get_layer_data(world, layer_type)
+                .is_some_and(|(_entity, _layer_info, _gerber_data, visibility)| visibility.visible);
+            // Only layers with a place in the quadrant layout are drawn
+            let on_screen = display_manager.quadrant_shows(layer_type);
+            // Skip if layer shouldn't render for current view; a custom layout may show either side
+            let side_shown = display_manager.quadrant_layout.custom || layer_type.should_render(display_manager.showing_top);
+            if visible && on_screen && side_shown {
+                if let Some(layer) = Self::prepare_layer(world, display_manager, layer_type, &outline) {
+                    layers.push(layer);
+                }
+            }
+        }
+        
+        if layers.is_empty() {
+            // Nothing visible: frame the outline on its own
+            layers.extend(Self::prepare_layer(world, display_manager, LayerType::MechanicalOutline, &outline));
+        }
+        Ok(layers)
+    }
+    
+    /// On-screen render transform of a layer (rotation, mirroring, image transform, quadrant offset)
+    fn layer_render_matrix(world: &mut World, display_manager: &DisplayManager, layer_type: LayerType) -> Option<Matrix3<f64>> {
+        crate::ecs::get_layer_render_transform(world, display_manager, layer_type)
+            .map(|transform| transform.to_matrix())
+    }
+    
+    /// Transform a layer, and the board outline drawn with it, into view coordinates
+    fn prepare_layer(
+        world: &mut World,
+        display_manager: &DisplayManager,
+        layer_type: LayerType,
+        outline: &[TaggedPrimitive],
+    ) -> Option<LayerExport> {
+        let matrix = Self::layer_render_matrix(world, display_manager, layer_type)?;
+        let primitives = crate::ecs::get_layer_primitives(world, layer_type);
+        
+        Some(LayerExport {
+            layer_type,
+            primitives: transform_primitives(&primitives, &matrix),
+            outline: transform_primitives(outline, &matrix),
+            annotations: Vec::new(),
+        })
+    }
+    
+    /// Render one layer as the PNG export does, framed on the layer and the board outline
+    /// Quadrant view need not be on; the integration tests compare this against reference images
+    pub fn render_layer(
+        world: &mut World,
+        display_manager: &DisplayManager,
+        layer_type: LayerType,
+        resolution: PngResolution,
+    ) -> Result<RgbaImage, String> {
+        let Some((outline, _inferred)) = crate::ecs::get_board_outline_primitives(world) else {
+            return Err("No visible layers to export".to_string());
+        };
+        let layer = Self::prepare_layer(world, display_manager, layer_type, &outline)
+            .ok_or_else(|| format!("{} is not loaded", layer_type.display_name()))?;
+        let bbox = Self::calculate_master_bounding_box(std::slice::from_ref(&layer))?;
+        let (width, height) = resolution.image_size(&bbox)?;
+        Ok(Self::render_layer_with_bbox(&layer, &bbox, width, height).0)
+    }
+    
+    /// Master bounding box shared by all exported images, for the current view settings
+    pub fn master_bounding_box(world: &mut World, display_manager: &DisplayManager) -> Result<BoundingBox, String> {
+        let layers = Self::collect_export_layers(world, display_manager)?;
+        Self::calculate_master_bounding_box(&layers)
+    }
+    
+    /// Calculate the master bounding box (defines size for all exports)
+    /// Union of every exported layer and its outline after the on-screen transform chain
+    /// (rotation, mirroring, image transform and quadrant offset). The center and design
+    /// offsets only move the on-screen view, so fitting the image to this box accounts for them.
+    fn calculate_master_bounding_box(layers: &[LayerExport]) -> Result<BoundingBox, String> {
+        let outline_bbox = layers
+            .iter()
+            .flat_map(|layer| [primitives_bounds(&layer.primitives), primitives_bounds(&layer.outline)])
+            .flatten()
+            .reduce(|mut combined, bbox| {
+                combined.expand(&bbox);
+                combined
+            })
+            .ok_or("No layer geometry to export")?;
         
         // Add some padding around the mechanical outline (5% on each side)
         let padding_factor = 0.05;
@@ -98,187 +315,70 @@ impl PngExporter {
     
     /// Export a single layer to PNG using the consistent master bounding box
     fn export_single_layer_with_bbox(
-        app: &DemoLensApp,
-        gerber_layer: &gerber_viewer::GerberLayer,
-        layer_type: &LayerType,
-        mechanical_outline: Option<&gerber_viewer::GerberLayer>,
+        layer: &LayerExport,
         master_bbox: &BoundingBox,
         output_path: &PathBuf,
         width: u32,
         height: u32,
     ) -> Result<(), String> {
-        // Create image buffer
-        let mut img: RgbaImage = ImageBuffer::new(width, height);
-        
-        // Fill with black background (PCB standard)
-        for pixel in img.pixels_mut() {
-            *pixel = Rgba([0, 0, 0, 255]); // Black background
-        }
-        
-        // Create viewport that matches our master bounding box
-        let viewport = Rect::from_min_size(
-            Pos2::ZERO,
-            Vec2::new(width as f32, height as f32)
-        );
-        
-        // Calculate view state to fit the master bounding box (same for all layers)
-        let view_state = Self::calculate_bbox_view_state(master_bbox, &viewport);
-        
         // Log the export operation
-        println!("Exporting {} layer to {:?}", layer_type.display_name(), output_path);
+        println!("Exporting {} layer to {:?}", layer.layer_type.display_name(), output_path);
         println!("  Using master bounding box: ({:.2}, {:.2}) to ({:.2}, {:.2}) mm", 
                  master_bbox.min.x, master_bbox.min.y, 
                  master_bbox.max.x, master_bbox.max.y);
-        println!("  Board dimensions: {:.2} x {:.2} mm", 
-                 master_bbox.width(), master_bbox.height());
         println!("  Image size: {}x{} pixels", width, height);
-        println!("  Scale: {:.2} (consistent for all layers)", view_state.scale);
         
-        // Render the gerber layer to the image buffer
-        Self::render_gerber_to_image(
-            app,
-            gerber_layer,
-            layer_type,
-            mechanical_outline,
-            &view_state,
-            &mut img,
-            width,
-            height,
-        )?;
+        let (img, view_state) = Self::render_layer_with_bbox(layer, master_bbox, width, height);
+        println!("  Scale: {:.2} (consistent for all layers)", view_state.scale);
         
-        // Save the image
-        img.save(output_path).map_err(|e| format!("Failed to save PNG: {}", e))?;
+        // Save the image with its physical resolution
+        let dpi = view_state.scale as f64 * MM_PER_INCH;
+        save_png_with_dpi(&img, output_path, dpi)?;
         
         Ok(())
     }
     
-    /// Render gerber layer to image buffer using a simplified approach
-    fn render_gerber_to_image(
-        app: &DemoLensApp,
-        _gerber_layer: &gerber_viewer::GerberLayer,
-        layer_type: &LayerType,
-        mechanical_outline: Option<&gerber_viewer::GerberLayer>,
-        _view_state: &ViewState,
-        img: &mut RgbaImage,
-        width: u32,
-        height: u32,
-    ) -> Result<(), String> {
-        // Note: Layer positions are managed by the DisplayManager
-        
-        // Get quadrant offset for this layer type - this is the key positioning info
-        let quadrant_offset = app.display_manager.get_quadrant_offset(layer_type);
-        
-        println!("🎯 Exporting {} with quadrant offset: ({:.1}, {:.1})", 
-                layer_type.display_name(), quadrant_offset.x, quadrant_offset.y);
+    /// Rasterize a layer fitted to the master bounding box, returning the image and the view used
+    fn render_layer_with_bbox(layer: &LayerExport, master_bbox: &BoundingBox, width: u32, height: u32) -> (RgbaImage, ViewState) {
+        // Black background (PCB standard)
+        let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, Rgba([0, 0, 0, 255]));
         
-        let layer_color = layer_type.color();
-        let rgba = [layer_color.r(), layer_color.g(), layer_color.b(), layer_color.a()];
-        
-        // Draw the layer representation using the quadrant offset directly
-        Self::draw_layer_in_quadrant(img, layer_type, &quadrant_offset, &rgba, width, height);
-        
-        // Draw mechanical outline border at image edge for reference  
-        if let Some(_outline_layer) = mechanical_outline {
-            let outline_color = LayerType::MechanicalOutline.color();
-            let outline_rgba = [outline_color.r(), outline_color.g(), outline_color.b(), outline_color.a()];
-            
-            // Draw simple border around entire image
-            let margin = 20;
-            Self::draw_rectangle_border(img, margin, margin, width - margin, height - margin, &outline_rgba, 2);
-        }
+        // Calculate view state to fit the master bounding box (same for all layers)
+        let viewport = Rect::from_min_size(Pos2::ZERO, Vec2::new(width as f32, height as f32));
+        let view_state = Self::calculate_bbox_view_state(master_bbox, &viewport);
         
-        Ok(())
+        Self::render_layer_to_image(layer, &view_state, &mut img);
+        (img, view_state)
     }
     
-    /// Draw layer in its proper quadrant using simple, direct positioning
-    fn draw_layer_in_quadrant(
-        img: &mut RgbaImage,
-        layer_type: &LayerType,
-        quadrant_offset: &VectorOffset,
-        color: &[u8; 4],
-        width: u32,
-        height: u32,
-    ) {
-        println!("  🎨 Drawing {} in quadrant at offset ({:.1}, {:.1})", 
-                layer_type.display_name(), quadrant_offset.x, quadrant_offset.y);
-        
-        // Simple quadrant mapping - divide image into 4 sections
-        let half_width = width / 2;
-        let half_height = height / 2;
-        let quarter_width = width / 4;
-        let quarter_height = height / 4;
-        
-        // Determine quadrant based on offset signs (from DisplayManager::get_quadrant_offset)
-        let (quadrant_x, quadrant_y) = if quadrant_offset.x > 0.0 && quadrant_offset.y > 0.0 {
-            // Quadrant 1 (top-right): Copper
-            (half_width + quarter_width / 2, quarter_height / 2)
-        } else if quadrant_offset.x < 0.0 && quadrant_offset.y > 0.0 {
-            // Quadrant 2 (top-left): Silkscreen  
-            (quarter_width / 2, quarter_height / 2)
-        } else if quadrant_offset.x < 0.0 && quadrant_offset.y < 0.0 {
-            // Quadrant 3 (bottom-left): Soldermask
-            (quarter_width / 2, half_height + quarter_height / 2)
-        } else if quadrant_offset.x > 0.0 && quadrant_offset.y < 0.0 {
-            // Quadrant 4 (bottom-right): Paste
-            (half_width + quarter_width / 2, half_height + quarter_height / 2)
-        } else {
-            // Center (mechanical outline)
-            (half_width, half_height)
+    /// Rasterize the layer geometry, with the board outline and any annotations on top
+    fn render_layer_to_image(layer: &LayerExport, view_state: &ViewState, img: &mut RgbaImage) {
+        // Pixel (0, 0) is the screen origin of the view state
+        let scale = view_state.scale as f64;
+        let grid = RasterGrid {
+            top_left: Point2::new(
+                -view_state.translation.x as f64 / scale,
+                view_state.translation.y as f64 / scale,
+            ),
+            pixels_per_mm: scale,
+            width: img.width(),
+            height: img.height(),
         };
         
-        // Draw layer representation in the calculated quadrant
-        let size = quarter_width.min(quarter_height) as u32;
-        let x1 = quadrant_x.saturating_sub(size / 2);
-        let y1 = quadrant_y.saturating_sub(size / 2);
-        let x2 = (quadrant_x + size / 2).min(width);
-        let y2 = (quadrant_y + size / 2).min(height);
-        
-        println!("  📍 Quadrant position: ({}, {}) -> Rectangle ({}, {}) to ({}, {})", 
-                quadrant_x, quadrant_y, x1, y1, x2, y2);
-        
-        // Draw based on layer type
-        match layer_type {
-            LayerType::Copper(_) => {
-                Self::fill_rectangle(img, x1, y1, x2, y2, color);
-            },
-            LayerType::Silkscreen(_) => {
-                // Draw silkscreen as text-like patterns
-                Self::draw_silkscreen_pattern(img, (x1, y1), (x2, y2), color);
-            },
-            LayerType::Soldermask(_) => {
-                // Draw soldermask as mostly filled with some openings
-                Self::fill_rectangle_with_openings(img, x1, y1, x2, y2, color);
-            },
-            LayerType::Paste(_) => {
-                // Draw paste as small squares/dots
-                Self::draw_paste_pattern(img, (x1, y1), (x2, y2), color);
-            },
-            LayerType::MechanicalOutline => {
-                // This is handled separately
-            }
+        Self::paint_mask(img, &rasterize_primitives(&layer.primitives, grid), layer.layer_type.color());
+        Self::paint_mask(img, &rasterize_primitives(&layer.outline, grid), LayerType::MechanicalOutline.color());
+        for annotation in &layer.annotations {
+            Self::paint_mask(img, &rasterize_primitives(&annotation.primitives(grid.pixels_per_mm), grid), annotation.color);
         }
     }
     
-    /// Draw mechanical outline as border
-    fn draw_outline_representation(
-        img: &mut RgbaImage,
-        _bbox: &BoundingBox,
-        _offset: &VectorOffset,
-        _view_state: &ViewState,
-        color: &[u8; 4],
-        width: u32,
-        height: u32,
-    ) {
-        println!("  Drawing mechanical outline");
-        
-        // Draw outline as a border around the same area as the layers
-        let margin = 200;
-        let x1 = margin;
-        let y1 = margin;
-        let x2 = width - margin;
-        let y2 = height - margin;
-        
-        Self::draw_rectangle_border(img, x1, y1, x2, y2, color, 5);
+    fn paint_mask(img: &mut RgbaImage, mask: &CoverageMask, color: egui::Color32) {
+        let rgba = Rgba([color.r(), color.g(), color.b(), 255]);
+        for (x, y, pixel) in img.enumerate_pixels_mut() {
+            if mask.get(x, y) {
+                *pixel = rgba;
+            }
+        }
     }
     
     /// Convert gerber coordinates to screen coordinates
@@ -301,45 +401,14 @@ impl PngExporter {
         (x, y)
     }
     
-    /// Calculate the transformed bounding box for a layer including all transformations
+    /// Bounding box of a layer's geometry after its render transform
+    /// Computed from the transformed shapes, so it grows to fit any rotation angle
     fn calculate_transformed_bounding_box(
-        app: &DemoLensApp,
-        gerber_layer: &gerber_viewer::GerberLayer,
-        layer_type: &LayerType,
+        primitives: &[TaggedPrimitive],
+        matrix: &Matrix3<f64>,
     ) -> Result<BoundingBox, String> {
-        // Get the original bounding box
-        let original_bbox = gerber_layer.bounding_box().clone();
-        
-        // Get quadrant offset for this layer type
-        let quadrant_offset = app.display_manager.get_quadrant_offset(layer_type);
-        
-        // Calculate combined offset
-        let combined_offset = VectorOffset {
-            x: app.display_manager.center_offset.x + quadrant_offset.x,
-            y: app.display_manager.center_offset.y + quadrant_offset.y,
-        };
-        
-        // Create transform similar to what's used in rendering
-        let origin: Vector2<f64> = app.display_manager.center_offset.clone().into();
-        let offset: Vector2<f64> = combined_offset.into();
-        
-        let transform = GerberTransform {
-            rotation: app.rotation_degrees.to_radians(),
-            mirroring: app.display_manager.mirroring.clone().into(),
-            origin: origin - offset,
-            offset,
-            scale: 1.0,
-        };
-        
-        // Transform all corners of the bounding box
-        let corners = original_bbox.vertices();
-        let transformed_corners: Vec<Point2<f64>> = corners
-            .into_iter()
-            .map(|corner| transform.apply_to_position(corner))
-            .collect();
-        
-        // Create new bounding box from transformed corners
-        Ok(BoundingBox::from_points(&transformed_corners))
+        primitives_bounds(&transform_primitives(primitives, matrix))
+            .ok_or_else(|| "Layer has no geometry to export".to_string())
     }
     
     /// Combine two bounding boxes into one that contains both
@@ -361,11 +430,12 @@ impl PngExporter {
         let bbox_width = bbox.width() as f32;
         let bbox_height = bbox.height() as f32;
         
-        // Calculate scale to fit the bounding box with some padding
+        // Calculate scale to fit the bounding box (the master bbox already carries the padding)
+        // so one pixel maps to a fixed physical size for the DPI metadata
         let scale = f32::min(
             viewport.width() / bbox_width,
             viewport.height() / bbox_height,
-        ) * 0.9; // 90% to add some padding
+        );
         
         // Calculate translation to center the bounding box in the viewport
         let bbox_center = bbox.center();
@@ -387,144 +457,9 @@ impl PngExporter {
         }
     }
     
-    /// Fill a rectangle in the image
-    fn fill_rectangle(img: &mut RgbaImage, x1: u32, y1: u32, x2: u32, y2: u32, color: &[u8; 4]) {
-        let min_x = x1.min(x2);
-        let max_x = x1.max(x2);
-        let min_y = y1.min(y2);
-        let max_y = y1.max(y2);
-        
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, y) {
-                    *pixel = Rgba(*color);
-                }
-            }
-        }
-    }
-    
-    /// Draw rectangle border
-    fn draw_rectangle_border(img: &mut RgbaImage, x1: u32, y1: u32, x2: u32, y2: u32, color: &[u8; 4], thickness: u32) {
-        let min_x = x1.min(x2);
-        let max_x = x1.max(x2);
-        let min_y = y1.min(y2);
-        let max_y = y1.max(y2);
-        
-        // Draw border lines
-        for t in 0..thickness {
-            // Top and bottom borders
-            for x in min_x..=max_x {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, min_y + t) {
-                    *pixel = Rgba(*color);
-                }
-                if max_y >= t {
-                    if let Some(pixel) = img.get_pixel_mut_checked(x, max_y - t) {
-                        *pixel = Rgba(*color);
-                    }
-                }
-            }
-            
-            // Left and right borders
-            for y in min_y..=max_y {
-                if let Some(pixel) = img.get_pixel_mut_checked(min_x + t, y) {
-                    *pixel = Rgba(*color);
-                }
-                if max_x >= t {
-                    if let Some(pixel) = img.get_pixel_mut_checked(max_x - t, y) {
-                        *pixel = Rgba(*color);
-                    }
-                }
-            }
-        }
-    }
-    
-    /// Draw trace pattern for copper layers
-    fn draw_trace_pattern(img: &mut RgbaImage, min_screen: (u32, u32), max_screen: (u32, u32), color: &[u8; 4]) {
-        let (min_x, min_y) = min_screen;
-        let (max_x, max_y) = max_screen;
-        
-        // Draw some horizontal and vertical lines to simulate traces
-        let step = 20;
-        
-        for i in (min_y..max_y).step_by(step) {
-            for x in min_x..max_x {
-                if let Some(pixel) = img.get_pixel_mut_checked(x, i) {
-                    *pixel = Rgba(*color);
-                }
-            }
-        }
-        
-        for i in (min_x..max_x).step_by(step) {
-            for y in min_y..max_y {
-                if let Some(pixel) = img.get_pixel_mut_checked(i, y) {
-                    *pixel = Rgba(*color);
-                }
-            }
-        }
-    }
-    
-    /// Draw silkscreen pattern
-    fn draw_silkscreen_pattern(img: &mut RgbaImage, min_screen: (u32, u32), max_screen: (u32, u32), color: &[u8; 4]) {
-        let (min_x, min_y) = min_screen;
-        let (max_x, max_y) = max_screen;
-        
-        // Draw some text-like patterns
-        for y in (min_y..max_y).step_by(10) {
-            for x in (min_x..max_x).step_by(15) {
-                // Draw small rectangles to simulate text
-                for dy in 0..3 {
-                    for dx in 0..8 {
-                        if let Some(pixel) = img.get_pixel_mut_checked(x + dx, y + dy) {
-                            *pixel = Rgba(*color);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    /// Draw soldermask with openings
-    fn fill_rectangle_with_openings(img: &mut RgbaImage, x1: u32, y1: u32, x2: u32, y2: u32, color: &[u8; 4]) {
-        let min_x = x1.min(x2);
-        let max_x = x1.max(x2);
-        let min_y = y1.min(y2);
-        let max_y = y1.max(y2);
-        
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                // Create some openings (skip every 25th pixel in a pattern)
-                if (x + y) % 25 != 0 {
-                    if let Some(pixel) = img.get_pixel_mut_checked(x, y) {
-                        *pixel = Rgba(*color);
-                    }
-                }
-            }
-        }
-    }
-    
-    /// Draw paste pattern as dots
-    fn draw_paste_pattern(img: &mut RgbaImage, min_screen: (u32, u32), max_screen: (u32, u32), color: &[u8; 4]) {
-        let (min_x, min_y) = min_screen;
-        let (max_x, max_y) = max_screen;
-        
-        // Draw dots in a grid pattern
-        for y in (min_y..max_y).step_by(8) {
-            for x in (min_x..max_x).step_by(8) {
-                // Draw 3x3 squares
-                for dy in 0..3 {
-                    for dx in 0..3 {
-                        if let Some(pixel) = img.get_pixel_mut_checked(x + dx, y + dy) {
-                            *pixel = Rgba(*color);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
     /// Calculate the appropriate view state for a single layer
     fn calculate_layer_view_state(
-        app: &DemoLensApp,
+        display_manager: &DisplayManager,
         gerber_layer: &gerber_viewer::GerberLayer,
         viewport: &Rect,
         layer_type: &LayerType,
@@ -540,7 +475,7 @@ impl PngExporter {
         ) * 0.95; // Add margin
 
         // Get quadrant offset for centering
-        let quadrant_offset = app.display_manager.get_quadrant_offset(layer_type);
+        let quadrant_offset = display_manager.get_quadrant_offset(layer_type);
         let center_x = bbox.center().x + quadrant_offset.x;
         let center_y = bbox.center().y + quadrant_offset.y;
 
@@ -554,10 +489,168 @@ impl PngExporter {
         }
     }
     
-    /// Alternative approach: Export visible viewport area as PNG
-    pub fn export_current_view(_app: &DemoLensApp, _output_path: &PathBuf, _viewport: &Rect) -> Result<(), String> {
-        // This would require integration with egui's rendering system
-        // For now, we'll suggest using the built-in screenshot functionality
-        Err("Use your OS screenshot tool to capture the current view. Full PNG export will be implemented in a future version.".to_string())
+    /// Save a screenshot of the gerber view (see `ui::view_clipboard`) as PNG
+    /// `pixels_per_mm` is the zoom of the view in physical pixels, written as the PNG resolution
+    pub fn export_current_view(image: &egui::ColorImage, output_path: &Path, pixels_per_mm: f64) -> Result<(), String> {
+        let [width, height] = image.size;
+        let pixels = image.pixels.iter().flat_map(|color| color.to_srgba_unmultiplied()).collect();
+        let img = RgbaImage::from_raw(width as u32, height as u32, pixels)
+            .ok_or_else(|| "Screenshot size does not match its pixels".to_string())?;
+        save_png_with_dpi(&img, output_path, pixels_per_mm * MM_PER_INCH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn board_bbox(width: f64, height: f64) -> BoundingBox {
+        BoundingBox {
+            min: Point2::new(0.0, 0.0),
+            max: Point2::new(width, height),
+        }
+    }
+
+    #[test]
+    fn test_dpi_resolution_follows_board_size() {
+        let size = PngResolution::Dpi(600.0).image_size(&board_bbox(100.0, 80.0));
+        assert_eq!(size, Ok((2362, 1890)));
+
+        let too_large = PngResolution::Dpi(10_000.0).image_size(&board_bbox(100.0, 80.0));
+        assert!(too_large.is_err());
+        assert!(PngResolution::Pixels { width: 20_000, height: 100 }.image_size(&board_bbox(1.0, 1.0)).is_err());
+    }
+
+    #[test]
+    fn test_saved_png_carries_dpi_metadata() {
+        let dir = TempDir::new("dpi");
+        let path = dir.path().join("layer.png");
+        save_png_with_dpi(&RgbaImage::new(4, 4), &path, 254.0).expect("PNG should save");
+
+        let decoder = png::Decoder::new(std::fs::File::open(&path).expect("PNG should exist"));
+        let reader = decoder.read_info().expect("PNG should decode");
+        let dims = reader.info().pixel_dims.expect("pHYs chunk should be written");
+
+        assert_eq!((dims.xppu, dims.yppu, dims.unit), (10_000, 10_000, png::Unit::Meter));
+    }
+
+    #[test]
+    fn test_view_screenshot_keeps_its_size_and_zoom() {
+        let dir = TempDir::new("view_png");
+        let path = dir.path().join("view.png");
+        let mut screenshot = egui::ColorImage::filled([5, 3], egui::Color32::from_rgb(200, 40, 40));
+        screenshot.pixels[4] = egui::Color32::from_rgb(10, 20, 30);
+        PngExporter::export_current_view(&screenshot, &path, 8.0).expect("view should save");
+
+        let decoder = png::Decoder::new(std::fs::File::open(&path).expect("PNG should exist"));
+        let reader = decoder.read_info().expect("PNG should decode");
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (5, 3));
+        // 8 px/mm is 8000 px/m
+        let dims = info.pixel_dims.expect("pHYs chunk should be written");
+        assert_eq!((dims.xppu, dims.yppu, dims.unit), (8_000, 8_000, png::Unit::Meter));
+
+        // Row-major, top row first, as the screenshot
+        let decoded = image::open(&path).expect("PNG should load").to_rgba8();
+        assert_eq!(decoded.get_pixel(4, 0).0, [10, 20, 30, 255]);
+        assert_eq!(decoded.get_pixel(0, 2).0, [200, 40, 40, 255]);
+    }
+
+    const SQUARE: &str = "%FSLAX46Y46*%\n%MOMM*%\nG36*\nX-5000000Y-5000000D02*\nX5000000Y-5000000D01*\nX5000000Y5000000D01*\nX-5000000Y5000000D01*\nX-5000000Y-5000000D01*\nG37*\nM02*\n";
+    const OFFSET_BAR: &str = "%FSLAX46Y46*%\n%MOMM*%\nG36*\nX1000000Y0D02*\nX3000000Y0D01*\nX3000000Y1000000D01*\nX1000000Y1000000D01*\nX1000000Y0D01*\nG37*\nM02*\n";
+
+    fn view_matrix(rotation_degrees: f32, mirror_x: bool) -> Matrix3<f64> {
+        gerber_viewer::GerberTransform {
+            rotation: rotation_degrees.to_radians(),
+            mirroring: crate::display::manager::MirroringSettings { x: mirror_x, y: false }.into(),
+            origin: nalgebra::Vector2::zeros(),
+            offset: nalgebra::Vector2::zeros(),
+            scale: 1.0,
+        }
+        .to_matrix()
+    }
+
+    #[test]
+    fn test_rotated_square_bbox_matches_analytic_bounds() {
+        let square = crate::ecs::extract_primitives(SQUARE);
+        for degrees in [0.0f32, 30.0, 45.0, 90.0, 135.0] {
+            let bbox = PngExporter::calculate_transformed_bounding_box(&square, &view_matrix(degrees, false))
+                .expect("square has geometry");
+
+            // A 10mm square rotated by θ spans 10·(|cos θ| + |sin θ|) on each axis
+            let theta = (degrees as f64).to_radians();
+            let half = 5.0 * (theta.cos().abs() + theta.sin().abs());
+            for (actual, expected) in [(bbox.min.x, -half), (bbox.min.y, -half), (bbox.max.x, half), (bbox.max.y, half)] {
+                assert!((actual - expected).abs() < 1e-6, "{}°: {} != {}", degrees, actual, expected);
+            }
+        }
+    }
+
+    // 40 x 20 mm board: hairline outline and a copper pour inset by 1mm
+    const BOARD_OUTLINE: &str = "%FSLAX46Y46*%\n%MOMM*%\n%ADD10C,0.100000*%\nD10*\nX0Y0D02*\nX40000000Y0D01*\nX40000000Y20000000D01*\nX0Y20000000D01*\nX0Y0D01*\nM02*\n";
+    const BOARD_COPPER: &str = "%FSLAX46Y46*%\n%MOMM*%\nG36*\nX1000000Y1000000D02*\nX39000000Y1000000D01*\nX39000000Y19000000D01*\nX1000000Y19000000D01*\nX1000000Y1000000D01*\nG37*\nM02*\n";
+
+    /// Layers of the fixture board spread out and transformed like the on-screen view
+    fn spread_board_layers(rotation_degrees: f32, mirror_x: bool) -> Vec<LayerExport> {
+        let mut display_manager = crate::display::DisplayManager::new();
+        display_manager.quadrant_view_enabled = true;
+        display_manager.set_quadrant_offset_magnitude(60.0);
+        display_manager.mirroring.x = mirror_x;
+        display_manager.rotation_center = crate::display::VectorOffset { x: 20.0, y: 10.0 };
+
+        let outline = crate::ecs::extract_primitives(BOARD_OUTLINE);
+        let copper = crate::ecs::extract_primitives(BOARD_COPPER);
+        [LayerType::Copper(1), LayerType::Silkscreen(1), LayerType::Soldermask(1)]
+            .into_iter()
+            .map(|layer_type| {
+                let matrix = crate::display::build_layer_transform(&display_manager, rotation_degrees, Some(layer_type)).to_matrix();
+                LayerExport {
+                    layer_type,
+                    primitives: transform_primitives(&copper, &matrix),
+                    outline: transform_primitives(&outline, &matrix),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_spread_layers_fit_inside_export_at_any_orientation() {
+        let cases = [(0.0, false), (90.0, false), (180.0, false), (270.0, false), (0.0, true)];
+        for (degrees, mirror_x) in cases {
+            let layers = spread_board_layers(degrees, mirror_x);
+            let master_bbox = PngExporter::calculate_master_bounding_box(&layers).expect("board has geometry");
+            let (width, height) = PngResolution::Pixels { width: 600, height: 300 }
+                .image_size(&master_bbox)
+                .expect("size is valid");
+            let view_state = PngExporter::calculate_bbox_view_state(
+                &master_bbox,
+                &Rect::from_min_size(Pos2::ZERO, Vec2::new(width as f32, height as f32)),
+            );
+
+            for layer in &layers {
+                let mut img: RgbaImage = ImageBuffer::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+                PngExporter::render_layer_to_image(layer, &view_state, &mut img);
+
+                let drawn = img.pixels().filter(|p| p.0 != [0, 0, 0, 255]).count();
+                assert!(drawn > 0, "{:?} at {}° drew nothing", layer.layer_type, degrees);
+
+                // Nothing touches the image border, so no layer was clipped
+                let on_border = img.enumerate_pixels()
+                    .filter(|(x, y, _)| *x == 0 || *y == 0 || *x == width - 1 || *y == height - 1)
+                    .any(|(_, _, p)| p.0 != [0, 0, 0, 255]);
+                assert!(!on_border, "{:?} at {}° (mirror {}) was clipped", layer.layer_type, degrees, mirror_x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mirrored_bbox_follows_view() {
+        let bar = crate::ecs::extract_primitives(OFFSET_BAR);
+        let bbox = PngExporter::calculate_transformed_bounding_box(&bar, &view_matrix(0.0, true))
+            .expect("bar has geometry");
+
+        assert!((bbox.min.x + 3.0).abs() < 1e-6 && (bbox.max.x + 1.0).abs() < 1e-6);
+        assert!(bbox.min.y.abs() < 1e-6 && (bbox.max.y - 1.0).abs() < 1e-6);
     }
-}
\ No newline at end of file
+}
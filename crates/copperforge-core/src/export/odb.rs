@@ -0,0 +1,360 @@
+//! Minimal ODB++ job writer
+//!
+//! Writes an uncompressed ODB++ directory (matrix, one `pcb` step, a profile
+//! and one features file per layer) from the loaded layers and drill data.
+//! Each extracted primitive becomes one feature record: segments are lines,
+//! flashes are pads with a standard symbol and regions are surfaces. Net
+//! attributes, components and macro aperture flashes are not written.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::world::World;
+use nalgebra::Point2;
+
+use crate::ecs::{ApertureShape, DrillHole, LayerType, Polarity, PrimitiveShape, Side, TaggedPrimitive};
+
+/// Name of the single step written
+pub const STEP_NAME: &str = "pcb";
+
+/// Standard symbol sizes are in microns when the features file is in mm
+const MICRONS_PER_MM: f64 = 1000.0;
+
+/// Standard ODB++ symbol, sizes in microns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Symbol {
+    Round(i64),
+    Rectangle(i64, i64),
+    Oval(i64, i64),
+}
+
+impl Symbol {
+    fn name(&self) -> String {
+        match *self {
+            Symbol::Round(d) => format!("r{}", d),
+            Symbol::Rectangle(w, h) => format!("rect{}x{}", w, h),
+            Symbol::Oval(w, h) => format!("oval{}x{}", w, h),
+        }
+    }
+}
+
+fn microns(mm: f64) -> i64 {
+    (mm * MICRONS_PER_MM).round() as i64
+}
+
+/// One record of a features file
+#[derive(Debug, Clone, PartialEq)]
+enum FeatureRecord {
+    Line { start: Point2<f64>, end: Point2<f64>, symbol: Symbol, polarity: Polarity },
+    Pad { center: Point2<f64>, symbol: Symbol, polarity: Polarity },
+    Surface { points: Vec<Point2<f64>>, polarity: Polarity },
+}
+
+impl FeatureRecord {
+    /// Feature for one primitive; None for macro flashes, whose shape is unknown
+    fn from_primitive(primitive: &TaggedPrimitive) -> Option<Self> {
+        let polarity = primitive.polarity;
+        let record = match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => {
+                FeatureRecord::Line { start: *start, end: *end, symbol: Symbol::Round(microns(*width)), polarity }
+            }
+            PrimitiveShape::Flash { center, width, height, aperture, .. } => {
+                let symbol = match aperture {
+                    // Polygons keep their circumscribed diameter, as in the gerber writer
+                    ApertureShape::Circle | ApertureShape::Polygon => Symbol::Round(microns(*width)),
+                    ApertureShape::Rectangle => Symbol::Rectangle(microns(*width), microns(*height)),
+                    ApertureShape::Obround => Symbol::Oval(microns(*width), microns(*height)),
+                    ApertureShape::Other => return None,
+                };
+                FeatureRecord::Pad { center: *center, symbol, polarity }
+            }
+            PrimitiveShape::Region { points } if points.len() >= 3 => FeatureRecord::Surface { points: points.clone(), polarity },
+            PrimitiveShape::Region { .. } => return None,
+        };
+        Some(record)
+    }
+}
+
+fn polarity_code(polarity: Polarity) -> char {
+    match polarity {
+        Polarity::Dark => 'P',
+        Polarity::Clear => 'N',
+    }
+}
+
+fn coordinate(point: &Point2<f64>) -> String {
+    format!("{:.6} {:.6}", point.x, point.y)
+}
+
+/// Twice the signed area; positive for counter-clockwise contours
+fn orientation(points: &[Point2<f64>]) -> f64 {
+    points.iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+/// Island contour record (`OB ... I` to `OE`); ODB++ wants islands clockwise
+fn write_island(out: &mut String, points: &[Point2<f64>]) {
+    let mut points = points.to_vec();
+    if orientation(&points) > 0.0 {
+        points.reverse();
+    }
+    let _ = writeln!(out, "OB {} I", coordinate(&points[0]));
+    for point in points.iter().skip(1).chain(std::iter::once(&points[0])) {
+        let _ = writeln!(out, "OS {}", coordinate(point));
+    }
+    out.push_str("OE\n");
+}
+
+/// Features file body with its symbol table
+#[derive(Default)]
+struct FeatureWriter {
+    symbols: HashMap<Symbol, usize>,
+    symbol_order: Vec<Symbol>,
+    body: String,
+    skipped: usize,
+}
+
+impl FeatureWriter {
+    fn symbol_index(&mut self, symbol: Symbol) -> usize {
+        *self.symbols.entry(symbol).or_insert_with(|| {
+            self.symbol_order.push(symbol);
+            self.symbol_order.len() - 1
+        })
+    }
+
+    fn add(&mut self, record: &FeatureRecord) {
+        match record {
+            FeatureRecord::Line { start, end, symbol, polarity } => {
+                let index = self.symbol_index(*symbol);
+                let _ = writeln!(self.body, "L {} {} {} {} 0", coordinate(start), coordinate(end), index, polarity_code(*polarity));
+            }
+            FeatureRecord::Pad { center, symbol, polarity } => {
+                let index = self.symbol_index(*symbol);
+                let _ = writeln!(self.body, "P {} {} {} 0 0", coordinate(center), index, polarity_code(*polarity));
+            }
+            FeatureRecord::Surface { points, polarity } => {
+                let _ = writeln!(self.body, "S {} 0", polarity_code(*polarity));
+                write_island(&mut self.body, points);
+                self.body.push_str("SE\n");
+            }
+        }
+    }
+
+    fn add_primitive(&mut self, primitive: &TaggedPrimitive) {
+        match FeatureRecord::from_primitive(primitive) {
+            Some(record) => self.add(&record),
+            None => self.skipped += 1,
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut content = String::from("UNITS=MM\n#\n#Feature symbol names\n#\n");
+        for (index, symbol) in self.symbol_order.iter().enumerate() {
+            let _ = writeln!(content, "${} {}", index, symbol.name());
+        }
+        content.push_str("#\n#Layer features\n#\n");
+        content.push_str(&self.body);
+        content
+    }
+}
+
+/// Features file for a layer's primitives and the number of skipped macro flashes
+pub fn write_features(primitives: &[TaggedPrimitive]) -> (String, usize) {
+    let mut writer = FeatureWriter::default();
+    for primitive in primitives {
+        writer.add_primitive(primitive);
+    }
+    let skipped = writer.skipped;
+    (writer.finish(), skipped)
+}
+
+/// Features file with one round pad per hole
+pub fn write_drill_features(holes: &[DrillHole]) -> String {
+    let mut writer = FeatureWriter::default();
+    for hole in holes {
+        writer.add(&FeatureRecord::Pad { center: hole.position, symbol: Symbol::Round(microns(hole.diameter)), polarity: Polarity::Dark });
+    }
+    writer.finish()
+}
+
+/// Step profile: the board contour as a single surface
+pub fn write_profile(contour: &[Point2<f64>]) -> String {
+    let mut content = String::from("UNITS=MM\n#\n#Layer features\n#\n");
+    if contour.len() >= 3 {
+        content.push_str("S P 0\n");
+        write_island(&mut content, contour);
+        content.push_str("SE\n");
+    }
+    content
+}
+
+/// ODB++ layer name, matrix context and type
+pub fn odb_layer(layer_type: LayerType) -> (String, &'static str, &'static str) {
+    let side = |side: Side| if side == Side::Top { "top" } else { "bottom" };
+    match layer_type {
+        LayerType::Copper(1) => ("top".to_string(), "BOARD", "SIGNAL"),
+        LayerType::Copper(2) => ("bottom".to_string(), "BOARD", "SIGNAL"),
+        LayerType::Copper(n) => (format!("inner{}", n - 2), "BOARD", "SIGNAL"),
+        LayerType::Soldermask(s) => (format!("soldermask_{}", side(s)), "BOARD", "SOLDER_MASK"),
+        LayerType::Silkscreen(s) => (format!("silkscreen_{}", side(s)), "BOARD", "SILK_SCREEN"),
+        LayerType::Paste(s) => (format!("paste_{}", side(s)), "BOARD", "SOLDER_PASTE"),
+        LayerType::MechanicalOutline => ("outline".to_string(), "MISC", "DOCUMENT"),
+    }
+}
+
+/// Position in the physical stack, top to bottom, for the matrix rows
+pub(super) fn stack_order(layer_type: LayerType) -> (u8, u8) {
+    match layer_type {
+        LayerType::Silkscreen(Side::Top) => (0, 0),
+        LayerType::Paste(Side::Top) => (1, 0),
+        LayerType::Soldermask(Side::Top) => (2, 0),
+        LayerType::Copper(1) => (3, 0),
+        LayerType::Copper(n) if n > 2 => (4, n),
+        LayerType::Copper(_) => (5, 0),
+        LayerType::Soldermask(Side::Bottom) => (6, 0),
+        LayerType::Paste(Side::Bottom) => (7, 0),
+        LayerType::Silkscreen(Side::Bottom) => (8, 0),
+        LayerType::MechanicalOutline => (10, 0),
+    }
+}
+
+/// One row of the matrix file
+struct MatrixRow {
+    name: String,
+    context: &'static str,
+    layer_type: &'static str,
+    /// Drill span as (start, end) signal layer names
+    span: Option<(String, String)>,
+}
+
+fn write_matrix(rows: &[MatrixRow]) -> String {
+    let mut content = format!("STEP {{\n   COL=1\n   NAME={}\n}}\n\n", STEP_NAME.to_uppercase());
+    for (index, row) in rows.iter().enumerate() {
+        let (start, end) = row.span.clone().unwrap_or_default();
+        let _ = write!(
+            content,
+            "LAYER {{\n   ROW={}\n   CONTEXT={}\n   TYPE={}\n   NAME={}\n   POLARITY=POSITIVE\n   START_NAME={}\n   END_NAME={}\n   OLD_NAME=\n   ADD_TYPE=\n   COLOR=0\n}}\n\n",
+            index + 1,
+            row.context,
+            row.layer_type,
+            row.name.to_uppercase(),
+            start.to_uppercase(),
+            end.to_uppercase()
+        );
+    }
+    content
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Result of an ODB++ export
+#[derive(Debug, Clone)]
+pub struct OdbExportResult {
+    pub job_dir: PathBuf,
+    pub layers: usize,
+    /// Macro aperture flashes that could not be written
+    pub skipped_flashes: usize,
+}
+
+/// Write the loaded board as an ODB++ job directory `<out_dir>/<job_name>`
+pub fn export(world: &mut World, out_dir: &Path, job_name: &str) -> Result<OdbExportResult, String> {
+    let mut loaded: Vec<LayerType> = LayerType::all()
+        .into_iter()
+        .filter(|layer_type| crate::ecs::get_layer_by_type(world, *layer_type).is_some())
+        .collect();
+    if loaded.is_empty() {
+        return Err("No layers loaded".to_string());
+    }
+    loaded.sort_by_key(|layer_type| stack_order(*layer_type));
+
+    let job_dir = out_dir.join(job_name);
+    let step_dir = job_dir.join("steps").join(STEP_NAME);
+    let mut rows = Vec::new();
+    let mut skipped_flashes = 0;
+
+    for layer_type in &loaded {
+        let (name, context, odb_type) = odb_layer(*layer_type);
+        let (features, skipped) = write_features(&crate::ecs::get_layer_primitives(world, *layer_type));
+        write_file(&step_dir.join("layers").join(&name).join("features"), &features)?;
+        skipped_flashes += skipped;
+        rows.push(MatrixRow { name, context, layer_type: odb_type, span: None });
+    }
+
+    // Drills span the outer signal layers that are present
+    let signal_names: Vec<String> = rows.iter().filter(|row| row.layer_type == "SIGNAL").map(|row| row.name.clone()).collect();
+    let span = signal_names.first().zip(signal_names.last()).map(|(start, end)| (start.clone(), end.clone()));
+    let holes = crate::ecs::get_drill_holes(world);
+    for (name, plated) in [("drill", true), ("drill_npth", false)] {
+        let holes: Vec<DrillHole> = holes.iter().filter(|hole| hole.plated == plated).cloned().collect();
+        if holes.is_empty() {
+            continue;
+        }
+        write_file(&step_dir.join("layers").join(name).join("features"), &write_drill_features(&holes))?;
+        // Non-drill rows come in stack order, so drills sit right after the copper
+        let position = rows.iter().rposition(|row| row.context == "BOARD").map_or(rows.len(), |index| index + 1);
+        rows.insert(position, MatrixRow { name: name.to_string(), context: "BOARD", layer_type: "DRILL", span: span.clone() });
+    }
+
+    let contour = match crate::ecs::get_board_outline_primitives(world) {
+        Some((primitives, _)) => crate::ecs::board_profile(&primitives).0,
+        None => Vec::new(),
+    };
+    write_file(&step_dir.join("profile"), &write_profile(&contour))?;
+    write_file(
+        &step_dir.join("stephdr"),
+        "UNITS=MM\nX_DATUM=0\nY_DATUM=0\nX_ORIGIN=0\nY_ORIGIN=0\nTOP_ACTIVE=0\nBOTTOM_ACTIVE=0\nRIGHT_ACTIVE=0\nLEFT_ACTIVE=0\n",
+    )?;
+    write_file(&job_dir.join("matrix").join("matrix"), &write_matrix(&rows))?;
+
+    let now = chrono::Local::now().format("%Y%m%d.%H%M%S");
+    write_file(
+        &job_dir.join("misc").join("info"),
+        &format!(
+            "UNITS=MM\nJOB_NAME={}\nODB_VERSION_MAJOR=8\nODB_VERSION_MINOR=1\nODB_SOURCE=CopperForge {}\nCREATION_DATE={}\nSAVE_DATE={}\nSAVE_APP=CopperForge\nSAVE_USER=\n",
+            job_name.to_lowercase(),
+            env!("CARGO_PKG_VERSION"),
+            now,
+            now
+        ),
+    )?;
+
+    Ok(OdbExportResult { job_dir, layers: rows.len(), skipped_flashes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primitive(shape: PrimitiveShape, polarity: Polarity) -> TaggedPrimitive {
+        TaggedPrimitive { net: None, polarity, shape }
+    }
+
+    #[test]
+    fn test_primitives_map_to_feature_records() {
+        let primitives = vec![
+            primitive(PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(5.0, 0.0), width: 0.2 }, Polarity::Dark),
+            primitive(PrimitiveShape::Flash { center: Point2::new(1.0, 2.0), width: 1.5, height: 0.8, aperture: ApertureShape::Rectangle, outline: None }, Polarity::Dark),
+            primitive(PrimitiveShape::Flash { center: Point2::new(3.0, 2.0), width: 0.2, height: 0.2, aperture: ApertureShape::Circle, outline: None }, Polarity::Clear),
+            primitive(PrimitiveShape::Flash { center: Point2::new(4.0, 2.0), width: 1.0, height: 1.0, aperture: ApertureShape::Other, outline: None }, Polarity::Dark),
+            primitive(PrimitiveShape::Region { points: vec![Point2::new(0.0, 0.0), Point2::new(2.0, 0.0), Point2::new(2.0, 2.0)] }, Polarity::Dark),
+        ];
+        let (features, skipped) = write_features(&primitives);
+        assert_eq!(skipped, 1);
+
+        // The 0.2 mm round symbol is shared by the line and the clear pad
+        assert!(features.contains("$0 r200\n$1 rect1500x800\n#"));
+        assert!(features.contains("L 0.000000 0.000000 5.000000 0.000000 0 P 0\n"));
+        assert!(features.contains("P 1.000000 2.000000 1 P 0 0\n"));
+        assert!(features.contains("P 3.000000 2.000000 0 N 0 0\n"));
+        // Counter-clockwise region written clockwise and closed
+        assert!(features.contains("S P 0\nOB 2.000000 2.000000 I\nOS 2.000000 0.000000\nOS 0.000000 0.000000\nOS 2.000000 2.000000\nOE\nSE\n"));
+    }
+}
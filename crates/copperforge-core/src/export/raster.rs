@@ -0,0 +1,347 @@
+//! Headless coverage rasterizer for extracted primitives
+//!
+//! Renders `TaggedPrimitive`s into a boolean mask by sampling pixel centres,
+//! honouring dark/clear polarity in file order. Used where egui's painter is not
+//! available (image diffs, tests).
+
+use std::sync::Arc;
+
+use gerber_viewer::BoundingBox;
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::ecs::{ApertureShape, Polarity, PrimitiveShape, TaggedPrimitive};
+
+/// Pixel grid over a region of gerber space (mm); row 0 is the top (max Y) edge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterGrid {
+    /// Gerber coordinates of the top-left corner of pixel (0, 0)
+    pub top_left: Point2<f64>,
+    pub pixels_per_mm: f64,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RasterGrid {
+    /// Grid covering `bounds`, lowering the resolution if either side would exceed `max_dimension`
+    pub fn covering(bounds: &BoundingBox, pixels_per_mm: f64, max_dimension: u32) -> Self {
+        let longest_side = bounds.width().max(bounds.height()).max(f64::EPSILON);
+        let pixels_per_mm = pixels_per_mm.min(max_dimension as f64 / longest_side);
+        Self {
+            top_left: Point2::new(bounds.min.x, bounds.max.y),
+            pixels_per_mm,
+            width: ((bounds.width() * pixels_per_mm).ceil() as u32).max(1),
+            height: ((bounds.height() * pixels_per_mm).ceil() as u32).max(1),
+        }
+    }
+
+    /// Gerber coordinates of a pixel centre
+    pub fn pixel_center(&self, x: u32, y: u32) -> Point2<f64> {
+        Point2::new(
+            self.top_left.x + (x as f64 + 0.5) / self.pixels_per_mm,
+            self.top_left.y - (y as f64 + 0.5) / self.pixels_per_mm,
+        )
+    }
+
+    /// Area of one pixel in mm²
+    pub fn pixel_area(&self) -> f64 {
+        1.0 / (self.pixels_per_mm * self.pixels_per_mm)
+    }
+
+    /// Inclusive pixel range touched by a gerber-space box, clamped to the grid
+    fn pixel_range(&self, min: Point2<f64>, max: Point2<f64>) -> Option<(u32, u32, u32, u32)> {
+        let x0 = ((min.x - self.top_left.x) * self.pixels_per_mm).floor().max(0.0);
+        let x1 = ((max.x - self.top_left.x) * self.pixels_per_mm).ceil().min(self.width as f64 - 1.0);
+        let y0 = ((self.top_left.y - max.y) * self.pixels_per_mm).floor().max(0.0);
+        let y1 = ((self.top_left.y - min.y) * self.pixels_per_mm).ceil().min(self.height as f64 - 1.0);
+        if x0 > x1 || y0 > y1 {
+            return None;
+        }
+        Some((x0 as u32, y0 as u32, x1 as u32, y1 as u32))
+    }
+}
+
+/// Boolean coverage mask, row-major
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageMask {
+    pub grid: RasterGrid,
+    pub pixels: Vec<bool>,
+}
+
+impl CoverageMask {
+    pub fn new(grid: RasterGrid) -> Self {
+        Self {
+            grid,
+            pixels: vec![false; grid.width as usize * grid.height as usize],
+        }
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        self.pixels[y as usize * self.grid.width as usize + x as usize]
+    }
+
+    fn set(&mut self, x: u32, y: u32, value: bool) {
+        let index = y as usize * self.grid.width as usize + x as usize;
+        self.pixels[index] = value;
+    }
+
+    pub fn covered_count(&self) -> usize {
+        self.pixels.iter().filter(|p| **p).count()
+    }
+}
+
+/// Bounding box of the primitives including stroke widths and aperture sizes
+pub fn primitives_bounds(primitives: &[TaggedPrimitive]) -> Option<BoundingBox> {
+    let mut points = Vec::new();
+    for primitive in primitives {
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => {
+                let r = Vector2::new(width / 2.0, width / 2.0);
+                points.extend([start - r, start + r, end - r, end + r]);
+            }
+            PrimitiveShape::Flash { center, width, height, .. } => {
+                let half = Vector2::new(width / 2.0, height / 2.0);
+                points.extend([center - half, center + half]);
+            }
+            PrimitiveShape::Region { points: contour } => points.extend(contour.iter().copied()),
+        }
+    }
+    if points.is_empty() {
+        None
+    } else {
+        Some(BoundingBox::from_points(&points))
+    }
+}
+
+/// Apply an affine transform (e.g. a layer's render transform) to primitives
+/// Rectangles become regions so rotations other than 90° stay exact; round
+/// shapes are scaled by the transform's uniform scale
+pub fn transform_primitives(primitives: &[TaggedPrimitive], matrix: &Matrix3<f64>) -> Vec<TaggedPrimitive> {
+    let scale = matrix.fixed_view::<2, 2>(0, 0).determinant().abs().sqrt();
+    let apply = |point: &Point2<f64>| matrix.transform_point(point);
+
+    primitives
+        .iter()
+        .map(|primitive| {
+            let shape = match &primitive.shape {
+                PrimitiveShape::Segment { start, end, width } => PrimitiveShape::Segment {
+                    start: apply(start),
+                    end: apply(end),
+                    width: width * scale,
+                },
+                PrimitiveShape::Flash { center, width, height, aperture: ApertureShape::Rectangle, .. } => {
+                    let (hw, hh) = (width / 2.0, height / 2.0);
+                    PrimitiveShape::Region {
+                        points: [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+                            .iter()
+                            .map(|(dx, dy)| apply(&Point2::new(center.x + dx, center.y + dy)))
+                            .collect(),
+                    }
+                }
+                PrimitiveShape::Flash { center, width, height, aperture: ApertureShape::Obround, .. } if width != height => {
+                    // Capsule along the long axis
+                    let radius = width.min(*height) / 2.0;
+                    let axis = if width > height {
+                        Vector2::new(width / 2.0 - radius, 0.0)
+                    } else {
+                        Vector2::new(0.0, height / 2.0 - radius)
+                    };
+                    PrimitiveShape::Segment {
+                        start: apply(&(center - axis)),
+                        end: apply(&(center + axis)),
+                        width: radius * 2.0 * scale,
+                    }
+                }
+                PrimitiveShape::Flash { center, width, height, aperture, outline } => {
+                    let outline = outline.as_ref().map(|outline| outline.transformed(matrix));
+                    // A rotated macro keeps a box centred on the flash that holds it
+                    let (width, height) = match &outline {
+                        Some(outline) if *aperture == ApertureShape::Other => outline.centered_size(),
+                        _ => (width * scale, height * scale),
+                    };
+                    PrimitiveShape::Flash { center: apply(center), width, height, aperture: *aperture, outline: outline.map(Arc::new) }
+                }
+                PrimitiveShape::Region { points } => PrimitiveShape::Region {
+                    points: points.iter().map(apply).collect(),
+                },
+            };
+            TaggedPrimitive {
+                net: primitive.net.clone(),
+                polarity: primitive.polarity,
+                shape,
+            }
+        })
+        .collect()
+}
+
+/// Area covered by primitives in mm², measured on a raster of `pixels_per_mm`
+/// (lowered so neither side exceeds `max_dimension`); clear primitives remove what
+/// was drawn before them, so pour voids and clearances are not counted
+pub fn covered_area(primitives: &[TaggedPrimitive], pixels_per_mm: f64, max_dimension: u32) -> f64 {
+    let Some(bounds) = primitives_bounds(primitives) else {
+        return 0.0;
+    };
+    let grid = RasterGrid::covering(&bounds, pixels_per_mm, max_dimension);
+    rasterize_primitives(primitives, grid).covered_count() as f64 * grid.pixel_area()
+}
+
+/// Rasterize primitives into a coverage mask on `grid`
+pub fn rasterize_primitives(primitives: &[TaggedPrimitive], grid: RasterGrid) -> CoverageMask {
+    let mut mask = CoverageMask::new(grid);
+    paint_primitives(&mut mask, primitives, false);
+    mask
+}
+
+/// Paint primitives over `mask` in file order: dark ones cover, clear ones uncover
+/// `inverted` swaps the two, as on a negative layer
+pub fn paint_primitives(mask: &mut CoverageMask, primitives: &[TaggedPrimitive], inverted: bool) {
+    // Hairlines (zero-width outlines) still cover half a pixel so they stay visible
+    let min_radius = 0.5 / mask.grid.pixels_per_mm;
+
+    for primitive in primitives {
+        let value = (primitive.polarity == Polarity::Dark) != inverted;
+        match &primitive.shape {
+            PrimitiveShape::Segment { start, end, width } => {
+                fill_capsule(mask, *start, *end, (width / 2.0).max(min_radius), value);
+            }
+            PrimitiveShape::Flash { center, outline: Some(outline), .. } => {
+                let (min, max) = outline.bounds();
+                fill_where(mask, center + min.coords, center + max.coords, value, |p| outline.contains(Point2::from(p - center)));
+            }
+            PrimitiveShape::Flash { center, width, height, aperture, .. } => match aperture {
+                ApertureShape::Circle | ApertureShape::Polygon => {
+                    fill_capsule(mask, *center, *center, width / 2.0, value);
+                }
+                ApertureShape::Rectangle => {
+                    let half = Vector2::new(width / 2.0, height / 2.0);
+                    fill_where(mask, center - half, center + half, value, |_| true);
+                }
+                ApertureShape::Obround => {
+                    let radius = width.min(*height) / 2.0;
+                    let axis = if width >= height {
+                        Vector2::new(width / 2.0 - radius, 0.0)
+                    } else {
+                        Vector2::new(0.0, height / 2.0 - radius)
+                    };
+                    fill_capsule(mask, center - axis, center + axis, radius, value);
+                }
+                // Undefined aperture: no known shape
+                ApertureShape::Other => {}
+            },
+            PrimitiveShape::Region { points } => fill_contours(mask, std::slice::from_ref(points), value),
+        }
+    }
+}
+
+fn fill_where(
+    mask: &mut CoverageMask,
+    min: Point2<f64>,
+    max: Point2<f64>,
+    value: bool,
+    inside: impl Fn(Point2<f64>) -> bool,
+) {
+    let Some((x0, y0, x1, y1)) = mask.grid.pixel_range(min, max) else {
+        return;
+    };
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            let center = mask.grid.pixel_center(x, y);
+            if center.x >= min.x && center.x <= max.x && center.y >= min.y && center.y <= max.y && inside(center) {
+                mask.set(x, y, value);
+            }
+        }
+    }
+}
+
+/// Fill every pixel within `radius` of the segment start..end
+fn fill_capsule(mask: &mut CoverageMask, start: Point2<f64>, end: Point2<f64>, radius: f64, value: bool) {
+    let r = Vector2::new(radius, radius);
+    let min = Point2::new(start.x.min(end.x), start.y.min(end.y)) - r;
+    let max = Point2::new(start.x.max(end.x), start.y.max(end.y)) + r;
+    let segment = end - start;
+    let length_sq = segment.norm_squared();
+
+    fill_where(mask, min, max, value, |p| {
+        let t = if length_sq > 0.0 { ((p - start).dot(&segment) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+        (p - (start + segment * t)).norm() <= radius
+    });
+}
+
+/// Even-odd scanline fill of closed contours, so contours inside others are holes
+pub fn fill_contours(mask: &mut CoverageMask, contours: &[Vec<Point2<f64>>], value: bool) {
+    let points = contours.iter().filter(|contour| contour.len() >= 3).flatten();
+    let (min, max) = points.fold(
+        (Point2::new(f64::INFINITY, f64::INFINITY), Point2::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(min, max), p| (Point2::new(min.x.min(p.x), min.y.min(p.y)), Point2::new(max.x.max(p.x), max.y.max(p.y))),
+    );
+    if !min.x.is_finite() {
+        return;
+    }
+    let Some((x0, y0, x1, y1)) = mask.grid.pixel_range(min, max) else {
+        return;
+    };
+
+    let mut crossings = Vec::new();
+    for y in y0..=y1 {
+        let scan_y = mask.grid.pixel_center(0, y).y;
+        crossings.clear();
+        for points in contours.iter().filter(|contour| contour.len() >= 3) {
+            for i in 0..points.len() {
+                let a = points[i];
+                let b = points[(i + 1) % points.len()];
+                if (a.y > scan_y) != (b.y > scan_y) {
+                    crossings.push(a.x + (scan_y - a.y) * (b.x - a.x) / (b.y - a.y));
+                }
+            }
+        }
+        crossings.sort_by(|a, b| a.total_cmp(b));
+
+        for pair in crossings.chunks_exact(2) {
+            for x in x0..=x1 {
+                let center_x = mask.grid.pixel_center(x, y).x;
+                if center_x >= pair[0] && center_x <= pair[1] {
+                    mask.set(x, y, value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10 x 10 mm pour with a 4 x 4 mm clearance in the middle
+    const POUR_WITH_CLEARANCE: &str = "%FSLAX46Y46*%\n%MOMM*%\n%LPD*%\nG36*\nX0Y0D02*\nX10000000Y0D01*\nX10000000Y10000000D01*\nX0Y10000000D01*\nX0Y0D01*\nG37*\n%LPC*%\nG36*\nX3000000Y3000000D02*\nX7000000Y3000000D01*\nX7000000Y7000000D01*\nX3000000Y7000000D01*\nX3000000Y3000000D01*\nG37*\nM02*\n";
+
+    #[test]
+    fn test_clearance_in_pour_is_a_hole() {
+        let primitives = crate::ecs::extract_primitives(POUR_WITH_CLEARANCE);
+        assert_eq!(primitives[1].polarity, Polarity::Clear);
+        // Painted from its primitives on screen, so the clearance erases the pour
+        assert!(crate::ecs::LayerPolarity::new(POUR_WITH_CLEARANCE, &primitives).needs_compositing());
+
+        let grid = RasterGrid::covering(&primitives_bounds(&primitives).unwrap(), 10.0, 4096);
+        let mask = rasterize_primitives(&primitives, grid);
+        // Pixel (50, 50) is the board center, inside the clearance; (10, 10) is pour
+        assert!(!mask.get(50, 50));
+        assert!(mask.get(10, 10));
+
+        let area = covered_area(&primitives, 10.0, 4096);
+        assert!((area - 84.0).abs() < 0.5, "area {}", area);
+    }
+
+    #[test]
+    fn test_macro_cutout_is_a_hole_in_the_pad_only() {
+        // 2 mm square pad with a 1 mm round cut-out, flashed twice: over a 4 mm pour and on its own
+        let gerber = "%FSLAX46Y46*%\n%MOMM*%\n%AMDonut*\n21,1,2,2,0,0,0*\n1,0,1,0,0*%\n%ADD10Donut*%\n%ADD11C,4*%\n\
+            D11*\nX0Y0D03*\nD10*\nX0Y0D03*\nX5000000Y0D03*\nM02*\n";
+        let primitives = crate::ecs::extract_primitives(gerber);
+        let grid = RasterGrid::covering(&primitives_bounds(&primitives).unwrap(), 10.0, 4096);
+        let mask = rasterize_primitives(&primitives, grid);
+        let pixel = |x: f64, y: f64| mask.get(((x - grid.top_left.x) * 10.0) as u32, ((grid.top_left.y - y) * 10.0) as u32);
+
+        assert!(pixel(0.0, 0.0), "the pour under the cut-out stays");
+        assert!(!pixel(5.0, 0.0), "the lone pad has its hole");
+        assert!(pixel(5.8, 0.8));
+    }
+}
@@ -0,0 +1,210 @@
+//! SVG markup of the current gerber view
+//!
+//! Visible layers are written from their extracted primitives, transformed with
+//! the on-screen layer transform and the view state, so the markup lines up
+//! with the viewport pixel for pixel. Only layer geometry and, if asked for,
+//! review annotations are written; grid and other overlays are drawn by the
+//! painter and have no vector source here.
+
+use std::fmt::Write;
+
+use bevy_ecs::world::World;
+use egui::Color32;
+use egui::emath::Rect;
+use gerber_viewer::ViewState;
+use nalgebra::Matrix3;
+
+use super::raster::transform_primitives;
+use crate::display::DisplayManager;
+use crate::ecs::{ApertureShape, LayerInfo, LayerType, Polarity, PrimitiveShape, RenderProperties, TaggedPrimitive, Visibility};
+use crate::project_manager::annotation::{arrow_head, Annotation, AnnotationKind};
+
+/// Arrow head length and label size of annotations (pixels)
+const ANNOTATION_ARROW_HEAD_PX: f64 = 10.0;
+const ANNOTATION_FONT_PX: f64 = 12.0;
+
+/// Gerber (mm) to viewport pixel coordinates, with (0, 0) at the viewport's top-left corner
+pub fn view_matrix(view_state: &ViewState, viewport: &Rect) -> Matrix3<f64> {
+    let scale = view_state.scale as f64;
+    Matrix3::new(
+        scale, 0.0, (view_state.translation.x - viewport.min.x) as f64,
+        0.0, -scale, (view_state.translation.y - viewport.min.y) as f64,
+        0.0, 0.0, 1.0,
+    )
+}
+
+fn hex(color: Color32) -> String {
+    let [r, g, b, _] = color.to_srgba_unmultiplied();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn opacity(color: Color32) -> f32 {
+    color.to_srgba_unmultiplied()[3] as f32 / 255.0
+}
+
+/// One primitive in pixel coordinates; clear primitives are painted with the background
+fn write_primitive(out: &mut String, primitive: &TaggedPrimitive, background: &str) {
+    let paint = match primitive.polarity {
+        Polarity::Dark => String::new(),
+        Polarity::Clear => format!(" fill=\"{0}\" stroke=\"{0}\" fill-opacity=\"1\" stroke-opacity=\"1\"", background),
+    };
+    let _ = match &primitive.shape {
+        PrimitiveShape::Segment { start, end, width } => writeln!(
+            out,
+            "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\" stroke-width=\"{:.3}\"{}/>",
+            start.x, start.y, end.x, end.y, width.max(1.0), paint
+        ),
+        PrimitiveShape::Flash { center, width, height, aperture: ApertureShape::Other, .. } => writeln!(
+            out,
+            "    <rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" stroke=\"none\"{}/>",
+            center.x - width / 2.0, center.y - height / 2.0, width, height, paint
+        ),
+        PrimitiveShape::Flash { center, width, .. } => writeln!(
+            out,
+            "    <circle cx=\"{:.3}\" cy=\"{:.3}\" r=\"{:.3}\" stroke=\"none\"{}/>",
+            center.x, center.y, width / 2.0, paint
+        ),
+        PrimitiveShape::Region { points } => {
+            let mut path = String::new();
+            for (index, point) in points.iter().enumerate() {
+                let _ = write!(path, "{}{:.3} {:.3} ", if index == 0 { "M" } else { "L" }, point.x, point.y);
+            }
+            writeln!(out, "    <path d=\"{}Z\" stroke=\"none\"{}/>", path, paint)
+        }
+    };
+}
+
+/// `<g>` element for one layer, with primitives already in pixel coordinates
+fn write_layer(out: &mut String, layer_type: LayerType, color: Color32, primitives: &[TaggedPrimitive], background: &str) {
+    let _ = writeln!(
+        out,
+        "  <g id=\"{}\" fill=\"{1}\" stroke=\"{1}\" fill-opacity=\"{2:.3}\" stroke-opacity=\"{2:.3}\" stroke-linecap=\"round\">",
+        layer_type.display_name().replace(['(', ')'], "").replace(' ', "_").to_lowercase(),
+        hex(color),
+        opacity(color),
+    );
+    for primitive in primitives {
+        write_primitive(out, primitive, background);
+    }
+    out.push_str("  </g>\n");
+}
+
+/// Escape text for SVG content and attributes
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// `<g>` element with the annotations; `to_view` maps board coordinates to pixels
+fn write_annotations(out: &mut String, annotations: &[Annotation], to_view: &Matrix3<f64>) {
+    out.push_str("  <g id=\"annotations\" fill=\"none\" stroke-width=\"2\" stroke-linecap=\"round\">\n");
+    for annotation in annotations {
+        let color = hex(Color32::from_rgb(annotation.color[0], annotation.color[1], annotation.color[2]));
+        let points = annotation.outline(to_view);
+        match (annotation.kind, points.as_slice()) {
+            (AnnotationKind::Note, [center]) => {
+                let _ = writeln!(out, "    <circle cx=\"{:.3}\" cy=\"{:.3}\" r=\"5\" fill=\"{2}\" stroke=\"{2}\"/>", center.x, center.y, color);
+            }
+            (AnnotationKind::Arrow, [tail, tip]) => {
+                let mut path = format!("M{:.3} {:.3} L{:.3} {:.3}", tail.x, tail.y, tip.x, tip.y);
+                if let Some([left, right]) = arrow_head(*tail, *tip, ANNOTATION_ARROW_HEAD_PX) {
+                    let _ = write!(path, " M{:.3} {:.3} L{:.3} {:.3} L{:.3} {:.3}", left.x, left.y, tip.x, tip.y, right.x, right.y);
+                }
+                let _ = writeln!(out, "    <path d=\"{}\" stroke=\"{}\"/>", path, color);
+            }
+            (_, points) => {
+                let points: Vec<String> = points.iter().map(|point| format!("{:.3},{:.3}", point.x, point.y)).collect();
+                let _ = writeln!(out, "    <polygon points=\"{}\" stroke=\"{1}\" fill=\"{1}\" fill-opacity=\"0.15\"/>", points.join(" "), color);
+            }
+        }
+        // Label just right of the note, arrow tail or first corner
+        if let (Some(anchor), false) = (points.first(), annotation.label().is_empty()) {
+            let _ = writeln!(
+                out,
+                "    <text x=\"{:.3}\" y=\"{:.3}\" font-family=\"sans-serif\" font-size=\"{}\" fill=\"{}\" stroke=\"none\">{}</text>",
+                anchor.x + 8.0, anchor.y - 4.0, ANNOTATION_FONT_PX, color, escape(annotation.label())
+            );
+        }
+    }
+    out.push_str("  </g>\n");
+}
+
+/// SVG document of the visible layers as they appear in `viewport`, with `annotations` on top
+pub fn view_svg(
+    world: &mut World,
+    display_manager: &DisplayManager,
+    view_state: &ViewState,
+    viewport: &Rect,
+    background: Color32,
+    annotations: &[Annotation],
+) -> Result<String, String> {
+    let mut query = world.query::<(&LayerInfo, &Visibility, &RenderProperties)>();
+    let mut layers: Vec<_> = query.iter(world)
+        .filter(|(_, visibility, _)| visibility.visible)
+        .map(|(info, _, props)| (info.layer_type, props.color, props.z_order))
+        .collect();
+    if layers.is_empty() {
+        return Err("No visible layers to copy".to_string());
+    }
+    layers.sort_by_key(|(_, _, z_order)| *z_order);
+
+    let to_view = view_matrix(view_state, viewport);
+    let background = hex(background);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.0}\" height=\"{1:.0}\" viewBox=\"0 0 {0:.3} {1:.3}\">",
+        viewport.width(), viewport.height()
+    );
+    let _ = writeln!(out, "  <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", background);
+    for (layer_type, color, _) in layers {
+        let Some(transform) = crate::ecs::get_layer_render_transform(world, display_manager, layer_type) else {
+            continue;
+        };
+        let matrix = to_view * transform.to_matrix();
+        let primitives = transform_primitives(&crate::ecs::get_layer_primitives(world, layer_type), &matrix);
+        if !primitives.is_empty() {
+            write_layer(&mut out, layer_type, color, &primitives, &background);
+        }
+    }
+    if !annotations.is_empty() {
+        // Annotations are in board coordinates, like the overlay drawn on screen
+        let board_to_view = crate::ecs::get_board_view_transform(world, display_manager)
+            .map_or_else(Matrix3::identity, |transform| transform.to_matrix());
+        write_annotations(&mut out, annotations, &(to_view * board_to_view));
+    }
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Pos2, Vec2};
+    use nalgebra::Point2;
+
+    #[test]
+    fn test_layer_markup_uses_viewport_pixels() {
+        let view_state = ViewState { scale: 10.0, base_scale: 10.0, translation: Vec2::new(150.0, 250.0) };
+        let viewport = Rect::from_min_size(Pos2::new(50.0, 50.0), Vec2::new(400.0, 300.0));
+        let primitives = [
+            TaggedPrimitive {
+                net: None,
+                polarity: Polarity::Dark,
+                shape: PrimitiveShape::Segment { start: Point2::new(0.0, 0.0), end: Point2::new(2.0, 1.0), width: 0.2 },
+            },
+            TaggedPrimitive {
+                net: None,
+                polarity: Polarity::Clear,
+                shape: PrimitiveShape::Flash { center: Point2::new(1.0, 0.0), width: 0.5, height: 0.5, aperture: ApertureShape::Circle, outline: None },
+            },
+        ];
+        let primitives = transform_primitives(&primitives, &view_matrix(&view_state, &viewport));
+
+        let mut out = String::new();
+        write_layer(&mut out, LayerType::Copper(1), Color32::from_rgb(255, 0, 0), &primitives, "#000000");
+        assert!(out.contains("<g id=\"top_copper_l1\" fill=\"#ff0000\""), "{}", out);
+        // Gerber (0, 0) lands at the view translation, Y grows downwards
+        assert!(out.contains("x1=\"100.000\" y1=\"200.000\" x2=\"120.000\" y2=\"190.000\" stroke-width=\"2.000\""), "{}", out);
+        assert!(out.contains("<circle cx=\"110.000\" cy=\"200.000\" r=\"2.500\" stroke=\"none\" fill=\"#000000\""), "{}", out);
+    }
+}
@@ -0,0 +1,6 @@
+//! Readers for board data that does not come as gerbers
+//!
+//! Imported layers are converted to gerber primitives and loaded like gerber
+//! layers, so rendering, DRC and export treat them the same.
+
+pub mod odb;
@@ -0,0 +1,639 @@
+//! ODB++ job reader
+//!
+//! Reads the layer artwork of an uncompressed ODB++ directory: the matrix gives
+//! the layer names, their order and their context/type, and each board layer's
+//! `features` file in the step gives the geometry. Lines, pads with standard
+//! symbols (round, square, rectangle, oval) and surfaces become primitives;
+//! other records (arcs, text, barcodes, pads with user symbols) are counted per
+//! layer and skipped. Pads on drill layers become drill holes.
+//!
+//! Every imported layer is written as gerber and loaded like a gerber file, so
+//! it renders, checks and exports like one. Surface holes are cut into their
+//! island, as gerber regions do, so they never clear other features; negative
+//! layers are written with a negative file polarity.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::world::World;
+use gerber_viewer::GerberTransform;
+use nalgebra::{Matrix3, Point2};
+
+use crate::ecs::outline::signed_area;
+use crate::ecs::{ApertureShape, DrillHole, LayerType, Polarity, PrimitiveShape, Side, TaggedPrimitive};
+
+/// Step read when the job has one with this name; otherwise the first step
+const PREFERRED_STEP: &str = "pcb";
+
+/// Segments per full turn when arcs in surface contours are flattened
+const ARC_SEGMENTS_PER_TURN: f64 = 64.0;
+
+/// One layer row of the matrix file; names are lower case like the layer directories
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixLayer {
+    pub row: u32,
+    pub name: String,
+    pub context: String,
+    pub layer_type: String,
+    /// `POLARITY=NEGATIVE`: the artwork is the clearance of a plane
+    pub negative: bool,
+}
+
+impl MatrixLayer {
+    fn is_signal(&self) -> bool {
+        self.context == "BOARD" && matches!(self.layer_type.as_str(), "SIGNAL" | "POWER_GROUND" | "MIXED")
+    }
+}
+
+/// Layer rows of a matrix file, in row order
+pub fn read_matrix(content: &str) -> Vec<MatrixLayer> {
+    let mut layers = Vec::new();
+    let mut current: Option<BTreeMap<String, String>> = None;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with("LAYER") && line.ends_with('{') {
+            current = Some(BTreeMap::new());
+        } else if line == "}" {
+            if let Some(fields) = current.take() {
+                let field = |key: &str| fields.get(key).cloned().unwrap_or_default();
+                layers.push(MatrixLayer {
+                    row: field("ROW").parse().unwrap_or(u32::MAX),
+                    name: field("NAME").to_lowercase(),
+                    context: field("CONTEXT").to_uppercase(),
+                    layer_type: field("TYPE").to_uppercase(),
+                    negative: field("POLARITY").eq_ignore_ascii_case("NEGATIVE"),
+                });
+            }
+        } else if let (Some(fields), Some((key, value))) = (current.as_mut(), line.split_once('=')) {
+            fields.insert(key.trim().to_uppercase(), value.trim().to_string());
+        }
+    }
+    layers.sort_by_key(|layer| layer.row);
+    layers
+}
+
+/// Layer type of each matrix row, `None` for rows we have no layer for
+///
+/// Signal layers are numbered top to bottom; mask, silkscreen and paste rows
+/// above the first signal layer are on the top side, the others on the bottom.
+pub fn assign_layer_types(rows: &[MatrixLayer]) -> Vec<Option<LayerType>> {
+    let signals: Vec<usize> = rows.iter().enumerate().filter(|(_, row)| row.is_signal()).map(|(index, _)| index).collect();
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let side = if signals.first().is_none_or(|first| index < *first) { Side::Top } else { Side::Bottom };
+            match (row.context.as_str(), row.layer_type.as_str()) {
+                _ if row.is_signal() => {
+                    let position = signals.iter().position(|signal| *signal == index)?;
+                    Some(match position {
+                        0 => LayerType::Copper(1),
+                        p if p == signals.len() - 1 => LayerType::Copper(2),
+                        p => LayerType::Copper(p as u8 + 2),
+                    })
+                }
+                ("BOARD", "SOLDER_MASK") => Some(LayerType::Soldermask(side)),
+                ("BOARD", "SILK_SCREEN") => Some(LayerType::Silkscreen(side)),
+                ("BOARD", "SOLDER_PASTE") => Some(LayerType::Paste(side)),
+                (_, "ROUT") => Some(LayerType::MechanicalOutline),
+                (_, "DOCUMENT") if row.name.contains("outline") || row.name.contains("edge") => Some(LayerType::MechanicalOutline),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Geometry of one features file and the records that were skipped, by kind
+#[derive(Debug, Clone, Default)]
+pub struct LayerFeatures {
+    pub primitives: Vec<TaggedPrimitive>,
+    pub unsupported: BTreeMap<String, usize>,
+}
+
+impl LayerFeatures {
+    fn skip(&mut self, kind: &str) {
+        *self.unsupported.entry(kind.to_string()).or_default() += 1;
+    }
+}
+
+/// Coordinates are in mm or inch; symbol sizes in microns or mils to match
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Units {
+    Mm,
+    Inch,
+}
+
+impl Units {
+    fn coordinate_mm(self, value: f64) -> f64 {
+        match self {
+            Units::Mm => value,
+            Units::Inch => value * 25.4,
+        }
+    }
+
+    fn symbol_mm(self, value: f64) -> f64 {
+        match self {
+            Units::Mm => value / 1000.0,
+            Units::Inch => value * 0.0254,
+        }
+    }
+}
+
+/// Aperture shape and size (mm) of a standard symbol; None for other symbols
+fn parse_symbol(name: &str, units: Units) -> Option<(ApertureShape, f64, f64)> {
+    let size = |text: &str| text.parse::<f64>().ok().filter(|size| *size > 0.0).map(|size| units.symbol_mm(size));
+    let pair = |text: &str| {
+        // Corner radius or chamfer suffixes (`xr..`, `xc..`) are ignored
+        let mut parts = text.split('x');
+        Some((size(parts.next()?)?, size(parts.next()?)?))
+    };
+    if let Some(rest) = name.strip_prefix("rect") {
+        let (width, height) = pair(rest)?;
+        Some((ApertureShape::Rectangle, width, height))
+    } else if let Some(rest) = name.strip_prefix("oval") {
+        let (width, height) = pair(rest)?;
+        Some((ApertureShape::Obround, width, height))
+    } else if let Some(rest) = name.strip_prefix('r') {
+        size(rest).map(|diameter| (ApertureShape::Circle, diameter, diameter))
+    } else if let Some(rest) = name.strip_prefix('s') {
+        size(rest).map(|side| (ApertureShape::Rectangle, side, side))
+    } else {
+        None
+    }
+}
+
+fn parse_polarity(code: &str) -> Option<Polarity> {
+    match code {
+        "P" => Some(Polarity::Dark),
+        "N" => Some(Polarity::Clear),
+        _ => None,
+    }
+}
+
+/// Points of an arc from `start` to `end` around `center`, without the start point
+fn arc_points(start: Point2<f64>, end: Point2<f64>, center: Point2<f64>, clockwise: bool) -> Vec<Point2<f64>> {
+    let radius = (start - center).norm();
+    let start_angle = (start.y - center.y).atan2(start.x - center.x);
+    let end_angle = (end.y - center.y).atan2(end.x - center.x);
+    let mut sweep = end_angle - start_angle;
+    if clockwise && sweep >= 0.0 {
+        sweep -= std::f64::consts::TAU;
+    } else if !clockwise && sweep <= 0.0 {
+        sweep += std::f64::consts::TAU;
+    }
+    let steps = (sweep.abs() / std::f64::consts::TAU * ARC_SEGMENTS_PER_TURN).ceil().max(1.0) as usize;
+    (1..=steps)
+        .map(|step| {
+            if step == steps {
+                return end;
+            }
+            let angle = start_angle + sweep * step as f64 / steps as f64;
+            Point2::new(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect()
+}
+
+/// Surface being read, between its `S` and `SE` records
+struct SurfaceBuilder {
+    polarity: Polarity,
+    /// Finished contours with whether they are holes
+    contours: Vec<(Vec<Point2<f64>>, bool)>,
+    open: Option<(Vec<Point2<f64>>, bool)>,
+}
+
+impl SurfaceBuilder {
+    /// Apply one contour record; false when the record is malformed
+    fn add(&mut self, fields: &[&str], units: Units) -> bool {
+        let number = |index: usize| fields.get(index).and_then(|text| text.parse::<f64>().ok()).map(|value| units.coordinate_mm(value));
+        let point = |index: usize| Some(Point2::new(number(index)?, number(index + 1)?));
+        match fields[0] {
+            "OB" => match point(1) {
+                Some(start) => {
+                    self.open = Some((vec![start], fields.get(3) == Some(&"H")));
+                    true
+                }
+                None => false,
+            },
+            "OS" => match (point(1), self.open.as_mut()) {
+                (Some(next), Some((points, _))) => {
+                    points.push(next);
+                    true
+                }
+                _ => false,
+            },
+            "OC" => match (point(1), point(3), self.open.as_mut()) {
+                (Some(end), Some(center), Some((points, _))) => {
+                    let start = *points.last().unwrap_or(&end);
+                    points.extend(arc_points(start, end, center, fields.get(5) == Some(&"Y")));
+                    true
+                }
+                _ => false,
+            },
+            "OE" => match self.open.take() {
+                Some((mut points, hole)) => {
+                    if points.len() > 1 && (points[0] - points[points.len() - 1]).norm() < 1e-9 {
+                        points.pop();
+                    }
+                    self.contours.push((points, hole));
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Push one region per island, with its holes cut in
+    fn finish(self, features: &mut LayerFeatures) {
+        let mut islands = Vec::new();
+        for (points, hole) in self.contours {
+            if points.len() < 3 {
+                features.skip("degenerate contour");
+            } else if !hole {
+                islands.push((points, Vec::new()));
+            } else if let Some((_, holes)) = islands.last_mut() {
+                holes.push(points);
+            } else {
+                features.skip("surface hole without island");
+            }
+        }
+        for (mut points, mut holes) in islands {
+            // Rightmost holes first, so a later cut never crosses an earlier one
+            holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+            for hole in &holes {
+                if !cut_in_hole(&mut points, hole) {
+                    features.skip("surface hole outside its island");
+                }
+            }
+            features.primitives.push(TaggedPrimitive { net: None, polarity: self.polarity, shape: PrimitiveShape::Region { points } });
+        }
+    }
+}
+
+fn max_x(points: &[Point2<f64>]) -> f64 {
+    points.iter().map(|point| point.x).fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Join `hole` to `outer` through a zero-width cut from the hole's rightmost
+/// vertex to the nearest outer edge on its right; false when there is none
+fn cut_in_hole(outer: &mut Vec<Point2<f64>>, hole: &[Point2<f64>]) -> bool {
+    let mut hole = hole.to_vec();
+    // The hole runs against the outer contour so the joined region stays simple
+    if (signed_area(&hole) > 0.0) == (signed_area(outer) > 0.0) {
+        hole.reverse();
+    }
+    let Some(start) = (0..hole.len()).max_by(|&a, &b| hole[a].x.total_cmp(&hole[b].x)) else { return false };
+    let from = hole[start];
+
+    let n = outer.len();
+    let mut nearest: Option<(f64, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (outer[i], outer[(i + 1) % n]);
+        if (a.y > from.y) == (b.y > from.y) {
+            continue;
+        }
+        let x = a.x + (from.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= from.x && nearest.is_none_or(|(best, _)| x < best) {
+            nearest = Some((x, i));
+        }
+    }
+    let Some((x, edge)) = nearest else { return false };
+    let to = Point2::new(x, from.y);
+
+    let mut joined = Vec::with_capacity(n + hole.len() + 3);
+    joined.extend_from_slice(&outer[..=edge]);
+    joined.push(to);
+    joined.extend((0..=hole.len()).map(|step| hole[(start + step) % hole.len()]));
+    joined.push(to);
+    joined.extend_from_slice(&outer[edge + 1..]);
+    *outer = joined;
+    true
+}
+
+/// Rotation of a pad in quarter turns; None for angles that are not a multiple of 90°
+fn pad_quarter_turns(fields: &[&str]) -> Option<i64> {
+    match fields.first().copied() {
+        None => Some(0),
+        Some("8" | "9") => {
+            let angle: f64 = fields.get(1)?.parse().ok()?;
+            let turns = angle / 90.0;
+            ((turns - turns.round()).abs() < 1e-6).then_some(turns.round() as i64)
+        }
+        Some(code) => code.parse::<i64>().ok().map(|code| code % 4),
+    }
+}
+
+/// Primitives of a features file; records that cannot be converted are counted by kind
+pub fn read_features(content: &str) -> LayerFeatures {
+    let mut features = LayerFeatures::default();
+    let mut units = Units::Inch;
+    let mut symbols: BTreeMap<usize, String> = BTreeMap::new();
+    let mut surface: Option<SurfaceBuilder> = None;
+
+    for line in content.lines() {
+        // Attributes follow the semicolon and are not used
+        let record = line.split(';').next().unwrap_or_default().trim();
+        if record.is_empty() || record.starts_with('#') {
+            continue;
+        }
+        if let Some(value) = record.strip_prefix("UNITS=").or_else(|| record.strip_prefix("U ")) {
+            units = if value.trim().eq_ignore_ascii_case("MM") { Units::Mm } else { Units::Inch };
+            continue;
+        }
+        let fields: Vec<&str> = record.split_whitespace().collect();
+
+        if let Some(builder) = surface.as_mut() {
+            if fields[0] == "SE" {
+                if let Some(builder) = surface.take() {
+                    builder.finish(&mut features);
+                }
+            } else if !builder.add(&fields, units) {
+                features.skip("malformed surface record");
+            }
+            continue;
+        }
+
+        let number = |index: usize| fields.get(index).and_then(|text| text.parse::<f64>().ok()).map(|value| units.coordinate_mm(value));
+        let point = |index: usize| Some(Point2::new(number(index)?, number(index + 1)?));
+        let symbol = |index: usize| {
+            let name = symbols.get(&fields.get(index)?.parse::<usize>().ok()?)?;
+            parse_symbol(name, units)
+        };
+
+        match fields[0] {
+            tag if tag.starts_with('$') => {
+                if let (Ok(index), Some(name)) = (tag[1..].parse::<usize>(), fields.get(1)) {
+                    symbols.insert(index, name.to_lowercase());
+                }
+            }
+            // Attribute name and text tables, feature count, layer id
+            tag if tag.starts_with('@') || tag.starts_with('&') || tag == "F" || tag.contains('=') => {}
+            "L" => match (point(1), point(3), symbol(5), fields.get(6).and_then(|code| parse_polarity(code))) {
+                (Some(start), Some(end), Some((ApertureShape::Circle, width, _)), Some(polarity)) => {
+                    features.primitives.push(TaggedPrimitive { net: None, polarity, shape: PrimitiveShape::Segment { start, end, width } });
+                }
+                (Some(_), Some(_), Some(_), Some(_)) => features.skip("line with square or rectangular symbol"),
+                (Some(_), Some(_), None, Some(_)) => features.skip("line with user symbol"),
+                _ => features.skip("malformed line"),
+            },
+            "P" if fields.get(3) == Some(&"-1") => features.skip("resized pad"),
+            "P" => match (point(1), symbol(3), fields.get(4).and_then(|code| parse_polarity(code))) {
+                (Some(center), Some((aperture, width, height)), Some(polarity)) => match pad_quarter_turns(&fields[6.min(fields.len())..]) {
+                    Some(turns) => {
+                        let (width, height) = if turns % 2 == 0 { (width, height) } else { (height, width) };
+                        features.primitives.push(TaggedPrimitive { net: None, polarity, shape: PrimitiveShape::Flash { center, width, height, aperture, outline: None } });
+                    }
+                    None if aperture == ApertureShape::Circle => {
+                        features.primitives.push(TaggedPrimitive { net: None, polarity, shape: PrimitiveShape::Flash { center, width, height, aperture, outline: None } });
+                    }
+                    None => features.skip("pad at an angle"),
+                },
+                (Some(_), None, Some(_)) => features.skip("pad with user symbol"),
+                _ => features.skip("malformed pad"),
+            },
+            "S" => match fields.get(1).and_then(|code| parse_polarity(code)) {
+                Some(polarity) => surface = Some(SurfaceBuilder { polarity, contours: Vec::new(), open: None }),
+                None => features.skip("malformed surface"),
+            },
+            "A" => features.skip("arc"),
+            "T" => features.skip("text"),
+            "B" => features.skip("barcode"),
+            other => features.skip(&format!("record {}", other)),
+        }
+    }
+    if surface.is_some() {
+        features.skip("unterminated surface");
+    }
+    features
+}
+
+/// What happened to one matrix row
+#[derive(Debug, Clone)]
+pub struct OdbLayerReport {
+    pub name: String,
+    /// Layer it was loaded as; None for rows that were not loaded
+    pub layer_type: Option<LayerType>,
+    pub primitives: usize,
+    pub drill_holes: usize,
+    pub unsupported: BTreeMap<String, usize>,
+    /// Why the row was not loaded
+    pub note: Option<String>,
+}
+
+/// Result of importing an ODB++ job
+#[derive(Debug, Clone)]
+pub struct OdbImportResult {
+    pub step: String,
+    pub layers: Vec<OdbLayerReport>,
+}
+
+impl OdbImportResult {
+    pub fn loaded_layers(&self) -> usize {
+        self.layers.iter().filter(|layer| layer.layer_type.is_some()).count()
+    }
+}
+
+/// Step directory to read: `pcb` when present, otherwise the first by name
+fn find_step(job_dir: &Path) -> Result<(String, PathBuf), String> {
+    let steps_dir = job_dir.join("steps");
+    let entries = std::fs::read_dir(&steps_dir).map_err(|e| format!("Failed to read {}: {}", steps_dir.display(), e))?;
+    let mut steps: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    steps.sort();
+    let step = steps.iter()
+        .find(|step| step.eq_ignore_ascii_case(PREFERRED_STEP))
+        .or(steps.first())
+        .cloned()
+        .ok_or_else(|| format!("{} has no steps", job_dir.display()))?;
+    Ok((step.clone(), steps_dir.join(step)))
+}
+
+/// Replace the layers in `world` with the artwork and drills of the ODB++ job in `job_dir`
+pub fn import(world: &mut World, job_dir: &Path) -> Result<OdbImportResult, String> {
+    let matrix_path = job_dir.join("matrix").join("matrix");
+    let matrix = std::fs::read_to_string(&matrix_path)
+        .map_err(|e| format!("Not an ODB++ job: failed to read {}: {}", matrix_path.display(), e))?;
+    let rows = read_matrix(&matrix);
+    if rows.is_empty() {
+        return Err(format!("{} lists no layers", matrix_path.display()));
+    }
+    let (step, step_dir) = find_step(job_dir)?;
+
+    crate::ecs::clear_all_layers_system(world);
+    let identity = GerberTransform::from_matrix(&Matrix3::identity());
+    let mut layers = Vec::new();
+
+    for (row, layer_type) in rows.iter().zip(assign_layer_types(&rows)) {
+        let mut report = OdbLayerReport {
+            name: row.name.clone(),
+            layer_type: None,
+            primitives: 0,
+            drill_holes: 0,
+            unsupported: BTreeMap::new(),
+            note: None,
+        };
+        let is_drill = row.context == "BOARD" && row.layer_type == "DRILL";
+        if layer_type.is_none() && !is_drill {
+            report.note = Some(format!("{} {} layer not imported", row.context.to_lowercase(), row.layer_type.to_lowercase()));
+            layers.push(report);
+            continue;
+        }
+
+        let layer_dir = step_dir.join("layers").join(&row.name);
+        let features_path = layer_dir.join("features");
+        let content = match std::fs::read_to_string(&features_path) {
+            Ok(content) => content,
+            // ODB++ ships compressed files as `features.Z`
+            Err(_) if ["features.Z", "features.z"].iter().any(|name| layer_dir.join(name).exists()) => {
+                report.note = Some("compressed features file not supported".to_string());
+                layers.push(report);
+                continue;
+            }
+            Err(e) => {
+                report.note = Some(format!("failed to read {}: {}", features_path.display(), e));
+                layers.push(report);
+                continue;
+            }
+        };
+        let features = read_features(&content);
+        report.unsupported = features.unsupported;
+
+        if is_drill {
+            let plated = !row.name.contains("npth");
+            let holes: Vec<DrillHole> = features.primitives.iter()
+                .filter_map(|primitive| match primitive.shape {
+                    PrimitiveShape::Flash { center, width, .. } => Some(DrillHole { position: center, diameter: width, plated }),
+                    _ => None,
+                })
+                .collect();
+            report.drill_holes = holes.len();
+            if let Some(mut drill_data) = world.get_resource_mut::<crate::ecs::DrillData>() {
+                drill_data.holes.extend(holes);
+                drill_data.filenames.push(row.name.clone());
+            }
+            layers.push(report);
+            continue;
+        }
+
+        let Some(layer_type) = layer_type else { continue };
+        if layers.iter().any(|layer: &OdbLayerReport| layer.layer_type == Some(layer_type)) {
+            report.note = Some(format!("{} is already loaded from another layer", layer_type.display_name()));
+            layers.push(report);
+            continue;
+        }
+
+        let (_, file_function) = crate::export::gerber::layer_file_suffix(layer_type);
+        let gerber = crate::export::gerber::write_transformed_gerber(&features.primitives, &identity, Some(&file_function));
+        let gerber_content = if row.negative {
+            gerber.content.replacen("%FSLAX", "%TF.FilePolarity,Negative*%\n%FSLAX", 1)
+        } else {
+            gerber.content
+        };
+        let Some(gerber_layer) = crate::ecs::parse_gerber(&gerber_content).0 else {
+            report.note = Some("converted artwork could not be parsed".to_string());
+            layers.push(report);
+            continue;
+        };
+        report.primitives = features.primitives.len();
+        report.layer_type = Some(layer_type);
+        crate::ecs::create_gerber_layer_entity_with_primitives(
+            world,
+            layer_type,
+            gerber_layer,
+            Some(gerber_content),
+            Some(PathBuf::from(&row.name)),
+            true,
+            Some(features.primitives),
+        );
+        crate::ecs::add_layer_assignment(world, row.name.clone(), layer_type);
+        layers.push(report);
+    }
+
+    Ok(OdbImportResult { step, layers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/odb_job")
+    }
+
+    #[test]
+    fn test_odb_fixture_imports_lines_pads_and_surfaces() {
+        let matrix = std::fs::read_to_string(fixture().join("matrix/matrix")).unwrap();
+        let rows = read_matrix(&matrix);
+        let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, ["soldermask_top", "top", "drill", "bottom", "notes"]);
+        assert_eq!(
+            assign_layer_types(&rows),
+            vec![Some(LayerType::Soldermask(Side::Top)), Some(LayerType::Copper(1)), None, Some(LayerType::Copper(2)), None]
+        );
+
+        let top = std::fs::read_to_string(fixture().join("steps/pcb/layers/top/features")).unwrap();
+        let features = read_features(&top);
+        let kinds: Vec<&str> = features.primitives.iter()
+            .map(|primitive| match primitive.shape {
+                PrimitiveShape::Segment { .. } => "line",
+                PrimitiveShape::Flash { .. } => "pad",
+                PrimitiveShape::Region { .. } => "surface",
+            })
+            .collect();
+        assert_eq!(kinds, ["line", "line", "pad", "pad", "surface"]);
+        let PrimitiveShape::Segment { width, .. } = features.primitives[0].shape else { unreachable!() };
+        assert!((width - 0.25).abs() < 1e-9);
+        let PrimitiveShape::Flash { width, aperture, .. } = features.primitives[2].shape else { unreachable!() };
+        assert!((width - 1.6).abs() < 1e-9 && aperture == ApertureShape::Circle);
+        // The hole is cut into the pour instead of clearing whatever lies under it
+        let PrimitiveShape::Region { points } = &features.primitives[4].shape else { unreachable!() };
+        assert_eq!(features.primitives[4].polarity, Polarity::Dark);
+        assert!(inside(points, Point2::new(21.0, 9.0)) && inside(points, Point2::new(26.0, 16.0)));
+        assert!(!inside(points, Point2::new(24.0, 9.0)) && !inside(points, Point2::new(24.5, 9.5)));
+        assert_eq!(features.unsupported.get("arc"), Some(&1));
+        assert_eq!(features.unsupported.get("text"), Some(&1));
+
+        let mut world = crate::ecs::setup_ecs_world();
+        let result = import(&mut world, &fixture()).unwrap();
+        assert_eq!(result.step, "pcb");
+        assert_eq!(result.loaded_layers(), 3);
+        assert_eq!(crate::ecs::get_layer_primitives(&mut world, LayerType::Copper(1)).len(), 5);
+        assert_eq!(crate::ecs::get_drill_holes(&world).len(), 2);
+
+        // The bottom row is a negative plane
+        assert!(rows[3].negative && !rows[1].negative);
+        assert!(crate::ecs::get_layer_polarity(&mut world, LayerType::Copper(2)).is_some_and(|polarity| polarity.negative));
+        assert!(!crate::ecs::get_layer_polarity(&mut world, LayerType::Copper(1)).is_some_and(|polarity| polarity.negative));
+    }
+
+    /// Even-odd point in polygon
+    fn inside(points: &[Point2<f64>], point: Point2<f64>) -> bool {
+        let n = points.len();
+        (0..n).filter(|&i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            (a.y > point.y) != (b.y > point.y) && point.x < a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y)
+        }).count() % 2 == 1
+    }
+
+    #[test]
+    fn test_holes_are_cut_into_their_island() {
+        let square = |x: f64, y: f64, size: f64| vec![Point2::new(x, y), Point2::new(x + size, y), Point2::new(x + size, y + size), Point2::new(x, y + size)];
+        let mut surface = SurfaceBuilder { polarity: Polarity::Dark, contours: Vec::new(), open: None };
+        surface.contours.push((square(0.0, 0.0, 10.0), false));
+        surface.contours.push((square(2.0, 2.0, 2.0), true));
+        surface.contours.push((square(6.0, 6.0, 2.0), true));
+        // A hole before any island and one outside its island are counted
+        surface.contours.insert(0, (square(0.0, 0.0, 1.0), true));
+        surface.contours.push((square(20.0, 0.0, 1.0), true));
+        let mut features = LayerFeatures::default();
+        surface.finish(&mut features);
+
+        assert_eq!(features.primitives.len(), 1);
+        let PrimitiveShape::Region { points } = &features.primitives[0].shape else { unreachable!() };
+        assert!(inside(points, Point2::new(1.0, 1.0)) && inside(points, Point2::new(5.0, 5.0)) && inside(points, Point2::new(9.0, 3.0)));
+        assert!(!inside(points, Point2::new(3.0, 3.0)) && !inside(points, Point2::new(7.0, 7.0)));
+        assert_eq!(features.unsupported.get("surface hole without island"), Some(&1));
+        assert_eq!(features.unsupported.get("surface hole outside its island"), Some(&1));
+    }
+}
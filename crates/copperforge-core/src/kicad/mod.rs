@@ -0,0 +1,5 @@
+//! Reading KiCad design files that sit next to the gerbers
+
+pub mod netlist;
+
+pub use netlist::{Net, NetlistComparison, PadRef, compare_with_copper, parse_net};
@@ -0,0 +1,213 @@
+//! KiCad netlist (`.net`) import
+//!
+//! The netlist is an S-expression export of the schematic. Only the `nets`
+//! section is read: each net's name and the component pins on it. KiCad 6+
+//! quotes every value, KiCad 5 leaves simple values bare; both parse.
+//!
+//! The nets are compared by name with the Gerber X2 net attributes of the
+//! loaded copper. Full connectivity (unrouted nets, shorts) needs a copper
+//! graph and is not done here.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use bevy_ecs::world::World;
+
+/// One component pin on a net
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PadRef {
+    /// Component reference designator (R1, U3)
+    pub reference: String,
+    /// Pin number or name on the footprint
+    pub pin: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Net {
+    pub name: String,
+    pub pads: Vec<PadRef>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>),
+}
+
+impl SExpr {
+    fn head(&self) -> Option<&str> {
+        match self {
+            SExpr::List(items) => match items.first() {
+                Some(SExpr::Atom(head)) => Some(head),
+                _ => None,
+            },
+            SExpr::Atom(_) => None,
+        }
+    }
+
+    /// Children of a list, after its head
+    fn children(&self) -> &[SExpr] {
+        match self {
+            SExpr::List(items) if !items.is_empty() => &items[1..],
+            _ => &[],
+        }
+    }
+
+    /// Value of a `(key value)` child
+    fn value(&self, key: &str) -> Option<&str> {
+        self.children().iter().find(|child| child.head() == Some(key)).and_then(|child| match child.children().first() {
+            Some(SExpr::Atom(value)) => Some(value.as_str()),
+            _ => None,
+        })
+    }
+}
+
+fn parse_sexpr(content: &str) -> Result<SExpr, String> {
+    let mut stack: Vec<Vec<SExpr>> = Vec::new();
+    let mut root = None;
+    let mut chars = content.chars().peekable();
+
+    let mut push = |stack: &mut Vec<Vec<SExpr>>, expr: SExpr| -> Result<(), String> {
+        match stack.last_mut() {
+            Some(list) => list.push(expr),
+            None if root.is_none() => root = Some(expr),
+            None => return Err("Unexpected data after the netlist".to_string()),
+        }
+        Ok(())
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => stack.push(Vec::new()),
+            ')' => {
+                let list = stack.pop().ok_or("Unbalanced ')' in netlist")?;
+                push(&mut stack, SExpr::List(list))?;
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => text.extend(chars.next()),
+                        Some(c) => text.push(c),
+                        None => return Err("Unterminated string in netlist".to_string()),
+                    }
+                }
+                push(&mut stack, SExpr::Atom(text))?;
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut text = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || next == '(' || next == ')' {
+                        break;
+                    }
+                    text.push(next);
+                    chars.next();
+                }
+                push(&mut stack, SExpr::Atom(text))?;
+            }
+        }
+    }
+    if !stack.is_empty() {
+        return Err("Netlist ends inside an open '('".to_string());
+    }
+    root.ok_or_else(|| "Netlist is empty".to_string())
+}
+
+/// Nets of netlist source text
+pub fn parse_net_str(content: &str) -> Result<Vec<Net>, String> {
+    let root = parse_sexpr(content)?;
+    if root.head() != Some("export") {
+        return Err("Not a KiCad netlist (expected an (export ...) file)".to_string());
+    }
+    let nets = root.children().iter()
+        .find(|child| child.head() == Some("nets"))
+        .ok_or("Netlist has no (nets ...) section")?;
+
+    nets.children().iter()
+        .filter(|net| net.head() == Some("net"))
+        .map(|net| {
+            let name = net.value("name").ok_or("Net without a name")?.to_string();
+            let pads = net.children().iter()
+                .filter(|node| node.head() == Some("node"))
+                .map(|node| Ok(PadRef {
+                    reference: node.value("ref").ok_or_else(|| format!("Pad on {} without a reference", name))?.to_string(),
+                    pin: node.value("pin").ok_or_else(|| format!("Pad on {} without a pin", name))?.to_string(),
+                }))
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(Net { name, pads })
+        })
+        .collect()
+}
+
+/// Read the nets of a KiCad `.net` file
+pub fn parse_net(path: &Path) -> Result<Vec<Net>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_net_str(&content)
+}
+
+/// Netlist nets against the net names found on the loaded copper
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetlistComparison {
+    /// Netlist nets (two or more pads) with no copper carrying their name
+    pub missing_in_copper: Vec<String>,
+    /// Copper net names the netlist does not know
+    pub unknown_in_netlist: Vec<String>,
+    /// Netlist nets found on the copper
+    pub matched: usize,
+}
+
+/// Compare netlist nets by name with the Gerber X2 net attributes of the loaded layers
+/// Gerbers without net attributes match nothing; check `get_net_list` first
+pub fn compare_with_copper(nets: &[Net], world: &World) -> NetlistComparison {
+    let copper: BTreeSet<String> = crate::ecs::get_net_list(world).into_iter()
+        .map(|(_, name)| name)
+        .filter(|name| !name.is_empty() && name != "N/C")
+        .collect();
+    let netlist: BTreeSet<&str> = nets.iter().map(|net| net.name.as_str()).collect();
+
+    let mut comparison = NetlistComparison {
+        unknown_in_netlist: copper.iter().filter(|name| !netlist.contains(name.as_str())).cloned().collect(),
+        ..Default::default()
+    };
+    for net in nets {
+        if copper.contains(&net.name) {
+            comparison.matched += 1;
+        } else if net.pads.len() >= 2 {
+            comparison.missing_in_copper.push(net.name.clone());
+        }
+    }
+    comparison
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quoted_and_bare_netlists() {
+        let kicad8 = r#"(export (version "E")
+  (design (source "board.kicad_sch") (tool "Eeschema 8.0.1"))
+  (components (comp (ref "R1") (value "10k")))
+  (nets
+    (net (code "1") (name "GND") (class "Default")
+      (node (ref "C1") (pin "2") (pintype "passive"))
+      (node (ref "R1") (pin "1") (pintype "passive")))
+    (net (code "2") (name "/SDA \"bus\"") (class "Default")
+      (node (ref "U1") (pin "5") (pinfunction "SDA") (pintype "bidirectional")))))"#;
+        let nets = parse_net_str(kicad8).expect("KiCad 8 netlist should parse");
+        assert_eq!(nets.len(), 2);
+        assert_eq!(nets[0].name, "GND");
+        assert_eq!(nets[0].pads[1], PadRef { reference: "R1".to_string(), pin: "1".to_string() });
+        assert_eq!(nets[1].name, "/SDA \"bus\"");
+
+        let kicad5 = "(export (version D)\n  (nets\n    (net (code 1) (name VCC)\n      (node (ref U1) (pin 14)))))";
+        let nets = parse_net_str(kicad5).expect("KiCad 5 netlist should parse");
+        assert_eq!(nets, vec![Net { name: "VCC".to_string(), pads: vec![PadRef { reference: "U1".to_string(), pin: "14".to_string() }] }]);
+
+        assert!(parse_net_str("(export (nets (net (name A))").is_err());
+        assert!(parse_net_str("(kicad_pcb)").is_err());
+    }
+}
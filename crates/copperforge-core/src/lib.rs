@@ -1,18 +1,29 @@
 // KiForge Core Library
 // Re-export all modules for external use
 
+pub mod api;
 pub mod display;
 pub mod drc_operations;
 pub mod ecs;
 pub mod export;
+pub mod import;
+pub mod kicad;
 // layer_operations module removed - all functionality moved to ECS
 pub mod navigation;
+pub mod project_manager;
+#[cfg(test)]
+mod test_support;
+
+// Desktop application, only with the default "gui" feature
+#[cfg(feature = "gui")]
 pub mod platform;
+#[cfg(feature = "gui")]
 pub mod project;
-pub mod project_manager;
+#[cfg(feature = "gui")]
 pub mod ui;
+#[cfg(feature = "gui")]
 pub mod app;
 
 // Re-export DemoLensApp from app module
+#[cfg(feature = "gui")]
 pub use app::DemoLensApp;
-
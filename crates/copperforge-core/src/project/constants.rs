@@ -12,4 +12,6 @@ pub const LOG_TYPE_CENTER_OFFSET: &str = "center_offset";
 pub const LOG_TYPE_DESIGN_OFFSET: &str = "design_offset";
 pub const LOG_TYPE_MIRROR: &str = "mirror";
 pub const LOG_TYPE_DRC: &str = "drc";
-pub const LOG_TYPE_GRID: &str = "grid";
\ No newline at end of file
+pub const LOG_TYPE_GRID: &str = "grid";
+pub const LOG_TYPE_AUTOSAVE: &str = "autosave";
+pub const LOG_TYPE_PARSER: &str = "parser";
\ No newline at end of file
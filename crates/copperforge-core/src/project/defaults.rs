@@ -1,61 +1,48 @@
 use std::io::BufReader;
+use bevy_ecs::world::World;
 use gerber_viewer::gerber_parser::parse;
 use gerber_viewer::GerberLayer;
-// LayerType and LayerInfo imports removed with LayerManager
-// Removed: LayerManager dependency
-// use crate::layer_operations::LayerManager;
+use crate::ecs::{LayerType, Side};
 
-/// Load default gerber layers from embedded assets (DISABLED - LayerManager removed)
-/*
-pub fn load_default_gerbers() -> LayerManager {
-    let mut layer_manager = LayerManager::new();
-    
-    // Map layer types to their corresponding gerber files
-    let layer_files = [
-        (LayerType::TopCopper, "cmod_s7-F_Cu.gbr"),
-        (LayerType::BottomCopper, "cmod_s7-B_Cu.gbr"),
-        (LayerType::TopSilk, "cmod_s7-F_SilkS.gbr"),
-        (LayerType::BottomSilk, "cmod_s7-B_SilkS.gbr"),
-        (LayerType::TopSoldermask, "cmod_s7-F_Mask.gbr"),
-        (LayerType::BottomSoldermask, "cmod_s7-B_Mask.gbr"),
-        (LayerType::MechanicalOutline, "cmod_s7-Edge_Cuts.gbr"),
-    ];
-    
-    // Load each layer's gerber file
-    for (layer_type, filename) in layer_files {
+/// Layers of the embedded default project (the Cmod S7 board) and their files
+const DEFAULT_LAYER_FILES: [(LayerType, &str); 9] = [
+    (LayerType::TOP_COPPER, "cmod_s7-F_Cu.gbr"),
+    (LayerType::BOTTOM_COPPER, "cmod_s7-B_Cu.gbr"),
+    (LayerType::Silkscreen(Side::Top), "cmod_s7-F_SilkS.gbr"),
+    (LayerType::Silkscreen(Side::Bottom), "cmod_s7-B_SilkS.gbr"),
+    (LayerType::Soldermask(Side::Top), "cmod_s7-F_Mask.gbr"),
+    (LayerType::Soldermask(Side::Bottom), "cmod_s7-B_Mask.gbr"),
+    (LayerType::Paste(Side::Top), "cmod_s7-F_Paste.gbr"),
+    (LayerType::Paste(Side::Bottom), "cmod_s7-B_Paste.gbr"),
+    (LayerType::MechanicalOutline, "cmod_s7-Edge_Cuts.gbr"),
+];
+
+/// Load the embedded default project into the world, replacing the loaded layers
+/// Layers are shown like a freshly loaded top side; returns the number of layers created
+pub fn load_default_gerbers(world: &mut World) -> usize {
+    crate::ecs::clear_all_layers_system(world);
+    let mut loaded = 0;
+    for (layer_type, filename) in DEFAULT_LAYER_FILES {
         let gerber_data = get_embedded_gerber_data(filename);
-        
-        let reader = BufReader::new(gerber_data.as_bytes());
-        let layer_gerber = match parse(reader) {
-            Ok(doc) => {
-                let commands = doc.into_commands();
-                Some(GerberLayer::new(commands))
-            }
-            Err(e) => {
-                eprintln!("Failed to parse {}: {:?}", filename, e);
-                None
-            }
+        let (Some(gerber_layer), _diagnostics) = crate::ecs::parse_gerber(gerber_data) else {
+            log::warn!("Failed to parse embedded {}", filename);
+            continue;
         };
-        
-        let mut layer_info = LayerInfo::new(
+        crate::ecs::create_gerber_layer_entity(
+            world,
             layer_type,
-            layer_gerber,
-            Some(gerber_data.to_string()),  // Store raw Gerber data for DRC
-            matches!(layer_type, LayerType::TopCopper | LayerType::MechanicalOutline),
+            gerber_layer,
+            Some(gerber_data.to_string()),
+            None,
+            layer_type.should_render(true),
         );
-        
-        // Initialize coordinates from the gerber layer if available
-        layer_info.initialize_coordinates_from_gerber();
-        
-        layer_manager.add_layer(layer_type, layer_info);
+        crate::ecs::add_layer_assignment(world, filename.to_string(), layer_type);
+        loaded += 1;
     }
-    
-    layer_manager
+    loaded
 }
-*/
 
 /// Get embedded gerber data by filename
-#[allow(dead_code)]
 fn get_embedded_gerber_data(filename: &str) -> &'static str {
     match filename {
         "cmod_s7-F_Cu.gbr" => include_str!("../../../../assets/cmod_s7-F_Cu.gbr"),
@@ -64,18 +51,35 @@ fn get_embedded_gerber_data(filename: &str) -> &'static str {
         "cmod_s7-B_SilkS.gbr" => include_str!("../../../../assets/cmod_s7-B_SilkS.gbr"),
         "cmod_s7-F_Mask.gbr" => include_str!("../../../../assets/cmod_s7-F_Mask.gbr"),
         "cmod_s7-B_Mask.gbr" => include_str!("../../../../assets/cmod_s7-B_Mask.gbr"),
+        "cmod_s7-F_Paste.gbr" => include_str!("../../../../assets/cmod_s7-F_Paste.gbr"),
+        "cmod_s7-B_Paste.gbr" => include_str!("../../../../assets/cmod_s7-B_Paste.gbr"),
         "cmod_s7-Edge_Cuts.gbr" => include_str!("../../../../assets/cmod_s7-Edge_Cuts.gbr"),
         _ => include_str!("../../../../assets/demo.gbr"), // Fallback
     }
 }
 
-/// Load the demo gerber for legacy compatibility
+/// Source of the bundled demo gerber
+pub const DEMO_GERBER: &str = include_str!("../../../../assets/demo.gbr");
+
+/// Load the demo gerber; only done when the user asks for the demo board
 pub fn load_demo_gerber() -> GerberLayer {
-    let demo_str = include_str!("../../../../assets/demo.gbr").as_bytes();
-    let reader = BufReader::new(demo_str);
+    let reader = BufReader::new(DEMO_GERBER.as_bytes());
     
     let doc = parse(reader).unwrap();
     
     let commands = doc.into_commands();
     GerberLayer::new(commands)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_project_loads_every_layer() {
+        let mut world = crate::ecs::setup_ecs_world();
+        assert_eq!(load_default_gerbers(&mut world), DEFAULT_LAYER_FILES.len());
+        assert!(crate::ecs::get_layer_by_type(&mut world, LayerType::MechanicalOutline).is_some());
+        assert_eq!(crate::ecs::get_layer_assignments(&world).get("cmod_s7-B_Cu.gbr"), Some(&LayerType::BOTTOM_COPPER));
+    }
+}
@@ -44,7 +44,80 @@ pub struct ProjectConfig {
     pub auto_reload_on_change: bool,
     pub user_timezone: Option<String>,
     pub use_24_hour_clock: bool,
-    pub global_units_mils: bool, // true = mils, false = mm
+    pub global_units_mils: bool, // true = mils, false = mm; kept for older builds
+    /// Display unit; configs from before it existed fall back to `global_units_mils`
+    #[serde(default)]
+    pub display_unit: Option<crate::ecs::DisplayUnit>,
+    #[serde(default)]
+    pub png_export: crate::export::PngExportSettings,
+    #[serde(default)]
+    pub drc_markers: crate::ui::drc_markers::DrcMarkerSettings,
+    /// Seconds between project auto-saves, 0 disables
+    #[serde(default = "default_autosave_interval")]
+    pub autosave_interval_secs: u32,
+    /// Pinch zoom and two-finger pan in the viewer
+    #[serde(default = "default_touch_gestures")]
+    pub touch_gestures: bool,
+    #[serde(default)]
+    pub minimap: crate::ui::minimap::MinimapSettings,
+    #[serde(default)]
+    pub dro: crate::ui::dro::DroSettings,
+    /// Eased rotation and flip transitions in the viewer
+    #[serde(default = "default_view_animations")]
+    pub view_animations: bool,
+    /// DRC violations the user chose to ignore, with reasons
+    #[serde(default)]
+    pub drc_ignores: crate::drc_operations::DrcIgnoreList,
+    /// Reuse extracted primitives of unchanged gerber files
+    #[serde(default = "default_use_parse_cache")]
+    pub use_parse_cache: bool,
+    /// Offer to reload single layers when their gerber file changes on disk
+    #[serde(default)]
+    pub watch_gerber_files: bool,
+    /// Language of the interface text
+    #[serde(default)]
+    pub language: crate::ui::i18n::Language,
+    /// Last tag filter of the project list
+    #[serde(default)]
+    pub project_tag_filter: crate::project_manager::TagFilter,
+    #[serde(default)]
+    pub cost_settings: crate::project_manager::cost::CostSettings,
+    /// Name of the active DRC rule profile
+    #[serde(default)]
+    pub drc_profile: Option<String>,
+    /// Remapped viewer shortcuts; actions not listed keep their default keys
+    #[serde(default)]
+    pub key_bindings: crate::ui::key_bindings::KeyBindings,
+    /// Command run after gerbers are generated
+    #[serde(default)]
+    pub post_gen_hook: crate::project_manager::post_gen_hook::PostGenHook,
+    /// When zoomed-out layers are drawn simplified
+    #[serde(default)]
+    pub lod: crate::ecs::LodSettings,
+    /// Frame time and render cache statistics drawn over the gerber view
+    #[serde(default)]
+    pub render_stats_overlay: bool,
+    /// Panel preview layout
+    #[serde(default)]
+    pub panel: crate::ecs::PanelSettings,
+}
+
+pub const DEFAULT_AUTOSAVE_INTERVAL_SECS: u32 = 120;
+
+fn default_autosave_interval() -> u32 {
+    DEFAULT_AUTOSAVE_INTERVAL_SECS
+}
+
+fn default_touch_gestures() -> bool {
+    true
+}
+
+fn default_view_animations() -> bool {
+    true
+}
+
+fn default_use_parse_cache() -> bool {
+    true
 }
 
 impl Default for ProjectConfig {
@@ -56,11 +129,35 @@ impl Default for ProjectConfig {
             user_timezone: None,
             use_24_hour_clock: false, // Default to 12-hour
             global_units_mils: false, // Default to mm
+            display_unit: None,
+            png_export: crate::export::PngExportSettings::default(),
+            drc_markers: crate::ui::drc_markers::DrcMarkerSettings::default(),
+            autosave_interval_secs: DEFAULT_AUTOSAVE_INTERVAL_SECS,
+            touch_gestures: true,
+            minimap: crate::ui::minimap::MinimapSettings::default(),
+            dro: crate::ui::dro::DroSettings::default(),
+            view_animations: true,
+            drc_ignores: crate::drc_operations::DrcIgnoreList::default(),
+            use_parse_cache: true,
+            watch_gerber_files: false,
+            language: crate::ui::i18n::Language::default(),
+            project_tag_filter: crate::project_manager::TagFilter::default(),
+            cost_settings: crate::project_manager::cost::CostSettings::default(),
+            drc_profile: None,
+            key_bindings: crate::ui::key_bindings::KeyBindings::default(),
+            post_gen_hook: crate::project_manager::post_gen_hook::PostGenHook::default(),
+            lod: crate::ecs::LodSettings::default(),
+            render_stats_overlay: false,
+            panel: crate::ecs::PanelSettings::default(),
         }
     }
 }
 
 impl ProjectConfig {
+    pub fn display_unit(&self) -> crate::ecs::DisplayUnit {
+        self.display_unit.unwrap_or(crate::ecs::DisplayUnit::from_legacy_mils(self.global_units_mils))
+    }
+    
     pub fn save_to_file(&self, path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(self)?;
         std::fs::create_dir_all(path.parent().unwrap())?;
@@ -1,7 +1,9 @@
 pub mod manager;
 pub mod constants;
 pub mod defaults;
+pub mod settings_bundle;
+pub mod workspace;
 
 // Re-export the main types for easy access
 pub use manager::{ProjectManager, ProjectState};
-pub use defaults::load_demo_gerber; // load_default_gerbers removed with LayerManager
\ No newline at end of file
+pub use defaults::{load_default_gerbers, load_demo_gerber, DEMO_GERBER};
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! User preferences bundled into one portable JSON file
+//!
+//! Each preference group is a named section carrying its own version, so a
+//! file written by a newer release can still be imported: sections this build
+//! understands are applied, the others are skipped and reported. Sections are
+//! independent; one that fails to parse does not stop the rest.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::drc_operations::profiles::DrcRuleProfile;
+use crate::drc_operations::types::DrcRules;
+use crate::ecs::DisplayUnit;
+
+/// Value of the `format` field, so other JSON files are rejected up front
+pub const SETTINGS_FORMAT: &str = "copperforge-settings";
+
+pub const TIME_SECTION: &str = "time";
+pub const UNITS_SECTION: &str = "units";
+pub const GRID_SECTION: &str = "grid";
+pub const DRC_PROFILES_SECTION: &str = "drc_profiles";
+pub const COLOR_SCHEME_SECTION: &str = "color_scheme";
+pub const KEY_BINDINGS_SECTION: &str = "key_bindings";
+
+/// Newest section version this build reads and the one it writes
+pub const SECTION_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSettings {
+    pub timezone: Option<String>,
+    pub use_24_hour_clock: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnitsSettings {
+    /// Kept for builds that only know mm and mils
+    pub mils: bool,
+    #[serde(default)]
+    pub unit: Option<DisplayUnit>,
+}
+
+impl UnitsSettings {
+    pub fn new(unit: DisplayUnit) -> Self {
+        Self { mils: unit.is_imperial(), unit: Some(unit) }
+    }
+
+    pub fn display_unit(&self) -> DisplayUnit {
+        self.unit.unwrap_or(DisplayUnit::from_legacy_mils(self.mils))
+    }
+}
+
+/// User rule profiles plus the rules in use
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcProfileSettings {
+    /// Profiles from the profiles directory; built-in ones are not exported
+    pub profiles: Vec<DrcRuleProfile>,
+    /// Name of the active profile, if any
+    #[serde(default)]
+    pub active: Option<String>,
+    pub rules: DrcRules,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsSection {
+    pub version: u32,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format: String,
+    /// Release that wrote the file, for the import report
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub exported: String,
+    pub sections: BTreeMap<String, SettingsSection>,
+}
+
+impl Default for SettingsBundle {
+    fn default() -> Self {
+        Self {
+            format: SETTINGS_FORMAT.to_string(),
+            app_version: crate::platform::parameters::gui::VERSION.to_string(),
+            exported: chrono::Local::now().to_rfc3339(),
+            sections: BTreeMap::new(),
+        }
+    }
+}
+
+/// Sections applied and skipped by an import
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub applied: Vec<String>,
+    /// Section name and why it was skipped
+    pub skipped: Vec<(String, String)>,
+}
+
+impl ImportReport {
+    pub fn skip(&mut self, section: &str, reason: impl Into<String>) {
+        self.skipped.push((section.to_string(), reason.into()));
+    }
+}
+
+impl SettingsBundle {
+    pub fn insert<T: Serialize>(&mut self, name: &str, value: &T) -> Result<(), String> {
+        let data = serde_json::to_value(value).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        self.sections.insert(name.to_string(), SettingsSection { version: SECTION_VERSION, data });
+        Ok(())
+    }
+
+    /// Parse a section, or record in `report` why it cannot be applied
+    /// Missing sections are neither applied nor skipped
+    pub fn section<T: DeserializeOwned>(&self, name: &str, report: &mut ImportReport) -> Option<T> {
+        let section = self.sections.get(name)?;
+        if section.version > SECTION_VERSION {
+            report.skip(name, format!("version {} is newer than the supported version {}", section.version, SECTION_VERSION));
+            return None;
+        }
+        match serde_json::from_value(section.data.clone()) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                report.skip(name, format!("invalid data: {}", e));
+                None
+            }
+        }
+    }
+
+    /// Sections in the file that this build does not know about
+    pub fn unknown_sections(&self) -> Vec<String> {
+        const KNOWN: [&str; 6] = [TIME_SECTION, UNITS_SECTION, GRID_SECTION, DRC_PROFILES_SECTION, COLOR_SCHEME_SECTION, KEY_BINDINGS_SECTION];
+        self.sections.keys().filter(|name| !KNOWN.contains(&name.as_str())).cloned().collect()
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let bundle: SettingsBundle = serde_json::from_str(&json).map_err(|e| format!("{} is not a settings file: {}", path.display(), e))?;
+        if bundle.format != SETTINGS_FORMAT {
+            return Err(format!("{} is not a settings file (format \"{}\")", path.display(), bundle.format));
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_round_trip_and_newer_versions_are_skipped() {
+        let mut bundle = SettingsBundle::default();
+        let time = TimeSettings { timezone: Some("Europe/Berlin".to_string()), use_24_hour_clock: false };
+        bundle.insert(TIME_SECTION, &time).unwrap();
+        bundle.insert(UNITS_SECTION, &UnitsSettings::new(DisplayUnit::Inches)).unwrap();
+        bundle.sections.get_mut(UNITS_SECTION).unwrap().version = SECTION_VERSION + 1;
+        bundle.sections.insert("macros".to_string(), SettingsSection { version: 1, data: serde_json::Value::Null });
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let loaded: SettingsBundle = serde_json::from_str(&json).unwrap();
+        let mut report = ImportReport::default();
+        assert_eq!(loaded.section::<TimeSettings>(TIME_SECTION, &mut report), Some(time));
+        assert_eq!(loaded.section::<UnitsSettings>(UNITS_SECTION, &mut report), None);
+        assert_eq!(loaded.section::<UnitsSettings>(GRID_SECTION, &mut report), None);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].1.contains("newer"));
+        assert_eq!(loaded.unknown_sections(), vec!["macros".to_string()]);
+
+        // A section with the wrong shape is skipped, not fatal
+        let mut report = ImportReport::default();
+        assert_eq!(loaded.section::<UnitsSettings>(TIME_SECTION, &mut report), None);
+        assert!(report.skipped[0].1.starts_with("invalid data"));
+    }
+}
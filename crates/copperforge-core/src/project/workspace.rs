@@ -0,0 +1,126 @@
+//! Shared viewer settings stored beside the PCB
+//!
+//! `.copperforge.json` next to the `.kicad_pcb` holds the settings a team wants
+//! under version control: layer colors, the DRC profile and rules, the grid and
+//! the design origin. Personal preferences (timezone, clock, units, window
+//! layout) are not part of the file. Every section is optional, so a
+//! hand-trimmed file only overrides what it lists; on load the file wins over
+//! the personal values, and saving rewrites it as a whole.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::display::{ColorScheme, GridSettings, VectorOffset};
+use crate::drc_operations::types::DrcRules;
+
+pub const WORKSPACE_FILE_NAME: &str = ".copperforge.json";
+
+/// Value of the `format` field, so other JSON files are rejected up front
+pub const WORKSPACE_FORMAT: &str = "copperforge-workspace";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceSettings {
+    pub format: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer_colors: Option<ColorScheme>,
+    /// Name of the DRC rule profile the rules came from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drc_profile: Option<String>,
+    /// Written without `display_unit`, which stays a personal preference
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "serialize_shared_rules")]
+    pub drc_rules: Option<DrcRules>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid: Option<GridSettings>,
+    /// Design origin in gerber coordinates (mm); absent while the origin is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<VectorOffset>,
+}
+
+impl Default for WorkspaceSettings {
+    fn default() -> Self {
+        Self {
+            format: WORKSPACE_FORMAT.to_string(),
+            layer_colors: None,
+            drc_profile: None,
+            drc_rules: None,
+            grid: None,
+            origin: None,
+        }
+    }
+}
+
+/// DRC rules minus the personal `display_unit`; loading falls back to the rules' default unit
+fn serialize_shared_rules<S: serde::Serializer>(rules: &Option<DrcRules>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut value = serde_json::to_value(rules).map_err(serde::ser::Error::custom)?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("display_unit");
+    }
+    value.serialize(serializer)
+}
+
+/// Workspace file of the PCB at `pcb_path`
+pub fn workspace_path(pcb_path: &Path) -> PathBuf {
+    pcb_path.parent().unwrap_or(Path::new(".")).join(WORKSPACE_FILE_NAME)
+}
+
+impl WorkspaceSettings {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let settings: WorkspaceSettings = serde_json::from_str(json).map_err(|e| format!("not a workspace file: {}", e))?;
+        if settings.format != WORKSPACE_FORMAT {
+            return Err(format!("not a workspace file (format \"{}\")", settings.format));
+        }
+        Ok(settings)
+    }
+
+    /// Settings beside the PCB, or `None` when the project has no workspace file
+    pub fn load_for_pcb(pcb_path: &Path) -> Result<Option<Self>, String> {
+        let path = workspace_path(pcb_path);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json).map(Some).map_err(|e| format!("{} is {}", path.display(), e))
+    }
+
+    /// Write the settings beside the PCB, replacing an existing file, and return its path
+    pub fn save_for_pcb(&self, pcb_path: &Path) -> Result<PathBuf, String> {
+        let path = workspace_path(pcb_path);
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize workspace settings: {}", e))?;
+        std::fs::write(&path, json + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_round_trips_and_partial_files_load() {
+        let settings = WorkspaceSettings {
+            drc_profile: Some("JLC 1oz standard".to_string()),
+            drc_rules: Some(DrcRules::default()),
+            grid: Some(GridSettings::default()),
+            origin: Some(VectorOffset { x: 12.5, y: -3.0 }),
+            ..WorkspaceSettings::default()
+        };
+        let json = serde_json::to_string_pretty(&settings).unwrap();
+        assert_eq!(WorkspaceSettings::from_json(&json).unwrap().drc_profile, settings.drc_profile);
+        // Only shared sections are written
+        let mut keys: Vec<String> = serde_json::from_str::<serde_json::Value>(&json).unwrap()
+            .as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, ["drc_profile", "drc_rules", "format", "grid", "origin"]);
+        // The unit the rules are shown in is personal
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["drc_rules"].get("display_unit").is_none());
+        assert!(value["drc_rules"].get("min_trace_width").is_some());
+
+        let partial = WorkspaceSettings::from_json(r#"{"format": "copperforge-workspace", "drc_profile": "Proto"}"#).unwrap();
+        assert_eq!(partial.drc_profile.as_deref(), Some("Proto"));
+        assert!(partial.grid.is_none() && partial.origin.is_none());
+        assert!(WorkspaceSettings::from_json(r#"{"format": "copperforge-settings"}"#).is_err());
+        assert_eq!(workspace_path(Path::new("boards/demo/demo.kicad_pcb")), Path::new("boards/demo/.copperforge.json"));
+    }
+}
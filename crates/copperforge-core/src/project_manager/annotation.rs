@@ -0,0 +1,133 @@
+//! Review annotations: text notes, arrows and highlight rectangles on the board
+//!
+//! Positions are source gerber coordinates (mm), like picked points, so the
+//! markup stays on its feature through rotation, mirroring and flips. The list
+//! is stored per project next to the picked points and can be drawn into the
+//! PNG and SVG exports.
+
+use nalgebra::{Matrix3, Point2};
+use serde::{Deserialize, Serialize};
+
+/// Angle between the arrow shaft and each side of its head
+const ARROW_HEAD_ANGLE: f64 = std::f64::consts::PI / 7.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnnotationKind {
+    Note,
+    /// From the start point to the end point, head at the end
+    Arrow,
+    /// Highlight with the start and end points as opposite corners
+    Rectangle,
+}
+
+impl AnnotationKind {
+    pub const ALL: [AnnotationKind; 3] = [AnnotationKind::Note, AnnotationKind::Arrow, AnnotationKind::Rectangle];
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            AnnotationKind::Note => "📝",
+            AnnotationKind::Arrow => "➡",
+            AnnotationKind::Rectangle => "▭",
+        }
+    }
+
+    /// Placed with a second click for the end point or opposite corner
+    pub fn needs_end(&self) -> bool {
+        !matches!(self, AnnotationKind::Note)
+    }
+}
+
+/// A review note placed on the board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    /// Note position, arrow tail or first rectangle corner
+    pub x: f64,
+    pub y: f64,
+    /// Arrow head or opposite rectangle corner; the note position for notes
+    pub end_x: f64,
+    pub end_y: f64,
+    pub text: String,
+    /// sRGBA, unmultiplied
+    pub color: [u8; 4],
+    pub resolved: bool,
+}
+
+impl Annotation {
+    pub fn new(kind: AnnotationKind, start: Point2<f64>, end: Point2<f64>, color: [u8; 4]) -> Self {
+        Self { kind, x: start.x, y: start.y, end_x: end.x, end_y: end.y, text: String::new(), color, resolved: false }
+    }
+
+    pub fn start(&self) -> Point2<f64> {
+        Point2::new(self.x, self.y)
+    }
+
+    pub fn end(&self) -> Point2<f64> {
+        Point2::new(self.end_x, self.end_y)
+    }
+
+    /// Corners of a rectangle, or the start and end point of other kinds, mapped by `matrix`
+    /// Rectangle corners go around the outline, so they stay a closed shape under rotation
+    pub fn outline(&self, matrix: &Matrix3<f64>) -> Vec<Point2<f64>> {
+        let (start, end) = (self.start(), self.end());
+        let points = match self.kind {
+            AnnotationKind::Note => vec![start],
+            AnnotationKind::Arrow => vec![start, end],
+            AnnotationKind::Rectangle => vec![start, Point2::new(end.x, start.y), end, Point2::new(start.x, end.y)],
+        };
+        points.iter().map(|point| matrix.transform_point(point)).collect()
+    }
+
+    /// Bounds of the outline in the coordinates `matrix` maps to
+    pub fn bounds(&self, matrix: &Matrix3<f64>) -> (Point2<f64>, Point2<f64>) {
+        let points = self.outline(matrix);
+        let min = points.iter().fold(Point2::new(f64::MAX, f64::MAX), |min, p| Point2::new(min.x.min(p.x), min.y.min(p.y)));
+        let max = points.iter().fold(Point2::new(f64::MIN, f64::MIN), |max, p| Point2::new(max.x.max(p.x), max.y.max(p.y)));
+        (min, max)
+    }
+
+    /// First line of the text, for labels drawn on the board
+    pub fn label(&self) -> &str {
+        self.text.lines().next().unwrap_or_default()
+    }
+}
+
+/// Ends of the two head strokes of an arrow pointing from `tail` to `tip`
+/// `length` is in the coordinates of the points; a zero-length arrow has no head
+pub fn arrow_head(tail: Point2<f64>, tip: Point2<f64>, length: f64) -> Option<[Point2<f64>; 2]> {
+    let direction = tail - tip;
+    let shaft = direction.norm();
+    if shaft <= f64::EPSILON {
+        return None;
+    }
+    let back = direction / shaft * length.min(shaft);
+    let side = |angle: f64| {
+        let (sin, cos) = angle.sin_cos();
+        tip + nalgebra::Vector2::new(back.x * cos - back.y * sin, back.x * sin + back.y * cos)
+    };
+    Some([side(ARROW_HEAD_ANGLE), side(-ARROW_HEAD_ANGLE)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_outline_and_arrow_head() {
+        let rectangle = Annotation::new(AnnotationKind::Rectangle, Point2::new(0.0, 0.0), Point2::new(4.0, 2.0), [255, 0, 0, 255]);
+        // A quarter turn keeps the corners in order around the outline
+        let quarter_turn = Matrix3::new(0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        let outline = rectangle.outline(&quarter_turn);
+        assert_eq!(outline.len(), 4);
+        assert!((outline[2] - Point2::new(-2.0, 4.0)).norm() < 1e-9);
+        let (min, max) = rectangle.bounds(&quarter_turn);
+        assert!((min - Point2::new(-2.0, 0.0)).norm() < 1e-9 && (max - Point2::new(0.0, 4.0)).norm() < 1e-9);
+
+        // Head strokes lean back from the tip, one on each side of the shaft
+        let [left, right] = arrow_head(Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), 2.0).unwrap();
+        assert!(left.x < 10.0 && right.x < 10.0);
+        assert!((left.y + right.y).abs() < 1e-9 && left.y.abs() > 0.5);
+        assert!(((left - Point2::new(10.0, 0.0)).norm() - 2.0).abs() < 1e-9);
+        assert_eq!(arrow_head(Point2::new(1.0, 1.0), Point2::new(1.0, 1.0), 2.0), None);
+    }
+}
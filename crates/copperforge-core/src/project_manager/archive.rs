@@ -0,0 +1,270 @@
+/// Portable project archives
+///
+/// A `.cfproj` archive is a zip holding `manifest.json` (metadata, BOM, notes,
+/// picked points and annotations) and a `board/` folder with the PCB file and its
+/// `gerber_output/` folder of gerber and drill files, the same layout gerber
+/// generation leaves next to a PCB.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::ProjectManagerState;
+use super::annotation::Annotation;
+use super::database::{PickedPoint, ProjectData, ProjectDatabaseError, generate_project_id};
+
+pub const ARCHIVE_EXTENSION: &str = "cfproj";
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const BOARD_DIR: &str = "board";
+const GERBER_DIR: &str = "gerber_output";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: ProjectData,
+    #[serde(default)]
+    pub picked_points: Vec<PickedPoint>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// PCB file name inside `board/`, if the file was available when exporting
+    pub pcb_file: Option<String>,
+    /// File names inside `board/gerber_output/`
+    pub gerber_files: Vec<String>,
+}
+
+fn archive_error(context: &str, error: impl std::fmt::Display) -> ProjectDatabaseError {
+    ProjectDatabaseError::Archive(format!("{}: {}", context, error))
+}
+
+/// Gerber output directory for a PCB, as written by gerber generation
+pub(crate) fn gerber_dir_for(pcb_path: &Path) -> PathBuf {
+    pcb_path.parent().unwrap_or(Path::new(".")).join(GERBER_DIR)
+}
+
+/// First of `dir`, `dir-2`, `dir-3`... that does not exist yet
+fn unique_dir(dir: PathBuf) -> PathBuf {
+    if !dir.exists() {
+        return dir;
+    }
+    let name = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    (2..)
+        .map(|n| dir.with_file_name(format!("{}-{}", name, n)))
+        .find(|candidate| !candidate.exists())
+        .expect("some suffix is free")
+}
+
+impl ProjectManagerState {
+    /// Write a project and the files it references to a single zip archive
+    pub fn export_archive(&self, project_id: &str, path: &Path) -> Result<(), ProjectDatabaseError> {
+        let database = self.database.as_ref()
+            .ok_or_else(|| ProjectDatabaseError::DatabaseRead("Database not initialized".to_string()))?;
+        // Prefer the in-memory copy of the open project, it may have unsaved edits
+        let project = match &self.current_project {
+            Some(current) if current.metadata.id == project_id => current.clone(),
+            _ => database.load_project(project_id)?
+                .ok_or_else(|| ProjectDatabaseError::DatabaseRead(format!("Project {} not found", project_id)))?,
+        };
+
+        let pcb_path = project.metadata.pcb_file_path.clone();
+        let pcb_file = pcb_path.is_file()
+            .then(|| pcb_path.file_name().map(|name| name.to_string_lossy().to_string()))
+            .flatten();
+        let mut gerber_files: Vec<PathBuf> = std::fs::read_dir(gerber_dir_for(&pcb_path))
+            .map(|entries| entries.flatten().map(|entry| entry.path()).filter(|path| path.is_file()).collect())
+            .unwrap_or_default();
+        gerber_files.sort();
+
+        let manifest = ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            picked_points: database.load_picked_points(project_id)?,
+            annotations: database.load_annotations(project_id)?,
+            project,
+            pcb_file: pcb_file.clone(),
+            gerber_files: gerber_files.iter()
+                .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+                .collect(),
+        };
+
+        let file = File::create(path).map_err(|e| archive_error("Failed to create archive", e))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        zip.start_file(MANIFEST_NAME, options).map_err(|e| archive_error("Failed to write manifest", e))?;
+        zip.write_all(manifest_json.as_bytes()).map_err(|e| archive_error("Failed to write manifest", e))?;
+
+        let mut files: Vec<(String, PathBuf)> = gerber_files.into_iter()
+            .zip(&manifest.gerber_files)
+            .map(|(source, name)| (format!("{}/{}/{}", BOARD_DIR, GERBER_DIR, name), source))
+            .collect();
+        if let Some(pcb_file) = pcb_file {
+            files.insert(0, (format!("{}/{}", BOARD_DIR, pcb_file), pcb_path));
+        }
+        for (entry_name, source) in files {
+            let contents = std::fs::read(&source)
+                .map_err(|e| archive_error(&format!("Failed to read {}", source.display()), e))?;
+            zip.start_file(entry_name.as_str(), options).map_err(|e| archive_error("Failed to write archive", e))?;
+            zip.write_all(&contents).map_err(|e| archive_error("Failed to write archive", e))?;
+        }
+
+        zip.finish().map_err(|e| archive_error("Failed to finish archive", e))?;
+        Ok(())
+    }
+
+    /// Unpack an archive next to it (into a folder named after it) and register
+    /// the project under a fresh id, with its PCB path pointing at the unpacked copy
+    /// Returns the new project id
+    pub fn import_archive(&mut self, path: &Path) -> Result<String, ProjectDatabaseError> {
+        let file = File::open(path).map_err(|e| archive_error("Failed to open archive", e))?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| archive_error("Not a project archive", e))?;
+
+        let manifest: ArchiveManifest = {
+            let mut entry = zip.by_name(MANIFEST_NAME).map_err(|e| archive_error("Archive has no manifest", e))?;
+            let mut json = String::new();
+            entry.read_to_string(&mut json).map_err(|e| archive_error("Failed to read manifest", e))?;
+            serde_json::from_str(&json).map_err(|e| ProjectDatabaseError::Deserialization(e.to_string()))?
+        };
+        if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+            return Err(ProjectDatabaseError::Archive(format!(
+                "Archive format {} is newer than this version supports ({})",
+                manifest.format_version, ARCHIVE_FORMAT_VERSION
+            )));
+        }
+
+        // Untrusted like the entries: only the file name is kept, so the PCB stays inside `board/`
+        let pcb_name = match &manifest.pcb_file {
+            Some(pcb_file) => Path::new(pcb_file).file_name()
+                .map(|name| name.to_os_string())
+                .ok_or_else(|| ProjectDatabaseError::Archive(format!("Invalid PCB file name in manifest: {}", pcb_file)))?,
+            None => manifest.project.metadata.pcb_file_path.file_name()
+                .map(|name| name.to_os_string())
+                .unwrap_or_else(|| "board.kicad_pcb".into()),
+        };
+
+        let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "project".to_string());
+        let dest = unique_dir(path.parent().unwrap_or(Path::new(".")).join(stem));
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(|e| archive_error("Failed to read archive", e))?;
+            // enclosed_name rejects absolute paths and `..`, so nothing lands outside `dest`
+            let Some(relative) = entry.enclosed_name() else {
+                continue;
+            };
+            if entry.is_dir() || relative == Path::new(MANIFEST_NAME) {
+                continue;
+            }
+            let target = dest.join(relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| archive_error("Failed to create folder", e))?;
+            }
+            let mut out = File::create(&target).map_err(|e| archive_error(&format!("Failed to create {}", target.display()), e))?;
+            std::io::copy(&mut entry, &mut out).map_err(|e| archive_error("Failed to unpack archive", e))?;
+        }
+
+        let board_dir = dest.join(BOARD_DIR);
+
+        let database = self.database.as_ref()
+            .ok_or_else(|| ProjectDatabaseError::DatabaseRead("Database not initialized".to_string()))?;
+        let mut project = manifest.project;
+        project.metadata.id = generate_project_id();
+        project.metadata.pcb_file_path = board_dir.join(pcb_name);
+        project.metadata.last_modified = Utc::now();
+
+        database.save_project(&project)?;
+        database.save_picked_points(&project.metadata.id, &manifest.picked_points)?;
+        database.save_annotations(&project.metadata.id, &manifest.annotations)?;
+        self.refresh_project_list()?;
+        Ok(project.metadata.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_manager::database::ProjectMetadata;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn test_archive_round_trip_rewrites_pcb_path() {
+        let root = TempDir::new("archive");
+        let board = root.path().join("source");
+        std::fs::create_dir_all(board.join(GERBER_DIR)).unwrap();
+        std::fs::write(board.join("demo.kicad_pcb"), "(kicad_pcb)").unwrap();
+        std::fs::write(board.join(GERBER_DIR).join("demo-F_Cu.gbr"), "G04 top*\nM02*\n").unwrap();
+
+        let mut state = ProjectManagerState::default();
+        state.initialize_database(&root.path().join("projects.db")).unwrap();
+        let project_id = state.create_project(
+            "Demo".to_string(),
+            String::new(),
+            board.join("demo.kicad_pcb"),
+            vec!["review".to_string()],
+            Vec::new(),
+        ).unwrap();
+
+        let archive = root.path().join("demo.cfproj");
+        state.export_archive(&project_id, &archive).unwrap();
+        let imported_id = state.import_archive(&archive).unwrap();
+        assert_ne!(imported_id, project_id);
+
+        let imported = state.database.as_ref().unwrap().load_project(&imported_id).unwrap().unwrap();
+        assert_eq!(imported.metadata.pcb_file_path, root.path().join("demo").join(BOARD_DIR).join("demo.kicad_pcb"));
+        assert_eq!(imported.metadata.tags, vec!["review".to_string()]);
+        assert!(imported.metadata.pcb_file_path.is_file());
+        assert!(gerber_dir_for(&imported.metadata.pcb_file_path).join("demo-F_Cu.gbr").is_file());
+    }
+
+    /// Archive holding only a manifest that names `pcb_file` as its PCB
+    fn write_manifest_only_archive(path: &Path, pcb_file: &str) {
+        let now = Utc::now();
+        let metadata = ProjectMetadata {
+            id: "proj_1".to_string(),
+            name: "Crafted".to_string(),
+            description: String::new(),
+            pcb_file_path: PathBuf::from("board.kicad_pcb"),
+            created_at: now,
+            last_modified: now,
+            version: String::new(),
+            tags: Vec::new(),
+        };
+        let manifest = ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            exported_at: now,
+            project: ProjectData { metadata, bom_components: Vec::new(), notes: String::new() },
+            picked_points: Vec::new(),
+            annotations: Vec::new(),
+            pcb_file: Some(pcb_file.to_string()),
+            gerber_files: Vec::new(),
+        };
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        zip.start_file(MANIFEST_NAME, zip::write::SimpleFileOptions::default()).unwrap();
+        zip.write_all(serde_json::to_string(&manifest).unwrap().as_bytes()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_manifest_pcb_file_cannot_leave_the_board_folder() {
+        let root = TempDir::new("archive_manifest");
+        let mut state = ProjectManagerState::default();
+        state.initialize_database(&root.path().join("projects.db")).unwrap();
+
+        let archive = root.path().join("crafted.cfproj");
+        write_manifest_only_archive(&archive, "../evil.kicad_pcb");
+        let imported_id = state.import_archive(&archive).unwrap();
+        let imported = state.database.as_ref().unwrap().load_project(&imported_id).unwrap().unwrap();
+        assert_eq!(imported.metadata.pcb_file_path, root.path().join("crafted").join(BOARD_DIR).join("evil.kicad_pcb"));
+
+        // A name with no file name left is refused before anything is unpacked
+        let archive = root.path().join("parent.cfproj");
+        write_manifest_only_archive(&archive, "..");
+        assert!(matches!(state.import_archive(&archive), Err(ProjectDatabaseError::Archive(_))));
+        assert!(!root.path().join("parent").exists());
+    }
+}
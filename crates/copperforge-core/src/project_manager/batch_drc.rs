@@ -0,0 +1,216 @@
+/// DRC sweep over every project in the database
+///
+/// Each project's `gerber_output/` folder is loaded into its own ECS world on a
+/// worker thread and checked with the given rules. Only a compact summary is
+/// kept per project, stored in the database next to the project.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::drc_operations::types::DrcRules;
+
+/// Outcome of the last DRC run on a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrcSummary {
+    pub checked_at: DateTime<Utc>,
+    /// Why the project could not be checked (missing gerbers, no outline...)
+    pub not_checked: Option<String>,
+    pub violations: usize,
+    /// Soldermask warnings
+    pub warnings: usize,
+    /// Violation count per rule name, sorted by rule
+    pub rule_counts: Vec<(String, usize)>,
+}
+
+/// Traffic-light state of a summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrcStatus {
+    Pass,
+    Warnings,
+    Fail,
+    NotChecked,
+}
+
+impl DrcSummary {
+    fn not_checked(reason: String) -> Self {
+        Self { checked_at: Utc::now(), not_checked: Some(reason), violations: 0, warnings: 0, rule_counts: Vec::new() }
+    }
+
+    pub fn status(&self) -> DrcStatus {
+        if self.not_checked.is_some() {
+            DrcStatus::NotChecked
+        } else if self.violations > 0 {
+            DrcStatus::Fail
+        } else if self.warnings > 0 {
+            DrcStatus::Warnings
+        } else {
+            DrcStatus::Pass
+        }
+    }
+
+    /// One line per rule, for tooltips
+    pub fn describe(&self) -> String {
+        let checked = self.checked_at.format("%Y-%m-%d %H:%M UTC");
+        if let Some(reason) = &self.not_checked {
+            return format!("Not checked ({}): {}", checked, reason);
+        }
+        let mut lines = vec![format!(
+            "Checked {}: {} violation(s), {} warning(s)",
+            checked, self.violations, self.warnings
+        )];
+        lines.extend(self.rule_counts.iter().map(|(rule, count)| format!("  {}: {}", rule, count)));
+        lines.join("\n")
+    }
+}
+
+/// Load a project's gerbers into a fresh world and run the full DRC on them
+pub fn check_project(pcb_path: &Path, rules: &DrcRules) -> DrcSummary {
+    let gerber_dir = super::archive::gerber_dir_for(pcb_path);
+    if !gerber_dir.is_dir() {
+        return DrcSummary::not_checked(format!("no gerber folder at {}", gerber_dir.display()));
+    }
+
+    let mut world = crate::ecs::setup_ecs_world();
+    let results = crate::api::load_gerber_directory(&mut world, &gerber_dir)
+        .and_then(|summary| {
+            if summary.loaded_layers == 0 {
+                Err("no gerber layers found".to_string())
+            } else {
+                crate::api::run_drc(&mut world, rules)
+            }
+        });
+    let results = match results {
+        Ok(results) => results,
+        Err(e) => return DrcSummary::not_checked(e),
+    };
+
+    let mut rule_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for violation in &results.violations {
+        *rule_counts.entry(violation.rule_name.clone()).or_default() += 1;
+    }
+    DrcSummary {
+        checked_at: Utc::now(),
+        not_checked: None,
+        violations: results.violations.len(),
+        warnings: results.mask_warnings.len(),
+        rule_counts: rule_counts.into_iter().collect(),
+    }
+}
+
+/// A project queued for the sweep
+#[derive(Debug, Clone)]
+pub struct BatchProject {
+    pub id: String,
+    pub name: String,
+    pub pcb_path: PathBuf,
+}
+
+enum BatchDrcProgress {
+    Checking { name: String, index: usize },
+    Checked { project_id: String, summary: DrcSummary },
+    Done,
+}
+
+/// Handle to a batch DRC sweep running on a worker thread
+pub struct BatchDrcJob {
+    receiver: Receiver<BatchDrcProgress>,
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+
+    pub total: usize,
+    /// Projects finished so far
+    pub done: usize,
+    /// Name of the project being checked
+    pub current_project: String,
+}
+
+impl BatchDrcJob {
+    /// Start checking the projects one after another on a background thread
+    pub fn spawn(projects: Vec<BatchProject>, rules: DrcRules) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_cancel = cancel.clone();
+        let total = projects.len();
+
+        let handle = std::thread::spawn(move || {
+            for (index, project) in projects.into_iter().enumerate() {
+                if worker_cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                let _ = sender.send(BatchDrcProgress::Checking { name: project.name.clone(), index });
+                let summary = check_project(&project.pcb_path, &rules);
+                let _ = sender.send(BatchDrcProgress::Checked { project_id: project.id, summary });
+            }
+            let _ = sender.send(BatchDrcProgress::Done);
+        });
+
+        Self { receiver, cancel, handle: Some(handle), total, done: 0, current_project: String::new() }
+    }
+
+    /// Stop after the project being checked
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelling(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Drain progress messages; returns the summaries that arrived and whether the sweep is over
+    pub fn poll(&mut self) -> (Vec<(String, DrcSummary)>, bool) {
+        let mut summaries = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(BatchDrcProgress::Checking { name, index }) => {
+                    self.current_project = name;
+                    self.done = index;
+                }
+                Ok(BatchDrcProgress::Checked { project_id, summary }) => {
+                    self.done += 1;
+                    summaries.push((project_id, summary));
+                }
+                Ok(BatchDrcProgress::Done) | Err(TryRecvError::Disconnected) => {
+                    if let Some(handle) = self.handle.take() {
+                        let _ = handle.join();
+                    }
+                    return (summaries, true);
+                }
+                Err(TryRecvError::Empty) => return (summaries, false),
+            }
+        }
+    }
+}
+
+impl Drop for BatchDrcJob {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_gerbers_are_not_checked() {
+        let pcb_path = std::env::temp_dir()
+            .join(format!("copperforge_batch_drc_{}", std::process::id()))
+            .join("board.kicad_pcb");
+        let summary = check_project(&pcb_path, &DrcRules::default());
+        assert_eq!(summary.status(), DrcStatus::NotChecked);
+
+        let mut failing = summary.clone();
+        failing.not_checked = None;
+        failing.violations = 2;
+        failing.rule_counts = vec![("Min Trace Width".to_string(), 2)];
+        assert_eq!(failing.status(), DrcStatus::Fail);
+        assert!(failing.describe().contains("Min Trace Width: 2"));
+    }
+}
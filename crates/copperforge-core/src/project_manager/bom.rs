@@ -8,7 +8,7 @@ use std::time::Duration;
 
 /// Component data for the BOM table
 /// This represents a single component in the Bill of Materials
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BomComponent {
     pub item_number: String,
     pub reference: String,
@@ -18,6 +18,10 @@ pub struct BomComponent {
     pub orientation: f64,
     pub value: String,
     pub footprint: String,
+    /// Copper layer the component is placed on ("F.Cu" or "B.Cu"), filled in from KiCad
+    /// Empty for BOMs saved before the layer was stored
+    #[serde(default)]
+    pub layer: String,
 }
 
 /// Events sent from UI to BOM backend
@@ -60,6 +64,7 @@ impl BomComponent {
             orientation: 0.0,
             value: String::new(),
             footprint: String::new(),
+            layer: String::new(),
         }
     }
     
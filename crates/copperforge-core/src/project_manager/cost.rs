@@ -0,0 +1,104 @@
+/// Rough fabrication cost from board area
+///
+/// Cost is area times a rate per layer count, discounted by quantity breaks,
+/// plus a one-off setup fee. Meant for ballpark quoting, not a fab's price list.
+
+use serde::{Deserialize, Serialize};
+
+/// Quantities shown in the estimate table
+pub const ESTIMATE_QUANTITIES: [u32; 3] = [1, 10, 100];
+
+const MM2_PER_DM2: f64 = 10_000.0;
+
+/// Price per dm² of board for a copper layer count
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerRate {
+    pub layers: u32,
+    pub per_dm2: f64,
+}
+
+/// Discount applied from `quantity` pieces upwards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantityBreak {
+    pub quantity: u32,
+    pub discount_percent: f64,
+}
+
+/// Cost estimator rates, remembered in the project config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostSettings {
+    pub currency: String,
+    /// One-off charge per order
+    pub setup_fee: f64,
+    pub layer_rates: Vec<LayerRate>,
+    pub quantity_breaks: Vec<QuantityBreak>,
+}
+
+impl Default for CostSettings {
+    fn default() -> Self {
+        Self {
+            currency: "$".to_string(),
+            setup_fee: 25.0,
+            layer_rates: vec![
+                LayerRate { layers: 1, per_dm2: 2.0 },
+                LayerRate { layers: 2, per_dm2: 3.0 },
+                LayerRate { layers: 4, per_dm2: 6.0 },
+                LayerRate { layers: 6, per_dm2: 9.0 },
+            ],
+            quantity_breaks: vec![
+                QuantityBreak { quantity: 10, discount_percent: 20.0 },
+                QuantityBreak { quantity: 100, discount_percent: 45.0 },
+            ],
+        }
+    }
+}
+
+/// Estimated price of one order quantity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    pub quantity: u32,
+    pub unit_cost: f64,
+    pub total: f64,
+}
+
+impl CostSettings {
+    /// Rate for a layer count; boards between listed counts use the next higher one
+    pub fn rate_for(&self, layers: u32) -> Option<f64> {
+        self.layer_rates.iter()
+            .filter(|rate| rate.layers >= layers)
+            .min_by_key(|rate| rate.layers)
+            .map(|rate| rate.per_dm2)
+    }
+
+    /// Discount (0..1) of the largest break not above `quantity`
+    fn discount_for(&self, quantity: u32) -> f64 {
+        self.quantity_breaks.iter()
+            .filter(|quantity_break| quantity_break.quantity <= quantity)
+            .max_by_key(|quantity_break| quantity_break.quantity)
+            .map(|quantity_break| (quantity_break.discount_percent / 100.0).clamp(0.0, 1.0))
+            .unwrap_or(0.0)
+    }
+
+    /// Estimate for `quantity` boards of `area_mm2`; None when no rate covers the layer count
+    pub fn estimate(&self, area_mm2: f64, layers: u32, quantity: u32) -> Option<CostEstimate> {
+        let board_cost = area_mm2 / MM2_PER_DM2 * self.rate_for(layers)? * (1.0 - self.discount_for(quantity));
+        let total = board_cost * quantity as f64 + self.setup_fee;
+        Some(CostEstimate { quantity, unit_cost: total / quantity.max(1) as f64, total })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_applies_layer_rate_and_quantity_break() {
+        let settings = CostSettings::default();
+        // 100 x 50 mm = 0.5 dm²; 3 layers are priced as 4
+        let estimate = settings.estimate(5000.0, 3, 10).unwrap();
+        assert!((estimate.total - (0.5 * 6.0 * 0.8 * 10.0 + 25.0)).abs() < 1e-9);
+        assert!((estimate.unit_cost - estimate.total / 10.0).abs() < 1e-9);
+        assert!(settings.estimate(5000.0, 8, 1).is_none());
+    }
+}
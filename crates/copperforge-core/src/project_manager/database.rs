@@ -1,6 +1,11 @@
 use serde::{Serialize, Deserialize};
 use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
+use crate::display::QuadrantLayout;
+use crate::drc_operations::DrcCheckConfig;
+use crate::ecs::{LayerNudges, PasteAdjustSettings};
+use crate::project_manager::annotation::Annotation;
+use crate::project_manager::batch_drc::DrcSummary;
 use crate::project_manager::bom::BomComponent;
 
 /// Database manager for project storage
@@ -29,6 +34,59 @@ pub struct ProjectData {
     pub notes: String,
 }
 
+/// Marks project records written since BOM components carry their layer
+/// Older records start with the length of the project id, whose first byte is never 0xFF
+const PROJECT_RECORD_V2: &[u8] = b"\xFFCFP2";
+
+/// BOM component as stored before `layer` was added
+#[derive(Serialize, Deserialize)]
+struct LegacyBomComponent {
+    item_number: String,
+    reference: String,
+    description: String,
+    x_location: f64,
+    y_location: f64,
+    orientation: f64,
+    value: String,
+    footprint: String,
+}
+
+/// Project record as stored before `PROJECT_RECORD_V2`
+#[derive(Serialize, Deserialize)]
+struct LegacyProjectData {
+    metadata: ProjectMetadata,
+    bom_components: Vec<LegacyBomComponent>,
+    notes: String,
+}
+
+impl From<LegacyProjectData> for ProjectData {
+    fn from(legacy: LegacyProjectData) -> Self {
+        let bom_components = legacy.bom_components
+            .into_iter()
+            .map(|c| BomComponent {
+                item_number: c.item_number,
+                reference: c.reference,
+                description: c.description,
+                x_location: c.x_location,
+                y_location: c.y_location,
+                orientation: c.orientation,
+                value: c.value,
+                footprint: c.footprint,
+                layer: String::new(),
+            })
+            .collect();
+        Self { metadata: legacy.metadata, bom_components, notes: legacy.notes }
+    }
+}
+
+/// A probe point picked on the board, in source gerber coordinates (mm)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickedPoint {
+    pub x: f64,
+    pub y: f64,
+    pub label: String,
+}
+
 impl ProjectDatabase {
     /// Create a new project database
     pub fn new(db_path: &Path) -> Result<Self, ProjectDatabaseError> {
@@ -41,7 +99,8 @@ impl ProjectDatabase {
     /// Save a project to the database
     pub fn save_project(&self, project: &ProjectData) -> Result<(), ProjectDatabaseError> {
         let key = format!("project:{}", project.metadata.id);
-        let value = bincode::serialize(project)
+        let mut value = PROJECT_RECORD_V2.to_vec();
+        bincode::serialize_into(&mut value, project)
             .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
         
         self.db.insert(key.as_bytes(), value)
@@ -49,6 +108,10 @@ impl ProjectDatabase {
         
         // Update index for quick lookups
         self.update_project_index(&project.metadata)?;
+
+        // Lower-case notes, so searching does not load every project
+        self.db.insert(format!("notes:{}", project.metadata.id).as_bytes(), project.notes.to_lowercase().as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
         
         Ok(())
     }
@@ -60,8 +123,11 @@ impl ProjectDatabase {
         if let Some(value) = self.db.get(key.as_bytes())
             .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
             
-            let project: ProjectData = bincode::deserialize(&value)
-                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string()))?;
+            let project = match value.strip_prefix(PROJECT_RECORD_V2) {
+                Some(record) => bincode::deserialize::<ProjectData>(record),
+                None => bincode::deserialize::<LegacyProjectData>(&value).map(ProjectData::from),
+            }
+            .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string()))?;
             
             Ok(Some(project))
         } else {
@@ -80,9 +146,12 @@ impl ProjectDatabase {
             let project_ids: Vec<String> = bincode::deserialize(&index_data)
                 .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string()))?;
             
+            // One unreadable record should not hide the others
             for project_id in project_ids {
-                if let Some(project) = self.load_project(&project_id)? {
-                    projects.push(project.metadata);
+                match self.load_project(&project_id) {
+                    Ok(Some(project)) => projects.push(project.metadata),
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Failed to load project {}: {}", project_id, e),
                 }
             }
         }
@@ -103,26 +172,296 @@ impl ProjectDatabase {
         // Remove from index
         self.remove_from_project_index(project_id)?;
         
+        // Remove the project's notes index, dock layout, picked points, annotations, acknowledged assembly issues,
+        // DRC summary, DRC check toggles, paste preview settings, layer nudges and quadrant layout
+        self.db.remove(format!("notes:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("dock:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("points:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("annotations:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("assembly_ignore:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("drc_summary:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("drc_checks:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("paste_adjust:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("nudges:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        self.db.remove(format!("quadrant_layout:{}", project_id).as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        
+        Ok(())
+    }
+
+    /// Save a project's dock layout (serialized JSON, kept opaque to the database)
+    pub fn save_dock_layout(&self, project_id: &str, layout_json: &str) -> Result<(), ProjectDatabaseError> {
+        let key = format!("dock:{}", project_id);
+        self.db.insert(key.as_bytes(), layout_json.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load a project's dock layout, if one has been saved
+    pub fn load_dock_layout(&self, project_id: &str) -> Result<Option<String>, ProjectDatabaseError> {
+        let key = format!("dock:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => {
+                let json = String::from_utf8(value.to_vec())
+                    .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string()))?;
+                Ok(Some(json))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Save a project's picked point list
+    pub fn save_picked_points(&self, project_id: &str, points: &[PickedPoint]) -> Result<(), ProjectDatabaseError> {
+        let key = format!("points:{}", project_id);
+        let value = bincode::serialize(points)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load a project's picked point list (empty if none has been saved)
+    pub fn load_picked_points(&self, project_id: &str) -> Result<Vec<PickedPoint>, ProjectDatabaseError> {
+        let key = format!("points:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Save a project's review annotations
+    pub fn save_annotations(&self, project_id: &str, annotations: &[Annotation]) -> Result<(), ProjectDatabaseError> {
+        let key = format!("annotations:{}", project_id);
+        let value = bincode::serialize(annotations)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load a project's review annotations (empty if none have been saved)
+    pub fn load_annotations(&self, project_id: &str) -> Result<Vec<Annotation>, ProjectDatabaseError> {
+        let key = format!("annotations:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Save the component pairs whose assembly issues have been acknowledged
+    pub fn save_assembly_ignores(&self, project_id: &str, pair_keys: &[String]) -> Result<(), ProjectDatabaseError> {
+        let key = format!("assembly_ignore:{}", project_id);
+        let value = bincode::serialize(pair_keys)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load the acknowledged assembly issue pairs (empty if none have been saved)
+    pub fn load_assembly_ignores(&self, project_id: &str) -> Result<Vec<String>, ProjectDatabaseError> {
+        let key = format!("assembly_ignore:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Save the result of the last batch DRC run on a project
+    pub fn save_drc_summary(&self, project_id: &str, summary: &DrcSummary) -> Result<(), ProjectDatabaseError> {
+        let key = format!("drc_summary:{}", project_id);
+        let value = bincode::serialize(summary)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load the last batch DRC result of a project, if it has been checked
+    pub fn load_drc_summary(&self, project_id: &str) -> Result<Option<DrcSummary>, ProjectDatabaseError> {
+        let key = format!("drc_summary:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map(Some)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Save which DRC checks a project runs
+    pub fn save_drc_checks(&self, project_id: &str, checks: &DrcCheckConfig) -> Result<(), ProjectDatabaseError> {
+        let key = format!("drc_checks:{}", project_id);
+        let value = bincode::serialize(checks)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load which DRC checks a project runs (None if they were never changed)
+    pub fn load_drc_checks(&self, project_id: &str) -> Result<Option<DrcCheckConfig>, ProjectDatabaseError> {
+        let key = format!("drc_checks:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map(Some)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Save a project's solder paste preview settings
+    pub fn save_paste_adjust(&self, project_id: &str, settings: &PasteAdjustSettings) -> Result<(), ProjectDatabaseError> {
+        let key = format!("paste_adjust:{}", project_id);
+        let value = bincode::serialize(settings)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load a project's solder paste preview settings (None if they were never changed)
+    pub fn load_paste_adjust(&self, project_id: &str) -> Result<Option<PasteAdjustSettings>, ProjectDatabaseError> {
+        let key = format!("paste_adjust:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map(Some)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Save a project's layer alignment nudges
+    pub fn save_layer_nudges(&self, project_id: &str, nudges: &LayerNudges) -> Result<(), ProjectDatabaseError> {
+        let key = format!("nudges:{}", project_id);
+        let value = bincode::serialize(nudges)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Load a project's layer alignment nudges (None if none were ever saved)
+    pub fn load_layer_nudges(&self, project_id: &str) -> Result<Option<LayerNudges>, ProjectDatabaseError> {
+        let key = format!("nudges:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map(Some)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Save a project's quadrant view layout
+    pub fn save_quadrant_layout(&self, project_id: &str, layout: &QuadrantLayout) -> Result<(), ProjectDatabaseError> {
+        let key = format!("quadrant_layout:{}", project_id);
+        let value = bincode::serialize(layout)
+            .map_err(|e| ProjectDatabaseError::Serialization(e.to_string()))?;
+        self.db.insert(key.as_bytes(), value)
+            .map_err(|e| ProjectDatabaseError::DatabaseWrite(e.to_string()))?;
         Ok(())
     }
 
-    /// Search projects by name or description
+    /// Load a project's quadrant view layout (None if it was never changed)
+    pub fn load_quadrant_layout(&self, project_id: &str) -> Result<Option<QuadrantLayout>, ProjectDatabaseError> {
+        let key = format!("quadrant_layout:{}", project_id);
+        match self.db.get(key.as_bytes())
+            .map_err(|e| ProjectDatabaseError::DatabaseRead(e.to_string()))? {
+            Some(value) => bincode::deserialize(&value)
+                .map(Some)
+                .map_err(|e| ProjectDatabaseError::Deserialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Search projects by name, description, tags or notes
     pub fn search_projects(&self, query: &str) -> Result<Vec<ProjectMetadata>, ProjectDatabaseError> {
         let all_projects = self.list_projects()?;
         let query_lower = query.to_lowercase();
         
-        let filtered: Vec<ProjectMetadata> = all_projects
+        let mut filtered = Vec::new();
+        for project in all_projects {
+            let in_metadata = project.name.to_lowercase().contains(&query_lower) ||
+                project.description.to_lowercase().contains(&query_lower) ||
+                project.tags.iter().any(|tag| tag.to_lowercase().contains(&query_lower));
+            if in_metadata || self.notes_contain(&project.id, &query_lower) {
+                filtered.push(project);
+            }
+        }
+        
+        Ok(filtered)
+    }
+
+    /// Whether a project's notes contain `query_lower`
+    /// Projects saved before notes were indexed are loaded instead; one that cannot be read does not match
+    fn notes_contain(&self, project_id: &str, query_lower: &str) -> bool {
+        match self.db.get(format!("notes:{}", project_id).as_bytes()) {
+            Ok(Some(notes)) => String::from_utf8_lossy(&notes).contains(query_lower),
+            _ => match self.load_project(project_id) {
+                Ok(project) => project.is_some_and(|data| data.notes.to_lowercase().contains(query_lower)),
+                Err(e) => {
+                    eprintln!("Failed to search the notes of {}: {}", project_id, e);
+                    false
+                }
+            },
+        }
+    }
+
+    /// Projects carrying the given tags (case-insensitive); all projects when `tags` is empty
+    /// With `match_all` a project needs every tag, otherwise any one of them
+    pub fn list_by_tags(&self, tags: &[String], match_all: bool) -> Result<Vec<ProjectMetadata>, ProjectDatabaseError> {
+        let all_projects = self.list_projects()?;
+        if tags.is_empty() {
+            return Ok(all_projects);
+        }
+        
+        let wanted: Vec<String> = tags.iter().map(|tag| tag.to_lowercase()).collect();
+        let filtered = all_projects
             .into_iter()
             .filter(|project| {
-                project.name.to_lowercase().contains(&query_lower) ||
-                project.description.to_lowercase().contains(&query_lower) ||
-                project.tags.iter().any(|tag| tag.to_lowercase().contains(&query_lower))
+                let has = |tag: &String| project.tags.iter().any(|t| t.to_lowercase() == *tag);
+                if match_all { wanted.iter().all(has) } else { wanted.iter().any(has) }
             })
             .collect();
         
         Ok(filtered)
     }
 
+    /// Every tag in use with the number of projects carrying it, sorted by tag
+    /// Tags differing only in case are counted together under the first spelling seen
+    pub fn tag_counts(&self) -> Result<Vec<(String, usize)>, ProjectDatabaseError> {
+        let mut counts: std::collections::BTreeMap<String, (String, usize)> = std::collections::BTreeMap::new();
+        for project in self.list_projects()? {
+            let mut seen = std::collections::HashSet::new();
+            for tag in &project.tags {
+                let key = tag.to_lowercase();
+                if seen.insert(key.clone()) {
+                    counts.entry(key).or_insert_with(|| (tag.clone(), 0)).1 += 1;
+                }
+            }
+        }
+        Ok(counts.into_values().collect())
+    }
+
     /// Find project by PCB file path
     pub fn find_project_by_pcb_path(&self, pcb_path: &std::path::Path) -> Result<Option<ProjectData>, ProjectDatabaseError> {
         let all_projects = self.list_projects()?;
@@ -224,6 +563,9 @@ pub enum ProjectDatabaseError {
     
     #[error("Failed to deserialize data: {0}")]
     Deserialization(String),
+    
+    #[error("Project archive error: {0}")]
+    Archive(String),
 }
 
 /// Helper function to generate unique project ID
@@ -235,4 +577,151 @@ pub fn generate_project_id() -> String {
         .as_millis();
     
     format!("proj_{}", timestamp)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use crate::ecs::LayerNudge;
+
+    #[test]
+    fn test_bom_layer_is_stored_and_legacy_records_load() {
+        let root = TempDir::new("bom_layer");
+        let database = ProjectDatabase::new(&root.path().join("projects.db")).unwrap();
+        let now = Utc::now();
+        let metadata = |id: &str| ProjectMetadata {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            pcb_file_path: PathBuf::from("board.kicad_pcb"),
+            created_at: now,
+            last_modified: now,
+            version: String::new(),
+            tags: Vec::new(),
+        };
+
+        let mut part = BomComponent::new("U1".to_string());
+        part.layer = "B.Cu".to_string();
+        database.save_project(&ProjectData { metadata: metadata("proj_1"), bom_components: vec![part], notes: String::new() }).unwrap();
+        let loaded = database.load_project("proj_1").unwrap().unwrap();
+        assert_eq!(loaded.bom_components[0].layer, "B.Cu");
+
+        // A record written before the layer was stored still loads, with the layer unknown
+        let legacy = LegacyProjectData {
+            metadata: metadata("proj_2"),
+            bom_components: vec![LegacyBomComponent {
+                item_number: "1".to_string(),
+                reference: "R1".to_string(),
+                description: String::new(),
+                x_location: 1.0,
+                y_location: 2.0,
+                orientation: 90.0,
+                value: "10k".to_string(),
+                footprint: "R_0603".to_string(),
+            }],
+            notes: "legacy".to_string(),
+        };
+        database.db.insert(b"project:proj_2", bincode::serialize(&legacy).unwrap()).unwrap();
+        let loaded = database.load_project("proj_2").unwrap().unwrap();
+        assert_eq!((loaded.bom_components[0].reference.as_str(), loaded.bom_components[0].layer.as_str()), ("R1", ""));
+        assert_eq!(loaded.notes, "legacy");
+    }
+
+    #[test]
+    fn test_search_uses_the_notes_index_and_skips_unreadable_projects() {
+        let root = TempDir::new("search");
+        let database = ProjectDatabase::new(&root.path().join("projects.db")).unwrap();
+        let now = Utc::now();
+        let project = |id: &str, notes: &str| ProjectData {
+            metadata: ProjectMetadata {
+                id: id.to_string(),
+                name: format!("Board {}", id),
+                description: String::new(),
+                pcb_file_path: PathBuf::from("board.kicad_pcb"),
+                created_at: now,
+                last_modified: now,
+                version: String::new(),
+                tags: vec!["Power".to_string()],
+            },
+            bom_components: Vec::new(),
+            notes: notes.to_string(),
+        };
+        database.save_project(&project("proj_1", "Rev B moves U3 to 0402 caps")).unwrap();
+        database.save_project(&project("proj_2", "")).unwrap();
+        // Saved before notes were indexed
+        database.save_project(&project("proj_3", "Needs a second 0402 reel")).unwrap();
+        database.db.remove(b"notes:proj_3").unwrap();
+        // Listed in the index but unreadable
+        database.save_project(&project("proj_4", "0402")).unwrap();
+        database.db.insert(b"project:proj_4", b"garbage".to_vec()).unwrap();
+
+        let ids = |query: &str| {
+            let mut ids: Vec<String> = database.search_projects(query).unwrap().into_iter().map(|p| p.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids("0402"), ["proj_1", "proj_3"]);
+        assert_eq!(ids("REV b"), ["proj_1"]);
+        assert_eq!(ids("power"), ["proj_1", "proj_2", "proj_3"]);
+        assert!(ids("missing").is_empty());
+
+        database.delete_project("proj_1").unwrap();
+        assert!(database.db.get(b"notes:proj_1").unwrap().is_none());
+        assert_eq!(ids("0402"), ["proj_3"]);
+    }
+
+    /// One per-project store: saves a value under a project and loads it back, both as `Debug` text
+    struct PerProjectStore {
+        name: &'static str,
+        save: fn(&ProjectDatabase, &str) -> String,
+        load: fn(&ProjectDatabase, &str) -> Option<String>,
+    }
+
+    #[test]
+    fn test_view_state_is_kept_per_project() {
+        let root = TempDir::new("per_project");
+        let database = ProjectDatabase::new(&root.path().join("projects.db")).unwrap();
+        let stores = [
+            PerProjectStore {
+                name: "dock layout",
+                save: |database, id| {
+                    let layout = r#"{"surfaces":[],"translations":{}}"#;
+                    database.save_dock_layout(id, layout).unwrap();
+                    format!("{:?}", layout)
+                },
+                load: |database, id| database.load_dock_layout(id).unwrap().map(|layout| format!("{:?}", layout)),
+            },
+            PerProjectStore {
+                name: "layer nudges",
+                save: |database, id| {
+                    let mut nudges = LayerNudges::default();
+                    nudges.0.insert("board-F_Paste.gbr".to_string(), LayerNudge { x: 0.25, y: -0.1, rotation_degrees: 0.0 });
+                    database.save_layer_nudges(id, &nudges).unwrap();
+                    format!("{:?}", nudges)
+                },
+                load: |database, id| database.load_layer_nudges(id).unwrap().map(|nudges| format!("{:?}", nudges)),
+            },
+            PerProjectStore {
+                name: "quadrant layout",
+                save: |database, id| {
+                    let layout = QuadrantLayout { custom: true, ..Default::default() };
+                    database.save_quadrant_layout(id, &layout).unwrap();
+                    format!("{:?}", layout)
+                },
+                load: |database, id| database.load_quadrant_layout(id).unwrap().map(|layout| format!("{:?}", layout)),
+            },
+        ];
+
+        for store in &stores {
+            let saved = (store.save)(&database, "proj_1");
+            assert_eq!((store.load)(&database, "proj_1"), Some(saved), "{}", store.name);
+            assert_eq!((store.load)(&database, "proj_2"), None, "{}", store.name);
+        }
+
+        // Deleting the project removes all of it
+        database.delete_project("proj_1").unwrap();
+        for store in &stores {
+            assert_eq!((store.load)(&database, "proj_1"), None, "{}", store.name);
+        }
+    }
+}
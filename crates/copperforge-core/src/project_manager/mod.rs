@@ -1,10 +1,30 @@
 pub mod database;
+pub mod annotation;
 pub mod bom;
+pub mod templates;
+pub mod archive;
+pub mod batch_drc;
+pub mod cost;
+pub mod post_gen_hook;
+pub mod recent;
 
 use database::{ProjectDatabase, ProjectData, ProjectMetadata, generate_project_id, ProjectDatabaseError};
 use bom::BomComponent;
+use templates::ProjectTemplate;
+use batch_drc::DrcSummary;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Tag filter applied to the project list, remembered between sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagFilter {
+    pub tags: Vec<String>,
+    /// Require every selected tag instead of any of them
+    pub match_all: bool,
+}
 
 /// Project manager state
 pub struct ProjectManagerState {
@@ -21,6 +41,16 @@ pub struct ProjectManagerState {
     pub new_project_pcb_path: Option<PathBuf>,
     pub show_pcb_file_dialog: bool,
     pub last_error: Option<String>,
+    pub templates: Vec<ProjectTemplate>,
+    /// Template chosen in the create dialog; None means "Blank"
+    pub selected_template: Option<String>,
+    /// The current project has changes not yet written to the database
+    pub dirty: bool,
+    pub tag_filter: TagFilter,
+    /// Tags of all projects with their counts, refreshed with the project list
+    pub tag_counts: Vec<(String, usize)>,
+    /// Last batch DRC result of each listed project that has one
+    pub drc_summaries: HashMap<String, DrcSummary>,
 }
 
 impl Default for ProjectManagerState {
@@ -39,6 +69,12 @@ impl Default for ProjectManagerState {
             new_project_pcb_path: None,
             show_pcb_file_dialog: false,
             last_error: None,
+            templates: Vec::new(),
+            selected_template: None,
+            dirty: false,
+            tag_filter: TagFilter::default(),
+            tag_counts: Vec::new(),
+            drc_summaries: HashMap::new(),
         }
     }
 }
@@ -46,12 +82,85 @@ impl Default for ProjectManagerState {
 impl ProjectManagerState {
     /// Initialize the project database
     pub fn initialize_database(&mut self, db_path: &Path) -> Result<(), ProjectDatabaseError> {
-        let database = ProjectDatabase::new(db_path)?;
-        self.project_list = database.list_projects()?;
-        self.database = Some(database);
+        self.database = Some(ProjectDatabase::new(db_path)?);
+        self.refresh_project_list()
+    }
+
+    /// Re-read the project list, applying the search query and tag filter
+    pub fn refresh_project_list(&mut self) -> Result<(), ProjectDatabaseError> {
+        let Some(database) = self.database.as_ref() else {
+            return Err(ProjectDatabaseError::DatabaseRead("Database not initialized".to_string()));
+        };
+        
+        let mut projects = database.list_by_tags(&self.tag_filter.tags, self.tag_filter.match_all)?;
+        if !self.search_query.is_empty() {
+            let matches: HashSet<String> = database.search_projects(&self.search_query)?
+                .into_iter()
+                .map(|project| project.id)
+                .collect();
+            projects.retain(|project| matches.contains(&project.id));
+        }
+        
+        self.tag_counts = database.tag_counts()?;
+        self.drc_summaries = projects.iter()
+            .filter_map(|project| match database.load_drc_summary(&project.id) {
+                Ok(summary) => summary.map(|summary| (project.id.clone(), summary)),
+                Err(e) => {
+                    eprintln!("Failed to load DRC summary for {}: {}", project.name, e);
+                    None
+                }
+            })
+            .collect();
+        self.project_list = projects;
+        Ok(())
+    }
+
+    /// Store a batch DRC result for a project and show it in the list
+    pub fn record_drc_summary(&mut self, project_id: &str, summary: DrcSummary) -> Result<(), ProjectDatabaseError> {
+        let Some(database) = self.database.as_ref() else {
+            return Err(ProjectDatabaseError::DatabaseRead("Database not initialized".to_string()));
+        };
+        database.save_drc_summary(project_id, &summary)?;
+        self.drc_summaries.insert(project_id.to_string(), summary);
         Ok(())
     }
 
+    /// Add a tag to the filter, or remove it if already selected
+    pub fn toggle_tag_filter(&mut self, tag: &str) -> Result<(), ProjectDatabaseError> {
+        let before = self.tag_filter.tags.len();
+        self.tag_filter.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+        if self.tag_filter.tags.len() == before {
+            self.tag_filter.tags.push(tag.to_string());
+        }
+        self.refresh_project_list()
+    }
+
+    pub fn set_tag_match_all(&mut self, match_all: bool) -> Result<(), ProjectDatabaseError> {
+        self.tag_filter.match_all = match_all;
+        self.refresh_project_list()
+    }
+
+    pub fn clear_tag_filter(&mut self) -> Result<(), ProjectDatabaseError> {
+        self.tag_filter.tags.clear();
+        self.refresh_project_list()
+    }
+
+    /// Reload the project templates from the templates directory
+    pub fn reload_templates(&mut self, templates_dir: &Path) {
+        self.templates = templates::load_templates(templates_dir);
+        if let Some(ref selected) = self.selected_template {
+            if !self.templates.iter().any(|t| &t.name == selected) {
+                self.selected_template = None;
+            }
+        }
+    }
+
+    /// Get the template chosen in the create dialog, if any
+    pub fn get_selected_template(&self) -> Option<&ProjectTemplate> {
+        self.selected_template.as_ref()
+            .and_then(|name| self.templates.iter().find(|t| &t.name == name))
+    }
+
     /// Create a new project
     pub fn create_project(
         &mut self,
@@ -83,8 +192,9 @@ impl ProjectManagerState {
             };
             
             database.save_project(&project_data)?;
-            self.project_list = database.list_projects()?;
+            self.refresh_project_list()?;
             self.current_project = Some(project_data);
+            self.dirty = false;
             
             Ok(project_id)
         } else {
@@ -94,10 +204,13 @@ impl ProjectManagerState {
 
     /// Load a project
     pub fn load_project(&mut self, project_id: &str) -> Result<(), ProjectDatabaseError> {
+        // Don't lose unsaved changes to the project being switched away from
+        self.save_current_project()?;
         if let Some(ref database) = self.database {
             if let Some(project) = database.load_project(project_id)? {
                 self.current_project = Some(project);
                 self.selected_project_id = Some(project_id.to_string());
+                self.dirty = false;
                 Ok(())
             } else {
                 Err(ProjectDatabaseError::DatabaseRead(format!("Project {} not found", project_id)))
@@ -111,13 +224,14 @@ impl ProjectManagerState {
     pub fn delete_project(&mut self, project_id: &str) -> Result<(), ProjectDatabaseError> {
         if let Some(ref database) = self.database {
             database.delete_project(project_id)?;
-            self.project_list = database.list_projects()?;
+            self.refresh_project_list()?;
             
             // Clear current project if it was deleted
             if let Some(ref current) = self.current_project {
                 if current.metadata.id == project_id {
                     self.current_project = None;
                     self.selected_project_id = None;
+                    self.dirty = false;
                 }
             }
             
@@ -127,41 +241,69 @@ impl ProjectManagerState {
         }
     }
 
-    /// Search projects
+    /// Search projects, within the current tag filter
     pub fn search_projects(&mut self, query: &str) -> Result<(), ProjectDatabaseError> {
-        if let Some(ref database) = self.database {
-            self.project_list = if query.is_empty() {
-                database.list_projects()?
-            } else {
-                database.search_projects(query)?
-            };
-            Ok(())
-        } else {
-            Err(ProjectDatabaseError::DatabaseRead("Database not initialized".to_string()))
-        }
+        self.search_query = query.to_string();
+        self.refresh_project_list()
     }
 
     /// Update current project with new BOM data
     pub fn update_project_bom(&mut self, bom_components: Vec<BomComponent>) -> Result<(), ProjectDatabaseError> {
+        if self.current_project.is_none() {
+            return Err(ProjectDatabaseError::DatabaseRead("No current project loaded".to_string()));
+        }
+        self.set_project_bom(bom_components);
+        self.dirty = true;
+        self.save_current_project().map(|_| ())
+    }
+
+    /// Replace the current project's BOM in memory, marking it dirty if it changed
+    pub fn set_project_bom(&mut self, bom_components: Vec<BomComponent>) {
         if let Some(ref mut current_project) = self.current_project {
-            if let Some(ref database) = self.database {
+            if current_project.bom_components != bom_components {
                 current_project.bom_components = bom_components;
-                current_project.metadata.last_modified = Utc::now();
-                
-                database.save_project(current_project)?;
-                self.project_list = database.list_projects()?;
-                
-                Ok(())
-            } else {
-                Err(ProjectDatabaseError::DatabaseRead("Database not initialized".to_string()))
+                self.dirty = true;
             }
-        } else {
-            Err(ProjectDatabaseError::DatabaseRead("No current project loaded".to_string()))
         }
     }
 
+    /// Flag the current project as changed so the next (auto)save writes it
+    pub fn mark_dirty(&mut self) {
+        if self.current_project.is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// Write the current project to the database if it has unsaved changes
+    /// Returns true if anything was saved
+    pub fn save_current_project(&mut self) -> Result<bool, ProjectDatabaseError> {
+        if !self.dirty {
+            return Ok(false);
+        }
+        let (Some(current_project), Some(database)) = (self.current_project.as_mut(), self.database.as_ref()) else {
+            return Ok(false);
+        };
+        
+        current_project.metadata.last_modified = Utc::now();
+        database.save_project(current_project)?;
+        self.refresh_project_list()?;
+        self.dirty = false;
+        Ok(true)
+    }
+
     /// Update project metadata
     pub fn update_project(&mut self, project_id: &str, name: String, description: String, tags: Vec<String>) -> Result<(), ProjectDatabaseError> {
+        // Edit the open project in place so its unsaved changes go out with the metadata
+        if let Some(ref mut current) = self.current_project {
+            if current.metadata.id == project_id {
+                current.metadata.name = name;
+                current.metadata.description = description;
+                current.metadata.tags = tags;
+                self.dirty = true;
+                return self.save_current_project().map(|_| ());
+            }
+        }
+        
         if let Some(ref database) = self.database {
             if let Some(mut project) = database.load_project(project_id)? {
                 project.metadata.name = name;
@@ -170,14 +312,7 @@ impl ProjectManagerState {
                 project.metadata.last_modified = chrono::Utc::now();
                 
                 database.save_project(&project)?;
-                self.project_list = database.list_projects()?;
-                
-                // Update current project if it's the one being edited
-                if let Some(ref current) = self.current_project {
-                    if current.metadata.id == project_id {
-                        self.current_project = Some(project);
-                    }
-                }
+                self.refresh_project_list()?;
                 
                 Ok(())
             } else {
@@ -196,5 +331,93 @@ impl ProjectManagerState {
         self.new_project_tags.clear();
         self.new_project_pcb_path = None;
         self.show_pcb_file_dialog = false;
+        self.selected_template = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn save_fixture_project(database: &ProjectDatabase, id: &str, name: &str, tags: &[&str]) {
+        let now = Utc::now();
+        let metadata = ProjectMetadata {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            pcb_file_path: PathBuf::from("board.kicad_pcb"),
+            created_at: now,
+            last_modified: now,
+            version: String::new(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        database.save_project(&ProjectData { metadata, bom_components: Vec::new(), notes: String::new() }).unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_tag_filter_combines_with_search() {
+        let root = TempDir::new("tag_filter");
+        let mut state = ProjectManagerState::default();
+        state.initialize_database(&root.path().join("projects.db")).unwrap();
+        // Fixed ids: generated ones are millisecond timestamps and can collide within a test
+        let database = state.database.as_ref().unwrap();
+        let projects = [("proj_1", "Motor driver", vec!["power", "rev-b"]), ("proj_2", "Sensor hub", vec!["Power"]), ("proj_3", "LED panel", vec!["rev-b"])];
+        for (id, name, tags) in projects {
+            save_fixture_project(database, id, name, &tags);
+        }
+        state.refresh_project_list().unwrap();
+        let names = |state: &ProjectManagerState| {
+            let mut names: Vec<String> = state.project_list.iter().map(|p| p.name.clone()).collect();
+            names.sort();
+            names
+        };
+
+        // Which spelling of a tag is shown depends on the listing order, so compare case-folded
+        let mut counts: Vec<(String, usize)> = state.tag_counts.iter().map(|(tag, count)| (tag.to_lowercase(), *count)).collect();
+        counts.sort();
+        assert_eq!(counts, vec![("power".to_string(), 2), ("rev-b".to_string(), 2)]);
+        state.toggle_tag_filter("POWER").unwrap();
+        assert_eq!(names(&state), vec!["Motor driver", "Sensor hub"]);
+        state.toggle_tag_filter("rev-b").unwrap();
+        assert_eq!(names(&state), vec!["LED panel", "Motor driver", "Sensor hub"]);
+        state.set_tag_match_all(true).unwrap();
+        assert_eq!(names(&state), vec!["Motor driver"]);
+        state.search_projects("sensor").unwrap();
+        assert!(state.project_list.is_empty());
+    }
+
+    #[test]
+    fn test_current_project_is_saved_only_when_dirty() {
+        let root = TempDir::new("autosave");
+        let mut state = ProjectManagerState::default();
+        state.initialize_database(&root.path().join("projects.db")).unwrap();
+        save_fixture_project(state.database.as_ref().unwrap(), "proj_1", "Motor driver", &[]);
+        save_fixture_project(state.database.as_ref().unwrap(), "proj_2", "Sensor hub", &[]);
+        let saved_bom = |state: &ProjectManagerState, id: &str| {
+            state.database.as_ref().unwrap().load_project(id).unwrap().unwrap().bom_components.len()
+        };
+
+        // Nothing to write until the project changes; an identical BOM is no change
+        state.load_project("proj_1").unwrap();
+        assert!(!state.save_current_project().unwrap());
+        state.set_project_bom(Vec::new());
+        assert!(!state.dirty);
+
+        state.set_project_bom(vec![BomComponent::new("R1".to_string())]);
+        assert!(state.dirty);
+        assert_eq!(saved_bom(&state, "proj_1"), 0);
+        assert!(state.save_current_project().unwrap());
+        assert!(!state.dirty);
+        assert_eq!(saved_bom(&state, "proj_1"), 1);
+
+        // Switching projects writes the unsaved changes of the one left behind
+        state.set_project_bom(vec![BomComponent::new("R1".to_string()), BomComponent::new("C1".to_string())]);
+        state.load_project("proj_2").unwrap();
+        assert_eq!(saved_bom(&state, "proj_1"), 2);
+        assert!(!state.dirty);
+        state.mark_dirty();
+        state.delete_project("proj_2").unwrap();
+        assert!(!state.dirty && state.current_project.is_none());
+    }
+}
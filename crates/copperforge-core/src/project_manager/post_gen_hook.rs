@@ -0,0 +1,348 @@
+//! External command run after gerbers are regenerated
+//!
+//! The command template may use `{gerber_dir}`, `{pcb_path}`, `{project_name}`
+//! and `{timestamp}`. Substituted values are quoted for the platform shell
+//! (`sh -c` on Unix, `cmd /C` on Windows), so templates must not add their own
+//! quotes around them. The command runs on a worker thread in the gerber
+//! output directory; its output is streamed back line by line. On timeout or
+//! cancel the whole process tree is killed, not just the shell.
+
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Placeholders accepted in the command template
+pub const HOOK_VARIABLES: [&str; 4] = ["{gerber_dir}", "{pcb_path}", "{project_name}", "{timestamp}"];
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Post-generation hook settings, saved with the app settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostGenHook {
+    pub enabled: bool,
+    pub command: String,
+    /// The process is killed after this many seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for PostGenHook {
+    fn default() -> Self {
+        Self { enabled: false, command: String::new(), timeout_secs: 120 }
+    }
+}
+
+/// Values substituted into the command template
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    pub gerber_dir: PathBuf,
+    pub pcb_path: PathBuf,
+    pub project_name: String,
+    pub timestamp: String,
+}
+
+impl HookContext {
+    /// Context stamped with the current local time; the project name defaults to the PCB file name
+    pub fn new(gerber_dir: &Path, pcb_path: &Path, project_name: Option<&str>) -> Self {
+        let project_name = project_name
+            .map(str::to_string)
+            .or_else(|| pcb_path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        Self {
+            gerber_dir: gerber_dir.to_path_buf(),
+            pcb_path: pcb_path.to_path_buf(),
+            project_name,
+            timestamp: chrono::Local::now().format("%Y%m%d-%H%M%S").to_string(),
+        }
+    }
+}
+
+/// Quote one value for `sh -c`: single quotes, with embedded ones closed and escaped
+pub fn quote_unix(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Quote one value for `cmd /C`; Windows paths cannot contain double quotes, so any are dropped
+pub fn quote_windows(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', ""))
+}
+
+fn quote(value: &str) -> String {
+    if cfg!(windows) { quote_windows(value) } else { quote_unix(value) }
+}
+
+/// Command line with every placeholder replaced by its quoted value
+pub fn resolve_command(template: &str, context: &HookContext) -> String {
+    resolve_with(template, context, quote)
+}
+
+fn resolve_with(template: &str, context: &HookContext, quote: fn(&str) -> String) -> String {
+    template
+        .replace("{gerber_dir}", &quote(&context.gerber_dir.to_string_lossy()))
+        .replace("{pcb_path}", &quote(&context.pcb_path.to_string_lossy()))
+        .replace("{project_name}", &quote(&context.project_name))
+        .replace("{timestamp}", &quote(&context.timestamp))
+}
+
+#[cfg(windows)]
+fn shell_command(command_line: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+    // Passed raw, as Rust's argument escaping is not what cmd expects; with /S cmd strips
+    // exactly the outer pair of quotes, so the quoted values inside are kept
+    let mut command = Command::new("cmd");
+    command.args(["/S", "/C"]).raw_arg(format!("\"{}\"", command_line));
+    command
+}
+
+#[cfg(not(windows))]
+fn shell_command(command_line: &str) -> Command {
+    use std::os::unix::process::CommandExt;
+    // Its own process group, so a kill reaches whatever the shell started
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(command_line).process_group(0);
+    command
+}
+
+/// Kill the shell and every process it started
+#[cfg(windows)]
+fn kill_process_tree(child: &mut Child) {
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &child.id().to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+}
+
+/// Kill the shell and every process it started
+#[cfg(not(windows))]
+fn kill_process_tree(child: &mut Child) {
+    // The shell leads its own group (see `shell_command`), whose id is its pid
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+}
+
+/// How the hook process ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookOutcome {
+    Succeeded,
+    /// Non-zero exit code, or None when killed by a signal
+    Failed(Option<i32>),
+    TimedOut,
+    Cancelled,
+    /// The process could not be started
+    Error(String),
+}
+
+/// Output line or the end of the run
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookEvent {
+    Stdout(String),
+    Stderr(String),
+    Finished(HookOutcome),
+}
+
+fn forward_lines(stream: impl Read + Send + 'static, sender: Sender<HookEvent>, wrap: fn(String) -> HookEvent) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            let _ = sender.send(wrap(line));
+        }
+    })
+}
+
+fn run(command_line: &str, working_dir: &Path, timeout: Duration, cancel: &AtomicBool, sender: &Sender<HookEvent>) -> HookOutcome {
+    let mut child = match shell_command(command_line)
+        .current_dir(working_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return HookOutcome::Error(format!("Failed to start hook in {}: {}", working_dir.display(), e)),
+    };
+
+    let readers: Vec<JoinHandle<()>> = [
+        child.stdout.take().map(|stdout| forward_lines(stdout, sender.clone(), HookEvent::Stdout)),
+        child.stderr.take().map(|stderr| forward_lines(stderr, sender.clone(), HookEvent::Stderr)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let started = Instant::now();
+    let outcome = loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => break HookOutcome::Succeeded,
+            Ok(Some(status)) => break HookOutcome::Failed(status.code()),
+            Ok(None) => {}
+            Err(e) => break HookOutcome::Error(format!("Failed to wait for hook: {}", e)),
+        }
+        let stop = if cancel.load(Ordering::Relaxed) {
+            Some(HookOutcome::Cancelled)
+        } else if started.elapsed() >= timeout {
+            Some(HookOutcome::TimedOut)
+        } else {
+            None
+        };
+        if let Some(outcome) = stop {
+            kill_process_tree(&mut child);
+            let _ = child.wait();
+            break outcome;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+
+    // Children that left the process group can still hold the pipes; don't wait on them then
+    if outcome == HookOutcome::Succeeded || matches!(outcome, HookOutcome::Failed(_)) {
+        for reader in readers {
+            let _ = reader.join();
+        }
+    }
+    outcome
+}
+
+/// Handle to a hook process running on a worker thread
+pub struct PostGenHookJob {
+    receiver: Receiver<HookEvent>,
+    cancel: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+
+    pub command_line: String,
+    pub started: Instant,
+}
+
+impl PostGenHookJob {
+    /// Run the resolved command in `context.gerber_dir`
+    pub fn spawn(hook: &PostGenHook, context: &HookContext) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_cancel = cancel.clone();
+        let command_line = resolve_command(&hook.command, context);
+        let worker_command = command_line.clone();
+        let working_dir = context.gerber_dir.clone();
+        let timeout = Duration::from_secs(hook.timeout_secs.max(1));
+
+        let handle = std::thread::spawn(move || {
+            let outcome = run(&worker_command, &working_dir, timeout, &worker_cancel, &sender);
+            let _ = sender.send(HookEvent::Finished(outcome));
+        });
+
+        Self { receiver, cancel, handle: Some(handle), command_line, started: Instant::now() }
+    }
+
+    /// Kill the process
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Drain the events that arrived; the last one is `Finished` once the run is over
+    pub fn poll(&mut self) -> Vec<HookEvent> {
+        let mut events = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(HookEvent::Finished(outcome)) => {
+                    if let Some(handle) = self.handle.take() {
+                        let _ = handle.join();
+                    }
+                    events.push(HookEvent::Finished(outcome));
+                    return events;
+                }
+                Ok(event) => events.push(event),
+                Err(TryRecvError::Disconnected) => {
+                    if self.handle.take().is_some() {
+                        events.push(HookEvent::Finished(HookOutcome::Error("Hook worker stopped unexpectedly".to_string())));
+                    }
+                    return events;
+                }
+                Err(TryRecvError::Empty) => return events,
+            }
+        }
+    }
+}
+
+impl Drop for PostGenHookJob {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_quotes_paths_with_spaces() {
+        let context = HookContext {
+            gerber_dir: PathBuf::from("/boards/my board/gerber_output"),
+            pcb_path: PathBuf::from("/boards/my board/Bob's.kicad_pcb"),
+            project_name: "Bob's board".to_string(),
+            timestamp: "20240301-102233".to_string(),
+        };
+        let template = "zip -r {project_name}_{timestamp}.zip {gerber_dir} && echo {pcb_path}";
+        assert_eq!(
+            resolve_with(template, &context, quote_unix),
+            r"zip -r 'Bob'\''s board'_'20240301-102233'.zip '/boards/my board/gerber_output' && echo '/boards/my board/Bob'\''s.kicad_pcb'"
+        );
+        assert_eq!(
+            resolve_with("7z a {gerber_dir}", &HookContext { gerber_dir: PathBuf::from(r"C:\My Boards\out"), ..context.clone() }, quote_windows),
+            r#"7z a "C:\My Boards\out""#
+        );
+        assert_eq!(HookContext::new(Path::new("out"), Path::new("/x/board.kicad_pcb"), None).project_name, "board");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hook_streams_output_and_times_out() {
+        let dir = std::env::temp_dir();
+        let hook = PostGenHook { enabled: true, command: "pwd; echo oops >&2; exit 3".to_string(), timeout_secs: 10 };
+        let mut job = PostGenHookJob::spawn(&hook, &HookContext::new(&dir, Path::new("board.kicad_pcb"), None));
+        let mut events = Vec::new();
+        while !matches!(events.last(), Some(HookEvent::Finished(_))) {
+            events.extend(job.poll());
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        assert!(events.contains(&HookEvent::Stderr("oops".to_string())));
+        assert!(events.iter().any(|event| matches!(event, HookEvent::Stdout(line) if !line.is_empty())));
+        assert_eq!(events.last(), Some(&HookEvent::Finished(HookOutcome::Failed(Some(3)))));
+
+        let cancel = AtomicBool::new(false);
+        let (sender, _receiver) = mpsc::channel();
+        assert_eq!(run("sleep 5", &dir, Duration::from_millis(100), &cancel, &sender), HookOutcome::TimedOut);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_timeout_kills_processes_started_by_the_hook() {
+        let dir = std::env::temp_dir();
+        let pid_file = dir.join(format!("copperforge_hook_child_{}", std::process::id()));
+        let command = format!("sleep 30 & echo $! > {}; wait", quote_unix(&pid_file.to_string_lossy()));
+        let cancel = AtomicBool::new(false);
+        let (sender, _receiver) = mpsc::channel();
+        assert_eq!(run(&command, &dir, Duration::from_millis(300), &cancel, &sender), HookOutcome::TimedOut);
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        let _ = std::fs::remove_file(&pid_file);
+        // Gone, or a zombie waiting to be reaped by init
+        let dead = || match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            Ok(stat) => stat.rsplit(')').next().is_some_and(|rest| rest.trim_start().starts_with('Z')),
+            Err(_) => true,
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !dead() && Instant::now() < deadline {
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        assert!(dead(), "background child {} survived the timeout", pid);
+    }
+}
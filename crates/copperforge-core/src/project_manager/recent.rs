@@ -0,0 +1,112 @@
+//! Most-recently-used PCB files, kept in the config dir
+//!
+//! Pinned entries stay at the top and are never pushed out by newer ones;
+//! the remaining slots hold the most recently opened files, newest first.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File name of the list under the config dir
+pub const RECENT_PROJECTS_FILE_NAME: &str = "recent_projects.json";
+
+/// Entries kept, pinned ones included
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub pcb_path: PathBuf,
+    /// Project name from the database, else the file stem
+    pub name: String,
+    pub last_opened: DateTime<Utc>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecentProjects {
+    /// Pinned entries first, then newest first
+    pub entries: Vec<RecentProject>,
+}
+
+impl RecentProjects {
+    /// Move `pcb_path` to the top of the unpinned entries, or refresh it in place when pinned
+    pub fn record(&mut self, pcb_path: &Path, name: &str, opened: DateTime<Utc>) {
+        let pinned = match self.entries.iter().position(|entry| entry.pcb_path == pcb_path) {
+            Some(i) => self.entries.remove(i).pinned,
+            None => false,
+        };
+        self.entries.push(RecentProject { pcb_path: pcb_path.to_path_buf(), name: name.to_string(), last_opened: opened, pinned });
+        self.sort_and_trim();
+    }
+
+    pub fn toggle_pin(&mut self, pcb_path: &Path) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.pcb_path == pcb_path) {
+            entry.pinned = !entry.pinned;
+        }
+        self.sort_and_trim();
+    }
+
+    pub fn remove(&mut self, pcb_path: &Path) {
+        self.entries.retain(|entry| entry.pcb_path != pcb_path);
+    }
+
+    /// Forget every entry that is not pinned
+    pub fn clear_unpinned(&mut self) {
+        self.entries.retain(|entry| entry.pinned);
+    }
+
+    fn sort_and_trim(&mut self) {
+        self.entries.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.last_opened.cmp(&a.last_opened)));
+        let pinned = self.entries.iter().filter(|entry| entry.pinned).count();
+        self.entries.truncate(MAX_RECENT_PROJECTS.max(pinned));
+    }
+
+    /// Read the list from the config dir; a missing or unreadable file gives an empty list
+    pub fn load(config_dir: &Path) -> Self {
+        std::fs::read_to_string(config_dir.join(RECENT_PROJECTS_FILE_NAME))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(config_dir).map_err(|e| format!("Failed to create {}: {}", config_dir.display(), e))?;
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize recent projects: {}", e))?;
+        let path = config_dir.join(RECENT_PROJECTS_FILE_NAME);
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_pinned_entries_stay_on_top_and_list_is_capped() {
+        let mut recent = RecentProjects::default();
+        let at = |minute: u32| Utc.with_ymd_and_hms(2024, 5, 1, 12, minute, 0).unwrap();
+        for i in 0..12 {
+            recent.record(Path::new(&format!("/boards/b{}.kicad_pcb", i)), &format!("b{}", i), at(i));
+        }
+        assert_eq!(recent.entries.len(), MAX_RECENT_PROJECTS);
+        assert_eq!(recent.entries[0].name, "b11");
+        assert_eq!(recent.entries.last().unwrap().name, "b2");
+
+        recent.toggle_pin(Path::new("/boards/b2.kicad_pcb"));
+        recent.record(Path::new("/boards/b12.kicad_pcb"), "b12", at(12));
+        assert_eq!(recent.entries[0].name, "b2");
+        assert_eq!(recent.entries[1].name, "b12");
+        assert!(!recent.entries.iter().any(|entry| entry.name == "b3"));
+
+        // Reopening an entry moves it up but keeps it pinned
+        recent.record(Path::new("/boards/b2.kicad_pcb"), "Renamed", at(13));
+        assert!(recent.entries[0].pinned && recent.entries[0].name == "Renamed");
+
+        recent.clear_unpinned();
+        assert_eq!(recent.entries.len(), 1);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::display::GridSettings;
+use crate::drc_operations::types::DrcRules;
+use crate::ecs::LayerType;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name shown in the template dropdown for "no template"
+pub const BLANK_TEMPLATE_NAME: &str = "Blank";
+
+/// Directory name for template files under the config dir
+pub const TEMPLATES_DIR_NAME: &str = "templates";
+
+/// Color of a single layer in a template's color theme
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateLayerColor {
+    pub layer: LayerType,
+    pub rgb: [u8; 3],
+}
+
+/// Saved bundle of defaults applied when creating a project
+/// Every field has a default so older or hand-edited template files still load
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectTemplate {
+    pub name: String,
+    pub tags: Vec<String>,
+    pub description: String,
+    pub drc_rules: DrcRules,
+    pub layer_colors: Vec<TemplateLayerColor>,
+    pub grid: GridSettings,
+}
+
+impl Default for ProjectTemplate {
+    fn default() -> Self {
+        Self {
+            name: "Untitled".to_string(),
+            tags: Vec::new(),
+            description: String::new(),
+            drc_rules: DrcRules::default(),
+            layer_colors: Vec::new(),
+            grid: GridSettings::default(),
+        }
+    }
+}
+
+impl ProjectTemplate {
+    pub fn load_from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let json = std::fs::read_to_string(path)?;
+        let template: ProjectTemplate = serde_json::from_str(&json)?;
+        Ok(template)
+    }
+
+    /// Write the template as `<name>.json` into `dir`, returning the file path
+    pub fn save_to_dir(&self, dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.json", template_file_stem(&self.name)));
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    /// Merge the template's description boilerplate and tags into a new project's values
+    /// A user-entered description wins; tags are combined without duplicates
+    pub fn apply_to_new_project(&self, description: &mut String, tags: &mut Vec<String>) {
+        if description.trim().is_empty() {
+            *description = self.description.clone();
+        }
+        for tag in self.tags.iter().rev() {
+            if !tags.contains(tag) {
+                tags.insert(0, tag.clone());
+            }
+        }
+    }
+}
+
+/// Templates directory under the application config dir
+pub fn templates_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join(TEMPLATES_DIR_NAME)
+}
+
+/// Load every `*.json` template in `dir`, sorted by name
+/// Files that are not valid JSON are skipped
+pub fn load_templates(dir: &Path) -> Vec<ProjectTemplate> {
+    let mut templates: Vec<ProjectTemplate> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+                .filter_map(|path| ProjectTemplate::load_from_file(&path).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    templates.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    templates
+}
+
+/// File-system safe stem for a template name
+fn template_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if stem.is_empty() { "template".to_string() } else { stem }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_with_missing_and_unknown_fields_loads_defaults() {
+        let json = r#"{
+            "name": "Four layer",
+            "tags": ["4L"],
+            "drc_rules": { "min_trace_width": 0.1 },
+            "grid": { "spacing_mm": 1.27 },
+            "some_future_field": 42
+        }"#;
+
+        let template: ProjectTemplate = serde_json::from_str(json).expect("template should load");
+        assert_eq!(template.name, "Four layer");
+        assert_eq!(template.tags, vec!["4L".to_string()]);
+        assert!(template.description.is_empty());
+        assert!(template.layer_colors.is_empty());
+
+        assert_eq!(template.drc_rules.min_trace_width, 0.1);
+        assert_eq!(template.drc_rules.min_spacing, DrcRules::default().min_spacing);
+        assert_eq!(template.grid.spacing_mm, 1.27);
+        assert_eq!(template.grid.enabled, GridSettings::default().enabled);
+    }
+
+    #[test]
+    fn test_apply_to_new_project_merges_tags() {
+        let template = ProjectTemplate {
+            tags: vec!["rf".to_string(), "4L".to_string()],
+            description: "Impedance controlled stackup".to_string(),
+            ..ProjectTemplate::default()
+        };
+
+        let mut description = String::new();
+        let mut tags = vec!["4L".to_string(), "prototype".to_string()];
+        template.apply_to_new_project(&mut description, &mut tags);
+
+        assert_eq!(description, "Impedance controlled stackup");
+        assert_eq!(tags, vec!["rf".to_string(), "4L".to_string(), "prototype".to_string()]);
+    }
+}
@@ -0,0 +1,36 @@
+//! Helpers shared by the unit tests
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Fresh directory under the system temp dir, removed with its contents when dropped
+/// Dropped on panic too, so a failing test does not leave files behind
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `name` only makes leftovers recognisable; the path is unique per call, process and start time
+    pub fn new(name: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let started = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos()).unwrap_or_default();
+        let path = std::env::temp_dir().join(format!(
+            "copperforge_{}_{}_{}_{}",
+            name,
+            std::process::id(),
+            started,
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path).unwrap_or_else(|e| panic!("Failed to create {}: {}", path.display(), e));
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::DemoLensApp;
+use crate::ecs::{LayerType, Side};
+use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
+use egui_mobius_reactive::Dynamic;
+
+const HISTOGRAM_BAR_WIDTH: f32 = 160.0;
+const SMALL_APERTURE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 100, 100);
+
+pub fn show_analysis_panel<'a>(
+    ui: &mut egui::Ui,
+    app: &'a mut DemoLensApp,
+    logger_state: &'a Dynamic<ReactiveEventLoggerState>,
+    log_colors: &'a Dynamic<LogColors>,
+) {
+    let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
+
+    ui.heading("Assembly Analysis");
+    ui.separator();
+
+    ui.horizontal(|ui| {
+        ui.label("Min paste aperture area:");
+        let response = ui.add(
+            egui::DragValue::new(&mut app.drc_manager.rules.min_paste_aperture_area)
+                .speed(0.01)
+                .range(0.0..=10.0)
+                .suffix(" mm²"),
+        );
+        if response.drag_stopped() || response.lost_focus() {
+            logger.log_info(&format!(
+                "Minimum paste aperture area set to {:.3} mm²",
+                app.drc_manager.rules.min_paste_aperture_area
+            ));
+        }
+    });
+    ui.add_space(8.0);
+
+    for side in [Side::Top, Side::Bottom] {
+        let layer_type = LayerType::Paste(side);
+        let flashes = crate::ecs::get_flash_primitives(&mut app.ecs_world, layer_type);
+        let min_area = app.drc_manager.rules.min_paste_aperture_area as f64;
+
+        ui.group(|ui| {
+            ui.label(egui::RichText::new(format!("{} Aperture Histogram", layer_type.display_name())).strong());
+
+            if flashes.is_empty() {
+                ui.label("No flashed apertures loaded");
+                return;
+            }
+
+            let histogram = crate::ecs::paste_aperture_histogram(&flashes);
+            let unknown = flashes.iter().filter(|flash| flash.area() <= 0.0).count();
+            let small: usize = histogram.iter().filter(|(area, _)| *area < min_area).map(|(_, count)| count).sum();
+            let max_count = histogram.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+
+            ui.label(format!("{} apertures, {} distinct sizes", flashes.len(), histogram.len()));
+
+            egui::Grid::new(format!("paste_histogram_{:?}", side))
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Area (mm²)");
+                    ui.label("Count");
+                    ui.label("");
+                    ui.end_row();
+
+                    for (area, count) in &histogram {
+                        let too_small = *area < min_area;
+                        let color = if too_small { SMALL_APERTURE_COLOR } else { ui.visuals().selection.bg_fill };
+
+                        let area_text = egui::RichText::new(format!("{:.3}", area));
+                        if too_small {
+                            ui.label(area_text.color(SMALL_APERTURE_COLOR))
+                                .on_hover_text("Below minimum paste aperture area: solder defect risk");
+                        } else {
+                            ui.label(area_text);
+                        }
+                        ui.label(count.to_string());
+
+                        let (rect, _) = ui.allocate_exact_size(egui::vec2(HISTOGRAM_BAR_WIDTH, 12.0), egui::Sense::hover());
+                        let bar_width = HISTOGRAM_BAR_WIDTH * *count as f32 / max_count as f32;
+                        ui.painter().rect_filled(
+                            egui::Rect::from_min_size(rect.min, egui::vec2(bar_width, rect.height())),
+                            2.0,
+                            color,
+                        );
+                        ui.end_row();
+                    }
+                });
+
+            if small > 0 {
+                ui.colored_label(
+                    SMALL_APERTURE_COLOR,
+                    format!("⚠ {} aperture(s) below {:.3} mm²", small, min_area),
+                );
+            }
+            if unknown > 0 {
+                ui.label(format!("{} macro aperture(s) not measured", unknown));
+            }
+        });
+        ui.add_space(8.0);
+    }
+}
@@ -0,0 +1,355 @@
+use egui::{Color32, Painter, Pos2, Rect, Stroke, Vec2};
+use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
+use egui_mobius_reactive::Dynamic;
+use nalgebra::{Matrix3, Point2};
+
+use crate::DemoLensApp;
+use crate::project_manager::annotation::{arrow_head, Annotation, AnnotationKind};
+use crate::ui::i18n::{tr, tr_args};
+
+const DEFAULT_ANNOTATION_COLOR: Color32 = Color32::from_rgb(255, 120, 40);
+const NOTE_MARKER_RADIUS: f32 = 6.0;
+const ARROW_HEAD_PX: f64 = 10.0;
+/// Resolved annotations are drawn faded
+const RESOLVED_ALPHA: f32 = 0.35;
+/// Smallest board extent framed when zooming to an annotation (mm)
+const MIN_FOCUS_SIZE_MM: f64 = 5.0;
+
+/// Review notes, arrows and rectangles on the board (list persisted per project)
+pub struct AnnotationState {
+    /// Kind placed by clicks in the gerber view while the tool is on
+    pub tool: Option<AnnotationKind>,
+    pub annotations: Vec<Annotation>,
+    pub color: Color32,
+    pub selected: Option<usize>,
+    pub hide_resolved: bool,
+    /// Draw the shown annotations into PNG and SVG exports
+    pub include_in_exports: bool,
+    /// First click of an arrow or rectangle, in board coordinates
+    pending_start: Option<Point2<f64>>,
+    /// Annotation to frame once the gerber view is drawn
+    pending_focus: Option<usize>,
+    // Project the list was loaded from / is saved to
+    project_id: Option<String>,
+}
+
+impl Default for AnnotationState {
+    fn default() -> Self {
+        Self {
+            tool: None,
+            annotations: Vec::new(),
+            color: DEFAULT_ANNOTATION_COLOR,
+            selected: None,
+            hide_resolved: false,
+            include_in_exports: true,
+            pending_start: None,
+            pending_focus: None,
+            project_id: None,
+        }
+    }
+}
+
+impl AnnotationState {
+    fn shown(&self) -> impl Iterator<Item = (usize, &Annotation)> {
+        self.annotations.iter().enumerate().filter(|(_, annotation)| !(self.hide_resolved && annotation.resolved))
+    }
+
+    /// Annotations to draw into an export: the shown ones, or none when left out
+    pub fn exported(&self) -> Vec<Annotation> {
+        if !self.include_in_exports {
+            return Vec::new();
+        }
+        self.shown().map(|(_, annotation)| annotation.clone()).collect()
+    }
+}
+
+/// Keep the annotation list in step with the open project
+/// Called every frame; loads the new project's annotations when the project changes.
+/// Annotations made with no project open are kept in memory and join the next project opened.
+pub fn sync_annotation_project(app: &mut DemoLensApp) {
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let current_id = state.current_project.as_ref().map(|project| project.metadata.id.clone());
+    if current_id == app.annotations.project_id {
+        return;
+    }
+
+    let unsaved = match app.annotations.project_id {
+        None => std::mem::take(&mut app.annotations.annotations),
+        Some(_) => Vec::new(),
+    };
+    app.annotations.annotations = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_annotations(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load annotations: {}", e);
+            Vec::new()
+        }),
+        _ => Vec::new(),
+    };
+    app.annotations.selected = None;
+    app.annotations.pending_start = None;
+    app.annotations.project_id = current_id;
+
+    if !unsaved.is_empty() {
+        let count = unsaved.len();
+        app.annotations.annotations.extend(unsaved);
+        save_annotations(app);
+        let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+        logger.log_info(&format!("Added {} unsaved annotation(s) to the opened project", count));
+    }
+}
+
+/// Store the annotation list with the open project, if any
+fn save_annotations(app: &DemoLensApp) {
+    let (Some(project_id), Some(state)) = (&app.annotations.project_id, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        if let Err(e) = database.save_annotations(project_id, &app.annotations.annotations) {
+            eprintln!("Failed to save annotations: {}", e);
+        }
+    }
+}
+
+/// Matrix from source gerber coordinates to view coordinates (rotation, mirroring, image transform)
+fn board_to_view_matrix(app: &mut DemoLensApp) -> Matrix3<f64> {
+    crate::ecs::get_board_view_transform(&mut app.ecs_world, &app.display_manager)
+        .map(|transform| transform.to_matrix())
+        .unwrap_or_else(Matrix3::identity)
+}
+
+fn color_array(color: Color32) -> [u8; 4] {
+    color.to_srgba_unmultiplied()
+}
+
+fn annotation_color(annotation: &Annotation) -> Color32 {
+    let [r, g, b, a] = annotation.color;
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Place the active kind at a clicked view position (as reported by `screen_to_gerber_coords`)
+/// Notes go down at once; arrows and rectangles take a second click for their end
+pub fn handle_click(app: &mut DemoLensApp, view_pos: Point2<f64>) {
+    let Some(kind) = app.annotations.tool else {
+        return;
+    };
+    let Some(view_to_board) = board_to_view_matrix(app).try_inverse() else {
+        return;
+    };
+    let position = view_to_board.transform_point(&view_pos);
+    let start = match (kind.needs_end(), app.annotations.pending_start.take()) {
+        (true, None) => {
+            app.annotations.pending_start = Some(position);
+            return;
+        }
+        (true, Some(start)) => start,
+        (false, _) => position,
+    };
+
+    let color = color_array(app.annotations.color);
+    app.annotations.annotations.push(Annotation::new(kind, start, position, color));
+    app.annotations.selected = Some(app.annotations.annotations.len() - 1);
+    save_annotations(app);
+    // The text is typed in the list
+    app.open_tab_request = Some(crate::ui::TabKind::Annotations);
+
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    logger.log_info(&format!("Added annotation {}", app.annotations.annotations.len()));
+}
+
+/// Toolbar buttons choosing the kind placed by clicks, and the color of new annotations
+pub fn render_annotation_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let mut tool = app.annotations.tool;
+    for kind in AnnotationKind::ALL {
+        let key = match kind {
+            AnnotationKind::Note => "annotations.note",
+            AnnotationKind::Arrow => "annotations.arrow",
+            AnnotationKind::Rectangle => "annotations.rectangle",
+        };
+        let selected = tool == Some(kind);
+        if ui.selectable_label(selected, kind.icon()).on_hover_text(tr(key)).clicked() {
+            tool = if selected { None } else { Some(kind) };
+        }
+    }
+    ui.color_edit_button_srgba(&mut app.annotations.color).on_hover_text(tr("annotations.color_hint"));
+
+    if tool != app.annotations.tool {
+        app.annotations.tool = tool;
+        app.annotations.pending_start = None;
+        if tool.is_some() {
+            // Clicks can only go to one tool at a time
+            app.ruler_active = false;
+            app.ruler_start = None;
+            app.ruler_end = None;
+            app.setting_origin_mode = false;
+            app.point_picker.active = false;
+        }
+    }
+}
+
+/// Draw the shown annotations, and the arrow or rectangle being placed up to the cursor
+pub fn render_annotations(app: &mut DemoLensApp, painter: &Painter) {
+    // The checkbox leaves annotations out of "Save view" and "Copy view" screenshots too
+    if app.view_clipboard.capturing() && !app.annotations.include_in_exports {
+        return;
+    }
+    let board_to_view = board_to_view_matrix(app);
+    let to_screen = |point: &Point2<f64>| app.view_state.gerber_to_screen_coords(*point);
+
+    for (index, annotation) in app.annotations.shown() {
+        let mut color = annotation_color(annotation);
+        if annotation.resolved {
+            color = color.gamma_multiply(RESOLVED_ALPHA);
+        }
+        let width = if app.annotations.selected == Some(index) { 3.0 } else { 2.0 };
+        let points: Vec<Pos2> = annotation.outline(&board_to_view).iter().map(to_screen).collect();
+        paint_shape(painter, annotation.kind, &points, Stroke::new(width, color));
+
+        let label = annotation.label();
+        if let Some(anchor) = points.first().filter(|_| !label.is_empty()) {
+            painter.text(*anchor + Vec2::new(8.0, -4.0), egui::Align2::LEFT_BOTTOM, label, egui::FontId::proportional(12.0), color);
+        }
+    }
+
+    // Rubber band from the first click
+    if let (Some(kind), Some(start)) = (app.annotations.tool, app.annotations.pending_start) {
+        if let Some(cursor) = painter.ctx().input(|i| i.pointer.hover_pos()) {
+            let start = to_screen(&board_to_view.transform_point(&start));
+            let points = match kind {
+                AnnotationKind::Rectangle => vec![start, Pos2::new(cursor.x, start.y), cursor, Pos2::new(start.x, cursor.y)],
+                _ => vec![start, cursor],
+            };
+            paint_shape(painter, kind, &points, Stroke::new(1.5, app.annotations.color.gamma_multiply(0.7)));
+        }
+    }
+}
+
+/// One annotation from its screen outline (see `Annotation::outline`)
+fn paint_shape(painter: &Painter, kind: AnnotationKind, points: &[Pos2], stroke: Stroke) {
+    match (kind, points) {
+        (AnnotationKind::Note, [center]) => {
+            painter.circle(*center, NOTE_MARKER_RADIUS, stroke.color.gamma_multiply(0.6), stroke);
+        }
+        (AnnotationKind::Arrow, [tail, tip]) => {
+            painter.line_segment([*tail, *tip], stroke);
+            let point = |pos: &Pos2| Point2::new(pos.x as f64, pos.y as f64);
+            if let Some(head) = arrow_head(point(tail), point(tip), ARROW_HEAD_PX) {
+                for end in head {
+                    painter.line_segment([*tip, Pos2::new(end.x as f32, end.y as f32)], stroke);
+                }
+            }
+        }
+        (_, points) => {
+            painter.add(egui::Shape::convex_polygon(points.to_vec(), stroke.color.gamma_multiply(0.15), stroke));
+        }
+    }
+}
+
+/// Frame the annotation clicked in the list
+pub fn focus_pending_annotation(app: &mut DemoLensApp, viewport: &Rect) {
+    let Some(annotation) = app.annotations.pending_focus.take().and_then(|index| app.annotations.annotations.get(index).cloned()) else {
+        return;
+    };
+    let (min, max) = annotation.bounds(&board_to_view_matrix(app));
+    let size = (max.x - min.x).max(max.y - min.y).max(MIN_FOCUS_SIZE_MM);
+    let center = nalgebra::center(&min, &max);
+
+    app.view_state.scale = (viewport.width().min(viewport.height()) as f64 * 0.5 / size) as f32;
+    app.view_state.translation = viewport.center().to_vec2()
+        - Vec2::new(center.x as f32 * app.view_state.scale, -center.y as f32 * app.view_state.scale);
+    app.sync_zoom_to_ecs();
+}
+
+/// Panel listing the annotations like review comments: text, color, resolved state, zoom and delete
+pub fn show_annotations_panel<'a>(
+    ui: &mut egui::Ui,
+    app: &'a mut DemoLensApp,
+    logger_state: &'a Dynamic<ReactiveEventLoggerState>,
+    log_colors: &'a Dynamic<LogColors>,
+) {
+    let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
+
+    ui.heading(tr("annotations.title"));
+    ui.horizontal(|ui| {
+        render_annotation_controls(ui, app);
+    });
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut app.annotations.hide_resolved, tr("annotations.hide_resolved"));
+        ui.checkbox(&mut app.annotations.include_in_exports, tr("annotations.include_in_exports"))
+            .on_hover_text(tr("annotations.include_in_exports_hint"));
+    });
+    let resolved = app.annotations.annotations.iter().filter(|annotation| annotation.resolved).count();
+    let open = app.annotations.annotations.len() - resolved;
+    ui.horizontal(|ui| {
+        ui.label(tr_args("annotations.summary", &[("open", &open), ("resolved", &resolved)]));
+        if ui.add_enabled(resolved > 0, egui::Button::new(tr("annotations.delete_resolved"))).clicked() {
+            app.annotations.annotations.retain(|annotation| !annotation.resolved);
+            app.annotations.selected = None;
+            save_annotations(app);
+            logger.log_info(&format!("Deleted {} resolved annotation(s)", resolved));
+        }
+    });
+    if app.annotations.project_id.is_none() && !app.annotations.annotations.is_empty() {
+        ui.colored_label(ui.visuals().warn_fg_color, tr("annotations.unsaved"));
+    }
+    ui.separator();
+
+    if app.annotations.annotations.is_empty() {
+        ui.label(tr("annotations.empty"));
+        return;
+    }
+
+    let mut changed = false;
+    let mut remove = None;
+    let mut focus = None;
+    let shown: Vec<usize> = app.annotations.shown().map(|(index, _)| index).collect();
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for index in shown {
+            let selected = app.annotations.selected == Some(index);
+            let annotation = &mut app.annotations.annotations[index];
+            ui.horizontal(|ui| {
+                if ui.selectable_label(selected, format!("{} {}", index + 1, annotation.kind.icon()))
+                    .on_hover_text(tr("annotations.zoom_hint"))
+                    .clicked()
+                {
+                    focus = Some(index);
+                }
+                let mut color = annotation_color(annotation);
+                if ui.color_edit_button_srgba(&mut color).changed() {
+                    annotation.color = color_array(color);
+                    changed = true;
+                }
+                changed |= ui.checkbox(&mut annotation.resolved, tr("annotations.resolved")).changed();
+                if ui.small_button("🗑").on_hover_text(tr("annotations.delete")).clicked() {
+                    remove = Some(index);
+                }
+            });
+            let response = ui.add(
+                egui::TextEdit::multiline(&mut annotation.text)
+                    .desired_rows(1)
+                    .desired_width(f32::INFINITY)
+                    .hint_text(tr("annotations.text_hint")),
+            );
+            if response.gained_focus() {
+                app.annotations.selected = Some(index);
+            }
+            changed |= response.changed();
+            ui.separator();
+        }
+    });
+
+    if let Some(index) = focus {
+        app.annotations.selected = Some(index);
+        app.annotations.pending_focus = Some(index);
+        app.open_tab_request = Some(crate::ui::TabKind::GerberView);
+    }
+    if let Some(index) = remove {
+        app.annotations.annotations.remove(index);
+        app.annotations.selected = None;
+        changed = true;
+        logger.log_info(&format!("Deleted annotation {}", index + 1));
+    }
+    if changed {
+        save_annotations(app);
+    }
+}
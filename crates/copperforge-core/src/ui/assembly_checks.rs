@@ -0,0 +1,528 @@
+use std::collections::BTreeSet;
+
+use egui_lens::ReactiveEventLogger;
+use nalgebra::Point2;
+
+use crate::DemoLensApp;
+use crate::drc_operations::assembly::{AssemblyIssue, PlacedComponent, check_component_overlaps};
+use crate::drc_operations::tenting::{self, Tenting, ViaTenting};
+use crate::drc_operations::thermal::{self, PadThermal};
+use crate::ecs::{AdjustedPaste, LayerType, PasteAdjustSettings, PasteReduction, Side, MIN_APERTURE_MM};
+use crate::project::constants::LOG_TYPE_DRC;
+use crate::ui::i18n::{tr, tr_args};
+use crate::ui::unit_value::UnitValue;
+use crate::project_manager::bom::BomComponent;
+
+/// Placement overlap results and the pairs acknowledged for the open project
+#[derive(Default)]
+pub struct AssemblyCheckState {
+    pub issues: Vec<AssemblyIssue>,
+    /// Components that could not be sized from their footprint or silkscreen
+    pub unknown_size: Vec<String>,
+    /// Pair keys (see `AssemblyIssue::pair_key`) hidden from the list
+    pub ignored: BTreeSet<String>,
+    pub show_ignored: bool,
+    /// Direct-connected pads found by the thermal relief heuristic
+    pub thermal_pads: Vec<PadThermal>,
+    /// Thermal pad to zoom to on the next frame
+    pending_thermal_focus: Option<usize>,
+    /// Vias classified by the tenting check
+    pub vias: Vec<ViaTenting>,
+    /// Color the via markers by tenting on the board
+    pub show_via_tenting: bool,
+    // Project the ignore list was loaded from / is saved to
+    project_id: Option<String>,
+}
+
+/// Keep the ignore list and paste preview settings in step with the open project
+/// Called every frame; loads the new project's list and settings and drops stale results when the project changes
+pub fn sync_assembly_check_project(app: &mut DemoLensApp) {
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let current_id = state.current_project.as_ref().map(|project| project.metadata.id.clone());
+    if current_id == app.assembly_checks.project_id {
+        return;
+    }
+
+    let ignored = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_assembly_ignores(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load ignored assembly issues: {}", e);
+            Vec::new()
+        }),
+        _ => Vec::new(),
+    };
+    let paste_adjust = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_paste_adjust(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load paste preview settings: {}", e);
+            None
+        }),
+        _ => None,
+    };
+    app.ecs_world.insert_resource(paste_adjust.unwrap_or_default());
+    app.assembly_checks = AssemblyCheckState {
+        ignored: ignored.into_iter().collect(),
+        show_ignored: app.assembly_checks.show_ignored,
+        show_via_tenting: app.assembly_checks.show_via_tenting,
+        project_id: current_id,
+        ..Default::default()
+    };
+}
+
+/// Store the ignore list with the open project, if any
+fn save_ignores(app: &DemoLensApp) {
+    let (Some(project_id), Some(state)) = (&app.assembly_checks.project_id, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        let pair_keys: Vec<String> = app.assembly_checks.ignored.iter().cloned().collect();
+        if let Err(e) = database.save_assembly_ignores(project_id, &pair_keys) {
+            eprintln!("Failed to save ignored assembly issues: {}", e);
+        }
+    }
+}
+
+/// Store the paste preview settings with the open project, if any
+fn save_paste_adjust(app: &DemoLensApp, settings: &PasteAdjustSettings) {
+    let (Some(project_id), Some(state)) = (&app.assembly_checks.project_id, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        if let Err(e) = database.save_paste_adjust(project_id, settings) {
+            eprintln!("Failed to save paste preview settings: {}", e);
+        }
+    }
+}
+
+fn placed_component(component: &BomComponent) -> PlacedComponent {
+    PlacedComponent {
+        reference: component.reference.clone(),
+        footprint: component.footprint.clone(),
+        side: if component.layer.starts_with("B.") { Side::Bottom } else { Side::Top },
+        x: component.x_location,
+        y: component.y_location,
+        rotation: component.orientation,
+    }
+}
+
+fn run_assembly_checks(app: &mut DemoLensApp, components: &[BomComponent], logger: &ReactiveEventLogger) {
+    let placed: Vec<PlacedComponent> = components.iter().map(placed_component).collect();
+    let clearance = app.drc_manager.rules.min_component_clearance as f64;
+
+    // Silkscreen is in gerber coordinates (y up), placements in KiCad coordinates (y down)
+    let world = &mut app.ecs_world;
+    let mut silkscreen = |side: Side| -> Vec<(Point2<f64>, Point2<f64>)> {
+        crate::ecs::get_layer_primitives(world, LayerType::Silkscreen(side))
+            .iter()
+            .map(|primitive| {
+                let (min, max) = primitive.shape.bounds();
+                (Point2::new(min.x, -max.y), Point2::new(max.x, -min.y))
+            })
+            .collect()
+    };
+    let result = check_component_overlaps(&placed, clearance, &mut silkscreen);
+
+    let ignored = result.issues.iter().filter(|issue| app.assembly_checks.ignored.contains(&issue.pair_key())).count();
+    logger.log_custom(LOG_TYPE_DRC, &format!(
+        "Assembly checks on {} components: {} issue(s), {} ignored",
+        placed.len(),
+        result.issues.len() - ignored,
+        ignored
+    ));
+    if !result.unknown_size.is_empty() {
+        logger.log_warning(&format!("No outline for {}: unknown package and no silkscreen nearby", result.unknown_size.join(", ")));
+    }
+    app.assembly_checks.issues = result.issues;
+    app.assembly_checks.unknown_size = result.unknown_size;
+}
+
+/// Component clearance setting, run button and the list of overlapping pairs
+pub fn show_assembly_checks_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    egui::CollapsingHeader::new(tr("assembly.heading"))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.add_space(4.0);
+            let rules = &mut app.drc_manager.rules;
+            ui.horizontal(|ui| {
+                ui.label(tr("assembly.min_component_clearance"));
+                let unit = rules.display_unit();
+                ui.add(UnitValue::new(&mut rules.min_component_clearance, unit).range_mm(0.0..=5.0));
+            }).response.on_hover_text(tr("assembly.min_component_clearance_hint"));
+
+            let components = app.bom_state.as_ref()
+                .map(|bom_state| bom_state.components.lock().unwrap().clone())
+                .unwrap_or_default();
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!components.is_empty(), egui::Button::new(tr("assembly.run"))
+                    .on_disabled_hover_text(tr("assembly.run_disabled_hint"))
+                    .clicked()
+                {
+                    run_assembly_checks(app, &components, logger);
+                }
+                ui.checkbox(&mut app.assembly_checks.show_ignored, tr("assembly.show_ignored"));
+            });
+
+            let state = &app.assembly_checks;
+            let visible: Vec<&AssemblyIssue> = state.issues.iter()
+                .filter(|issue| state.show_ignored || !state.ignored.contains(&issue.pair_key()))
+                .collect();
+            let active = state.issues.iter().filter(|issue| !state.ignored.contains(&issue.pair_key())).count();
+            let hidden = state.issues.len() - active;
+            let summary_key = if active == 1 { "assembly.issue_one" } else { "assembly.issues" };
+            ui.label(egui::RichText::new(tr_args(summary_key, &[("count", &active), ("ignored", &hidden)]))
+                .color(if active > 0 { egui::Color32::from_rgb(230, 126, 34) } else { egui::Color32::from_rgb(46, 204, 113) }));
+
+            let unit = app.drc_manager.rules.display_unit();
+            let format_length = |mm: f64| unit.format_mm(mm, 3);
+            let mut toggled = None;
+            let mut zoom_to = None;
+            egui::ScrollArea::vertical()
+                .id_salt("assembly_issues")
+                .max_height(180.0)
+                .show(ui, |ui| {
+                    for issue in &visible {
+                        let key = issue.pair_key();
+                        ui.horizontal(|ui| {
+                            let mut ignore = state.ignored.contains(&key);
+                            if ui.checkbox(&mut ignore, "").on_hover_text(tr("assembly.ignore_pair_hint")).changed() {
+                                toggled = Some((key.clone(), ignore));
+                            }
+                            let side = if issue.side == Side::Top { tr("assembly.side_top") } else { tr("assembly.side_bottom") };
+                            let detail = if issue.overlap > 0.0 {
+                                tr_args("assembly.overlap", &[("length", &format_length(issue.overlap))])
+                            } else {
+                                tr_args("assembly.apart", &[("length", &format_length(issue.spacing))])
+                            };
+                            if ui.link(format!("{} ↔ {}", issue.ref_a, issue.ref_b))
+                                .on_hover_text(tr("assembly.zoom_pair_hint"))
+                                .clicked()
+                            {
+                                zoom_to = Some((*issue).clone());
+                            }
+                            ui.label(egui::RichText::new(format!("{} ({})", detail, side)).color(egui::Color32::GRAY));
+                        });
+                    }
+                });
+            if !state.unknown_size.is_empty() {
+                ui.label(egui::RichText::new(tr_args("assembly.unknown_size", &[("refs", &state.unknown_size.join(", "))]))
+                    .color(egui::Color32::GRAY));
+            }
+
+            if let Some((key, ignore)) = toggled {
+                if ignore {
+                    app.assembly_checks.ignored.insert(key);
+                } else {
+                    app.assembly_checks.ignored.remove(&key);
+                }
+                save_ignores(app);
+            }
+            if let Some(issue) = zoom_to {
+                // Goes through the cross-probe path so it is centered in the viewport
+                let mut target = BomComponent::new(format!("{}/{}", issue.ref_a, issue.ref_b));
+                target.x_location = issue.x;
+                target.y_location = issue.y;
+                *app.pending_cross_probe.lock().unwrap() = Some(target);
+            }
+
+            ui.add_space(8.0);
+            show_thermal_relief_checks(ui, app, logger);
+            ui.add_space(8.0);
+            show_via_tenting_checks(ui, app, logger);
+            ui.add_space(8.0);
+            show_paste_preview(ui, app, logger);
+        });
+}
+
+fn run_thermal_checks(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let pads = thermal::check_loaded_copper(&mut app.ecs_world, &app.drc_manager.rules);
+    let through_hole = pads.iter().filter(|pad| pad.through_hole).count();
+    logger.log_custom(LOG_TYPE_DRC, &format!(
+        "Thermal relief check: {} direct-connected pad(s) in pours ({} through-hole)",
+        pads.len(),
+        through_hole
+    ));
+    app.assembly_checks.thermal_pads = pads;
+    app.assembly_checks.pending_thermal_focus = None;
+}
+
+/// Ring distance and coverage thresholds of the thermal relief heuristic, and its candidates
+fn show_thermal_relief_checks(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.label(egui::RichText::new(tr("assembly.thermal_heading")).strong());
+    let rules = &mut app.drc_manager.rules;
+    let unit = rules.display_unit();
+    ui.horizontal(|ui| {
+        ui.label(tr("assembly.ring_distance"));
+        ui.add(UnitValue::new(&mut rules.thermal_ring_distance, unit).range_mm(0.01..=2.0).speed_mm(0.01));
+    }).response.on_hover_text(tr("assembly.ring_distance_hint"));
+    ui.horizontal(|ui| {
+        ui.label(tr("assembly.direct_at"));
+        let mut direct = rules.thermal_direct_coverage * 100.0;
+        if ui.add(egui::DragValue::new(&mut direct).range(1.0..=100.0).speed(1.0).suffix(" %")).changed() {
+            rules.thermal_direct_coverage = direct / 100.0;
+        }
+        ui.label(tr("assembly.isolated_below"));
+        let mut isolated = rules.thermal_min_coverage * 100.0;
+        if ui.add(egui::DragValue::new(&mut isolated).range(0.0..=100.0).speed(1.0).suffix(" %")).changed() {
+            rules.thermal_min_coverage = isolated / 100.0;
+        }
+    }).response.on_hover_text(tr("assembly.coverage_hint"));
+    rules.thermal_min_coverage = rules.thermal_min_coverage.min(rules.thermal_direct_coverage);
+
+    ui.horizontal(|ui| {
+        if ui.button(tr("assembly.find_direct")).clicked() {
+            run_thermal_checks(app, logger);
+        }
+        let count = app.assembly_checks.thermal_pads.len();
+        let candidates_key = if count == 1 { "assembly.candidate_one" } else { "assembly.candidates" };
+        ui.label(egui::RichText::new(tr_args(candidates_key, &[("count", &count)]))
+            .color(if count > 0 { egui::Color32::from_rgb(230, 126, 34) } else { egui::Color32::from_rgb(46, 204, 113) }));
+    });
+
+    let unit = app.drc_manager.rules.display_unit();
+    let mut focus = None;
+    egui::ScrollArea::vertical()
+        .id_salt("thermal_pads")
+        .max_height(150.0)
+        .show(ui, |ui| {
+            for (i, pad) in app.assembly_checks.thermal_pads.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let position = format!(
+                        "T{} {} ({}, {})",
+                        i + 1,
+                        pad.layer.display_name(),
+                        unit.format_mm(pad.position.x, 3),
+                        unit.format_mm(pad.position.y, 3),
+                    );
+                    if ui.link(position).on_hover_text(tr("assembly.zoom_pad_hint")).clicked() {
+                        focus = Some(i);
+                    }
+                    ui.label(egui::RichText::new(pad_description(pad)).color(egui::Color32::GRAY));
+                });
+            }
+        });
+    if focus.is_some() {
+        app.assembly_checks.pending_thermal_focus = focus;
+    }
+    ui.label(egui::RichText::new(tr("assembly.thermal_heuristic")).small().color(egui::Color32::GRAY));
+}
+
+fn run_tenting_checks(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    match tenting::check_loaded_vias(&mut app.ecs_world, &app.drc_manager.rules) {
+        Ok(vias) => {
+            let exposed = vias.iter().filter(|via| via.tenting.is_exposed()).count();
+            logger.log_custom(LOG_TYPE_DRC, &format!("Via tenting: {} via(s), {} exposed on at least one side", vias.len(), exposed));
+            app.assembly_checks.vias = vias;
+        }
+        Err(e) => {
+            logger.log_warning(&format!("Via tenting: {}", e));
+            app.assembly_checks.vias.clear();
+        }
+    }
+}
+
+fn export_exposed_vias(app: &DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .set_file_name("exposed_vias.csv")
+        .save_file()
+    else {
+        return;
+    };
+    match std::fs::write(&path, tenting::exposed_vias_csv(&app.assembly_checks.vias)) {
+        Ok(()) => logger.log_info(&format!("Exported exposed vias to {}", path.display())),
+        Err(e) => logger.log_error(&format!("Failed to write {}: {}", path.display(), e)),
+    }
+}
+
+/// Via size limit and mask matching tolerance of the tenting check, and the counts per class
+fn show_via_tenting_checks(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.label(egui::RichText::new(tr("assembly.tenting_heading")).strong());
+    let rules = &mut app.drc_manager.rules;
+    let unit = rules.display_unit();
+    ui.horizontal(|ui| {
+        ui.label(tr("assembly.max_via_drill"));
+        ui.add(UnitValue::new(&mut rules.via_max_drill, unit).range_mm(0.05..=2.0).speed_mm(0.01));
+    }).response.on_hover_text(tr("assembly.max_via_drill_hint"));
+    ui.horizontal(|ui| {
+        ui.label(tr("assembly.mask_tolerance"));
+        ui.add(UnitValue::new(&mut rules.via_mask_tolerance, unit).range_mm(0.0..=1.0).speed_mm(0.005));
+    }).response.on_hover_text(tr("assembly.mask_tolerance_hint"));
+
+    ui.horizontal(|ui| {
+        if ui.button(tr("assembly.classify_vias")).on_hover_text(tr("assembly.classify_vias_hint")).clicked() {
+            run_tenting_checks(app, logger);
+        }
+        ui.checkbox(&mut app.assembly_checks.show_via_tenting, tr("assembly.show_on_board"))
+            .on_hover_text(tr("assembly.show_on_board_hint"));
+    });
+
+    let vias = &app.assembly_checks.vias;
+    if !vias.is_empty() {
+        for tenting in Tenting::ALL {
+            let count = vias.iter().filter(|via| via.tenting == tenting).count();
+            ui.label(egui::RichText::new(format!("{}: {}", tenting_label(tenting), count)).color(tenting_color(tenting)));
+        }
+        let exposed = vias.iter().any(|via| via.tenting.is_exposed());
+        if ui.add_enabled(exposed, egui::Button::new(tr("assembly.export_exposed"))).clicked() {
+            export_exposed_vias(app, logger);
+        }
+    }
+}
+
+/// Write the adjusted paste apertures of one side as a gerber for the stencil maker
+fn export_adjusted_paste(side: Side, adjusted: &AdjustedPaste, logger: &ReactiveEventLogger) {
+    let (suffix, file_function) = crate::export::gerber::layer_file_suffix(LayerType::Paste(side));
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Gerber", &["gbr"])
+        .set_file_name(format!("{}_adjusted.gbr", suffix))
+        .save_file()
+    else {
+        return;
+    };
+    // Source coordinates, like the loaded paste file
+    let identity = gerber_viewer::GerberTransform::from_matrix(&nalgebra::Matrix3::identity());
+    let result = crate::export::gerber::write_transformed_gerber(&adjusted.primitives, &identity, Some(&file_function));
+    match std::fs::write(&path, result.content) {
+        Ok(()) => {
+            logger.log_info(&format!("Exported adjusted {} to {}", LayerType::Paste(side).display_name(), path.display()));
+            if result.skipped_flashes > 0 {
+                logger.log_warning(&format!("{} flash(es) of undefined apertures could not be written", result.skipped_flashes));
+            }
+        }
+        Err(e) => logger.log_error(&format!("Failed to write {}: {}", path.display(), e)),
+    }
+    if !adjusted.clamped.is_empty() {
+        logger.log_warning(&format!("{} paste aperture(s) were clamped to {} mm", adjusted.clamped.len(), MIN_APERTURE_MM));
+    }
+}
+
+/// Paste reduction amount, the area summary per paste layer, clamped apertures and the export
+fn show_paste_preview(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.label(egui::RichText::new(tr("assembly.paste_heading")).strong());
+    let unit = app.drc_manager.rules.display_unit();
+    let mut settings = *app.ecs_world.resource::<PasteAdjustSettings>();
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut settings.enabled, tr("assembly.shrink_paste"))
+            .on_hover_text(tr("assembly.shrink_paste_hint"));
+        egui::ComboBox::from_id_salt("paste_reduction")
+            .selected_text(match settings.reduction {
+                PasteReduction::Percent => "%",
+                PasteReduction::PerSide => tr("assembly.per_side"),
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.reduction, PasteReduction::Percent, "%");
+                ui.selectable_value(&mut settings.reduction, PasteReduction::PerSide, tr("assembly.per_side"));
+            });
+        match settings.reduction {
+            PasteReduction::Percent => {
+                ui.add(egui::DragValue::new(&mut settings.amount).range(-50.0..=100.0).speed(0.5).suffix(" %"));
+            }
+            PasteReduction::PerSide => {
+                ui.add(UnitValue::new(&mut settings.amount, unit).range_mm(-0.5..=0.5).speed_mm(0.005));
+            }
+        }
+    }).response.on_hover_text(tr("assembly.paste_amount_hint"));
+    if settings.reduction == PasteReduction::Percent {
+        settings.amount = settings.amount.clamp(-50.0, 100.0);
+    }
+    if settings != *app.ecs_world.resource::<PasteAdjustSettings>() {
+        app.ecs_world.insert_resource(settings);
+        save_paste_adjust(app, &settings);
+    }
+    if !settings.enabled {
+        return;
+    }
+
+    // Computed before drawing; refreshed here so the summary follows the settings at once
+    crate::ecs::update_paste_preview(&mut app.ecs_world);
+    let mut export = None;
+    for side in [Side::Top, Side::Bottom] {
+        let Some(adjusted) = crate::ecs::get_adjusted_paste(&mut app.ecs_world, side) else {
+            continue;
+        };
+        let change = if adjusted.original_area > 0.0 { (adjusted.adjusted_area / adjusted.original_area - 1.0) * 100.0 } else { 0.0 };
+        ui.horizontal(|ui| {
+            ui.label(tr_args("assembly.paste_area", &[
+                ("layer", &LayerType::Paste(side).display_name()),
+                ("original", &format!("{:.3}", adjusted.original_area)),
+                ("adjusted", &format!("{:.3}", adjusted.adjusted_area)),
+                ("change", &format!("{:+.1}", change)),
+            ]));
+            if ui.small_button(tr("assembly.export_paste")).clicked() {
+                export = Some((side, adjusted.clone()));
+            }
+        });
+        if !adjusted.clamped.is_empty() {
+            ui.label(egui::RichText::new(tr_args("assembly.paste_clamped", &[("count", &adjusted.clamped.len()), ("size", &unit.format_mm(MIN_APERTURE_MM, 3))]))
+                .color(egui::Color32::from_rgb(230, 126, 34)));
+            egui::ScrollArea::vertical()
+                .id_salt(("paste_clamped", side == Side::Top))
+                .max_height(100.0)
+                .show(ui, |ui| {
+                    for position in &adjusted.clamped {
+                        ui.label(egui::RichText::new(format!(
+                            "  ({}, {})",
+                            unit.format_mm(position.x, 3),
+                            unit.format_mm(position.y, 3)
+                        )).color(egui::Color32::GRAY));
+                    }
+                });
+        }
+    }
+    if let Some((side, adjusted)) = export {
+        export_adjusted_paste(side, &adjusted, logger);
+    }
+}
+
+/// Class of a via as shown in the panel
+fn tenting_label(tenting: Tenting) -> &'static str {
+    match tenting {
+        Tenting::Tented => tr("assembly.tenting.tented"),
+        Tenting::ExposedTop => tr("assembly.tenting.exposed_top"),
+        Tenting::ExposedBottom => tr("assembly.tenting.exposed_bottom"),
+        Tenting::ExposedBoth => tr("assembly.tenting.exposed_both"),
+    }
+}
+
+/// How a thermal relief candidate joins its pour, as shown in the panel
+fn pad_description(pad: &PadThermal) -> String {
+    let connection = match pad.connection {
+        thermal::PadConnection::Direct => tr("assembly.thermal.direct"),
+        thermal::PadConnection::ThermalRelief => tr("assembly.thermal.relief"),
+        thermal::PadConnection::Isolated => tr("assembly.thermal.isolated"),
+    };
+    let key = if pad.through_hole { "assembly.thermal.through_hole_pad" } else { "assembly.thermal.pad" };
+    tr_args(key, &[("connection", &connection), ("coverage", &format!("{:.0}", pad.coverage * 100.0))])
+}
+
+/// Marker color of a via: green when tented, yellow when exposed on one side, red on both
+pub fn tenting_color(tenting: Tenting) -> egui::Color32 {
+    match tenting {
+        Tenting::Tented => egui::Color32::from_rgb(46, 204, 113),
+        Tenting::ExposedTop | Tenting::ExposedBottom => egui::Color32::from_rgb(241, 196, 15),
+        Tenting::ExposedBoth => egui::Color32::from_rgb(231, 76, 60),
+    }
+}
+
+/// Zoom to the thermal relief candidate clicked in the list and center it
+pub fn focus_pending_thermal_pad(app: &mut DemoLensApp, viewport: &egui::Rect) {
+    let Some(index) = app.assembly_checks.pending_thermal_focus.take() else {
+        return;
+    };
+    let Some(pad) = app.assembly_checks.thermal_pads.get(index).cloned() else {
+        return;
+    };
+    // Pad and ring fill about a sixth of the view
+    let extent = (pad.width.max(pad.height) + 2.0 * app.drc_manager.rules.thermal_ring_distance as f64) as f32;
+    let target_scale = viewport.width().min(viewport.height()) / (extent * 6.0);
+    if target_scale > app.view_state.scale {
+        let anchor = crate::ui::tabs::board_screen_pos(app, pad.position.x, pad.position.y);
+        let factor = target_scale / app.view_state.scale;
+        crate::ui::gestures::zoom_around_point(app, anchor, factor);
+    }
+    let screen_pos = crate::ui::tabs::board_screen_pos(app, pad.position.x, pad.position.y);
+    app.view_state.translation += viewport.center() - screen_pos;
+    app.sync_zoom_to_ecs();
+}
@@ -0,0 +1,120 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::project::constants::LOG_TYPE_DRC;
+use crate::project_manager::batch_drc::{BatchDrcJob, BatchProject, DrcStatus, DrcSummary};
+
+const PASS_COLOR: Color32 = Color32::from_rgb(46, 204, 113);
+const WARNING_COLOR: Color32 = Color32::from_rgb(241, 196, 15);
+const FAIL_COLOR: Color32 = Color32::from_rgb(231, 76, 60);
+const NOT_CHECKED_COLOR: Color32 = Color32::GRAY;
+
+fn status_color(status: DrcStatus) -> Color32 {
+    match status {
+        DrcStatus::Pass => PASS_COLOR,
+        DrcStatus::Warnings => WARNING_COLOR,
+        DrcStatus::Fail => FAIL_COLOR,
+        DrcStatus::NotChecked => NOT_CHECKED_COLOR,
+    }
+}
+
+/// Colored dot for a project's last batch DRC result, with the counts as tooltip
+/// Projects never checked get a hollow dot
+pub fn show_drc_badge(ui: &mut egui::Ui, summary: Option<&DrcSummary>) -> egui::Response {
+    let (text, tooltip) = match summary {
+        Some(summary) => (RichText::new("●").color(status_color(summary.status())), summary.describe()),
+        None => (RichText::new("○").color(NOT_CHECKED_COLOR), "No batch DRC run yet".to_string()),
+    };
+    ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_text(format!("{}\nClick to open the project", tooltip))
+}
+
+fn start_batch_drc(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(database) = app.project_manager_state.as_ref().and_then(|state| state.database.as_ref()) else {
+        return;
+    };
+    let projects: Vec<BatchProject> = match database.list_projects() {
+        Ok(projects) => projects.into_iter()
+            .map(|project| BatchProject { id: project.id, name: project.name, pcb_path: project.pcb_file_path })
+            .collect(),
+        Err(e) => {
+            logger.log_error(&format!("Failed to list projects for batch DRC: {}", e));
+            return;
+        }
+    };
+
+    logger.log_custom(LOG_TYPE_DRC, &format!("Batch DRC started on {} project(s)", projects.len()));
+    app.batch_drc_job = Some(BatchDrcJob::spawn(projects, app.drc_manager.rules.clone()));
+}
+
+/// Store finished project results and report when the sweep is over
+/// Called every frame from the app so the sweep progresses with the panel closed
+pub fn poll_batch_drc_job(app: &mut DemoLensApp, ctx: &egui::Context) {
+    let Some(job) = app.batch_drc_job.as_mut() else {
+        return;
+    };
+    let (summaries, finished) = job.poll();
+    if !finished {
+        ctx.request_repaint();
+    }
+
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    if let Some(state) = app.project_manager_state.as_mut() {
+        for (project_id, summary) in summaries {
+            if let Err(e) = state.record_drc_summary(&project_id, summary) {
+                logger.log_error(&format!("Failed to save DRC summary: {}", e));
+            }
+        }
+    }
+
+    if finished {
+        let job = app.batch_drc_job.take().expect("batch DRC job checked above");
+        let cancelled = if job.is_cancelling() { " (cancelled)" } else { "" };
+        logger.log_custom(LOG_TYPE_DRC, &format!("Batch DRC finished{}: {} of {} project(s) checked", cancelled, job.done, job.total));
+    }
+}
+
+/// Run button (or progress of a running sweep) and the pass/fail counts of the listed projects
+pub fn show_batch_drc_controls(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.horizontal(|ui| {
+        if let Some(job) = &app.batch_drc_job {
+            let text = if job.is_cancelling() {
+                "Cancelling...".to_string()
+            } else {
+                format!("{}/{}: {}", job.done, job.total, job.current_project)
+            };
+            ui.add(egui::ProgressBar::new(job.done as f32 / job.total.max(1) as f32).text(text).desired_width(200.0));
+            if ui.add_enabled(!job.is_cancelling(), egui::Button::new("⏹ Cancel")).clicked() {
+                job.cancel();
+            }
+        } else if ui.button("🔍 Run DRC on all projects")
+            .on_hover_text("Check the generated gerbers of every project with the current DRC rules")
+            .clicked()
+        {
+            start_batch_drc(app, logger);
+        }
+    });
+
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let mut counts = [0usize; 4];
+    for summary in state.project_list.iter().filter_map(|project| state.drc_summaries.get(&project.id)) {
+        let index = match summary.status() {
+            DrcStatus::Pass => 0,
+            DrcStatus::Warnings => 1,
+            DrcStatus::Fail => 2,
+            DrcStatus::NotChecked => 3,
+        };
+        counts[index] += 1;
+    }
+    if counts.iter().sum::<usize>() > 0 {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("● {} pass", counts[0])).color(PASS_COLOR));
+            ui.label(RichText::new(format!("● {} warnings", counts[1])).color(WARNING_COLOR));
+            ui.label(RichText::new(format!("● {} fail", counts[2])).color(FAIL_COLOR));
+            ui.label(RichText::new(format!("● {} not checked", counts[3])).color(NOT_CHECKED_COLOR));
+        });
+    }
+}
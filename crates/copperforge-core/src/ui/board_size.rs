@@ -0,0 +1,204 @@
+use egui::{Color32, RichText};
+
+use crate::DemoLensApp;
+use crate::ecs::{BoardGeometry, LayerType};
+use crate::project_manager::cost::{CostSettings, ESTIMATE_QUANTITIES, LayerRate, QuantityBreak};
+use crate::ui::i18n::{tr, tr_args};
+
+const MM_PER_INCH: f64 = 25.4;
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 126, 34);
+
+/// Raster resolution of the copper area measurement
+const COPPER_AREA_PIXELS_PER_MM: f64 = 20.0;
+const COPPER_AREA_MAX_DIMENSION: u32 = 4096;
+
+/// Outline measurements, recomputed only when the outline changes, and copper areas,
+/// recomputed when a copper layer changes
+#[derive(Default)]
+pub struct BoardSizeState {
+    geometry: Option<Result<BoardGeometry, String>>,
+    outline_hash: Option<u64>,
+    copper_areas: Vec<(LayerType, f64)>,
+    copper_hash: Option<u64>,
+}
+
+fn board_geometry(app: &mut DemoLensApp) -> Option<&Result<BoardGeometry, String>> {
+    let outline_hash = crate::ecs::get_layer_content_hashes(&mut app.ecs_world)
+        .get(&LayerType::MechanicalOutline)
+        .copied();
+    if outline_hash != app.board_size.outline_hash {
+        app.board_size.geometry = outline_hash.map(|_| crate::ecs::get_board_geometry(&mut app.ecs_world));
+        app.board_size.outline_hash = outline_hash;
+    }
+    app.board_size.geometry.as_ref()
+}
+
+/// Copper area of each copper layer, clearances and pour voids excluded
+fn copper_areas(app: &mut DemoLensApp) -> &[(LayerType, f64)] {
+    let mut hashes: Vec<(LayerType, u64)> = crate::ecs::get_layer_content_hashes(&mut app.ecs_world)
+        .into_iter()
+        .filter(|(layer_type, _)| layer_type.is_copper())
+        .collect();
+    hashes.sort_by_key(|(layer_type, _)| layer_type.copper_layer_number());
+    let copper_hash = {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        hashes.iter().for_each(|(_, hash)| hash.hash(&mut hasher));
+        hasher.finish()
+    };
+    if app.board_size.copper_hash != Some(copper_hash) {
+        app.board_size.copper_areas = hashes.iter()
+            .map(|(layer_type, _)| {
+                let primitives = crate::ecs::get_layer_primitives(&mut app.ecs_world, *layer_type);
+                let area = crate::export::raster::covered_area(&primitives, COPPER_AREA_PIXELS_PER_MM, COPPER_AREA_MAX_DIMENSION);
+                (*layer_type, area)
+            })
+            .collect();
+        app.board_size.copper_hash = Some(copper_hash);
+    }
+    &app.board_size.copper_areas
+}
+
+fn format_length(mm: f64) -> String {
+    format!("{:.2} mm ({:.3} in)", mm, mm / MM_PER_INCH)
+}
+
+/// Board area, perimeter and sizes from the outline, with a fab cost estimate
+pub fn show_board_size_section(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let copper_layers = crate::ecs::get_loaded_layer_types(&mut app.ecs_world)
+        .iter()
+        .filter(|layer_type| layer_type.is_copper())
+        .count() as u32;
+    let Some(geometry) = board_geometry(app).cloned() else {
+        return;
+    };
+    let copper_areas = copper_areas(app).to_vec();
+
+    ui.group(|ui| {
+        ui.label(tr("board.heading"));
+        ui.separator();
+
+        let geometry = match geometry {
+            Ok(geometry) => geometry,
+            Err(e) => {
+                ui.label(RichText::new(format!("⚠ {}", e)).color(WARNING_COLOR));
+                ui.label(RichText::new(tr("board.needs_outline")).color(Color32::GRAY));
+                return;
+            }
+        };
+        if geometry.open_chains > 0 {
+            ui.label(RichText::new(tr_args("board.open_chains", &[("count", &geometry.open_chains)])).color(WARNING_COLOR));
+        }
+
+        egui::Grid::new("board_size_grid").num_columns(2).spacing([12.0, 2.0]).show(ui, |ui| {
+            ui.label(tr("board.area"));
+            ui.label(format!("{:.1} mm² ({:.3} in², {:.4} dm²)", geometry.area, geometry.area / (MM_PER_INCH * MM_PER_INCH), geometry.area / 10_000.0));
+            ui.end_row();
+            ui.label(tr("board.perimeter"));
+            ui.label(format_length(geometry.perimeter));
+            ui.end_row();
+            if geometry.cutouts > 0 {
+                ui.label(tr("board.cutouts"));
+                ui.label(tr_args("board.cutouts_value", &[("count", &geometry.cutouts), ("edge", &format_length(geometry.cutout_perimeter))]));
+                ui.end_row();
+            }
+            ui.label(tr("board.bounding_box"));
+            ui.label(format!("{:.2} × {:.2} mm", geometry.bounding_size.0, geometry.bounding_size.1));
+            ui.end_row();
+            ui.label(tr("board.smallest_rectangle"));
+            ui.label(tr_args("board.smallest_rectangle_value", &[
+                ("width", &format!("{:.2}", geometry.min_rect.width)),
+                ("height", &format!("{:.2}", geometry.min_rect.height)),
+                ("angle", &format!("{:.1}", geometry.min_rect.angle_degrees)),
+            ]));
+            ui.end_row();
+            ui.label(tr("board.copper_layers"));
+            ui.label(copper_layers.to_string());
+            ui.end_row();
+            for (layer_type, area) in &copper_areas {
+                ui.label(tr_args("board.layer_copper", &[("layer", &layer_type.display_name())]));
+                ui.label(tr_args("board.layer_copper_value", &[
+                    ("area", &format!("{:.1}", area)),
+                    ("percent", &format!("{:.0}", area / geometry.area.max(f64::EPSILON) * 100.0)),
+                ]))
+                    .on_hover_text(tr("board.layer_copper_hint"));
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(4.0);
+        let settings = &app.cost_settings;
+        let estimates: Vec<_> = ESTIMATE_QUANTITIES.iter()
+            .filter_map(|&quantity| settings.estimate(geometry.area, copper_layers, quantity))
+            .collect();
+        if estimates.is_empty() {
+            ui.label(RichText::new(tr_args("board.no_cost_rate", &[("count", &copper_layers)])).color(WARNING_COLOR));
+        } else {
+            egui::Grid::new("board_cost_grid").num_columns(3).striped(true).spacing([16.0, 2.0]).show(ui, |ui| {
+                ui.strong(tr("board.quantity"));
+                ui.strong(tr("board.per_board"));
+                ui.strong(tr("board.total"));
+                ui.end_row();
+                for estimate in &estimates {
+                    ui.label(estimate.quantity.to_string());
+                    ui.label(format!("{}{:.2}", settings.currency, estimate.unit_cost));
+                    ui.label(format!("{}{:.2}", settings.currency, estimate.total));
+                    ui.end_row();
+                }
+            });
+        }
+
+        egui::CollapsingHeader::new(tr("board.cost_rates"))
+            .id_salt("board_cost_rates")
+            .default_open(false)
+            .show(ui, |ui| show_cost_settings(ui, &mut app.cost_settings));
+    });
+}
+
+/// Editor for the per-layer rates, quantity breaks and setup fee
+fn show_cost_settings(ui: &mut egui::Ui, settings: &mut CostSettings) {
+    ui.horizontal(|ui| {
+        ui.label(tr("board.currency"));
+        ui.add(egui::TextEdit::singleline(&mut settings.currency).desired_width(40.0));
+        ui.label(tr("board.setup_fee"));
+        ui.add(egui::DragValue::new(&mut settings.setup_fee).speed(1.0).range(0.0..=10_000.0));
+    });
+
+    ui.label(tr("board.layer_rates"));
+    let mut remove_rate = None;
+    for (index, rate) in settings.layer_rates.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut rate.layers).range(1..=64).suffix(tr("board.layers_suffix")));
+            ui.add(egui::DragValue::new(&mut rate.per_dm2).speed(0.1).range(0.0..=1000.0).prefix(&settings.currency));
+            if ui.small_button("🗑").clicked() {
+                remove_rate = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove_rate {
+        settings.layer_rates.remove(index);
+    }
+    if ui.small_button(tr("board.add_rate")).clicked() {
+        let layers = settings.layer_rates.iter().map(|rate| rate.layers).max().unwrap_or(0) + 2;
+        settings.layer_rates.push(LayerRate { layers, per_dm2: 0.0 });
+    }
+
+    ui.label(tr("board.quantity_breaks"));
+    let mut remove_break = None;
+    for (index, quantity_break) in settings.quantity_breaks.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut quantity_break.quantity).range(1..=1_000_000).prefix(tr("board.from_prefix")).suffix(tr("board.pieces_suffix")));
+            ui.add(egui::DragValue::new(&mut quantity_break.discount_percent).speed(0.5).range(0.0..=100.0).suffix(tr("board.discount_suffix")));
+            if ui.small_button("🗑").clicked() {
+                remove_break = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove_break {
+        settings.quantity_breaks.remove(index);
+    }
+    if ui.small_button(tr("board.add_break")).clicked() {
+        let quantity = settings.quantity_breaks.iter().map(|b| b.quantity).max().unwrap_or(1) * 10;
+        settings.quantity_breaks.push(QuantityBreak { quantity, discount_percent: 0.0 });
+    }
+}
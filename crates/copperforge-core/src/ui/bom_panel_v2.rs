@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 use crate::DemoLensApp;
-use crate::ecs::{UnitsResource, mm_to_nm, nm_to_mils};
+use crate::ecs::{DisplayUnit, UnitsResource};
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState};
 use egui_lens::LogColors;
 use egui_mobius_reactive::*;
@@ -292,6 +292,7 @@ fn try_fetch_components_blocking() -> Result<Vec<BomComponent>, String> {
                     orientation: fp.rotation,
                     value: fp.value.clone(),
                     footprint: fp.footprint_name.clone(),
+                    layer: fp.layer.clone(),
                 };
                 components.push(component);
             }
@@ -357,10 +358,7 @@ pub fn show_bom_panel(
     let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
     
     // Get units resource information before any mutable borrows
-    let is_mils = {
-        let units_resource = get_units(app);
-        units_resource.is_mils()
-    };
+    let unit = get_units(app).display_unit;
     
     // Initialize BOM state if not already done
     if app.bom_state.is_none() {
@@ -496,7 +494,7 @@ pub fn show_bom_panel(
             let filter_text = bom_state.filter_text.lock().unwrap();
             let mut selected_component = bom_state.selected_component.lock().unwrap();
             
-            show_bom_table_optimized(ui, &components, &filter_text, is_mils, &mut selected_component, &bom_state.cross_probe_signal);
+            show_bom_table_optimized(ui, &components, &filter_text, unit, &mut selected_component, &bom_state.cross_probe_signal);
         }
         
         // Request repaint if needed
@@ -508,7 +506,7 @@ pub fn show_bom_panel(
 }
 
 /// Show the BOM table using TableBuilder with cross-probing support
-fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filter_text: &str, is_mils: bool, selected_component: &mut Option<BomComponent>, cross_probe_signal: &Signal<BomComponent>) {
+fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filter_text: &str, unit: DisplayUnit, selected_component: &mut Option<BomComponent>, cross_probe_signal: &Signal<BomComponent>) {
     let filter_lower = filter_text.to_lowercase();
     let should_filter = !filter_text.is_empty();
     
@@ -536,8 +534,8 @@ fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filt
     }
     
     // Configure column labels with proper units
-    let x_label = if is_mils { "X (mils)" } else { "X (mm)" };
-    let y_label = if is_mils { "Y (mils)" } else { "Y (mm)" };
+    let x_label = format!("X ({})", unit.suffix());
+    let y_label = format!("Y ({})", unit.suffix());
     
     // Use virtual scrolling for large lists to improve performance
     let use_virtual_scrolling = filtered_components.len() > 100;
@@ -562,8 +560,8 @@ fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filt
                 header.col(|ui| { ui.strong("Item"); });
                 header.col(|ui| { ui.strong("Reference"); });
                 header.col(|ui| { ui.strong("Description"); });
-                header.col(|ui| { ui.strong(x_label); });
-                header.col(|ui| { ui.strong(y_label); });
+                header.col(|ui| { ui.strong(x_label.as_str()); });
+                header.col(|ui| { ui.strong(y_label.as_str()); });
                 header.col(|ui| { ui.strong("Rotation (°)"); });
                 header.col(|ui| { ui.strong("Value"); });
                 header.col(|ui| { ui.strong("Footprint"); });
@@ -574,7 +572,7 @@ fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filt
                     |row| {
                         let row_index = row.index();
                         if let Some(component) = filtered_components.get(row_index) {
-                            let response = render_component_row_clickable(row, component, is_mils, row_index + 1);
+                            let response = render_component_row_clickable(row, component, unit, row_index + 1);
                             if response.clicked() {
                                 clicked_row_index = Some(row_index);
                             }
@@ -599,8 +597,8 @@ fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filt
                 header.col(|ui| { ui.strong("Item"); });
                 header.col(|ui| { ui.strong("Reference"); });
                 header.col(|ui| { ui.strong("Description"); });
-                header.col(|ui| { ui.strong(x_label); });
-                header.col(|ui| { ui.strong(y_label); });
+                header.col(|ui| { ui.strong(x_label.as_str()); });
+                header.col(|ui| { ui.strong(y_label.as_str()); });
                 header.col(|ui| { ui.strong("Rotation (°)"); });
                 header.col(|ui| { ui.strong("Value"); });
                 header.col(|ui| { ui.strong("Footprint"); });
@@ -608,7 +606,7 @@ fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filt
             .body(|mut body| {
                 for (row_index, component) in filtered_components.iter().enumerate() {
                     body.row(18.0, |row| {
-                        let response = render_component_row_clickable(row, component, is_mils, row_index + 1);
+                        let response = render_component_row_clickable(row, component, unit, row_index + 1);
                         if response.clicked() {
                             clicked_row_index = Some(row_index);
                         }
@@ -628,7 +626,7 @@ fn show_bom_table_optimized(ui: &mut egui::Ui, components: &[BomComponent], filt
 }
 
 /// Render a single component row - extracted for reuse
-fn render_component_row(mut row: egui_extras::TableRow, component: &BomComponent, is_mils: bool) {
+fn render_component_row(mut row: egui_extras::TableRow, component: &BomComponent, unit: DisplayUnit) {
     row.col(|ui| {
         ui.label(&component.item_number);
     });
@@ -639,21 +637,11 @@ fn render_component_row(mut row: egui_extras::TableRow, component: &BomComponent
         ui.label(&component.description);
     });
     row.col(|ui| {
-        let x_text = if is_mils {
-            let x_nm = mm_to_nm(component.x_location as f32);
-            format!("{:.0}", nm_to_mils(x_nm))
-        } else {
-            format!("{:.2}", component.x_location)
-        };
+        let x_text = unit.format_number(component.x_location, 2);
         ui.label(x_text);
     });
     row.col(|ui| {
-        let y_text = if is_mils {
-            let y_nm = mm_to_nm(component.y_location as f32);
-            format!("{:.0}", nm_to_mils(y_nm))
-        } else {
-            format!("{:.2}", component.y_location)
-        };
+        let y_text = unit.format_number(component.y_location, 2);
         ui.label(y_text);
     });
     row.col(|ui| {
@@ -668,7 +656,7 @@ fn render_component_row(mut row: egui_extras::TableRow, component: &BomComponent
 }
 
 /// Render a single component row with click detection for cross-probing
-fn render_component_row_clickable(mut row: egui_extras::TableRow, component: &BomComponent, is_mils: bool, item_number: usize) -> egui::Response {
+fn render_component_row_clickable(mut row: egui_extras::TableRow, component: &BomComponent, unit: DisplayUnit, item_number: usize) -> egui::Response {
     let mut response = None;
     
     row.col(|ui| {
@@ -683,22 +671,12 @@ fn render_component_row_clickable(mut row: egui_extras::TableRow, component: &Bo
         if response.is_none() { response = Some(r); }
     });
     row.col(|ui| {
-        let x_text = if is_mils {
-            let x_nm = mm_to_nm(component.x_location as f32);
-            format!("{:.0}", nm_to_mils(x_nm))
-        } else {
-            format!("{:.2}", component.x_location)
-        };
+        let x_text = unit.format_number(component.x_location, 2);
         let r = ui.selectable_label(false, x_text);
         if response.is_none() { response = Some(r); }
     });
     row.col(|ui| {
-        let y_text = if is_mils {
-            let y_nm = mm_to_nm(component.y_location as f32);
-            format!("{:.0}", nm_to_mils(y_nm))
-        } else {
-            format!("{:.2}", component.y_location)
-        };
+        let y_text = unit.format_number(component.y_location, 2);
         let r = ui.selectable_label(false, y_text);
         if response.is_none() { response = Some(r); }
     });
@@ -720,7 +698,7 @@ fn render_component_row_clickable(mut row: egui_extras::TableRow, component: &Bo
 }
 
 /// Show the BOM table - legacy version
-fn show_bom_table(ui: &mut egui::Ui, components: &[BomComponent], is_mils: bool) {
+fn show_bom_table(ui: &mut egui::Ui, components: &[BomComponent], unit: DisplayUnit) {
     if components.is_empty() {
         ui.centered_and_justified(|ui| {
             ui.label("No components available. Make sure KiCad is running with a PCB open.");
@@ -779,21 +757,11 @@ fn show_bom_table(ui: &mut egui::Ui, components: &[BomComponent], is_mils: bool)
                         ui.label(&component.description);
                     });
                     row.col(|ui| {
-                        let x_text = if is_mils {
-                            let x_nm = mm_to_nm(component.x_location as f32);
-                            format!("{:.0}", nm_to_mils(x_nm))
-                        } else {
-                            format!("{:.2}", component.x_location)
-                        };
+                        let x_text = unit.format_number(component.x_location, 2);
                         ui.label(x_text);
                     });
                     row.col(|ui| {
-                        let y_text = if is_mils {
-                            let y_nm = mm_to_nm(component.y_location as f32);
-                            format!("{:.0}", nm_to_mils(y_nm))
-                        } else {
-                            format!("{:.2}", component.y_location)
-                        };
+                        let y_text = unit.format_number(component.y_location, 2);
                         ui.label(y_text);
                     });
                     row.col(|ui| {
@@ -0,0 +1,113 @@
+use egui::{Color32, Painter, Pos2, Shape, Stroke, Vec2};
+use serde::{Deserialize, Serialize};
+
+/// Markers are never drawn smaller than this in high-contrast mode
+const HIGH_CONTRAST_MIN_SIZE: f32 = 12.0;
+const MARKER_STROKE_WIDTH: f32 = 2.0;
+const OUTLINE_STROKE_WIDTH: f32 = 5.0;
+
+/// Accessibility options for the DRC markers drawn over the board
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrcMarkerSettings {
+    /// Distinct shape per rule type, larger markers and a dark outline
+    pub high_contrast: bool,
+    /// Number each marker to match the violation list
+    pub show_labels: bool,
+}
+
+impl DrcMarkerSettings {
+    pub fn marker_size(&self, size: f32) -> f32 {
+        if self.high_contrast { size.max(HIGH_CONTRAST_MIN_SIZE) } else { size }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerShape {
+    Cross,
+    /// Ring of radius `size`, leaving the marked spot (e.g. a hole) visible
+    Ring,
+    Triangle,
+    Square,
+    Diamond,
+}
+
+impl MarkerShape {
+    /// Shape for a rule in high-contrast mode: triangle for spacing, square for
+    /// width/size and diamond for board edge clearance
+    pub fn for_rule(rule_name: &str) -> Self {
+        let rule = rule_name.to_lowercase();
+        if rule.contains("edge") || rule.contains("outline") || rule.contains("boundary") {
+            Self::Diamond
+        } else if rule.contains("spacing") || rule.contains("clearance") || rule.contains("sliver") {
+            Self::Triangle
+        } else if rule.contains("width") || rule.contains("diameter") || rule.contains("annular") {
+            Self::Square
+        } else {
+            Self::Cross
+        }
+    }
+}
+
+/// Draw a DRC marker, optionally with a dark outline so it stands out over any layer color
+pub fn draw_violation_marker(painter: &Painter, center: Pos2, size: f32, color: Color32, shape: MarkerShape, outlined: bool) {
+    if outlined {
+        paint_marker_shape(painter, center, size, shape, Stroke::new(OUTLINE_STROKE_WIDTH, Color32::BLACK));
+    }
+    paint_marker_shape(painter, center, size, shape, Stroke::new(MARKER_STROKE_WIDTH, color));
+}
+
+fn paint_marker_shape(painter: &Painter, center: Pos2, size: f32, shape: MarkerShape, stroke: Stroke) {
+    let half_size = size / 2.0;
+    let offset = |x: f32, y: f32| center + Vec2::new(x, y) * half_size;
+    match shape {
+        MarkerShape::Cross => {
+            painter.line_segment([offset(-1.0, -1.0), offset(1.0, 1.0)], stroke);
+            painter.line_segment([offset(-1.0, 1.0), offset(1.0, -1.0)], stroke);
+        }
+        MarkerShape::Ring => {
+            painter.circle_stroke(center, size, stroke);
+        }
+        MarkerShape::Triangle => {
+            painter.add(Shape::closed_line(vec![offset(0.0, -1.2), offset(1.1, 0.8), offset(-1.1, 0.8)], stroke));
+        }
+        MarkerShape::Square => {
+            painter.add(Shape::closed_line(
+                vec![offset(-1.0, -1.0), offset(1.0, -1.0), offset(1.0, 1.0), offset(-1.0, 1.0)],
+                stroke,
+            ));
+        }
+        MarkerShape::Diamond => {
+            painter.add(Shape::closed_line(
+                vec![offset(0.0, -1.3), offset(1.3, 0.0), offset(0.0, 1.3), offset(-1.3, 0.0)],
+                stroke,
+            ));
+        }
+    }
+}
+
+/// Number next to a marker, white on a dark shadow for contrast
+pub fn draw_marker_label(painter: &Painter, center: Pos2, size: f32, text: &str) {
+    let anchor = center + Vec2::new(size * 0.75 + 2.0, -size * 0.75 - 2.0);
+    let font = egui::FontId::monospace(11.0);
+    painter.text(anchor + Vec2::splat(1.0), egui::Align2::LEFT_BOTTOM, text, font.clone(), Color32::BLACK);
+    painter.text(anchor, egui::Align2::LEFT_BOTTOM, text, font, Color32::WHITE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drc_operations::drill::{RULE_DRILL_SPACING, RULE_DRILL_TO_COPPER, RULE_MIN_DRILL_DIAMETER};
+    use crate::drc_operations::mask::{RULE_MASK_REGISTRATION, RULE_MASK_SLIVER};
+
+    #[test]
+    fn test_marker_shape_per_rule() {
+        assert_eq!(MarkerShape::for_rule("Minimum Trace Width"), MarkerShape::Square);
+        assert_eq!(MarkerShape::for_rule(RULE_MIN_DRILL_DIAMETER), MarkerShape::Square);
+        assert_eq!(MarkerShape::for_rule(RULE_DRILL_SPACING), MarkerShape::Triangle);
+        assert_eq!(MarkerShape::for_rule(RULE_DRILL_TO_COPPER), MarkerShape::Triangle);
+        assert_eq!(MarkerShape::for_rule(RULE_MASK_SLIVER), MarkerShape::Triangle);
+        assert_eq!(MarkerShape::for_rule("Board Edge Clearance"), MarkerShape::Diamond);
+        assert_eq!(MarkerShape::for_rule(RULE_MASK_REGISTRATION), MarkerShape::Cross);
+    }
+}
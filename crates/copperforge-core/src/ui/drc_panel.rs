@@ -1,8 +1,12 @@
-use crate::{DemoLensApp, project::constants::LOG_TYPE_DRC, ecs::LayerType};
-use crate::drc_operations::TraceQualityType;
+use crate::{DemoLensApp, project::constants::LOG_TYPE_DRC, ecs::{DisplayUnit, LayerType}};
+use crate::ui::unit_value::UnitValue;
+use crate::drc_operations::{DrcCheckConfig, DrcJobOutcome, TraceQualityType};
+use crate::ui::i18n::{tr, tr_args};
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::Dynamic;
 
+const MASK_WARNING_TEXT_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 126, 34);
+
 pub fn show_drc_panel<'a>(
     ui: &mut egui::Ui, 
     app: &'a mut DemoLensApp,
@@ -12,180 +16,78 @@ pub fn show_drc_panel<'a>(
     let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
     // Design Rule Check section
     ui.horizontal(|ui| {
-        ui.heading("Design Rule Check");
+        ui.heading(tr("drc.heading"));
         
         // Add some spacing to push the button to the right
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            if ui.button("🔍 Run DRC").clicked() {
-                // Check if a ruleset is loaded
-                if let Some(ref ruleset) = app.drc_manager.current_ruleset {
-                    // Run actual DRC analysis
-                    logger.log_info("Starting Design Rule Check");
-                    logger.log_info(&format!("Using {} ruleset", ruleset));
-                    logger.log_info("Analyzing Gerber files with imageproc trace detection");
-                    
-                    // Run the actual DRC check (now includes OpenCV)
-                    // Convert ECS layers to legacy format for DRC compatibility
-                    let legacy_layers = convert_ecs_to_legacy_layers(&mut app.ecs_world);
-                    let violations = crate::drc_operations::run_simple_drc_check(
-                        &legacy_layers,
-                        &app.drc_manager.rules,
-                        &mut app.drc_manager.trace_quality_issues
-                    );
-                    
-                    logger.log_info("Running imageproc edge detection and morphological analysis");
-                    logger.log_info("Checking trace widths with Canny edge detection");
-                    logger.log_info("Checking via sizes");
-                    logger.log_info("Checking spacing rules");
-                    logger.log_info("Checking drill sizes");
-                    
-                    // Report violations
-                    if violations.is_empty() {
-                        logger.log_info("✅ No violations found");
-                        logger.log_info("DRC analysis completed successfully");
-                    } else {
-                        logger.log_warning(&format!("⚠️  Found {} violation(s):", violations.len()));
-                        for violation in &violations {
-                            logger.log_error(&format!("❌ {}", violation.format_message()));
-                        }
-                        logger.log_info("DRC analysis completed with violations");
-                    }
-                } else {
-                    logger.log_warning("Cannot run DRC: No ruleset loaded");
-                    logger.log_info("Please select a PCB manufacturer ruleset first");
-                }
+            let idle = app.drc_job.is_none();
+            let force_full = ui.add_enabled(idle, egui::Button::new(tr("drc.force_full")))
+                .on_hover_text(tr("drc.force_full_hint"))
+                .clicked();
+            if ui.add_enabled(idle, egui::Button::new(tr("drc.run"))).clicked() || force_full {
+                run_drc(app, &logger, force_full);
+            }
+            if ui.add_enabled(idle && app.drc_manager.has_results, egui::Button::new(tr("drc.export_report")))
+                .on_hover_text(tr("drc.export_report_hint"))
+                .clicked()
+            {
+                export_drc_report(app, &logger);
             }
         });
     });
     ui.add_space(4.0);
     
+    show_drc_progress(ui, app);
+    show_region_controls(ui, app);
+    
     // Simple DRC Rules Entry
-    egui::CollapsingHeader::new("DRC Rules")
+    egui::CollapsingHeader::new(tr("drc.rules"))
+        .id_salt("drc_rules")
         .default_open(true)
         .show(ui, |ui| {
             ui.add_space(4.0);
             
             // Unit toggle
             ui.horizontal(|ui| {
-                ui.label("Units:");
-                ui.selectable_value(&mut app.drc_manager.rules.use_mils, false, "mm");
-                ui.selectable_value(&mut app.drc_manager.rules.use_mils, true, "mils");
-            });
-            ui.add_space(4.0);
-            
-            // Trace Width
-            ui.horizontal(|ui| {
-                ui.label("Min Trace Width:");
-                let mut display_value = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_trace_width);
-                let range = if app.drc_manager.rules.use_mils { 2.0..=80.0 } else { 0.05..=2.0 };
-                let speed = if app.drc_manager.rules.use_mils { 0.1 } else { 0.01 };
-                
-                if ui.add(egui::DragValue::new(&mut display_value)
-                    .speed(speed)
-                    .range(range)
-                    .suffix(app.drc_manager.rules.unit_suffix())).changed() {
-                    app.drc_manager.rules.min_trace_width = app.drc_manager.rules.set_from_display(display_value);
-                }
-            });
-            
-            // Via Diameter  
-            ui.horizontal(|ui| {
-                ui.label("Min Via Diameter:");
-                let mut display_value = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_via_diameter);
-                let range = if app.drc_manager.rules.use_mils { 4.0..=200.0 } else { 0.1..=5.0 };
-                let speed = if app.drc_manager.rules.use_mils { 0.1 } else { 0.01 };
-                
-                if ui.add(egui::DragValue::new(&mut display_value)
-                    .speed(speed)
-                    .range(range)
-                    .suffix(app.drc_manager.rules.unit_suffix())).changed() {
-                    app.drc_manager.rules.min_via_diameter = app.drc_manager.rules.set_from_display(display_value);
-                }
-            });
-            
-            // Drill Diameter
-            ui.horizontal(|ui| {
-                ui.label("Min Drill Diameter:");
-                let mut display_value = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_drill_diameter);
-                let range = if app.drc_manager.rules.use_mils { 2.0..=120.0 } else { 0.05..=3.0 };
-                let speed = if app.drc_manager.rules.use_mils { 0.1 } else { 0.01 };
-                
-                if ui.add(egui::DragValue::new(&mut display_value)
-                    .speed(speed)
-                    .range(range)
-                    .suffix(app.drc_manager.rules.unit_suffix())).changed() {
-                    app.drc_manager.rules.min_drill_diameter = app.drc_manager.rules.set_from_display(display_value);
+                ui.label(tr("drc.units"));
+                for unit in crate::ecs::DisplayUnit::CHOICES {
+                    ui.selectable_value(&mut app.drc_manager.rules.display_unit, unit, unit.suffix());
                 }
             });
+            ui.add_space(4.0);
             
-            // Spacing
-            ui.horizontal(|ui| {
-                ui.label("Min Spacing:");
-                let mut display_value = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_spacing);
-                let range = if app.drc_manager.rules.use_mils { 2.0..=80.0 } else { 0.05..=2.0 };
-                let speed = if app.drc_manager.rules.use_mils { 0.1 } else { 0.01 };
-                
-                if ui.add(egui::DragValue::new(&mut display_value)
-                    .speed(speed)
-                    .range(range)
-                    .suffix(app.drc_manager.rules.unit_suffix())).changed() {
-                    app.drc_manager.rules.min_spacing = app.drc_manager.rules.set_from_display(display_value);
-                }
-            });
+            let unit = app.drc_manager.rules.display_unit();
+            rule_field(ui, tr("drc.rule.min_trace_width"), &mut app.drc_manager.rules.min_trace_width, unit, 0.05..=2.0);
+            rule_field(ui, tr("drc.rule.min_via_diameter"), &mut app.drc_manager.rules.min_via_diameter, unit, 0.1..=5.0);
+            rule_field(ui, tr("drc.rule.min_drill_diameter"), &mut app.drc_manager.rules.min_drill_diameter, unit, 0.05..=3.0);
+            rule_field(ui, tr("drc.rule.min_spacing"), &mut app.drc_manager.rules.min_spacing, unit, 0.05..=2.0);
+            rule_field(ui, tr("drc.rule.min_annular_ring"), &mut app.drc_manager.rules.min_annular_ring, unit, 0.05..=1.0);
+            rule_field(ui, tr("drc.rule.min_drill_spacing"), &mut app.drc_manager.rules.min_drill_spacing, unit, 0.1..=5.0).on_hover_text(tr("drc.rule.min_drill_spacing_hint"));
+            rule_field(ui, tr("drc.rule.min_drill_to_copper"), &mut app.drc_manager.rules.min_drill_to_copper, unit, 0.05..=2.0).on_hover_text(tr("drc.rule.min_drill_to_copper_hint"));
+            rule_field(ui, tr("drc.rule.min_silk_line_width"), &mut app.drc_manager.rules.min_silk_line_width, unit, 0.02..=1.0);
+            rule_field(ui, tr("drc.rule.min_silk_text_height"), &mut app.drc_manager.rules.min_silk_text_height, unit, 0.1..=5.0);
+            rule_field(ui, tr("drc.rule.min_edge_clearance"), &mut app.drc_manager.rules.min_edge_clearance, unit, 0.0..=5.0).on_hover_text(tr("drc.rule.min_edge_clearance_hint"));
             
-            // Annular Ring
-            ui.horizontal(|ui| {
-                ui.label("Min Annular Ring:");
-                let mut display_value = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_annular_ring);
-                let range = if app.drc_manager.rules.use_mils { 2.0..=40.0 } else { 0.05..=1.0 };
-                let speed = if app.drc_manager.rules.use_mils { 0.1 } else { 0.01 };
-                
-                if ui.add(egui::DragValue::new(&mut display_value)
-                    .speed(speed)
-                    .range(range)
-                    .suffix(app.drc_manager.rules.unit_suffix())).changed() {
-                    app.drc_manager.rules.min_annular_ring = app.drc_manager.rules.set_from_display(display_value);
-                }
-            });
+            ui.add_space(4.0);
+            show_layer_overrides(ui, app);
             
             ui.add_space(8.0);
             
-            // Preset buttons
-            ui.horizontal(|ui| {
-                if ui.button("🏭 JLC PCB Defaults").clicked() {
-                    app.drc_manager.rules.min_trace_width = 0.15;   // 6 mil
-                    app.drc_manager.rules.min_via_diameter = 0.3;   // 12 mil  
-                    app.drc_manager.rules.min_drill_diameter = 0.2; // 8 mil
-                    app.drc_manager.rules.min_spacing = 0.15;       // 6 mil
-                    app.drc_manager.rules.min_annular_ring = 0.1;   // 4 mil
-                    app.drc_manager.rules.use_mils = false;         // JLC uses metric
-                    app.drc_manager.current_ruleset = Some("JLC PCB".to_string());
-                    logger.log_info("Loaded JLC PCB design rules (0.15mm/6mil trace/space)");
-                }
-                
-                if ui.button("🔧 Conservative").clicked() {
-                    app.drc_manager.rules.min_trace_width = 0.2;    // 8 mil
-                    app.drc_manager.rules.min_via_diameter = 0.4;   // 16 mil
-                    app.drc_manager.rules.min_drill_diameter = 0.25; // 10 mil
-                    app.drc_manager.rules.min_spacing = 0.2;        // 8 mil
-                    app.drc_manager.rules.min_annular_ring = 0.15;  // 6 mil
-                    app.drc_manager.rules.use_mils = false;         // Conservative uses metric
-                    app.drc_manager.current_ruleset = Some("Conservative".to_string());
-                    logger.log_info("Loaded conservative design rules (0.2mm/8mil trace/space)");
-                }
-            });
+            // Rule profiles
+            crate::ui::drc_profiles::show_profile_controls(ui, app, &logger);
             
             ui.add_space(4.0);
             
             // Load current settings and run DRC
             ui.horizontal(|ui| {
-                if ui.button("✅ Load Current Settings & Run DRC").clicked() {
+                if ui.button(tr("drc.load_and_run")).clicked() {
                     // Create custom ruleset name from current values
-                    let unit_str = if app.drc_manager.rules.use_mils { "mils" } else { "mm" };
-                    let trace_val = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_trace_width);
-                    let space_val = app.drc_manager.rules.get_display_value(app.drc_manager.rules.min_spacing);
+                    let unit_str = app.drc_manager.rules.unit_suffix();
+                    let rules = &app.drc_manager.rules;
+                    let trace_val = rules.display_unit.format_number(rules.min_trace_width as f64, 1);
+                    let space_val = rules.display_unit.format_number(rules.min_spacing as f64, 1);
                     
-                    let ruleset_name = format!("Custom ({:.1}/{:.1} {unit_str} trace/space)", 
+                    let ruleset_name = format!("Custom ({}/{} {unit_str} trace/space)", 
                         trace_val, space_val);
                     
                     app.drc_manager.current_ruleset = Some(ruleset_name.clone());
@@ -197,45 +99,41 @@ pub fn show_drc_panel<'a>(
                     logger.log_info(&format!("  Min Drill Diameter: {:.3}mm", app.drc_manager.rules.min_drill_diameter));
                     logger.log_info(&format!("  Min Spacing: {:.3}mm", app.drc_manager.rules.min_spacing));
                     logger.log_info(&format!("  Min Annular Ring: {:.3}mm", app.drc_manager.rules.min_annular_ring));
+                    logger.log_info(&format!("  Min Drill Spacing: {:.3}mm", app.drc_manager.rules.min_drill_spacing));
+                    logger.log_info(&format!("  Min Drill to Copper: {:.3}mm", app.drc_manager.rules.min_drill_to_copper));
+                    logger.log_info(&format!("  Min Edge Clearance: {:.3}mm", app.drc_manager.rules.min_edge_clearance));
                     
-                    // Run actual DRC analysis with current settings
-                    logger.log_info("Starting Design Rule Check with custom settings");
-                    logger.log_info("Analyzing Gerber files...");
-                    
-                    // Run the actual DRC check (now includes OpenCV)
-                    // Convert ECS layers to legacy format for DRC compatibility
-                    let legacy_layers = convert_ecs_to_legacy_layers(&mut app.ecs_world);
-                    let violations = crate::drc_operations::run_simple_drc_check(
-                        &legacy_layers,
-                        &app.drc_manager.rules,
-                        &mut app.drc_manager.trace_quality_issues
-                    );
-                    
-                    logger.log_info("Running imageproc edge detection and morphological analysis");
-                    logger.log_info("Checking trace widths with Canny edge detection");
-                    logger.log_info("Checking via sizes");
-                    logger.log_info("Checking spacing rules");
-                    logger.log_info("Checking drill sizes");
-                    logger.log_info("Checking annular rings");
-                    
-                    // Report violations
-                    if violations.is_empty() {
-                        logger.log_info("✅ No violations found");
-                        logger.log_info("DRC analysis completed successfully");
-                    } else {
-                        logger.log_warning(&format!("⚠️  Found {} violation(s):", violations.len()));
-                        for violation in &violations {
-                            logger.log_error(&format!("❌ {}", violation.format_message()));
-                        }
-                        logger.log_info("DRC analysis completed with violations");
-                    }
+                    // Run DRC with current settings (rule changes invalidate the cache)
+                    run_drc(app, &logger, false);
                 }
             });
         });
     
+    show_check_toggles(ui, app);
+    
+    ui.add_space(4.0);
+    
+    egui::CollapsingHeader::new(tr_args("drc.violations", &[("count", &app.drc_manager.active_violations().count())]))
+        .id_salt("drc_violations")
+        .default_open(true)
+        .show(ui, |ui| crate::ui::drc_violations::show_violation_tree(ui, app, &logger));
+    
+    ui.add_space(4.0);
+    
+    show_mask_checks_section(ui, app, &logger);
+    
     ui.add_space(4.0);
     
-    egui::CollapsingHeader::new("PCB Manufacturer Rules")
+    crate::ui::assembly_checks::show_assembly_checks_section(ui, app, &logger);
+    
+    ui.add_space(4.0);
+    
+    crate::ui::netlist_checks::show_netlist_checks_section(ui, app, &logger);
+    
+    ui.add_space(4.0);
+    
+    egui::CollapsingHeader::new(tr("drc.manufacturer_rules"))
+        .id_salt("drc_manufacturer_rules")
         .default_open(false)
         .show(ui, |ui| {
             ui.add_space(4.0);
@@ -243,18 +141,18 @@ pub fn show_drc_panel<'a>(
             // Current ruleset display
             if let Some(ref ruleset) = app.drc_manager.current_ruleset {
                 ui.horizontal(|ui| {
-                    ui.label("Current ruleset:");
+                    ui.label(tr("drc.current_ruleset"));
                     ui.label(egui::RichText::new(ruleset).strong().color(egui::Color32::from_rgb(46, 204, 113)));
                 });
                 ui.add_space(4.0);
             } else {
-                ui.label(egui::RichText::new("No DRC ruleset loaded").color(egui::Color32::from_rgb(231, 76, 60)));
+                ui.label(egui::RichText::new(tr("drc.no_ruleset")).color(egui::Color32::from_rgb(231, 76, 60)));
                 ui.add_space(4.0);
             }
             
             // PCB Manufacturer buttons
             ui.vertical(|ui| {
-                if ui.button("🏭 JLC PCB Rules").clicked() {
+                if ui.button(tr("drc.jlc")).clicked() {
                     app.drc_manager.current_ruleset = Some("JLC PCB".to_string());
                     logger.log_custom(
                         LOG_TYPE_DRC,
@@ -262,7 +160,7 @@ pub fn show_drc_panel<'a>(
                     );
                 }
                 
-                if ui.button("🏭 PCB WAY Rules").clicked() {
+                if ui.button(tr("drc.pcbway")).clicked() {
                     app.drc_manager.current_ruleset = Some("PCB WAY".to_string());
                     logger.log_custom(
                         LOG_TYPE_DRC,
@@ -270,7 +168,7 @@ pub fn show_drc_panel<'a>(
                     );
                 }
                 
-                if ui.button("🏭 Advanced Circuits Rules").clicked() {
+                if ui.button(tr("drc.advanced_circuits")).clicked() {
                     app.drc_manager.current_ruleset = Some("Advanced Circuits".to_string());
                     logger.log_custom(
                         LOG_TYPE_DRC,
@@ -282,7 +180,7 @@ pub fn show_drc_panel<'a>(
                 
                 // Clear ruleset button
                 if app.drc_manager.current_ruleset.is_some() {
-                    if ui.button("🗑 Clear Ruleset").clicked() {
+                    if ui.button(tr("drc.clear_ruleset")).clicked() {
                         if let Some(ref ruleset) = app.drc_manager.current_ruleset {
                             logger.log_custom(
                                 LOG_TYPE_DRC,
@@ -298,7 +196,8 @@ pub fn show_drc_panel<'a>(
     ui.add_space(4.0);
     
     // Trace Quality Analysis section
-    egui::CollapsingHeader::new("Trace Quality Analysis")
+    egui::CollapsingHeader::new(tr("drc.trace_quality"))
+        .id_salt("drc_trace_quality")
         .default_open(true)
         .show(ui, |ui| {
             ui.add_space(4.0);
@@ -314,7 +213,7 @@ pub fn show_drc_panel<'a>(
             
             // Display summary
             ui.horizontal(|ui| {
-                ui.label("Sharp Corners:");
+                ui.label(tr("drc.sharp_corners"));
                 ui.label(egui::RichText::new(&format!("{}", corner_count))
                     .color(if corner_count > 0 { 
                         egui::Color32::from_rgb(230, 126, 34) 
@@ -324,7 +223,7 @@ pub fn show_drc_panel<'a>(
                     
                 ui.separator();
                     
-                ui.label("Unnecessary Jogs:");
+                ui.label(tr("drc.unnecessary_jogs"));
                 ui.label(egui::RichText::new(&format!("{}", jog_count))
                     .color(if jog_count > 0 { 
                         egui::Color32::from_rgb(230, 126, 34) 
@@ -337,44 +236,12 @@ pub fn show_drc_panel<'a>(
             
             // Action buttons
             ui.horizontal(|ui| {
-                if ui.button("🔍 Analyze Corners").clicked() {
-                    logger.log_info("Starting trace quality analysis...");
-                    
-                    // Run the DRC check which includes quality analysis
-                    // Convert ECS layers to legacy format for DRC compatibility
-                    let legacy_layers = convert_ecs_to_legacy_layers(&mut app.ecs_world);
-                    let _violations = crate::drc_operations::run_simple_drc_check(
-                        &legacy_layers,
-                        &app.drc_manager.rules,
-                        &mut app.drc_manager.trace_quality_issues
-                    );
-                    
-                    let corner_issues = app.drc_manager.trace_quality_issues.iter()
-                        .filter(|issue| matches!(issue.issue_type, TraceQualityType::SharpCorner))
-                        .count();
-                        
-                    let jog_issues = app.drc_manager.trace_quality_issues.iter()
-                        .filter(|issue| matches!(issue.issue_type, TraceQualityType::UnnecessaryJog))
-                        .count();
-                    
-                    logger.log_info(&format!("Found {} sharp corners that could be rounded", corner_issues));
-                    logger.log_info(&format!("Found {} unnecessary jogs that could be simplified", jog_issues));
-                    
-                    // Log details of corner issues
-                    for issue in &app.drc_manager.trace_quality_issues {
-                        if matches!(issue.issue_type, TraceQualityType::SharpCorner) {
-                            logger.log_warning(&format!("🔧 Corner at ({:.2}, {:.2}): {}", 
-                                issue.location.0, issue.location.1, issue.description));
-                        }
-                    }
-                    
-                    if corner_issues == 0 && jog_issues == 0 {
-                        logger.log_info("✅ No trace quality issues found - excellent routing!");
-                    }
+                if ui.add_enabled(app.drc_job.is_none(), egui::Button::new(tr("drc.analyze_corners"))).clicked() {
+                    start_trace_quality_analysis(app, &logger);
                 }
                 
                 if corner_count > 0 {
-                    if ui.button("🔧 Fix Corners").clicked() {
+                    if ui.button(tr("drc.fix_corners")).clicked() {
                         logger.log_info("Starting corner rounding optimization...");
                         
                         let corners_to_fix = app.drc_manager.trace_quality_issues.iter()
@@ -453,11 +320,11 @@ pub fn show_drc_panel<'a>(
             // Clear overlay button
             ui.horizontal(|ui| {
                 if !app.drc_manager.corner_overlay_shapes.is_empty() {
-                    if ui.button("🗑 Clear Corner Overlay").clicked() {
+                    if ui.button(tr("drc.clear_corner_overlay")).clicked() {
                         app.drc_manager.corner_overlay_shapes.clear();
                         logger.log_info("Cleared corner overlay visualization");
                     }
-                    ui.label(format!("({} overlay shapes)", app.drc_manager.corner_overlay_shapes.len()));
+                    ui.label(tr_args("drc.overlay_shapes", &[("count", &app.drc_manager.corner_overlay_shapes.len())]));
                 }
             });
             
@@ -466,7 +333,7 @@ pub fn show_drc_panel<'a>(
             // Show detailed issues if any exist
             if !app.drc_manager.trace_quality_issues.is_empty() {
                 ui.separator();
-                ui.label("Quality Issues:");
+                ui.label(tr("drc.quality_issues"));
                 
                 egui::ScrollArea::vertical()
                     .max_height(150.0)
@@ -495,53 +362,547 @@ pub fn show_drc_panel<'a>(
         });
 }
 
-/// Helper function to convert ECS layers to legacy format for DRC compatibility
-use std::collections::HashMap;
-use gerber_viewer::GerberLayer;
+// DRC layer snapshot now lives with the checks; re-exported for existing callers
+pub use crate::drc_operations::LayerInfo;
+
+/// Ruleset name when a DRC run may start: none is running, a ruleset is loaded and the layer set is complete
+fn ruleset_to_run(app: &mut DemoLensApp, logger: &ReactiveEventLogger) -> Option<String> {
+    if app.drc_job.is_some() {
+        logger.log_warning("DRC is already running");
+        return None;
+    }
+    
+    // Check if a ruleset is loaded
+    let Some(ruleset) = app.drc_manager.current_ruleset.clone() else {
+        logger.log_warning("Cannot run DRC: No ruleset loaded");
+        logger.log_info("Please select a PCB manufacturer ruleset first");
+        return None;
+    };
+    
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Drc, "run DRC", logger).ok()?;
+    Some(ruleset)
+}
+
+/// Start DRC on a worker thread, re-checking only layers that changed since the last run
+pub(crate) fn run_drc(app: &mut DemoLensApp, logger: &ReactiveEventLogger, force_full: bool) {
+    let Some(ruleset) = ruleset_to_run(app, logger) else {
+        return;
+    };
+    
+    logger.log_info("Starting Design Rule Check");
+    logger.log_info(&format!("Using {} ruleset", ruleset));
+    
+    // Convert ECS layers to legacy format for DRC compatibility
+    let legacy_layers = crate::drc_operations::collect_drc_layers(&mut app.ecs_world);
+    let layer_hashes = crate::ecs::get_layer_content_hashes(&mut app.ecs_world);
+    let Some(plan) = app.drc_manager.prepare_incremental_check(&legacy_layers, &layer_hashes, force_full) else {
+        logger.log_warning("Cannot run DRC: No mechanical outline loaded to determine the PCB boundary");
+        return;
+    };
+    
+    run_drill_checks(app, logger);
+    run_mask_checks(app, logger);
+    
+    let project_key = app.project_manager.get_pcb_path().cloned();
+    app.drc_job = Some(crate::drc_operations::DrcJob::spawn(plan.snapshot, project_key, plan.cached_layers));
+}
+
+/// Start DRC on the copper and silkscreen inside a region drawn in the view (view coordinates)
+/// Each layer's primitives touching the region are checked afresh; drill and mask
+/// results are limited to the region as well
+pub(crate) fn run_regional_drc(app: &mut DemoLensApp, logger: &ReactiveEventLogger, region: crate::drc_operations::DrcRegion) {
+    let Some(ruleset) = ruleset_to_run(app, logger) else {
+        return;
+    };
+    
+    let layers = crate::drc_operations::collect_drc_layers(&mut app.ecs_world);
+    let Some(boundary) = crate::drc_operations::types::get_pcb_boundary(&layers) else {
+        logger.log_warning("Cannot run DRC: No mechanical outline loaded to determine the PCB boundary");
+        return;
+    };
+    logger.log_info(&format!("Starting regional Design Rule Check in {} with {} ruleset", region_label(&region, app.display_unit), ruleset));
+    
+    // The region in each layer's own coordinates, so rotation, mirroring and nudges match the screen
+    let mut source_bounds = std::collections::HashMap::new();
+    let mut regional_layers = Vec::new();
+    for (layer_type, layer_info) in &layers {
+        let Some(to_source) = crate::ecs::get_layer_render_transform(&mut app.ecs_world, &app.display_manager, *layer_type)
+            .and_then(|transform| transform.to_matrix().try_inverse())
+        else {
+            continue;
+        };
+        let (min, max) = region.source_bounds(&to_source);
+        source_bounds.insert(*layer_type, (min, max));
+        if !crate::drc_operations::types::has_layer_checks(layer_type) {
+            continue;
+        }
+        let primitives = crate::ecs::get_layer_primitives(&mut app.ecs_world, *layer_type);
+        if let Some(regional) = crate::drc_operations::region::regional_layer(layer_info, &primitives, min, max) {
+            regional_layers.push((regional, 0));
+        }
+    }
+    
+    // Drill holes and mask warnings are located in board coordinates, as on top copper
+    run_drill_checks(app, logger);
+    run_mask_checks(app, logger);
+    if let Some((min, max)) = source_bounds.get(&LayerType::Copper(1)).copied() {
+        let in_region = |violation: &crate::drc_operations::types::DrcViolation| crate::drc_operations::region::violation_in_bounds(violation, min, max);
+        app.drc_manager.drill_violations.retain(in_region);
+        app.drc_manager.mask_warnings.retain(in_region);
+    }
+    
+    let snapshot = crate::drc_operations::DrcSnapshot {
+        layers: regional_layers,
+        boundary,
+        rules: app.drc_manager.rules.clone(),
+        checks: app.drc_manager.checks,
+        outline: layers.get(&LayerType::MechanicalOutline).and_then(|outline| outline.primitives.clone()),
+    };
+    let project_key = app.project_manager.get_pcb_path().cloned();
+    let mut job = crate::drc_operations::DrcJob::spawn(snapshot, project_key, Vec::new());
+    job.region = Some(region);
+    job.region_bounds = source_bounds;
+    app.drc_job = Some(job);
+}
+
+/// Region corners as shown in the DRC panel and the log
+fn region_label(region: &crate::drc_operations::DrcRegion, unit: DisplayUnit) -> String {
+    let point = |p: nalgebra::Point2<f64>| format!("({}, {})", unit.format_mm(p.x, 3), unit.format_mm(p.y, 3));
+    format!("{} – {}", point(region.min), point(region.max))
+}
+
+/// "DRC in region" tool toggle, and the region of the current results with a button to clear it
+fn show_region_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    ui.horizontal(|ui| {
+        ui.toggle_value(&mut app.drc_region_armed, tr("drc.region_tool"))
+            .on_hover_text(tr("drc.region_tool_hint"));
+        if let Some(region) = app.drc_manager.region {
+            ui.label(egui::RichText::new(tr_args("drc.regional_run", &[("region", &region_label(&region, app.display_unit))])).italics());
+            if ui.small_button("✖").on_hover_text(tr("drc.clear_region")).clicked() {
+                app.drc_manager.clear_violations();
+            }
+        }
+    });
+    ui.add_space(4.0);
+}
+
+/// Start the corner and jog analysis on a worker thread; results replace the trace quality issues only
+fn start_trace_quality_analysis(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    logger.log_info("Starting trace quality analysis...");
+    
+    let layers = crate::drc_operations::collect_drc_layers(&mut app.ecs_world);
+    let Some(boundary) = crate::drc_operations::types::get_pcb_boundary(&layers) else {
+        logger.log_warning("Cannot analyze corners: No mechanical outline loaded to determine the PCB boundary");
+        return;
+    };
+    let snapshot = crate::drc_operations::DrcSnapshot {
+        layers: layers.into_values().filter(|info| info.layer_type.is_copper()).map(|info| (info, 0)).collect(),
+        boundary,
+        rules: app.drc_manager.rules.clone(),
+        checks: DrcCheckConfig::trace_quality_only(),
+        outline: None,
+    };
+    let project_key = app.project_manager.get_pcb_path().cloned();
+    let mut job = crate::drc_operations::DrcJob::spawn(snapshot, project_key, Vec::new());
+    job.trace_quality_only = true;
+    app.drc_job = Some(job);
+}
+
+/// Log the corner and jog counts of a finished trace quality analysis
+fn log_trace_quality_results(app: &DemoLensApp, logger: &ReactiveEventLogger) {
+    let corner_issues = app.drc_manager.trace_quality_issues.iter()
+        .filter(|issue| matches!(issue.issue_type, TraceQualityType::SharpCorner))
+        .count();
+        
+    let jog_issues = app.drc_manager.trace_quality_issues.iter()
+        .filter(|issue| matches!(issue.issue_type, TraceQualityType::UnnecessaryJog))
+        .count();
+    
+    logger.log_info(&format!("Found {} sharp corners that could be rounded", corner_issues));
+    logger.log_info(&format!("Found {} unnecessary jogs that could be simplified", jog_issues));
+    
+    // Log details of corner issues
+    for issue in &app.drc_manager.trace_quality_issues {
+        if matches!(issue.issue_type, TraceQualityType::SharpCorner) {
+            logger.log_warning(&format!("🔧 Corner at ({:.2}, {:.2}): {}", 
+                issue.location.0, issue.location.1, issue.description));
+        }
+    }
+    
+    if corner_issues == 0 && jog_issues == 0 {
+        logger.log_info("✅ No trace quality issues found - excellent routing!");
+    }
+}
+
+/// Save the last DRC results as a JSON report
+fn export_drc_report(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter(tr("drc.report_filter"), &["json"])
+        .set_file_name("drc_report.json")
+        .save_file()
+    else {
+        return;
+    };
+    
+    let pcb_path = app.project_manager.get_pcb_path().map(|path| path.as_path());
+    let mut report = app.drc_manager.build_report(pcb_path);
+    report.provenance = crate::ecs::get_provenance_summary(&mut app.ecs_world);
+    match crate::drc_operations::write_drc_report(&path, &report) {
+        Ok(()) => logger.log_info(&format!("Exported DRC report to {}", path.display())),
+        Err(e) => logger.log_error(&e),
+    }
+}
+
+/// Check the loaded drill holes; results are merged when the layer DRC completes
+fn run_drill_checks(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let (hole_count, violations) = crate::drc_operations::drill::check_loaded_drills(&mut app.ecs_world, &app.drc_manager.rules, &app.drc_manager.checks);
+    app.drc_manager.drill_violations = violations;
+    if hole_count == 0 {
+        logger.log_custom(LOG_TYPE_DRC, "No drill files loaded, skipping drill checks");
+        return;
+    }
+    
+    logger.log_custom(LOG_TYPE_DRC, &format!(
+        "Checked {} drill holes: {} drill violation(s)",
+        hole_count,
+        app.drc_manager.drill_violations.len()
+    ));
+}
 
-/// Simple LayerInfo for DRC compatibility (replaces layer_operations::LayerInfo)
-#[derive(Debug)]
-pub struct LayerInfo {
-    pub layer_type: LayerType,
-    pub gerber_layer: Option<GerberLayer>,
-    pub raw_gerber_data: Option<String>,
-    pub visible: bool,
-    pub color: egui::Color32,
+/// Keep the enabled checks in step with the open project
+/// Called every frame; loads the new project's checks (all enabled if never changed) when the project changes
+pub fn sync_drc_checks_project(app: &mut DemoLensApp) {
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let current_id = state.current_project.as_ref().map(|project| project.metadata.id.clone());
+    if current_id == app.drc_manager.checks_project {
+        return;
+    }
+
+    let checks = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_drc_checks(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load DRC checks: {}", e);
+            None
+        }),
+        _ => None,
+    };
+    app.drc_manager.checks = checks.unwrap_or_default();
+    app.drc_manager.checks_project = current_id;
 }
 
-impl LayerInfo {
-    pub fn new(layer_type: LayerType, gerber_layer: Option<GerberLayer>, raw_gerber_data: Option<String>, visible: bool) -> Self {
-        Self {
-            layer_type,
-            gerber_layer,
-            raw_gerber_data,
-            visible,
-            color: layer_type.color(),
+/// Store the enabled checks with the open project, if any
+fn save_drc_checks(app: &DemoLensApp) {
+    let (Some(project_id), Some(state)) = (&app.drc_manager.checks_project, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        if let Err(e) = database.save_drc_checks(project_id, &app.drc_manager.checks) {
+            eprintln!("Failed to save DRC checks: {}", e);
         }
     }
 }
 
-fn convert_ecs_to_legacy_layers(world: &mut bevy_ecs::world::World) -> HashMap<LayerType, LayerInfo> {
-    let mut legacy_layers = HashMap::new();
-    
-    for layer_type in LayerType::all() {
-        if let Some((_entity, layer_info, gerber_data, visibility)) = crate::ecs::get_layer_data(world, layer_type) {
-            // Create legacy LayerInfo from ECS data
-            let mut legacy_layer_info = LayerInfo::new(
-                layer_info.layer_type,
-                Some(gerber_data.0.clone()),
-                None, // Raw gerber data not needed for DRC
-                visibility.visible,
-            );
+/// Toggles for the individual checks, with fast and full presets
+fn show_check_toggles(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let before = app.drc_manager.checks;
+    egui::CollapsingHeader::new(tr("drc.checks"))
+        .id_salt("drc_checks")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.add_space(4.0);
+            let checks = &mut app.drc_manager.checks;
+            ui.checkbox(&mut checks.check_trace_width, tr("drc.check.trace_width"));
+            ui.checkbox(&mut checks.check_spacing, tr("drc.check.spacing"))
+                .on_hover_text(tr("drc.check.spacing_hint"));
+            ui.checkbox(&mut checks.check_annular, tr("drc.check.annular"))
+                .on_hover_text(tr("drc.check.annular_hint"));
+            ui.checkbox(&mut checks.check_corners, tr("drc.check.corners"))
+                .on_hover_text(tr("drc.check.quality_hint"));
+            ui.checkbox(&mut checks.check_jogs, tr("drc.check.jogs"))
+                .on_hover_text(tr("drc.check.quality_hint"));
+            ui.checkbox(&mut checks.check_silkscreen, tr("drc.check.silkscreen"))
+                .on_hover_text(tr("drc.check.silkscreen_hint"));
+            ui.checkbox(&mut checks.check_edge_clearance, tr("drc.check.edge_clearance"))
+                .on_hover_text(tr("drc.check.edge_clearance_hint"));
+            
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr("drc.checks_fast")).on_hover_text(tr("drc.checks_fast_hint")).clicked() {
+                    *checks = DrcCheckConfig::fast();
+                }
+                if ui.button(tr("drc.checks_all")).on_hover_text(tr("drc.checks_all_hint")).clicked() {
+                    *checks = DrcCheckConfig::default();
+                }
+            });
+        });
+    if app.drc_manager.checks != before {
+        save_drc_checks(app);
+    }
+}
+
+/// Layer picker with the thresholds overriding the global rules on that layer
+/// Only the rules of the per-layer checks can be overridden: trace width on copper, line
+/// width and text height on silkscreen
+fn show_layer_overrides(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    egui::CollapsingHeader::new(tr("drc.layer_overrides"))
+        .id_salt("drc_layer_overrides")
+        .default_open(false)
+        .show(ui, |ui| {
+            let rules = &mut app.drc_manager.rules;
+            let unit = rules.display_unit();
             
-            // Get color from ECS render properties
-            if let Some(render_props) = crate::ecs::get_layer_render_properties(world, layer_type) {
-                legacy_layer_info.color = render_props.color;
+            // The picked layer is view state only
+            let layer_id = egui::Id::new("drc_override_layer");
+            let mut layer = ui.data(|data| data.get_temp::<LayerType>(layer_id)).unwrap_or(LayerType::Silkscreen(crate::ecs::Side::Top));
+            egui::ComboBox::from_id_salt("drc_override_layer")
+                .selected_text(layer.display_name())
+                .show_ui(ui, |ui| {
+                    for choice in LayerType::all().into_iter().filter(crate::drc_operations::types::has_layer_checks) {
+                        let marker = if rules.layer_overrides.contains_key(&choice) { " ●" } else { "" };
+                        ui.selectable_value(&mut layer, choice, format!("{}{}", choice.display_name(), marker));
+                    }
+                });
+            ui.data_mut(|data| data.insert_temp(layer_id, layer));
+            
+            let mut overridden = rules.layer_overrides.contains_key(&layer);
+            if ui.checkbox(&mut overridden, tr("drc.override_layer"))
+                .on_hover_text(tr("drc.override_layer_hint"))
+                .changed()
+            {
+                if overridden {
+                    rules.add_layer_override(layer);
+                } else {
+                    rules.layer_overrides.remove(&layer);
+                }
             }
             
-            legacy_layers.insert(layer_type, legacy_layer_info);
+            let global = rules.clone();
+            let Some(layer_rules) = rules.layer_overrides.get_mut(&layer) else {
+                ui.label(egui::RichText::new(tr("drc.override_uses_global")).italics());
+                return;
+            };
+            if layer.is_copper() {
+                override_field(ui, tr("drc.rule.min_trace_width"), &mut layer_rules.min_trace_width, global.min_trace_width, unit, 0.05..=2.0);
+                override_field(ui, tr("drc.rule.min_edge_clearance"), &mut layer_rules.min_edge_clearance, global.min_edge_clearance, unit, 0.0..=5.0);
+            } else {
+                override_field(ui, tr("drc.rule.min_silk_line_width"), &mut layer_rules.min_silk_line_width, global.min_silk_line_width, unit, 0.02..=1.0);
+                override_field(ui, tr("drc.rule.min_silk_text_height"), &mut layer_rules.min_silk_text_height, global.min_silk_text_height, unit, 0.1..=5.0);
+            }
+        });
+}
+
+/// Layer threshold that follows the global rule until its box is ticked
+fn override_field(
+    ui: &mut egui::Ui,
+    label: &str,
+    value_mm: &mut Option<f32>,
+    global_mm: f32,
+    unit: DisplayUnit,
+    range_mm: std::ops::RangeInclusive<f64>,
+) {
+    ui.horizontal(|ui| {
+        let mut overridden = value_mm.is_some();
+        if ui.checkbox(&mut overridden, label).on_hover_text(tr("drc.override_field_hint")).changed() {
+            *value_mm = overridden.then_some(global_mm);
+        }
+        match value_mm {
+            Some(value) => {
+                ui.add(UnitValue::new(value, unit).range_mm(range_mm));
+            }
+            None => {
+                ui.label(egui::RichText::new(unit.format_mm(global_mm as f64, 3)).weak());
+            }
         }
+    });
+}
+
+/// Labelled rule threshold stored in mm
+fn rule_field(ui: &mut egui::Ui, label: &str, value_mm: &mut f32, unit: DisplayUnit, range_mm: std::ops::RangeInclusive<f64>) -> egui::Response {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(UnitValue::new(value_mm, unit).range_mm(range_mm));
+    }).response
+}
+
+/// Soldermask sliver and registration toggles, thresholds and results
+fn show_mask_checks_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    egui::CollapsingHeader::new(tr("drc.mask_checks"))
+        .id_salt("drc_mask_checks")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.add_space(4.0);
+            let rules = &mut app.drc_manager.rules;
+            let unit = rules.display_unit();
+            
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut rules.mask_sliver_check, tr("drc.rule.min_mask_sliver"));
+                ui.add_enabled(rules.mask_sliver_check, UnitValue::new(&mut rules.min_mask_sliver, unit)
+                    .range_mm(0.01..=1.0)
+                    .speed_mm(0.005));
+            }).response.on_hover_text(tr("drc.rule.min_mask_sliver_hint"));
+            
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut rules.mask_registration_check, tr("drc.rule.max_mask_expansion"));
+                ui.add_enabled(rules.mask_registration_check, UnitValue::new(&mut rules.max_mask_expansion, unit)
+                    .range_mm(0.01..=1.0)
+                    .speed_mm(0.005));
+            }).response.on_hover_text(tr("drc.rule.max_mask_expansion_hint"));
+            
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button(tr("drc.run_mask_checks")).clicked() {
+                    run_mask_checks(app, logger);
+                }
+                
+                let count = app.drc_manager.mask_warnings.len();
+                ui.label(egui::RichText::new(tr_args(if count == 1 { "drc.warning_one" } else { "drc.warnings" }, &[("count", &count)]))
+                    .color(if count > 0 { MASK_WARNING_TEXT_COLOR } else { egui::Color32::from_rgb(46, 204, 113) }));
+            });
+            
+            if !app.drc_manager.mask_warnings.is_empty() {
+                egui::ScrollArea::vertical()
+                    .id_salt("mask_warnings")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for warning in &app.drc_manager.mask_warnings {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("⚠ {}: {}", warning.layer, warning.description));
+                                ui.label(egui::RichText::new(format!("({:.2}, {:.2})", warning.x, warning.y))
+                                    .color(egui::Color32::GRAY));
+                            });
+                        }
+                    });
+            }
+        });
+}
+
+/// Check soldermask slivers and registration on both sides; results are kept as warnings
+fn run_mask_checks(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let rules = &app.drc_manager.rules;
+    if !rules.mask_sliver_check && !rules.mask_registration_check {
+        app.drc_manager.mask_warnings.clear();
+        return;
+    }
+    
+    let Some(warnings) = crate::drc_operations::mask::check_loaded_masks(&mut app.ecs_world, rules) else {
+        app.drc_manager.mask_warnings.clear();
+        logger.log_custom(LOG_TYPE_DRC, "No soldermask layers loaded, skipping mask checks");
+        return;
+    };
+    
+    logger.log_custom(LOG_TYPE_DRC, &format!("Mask checks: {} warning(s)", warnings.len()));
+    for warning in &warnings {
+        logger.log_warning(&format!("⚠️  {}", warning.format_message()));
     }
+    app.drc_manager.mask_warnings = warnings;
+}
+
+/// Poll the background DRC job and merge its results once complete
+/// Called every frame from the app so results land even when the DRC tab is hidden
+pub fn poll_drc_job(app: &mut DemoLensApp, ctx: &egui::Context) {
+    let Some(job) = app.drc_job.as_mut() else {
+        return;
+    };
+    
+    let outcome = job.poll();
+    
+    // Show each finished layer's markers right away, unless another project was loaded meanwhile
+    let completed = job.take_completed();
+    if !completed.is_empty() && job.shows_layers_as_they_finish(app.project_manager.get_pcb_path()) {
+        app.drc_manager.apply_layer_results(completed);
+    }
+    
+    let Some(outcome) = outcome else {
+        // Keep repainting so the progress bar advances
+        ctx.request_repaint();
+        return;
+    };
     
-    legacy_layers
-}
\ No newline at end of file
+    let job = app.drc_job.take().expect("DRC job checked above");
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    
+    let results = match outcome {
+        DrcJobOutcome::Finished(results) => results,
+        DrcJobOutcome::Cancelled => {
+            // Layers checked before the cancel are already listed; the rest only have outdated results
+            if job.shows_layers_as_they_finish(app.project_manager.get_pcb_path()) && !job.unfinished_layers().is_empty() {
+                app.drc_manager.clear_layer_results(job.unfinished_layers());
+                let names: Vec<String> = job.unfinished_layers().iter().map(|l| l.display_name()).collect();
+                logger.log_warning(&format!("DRC cancelled; cleared the outdated results of {}", names.join(", ")));
+            } else {
+                logger.log_warning("DRC cancelled");
+            }
+            return;
+        }
+        DrcJobOutcome::Failed(error) => {
+            logger.log_error(&format!("DRC failed: {}", error));
+            return;
+        }
+    };
+    
+    // Discard results computed for a project that is no longer loaded
+    if job.project_key.as_ref() != app.project_manager.get_pcb_path() {
+        logger.log_warning("Discarded DRC results from a previously loaded project");
+        return;
+    }
+    
+    if job.trace_quality_only {
+        app.drc_manager.trace_quality_issues = results.into_iter().flat_map(|result| result.trace_quality_issues).collect();
+        log_trace_quality_results(app, &logger);
+        return;
+    }
+    
+    for result in &results {
+        logger.log_custom(LOG_TYPE_DRC, &format!("Checked {}", result.layer_type.display_name()));
+    }
+    if !job.cached_layers.is_empty() {
+        let names: Vec<String> = job.cached_layers.iter().map(|l| l.display_name()).collect();
+        logger.log_custom(LOG_TYPE_DRC, &format!("Unchanged, reused cached results: {}", names.join(", ")));
+    }
+    
+    match job.region {
+        Some(region) => app.drc_manager.apply_regional_results(results, region, &job.region_bounds),
+        None => app.drc_manager.apply_layer_results(results),
+    }
+    
+    // Report violations; ignored ones are only counted
+    let violations: Vec<_> = app.drc_manager.active_violations().map(|(_, violation)| violation).collect();
+    let ignored = app.drc_manager.violations.len() - violations.len();
+    if ignored > 0 {
+        logger.log_custom(LOG_TYPE_DRC, &format!("{} ignored violation(s) not reported", ignored));
+    }
+    if violations.is_empty() {
+        logger.log_info("✅ No violations found");
+        logger.log_info("DRC analysis completed successfully");
+    } else {
+        logger.log_warning(&format!("⚠️  Found {} violation(s):", violations.len()));
+        for violation in violations {
+            logger.log_error(&format!("❌ {}", violation.format_message()));
+        }
+        logger.log_info("DRC analysis completed with violations");
+    }
+}
+
+/// Progress bar and cancel button for a running DRC job
+fn show_drc_progress(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let Some(job) = &app.drc_job else {
+        return;
+    };
+    
+    ui.horizontal(|ui| {
+        let text = if job.is_cancelling() {
+            tr("drc.progress_cancelling").to_string()
+        } else if job.current_layer.is_empty() {
+            tr("drc.progress_starting").to_string()
+        } else {
+            tr_args("drc.progress_checking", &[("layer", &job.current_layer)])
+        };
+        ui.add(egui::ProgressBar::new(job.progress).text(text).desired_width(240.0));
+        
+        if ui.add_enabled(!job.is_cancelling(), egui::Button::new(tr("drc.cancel"))).clicked() {
+            job.cancel();
+        }
+    });
+    ui.add_space(4.0);
+}
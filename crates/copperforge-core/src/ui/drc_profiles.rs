@@ -0,0 +1,178 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::drc_operations::profiles::{self, DrcRuleProfile};
+use crate::project::constants::LOG_TYPE_DRC;
+use crate::ui::i18n::tr;
+
+/// Rule profiles offered in the DRC panel and the Save-as name being typed
+#[derive(Default)]
+pub struct DrcProfileState {
+    pub profiles: Vec<DrcRuleProfile>,
+    pub save_as_name: String,
+    loaded: bool,
+}
+
+impl DrcProfileState {
+    /// Re-read the profiles directory the next time the panel is shown
+    pub fn invalidate(&mut self) {
+        self.loaded = false;
+    }
+}
+
+/// Re-read the profiles directory, logging files that failed or were filled with defaults
+fn reload_profiles(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let (profiles, problems) = profiles::load_profiles(&profiles::profiles_dir(&app.config_path));
+    for problem in problems {
+        logger.log_warning(&problem);
+    }
+    app.drc_profiles.profiles = profiles;
+    app.drc_profiles.loaded = true;
+}
+
+fn apply_profile(app: &mut DemoLensApp, profile: &DrcRuleProfile, logger: &ReactiveEventLogger) {
+    app.drc_manager.update_rules(profile.rules.clone());
+    app.drc_manager.current_ruleset = Some(profile.name.clone());
+    logger.log_custom(LOG_TYPE_DRC, &format!("Loaded rule profile \"{}\"", profile.name));
+}
+
+/// Apply the profile recorded in the project config, if it still exists
+/// Unknown names are kept as the ruleset name so the report still shows what was used
+pub fn restore_profile(app: &mut DemoLensApp, name: &str) {
+    let (available, problems) = profiles::load_profiles(&profiles::profiles_dir(&app.config_path));
+    for problem in problems {
+        eprintln!("{}", problem);
+    }
+    match available.iter().find(|profile| profile.name == name) {
+        Some(profile) => app.drc_manager.update_rules(profile.rules.clone()),
+        None => eprintln!("Rule profile \"{}\" not found; keeping the default rules", name),
+    }
+    app.drc_manager.current_ruleset = Some(name.to_string());
+}
+
+fn current_profile(app: &DemoLensApp) -> DrcRuleProfile {
+    DrcRuleProfile {
+        name: app.drc_manager.current_ruleset.clone().unwrap_or_else(|| "Custom".to_string()),
+        description: String::new(),
+        rules: app.drc_manager.rules.clone(),
+    }
+}
+
+fn save_as(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let profile = DrcRuleProfile { name: app.drc_profiles.save_as_name.trim().to_string(), ..current_profile(app) };
+    match profiles::save_profile(&profile, &profiles::profiles_dir(&app.config_path)) {
+        Ok(path) => {
+            logger.log_info(&format!("Saved rule profile \"{}\" to {}", profile.name, path.display()));
+            app.drc_manager.current_ruleset = Some(profile.name);
+            app.drc_profiles.save_as_name.clear();
+            reload_profiles(app, logger);
+        }
+        Err(e) => logger.log_error(&format!("Save rule profile failed: {}", e)),
+    }
+}
+
+/// Import a shared profile into the profiles directory and make it active
+fn import_profile(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(path) = rfd::FileDialog::new()
+        .set_title(tr("drc.profile_import_title"))
+        .add_filter(tr("drc.profile_filter"), &["json"])
+        .pick_file()
+    else {
+        return;
+    };
+    let (profile, missing) = match profiles::load_profile_file(&path) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            logger.log_error(&format!("Import rule profile failed: {}", e));
+            return;
+        }
+    };
+    if !missing.is_empty() {
+        logger.log_warning(&profiles::missing_fields_warning(&profile.name, &missing));
+    }
+    if profiles::is_builtin_profile(&profile.name) {
+        logger.log_error(&format!("Import rule profile failed: \"{}\" is a built-in profile name; rename it in the file", profile.name));
+        return;
+    }
+    match profiles::save_profile(&profile, &profiles::profiles_dir(&app.config_path)) {
+        Ok(_) => {
+            apply_profile(app, &profile, logger);
+            reload_profiles(app, logger);
+        }
+        Err(e) => logger.log_error(&format!("Import rule profile failed: {}", e)),
+    }
+}
+
+fn export_profile(app: &DemoLensApp, logger: &ReactiveEventLogger) {
+    let profile = current_profile(app);
+    let Some(path) = rfd::FileDialog::new()
+        .set_title(tr("drc.profile_export_title"))
+        .add_filter(tr("drc.profile_filter"), &["json"])
+        .set_file_name(format!("{}.json", profile.name))
+        .save_file()
+    else {
+        return;
+    };
+    match profiles::export_profile(&profile, &path) {
+        Ok(()) => logger.log_info(&format!("Exported rule profile \"{}\" to {}", profile.name, path.display())),
+        Err(e) => logger.log_error(&format!("Export rule profile failed: {}", e)),
+    }
+}
+
+/// Profile dropdown with Save-as, Import and Export
+pub fn show_profile_controls(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if !app.drc_profiles.loaded {
+        reload_profiles(app, logger);
+    }
+
+    let selected = app.drc_manager.current_ruleset.clone().unwrap_or_else(|| tr("drc.profile_custom").to_string());
+    let mut chosen = None;
+    ui.horizontal(|ui| {
+        ui.label(tr("drc.profile"));
+        egui::ComboBox::from_id_salt("drc_rule_profile")
+            .selected_text(&selected)
+            .width(200.0)
+            .show_ui(ui, |ui| {
+                for profile in &app.drc_profiles.profiles {
+                    let label = if profiles::is_builtin_profile(&profile.name) {
+                        format!("🔒 {}", profile.name)
+                    } else {
+                        profile.name.clone()
+                    };
+                    let response = ui.selectable_label(profile.name == selected, label);
+                    let response = if profile.description.is_empty() { response } else { response.on_hover_text(&profile.description) };
+                    if response.clicked() {
+                        chosen = Some(profile.clone());
+                    }
+                }
+            });
+        if ui.button(tr("drc.profile_import")).on_hover_text(tr("drc.profile_import_hint")).clicked() {
+            import_profile(app, logger);
+        }
+        if ui.button(tr("drc.profile_export")).on_hover_text(tr("drc.profile_export_hint")).clicked() {
+            export_profile(app, logger);
+        }
+    });
+    if let Some(profile) = chosen {
+        apply_profile(app, &profile, logger);
+    }
+
+    let active_builtin = app.drc_manager.current_ruleset.as_deref().is_some_and(profiles::is_builtin_profile);
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut app.drc_profiles.save_as_name)
+            .hint_text(tr("drc.profile_name_hint"))
+            .desired_width(160.0));
+        let name = app.drc_profiles.save_as_name.trim();
+        if ui.add_enabled(!name.is_empty() && !profiles::is_builtin_profile(name), egui::Button::new(tr("drc.profile_save_as")))
+            .on_hover_text(tr("drc.profile_save_as_hint"))
+            .on_disabled_hover_text(tr("drc.profile_save_as_disabled"))
+            .clicked()
+        {
+            save_as(app, logger);
+        }
+    });
+    if active_builtin {
+        ui.label(RichText::new(tr("drc.profile_builtin_read_only")).small().color(Color32::GRAY));
+    }
+}
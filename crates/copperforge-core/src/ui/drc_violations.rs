@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+
+use egui::{Color32, Painter, Rect, RichText, Stroke};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::drc_operations::types::DrcViolation;
+use crate::drc_operations::violation_key;
+use crate::project::constants::LOG_TYPE_DRC;
+use crate::ui::i18n::{tr, tr_args};
+
+const PULSE_SECS: f64 = 1.5;
+const PULSE_PERIOD_SECS: f64 = 0.5;
+const PULSE_COLOR: Color32 = Color32::from_rgb(255, 220, 0);
+const PASS_COLOR: Color32 = Color32::from_rgb(46, 204, 113);
+
+/// Selection in the violation tree and the marker being brought into view
+#[derive(Default)]
+pub struct DrcViolationListState {
+    /// `violation_key` of the selected row
+    pub selected: Option<String>,
+    ignore_reason: String,
+    /// Violation to center in the gerber view on its next frame
+    pending_focus: Option<String>,
+    /// Violation whose marker is pulsing, and when the pulse started
+    pulse: Option<(String, f64)>,
+}
+
+fn find_violation<'a>(app: &'a DemoLensApp, key: &str) -> Option<&'a DrcViolation> {
+    app.drc_manager.violations.iter().find(|violation| violation_key(violation) == key)
+}
+
+/// Violations grouped by rule, then layer, as indices into `violations`
+fn group_violations<'a>(violations: impl Iterator<Item = (usize, &'a DrcViolation)>) -> BTreeMap<String, BTreeMap<String, Vec<usize>>> {
+    let mut groups: BTreeMap<String, BTreeMap<String, Vec<usize>>> = BTreeMap::new();
+    for (index, violation) in violations {
+        groups.entry(violation.rule_name.clone())
+            .or_default()
+            .entry(violation.layer.clone())
+            .or_default()
+            .push(index);
+    }
+    groups
+}
+
+/// Violations grouped by rule and layer; clicking a row brings it into view
+pub fn show_violation_tree(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if !app.drc_manager.has_results {
+        ui.label(RichText::new(tr("drc.list_empty")).color(Color32::GRAY));
+        return;
+    }
+
+    let groups = group_violations(app.drc_manager.active_violations());
+    let ignored_count = app.drc_manager.violations.len() - app.drc_manager.active_violations().count();
+    if groups.is_empty() {
+        ui.label(RichText::new(tr_args("drc.list_none", &[("ignored", &ignored_count)])).color(PASS_COLOR));
+    }
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical()
+        .id_salt("drc_violation_tree")
+        .max_height(250.0)
+        .show(ui, |ui| {
+            for (rule, layers) in &groups {
+                let rule_count: usize = layers.values().map(Vec::len).sum();
+                egui::CollapsingHeader::new(format!("{} ({})", rule, rule_count))
+                    .id_salt(("drc_rule_group", rule))
+                    .default_open(groups.len() == 1)
+                    .show(ui, |ui| {
+                        for (layer, indices) in layers {
+                            egui::CollapsingHeader::new(format!("{} ({})", layer, indices.len()))
+                                .id_salt(("drc_layer_group", rule, layer))
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    for &index in indices {
+                                        let violation = &app.drc_manager.violations[index];
+                                        let key = violation_key(violation);
+                                        let selected = app.drc_violation_list.selected.as_deref() == Some(key.as_str());
+                                        let text = tr_args("drc.list_row", &[
+                                            ("index", &(index + 1)),
+                                            ("description", &violation.description),
+                                            ("measured", &format!("{:.3}", violation.measured_value)),
+                                            ("required", &format!("{:.3}", violation.required_value)),
+                                            ("x", &format!("{:.2}", violation.x)),
+                                            ("y", &format!("{:.2}", violation.y)),
+                                        ]);
+                                        if ui.selectable_label(selected, text).on_hover_text(tr("drc.list_show_hint")).clicked() {
+                                            clicked = Some(key);
+                                        }
+                                    }
+                                });
+                        }
+                    });
+            }
+        });
+
+    if let Some(key) = clicked {
+        app.drc_violation_list.selected = Some(key.clone());
+        app.drc_violation_list.pending_focus = Some(key);
+    }
+
+    show_selected_violation(ui, app, logger);
+    show_ignored_violations(ui, app, logger);
+}
+
+/// Copy and ignore actions for the selected violation
+fn show_selected_violation(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(key) = app.drc_violation_list.selected.clone() else {
+        return;
+    };
+    let Some(violation) = find_violation(app, &key).filter(|v| !app.drc_manager.ignored.is_ignored(v)).cloned() else {
+        app.drc_violation_list.selected = None;
+        return;
+    };
+
+    ui.add_space(4.0);
+    ui.group(|ui| {
+        ui.label(violation.format_message());
+        ui.horizontal(|ui| {
+            if ui.button(tr("drc.copy_violation")).clicked() {
+                ui.ctx().copy_text(violation.format_message());
+                logger.log_info("Copied DRC violation to clipboard");
+            }
+            if ui.button(tr("drc.show_violation")).clicked() {
+                app.drc_violation_list.pending_focus = Some(key.clone());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut app.drc_violation_list.ignore_reason)
+                .hint_text(tr("drc.ignore_reason_hint"))
+                .desired_width(180.0));
+            let reason = app.drc_violation_list.ignore_reason.trim().to_string();
+            if ui.add_enabled(!reason.is_empty(), egui::Button::new(tr("drc.ignore"))
+                .on_hover_text(tr("drc.ignore_hint"))
+                .clicked()
+            {
+                app.drc_manager.ignored.ignore(&violation, &reason);
+                app.drc_violation_list.selected = None;
+                app.drc_violation_list.ignore_reason.clear();
+                logger.log_custom(LOG_TYPE_DRC, &format!("Ignored {} ({})", violation.format_message(), reason));
+            }
+        });
+    });
+}
+
+/// Collapsed list of ignored violations with their reasons
+fn show_ignored_violations(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if app.drc_manager.ignored.is_empty() {
+        return;
+    }
+
+    let mut restore = None;
+    egui::CollapsingHeader::new(tr_args("drc.ignored", &[("count", &app.drc_manager.ignored.len())]))
+        .id_salt("drc_ignored_violations")
+        .default_open(false)
+        .show(ui, |ui| {
+            for (key, entry) in app.drc_manager.ignored.iter() {
+                let found = find_violation(app, key).is_some();
+                ui.horizontal(|ui| {
+                    if ui.small_button("↩").on_hover_text(tr("drc.stop_ignoring")).clicked() {
+                        restore = Some(key.clone());
+                    }
+                    ui.label(RichText::new(&entry.message).color(if found { Color32::LIGHT_GRAY } else { Color32::GRAY }));
+                });
+                let reason = if found {
+                    tr_args("drc.ignored_reason", &[("reason", &entry.reason)])
+                } else {
+                    tr_args("drc.ignored_reason_stale", &[("reason", &entry.reason)])
+                };
+                ui.label(RichText::new(format!("    {}", reason)).italics().color(Color32::GRAY));
+            }
+        });
+
+    if let Some(key) = restore {
+        app.drc_manager.ignored.restore(&key);
+        logger.log_custom(LOG_TYPE_DRC, "Restored an ignored DRC violation");
+    }
+}
+
+/// Center the clicked violation in the view and start its marker pulse
+pub fn focus_pending_violation(app: &mut DemoLensApp, viewport: &Rect, now: f64) {
+    let Some(key) = app.drc_violation_list.pending_focus.take() else {
+        return;
+    };
+    let Some(violation) = find_violation(app, &key) else {
+        return;
+    };
+
+    let screen_pos = crate::ui::tabs::violation_screen_pos(app, violation);
+    app.view_state.translation += viewport.center() - screen_pos;
+    app.drc_violation_list.pulse = Some((key, now));
+}
+
+/// Expanding rings around the focused violation's marker
+pub fn draw_violation_pulse(app: &mut DemoLensApp, painter: &Painter, now: f64) {
+    let Some((key, started)) = &app.drc_violation_list.pulse else {
+        return;
+    };
+    let elapsed = now - started;
+    let violation = find_violation(app, key);
+    let Some(violation) = violation.filter(|_| elapsed < PULSE_SECS) else {
+        app.drc_violation_list.pulse = None;
+        return;
+    };
+
+    let phase = ((elapsed / PULSE_PERIOD_SECS).fract()) as f32;
+    let center = crate::ui::tabs::violation_screen_pos(app, violation);
+    painter.circle_stroke(center, 6.0 + 24.0 * phase, Stroke::new(2.5, PULSE_COLOR.gamma_multiply(1.0 - phase)));
+    painter.ctx().request_repaint();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(rule: &str, layer: &str) -> DrcViolation {
+        DrcViolation {
+            rule_name: rule.to_string(),
+            description: String::new(),
+            layer: layer.to_string(),
+            measured_value: 0.0,
+            required_value: 0.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_group_violations_by_rule_then_layer() {
+        let violations = [
+            violation("Min Spacing", "Bottom Copper"),
+            violation("Min Trace Width", "Top Copper"),
+            violation("Min Spacing", "Top Copper"),
+            violation("Min Spacing", "Bottom Copper"),
+        ];
+        let groups = group_violations(violations.iter().enumerate());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["Min Spacing"]["Bottom Copper"], vec![0, 3]);
+        assert_eq!(groups["Min Spacing"]["Top Copper"], vec![2]);
+    }
+}
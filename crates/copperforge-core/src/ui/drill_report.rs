@@ -0,0 +1,152 @@
+use egui::{Color32, Painter, Pos2, Stroke, Vec2};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ecs::{drill_symbol_style, DrillReport, DRILL_SYMBOL_RING_SPACING};
+use crate::ui::i18n::{tr, tr_args};
+
+/// Drill symbols stand out against copper, mask and the hole rings
+const DRILL_SYMBOL_COLOR: Color32 = Color32::from_rgb(0, 220, 255);
+/// Smallest drawn symbol, so symbols stay apart from each other when zoomed out
+const MIN_SYMBOL_RADIUS: f32 = 4.0;
+
+/// Drill report window and the symbol overlay on the drill holes
+#[derive(Default)]
+pub struct DrillReportState {
+    pub open: bool,
+    /// Mark every hole with the symbol of its diameter
+    pub show_symbols: bool,
+}
+
+/// Symbol of a drill size centred on `center`, drawn as in the fab drawing drill map
+/// (see `drill_symbol_style`)
+pub fn paint_drill_symbol(painter: &Painter, symbol: usize, center: Pos2, radius: f32, stroke: Stroke) {
+    let (shape, filled, rings) = drill_symbol_style(symbol);
+    let fill = if filled { stroke.color } else { Color32::TRANSPARENT };
+    for ring in 1..=rings {
+        painter.circle_stroke(center, radius * (1.0 + ring as f32 * DRILL_SYMBOL_RING_SPACING as f32), stroke);
+    }
+    let polygon = |corners: &[(f32, f32)]| {
+        let points = corners.iter().map(|(dx, dy)| center + Vec2::new(dx * radius, dy * radius)).collect();
+        painter.add(egui::Shape::convex_polygon(points, fill, stroke));
+    };
+    let cross = |diagonal: bool| {
+        let (dx, dy) = if diagonal { (radius, radius) } else { (radius, 0.0) };
+        painter.line_segment([center - Vec2::new(dx, dy), center + Vec2::new(dx, dy)], stroke);
+        painter.line_segment([center - Vec2::new(-dy, dx), center + Vec2::new(-dy, dx)], stroke);
+    };
+    match shape {
+        0 => {
+            painter.circle(center, radius, fill, stroke);
+            cross(false);
+        }
+        1 => cross(true),
+        2 => polygon(&[(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]),
+        3 => polygon(&[(0.0, -1.0), (0.866, 0.5), (-0.866, 0.5)]),
+        4 => polygon(&[(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]),
+        5 => {
+            painter.circle(center, radius, fill, stroke);
+        }
+        6 => polygon(&[(1.0, 0.0), (0.5, 0.866), (-0.5, 0.866), (-1.0, 0.0), (-0.5, -0.866), (0.5, -0.866)]),
+        _ => cross(false),
+    }
+}
+
+/// Mark each drill hole with the symbol of its diameter, registered like the holes themselves
+pub fn render_drill_symbols(app: &mut DemoLensApp, painter: &Painter) {
+    if !app.drill_report.show_symbols {
+        return;
+    }
+    let holes = crate::ecs::get_drill_holes(&app.ecs_world);
+    if holes.is_empty() {
+        return;
+    }
+    let Some(transform) = crate::ecs::get_drill_render_transform(&mut app.ecs_world, &app.display_manager) else {
+        return;
+    };
+    let matrix = transform.to_matrix();
+    let report = crate::ecs::drill_report(&holes);
+    let stroke = Stroke::new(1.5, DRILL_SYMBOL_COLOR);
+    for hole in &holes {
+        let Some(symbol) = report.symbol_for(hole.diameter) else {
+            continue;
+        };
+        let center = app.view_state.gerber_to_screen_coords(matrix.transform_point(&hole.position));
+        let radius = (hole.diameter as f32 * app.view_state.scale * 0.35).max(MIN_SYMBOL_RADIUS);
+        paint_drill_symbol(painter, symbol, center, radius, stroke);
+    }
+}
+
+fn export_csv(report: &DrillReport, logger: &ReactiveEventLogger) {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .set_file_name("drill_report.csv")
+        .save_file()
+    else {
+        return;
+    };
+    match std::fs::write(&path, report.to_csv()) {
+        Ok(()) => logger.log_info(&format!("Exported drill report to {}", path.display())),
+        Err(e) => logger.log_error(&format!("Failed to write {}: {}", path.display(), e)),
+    }
+}
+
+/// Window with the drill report: hole count per diameter, plated and non-plated apart
+pub fn show_drill_report_window(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if !app.drill_report.open {
+        return;
+    }
+
+    let report = crate::ecs::drill_report(&crate::ecs::get_drill_holes(&app.ecs_world));
+    let unit = app.display_unit;
+    let mut open = true;
+    egui::Window::new(tr("drill_report.title"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_width(360.0)
+        .show(ctx, |ui| {
+            if report.rows.is_empty() {
+                ui.label(tr("drill_report.empty"));
+                return;
+            }
+            egui::Grid::new("drill_report_grid")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong(tr("drill_report.symbol"));
+                    ui.strong(tr("drill_report.diameter"));
+                    ui.strong(tr("drill_report.plated"));
+                    ui.strong(tr("drill_report.non_plated"));
+                    ui.strong(tr("drill_report.count"));
+                    ui.end_row();
+                    // Tall enough for the rings of the last symbol
+                    let rings = report.rows.last().map_or(0, |row| drill_symbol_style(row.symbol).2);
+                    let symbol_size = 2.0 * 6.0 * (1.0 + rings as f32 * DRILL_SYMBOL_RING_SPACING as f32) + 4.0;
+                    for row in &report.rows {
+                        let (rect, _) = ui.allocate_exact_size(Vec2::new(symbol_size.max(20.0), symbol_size), egui::Sense::hover());
+                        paint_drill_symbol(ui.painter(), row.symbol, rect.center(), 6.0, Stroke::new(1.5, ui.visuals().text_color()));
+                        ui.label(unit.format_mm(row.diameter, 3));
+                        ui.label(row.plated.to_string());
+                        ui.label(row.non_plated.to_string());
+                        ui.label(row.count().to_string());
+                        ui.end_row();
+                    }
+                    ui.label("");
+                    ui.strong(tr("drill_report.total"));
+                    ui.strong(report.plated().to_string());
+                    ui.strong(report.non_plated().to_string());
+                    ui.strong(report.total().to_string());
+                    ui.end_row();
+                });
+            ui.label(tr_args("drill_report.summary", &[("sizes", &report.rows.len()), ("total", &report.total())]));
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut app.drill_report.show_symbols, tr("drill_report.show_symbols"))
+                    .on_hover_text(tr("drill_report.show_symbols_hint"));
+                if ui.button(tr("drill_report.export_csv")).clicked() {
+                    export_csv(&report, logger);
+                }
+            });
+        });
+    app.drill_report.open = open;
+}
@@ -0,0 +1,128 @@
+use egui::{Color32, FontId, Rect, Stroke, Vec2};
+use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
+
+use crate::DemoLensApp;
+use crate::ecs::UnitsResource;
+use crate::ui::minimap::ViewportCorner;
+
+const DRO_MARGIN: f32 = 10.0;
+const DRO_PADDING: f32 = 6.0;
+const DRO_BACKGROUND: Color32 = Color32::from_rgba_premultiplied(10, 10, 14, 220);
+const DRO_TEXT_COLOR: Color32 = Color32::from_rgb(120, 230, 120);
+
+/// Fixed coordinate readout options, remembered in the project config
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DroSettings {
+    pub enabled: bool,
+    pub corner: ViewportCorner,
+    /// Coordinate label that follows the mouse pointer
+    pub follow_cursor: bool,
+}
+
+impl Default for DroSettings {
+    fn default() -> Self {
+        Self { enabled: false, corner: ViewportCorner::TopRight, follow_cursor: true }
+    }
+}
+
+/// Last clicked position in the gerber view, for the delta readout
+#[derive(Default)]
+pub struct DroState {
+    pub last_click: Option<Point2<f64>>,
+}
+
+/// Remember a click in the viewport (view coordinates)
+pub fn record_click(app: &mut DemoLensApp, position: Point2<f64>) {
+    app.dro.last_click = Some(position);
+}
+
+/// DRO toggle, corner and follow-cursor readout, shown in the View Settings tab
+pub fn show_dro_settings(ui: &mut egui::Ui, settings: &mut DroSettings) {
+    ui.checkbox(&mut settings.enabled, "Show coordinate readout (DRO)")
+        .on_hover_text("Fixed panel with cursor X/Y, distance from the last click and zoom");
+    ui.add_enabled_ui(settings.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Corner:");
+            egui::ComboBox::from_id_salt("dro_corner")
+                .selected_text(settings.corner.name())
+                .show_ui(ui, |ui| {
+                    for corner in ViewportCorner::ALL {
+                        ui.selectable_value(&mut settings.corner, corner, corner.name());
+                    }
+                });
+        });
+    });
+    ui.checkbox(&mut settings.follow_cursor, "Show coordinates next to the cursor");
+}
+
+/// Signed length in the display unit (Nanometer is unsigned, so scale instead of converting)
+fn format_length(units: &UnitsResource, mm: f64) -> String {
+    format!("{:>10} {}", units.display_unit.format_number(mm, 3), units.unit_suffix())
+}
+
+/// Lines of the readout for a cursor position relative to the origin
+fn dro_lines(units: &UnitsResource, cursor: Option<Point2<f64>>, delta: Option<nalgebra::Vector2<f64>>, zoom_percent: f32) -> Vec<String> {
+    let dash = format!("{:>14}", "—");
+    let mut lines = match cursor {
+        Some(cursor) => vec![format!("X  {}", format_length(units, cursor.x)), format!("Y  {}", format_length(units, cursor.y))],
+        None => vec![format!("X  {}", dash), format!("Y  {}", dash)],
+    };
+    match delta {
+        Some(delta) => {
+            lines.push(format!("dX {}", format_length(units, delta.x)));
+            lines.push(format!("dY {}", format_length(units, delta.y)));
+            lines.push(format!("d  {}", format_length(units, delta.norm())));
+        }
+        None => lines.extend(["dX", "dY", "d "].map(|label| format!("{} {}", label, dash))),
+    }
+    lines.push(format!("Zoom {:>9.1}%", zoom_percent));
+    lines
+}
+
+/// Fixed readout panel in the chosen viewport corner
+pub fn show_dro(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect) {
+    if !app.dro_settings.enabled {
+        return;
+    }
+
+    let origin = app.display_manager.design_origin();
+    let cursor = ui.input(|i| i.pointer.hover_pos())
+        .filter(|pos| viewport.contains(*pos))
+        .map(|pos| app.view_state.screen_to_gerber_coords(pos));
+    let delta = cursor.zip(app.dro.last_click).map(|(cursor, click)| cursor - click);
+    let zoom_percent = app.ecs_world.get_resource::<crate::ecs::ZoomResource>()
+        .map(|zoom| zoom.get_zoom_percentage())
+        .unwrap_or(app.view_state.scale * 100.0);
+    let Some(units) = app.ecs_world.get_resource::<UnitsResource>() else {
+        return;
+    };
+    let lines = dro_lines(units, cursor.map(|cursor| Point2::from(cursor - origin)), delta, zoom_percent);
+
+    let painter = ui.painter().with_clip_rect(*viewport);
+    let galley = painter.layout_no_wrap(crate::ui::i18n::localize_number(&lines.join("\n")), FontId::monospace(12.0), DRO_TEXT_COLOR);
+    let panel = app.dro_settings.corner.place(viewport, galley.size() + Vec2::splat(DRO_PADDING * 2.0), DRO_MARGIN);
+    painter.rect_filled(panel, 3.0, DRO_BACKGROUND);
+    painter.rect_stroke(panel, 3.0, Stroke::new(1.0, Color32::from_gray(90)), egui::StrokeKind::Inside);
+    painter.galley(panel.min + Vec2::splat(DRO_PADDING), galley, DRO_TEXT_COLOR);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dro_lines_show_delta_in_display_units() {
+        let mut units = UnitsResource::default();
+        units.set_mm();
+        let lines = dro_lines(&units, Some(Point2::new(1.5, -2.0)), Some(nalgebra::Vector2::new(3.0, 4.0)), 250.0);
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("X ") && lines[0].ends_with("1.500 mm"));
+        assert!(lines[4].ends_with("5.000 mm"));
+        assert!(lines[5].contains("250.0%"));
+
+        let lines = dro_lines(&units, None, None, 100.0);
+        assert!(lines[2].starts_with("dX") && lines[2].ends_with('—'));
+    }
+}
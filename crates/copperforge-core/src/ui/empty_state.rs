@@ -0,0 +1,94 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ecs::LayerType;
+use crate::ui::i18n::{tr, tr_args};
+
+/// Recent projects listed in the empty state; the ribbon menu has the rest
+const MAX_RECENT_SHOWN: usize = 5;
+
+/// Replace the loaded layers with the bundled default project (all layers of the Cmod S7 board)
+/// Falls back to the single demo gerber as top copper should none of them parse
+pub fn load_demo_board(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let loaded = crate::project::load_default_gerbers(&mut app.ecs_world);
+    if loaded == 0 {
+        crate::ecs::create_gerber_layer_entity(
+            &mut app.ecs_world,
+            LayerType::TOP_COPPER,
+            crate::project::load_demo_gerber(),
+            Some(crate::project::DEMO_GERBER.to_string()),
+            None,
+            true,
+        );
+    }
+    app.needs_initial_view = true;
+    logger.log_info(&format!("Loaded the demo board ({} layers)", loaded.max(1)));
+}
+
+/// Centered start panel of the gerber view while no layers are loaded
+pub fn show_empty_state(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let logger_state = app.logger_state.clone();
+    let log_colors = app.log_colors.clone();
+    let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
+
+    let mut open_pcb = false;
+    let mut load_folder = false;
+    let mut load_demo = false;
+    let mut open_recent = None;
+
+    ui.vertical_centered(|ui| {
+        ui.add_space((ui.available_height() * 0.25).max(0.0));
+        ui.heading(tr("empty.title"));
+        ui.label(RichText::new(tr("empty.subtitle")).color(Color32::GRAY));
+        ui.add_space(16.0);
+
+        open_pcb = ui.add(egui::Button::new(tr("empty.open_pcb")).min_size(egui::vec2(220.0, 32.0)))
+            .on_hover_text(tr("empty.open_pcb_hint"))
+            .clicked();
+        ui.add_space(4.0);
+        load_folder = ui.add(egui::Button::new(tr("empty.load_folder")).min_size(egui::vec2(220.0, 32.0)))
+            .on_hover_text(tr("empty.load_folder_hint"))
+            .clicked();
+
+        let unassigned = crate::ecs::get_unassigned_gerbers(&app.ecs_world).len();
+        if unassigned > 0 {
+            ui.add_space(8.0);
+            ui.label(RichText::new(tr_args("empty.unassigned", &[("count", &unassigned)])).color(Color32::from_rgb(241, 196, 15)));
+        }
+
+        let recent = &app.recent_projects.list.entries;
+        if !recent.is_empty() {
+            ui.add_space(16.0);
+            ui.label(RichText::new(tr("empty.recent")).strong());
+            for entry in recent.iter().take(MAX_RECENT_SHOWN) {
+                if ui.link(&entry.name).on_hover_text(entry.pcb_path.display().to_string()).clicked() {
+                    open_recent = Some(entry.pcb_path.clone());
+                }
+            }
+        }
+
+        ui.add_space(16.0);
+        load_demo = ui.link(RichText::new(tr("empty.load_demo")).small())
+            .on_hover_text(tr("empty.load_demo_hint"))
+            .clicked();
+    });
+
+    if open_pcb {
+        app.project_manager.open_file_dialog();
+    }
+    if load_folder {
+        if let Some(dir) = rfd::FileDialog::new().set_title("Select gerber directory").pick_folder() {
+            logger.log_info(&format!("Loading gerbers from {}", dir.display()));
+            crate::ui::status_bar::defer_logged(app, tr("status.loading_gerbers"), move |app, logger| {
+                crate::ui::project_panel::load_gerbers_into_viewer(app, &dir, logger)
+            });
+        }
+    }
+    if let Some(path) = open_recent {
+        crate::ui::recent_projects::open_recent(app, &path, &logger);
+    }
+    if load_demo {
+        load_demo_board(app, &logger);
+    }
+}
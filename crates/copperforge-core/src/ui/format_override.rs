@@ -0,0 +1,126 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ui::i18n::{tr, tr_args};
+use crate::ecs::{FormatOverride, GerberFormat, GerberUnits, LayerType, ZeroOmission};
+
+const MAX_DIGITS: u8 = 7;
+
+/// File whose coordinate format is being corrected
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatTarget {
+    Layer(LayerType),
+    Unassigned(String),
+}
+
+/// Open "Coordinate format" window and the values being edited
+#[derive(Debug, Clone)]
+pub struct FormatOverrideEditor {
+    pub target: FormatTarget,
+    pub detected: GerberFormat,
+    pub value: FormatOverride,
+}
+
+/// Detected format next to a file name; clicking it opens the override window
+pub fn show_format_badge(ui: &mut egui::Ui, app: &mut DemoLensApp, target: FormatTarget, format: GerberFormat) {
+    let text = RichText::new(format.summary()).small().color(Color32::GRAY);
+    let badge = ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_text(format!("{}\n{}", format.describe(), tr("format.click_hint")));
+    if badge.clicked() {
+        app.format_override_editor = Some(FormatOverrideEditor {
+            target,
+            detected: format,
+            value: FormatOverride::from_detected(&format),
+        });
+    }
+}
+
+fn target_name(target: &FormatTarget) -> String {
+    match target {
+        FormatTarget::Layer(layer_type) => layer_type.display_name().to_string(),
+        FormatTarget::Unassigned(filename) => filename.clone(),
+    }
+}
+
+/// Override window: digits, zeros and units, then re-parse the file with them
+pub fn show_format_override_window(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(editor) = &mut app.format_override_editor else {
+        return;
+    };
+
+    let mut open = true;
+    let mut reparse = false;
+    egui::Window::new(tr_args("format.title", &[("target", &target_name(&editor.target))]))
+        .id(egui::Id::new("format_override_window"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(RichText::new(tr_args("format.detected", &[("format", &editor.detected.summary())])).color(Color32::GRAY));
+            ui.add_space(4.0);
+            egui::Grid::new("format_override_grid").num_columns(2).show(ui, |ui| {
+                ui.label(tr("format.integer_digits"));
+                ui.add(egui::DragValue::new(&mut editor.value.integer_digits).range(1..=MAX_DIGITS));
+                ui.end_row();
+                ui.label(tr("format.decimal_digits"));
+                ui.add(egui::DragValue::new(&mut editor.value.decimal_digits).range(1..=MAX_DIGITS));
+                ui.end_row();
+                ui.label(tr("format.zeros"));
+                egui::ComboBox::from_id_salt("format_override_zeros")
+                    .selected_text(editor.value.zero_omission.name())
+                    .show_ui(ui, |ui| {
+                        for zeros in ZeroOmission::ALL {
+                            ui.selectable_value(&mut editor.value.zero_omission, zeros, zeros.name());
+                        }
+                    });
+                ui.end_row();
+                ui.label(tr("format.units"));
+                ui.horizontal(|ui| {
+                    for units in GerberUnits::ALL {
+                        ui.radio_value(&mut editor.value.units, units, units.name());
+                    }
+                });
+                ui.end_row();
+            });
+            ui.add_space(4.0);
+            if ui.button(tr("format.reparse")).on_hover_text(tr("format.reparse_hint")).clicked() {
+                reparse = true;
+            }
+        });
+
+    if reparse {
+        let editor = app.format_override_editor.take().expect("editor is open");
+        apply_format_override(app, &editor, logger);
+    } else if !open {
+        app.format_override_editor = None;
+    }
+}
+
+fn apply_format_override(app: &mut DemoLensApp, editor: &FormatOverrideEditor, logger: &ReactiveEventLogger) {
+    let name = target_name(&editor.target);
+    let result = match &editor.target {
+        FormatTarget::Layer(layer_type) => match app.project_manager.get_gerber_dir().cloned() {
+            Some(gerber_dir) => {
+                crate::ecs::reparse_layer_with_format(&mut app.ecs_world, *layer_type, &gerber_dir, editor.value).map(|_| ())
+            }
+            None => Err("No gerber directory is loaded".to_string()),
+        },
+        FormatTarget::Unassigned(filename) => {
+            crate::ecs::reparse_unassigned_with_format(&mut app.ecs_world, filename, editor.value)
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            let format = editor.value;
+            logger.log_info(&format!(
+                "Re-parsed {} as {}.{} {}, {}",
+                name, format.integer_digits, format.decimal_digits, format.units.name(), format.zero_omission.name()
+            ));
+            app.needs_initial_view = true;
+            crate::ui::layer_controls::run_registration_check(&mut app.ecs_world, logger);
+        }
+        Err(e) => logger.log_error(&format!("Failed to re-parse {}: {}", name, e)),
+    }
+}
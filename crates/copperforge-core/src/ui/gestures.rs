@@ -0,0 +1,141 @@
+use egui::{Event, MouseWheelUnit, Pos2, Vec2};
+
+use crate::DemoLensApp;
+
+const MIN_SCALE: f32 = 0.01;
+const MAX_SCALE: f32 = 100.0;
+
+/// A two-finger touch shorter than this without moving counts as a tap (seconds)
+const TAP_MAX_DURATION: f64 = 0.3;
+/// Second tap must follow the first within this time to fit the board (seconds)
+const DOUBLE_TAP_INTERVAL: f64 = 0.4;
+/// Movement (points) or zoom change that turns a tap into a gesture
+const TAP_MAX_MOVEMENT: f32 = 4.0;
+const TAP_MAX_ZOOM_CHANGE: f32 = 0.02;
+
+/// Tracks two-finger touches to recognise the double-tap
+#[derive(Default)]
+pub struct GestureState {
+    /// Input time the current two-finger touch started
+    touch_started: Option<f64>,
+    touch_moved: bool,
+    last_tap: Option<f64>,
+}
+
+/// Scale the view by `factor`, keeping the board point under `anchor` in place
+pub fn zoom_around_point(app: &mut DemoLensApp, anchor: Pos2, factor: f32) {
+    let gerber_point = app.view_state.screen_to_gerber_coords(anchor);
+    app.view_state.scale = (app.view_state.scale * factor).clamp(MIN_SCALE, MAX_SCALE);
+    let new_screen_pos = app.view_state.gerber_to_screen_coords(gerber_point);
+    app.view_state.translation += anchor - new_screen_pos;
+    app.sync_zoom_to_ecs();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollKind {
+    /// Mouse wheel notches, used for zoom
+    Wheel,
+    /// Trackpad two-finger scroll, used for pan
+    Pan,
+}
+
+/// Trackpad scrolling arrives in points, mouse wheels in lines or pages
+/// Modifier-held scrolling is skipped as egui already reports it as zoom
+pub fn scroll_kind(unit: MouseWheelUnit, modifiers: egui::Modifiers) -> Option<ScrollKind> {
+    if modifiers.command || modifiers.ctrl {
+        return None;
+    }
+    match unit {
+        MouseWheelUnit::Point => Some(ScrollKind::Pan),
+        MouseWheelUnit::Line | MouseWheelUnit::Page => Some(ScrollKind::Wheel),
+    }
+}
+
+/// Summed (wheel delta, trackpad pan delta) of this frame's scroll events
+pub fn split_scroll_input(events: &[Event]) -> (Vec2, Vec2) {
+    events.iter().fold((Vec2::ZERO, Vec2::ZERO), |(wheel, pan), event| match event {
+        Event::MouseWheel { unit, delta, modifiers, .. } => match scroll_kind(*unit, *modifiers) {
+            Some(ScrollKind::Wheel) => (wheel + *delta, pan),
+            Some(ScrollKind::Pan) => (wheel, pan + *delta),
+            None => (wheel, pan),
+        },
+        _ => (wheel, pan),
+    })
+}
+
+/// Pinch zoom, two-finger pan and two-finger double-tap to fit
+/// Returns true while a touch gesture is in progress, so other tools can stand down
+pub fn handle_touch_gestures(ui: &egui::Ui, app: &mut DemoLensApp, response: &egui::Response) -> bool {
+    if !app.touch_gestures_enabled {
+        return false;
+    }
+
+    let (now, multi_touch, zoom_delta, events) = ui.input(|i| (i.time, i.multi_touch(), i.zoom_delta(), i.events.clone()));
+
+    if let Some(touch) = multi_touch {
+        if app.gestures.touch_started.is_none() {
+            app.gestures.touch_started = Some(now);
+            app.gestures.touch_moved = false;
+        }
+        if touch.translation_delta.length() > TAP_MAX_MOVEMENT || (touch.zoom_delta - 1.0).abs() > TAP_MAX_ZOOM_CHANGE {
+            app.gestures.touch_moved = true;
+        }
+
+        if response.contains_pointer() || response.rect.contains(touch.center_pos) {
+            if touch.zoom_delta != 1.0 {
+                zoom_around_point(app, touch.center_pos, touch.zoom_delta);
+            }
+            if touch.translation_delta != Vec2::ZERO {
+                app.view_state.translation += touch.translation_delta;
+                app.sync_zoom_to_ecs();
+            }
+        }
+        return true;
+    }
+
+    if let Some(started) = app.gestures.touch_started.take() {
+        let was_tap = !app.gestures.touch_moved && now - started < TAP_MAX_DURATION;
+        if was_tap {
+            if app.gestures.last_tap.is_some_and(|last| now - last < DOUBLE_TAP_INTERVAL) {
+                app.gestures.last_tap = None;
+                app.needs_initial_view = true;
+            } else {
+                app.gestures.last_tap = Some(now);
+            }
+        }
+    }
+
+    if !response.contains_pointer() {
+        return false;
+    }
+
+    // Trackpad pinch (and Ctrl+scroll) zooms around the cursor
+    if zoom_delta != 1.0 {
+        if let Some(anchor) = ui.input(|i| i.pointer.hover_pos()) {
+            zoom_around_point(app, anchor, zoom_delta);
+        }
+    }
+
+    let (_, pan) = split_scroll_input(&events);
+    if pan != Vec2::ZERO {
+        app.view_state.translation += pan;
+        app.sync_zoom_to_ecs();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trackpad_scroll_pans_and_wheel_zooms() {
+        let none = egui::Modifiers::NONE;
+        assert_eq!(scroll_kind(MouseWheelUnit::Line, none), Some(ScrollKind::Wheel));
+        assert_eq!(scroll_kind(MouseWheelUnit::Page, none), Some(ScrollKind::Wheel));
+        assert_eq!(scroll_kind(MouseWheelUnit::Point, none), Some(ScrollKind::Pan));
+        // Already turned into zoom_delta by egui
+        assert_eq!(scroll_kind(MouseWheelUnit::Line, egui::Modifiers::CTRL), None);
+        assert_eq!(scroll_kind(MouseWheelUnit::Point, egui::Modifiers::COMMAND), None);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{DemoLensApp, project::constants::LOG_TYPE_GRID, display::grid::{get_grid_status, GridStatus}};
-use crate::ecs::{UnitsResource, mm_to_nm, nm_to_mm, mils_to_nm, nm_to_mils};
+use crate::ecs::{mm_to_nm, nm_to_mm, mils_to_nm, nm_to_mils};
+use crate::ui::unit_value::{self, UnitValue};
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::Dynamic;
 
@@ -20,72 +21,43 @@ pub fn show_grid_panel<'a>(
     }
     
     ui.horizontal(|ui| {
-        // Get units from ECS
-        let units_resource = app.ecs_world.get_resource::<UnitsResource>()
-            .expect("UnitsResource should exist");
+        let unit = unit_value::display_unit(&app.ecs_world);
+        ui.label(format!("Grid Spacing ({}):", unit.suffix()));
         
-        let label = if units_resource.is_mils() {
-            "Grid Spacing (mils):"
-        } else {
-            "Grid Spacing (mm):"
-        };
-        ui.label(label);
-        
-        let _prev_spacing_mm = app.grid_settings.spacing_mm;
+        let prev_spacing_mm = app.grid_settings.spacing_mm;
         
-        if units_resource.is_mils() {
-            // Convert to mils for display using nanometer precision
-            let spacing_nm = mm_to_nm(app.grid_settings.spacing_mm);
-            let mut spacing_mils = nm_to_mils(spacing_nm);
-            let prev_mils = spacing_mils;
-            
-            // Add slider
-            let slider_response = ui.add(
+        let slider_changed = if unit.is_imperial() {
+            // Slider in mils (also for inches), converted through nanometers for precision
+            let mut spacing_mils = nm_to_mils(mm_to_nm(app.grid_settings.spacing_mm));
+            let changed = ui.add(
                 egui::Slider::new(&mut spacing_mils, 1.0..=1000.0)
                     .logarithmic(true)
-            );
-            
-            // Add text input box next to slider
-            let text_response = ui.add(
-                egui::DragValue::new(&mut spacing_mils)
-                    .speed(1.0)
-                    .range(1.0..=1000.0)
-                    .suffix(" mils")
-            );
-            
-            if slider_response.changed() || text_response.changed() {
-                // Convert back through nanometers for precision
-                let spacing_nm = mils_to_nm(spacing_mils);
-                app.grid_settings.spacing_mm = nm_to_mm(spacing_nm);
-                logger.log_custom(
-                    LOG_TYPE_GRID,
-                    &format!("Grid spacing changed from {:.1} to {:.1} mils", prev_mils, spacing_mils)
-                );
+                    .show_value(false)
+            ).changed();
+            if changed {
+                app.grid_settings.spacing_mm = nm_to_mm(mils_to_nm(spacing_mils));
             }
+            changed
         } else {
-            // Work directly in mm
-            let prev_mm = app.grid_settings.spacing_mm;
-            
-            // Add slider
-            let slider_response = ui.add(
+            ui.add(
                 egui::Slider::new(&mut app.grid_settings.spacing_mm, 0.025..=25.0)
                     .logarithmic(true)
+                    .show_value(false)
+            ).changed()
+        };
+        
+        // Text entry next to the slider
+        let text_changed = ui.add(UnitValue::new(&mut app.grid_settings.spacing_mm, unit).range_mm(0.025..=25.4)).changed();
+        
+        if slider_changed || text_changed {
+            logger.log_custom(
+                LOG_TYPE_GRID,
+                &format!(
+                    "Grid spacing changed from {} to {}",
+                    unit.format_mm(prev_spacing_mm as f64, 3),
+                    unit.format_mm(app.grid_settings.spacing_mm as f64, 3)
+                )
             );
-            
-            // Add text input box next to slider
-            let text_response = ui.add(
-                egui::DragValue::new(&mut app.grid_settings.spacing_mm)
-                    .speed(0.1)
-                    .range(0.025..=25.0)
-                    .suffix(" mm")
-            );
-            
-            if slider_response.changed() || text_response.changed() {
-                logger.log_custom(
-                    LOG_TYPE_GRID,
-                    &format!("Grid spacing changed from {:.2} to {:.2} mm", prev_mm, app.grid_settings.spacing_mm)
-                );
-            }
         }
     });
     
@@ -100,6 +72,36 @@ pub fn show_grid_panel<'a>(
         }
     });
     
+    ui.horizontal(|ui| {
+        ui.label("Grid Style:");
+        let prev_style = app.grid_settings.style;
+        egui::ComboBox::from_id_salt("grid_panel_style")
+            .selected_text(app.grid_settings.style.name())
+            .show_ui(ui, |ui| {
+                for style in crate::display::GridStyle::ALL {
+                    ui.selectable_value(&mut app.grid_settings.style, style, style.name());
+                }
+            });
+        if app.grid_settings.style != prev_style {
+            logger.log_custom(LOG_TYPE_GRID, &format!("Grid style set to {}", app.grid_settings.style.name()));
+        }
+    });
+    
+    if app.grid_settings.style != crate::display::GridStyle::Dots {
+        ui.horizontal(|ui| {
+            ui.label("Major line every:");
+            ui.add(egui::DragValue::new(&mut app.grid_settings.major_every).range(1..=20))
+                .on_hover_text("Number of grid steps between the brighter major lines or rings");
+        });
+    }
+    if app.grid_settings.style == crate::display::GridStyle::Polar {
+        ui.horizontal(|ui| {
+            ui.label("Radial line spacing:");
+            ui.add(egui::DragValue::new(&mut app.grid_settings.polar_angle_deg).speed(0.5).range(1.0..=180.0).suffix("°"))
+                .on_hover_text("Rings follow the grid spacing and are centered on the origin; snapping picks ring and radial line crossings");
+        });
+    }
+    
     // Enterprise features section
     ui.separator();
     ui.heading("Grid Features");
@@ -114,7 +116,8 @@ pub fn show_grid_panel<'a>(
     
     // Align to grid button
     ui.horizontal(|ui| {
-        if ui.button("⌗ Align View to Grid (A)").clicked() {
+        let shortcut = app.key_bindings.get(crate::ui::key_bindings::ShortcutAction::AlignToGrid);
+        if ui.button(format!("⌗ Align View to Grid ({})", shortcut)).clicked() {
             crate::display::align_to_grid(&mut app.view_state, &app.grid_settings);
             logger.log_custom(LOG_TYPE_GRID, "View aligned to grid");
         }
@@ -0,0 +1,150 @@
+//! UI string catalogs
+//!
+//! Text shown in the interface is looked up by key in the catalog of the
+//! selected language, embedded from `locales/<code>.json`. A key missing from a
+//! translation falls back to English, so a partial catalog never shows raw
+//! identifiers. Log and diagnostic messages stay English.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Self::English, Self::German];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+        }
+    }
+
+    /// Name of the language in that language, for the selector
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    /// Separator between the integer and fractional part of numbers in readouts
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Self::English => '.',
+            Self::German => ',',
+        }
+    }
+
+    fn catalog_source(&self) -> &'static str {
+        match self {
+            Self::English => include_str!("locales/en.json"),
+            Self::German => include_str!("locales/de.json"),
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|language| language == self).unwrap_or(0)
+    }
+}
+
+type Catalog = HashMap<String, String>;
+
+static CATALOGS: Lazy<Vec<Catalog>> = Lazy::new(|| {
+    Language::ALL.iter()
+        .map(|language| {
+            serde_json::from_str(language.catalog_source())
+                .unwrap_or_else(|e| panic!("locales/{}.json is not a valid catalog: {}", language.code(), e))
+        })
+        .collect()
+});
+
+/// Index of the selected language in `Language::ALL`
+static SELECTED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_language(language: Language) {
+    SELECTED.store(language.index(), Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL.get(SELECTED.load(Ordering::Relaxed)).copied().unwrap_or_default()
+}
+
+fn lookup(language: Language, key: &str) -> Option<&'static str> {
+    CATALOGS[language.index()].get(key).map(String::as_str)
+}
+
+fn tr_in(language: Language, key: &str) -> &str {
+    lookup(language, key)
+        .or_else(|| lookup(Language::English, key))
+        .unwrap_or(key)
+}
+
+/// Text for `key` in the selected language, else in English
+pub fn tr(key: &str) -> &str {
+    tr_in(language(), key)
+}
+
+/// Text for `key` with its `{name}` placeholders filled in
+pub fn tr_args(key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    args.iter().fold(tr(key).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+fn localize_number_in(language: Language, text: &str) -> String {
+    let separator = language.decimal_separator();
+    if separator == '.' {
+        return text.to_string();
+    }
+    let chars: Vec<char> = text.chars().collect();
+    chars.iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let between_digits = i > 0 && chars[i - 1].is_ascii_digit() && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit());
+            if *c == '.' && between_digits { separator } else { *c }
+        })
+        .collect()
+}
+
+/// A formatted measurement with the decimal separator of the selected language ("1.500 mm" → "1,500 mm")
+pub fn localize_number(text: &str) -> String {
+    localize_number_in(language(), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{').skip(1).filter_map(|part| part.split_once('}')).map(|(name, _)| name).collect()
+    }
+
+    #[test]
+    fn test_translations_are_complete_and_fall_back_to_english() {
+        let english = &CATALOGS[Language::English.index()];
+        for language in Language::ALL {
+            let catalog = &CATALOGS[language.index()];
+            for (key, text) in catalog {
+                let source = english.get(key).unwrap_or_else(|| panic!("{}: {} is not in the English catalog", language.code(), key));
+                assert_eq!(placeholders(text), placeholders(source), "{}: {}", language.code(), key);
+            }
+            let missing: Vec<_> = english.keys().filter(|key| !catalog.contains_key(*key)).collect();
+            assert!(missing.is_empty(), "{} is missing {:?}", language.code(), missing);
+        }
+
+        assert_eq!(tr_in(Language::German, "ribbon.browse"), "Durchsuchen…");
+        assert_eq!(tr_in(Language::German, "no.such.key"), "no.such.key");
+        assert_eq!(localize_number_in(Language::German, "X 1.500 mm, v0.2 (Rev. 3)"), "X 1,500 mm, v0,2 (Rev. 3)");
+        assert_eq!(localize_number_in(Language::English, "1.500 mm"), "1.500 mm");
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+use egui::{Color32, Key, RichText};
+use egui_lens::ReactiveEventLogger;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::DemoLensApp;
+use crate::ui::i18n::tr;
+
+const CONFLICT_COLOR: Color32 = Color32::from_rgb(231, 76, 60);
+
+/// Viewer actions with a remappable shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ShortcutAction {
+    FlipView,
+    ToggleUnits,
+    RotateView,
+    AlignToGrid,
+    ToggleRuler,
+    ToggleActiveLayer,
+    CopyView,
+    SaveViewPng,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 8] = [
+        ShortcutAction::FlipView,
+        ShortcutAction::ToggleUnits,
+        ShortcutAction::RotateView,
+        ShortcutAction::AlignToGrid,
+        ShortcutAction::ToggleRuler,
+        ShortcutAction::ToggleActiveLayer,
+        ShortcutAction::CopyView,
+        ShortcutAction::SaveViewPng,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::FlipView => "Flip board view",
+            ShortcutAction::ToggleUnits => "Cycle units",
+            ShortcutAction::RotateView => "Rotate 90°",
+            ShortcutAction::AlignToGrid => "Align view to grid",
+            ShortcutAction::ToggleRuler => "Ruler",
+            ShortcutAction::ToggleActiveLayer => "Show/hide active layer",
+            ShortcutAction::CopyView => "Copy view as image",
+            ShortcutAction::SaveViewPng => "Save view as PNG",
+        }
+    }
+
+    /// Label in the interface language; `label` stays English for log messages
+    pub fn display_label(&self) -> &'static str {
+        tr(match self {
+            ShortcutAction::FlipView => "shortcut.flip_view",
+            ShortcutAction::ToggleUnits => "shortcut.toggle_units",
+            ShortcutAction::RotateView => "shortcut.rotate_view",
+            ShortcutAction::AlignToGrid => "shortcut.align_to_grid",
+            ShortcutAction::ToggleRuler => "shortcut.ruler",
+            ShortcutAction::ToggleActiveLayer => "shortcut.toggle_active_layer",
+            ShortcutAction::CopyView => "shortcut.copy_view",
+            ShortcutAction::SaveViewPng => "shortcut.save_view_png",
+        })
+    }
+
+    pub fn default_shortcut(&self) -> KeyShortcut {
+        match self {
+            ShortcutAction::FlipView => KeyShortcut::plain(Key::F),
+            ShortcutAction::ToggleUnits => KeyShortcut::plain(Key::U),
+            ShortcutAction::RotateView => KeyShortcut::plain(Key::R),
+            ShortcutAction::AlignToGrid => KeyShortcut::plain(Key::A),
+            ShortcutAction::ToggleRuler => KeyShortcut::plain(Key::M),
+            ShortcutAction::ToggleActiveLayer => KeyShortcut::plain(Key::V),
+            ShortcutAction::CopyView => KeyShortcut { key: Key::C, command: true, shift: true, alt: false },
+            ShortcutAction::SaveViewPng => KeyShortcut { key: Key::P, command: true, shift: true, alt: false },
+        }
+    }
+}
+
+/// A key with the exact modifiers that must be held
+/// Stored as text such as "Ctrl+Shift+C" so the config stays readable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyShortcut {
+    pub key: Key,
+    /// Ctrl, or Cmd on macOS
+    pub command: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyShortcut {
+    pub fn plain(key: Key) -> Self {
+        Self { key, command: false, shift: false, alt: false }
+    }
+
+    pub fn from_input(key: Key, modifiers: egui::Modifiers) -> Self {
+        Self { key, command: modifiers.command, shift: modifiers.shift, alt: modifiers.alt }
+    }
+
+    /// Pressed this frame with exactly these modifiers, so F and Shift+F can mean different things
+    pub fn pressed(&self, input: &egui::InputState) -> bool {
+        let modifiers = input.modifiers;
+        input.key_pressed(self.key)
+            && modifiers.command == self.command
+            && modifiers.shift == self.shift
+            && modifiers.alt == self.alt
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut shortcut = Self::plain(Key::Escape);
+        let mut key = None;
+        for part in text.split('+').map(str::trim) {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => shortcut.command = true,
+                "shift" => shortcut.shift = true,
+                "alt" | "option" => shortcut.alt = true,
+                _ => key = Some(Key::from_name(part).ok_or_else(|| format!("Unknown key \"{}\" in \"{}\"", part, text))?),
+            }
+        }
+        shortcut.key = key.ok_or_else(|| format!("No key in shortcut \"{}\"", text))?;
+        Ok(shortcut)
+    }
+}
+
+impl std::fmt::Display for KeyShortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.command {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{}", self.key.name())
+    }
+}
+
+impl Serialize for KeyShortcut {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyShortcut {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        KeyShortcut::parse(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What a shortcut is already used for outside the remappable actions
+fn reserved_use(shortcut: &KeyShortcut) -> Option<&'static str> {
+    let digit = matches!(
+        shortcut.key,
+        Key::Num1 | Key::Num2 | Key::Num3 | Key::Num4 | Key::Num5 | Key::Num6 | Key::Num7 | Key::Num8 | Key::Num9
+    );
+    if shortcut.key == Key::Escape {
+        Some("cancelling the ruler and net highlight")
+    } else if digit && shortcut.alt && !shortcut.command && !shortcut.shift {
+        Some("toggling layers (Alt+1..9)")
+    } else {
+        None
+    }
+}
+
+/// Shortcut per action; actions missing from an older config use their default
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeyBindings(pub BTreeMap<ShortcutAction, KeyShortcut>);
+
+impl KeyBindings {
+    pub fn get(&self, action: ShortcutAction) -> KeyShortcut {
+        self.0.get(&action).copied().unwrap_or_else(|| action.default_shortcut())
+    }
+
+    /// Bind `action`; binding it to its default forgets the override
+    pub fn set(&mut self, action: ShortcutAction, shortcut: KeyShortcut) {
+        if shortcut == action.default_shortcut() {
+            self.0.remove(&action);
+        } else {
+            self.0.insert(action, shortcut);
+        }
+    }
+
+    /// Was the shortcut of `action` pressed this frame
+    pub fn pressed(&self, action: ShortcutAction, input: &egui::InputState) -> bool {
+        self.get(action).pressed(input)
+    }
+
+    /// Problems with the binding of `action`: other actions on the same keys, or a reserved key
+    pub fn conflicts(&self, action: ShortcutAction) -> Vec<String> {
+        let shortcut = self.get(action);
+        let mut conflicts: Vec<String> = ShortcutAction::ALL
+            .into_iter()
+            .filter(|other| *other != action && self.get(*other) == shortcut)
+            .map(|other| format!("also bound to {}", other.label()))
+            .collect();
+        if let Some(reserved) = reserved_use(&shortcut) {
+            conflicts.push(format!("already used for {}", reserved));
+        }
+        conflicts
+    }
+}
+
+/// Action whose shortcut is being recorded in the editor
+#[derive(Default)]
+pub struct KeyBindingEditor {
+    pub recording: Option<ShortcutAction>,
+}
+
+/// First key pressed this frame, with its modifiers; ignores lone modifier presses
+fn recorded_key(ui: &egui::Ui) -> Option<KeyShortcut> {
+    ui.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            egui::Event::Key { key, pressed: true, modifiers, .. } => Some(KeyShortcut::from_input(*key, *modifiers)),
+            _ => None,
+        })
+    })
+}
+
+/// Shortcut table in the settings panel: click a shortcut, then press the new keys
+pub fn show_key_bindings_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.group(|ui| {
+        ui.label("Keyboard Shortcuts");
+
+        if let Some(action) = app.key_binding_editor.recording {
+            match recorded_key(ui) {
+                Some(shortcut) if shortcut == KeyShortcut::plain(Key::Escape) => app.key_binding_editor.recording = None,
+                Some(shortcut) => {
+                    app.key_bindings.set(action, shortcut);
+                    app.key_binding_editor.recording = None;
+                    logger.log_info(&format!("{} is now {}", action.label(), shortcut));
+                    for conflict in app.key_bindings.conflicts(action) {
+                        logger.log_warning(&format!("{} ({}): {}", action.label(), shortcut, conflict));
+                    }
+                }
+                None => {}
+            }
+        }
+
+        egui::Grid::new("key_bindings_grid").num_columns(3).striped(true).show(ui, |ui| {
+            for action in ShortcutAction::ALL {
+                ui.label(action.display_label());
+                let recording = app.key_binding_editor.recording == Some(action);
+                let text = if recording { "Press keys… (Esc cancels)".to_string() } else { app.key_bindings.get(action).to_string() };
+                if ui.selectable_label(recording, text).clicked() {
+                    app.key_binding_editor.recording = if recording { None } else { Some(action) };
+                }
+                ui.horizontal(|ui| {
+                    if app.key_bindings.get(action) != action.default_shortcut()
+                        && ui.small_button("↺").on_hover_text(format!("Reset to {}", action.default_shortcut())).clicked()
+                    {
+                        app.key_bindings.set(action, action.default_shortcut());
+                    }
+                    let conflicts = app.key_bindings.conflicts(action);
+                    if !conflicts.is_empty() {
+                        ui.label(RichText::new("⚠ conflict").color(CONFLICT_COLOR)).on_hover_text(conflicts.join("\n"));
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+        if ui.add_enabled(!app.key_bindings.0.is_empty(), egui::Button::new("Reset all")).clicked() {
+            app.key_bindings = KeyBindings::default();
+            logger.log_info("Keyboard shortcuts reset to the defaults");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_round_trip_and_conflicts() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(bindings.get(ShortcutAction::CopyView).to_string(), "Ctrl+Shift+C");
+        assert!(ShortcutAction::ALL.iter().all(|action| bindings.conflicts(*action).is_empty()));
+
+        bindings.set(ShortcutAction::ToggleRuler, KeyShortcut::plain(Key::F));
+        bindings.set(ShortcutAction::RotateView, KeyShortcut { key: Key::Num2, command: false, shift: false, alt: true });
+        assert_eq!(bindings.conflicts(ShortcutAction::FlipView), vec!["also bound to Ruler".to_string()]);
+        assert_eq!(bindings.conflicts(ShortcutAction::RotateView).len(), 1);
+
+        let json = serde_json::to_string(&bindings).unwrap();
+        assert!(json.contains("\"Alt+2\""));
+        assert_eq!(serde_json::from_str::<KeyBindings>(&json).unwrap(), bindings);
+
+        // Setting the default again drops the override
+        bindings.set(ShortcutAction::ToggleRuler, KeyShortcut::plain(Key::M));
+        assert!(!bindings.0.contains_key(&ShortcutAction::ToggleRuler));
+        assert!(KeyShortcut::parse("Ctrl+Banana").is_err());
+    }
+}
@@ -1,8 +1,84 @@
-use crate::{DemoLensApp, ecs::{LayerType, Side}};
+use std::collections::BTreeSet;
+
+use crate::{DemoLensApp, ecs::{LayerGroup, LayerType, Side}};
+use crate::ui::format_override::FormatTarget;
+use crate::ui::i18n::{tr, tr_args};
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use eframe::emath::Vec2;
 use egui_mobius_reactive::*; 
 
+const REGISTRATION_WARNING_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 126, 34);
+const NEGATIVE_BADGE_COLOR: egui::Color32 = egui::Color32::from_rgb(155, 89, 182);
+
+/// Board side shown by the layer list filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SideFilter {
+    #[default]
+    All,
+    Top,
+    Bottom,
+}
+
+/// Which loaded layers the layer list shows; only narrows the list, never changes visibility
+#[derive(Debug, Clone, Default)]
+pub struct LayerListFilter {
+    /// Case-insensitive substring of the layer or file name
+    pub text: String,
+    pub side: SideFilter,
+    pub copper_only: bool,
+}
+
+impl LayerListFilter {
+    pub fn is_active(&self) -> bool {
+        !self.text.trim().is_empty() || self.side != SideFilter::All || self.copper_only
+    }
+
+    pub fn matches(&self, layer_type: LayerType, file_name: Option<&str>) -> bool {
+        let side_matches = match self.side {
+            SideFilter::All => true,
+            SideFilter::Top => layer_type.group() == LayerGroup::Top,
+            SideFilter::Bottom => layer_type.group() == LayerGroup::Bottom,
+        };
+        let needle = self.text.trim().to_lowercase();
+        let text_matches = needle.is_empty()
+            || layer_type.display_name().to_lowercase().contains(&needle)
+            || file_name.is_some_and(|name| name.to_lowercase().contains(&needle));
+        side_matches && text_matches && (!self.copper_only || layer_type.is_copper())
+    }
+}
+
+/// Unassigned files picked for a bulk assignment, and those auto-assign could not classify
+#[derive(Debug, Clone)]
+pub struct UnassignedSelection {
+    pub selected: BTreeSet<String>,
+    pub bulk_target: LayerType,
+    pub unmatched: BTreeSet<String>,
+}
+
+impl Default for UnassignedSelection {
+    fn default() -> Self {
+        Self { selected: BTreeSet::new(), bulk_target: LayerType::Copper(1), unmatched: BTreeSet::new() }
+    }
+}
+
+/// Filter box and quick side/copper toggles above the layer list
+fn show_layer_filter(ui: &mut egui::Ui, filter: &mut LayerListFilter) {
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut filter.text)
+            .hint_text(tr("layers.filter_hint"))
+            .desired_width(140.0));
+        for (side, label) in [(SideFilter::Top, tr("layers.top_only")), (SideFilter::Bottom, tr("layers.bottom_only"))] {
+            if ui.selectable_label(filter.side == side, label).clicked() {
+                filter.side = if filter.side == side { SideFilter::All } else { side };
+            }
+        }
+        ui.toggle_value(&mut filter.copper_only, tr("layers.copper_only"));
+        if filter.is_active() && ui.small_button("✖").on_hover_text(tr("layers.clear_filter")).clicked() {
+            *filter = LayerListFilter::default();
+        }
+    });
+}
+
 pub fn show_layers_panel<'a>(    ui: &mut egui::Ui, 
     app: &'a mut DemoLensApp,
     logger_state: &'a Dynamic<ReactiveEventLoggerState>,
@@ -10,7 +86,7 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
     let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
     
     // Layer visibility controls
-    ui.label("All Gerber Layers:");
+    ui.label(tr("layers.all_layers"));
     ui.add_space(4.0);
     
     // Quick controls
@@ -20,7 +96,7 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
         let total_layers = crate::ecs::get_layer_count(&mut app.ecs_world);
         let all_visible = visible_layers.len() == total_layers && total_layers > 0;
         let mut all_on = all_visible;
-        if ui.checkbox(&mut all_on, "All").clicked() {
+        if ui.checkbox(&mut all_on, tr("layers.all")).clicked() {
             for layer_type in LayerType::all() {
                 crate::ecs::set_layer_visibility(&mut app.ecs_world, layer_type, all_on);
             }
@@ -30,23 +106,23 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
         
         ui.separator();
         
-        if ui.button("Show All").clicked() {
+        if ui.button(tr("layers.show_all")).clicked() {
             for layer_type in LayerType::all() {
                 crate::ecs::set_layer_visibility(&mut app.ecs_world, layer_type, true);
             }
             logger.log_info("All layers shown");
         }
-        if ui.button("Hide All").clicked() {
+        if ui.button(tr("layers.hide_all")).clicked() {
             for layer_type in LayerType::all() {
                 crate::ecs::set_layer_visibility(&mut app.ecs_world, layer_type, false);
             }
             logger.log_info("All layers hidden");
         }
-        if ui.button("TOP").clicked() {
+        if ui.button(tr("layers.top")).clicked() {
             for layer_type in LayerType::all() {
                 let visible = match layer_type {
                     LayerType::Copper(1) | LayerType::Silkscreen(Side::Top) | LayerType::Soldermask(Side::Top) | LayerType::Paste(Side::Top) => true,
-                    LayerType::Copper(_) => false,  // All other copper layers (inner/bottom)
+                    LayerType::Copper(_) => false,  // Inner and bottom copper
                     LayerType::Silkscreen(Side::Bottom) | LayerType::Soldermask(Side::Bottom) | LayerType::Paste(Side::Bottom) => false,
                     LayerType::MechanicalOutline => true, // Keep outline visible
                 };
@@ -55,11 +131,12 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
             logger.log_info("Top layers shown");
             ui.ctx().request_repaint();
         }
-        if ui.button("BOTTOM").clicked() {
+        if ui.button(tr("layers.bottom")).clicked() {
             for layer_type in LayerType::all() {
                 let visible = match layer_type {
                     LayerType::Copper(1) | LayerType::Silkscreen(Side::Top) | LayerType::Soldermask(Side::Top) | LayerType::Paste(Side::Top) => false,
-                    LayerType::Copper(_) => true,  // All other copper layers (inner/bottom)
+                    LayerType::Copper(2) => true,
+                    LayerType::Copper(_) => false,  // Inner copper
                     LayerType::Silkscreen(Side::Bottom) | LayerType::Soldermask(Side::Bottom) | LayerType::Paste(Side::Bottom) => true,
                     LayerType::MechanicalOutline => true, // Keep outline visible
                 };
@@ -68,7 +145,7 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
             logger.log_info("Bottom layers shown");
             ui.ctx().request_repaint();
         }
-        if ui.button("ASSEMBLY").clicked() {
+        if ui.button(tr("layers.assembly")).clicked() {
             for layer_type in LayerType::all() {
                 let visible = match layer_type {
                     LayerType::Silkscreen(_) | LayerType::MechanicalOutline => true,
@@ -81,94 +158,170 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
         }
     });
     ui.add_space(4.0);
+    show_color_scheme_controls(ui, app, &logger);
+    ui.add_space(4.0);
     
     // Track actions to perform after the UI loop
     let mut show_only_layer: Option<LayerType> = None;
+    let mut new_active_layer: Option<LayerType> = None;
+    let active_layer = crate::ecs::get_active_layer(&app.ecs_world);
+    let loaded_layers = crate::ecs::get_loaded_layer_types(&mut app.ecs_world);
     let mut toggle_color_picker: Option<LayerType> = None;
     
     // Track visibility changes to apply after reading
     let mut visibility_changes = Vec::new();
     let mut color_changes = Vec::new();
     
-    for layer_type in LayerType::all() {
-        // Get layer data from ECS
-        if let Some((_entity, _layer_info, _gerber_data, visibility)) = crate::ecs::get_layer_data(&mut app.ecs_world, layer_type) {
-            let was_visible = visibility.visible;
-            let current_color = crate::ecs::get_layer_render_properties(&mut app.ecs_world, layer_type)
-                .map(|props| props.color)
-                .unwrap_or(layer_type.color());
+    show_layer_filter(ui, &mut app.layer_list_filter);
+    let filter = app.layer_list_filter.clone();
+    let shown_layers: Vec<LayerType> = loaded_layers.iter().copied()
+        .filter(|layer_type| {
+            let file_name = crate::ecs::get_layer_data(&mut app.ecs_world, *layer_type)
+                .and_then(|(_, layer_info, _, _)| layer_info.file_path.as_ref()?.file_name().map(|name| name.to_string_lossy().to_string()));
+            filter.matches(*layer_type, file_name.as_deref())
+        })
+        .collect();
+    if filter.is_active() {
+        ui.label(egui::RichText::new(tr_args("layers.filter_count", &[("shown", &shown_layers.len()), ("total", &loaded_layers.len())])).small().weak());
+    }
+    
+    for group in LayerGroup::ALL {
+        let group_layers: Vec<LayerType> = shown_layers.iter().copied().filter(|layer| layer.group() == group).collect();
+        if group_layers.is_empty() {
+            continue;
+        }
+        ui.label(egui::RichText::new(group.name()).small().strong());
+        for layer_type in group_layers {
+            // Get layer data from ECS
+            if let Some((_entity, _layer_info, _gerber_data, visibility)) = crate::ecs::get_layer_data(&mut app.ecs_world, layer_type) {
+                let was_visible = visibility.visible;
+                let current_color = crate::ecs::get_layer_render_properties(&mut app.ecs_world, layer_type)
+                    .map(|props| props.color)
+                    .unwrap_or(layer_type.color());
+                let flash_count = if matches!(layer_type, LayerType::Copper(_)) {
+                    Some(crate::ecs::get_flash_count(&mut app.ecs_world, layer_type))
+                } else {
+                    None
+                };
+                let registration_issues = crate::ecs::get_registration_issues(&app.ecs_world, layer_type);
+                let format = crate::ecs::get_layer_format(&mut app.ecs_world, layer_type);
+                let diagnostics = crate::ecs::get_layer_diagnostics(&mut app.ecs_world, layer_type);
+                let polarity = crate::ecs::get_layer_polarity(&mut app.ecs_world, layer_type);
             
-            // Show ALL layers regardless of top/bottom view
-            ui.horizontal(|ui| {
-                let mut current_visible = was_visible;
-                ui.checkbox(&mut current_visible, "");
+                // Show ALL layers regardless of top/bottom view
+                ui.horizontal(|ui| {
+                    let mut current_visible = was_visible;
+                    ui.checkbox(&mut current_visible, "");
                 
-                // Track visibility changes
-                if current_visible != was_visible {
-                    visibility_changes.push((layer_type, current_visible));
-                }
+                    // Track visibility changes
+                    if current_visible != was_visible {
+                        visibility_changes.push((layer_type, current_visible));
+                    }
                 
-                // Color picker - clickable color indicator box
-                let response = ui.allocate_response(Vec2::new(20.0, 16.0), egui::Sense::click());
-                ui.painter().rect_filled(response.rect, 2.0, current_color);
+                    // Color picker - clickable color indicator box
+                    let response = ui.allocate_response(Vec2::new(20.0, 16.0), egui::Sense::click());
+                    ui.painter().rect_filled(response.rect, 2.0, current_color);
                 
-                // Handle single and double clicks on color box
-                if response.double_clicked() {
-                    // Double-click: Show only this layer
-                    show_only_layer = Some(layer_type);
-                } else if response.clicked() {
-                    // Single click: Show color picker popup
-                    toggle_color_picker = Some(layer_type);
-                }
+                    // Handle single and double clicks on color box
+                    if response.double_clicked() {
+                        // Double-click: Show only this layer
+                        show_only_layer = Some(layer_type);
+                    } else if response.clicked() {
+                        // Single click: Show color picker popup
+                        toggle_color_picker = Some(layer_type);
+                    }
                 
-                // Show color picker popup if active
-                let show_picker = ui.ctx().memory(|mem| {
-                    mem.data.get_temp::<bool>(egui::Id::new(format!("color_picker_{:?}", layer_type))).unwrap_or(false)
-                });
+                    // Show color picker popup if active
+                    let show_picker = ui.ctx().memory(|mem| {
+                        mem.data.get_temp::<bool>(egui::Id::new(format!("color_picker_{:?}", layer_type))).unwrap_or(false)
+                    });
                 
-                if show_picker {
-                    egui::Window::new(format!("Color for {}", layer_type.display_name()))
-                        .id(egui::Id::new(format!("color_window_{:?}", layer_type)))
-                        .collapsible(false)
-                        .resizable(false)
-                        .show(ui.ctx(), |ui| {
-                            let mut color_array = [
-                                current_color.r() as f32 / 255.0,
-                                current_color.g() as f32 / 255.0,
-                                current_color.b() as f32 / 255.0,
-                            ];
+                    if show_picker {
+                        egui::Window::new(tr_args("layers.color_for", &[("layer", &layer_type.display_name())]))
+                            .id(egui::Id::new(format!("color_window_{:?}", layer_type)))
+                            .collapsible(false)
+                            .resizable(false)
+                            .show(ui.ctx(), |ui| {
+                                let mut color_array = [
+                                    current_color.r() as f32 / 255.0,
+                                    current_color.g() as f32 / 255.0,
+                                    current_color.b() as f32 / 255.0,
+                                ];
                             
-                            if ui.color_edit_button_rgb(&mut color_array).changed() {
-                                let new_color = egui::Color32::from_rgb(
-                                    (color_array[0] * 255.0) as u8,
-                                    (color_array[1] * 255.0) as u8,
-                                    (color_array[2] * 255.0) as u8,
-                                );
-                                color_changes.push((layer_type, new_color));
-                            }
-                            
-                            ui.horizontal(|ui| {
-                                if ui.button("Reset to Default").clicked() {
-                                    color_changes.push((layer_type, layer_type.color()));
-                                }
-                                if ui.button("Close").clicked() {
-                                    ui.ctx().memory_mut(|mem| {
-                                        mem.data.remove::<bool>(egui::Id::new(format!("color_picker_{:?}", layer_type)));
-                                    });
+                                if ui.color_edit_button_rgb(&mut color_array).changed() {
+                                    let new_color = egui::Color32::from_rgb(
+                                        (color_array[0] * 255.0) as u8,
+                                        (color_array[1] * 255.0) as u8,
+                                        (color_array[2] * 255.0) as u8,
+                                    );
+                                    color_changes.push((layer_type, new_color));
                                 }
+                            
+                                ui.horizontal(|ui| {
+                                    if ui.button(tr("layers.reset_color")).clicked() {
+                                        color_changes.push((layer_type, layer_type.color()));
+                                    }
+                                    if ui.button(tr("layers.close")).clicked() {
+                                        ui.ctx().memory_mut(|mem| {
+                                            mem.data.remove::<bool>(egui::Id::new(format!("color_picker_{:?}", layer_type)));
+                                        });
+                                    }
+                                });
                             });
-                        });
-                }
+                    }
                 
-                ui.label(layer_type.display_name());
+                    // Clicking the name makes it the active layer (V toggles it)
+                    let mut name_label = ui.selectable_label(layer_type == active_layer, layer_type.display_name());
+                    if name_label.clicked() {
+                        new_active_layer = Some(layer_type);
+                    }
+                    name_label.context_menu(|ui| {
+                        if ui.button(tr("nudge.adjust_position")).clicked() {
+                            app.layer_nudge.adjusting = Some(layer_type);
+                            ui.close();
+                        }
+                    });
+                    if let Some(position) = loaded_layers.iter().position(|loaded| *loaded == layer_type).filter(|p| *p < 9) {
+                        name_label = name_label.on_hover_text(tr_args("layers.alt_toggle_hint", &[("key", &(position + 1))]));
+                    }
+                    if let Some(flash_count) = flash_count {
+                        name_label.on_hover_text(tr_args("layers.flash_count", &[("count", &flash_count)]));
+                    }
+                    if let Some(format) = format {
+                        crate::ui::format_override::show_format_badge(ui, app, FormatTarget::Layer(layer_type), format);
+                    }
+                    if let Some(nudge) = crate::ecs::get_layer_nudge(&mut app.ecs_world, layer_type).filter(|nudge| !nudge.is_identity()) {
+                        crate::ui::layer_nudge::show_offset_badge(ui, app, layer_type, nudge);
+                    }
+                    if let Some(diagnostics) = &diagnostics {
+                        crate::ui::parse_problems::show_problems_badge(ui, app, diagnostics);
+                    }
+                    if polarity.is_some_and(|polarity| polarity.negative) {
+                        ui.label(egui::RichText::new(tr("layers.negative_badge")).small().color(NEGATIVE_BADGE_COLOR))
+                            .on_hover_text(tr("layers.negative_hint"));
+                    } else if polarity.is_some_and(|polarity| polarity.has_clear) {
+                        ui.label(egui::RichText::new("LPC").small().color(egui::Color32::GRAY))
+                            .on_hover_text(tr("layers.clear_hint"));
+                    }
                 
-                if current_visible != was_visible {
-                    logger.log_info(&format!("{} layer {}", 
-                        layer_type.display_name(),
-                        if current_visible { "shown" } else { "hidden" }
-                    ));
-                }
-            });
+                    if !registration_issues.is_empty() {
+                        let details: Vec<String> = registration_issues.iter().map(|issue| issue.description()).collect();
+                        let warning = ui.add(egui::Label::new(egui::RichText::new("⚠").color(REGISTRATION_WARNING_COLOR))
+                            .sense(egui::Sense::click()))
+                            .on_hover_text(format!("{}\n{}", details.join("\n"), tr("layers.click_for_details")));
+                        if warning.clicked() {
+                            app.show_registration_details = true;
+                        }
+                    }
+                
+                    if current_visible != was_visible {
+                        logger.log_info(&format!("{} layer {}", 
+                            layer_type.display_name(),
+                            if current_visible { "shown" } else { "hidden" }
+                        ));
+                    }
+                });
+            }
         }
     }
     
@@ -182,6 +335,11 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
         crate::ecs::update_layer_render_properties(&mut app.ecs_world, layer_type, color);
     }
     
+    if let Some(layer_type) = new_active_layer {
+        crate::ecs::set_active_layer(&mut app.ecs_world, layer_type);
+        logger.log_info(&format!("Active layer: {}", layer_type.display_name()));
+    }
+    
     // Handle deferred actions after the UI loop
     if let Some(target_layer) = show_only_layer {
         for layer_type_iter in LayerType::all() {
@@ -200,19 +358,54 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
         });
     }
     
+    ui.add_space(4.0);
+    crate::ui::layer_nudge::show_layer_nudge_section(ui, app, &logger);
+    crate::ui::layer_nudge::show_adjust_position_window(ui.ctx(), app, &logger);
+    show_drill_hole_controls(ui, app, &logger);
+    crate::ui::drill_report::show_drill_report_window(ui.ctx(), app, &logger);
+    
     // Show unassigned gerbers section if any exist
     if crate::ecs::has_unassigned_gerbers(&app.ecs_world) {
         ui.add_space(8.0);
         ui.separator();
-        ui.heading("Unassigned Gerber Files");
-        ui.label("Please assign these files to their correct layer types:");
+        ui.heading(tr("layers.unassigned"));
+        ui.label(tr("layers.unassigned_hint"));
         ui.add_space(4.0);
         
         let mut assignments_to_make = Vec::new();
+        let unassigned_files = crate::ecs::get_unassigned_gerbers(&app.ecs_world);
+        let still_unassigned = |name: &String| unassigned_files.iter().any(|unassigned| &unassigned.filename == name);
+        app.unassigned_selection.selected.retain(still_unassigned);
+        app.unassigned_selection.unmatched.retain(still_unassigned);
+        
+        ui.horizontal(|ui| {
+            if ui.small_button(tr("layers.select_all")).clicked() {
+                app.unassigned_selection.selected = unassigned_files.iter().map(|unassigned| unassigned.filename.clone()).collect();
+            }
+            if ui.small_button(tr("layers.select_none")).clicked() {
+                app.unassigned_selection.selected.clear();
+            }
+        });
         
-        for unassigned in crate::ecs::get_unassigned_gerbers(&app.ecs_world) {
+        for unassigned in &unassigned_files {
             ui.horizontal(|ui| {
+                let mut checked = app.unassigned_selection.selected.contains(&unassigned.filename);
+                if ui.checkbox(&mut checked, "").changed() {
+                    if checked {
+                        app.unassigned_selection.selected.insert(unassigned.filename.clone());
+                    } else {
+                        app.unassigned_selection.selected.remove(&unassigned.filename);
+                    }
+                }
                 ui.label(&unassigned.filename);
+                if app.unassigned_selection.unmatched.contains(&unassigned.filename) {
+                    ui.colored_label(REGISTRATION_WARNING_COLOR, tr("layers.no_match"));
+                }
+                let format = crate::ecs::GerberFormat::from_content(&unassigned.content);
+                crate::ui::format_override::show_format_badge(ui, app, FormatTarget::Unassigned(unassigned.filename.clone()), format);
+                if let Some(diagnostics) = crate::ecs::saved_parse_diagnostics(&app.ecs_world, &unassigned.filename) {
+                    crate::ui::parse_problems::show_problems_badge(ui, app, &diagnostics);
+                }
                 ui.add_space(10.0);
                 
                 // Create dropdown for layer type selection
@@ -229,7 +422,7 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
                             let already_assigned = crate::ecs::get_layer_by_type_readonly(&mut app.ecs_world, layer_type).is_some();
                             
                             if already_assigned {
-                                ui.add_enabled(false, egui::Button::new(format!("✓ {} (assigned)", layer_type.display_name())));
+                                ui.add_enabled(false, egui::Button::new(tr_args("layers.assigned", &[("layer", &layer_type.display_name())])));
                             } else if ui.selectable_value(&mut assignments_to_make, vec![(unassigned.filename.clone(), layer_type)], layer_type.display_name()).clicked() {
                                 assignments_to_make.push((unassigned.filename.clone(), layer_type));
                             }
@@ -238,7 +431,42 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
             });
         }
         
+        let selected: Vec<String> = app.unassigned_selection.selected.iter().cloned().collect();
+        let mut bulk_assign = false;
+        if !selected.is_empty() {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(tr_args("layers.bulk_assign", &[("count", &selected.len())]));
+                egui::ComboBox::from_id_salt("bulk_assign_target")
+                    .selected_text(app.unassigned_selection.bulk_target.display_name())
+                    .show_ui(ui, |ui| {
+                        for layer_type in LayerType::all() {
+                            ui.selectable_value(&mut app.unassigned_selection.bulk_target, layer_type, layer_type.display_name());
+                        }
+                    });
+                bulk_assign = ui.button(tr("layers.bulk_assign_button"))
+                    .on_hover_text(tr("layers.bulk_assign_hint"))
+                    .clicked();
+            });
+        }
+        if bulk_assign {
+            let target = app.unassigned_selection.bulk_target;
+            let (assigned, left) = crate::ecs::bulk_assign_gerbers_system(&mut app.ecs_world, &selected, target);
+            for (filename, layer_type) in &assigned {
+                logger.log_info(&format!("Assigned {} to {:?}", filename, layer_type));
+            }
+            if !left.is_empty() {
+                logger.log_warning(&format!("No free {} layer for {}", target.display_name(), left.join(", ")));
+            }
+            app.unassigned_selection.selected.clear();
+            if !assigned.is_empty() {
+                app.needs_initial_view = true;
+                run_registration_check(&mut app.ecs_world, &logger);
+            }
+        }
+        
         // Apply assignments using ECS system
+        let assigned_any = !assignments_to_make.is_empty();
         for (filename, layer_type) in assignments_to_make {
             match crate::ecs::assign_gerber_to_layer_system(&mut app.ecs_world, filename.clone(), layer_type) {
                 Ok(_entity) => {
@@ -250,23 +478,232 @@ pub fn show_layers_panel<'a>(    ui: &mut egui::Ui,
                 }
             }
         }
+        if assigned_any {
+            run_registration_check(&mut app.ecs_world, &logger);
+        }
         
         if crate::ecs::has_unassigned_gerbers(&app.ecs_world) {
             ui.add_space(8.0);
-            if ui.button("Auto-detect All").clicked() {
+            if ui.button(tr("layers.auto_detect")).clicked() {
                 // Use ECS system for auto-detection and assignment
+                let total = crate::ecs::get_unassigned_gerbers(&app.ecs_world).len();
                 let newly_assigned = crate::ecs::auto_assign_gerbers_system(&mut app.ecs_world);
+                let remaining: Vec<String> = crate::ecs::get_unassigned_gerbers(&app.ecs_world)
+                    .into_iter()
+                    .map(|unassigned| unassigned.filename)
+                    .collect();
                 
-                if newly_assigned.is_empty() {
-                    logger.log_warning("Could not auto-detect any remaining files");
-                } else {
-                    for (filename, layer_type) in newly_assigned {
-                        logger.log_info(&format!("Auto-detected {} as {:?}", filename, layer_type));
-                    }
+                logger.log_info(&format!("Auto-assigned {} of {} remaining files", newly_assigned.len(), total));
+                for (filename, layer_type) in &newly_assigned {
+                    logger.log_info(&format!("Auto-detected {} as {:?}", filename, layer_type));
+                }
+                if !remaining.is_empty() {
+                    logger.log_warning(&format!("Could not classify {}; choose their layers by hand", remaining.join(", ")));
+                }
+                app.unassigned_selection.unmatched = remaining.into_iter().collect();
+                if !newly_assigned.is_empty() {
                     app.needs_initial_view = true;
+                    run_registration_check(&mut app.ecs_world, &logger);
                 }
             }
         }
     }
     
-}
\ No newline at end of file
+    show_registration_details(ui.ctx(), app, &logger);
+    crate::ui::format_override::show_format_override_window(ui.ctx(), app, &logger);
+}
+
+/// Check the loaded layers line up with the outline and share units/precision
+/// Each mismatch is logged; the layer panel marks the affected layers
+pub fn run_registration_check(world: &mut bevy_ecs::world::World, logger: &ReactiveEventLogger) {
+    let issues = crate::ecs::check_layer_registration(world);
+    for issue in &issues {
+        logger.log_warning(&format!("⚠️  Registration: {}", issue.format_message()));
+    }
+    if !issues.is_empty() {
+        logger.log_info("Click ⚠ next to a layer in the Layers panel for the full list");
+    }
+}
+
+/// Popup listing every registration mismatch, so the right file can be regenerated
+fn show_registration_details(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if !app.show_registration_details {
+        return;
+    }
+    
+    let issues = app.ecs_world.get_resource::<crate::ecs::RegistrationIssues>()
+        .map(|issues| issues.0.clone())
+        .unwrap_or_default();
+    let mut open = true;
+    let mut recheck = false;
+    egui::Window::new(tr("registration.title"))
+        .id(egui::Id::new("registration_details"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_width(480.0)
+        .show(ctx, |ui| {
+            if issues.is_empty() {
+                ui.label(tr("registration.ok"));
+            } else {
+                ui.label(tr("registration.issues"));
+                ui.add_space(4.0);
+                egui::Grid::new("registration_issues_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("registration.layer"));
+                        ui.strong(tr("registration.file"));
+                        ui.strong(tr("registration.problem"));
+                        ui.end_row();
+                        for issue in &issues {
+                            ui.label(issue.layer_type.display_name());
+                            ui.label(issue.file_name.as_deref().unwrap_or("-"));
+                            ui.label(egui::RichText::new(issue.description()).color(REGISTRATION_WARNING_COLOR));
+                            ui.end_row();
+                        }
+                    });
+            }
+            ui.add_space(4.0);
+            if ui.button(tr("registration.recheck")).clicked() {
+                recheck = true;
+            }
+        });
+    
+    if recheck {
+        run_registration_check(&mut app.ecs_world, logger);
+    }
+    app.show_registration_details = open;
+}
+
+/// Drill hole toggle with a legend for the plated and non-plated hole styles
+fn show_drill_hole_controls(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let holes = crate::ecs::get_drill_holes(&app.ecs_world);
+    if holes.is_empty() {
+        return;
+    }
+    let plated = holes.iter().filter(|hole| hole.plated).count();
+
+    ui.add_space(4.0);
+    ui.label(egui::RichText::new(tr("layers.drill")).small().strong());
+    if ui.checkbox(&mut app.display_manager.show_drill_holes, tr("layers.drill_show")).changed() {
+        logger.log_info(if app.display_manager.show_drill_holes { "Drill holes shown" } else { "Drill holes hidden" });
+    }
+    ui.add_enabled_ui(app.display_manager.show_drill_holes, |ui| {
+        ui.horizontal(|ui| {
+            use crate::display::DrillHoleStyle;
+            ui.radio_value(&mut app.display_manager.drill_hole_style, DrillHoleStyle::Knockout, tr("layers.drill_knockout"))
+                .on_hover_text(tr("layers.drill_knockout_hint"));
+            ui.radio_value(&mut app.display_manager.drill_hole_style, DrillHoleStyle::Outline, tr("layers.drill_outline"))
+                .on_hover_text(tr("layers.drill_outline_hint"));
+        });
+    });
+    let hole_fill = if app.display_manager.knocks_out_drill_holes() { ui.visuals().extreme_bg_color } else { egui::Color32::TRANSPARENT };
+    for (is_plated, count) in [(true, plated), (false, holes.len() - plated)] {
+        ui.horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(Vec2::new(20.0, 16.0), egui::Sense::hover());
+            let radius = 5.5;
+            if is_plated {
+                ui.painter().circle(rect.center(), radius, hole_fill, egui::Stroke::new(1.5, crate::ecs::PLATED_HOLE_RING_COLOR));
+            } else {
+                ui.painter().circle_stroke(rect.center(), radius, egui::Stroke::new(1.0, crate::ecs::NON_PLATED_HOLE_OUTLINE_COLOR));
+            }
+            let key = if is_plated { "layers.drill_plated" } else { "layers.drill_non_plated" };
+            ui.label(tr_args(key, &[("count", &count)]));
+        });
+    }
+    ui.horizontal(|ui| {
+        if ui.button(tr("drill_report.open")).on_hover_text(tr("drill_report.open_hint")).clicked() {
+            app.drill_report.open = true;
+        }
+        ui.checkbox(&mut app.drill_report.show_symbols, tr("drill_report.show_symbols"))
+            .on_hover_text(tr("drill_report.show_symbols_hint"));
+    });
+}
+
+/// Preset/imported color scheme picker with JSON import and export
+fn show_color_scheme_controls(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.horizontal(|ui| {
+        ui.label(tr("layers.color_scheme"));
+        let selected_name = app.color_schemes
+            .get(app.selected_color_scheme)
+            .map(|scheme| scheme.name.clone())
+            .unwrap_or_default();
+        egui::ComboBox::from_id_salt("layer_color_scheme")
+            .selected_text(selected_name)
+            .show_ui(ui, |ui| {
+                for (i, scheme) in app.color_schemes.iter().enumerate() {
+                    ui.selectable_value(&mut app.selected_color_scheme, i, &scheme.name);
+                }
+            });
+
+        if ui.button(tr("layers.apply_scheme")).on_hover_text(tr("layers.apply_scheme_hint")).clicked() {
+            if let Some(scheme) = app.color_schemes.get(app.selected_color_scheme) {
+                let updated = scheme.apply(&mut app.ecs_world);
+                logger.log_info(&format!("Applied color scheme '{}' to {} layer(s)", scheme.name, updated));
+                ui.ctx().request_repaint();
+            }
+        }
+
+        if ui.button(tr("layers.import_scheme")).on_hover_text(tr("layers.import_scheme_hint")).clicked() {
+            if let Some(path) = rfd::FileDialog::new().add_filter(tr("layers.color_scheme_filter"), &["json"]).pick_file() {
+                match crate::display::ColorScheme::load_from_file(&path) {
+                    Ok(scheme) => {
+                        let updated = scheme.apply(&mut app.ecs_world);
+                        logger.log_info(&format!("Imported color scheme '{}' ({} layer(s) updated)", scheme.name, updated));
+                        // Re-importing a scheme replaces the earlier copy
+                        match app.color_schemes.iter().position(|existing| existing.name == scheme.name) {
+                            Some(i) => {
+                                app.color_schemes[i] = scheme;
+                                app.selected_color_scheme = i;
+                            }
+                            None => {
+                                app.color_schemes.push(scheme);
+                                app.selected_color_scheme = app.color_schemes.len() - 1;
+                            }
+                        }
+                        ui.ctx().request_repaint();
+                    }
+                    Err(e) => logger.log_error(&format!("Failed to import color scheme: {}", e)),
+                }
+            }
+        }
+
+        if ui.button(tr("layers.export_scheme")).on_hover_text(tr("layers.export_scheme_hint")).clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter(tr("layers.color_scheme_filter"), &["json"])
+                .set_file_name("color_scheme.json")
+                .save_file()
+            {
+                let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("Custom").to_string();
+                let scheme = crate::display::ColorScheme::from_world(&name, &mut app.ecs_world);
+                match scheme.save_to_file(&path) {
+                    Ok(()) => logger.log_info(&format!("Exported color scheme to {}", path.display())),
+                    Err(e) => logger.log_error(&format!("Failed to export color scheme: {}", e)),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_filter_combines_text_side_and_copper() {
+        let mut filter = LayerListFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(LayerType::inner_copper(1), None));
+
+        filter.side = SideFilter::Top;
+        assert!(filter.matches(LayerType::Silkscreen(Side::Top), None));
+        assert!(!filter.matches(LayerType::inner_copper(1), None));
+        filter.copper_only = true;
+        assert!(!filter.matches(LayerType::Silkscreen(Side::Top), None));
+        assert!(filter.matches(LayerType::Copper(1), None));
+
+        let filter = LayerListFilter { text: "EDGE".to_string(), ..Default::default() };
+        assert!(filter.matches(LayerType::MechanicalOutline, Some("board-Edge_Cuts.gbr")));
+        assert!(!filter.matches(LayerType::Copper(1), Some("board-F_Cu.gbr")));
+    }
+}
@@ -0,0 +1,237 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ecs::{DEFAULT_NUDGE_ROTATION_STEP, DEFAULT_NUDGE_STEP, DisplayUnit, LayerNudge, LayerType};
+use crate::ui::i18n::{tr, tr_args};
+use crate::ui::unit_value::{self, UnitValue};
+
+const OFFSET_BADGE_COLOR: Color32 = Color32::from_rgb(52, 152, 219);
+
+/// Nudge panel options for the current session
+pub struct LayerNudgeState {
+    /// Arrow keys move the active layer while set
+    pub arrow_keys: bool,
+    /// Arrow-key step in board units
+    pub step: f64,
+    /// Layer whose "Adjust position" window is open
+    pub adjusting: Option<LayerType>,
+    /// Project the saved nudges were loaded from / are saved to
+    pub project_id: Option<String>,
+}
+
+impl Default for LayerNudgeState {
+    fn default() -> Self {
+        Self { arrow_keys: false, step: DEFAULT_NUDGE_STEP, adjusting: None, project_id: None }
+    }
+}
+
+/// Keep the saved nudges in step with the open project
+/// Called every frame; loads the new project's nudges (none if never saved) when the project changes
+pub fn sync_layer_nudge_project(app: &mut DemoLensApp) {
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let current_id = state.current_project.as_ref().map(|project| project.metadata.id.clone());
+    if current_id == app.layer_nudge.project_id {
+        return;
+    }
+
+    let nudges = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_layer_nudges(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load layer nudges: {}", e);
+            None
+        }),
+        _ => None,
+    };
+    crate::ecs::set_layer_nudges(&mut app.ecs_world, nudges.unwrap_or_default());
+    app.layer_nudge.project_id = current_id;
+}
+
+/// Store the saved nudges with the open project, if any
+fn save_layer_nudges(app: &DemoLensApp) {
+    let (Some(project_id), Some(state)) = (&app.layer_nudge.project_id, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        if let Err(e) = database.save_layer_nudges(project_id, &crate::ecs::get_layer_nudges(&app.ecs_world)) {
+            eprintln!("Failed to save layer nudges: {}", e);
+        }
+    }
+}
+
+/// Set a loaded layer's nudge and store it with the open project
+fn set_nudge(app: &mut DemoLensApp, layer_type: LayerType, nudge: LayerNudge) {
+    if crate::ecs::set_layer_nudge(&mut app.ecs_world, layer_type, nudge) {
+        save_layer_nudges(app);
+    }
+}
+
+/// Change of the active layer's nudge for this frame's arrow keys
+/// Shift moves ten steps, Alt+Left/Right rotates instead of moving
+pub fn nudge_from_keys(input: &egui::InputState, step: f64) -> Option<LayerNudge> {
+    let step = if input.modifiers.shift { step * 10.0 } else { step };
+    let pressed = |key| input.key_pressed(key);
+    let mut delta = LayerNudge::default();
+    if input.modifiers.alt {
+        if pressed(egui::Key::ArrowLeft) {
+            delta.rotation_degrees += DEFAULT_NUDGE_ROTATION_STEP;
+        }
+        if pressed(egui::Key::ArrowRight) {
+            delta.rotation_degrees -= DEFAULT_NUDGE_ROTATION_STEP;
+        }
+    } else {
+        if pressed(egui::Key::ArrowLeft) {
+            delta.x -= step;
+        }
+        if pressed(egui::Key::ArrowRight) {
+            delta.x += step;
+        }
+        if pressed(egui::Key::ArrowUp) {
+            delta.y += step;
+        }
+        if pressed(egui::Key::ArrowDown) {
+            delta.y -= step;
+        }
+    }
+    (!delta.is_identity()).then_some(delta)
+}
+
+/// Add a nudge delta to a loaded layer; returns the new nudge
+pub fn apply_nudge_delta(app: &mut DemoLensApp, layer_type: LayerType, delta: LayerNudge) -> Option<LayerNudge> {
+    let mut nudge = crate::ecs::get_layer_nudge(&mut app.ecs_world, layer_type)?;
+    nudge.x += delta.x;
+    nudge.y += delta.y;
+    nudge.rotation_degrees += delta.rotation_degrees;
+    set_nudge(app, layer_type, nudge);
+    Some(nudge)
+}
+
+/// X/Y offset and rotation of the active layer, for lining up a misregistered gerber
+pub fn show_layer_nudge_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let active_layer = crate::ecs::get_active_layer(&app.ecs_world);
+    egui::CollapsingHeader::new(tr("nudge.section"))
+        .id_salt("layer_nudge_section")
+        .default_open(false)
+        .show(ui, |ui| {
+            let Some(mut nudge) = crate::ecs::get_layer_nudge(&mut app.ecs_world, active_layer) else {
+                ui.label(RichText::new(tr_args("nudge.not_loaded", &[("layer", &active_layer.display_name())])).weak());
+                return;
+            };
+            ui.label(RichText::new(active_layer.display_name()).strong());
+
+            let unit = unit_value::display_unit(&app.ecs_world);
+            if show_nudge_grid(ui, "layer_nudge_grid", &mut nudge, unit, app.layer_nudge.step) {
+                set_nudge(app, active_layer, nudge);
+            }
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut app.layer_nudge.arrow_keys, tr("nudge.arrow_keys"))
+                    .on_hover_text(tr("nudge.arrow_keys_hint"));
+                ui.add(egui::DragValue::new(&mut app.layer_nudge.step).range(0.0001..=1.0).speed(0.001).prefix(tr("nudge.step_prefix")).suffix(" mm"));
+            });
+            if ui.add_enabled(!nudge.is_identity(), egui::Button::new(tr("nudge.reset_transform"))).clicked() {
+                set_nudge(app, active_layer, LayerNudge::default());
+                logger.log_info(&format!("Reset alignment of {}", active_layer.display_name()));
+            }
+        });
+}
+
+/// X/Y offset in the display unit and rotation; true when a value changed
+fn show_nudge_grid(ui: &mut egui::Ui, id: &str, nudge: &mut LayerNudge, unit: DisplayUnit, step: f64) -> bool {
+    let before = *nudge;
+    egui::Grid::new(id).num_columns(2).show(ui, |ui| {
+        ui.label(tr("nudge.x_offset"));
+        ui.add(UnitValue::new(&mut nudge.x, unit).speed_mm(step));
+        ui.end_row();
+        ui.label(tr("nudge.y_offset"));
+        ui.add(UnitValue::new(&mut nudge.y, unit).speed_mm(step));
+        ui.end_row();
+        ui.label(tr("nudge.rotation"));
+        ui.add(egui::DragValue::new(&mut nudge.rotation_degrees).speed(DEFAULT_NUDGE_ROTATION_STEP).suffix("°").max_decimals(3));
+        ui.end_row();
+    });
+    *nudge != before
+}
+
+/// "offset" badge on a layer row whose nudge is not zero; clicking it opens the adjust window
+pub fn show_offset_badge(ui: &mut egui::Ui, app: &mut DemoLensApp, layer_type: LayerType, nudge: LayerNudge) {
+    let unit = unit_value::display_unit(&app.ecs_world);
+    let mut details = tr_args("nudge.badge_hint", &[
+        ("x", &unit.format_mm(nudge.x, 3)),
+        ("y", &unit.format_mm(nudge.y, 3)),
+    ]);
+    if nudge.rotation_degrees != 0.0 {
+        details.push_str(&format!(", {:.3}°", nudge.rotation_degrees));
+    }
+    let badge = ui.add(egui::Label::new(RichText::new(tr("nudge.badge")).small().color(OFFSET_BADGE_COLOR)).sense(egui::Sense::click()))
+        .on_hover_text(crate::ui::i18n::localize_number(&details));
+    if badge.clicked() {
+        app.layer_nudge.adjusting = Some(layer_type);
+    }
+}
+
+/// Small window to move one layer, opened from the layer's context menu or its offset badge
+/// Changes apply as they are typed, so the layer moves under the window
+pub fn show_adjust_position_window(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(layer_type) = app.layer_nudge.adjusting else {
+        return;
+    };
+    let Some(mut nudge) = crate::ecs::get_layer_nudge(&mut app.ecs_world, layer_type) else {
+        app.layer_nudge.adjusting = None;
+        return;
+    };
+    let unit = unit_value::display_unit(&app.ecs_world);
+    let reference = crate::ecs::alignment_reference(layer_type)
+        .filter(|reference| crate::ecs::get_layer_nudge(&mut app.ecs_world, *reference).is_some());
+
+    let mut open = true;
+    let mut auto_align = false;
+    let mut reset = false;
+    let mut changed = false;
+    egui::Window::new(tr_args("nudge.window_title", &[("layer", &layer_type.display_name())]))
+        .id(egui::Id::new("adjust_layer_position"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            changed = show_nudge_grid(ui, "adjust_layer_position_grid", &mut nudge, unit, app.layer_nudge.step);
+            ui.horizontal(|ui| {
+                let hint = match reference {
+                    Some(reference) => tr_args("nudge.auto_align_hint", &[("layer", &reference.display_name())]),
+                    None => tr("nudge.auto_align_unavailable").to_string(),
+                };
+                auto_align = ui.add_enabled(reference.is_some(), egui::Button::new(tr("nudge.auto_align")))
+                    .on_hover_text(hint)
+                    .clicked();
+                reset = ui.add_enabled(!nudge.is_identity(), egui::Button::new(tr("nudge.reset"))).clicked();
+            });
+        });
+
+    if changed {
+        set_nudge(app, layer_type, nudge);
+    }
+    if auto_align {
+        match crate::ecs::auto_align_nudge(&mut app.ecs_world, layer_type) {
+            Ok((nudge, matched, total)) => {
+                set_nudge(app, layer_type, nudge);
+                logger.log_info(&format!(
+                    "Aligned {} to copper: offset {}, {} ({} of {} flashes matched)",
+                    layer_type.display_name(),
+                    unit.format_mm(nudge.x, 3),
+                    unit.format_mm(nudge.y, 3),
+                    matched,
+                    total,
+                ));
+            }
+            Err(e) => logger.log_warning(&e),
+        }
+    }
+    if reset {
+        set_nudge(app, layer_type, LayerNudge::default());
+        logger.log_info(&format!("Reset alignment of {}", layer_type.display_name()));
+    }
+    if !open {
+        app.layer_nudge.adjusting = None;
+    }
+}
@@ -0,0 +1,85 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ecs::{LayerSetIssue, LayerSetOperation, LayerSetSeverity};
+
+const INFO_COLOR: Color32 = Color32::from_rgb(120, 160, 200);
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 126, 34);
+const ERROR_COLOR: Color32 = Color32::from_rgb(231, 76, 60);
+
+fn severity_color(severity: LayerSetSeverity) -> Color32 {
+    match severity {
+        LayerSetSeverity::Info => INFO_COLOR,
+        LayerSetSeverity::Warning => WARNING_COLOR,
+        LayerSetSeverity::Blocking => ERROR_COLOR,
+    }
+}
+
+fn log_issue(issue: &LayerSetIssue, logger: &ReactiveEventLogger) {
+    match issue.severity() {
+        LayerSetSeverity::Info => logger.log_info(&format!("Layers: {}", issue.format_message())),
+        _ => logger.log_warning(&format!("⚠️  Layers: {}", issue.format_message())),
+    }
+}
+
+/// Check the layer set after a load and log every issue
+pub fn run_layer_set_check(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    for issue in crate::ecs::validate_layer_set(&mut app.ecs_world) {
+        log_issue(&issue, logger);
+    }
+}
+
+/// Check the layer set before `operation`, logging the issues
+/// Returns the logged error when an issue stops the operation; `action` names it
+pub fn check_before(app: &mut DemoLensApp, operation: LayerSetOperation, action: &str, logger: &ReactiveEventLogger) -> Result<(), String> {
+    let issues = crate::ecs::validate_layer_set(&mut app.ecs_world);
+    if let Some(blocking) = issues.iter().find(|issue| issue.blocks(operation)) {
+        let error = format!("Cannot {}: {}", action, blocking.message());
+        logger.log_error(&error);
+        logger.log_info(blocking.hint().as_str());
+        return Err(error);
+    }
+    for issue in issues.iter().filter(|issue| issue.severity() == LayerSetSeverity::Warning) {
+        log_issue(issue, logger);
+    }
+    Ok(())
+}
+
+/// One-line strip above the gerber view with the most severe layer set issue
+/// The details button lists every issue with its hint
+pub fn show_layer_set_strip(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    // Cheap enough to redo every frame, so hiding a layer shows up right away
+    let issues = crate::ecs::validate_layer_set(&mut app.ecs_world);
+    let Some(top) = issues.first() else {
+        return;
+    };
+    // Nothing loaded yet is the normal empty state, not worth a strip
+    if *top == LayerSetIssue::NoLayers {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        let color = severity_color(top.severity());
+        ui.label(RichText::new(format!("{} {}", top.severity().icon(), top.message())).color(color))
+            .on_hover_text(top.hint());
+        let label = if issues.len() > 1 { format!("Details ({})", issues.len()) } else { "Details".to_string() };
+        ui.menu_button(RichText::new(label).small(), |ui| {
+            ui.set_max_width(420.0);
+            for issue in &issues {
+                ui.label(RichText::new(format!("{} {}", issue.severity().icon(), issue.message())).color(severity_color(issue.severity())));
+                ui.label(RichText::new(issue.hint()).small().color(Color32::GRAY));
+                let blocked: Vec<&str> = [(LayerSetOperation::Drc, "DRC"), (LayerSetOperation::Export, "exports")]
+                    .into_iter()
+                    .filter(|(operation, _)| issue.blocks(*operation))
+                    .map(|(_, name)| name)
+                    .collect();
+                if !blocked.is_empty() {
+                    ui.label(RichText::new(format!("Blocks {}", blocked.join(" and "))).small().color(ERROR_COLOR));
+                }
+                ui.add_space(4.0);
+            }
+        });
+    });
+    ui.separator();
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::DemoLensApp;
+use crate::ecs::LayerType;
+use crate::project::ProjectState;
+use crate::ui::i18n::tr;
+
+/// Watcher on the gerber directory of the loaded project and the layers changed on disk since
+#[derive(Default)]
+pub struct LayerWatchState {
+    watcher: Option<(PathBuf, RecommendedWatcher, Receiver<notify::Result<Event>>)>,
+    /// Changed layers offered for reload, in the order they changed
+    pending: Vec<LayerType>,
+}
+
+impl LayerWatchState {
+    fn stop(&mut self) {
+        self.watcher = None;
+        self.pending.clear();
+    }
+}
+
+/// Loaded layers whose source file is among `paths`
+fn changed_layers(paths: &[PathBuf], assignments: &HashMap<String, LayerType>) -> Vec<LayerType> {
+    paths.iter()
+        .filter_map(|path| path.file_name())
+        .filter_map(|name| assignments.get(name.to_string_lossy().as_ref()).copied())
+        .collect()
+}
+
+fn start_watcher(gerber_dir: &Path, ctx: &egui::Context) -> Result<(RecommendedWatcher, Receiver<notify::Result<Event>>), String> {
+    let (sender, receiver) = channel();
+    let ctx = ctx.clone();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = sender.send(event);
+        ctx.request_repaint();
+    })
+    .map_err(|e| format!("Failed to start the gerber file watcher: {}", e))?;
+    watcher.watch(gerber_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", gerber_dir.display(), e))?;
+    Ok((watcher, receiver))
+}
+
+/// Keep the watcher on the gerber directory of the loaded project and collect changed layers
+/// Called every frame from the app; while gerbers are generated or loaded nothing is watched,
+/// since the whole project is reloaded then anyway
+pub fn poll_layer_watch(app: &mut DemoLensApp, ctx: &egui::Context) {
+    let gerber_dir = match &app.project_manager.state {
+        ProjectState::Ready { gerber_dir, .. } if app.watch_gerber_files => gerber_dir.clone(),
+        _ => {
+            app.layer_watch.stop();
+            return;
+        }
+    };
+
+    if app.layer_watch.watcher.as_ref().map(|(dir, _, _)| dir) != Some(&gerber_dir) {
+        app.layer_watch.stop();
+        match start_watcher(&gerber_dir, ctx) {
+            Ok((watcher, receiver)) => app.layer_watch.watcher = Some((gerber_dir, watcher, receiver)),
+            Err(e) => {
+                let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+                logger.log_error(&e);
+                logger.log_warning("Gerber file watching turned off");
+                app.watch_gerber_files = false;
+                return;
+            }
+        }
+    }
+
+    let Some((_, _, receiver)) = &app.layer_watch.watcher else {
+        return;
+    };
+    let paths: Vec<PathBuf> = receiver.try_iter()
+        .filter_map(Result::ok)
+        .filter(|event| matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)))
+        .flat_map(|event| event.paths)
+        .collect();
+    if !paths.is_empty() {
+        let assignments = crate::ecs::get_layer_assignments(&app.ecs_world);
+        for layer in changed_layers(&paths, &assignments) {
+            if !app.layer_watch.pending.contains(&layer) {
+                app.layer_watch.pending.push(layer);
+            }
+        }
+    }
+
+    show_reload_prompt(app, ctx);
+}
+
+/// Re-parse the given layers from disk in place, keeping their visibility, color and transform,
+/// then re-run DRC for them when there are results to update
+fn reload_layers(app: &mut DemoLensApp, layers: Vec<LayerType>, logger: &ReactiveEventLogger) {
+    let Some(gerber_dir) = app.project_manager.get_gerber_dir().cloned() else {
+        return;
+    };
+    let mut reloaded = 0;
+    for layer in layers {
+        app.layer_watch.pending.retain(|pending| *pending != layer);
+        match crate::ecs::reload_layer_from_file_system(&mut app.ecs_world, layer, &gerber_dir) {
+            Ok(true) => {
+                logger.log_info(&format!("Reloaded {} from disk", layer.display_name()));
+                reloaded += 1;
+            }
+            Ok(false) => logger.log_info(&format!("{} is unchanged on disk", layer.display_name())),
+            Err(e) => logger.log_error(&e),
+        }
+    }
+    if reloaded == 0 {
+        return;
+    }
+
+    crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+    crate::ui::parse_problems::log_parse_diagnostics(app, logger);
+    if app.drc_manager.has_results && app.drc_job.is_none() {
+        // Unchanged layers are served from the DRC cache
+        crate::ui::drc_panel::run_drc(app, logger, false);
+    }
+}
+
+fn show_reload_prompt(app: &mut DemoLensApp, ctx: &egui::Context) {
+    if app.layer_watch.pending.is_empty() {
+        return;
+    }
+
+    let mut reload = Vec::new();
+    let mut dismissed = false;
+    egui::Area::new(egui::Id::new("layer_watch_prompt"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -40.0))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(RichText::new(tr("dialog.reload.title")).strong());
+                for layer in &app.layer_watch.pending {
+                    ui.horizontal(|ui| {
+                        ui.label(layer.display_name());
+                        if ui.small_button(tr("dialog.reload.one")).clicked() {
+                            reload.push(*layer);
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("dialog.reload.all")).clicked() {
+                        reload = app.layer_watch.pending.clone();
+                    }
+                    dismissed = ui.button(tr("dialog.reload.ignore")).clicked();
+                });
+                ui.label(RichText::new(tr("dialog.reload.hint")).small().color(Color32::GRAY));
+            });
+        });
+
+    if dismissed {
+        app.layer_watch.pending.clear();
+    }
+    if !reload.is_empty() {
+        let logger_state = app.logger_state.clone();
+        let log_colors = app.log_colors.clone();
+        let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
+        reload_layers(app, reload, &logger);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_files_map_to_loaded_layers() {
+        let assignments = HashMap::from([
+            ("board-F_Cu.gbr".to_string(), LayerType::Copper(1)),
+            ("board-Edge_Cuts.gbr".to_string(), LayerType::MechanicalOutline),
+        ]);
+        let paths = [
+            PathBuf::from("/out/board-F_Cu.gbr"),
+            PathBuf::from("/out/board-B_Cu.gbr"),
+            PathBuf::from("/out/board-Edge_Cuts.gbr"),
+        ];
+        assert_eq!(changed_layers(&paths, &assignments), vec![LayerType::Copper(1), LayerType::MechanicalOutline]);
+    }
+}
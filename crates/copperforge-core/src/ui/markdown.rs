@@ -0,0 +1,281 @@
+//! Minimal Markdown rendering for project notes
+//!
+//! Covers what design notes and review checklists need: headings, bullet,
+//! numbered and task lists, fenced code blocks, rules, and inline bold,
+//! italic, code and links. Anything else is shown as plain text.
+
+use egui::{Color32, RichText};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Heading { level: u8, text: String },
+    Paragraph(String),
+    ListItem {
+        /// Nesting level from the indentation, two spaces per level
+        depth: usize,
+        /// Item number for numbered lists
+        number: Option<u32>,
+        /// Task list state for `- [ ]` / `- [x]` items
+        checked: Option<bool>,
+        text: String,
+    },
+    CodeBlock { language: String, code: String },
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Span {
+    Text(String),
+    Bold(String),
+    Italic(String),
+    Code(String),
+    Link { text: String, url: String },
+}
+
+fn list_item(line: &str) -> Option<Block> {
+    let trimmed = line.trim_start();
+    let depth = (line.len() - trimmed.len()) / 2;
+
+    let (number, rest) = if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")).or_else(|| trimmed.strip_prefix("+ ")) {
+        (None, rest)
+    } else {
+        let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+        let rest = trimmed[digits..].strip_prefix(". ").or_else(|| trimmed[digits..].strip_prefix(") "))?;
+        (Some(trimmed[..digits].parse().ok()?), rest)
+    };
+
+    let (checked, text) = if let Some(text) = rest.strip_prefix("[ ] ") {
+        (Some(false), text)
+    } else if let Some(text) = rest.strip_prefix("[x] ").or_else(|| rest.strip_prefix("[X] ")) {
+        (Some(true), text)
+    } else {
+        (None, rest)
+    };
+
+    Some(Block::ListItem { depth, number, checked, text: text.trim().to_string() })
+}
+
+/// Split markdown text into blocks; consecutive text lines join into one paragraph
+pub fn parse_blocks(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<(String, Vec<&str>)> = None;
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some((language, lines)) = code.as_mut() {
+            if trimmed.starts_with("```") {
+                blocks.push(Block::CodeBlock { language: std::mem::take(language), code: lines.join("\n") });
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if let Some(language) = trimmed.strip_prefix("```") {
+            flush(&mut paragraph, &mut blocks);
+            code = Some((language.trim().to_string(), Vec::new()));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else if trimmed.chars().all(|c| c == '-') && trimmed.len() >= 3 {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Rule);
+        } else if let Some(heading) = trimmed
+            .find(|c| c != '#')
+            .filter(|&level| (1..=6).contains(&level) && trimmed[level..].starts_with(' '))
+            .map(|level| Block::Heading { level: level as u8, text: trimmed[level..].trim().to_string() })
+        {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(heading);
+        } else if let Some(item) = list_item(line) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(item);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    // An unterminated fence still shows its contents
+    if let Some((language, lines)) = code {
+        blocks.push(Block::CodeBlock { language, code: lines.join("\n") });
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Split a line into styled spans; unmatched markers are kept as text
+pub fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => rest[1..].find('`').map(|end| (Span::Code(rest[1..1 + end].to_string()), end + 2)),
+            '*' if rest.starts_with("**") => rest[2..]
+                .find("**")
+                .filter(|&end| end > 0)
+                .map(|end| (Span::Bold(rest[2..2 + end].to_string()), end + 4)),
+            // `_` is left alone, it is too common in net and signal names
+            '*' => rest[1..]
+                .find('*')
+                .filter(|&end| end > 0 && !rest[1..].starts_with(' '))
+                .map(|end| (Span::Italic(rest[1..1 + end].to_string()), end + 2)),
+            '[' => rest.find("](").and_then(|middle| {
+                let end = middle + 2 + rest[middle + 2..].find(')')?;
+                Some((
+                    Span::Link { text: rest[1..middle].to_string(), url: rest[middle + 2..end].to_string() },
+                    end + 1,
+                ))
+            }),
+            _ => None,
+        };
+
+        match styled {
+            Some((span, consumed)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::Text(std::mem::take(&mut plain)));
+                }
+                spans.push(span);
+                rest = &rest[consumed..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::Text(plain));
+    }
+    spans
+}
+
+fn show_inline(ui: &mut egui::Ui, text: &str, base: impl Fn(String) -> RichText) {
+    ui.spacing_mut().item_spacing.x = 0.0;
+    for span in parse_inline(text) {
+        match span {
+            Span::Text(text) => {
+                ui.label(base(text));
+            }
+            Span::Bold(text) => {
+                ui.label(base(text).strong());
+            }
+            Span::Italic(text) => {
+                ui.label(base(text).italics());
+            }
+            Span::Code(text) => {
+                ui.label(base(text).code());
+            }
+            Span::Link { text, url } => {
+                ui.hyperlink_to(text, url);
+            }
+        }
+    }
+}
+
+/// Render markdown text into the current layout
+pub fn show_markdown(ui: &mut egui::Ui, text: &str) {
+    for block in parse_blocks(text) {
+        match block {
+            Block::Heading { level, text } => {
+                ui.add_space(if level == 1 { 6.0 } else { 4.0 });
+                let size = match level {
+                    1 => 20.0,
+                    2 => 17.0,
+                    _ => 15.0,
+                };
+                ui.horizontal_wrapped(|ui| show_inline(ui, &text, |t| RichText::new(t).size(size).strong()));
+            }
+            Block::Paragraph(text) => {
+                ui.horizontal_wrapped(|ui| show_inline(ui, &text, RichText::new));
+            }
+            Block::ListItem { depth, number, checked, text } => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.add_space(8.0 + depth as f32 * 16.0);
+                    match (checked, number) {
+                        (Some(mut checked), _) => {
+                            ui.add_enabled(false, egui::Checkbox::without_text(&mut checked));
+                        }
+                        (None, Some(number)) => {
+                            ui.label(format!("{}. ", number));
+                        }
+                        (None, None) => {
+                            ui.label("• ");
+                        }
+                    }
+                    show_inline(ui, &text, RichText::new);
+                });
+            }
+            Block::CodeBlock { code, .. } => {
+                egui::Frame::group(ui.style())
+                    .fill(ui.visuals().code_bg_color)
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        ui.label(RichText::new(code).monospace());
+                    });
+            }
+            Block::Rule => {
+                ui.separator();
+            }
+        }
+    }
+}
+
+/// Append a dated "Review" heading to the notes, on a line of its own after a blank line
+pub fn append_review_note(notes: &mut String, timestamp: &str) {
+    let trimmed = notes.trim_end().len();
+    notes.truncate(trimmed);
+    if !notes.is_empty() {
+        notes.push_str("\n\n");
+    }
+    notes.push_str(&format!("## Review {}\n\n", timestamp));
+}
+
+/// Placeholder shown instead of an empty preview
+pub fn show_empty_hint(ui: &mut egui::Ui, hint: &str) {
+    ui.label(RichText::new(hint).italics().color(Color32::GRAY));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blocks_and_inline_spans() {
+        let notes = "# Review\nSee the [datasheet](https://example.com/ds.pdf)\nfor **pinout**.\n\n- [x] Check `VBUS` fuse\n  - nested\n2. second\n```rust\nlet x = 1;\n```\n---";
+        let blocks = parse_blocks(notes);
+        assert_eq!(blocks[0], Block::Heading { level: 1, text: "Review".to_string() });
+        assert_eq!(blocks[1], Block::Paragraph("See the [datasheet](https://example.com/ds.pdf) for **pinout**.".to_string()));
+        assert_eq!(blocks[2], Block::ListItem { depth: 0, number: None, checked: Some(true), text: "Check `VBUS` fuse".to_string() });
+        assert_eq!(blocks[3], Block::ListItem { depth: 1, number: None, checked: None, text: "nested".to_string() });
+        assert_eq!(blocks[4], Block::ListItem { depth: 0, number: Some(2), checked: None, text: "second".to_string() });
+        assert_eq!(blocks[5], Block::CodeBlock { language: "rust".to_string(), code: "let x = 1;".to_string() });
+        assert_eq!(blocks[6], Block::Rule);
+
+        let Block::Paragraph(paragraph) = &blocks[1] else { unreachable!() };
+        assert_eq!(parse_inline(paragraph), vec![
+            Span::Text("See the ".to_string()),
+            Span::Link { text: "datasheet".to_string(), url: "https://example.com/ds.pdf".to_string() },
+            Span::Text(" for ".to_string()),
+            Span::Bold("pinout".to_string()),
+            Span::Text(".".to_string()),
+        ]);
+        // Unmatched markers stay literal
+        assert_eq!(parse_inline("2 * 3 [x"), vec![Span::Text("2 * 3 [x".to_string())]);
+
+        let mut notes = "- [ ] layout\n\n".to_string();
+        append_review_note(&mut notes, "2026-03-14 09:30");
+        assert_eq!(notes, "- [ ] layout\n\n## Review 2026-03-14 09:30\n\n");
+        assert_eq!(parse_blocks(&notes)[1], Block::Heading { level: 2, text: "Review 2026-03-14 09:30".to_string() });
+    }
+}
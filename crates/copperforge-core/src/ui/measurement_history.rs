@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use egui_lens::ReactiveEventLogger;
+use nalgebra::{Matrix3, Point2};
+
+use crate::DemoLensApp;
+use crate::ecs::DisplayUnit;
+use crate::ui::i18n::{tr, tr_args};
+use crate::ui::unit_value;
+
+/// A completed ruler measurement; points are kept in source gerber coordinates
+/// like picked points, so a recalled measurement stays on the board after the view turns
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasurementRecord {
+    pub start: Point2<f64>,
+    pub end: Point2<f64>,
+    /// Straight-line length in mm
+    pub distance: f64,
+    pub taken_at: DateTime<Local>,
+}
+
+/// Measurements taken this session, oldest first, and the one drawn back on the board
+#[derive(Default)]
+pub struct MeasurementHistory {
+    pub entries: Vec<MeasurementRecord>,
+    pub recalled: Option<usize>,
+}
+
+/// Matrix from source gerber coordinates to view coordinates, as the ruler sees them
+fn board_to_view_matrix(app: &mut DemoLensApp) -> Matrix3<f64> {
+    crate::ecs::get_board_view_transform(&mut app.ecs_world, &app.display_manager)
+        .map(|transform| transform.to_matrix())
+        .unwrap_or_else(Matrix3::identity)
+}
+
+/// Add a straight ruler measurement between two view positions
+/// Along-edge measurements are not recorded: their length depends on the outline, not the two points
+pub fn record_measurement(app: &mut DemoLensApp, start: Point2<f64>, end: Point2<f64>) {
+    let Some(view_to_board) = board_to_view_matrix(app).try_inverse() else {
+        return;
+    };
+    app.measurement_history.entries.push(MeasurementRecord {
+        start: view_to_board.transform_point(&start),
+        end: view_to_board.transform_point(&end),
+        distance: (end - start).norm(),
+        taken_at: Local::now(),
+    });
+    app.measurement_history.recalled = None;
+}
+
+/// View positions of the recalled measurement, if one is selected
+pub fn recalled_measurement(app: &mut DemoLensApp) -> Option<(Point2<f64>, Point2<f64>)> {
+    let record = app.measurement_history.entries.get(app.measurement_history.recalled?)?.clone();
+    let board_to_view = board_to_view_matrix(app);
+    Some((board_to_view.transform_point(&record.start), board_to_view.transform_point(&record.end)))
+}
+
+fn csv_value(mm: f64, unit: DisplayUnit) -> String {
+    unit.format_number(mm, unit_value::ENTRY_MM_DECIMALS)
+}
+
+/// CSV with one row per measurement; positions relative to `origin`, lengths in `unit`
+pub fn measurements_to_csv(records: &[MeasurementRecord], board_to_view: &Matrix3<f64>, origin: Point2<f64>, unit: DisplayUnit) -> String {
+    let suffix = unit.suffix();
+    let mut csv = format!("index,time,start_x_{suffix},start_y_{suffix},end_x_{suffix},end_y_{suffix},dx_{suffix},dy_{suffix},distance_{suffix}\n");
+    for (i, record) in records.iter().enumerate() {
+        let start = board_to_view.transform_point(&record.start) - origin;
+        let end = board_to_view.transform_point(&record.end) - origin;
+        let values = [start.x, start.y, end.x, end.y, (end.x - start.x).abs(), (end.y - start.y).abs(), record.distance];
+        let values: Vec<String> = values.iter().map(|mm| csv_value(*mm, unit)).collect();
+        csv.push_str(&format!("{},{},{}\n", i + 1, record.taken_at.format("%Y-%m-%d %H:%M:%S"), values.join(",")));
+    }
+    csv
+}
+
+fn export_csv(app: &mut DemoLensApp, path: &Path) -> Result<(), String> {
+    let board_to_view = board_to_view_matrix(app);
+    let unit = unit_value::display_unit(&app.ecs_world);
+    let csv = measurements_to_csv(&app.measurement_history.entries, &board_to_view, app.display_manager.design_origin(), unit);
+    std::fs::write(path, csv).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Toolbar dropdown listing this session's measurements; picking one draws it on the board again
+pub fn render_measurement_history_menu(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let count = app.measurement_history.entries.len();
+    if count == 0 {
+        return;
+    }
+    let unit = unit_value::display_unit(&app.ecs_world);
+    let logger_state = app.logger_state.clone();
+    let log_colors = app.log_colors.clone();
+    let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
+
+    let mut export = false;
+    let mut clear = false;
+    ui.menu_button(tr_args("history.menu", &[("count", &count)]), |ui| {
+        egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+            for (i, record) in app.measurement_history.entries.iter().enumerate() {
+                let selected = app.measurement_history.recalled == Some(i);
+                let text = crate::ui::i18n::localize_number(&format!(
+                    "#{}  {}  {}",
+                    i + 1,
+                    unit.format_mm(record.distance, 3),
+                    record.taken_at.format("%H:%M:%S"),
+                ));
+                if ui.selectable_label(selected, text).on_hover_text(tr("history.recall_hint")).clicked() {
+                    app.measurement_history.recalled = if selected { None } else { Some(i) };
+                }
+            }
+        });
+        ui.separator();
+        ui.horizontal(|ui| {
+            export = ui.button(tr("history.export")).clicked();
+            clear = ui.button(tr("history.clear")).clicked();
+        });
+    });
+
+    if export {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("measurements.csv")
+            .save_file()
+        {
+            match export_csv(app, &path) {
+                Ok(()) => logger.log_info(&format!("Exported {} measurement(s) to {}", count, path.display())),
+                Err(e) => logger.log_error(&e),
+            }
+        }
+    }
+    if clear {
+        app.measurement_history = MeasurementHistory::default();
+        logger.log_info("Cleared measurement history");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_rows_are_relative_to_origin_in_display_units() {
+        let taken_at = DateTime::parse_from_rfc3339("2026-03-01T09:30:00+00:00").unwrap().with_timezone(&Local);
+        let record = MeasurementRecord { start: Point2::new(1.0, 2.0), end: Point2::new(4.0, 6.0), distance: 5.0, taken_at };
+        // View turned 90° counter-clockwise, origin at (10, 0)
+        let board_to_view = Matrix3::new(0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+
+        let csv = measurements_to_csv(&[record.clone()], &board_to_view, Point2::new(10.0, 0.0), DisplayUnit::Millimeters);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "index,time,start_x_mm,start_y_mm,end_x_mm,end_y_mm,dx_mm,dy_mm,distance_mm");
+        assert!(lines[1].ends_with(",-12.0000,1.0000,-16.0000,4.0000,4.0000,3.0000,5.0000"), "{}", lines[1]);
+
+        let csv = measurements_to_csv(&[record], &Matrix3::identity(), Point2::origin(), DisplayUnit::Mils);
+        assert!(csv.lines().next().unwrap().ends_with("distance_mils"));
+        assert!(csv.ends_with(",196.850\n"), "{}", csv);
+    }
+}
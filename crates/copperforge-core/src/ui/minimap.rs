@@ -0,0 +1,265 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use egui::{Color32, Pos2, Rect, Sense, Stroke, TextureHandle, Vec2};
+use gerber_viewer::BoundingBox;
+use nalgebra::Point2;
+use serde::{Deserialize, Serialize};
+
+use crate::DemoLensApp;
+use crate::ecs::{LayerType, TaggedPrimitive};
+use crate::export::raster::{RasterGrid, primitives_bounds, rasterize_primitives, transform_primitives};
+
+/// Longest side of the inset, in points
+const MINIMAP_SIZE: f32 = 200.0;
+const MINIMAP_MARGIN: f32 = 10.0;
+/// Fill alpha of each layer in the overview, so stacked layers stay readable
+const LAYER_ALPHA: f32 = 0.45;
+const BACKGROUND_COLOR: Color32 = Color32::from_rgba_premultiplied(10, 10, 14, 220);
+const OUTLINE_COLOR: Color32 = Color32::from_rgb(220, 220, 220);
+const VIEWPORT_COLOR: Color32 = Color32::from_rgb(255, 200, 0);
+
+/// Viewport corner an overlay is anchored to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ViewportCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl ViewportCorner {
+    pub const ALL: [ViewportCorner; 4] = [Self::TopLeft, Self::TopRight, Self::BottomLeft, Self::BottomRight];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TopLeft => "Top left",
+            Self::TopRight => "Top right",
+            Self::BottomLeft => "Bottom left",
+            Self::BottomRight => "Bottom right",
+        }
+    }
+
+    /// Rectangle of `size` in this corner of `viewport`, `margin` in from the edges
+    pub fn place(&self, viewport: &Rect, size: Vec2, margin: f32) -> Rect {
+        let x = match self {
+            Self::TopLeft | Self::BottomLeft => viewport.min.x + margin,
+            Self::TopRight | Self::BottomRight => viewport.max.x - margin - size.x,
+        };
+        let y = match self {
+            Self::TopLeft | Self::TopRight => viewport.min.y + margin,
+            Self::BottomLeft | Self::BottomRight => viewport.max.y - margin - size.y,
+        };
+        Rect::from_min_size(Pos2::new(x, y), size)
+    }
+}
+
+/// Overview inset options, remembered in the project config
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinimapSettings {
+    pub enabled: bool,
+    pub corner: ViewportCorner,
+}
+
+impl Default for MinimapSettings {
+    fn default() -> Self {
+        Self { enabled: true, corner: ViewportCorner::default() }
+    }
+}
+
+/// Cached overview texture; rebuilt only when the layers drawn in it change
+#[derive(Default)]
+pub struct MinimapState {
+    texture: Option<TextureHandle>,
+    grid: Option<RasterGrid>,
+    /// Hash of the visible layers, colors, contents and transforms the texture was built from
+    key: Option<u64>,
+}
+
+/// One layer as drawn in the overview, in view coordinates
+struct OverviewLayer {
+    layer_type: LayerType,
+    color: Color32,
+    primitives: Vec<TaggedPrimitive>,
+}
+
+/// Visible layers with their colors and on-screen transforms, and a hash of all of it
+fn collect_overview_layers(app: &mut DemoLensApp, build: bool) -> (u64, Vec<OverviewLayer>) {
+    let mut hasher = DefaultHasher::new();
+    let content_hashes = crate::ecs::get_layer_content_hashes(&mut app.ecs_world);
+    let showing_top = app.display_manager.showing_top;
+    let mut layers = Vec::new();
+
+    for layer_type in LayerType::all() {
+        let Some(content_hash) = content_hashes.get(&layer_type) else {
+            continue;
+        };
+        let is_outline = layer_type == LayerType::MechanicalOutline;
+        let visible = crate::ecs::get_layer_visibility(&mut app.ecs_world, layer_type);
+        if !is_outline && !(visible && layer_type.should_render(showing_top)) {
+            continue;
+        }
+        let Some(matrix) = crate::ecs::get_layer_render_transform(&mut app.ecs_world, &app.display_manager, layer_type)
+            .map(|transform| transform.to_matrix())
+        else {
+            continue;
+        };
+        let color = if is_outline {
+            OUTLINE_COLOR
+        } else {
+            crate::ecs::get_layer_render_properties(&mut app.ecs_world, layer_type)
+                .map(|properties| properties.color)
+                .unwrap_or(Color32::GRAY)
+        };
+
+        (layer_type, content_hash, color.to_array()).hash(&mut hasher);
+        matrix.iter().for_each(|value| value.to_bits().hash(&mut hasher));
+        if build {
+            let primitives = crate::ecs::get_layer_primitives(&mut app.ecs_world, layer_type);
+            layers.push(OverviewLayer { layer_type, color, primitives: transform_primitives(&primitives, &matrix) });
+        }
+    }
+    (hasher.finish(), layers)
+}
+
+/// Rasterize the overview layers into an image no larger than the inset
+fn render_overview(layers: &[OverviewLayer], ppp: f32) -> Option<(RasterGrid, egui::ColorImage)> {
+    let all: Vec<TaggedPrimitive> = layers.iter().flat_map(|layer| layer.primitives.iter().cloned()).collect();
+    let bounds: BoundingBox = primitives_bounds(&all)?;
+    let max_pixels = (MINIMAP_SIZE * ppp).round() as u32;
+    let grid = RasterGrid::covering(&bounds, f64::MAX, max_pixels);
+
+    let mut pixels = vec![Color32::TRANSPARENT; grid.width as usize * grid.height as usize];
+    // Outline last so it stays on top of the copper
+    let ordered = layers.iter().filter(|l| l.layer_type != LayerType::MechanicalOutline)
+        .chain(layers.iter().filter(|l| l.layer_type == LayerType::MechanicalOutline));
+    for layer in ordered {
+        let alpha = if layer.layer_type == LayerType::MechanicalOutline { 1.0 } else { LAYER_ALPHA };
+        let color = layer.color.gamma_multiply(alpha);
+        let mask = rasterize_primitives(&layer.primitives, grid);
+        for (pixel, covered) in pixels.iter_mut().zip(&mask.pixels) {
+            if *covered {
+                *pixel = over(*pixel, color);
+            }
+        }
+    }
+
+    let bytes: Vec<u8> = pixels.iter().flat_map(|pixel| pixel.to_array()).collect();
+    let image = egui::ColorImage::from_rgba_premultiplied([grid.width as usize, grid.height as usize], &bytes);
+    Some((grid, image))
+}
+
+/// Premultiplied "source over destination" compositing
+fn over(dst: Color32, src: Color32) -> Color32 {
+    let keep = 255 - src.a() as u32;
+    let channel = |s: u8, d: u8| (s as u32 + (d as u32 * keep + 127) / 255).min(255) as u8;
+    Color32::from_rgba_premultiplied(
+        channel(src.r(), dst.r()),
+        channel(src.g(), dst.g()),
+        channel(src.b(), dst.b()),
+        channel(src.a(), dst.a()),
+    )
+}
+
+/// Inset rectangle for an image of `image_size` pixels in the chosen corner of the viewport
+fn inset_rect(viewport: &Rect, corner: ViewportCorner, image_size: Vec2) -> Rect {
+    let size = image_size * (MINIMAP_SIZE / image_size.x.max(image_size.y));
+    corner.place(viewport, size, MINIMAP_MARGIN)
+}
+
+/// Inset position of a gerber point, and back
+fn gerber_to_inset(grid: &RasterGrid, inset: &Rect, point: Point2<f64>) -> Pos2 {
+    let scale = inset.width() / grid.width as f32;
+    Pos2::new(
+        inset.min.x + ((point.x - grid.top_left.x) * grid.pixels_per_mm) as f32 * scale,
+        inset.min.y + ((grid.top_left.y - point.y) * grid.pixels_per_mm) as f32 * scale,
+    )
+}
+
+fn inset_to_gerber(grid: &RasterGrid, inset: &Rect, pos: Pos2) -> Point2<f64> {
+    let scale = inset.width() / grid.width as f32;
+    Point2::new(
+        grid.top_left.x + ((pos.x - inset.min.x) / scale) as f64 / grid.pixels_per_mm,
+        grid.top_left.y - ((pos.y - inset.min.y) / scale) as f64 / grid.pixels_per_mm,
+    )
+}
+
+/// Minimap toggle and corner, shown in the View Settings tab
+pub fn show_minimap_settings(ui: &mut egui::Ui, settings: &mut MinimapSettings) {
+    ui.checkbox(&mut settings.enabled, "Show overview minimap")
+        .on_hover_text("Whole board with the visible area outlined; click or drag in it to move the view");
+    ui.add_enabled_ui(settings.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Corner:");
+            egui::ComboBox::from_id_salt("minimap_corner")
+                .selected_text(settings.corner.name())
+                .show_ui(ui, |ui| {
+                    for corner in ViewportCorner::ALL {
+                        ui.selectable_value(&mut settings.corner, corner, corner.name());
+                    }
+                });
+        });
+    });
+}
+
+/// Overview inset with the current viewport outlined; click or drag in it to recenter the view
+pub fn show_minimap(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect) {
+    if !app.minimap_settings.enabled {
+        return;
+    }
+
+    let (key, _) = collect_overview_layers(app, false);
+    if app.minimap.key != Some(key) {
+        let (_, layers) = collect_overview_layers(app, true);
+        let rendered = render_overview(&layers, ui.ctx().pixels_per_point());
+        app.minimap.grid = rendered.as_ref().map(|(grid, _)| *grid);
+        app.minimap.texture = rendered.map(|(_, image)| ui.ctx().load_texture("minimap", image, egui::TextureOptions::LINEAR));
+        app.minimap.key = Some(key);
+    }
+    let (Some(texture), Some(grid)) = (&app.minimap.texture, app.minimap.grid) else {
+        return;
+    };
+
+    let inset = inset_rect(viewport, app.minimap_settings.corner, texture.size_vec2());
+    let response = ui.interact(inset, ui.id().with("minimap"), Sense::click_and_drag());
+    if let Some(pos) = response.interact_pointer_pos().filter(|_| response.clicked() || response.dragged()) {
+        let target = inset_to_gerber(&grid, &inset, inset.clamp(pos));
+        let screen = app.view_state.gerber_to_screen_coords(target);
+        app.view_state.translation += viewport.center() - screen;
+        app.sync_zoom_to_ecs();
+    }
+
+    let painter = ui.painter().with_clip_rect(*viewport);
+    painter.rect_filled(inset.expand(2.0), 3.0, BACKGROUND_COLOR);
+    painter.image(texture.id(), inset, Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)), Color32::WHITE);
+
+    let visible_min = app.view_state.screen_to_gerber_coords(viewport.left_bottom());
+    let visible_max = app.view_state.screen_to_gerber_coords(viewport.right_top());
+    let view_rect = Rect::from_two_pos(
+        gerber_to_inset(&grid, &inset, visible_min),
+        gerber_to_inset(&grid, &inset, visible_max),
+    );
+    painter.with_clip_rect(inset).rect_stroke(view_rect, 0.0, Stroke::new(1.5, VIEWPORT_COLOR), egui::StrokeKind::Middle);
+    painter.rect_stroke(inset.expand(2.0), 3.0, Stroke::new(1.0, Color32::from_gray(90)), egui::StrokeKind::Outside);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inset_mapping_round_trips() {
+        let grid = RasterGrid { top_left: Point2::new(-10.0, 40.0), pixels_per_mm: 2.0, width: 200, height: 100 };
+        let viewport = Rect::from_min_size(Pos2::ZERO, Vec2::new(800.0, 600.0));
+        let inset = inset_rect(&viewport, ViewportCorner::BottomRight, Vec2::new(200.0, 100.0));
+        assert_eq!(inset, Rect::from_min_size(Pos2::new(590.0, 490.0), Vec2::new(200.0, 100.0)));
+
+        let point = Point2::new(35.0, 12.5);
+        let back = inset_to_gerber(&grid, &inset, gerber_to_inset(&grid, &inset, point));
+        assert!((back - point).norm() < 1e-4);
+        // Top-left of the grid lands on the inset corner
+        assert_eq!(gerber_to_inset(&grid, &inset, grid.top_left), inset.min);
+    }
+}
@@ -10,6 +10,42 @@ pub mod tabs;
 pub mod selection;
 pub mod bom_panel_v2;
 pub mod project_manager_panel;
+pub mod analysis_panel;
+pub mod revision_compare;
+pub mod point_picker;
+pub mod annotations;
+pub mod drc_markers;
+pub mod drc_violations;
+pub mod gestures;
+pub mod assembly_checks;
+pub mod markdown;
+pub mod minimap;
+pub mod dro;
+pub mod batch_drc;
+pub mod board_size;
+pub mod format_override;
+pub mod layer_nudge;
+pub mod view_clipboard;
+pub mod parse_problems;
+pub mod realistic_view;
+pub mod unit_value;
+pub mod netlist_checks;
+pub mod drc_profiles;
+pub mod view_3d;
+pub mod layer_set;
+pub mod key_bindings;
+pub mod provenance;
+pub mod post_gen_hook;
+pub mod layer_watch;
+pub mod panel;
+pub mod recent_projects;
+pub mod i18n;
+pub mod measurement_history;
+pub mod empty_state;
+pub mod status_bar;
+pub mod workspace_sync;
+pub mod primitive_inspect;
+pub mod drill_report;
 
 // Re-export the show functions for each panel
 pub use layer_controls::show_layers_panel;
@@ -17,8 +53,17 @@ pub use drc_panel::show_drc_panel;
 pub use grid_settings::show_grid_panel;
 pub use project_panel::show_project_panel;
 pub use settings_panel::show_settings_panel;
+pub use analysis_panel::show_analysis_panel;
 pub use about_panel::AboutPanel;
 pub use bom_panel_v2::{show_bom_panel, BomPanelState};
+pub use revision_compare::RevisionCompareState;
+pub use point_picker::{show_point_picker_panel, PointPickerState};
+pub use annotations::{show_annotations_panel, AnnotationState};
+pub use drill_report::DrillReportState;
+pub use assembly_checks::AssemblyCheckState;
+pub use netlist_checks::NetlistCheckState;
+pub use drc_profiles::DrcProfileState;
+pub use view_3d::View3dState;
 
 // Re-export tab-related types
 pub use tabs::{Tab, TabKind, TabViewer};
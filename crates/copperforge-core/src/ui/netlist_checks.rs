@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::kicad::{Net, NetlistComparison};
+use crate::project::constants::LOG_TYPE_DRC;
+
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 126, 34);
+
+/// Imported KiCad netlist and its comparison with the loaded copper
+#[derive(Default)]
+pub struct NetlistCheckState {
+    pub path: Option<PathBuf>,
+    pub nets: Vec<Net>,
+    pub comparison: Option<NetlistComparison>,
+}
+
+fn import_netlist(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(path) = rfd::FileDialog::new()
+        .set_title("Import KiCad netlist")
+        .add_filter("KiCad netlist", &["net"])
+        .pick_file()
+    else {
+        return;
+    };
+    match crate::kicad::parse_net(&path) {
+        Ok(nets) => {
+            let pads: usize = nets.iter().map(|net| net.pads.len()).sum();
+            logger.log_info(&format!("Imported netlist {}: {} nets, {} pads", path.display(), nets.len(), pads));
+            app.netlist_checks = NetlistCheckState { path: Some(path), nets, comparison: None };
+        }
+        Err(e) => logger.log_error(&format!("Netlist import failed: {}", e)),
+    }
+}
+
+fn compare_netlist(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if crate::ecs::get_net_list(&app.ecs_world).is_empty() {
+        logger.log_warning("The loaded gerbers carry no net attributes (X2 %TO.N); nothing to compare the netlist with");
+        return;
+    }
+    let comparison = crate::kicad::compare_with_copper(&app.netlist_checks.nets, &app.ecs_world);
+    logger.log_custom(LOG_TYPE_DRC, &format!(
+        "Netlist vs copper: {} nets matched, {} missing from copper, {} copper nets not in the netlist",
+        comparison.matched,
+        comparison.missing_in_copper.len(),
+        comparison.unknown_in_netlist.len()
+    ));
+    app.netlist_checks.comparison = Some(comparison);
+}
+
+fn name_list(ui: &mut egui::Ui, id: &str, title: String, names: &[String]) {
+    if names.is_empty() {
+        return;
+    }
+    egui::CollapsingHeader::new(RichText::new(title).color(WARNING_COLOR))
+        .id_salt(id)
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical().id_salt((id, "scroll")).max_height(140.0).show(ui, |ui| {
+                for name in names {
+                    ui.label(name);
+                }
+            });
+        });
+}
+
+/// Netlist import, net/pad counts and the name cross-check against the copper
+pub fn show_netlist_checks_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    egui::CollapsingHeader::new("Netlist")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("📂 Import KiCad netlist…").clicked() {
+                    import_netlist(app, logger);
+                }
+                if ui.add_enabled(!app.netlist_checks.nets.is_empty(), egui::Button::new("🔍 Compare with copper"))
+                    .on_hover_text("Match netlist net names with the net attributes in the loaded gerbers")
+                    .clicked()
+                {
+                    compare_netlist(app, logger);
+                }
+            });
+
+            let state = &app.netlist_checks;
+            let Some(path) = &state.path else {
+                ui.label(RichText::new("No netlist imported").color(Color32::GRAY));
+                return;
+            };
+            let pads: usize = state.nets.iter().map(|net| net.pads.len()).sum();
+            ui.label(format!(
+                "{}: {} nets, {} pads",
+                path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default(),
+                state.nets.len(),
+                pads
+            ));
+
+            egui::CollapsingHeader::new("Nets")
+                .id_salt("netlist_nets")
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical().id_salt("netlist_nets_scroll").max_height(180.0).show(ui, |ui| {
+                        egui::Grid::new("netlist_net_grid").striped(true).show(ui, |ui| {
+                            for net in &state.nets {
+                                ui.label(&net.name);
+                                ui.label(RichText::new(format!("{} pad{}", net.pads.len(), if net.pads.len() == 1 { "" } else { "s" }))
+                                    .color(Color32::GRAY))
+                                    .on_hover_text(net.pads.iter()
+                                        .map(|pad| format!("{}-{}", pad.reference, pad.pin))
+                                        .collect::<Vec<_>>()
+                                        .join(", "));
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+
+            if let Some(comparison) = &state.comparison {
+                ui.label(format!("{} of {} nets found on the copper", comparison.matched, state.nets.len()));
+                name_list(ui, "netlist_missing", format!("Missing from copper ({})", comparison.missing_in_copper.len()), &comparison.missing_in_copper);
+                name_list(ui, "netlist_unknown", format!("Not in the netlist ({})", comparison.unknown_in_netlist.len()), &comparison.unknown_in_netlist);
+                ui.label(RichText::new("Names only: unrouted and shorted nets need a connectivity check").small().color(Color32::GRAY));
+            }
+        });
+}
@@ -1,4 +1,6 @@
 use crate::DemoLensApp;
+use crate::export::PngResolution;
+use crate::ui::i18n::{tr, tr_args};
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::Dynamic;
 
@@ -14,11 +16,13 @@ pub fn show_orientation_panel<'a>(
 
 #[allow(dead_code)]
 /// Export layers from quadrant view to PNG files
-pub fn export_quadrant_layers_to_png(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+pub fn export_quadrant_layers_to_png(app: &mut DemoLensApp, logger: &ReactiveEventLogger) -> Result<(), String> {
     if !app.display_manager.quadrant_view_enabled {
-        logger.log_error("Quadrant view must be enabled to export layers as PNG");
-        return;
+        let error = "Quadrant view must be enabled to export layers as PNG".to_string();
+        logger.log_error(&error);
+        return Err(error);
     }
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Export, "export PNGs", logger)?;
     
     // Use the downloads directory for exports
     let export_dir = if let Some(downloads_dir) = dirs::download_dir() {
@@ -27,13 +31,15 @@ pub fn export_quadrant_layers_to_png(app: &mut DemoLensApp, logger: &ReactiveEve
         std::env::current_dir().unwrap_or_default().join("layer_exports")
     };
     
-    // Standard resolution for PCB layer exports (300 DPI equivalent)
-    let width = 2048;
-    let height = 2048;
+    let resolution = app.png_export_settings.resolution;
     
     logger.log_info(&format!("Starting PNG export to: {}", export_dir.display()));
+    if crate::ecs::get_board_outline_primitives(&mut app.ecs_world).is_some_and(|(_, inferred)| inferred) {
+        logger.log_info("No mechanical outline loaded - using the bounding box of the visible layers as the board outline");
+    }
     
-    match crate::export::PngExporter::export_quadrant_layers(app, &export_dir, width, height) {
+    let annotations = app.annotations.exported();
+    match crate::export::PngExporter::export_quadrant_layers(&mut app.ecs_world, &app.display_manager, &export_dir, resolution, &annotations) {
         Ok(exported_files) => {
             logger.log_info(&format!("Successfully exported {} layer files:", exported_files.len()));
             for file_path in exported_files {
@@ -42,9 +48,98 @@ pub fn export_quadrant_layers_to_png(app: &mut DemoLensApp, logger: &ReactiveEve
                 }
             }
             logger.log_info(&format!("Export directory: {}", export_dir.display()));
+            Ok(())
         },
         Err(error) => {
-            logger.log_error(&format!("PNG export failed: {}", error));
+            let error = format!("PNG export failed: {}", error);
+            logger.log_error(&error);
+            Err(error)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Dialog choosing the PNG export resolution before exporting the quadrant layers
+pub fn show_png_export_dialog(ctx: &egui::Context, app: &mut DemoLensApp) {
+    if !app.show_png_export_dialog {
+        return;
+    }
+
+    let mut open = true;
+    let mut export_clicked = false;
+    let master_bbox = crate::export::PngExporter::master_bounding_box(&mut app.ecs_world, &app.display_manager);
+
+    egui::Window::new(tr("png.title"))
+        .id(egui::Id::new("png_export_dialog"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let resolution = &mut app.png_export_settings.resolution;
+            let by_dpi = matches!(resolution, PngResolution::Dpi(_));
+
+            ui.horizontal(|ui| {
+                if ui.radio(!by_dpi, tr("png.pixel_size")).clicked() && by_dpi {
+                    *resolution = PngResolution::Pixels { width: 2048, height: 2048 };
+                }
+                if ui.radio(by_dpi, tr("png.dpi")).clicked() && !by_dpi {
+                    *resolution = PngResolution::Dpi(600.0);
+                }
+            });
+
+            match resolution {
+                PngResolution::Pixels { width, height } => {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("png.width"));
+                        ui.add(egui::DragValue::new(width).range(1..=u32::MAX).suffix(" px"));
+                        ui.label(tr("png.height"));
+                        ui.add(egui::DragValue::new(height).range(1..=u32::MAX).suffix(" px"));
+                    });
+                }
+                PngResolution::Dpi(dpi) => {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("png.resolution"));
+                        ui.add(egui::DragValue::new(dpi).range(1.0..=10_000.0).speed(10.0).suffix(" DPI"));
+                    });
+                }
+            }
+
+            ui.checkbox(&mut app.annotations.include_in_exports, tr("png.annotations"))
+                .on_hover_text(tr("png.annotations_hint"));
+
+            ui.separator();
+            let size = master_bbox.as_ref()
+                .map_err(|e| e.clone())
+                .and_then(|bbox| resolution.image_size(bbox).map(|size| (size, bbox)));
+            match &size {
+                Ok(((width, height), bbox)) => {
+                    ui.label(tr_args("png.output", &[
+                        ("width", width),
+                        ("height", height),
+                        ("board_width", &format!("{:.1}", bbox.width())),
+                        ("board_height", &format!("{:.1}", bbox.height())),
+                    ]));
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), e);
+                }
+            }
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.add_enabled(size.is_ok(), egui::Button::new(tr("png.export"))).clicked() {
+                    export_clicked = true;
+                }
+                if ui.button(tr("png.cancel")).clicked() {
+                    app.show_png_export_dialog = false;
+                }
+            });
+        });
+
+    if export_clicked {
+        app.show_png_export_dialog = false;
+        crate::ui::status_bar::defer_logged(app, crate::ui::i18n::tr("status.exporting_pngs"), export_quadrant_layers_to_png);
+    }
+    if !open {
+        app.show_png_export_dialog = false;
+    }
+}
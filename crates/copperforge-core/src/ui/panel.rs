@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ecs::{LayerType, PanelSettings};
+use crate::ui::unit_value::{self, UnitValue};
+
+/// Panel grid, spacing and rails, shown in the View Settings tab
+/// Changing the layout reframes the view so the whole panel stays visible
+pub fn show_panel_settings(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let unit = unit_value::display_unit(&app.ecs_world);
+    let mut settings = *app.ecs_world.resource::<PanelSettings>();
+
+    ui.checkbox(&mut settings.enabled, "Show panel")
+        .on_hover_text("Repeat the board in a grid with rails, as it would be fabricated");
+    ui.add_enabled_ui(settings.enabled, |ui| {
+        egui::Grid::new("panel_settings").num_columns(2).show(ui, |ui| {
+            ui.label("Columns × rows:");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.columns).range(1..=20));
+                ui.label("×");
+                ui.add(egui::DragValue::new(&mut settings.rows).range(1..=20));
+            });
+            ui.end_row();
+
+            ui.label("Spacing X / Y:");
+            ui.horizontal(|ui| {
+                ui.add(UnitValue::new(&mut settings.spacing_x, unit).range_mm(0.0..=50.0));
+                ui.add(UnitValue::new(&mut settings.spacing_y, unit).range_mm(0.0..=50.0));
+            });
+            ui.end_row();
+
+            ui.label("Rail width:");
+            ui.add(UnitValue::new(&mut settings.rail_width, unit).range_mm(0.0..=50.0))
+                .on_hover_text("0 for no rails");
+            ui.end_row();
+
+            ui.label("Rail gap:");
+            ui.add(UnitValue::new(&mut settings.rail_gap, unit).range_mm(0.0..=50.0));
+            ui.end_row();
+        });
+        ui.checkbox(&mut settings.side_rails, "Rails on the left and right too");
+
+        if let Some(layout) = crate::ecs::get_panel_layout(&mut app.ecs_world) {
+            ui.label(format!(
+                "{} boards, panel {} × {}",
+                layout.offsets.len(),
+                unit.format_mm(layout.bounds.width(), 3),
+                unit.format_mm(layout.bounds.height(), 3),
+            ));
+        }
+    });
+
+    if settings != *app.ecs_world.resource::<PanelSettings>() {
+        *app.ecs_world.resource_mut::<PanelSettings>() = settings;
+        app.needs_initial_view = true;
+    }
+}
+
+/// Write every visible layer repeated over the panel, with the current display transform applied
+/// The outline gets every board outline plus the outer frame of the rails
+pub fn export_panel_gerbers(app: &mut DemoLensApp, pcb_path: &Path, output_dir: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Export, "export panel gerbers", logger)?;
+    let Some(layout) = crate::ecs::get_panel_layout(&mut app.ecs_world) else {
+        let error = "Turn on the panel in View Settings before exporting it".to_string();
+        logger.log_warning(&error);
+        return Err(error);
+    };
+
+    let mut layers = Vec::new();
+    for layer_type in LayerType::all() {
+        if !crate::ecs::get_layer_visibility(&mut app.ecs_world, layer_type) {
+            continue;
+        }
+        let primitives = crate::ecs::get_layer_primitives(&mut app.ecs_world, layer_type);
+        if primitives.is_empty() {
+            continue;
+        }
+        let primitives = if layer_type == LayerType::MechanicalOutline {
+            layout.panel_outline(&primitives)
+        } else {
+            crate::ecs::panelize_primitives(&primitives, &layout.offsets)
+        };
+        if let Some(transform) = crate::ecs::get_layer_export_transform(&mut app.ecs_world, &app.display_manager, layer_type) {
+            layers.push((layer_type, primitives, transform));
+        }
+    }
+
+    if layers.is_empty() {
+        let error = "No visible layers with exportable geometry".to_string();
+        logger.log_warning(&error);
+        return Err(error);
+    }
+
+    let base_name = pcb_path.file_stem()
+        .map(|stem| format!("{}-panel", stem.to_string_lossy()))
+        .unwrap_or_else(|| "panel".to_string());
+
+    match crate::export::gerber::export_transformed_layers(&layers, output_dir, &base_name) {
+        Ok((written, skipped)) => {
+            logger.log_info(&format!(
+                "Exported {} panel gerber(s) with {} boards to {}",
+                written.len(),
+                layout.offsets.len(),
+                output_dir.display(),
+            ));
+            if skipped > 0 {
+                logger.log_warning(&format!("{} flash(es) of undefined apertures could not be exported", skipped));
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let error = format!("Panel export failed: {}", e);
+            logger.log_error(&error);
+            Err(error)
+        }
+    }
+}
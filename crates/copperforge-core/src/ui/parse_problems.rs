@@ -0,0 +1,105 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ui::i18n::{tr, tr_args};
+use crate::ecs::{DiagnosticSeverity, ParseDiagnostics};
+use crate::project::constants::LOG_TYPE_PARSER;
+
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 126, 34);
+const ERROR_COLOR: Color32 = Color32::from_rgb(231, 76, 60);
+
+fn badge_color(diagnostics: &ParseDiagnostics) -> Color32 {
+    if diagnostics.has_errors() { ERROR_COLOR } else { WARNING_COLOR }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Problem count next to a layer or file; hover lists the messages, click opens all problems
+pub fn show_problems_badge(ui: &mut egui::Ui, app: &mut DemoLensApp, diagnostics: &ParseDiagnostics) {
+    let text = RichText::new(format!("⚠ {}", diagnostics.count())).small().color(badge_color(diagnostics));
+    let badge = ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_text(format!("{}\n\n{}", diagnostics.details(), tr("parse.click_hint")));
+    if badge.clicked() {
+        app.show_parse_problems = true;
+    }
+}
+
+/// Subtle "N warnings" note in the project ribbon after a load with parse problems
+pub fn show_problems_indicator(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let total = crate::ecs::get_load_diagnostics(&app.ecs_world).total();
+    if total == 0 {
+        return;
+    }
+    let text = RichText::new(tr_args(if total == 1 { "parse.warning_one" } else { "parse.warnings" }, &[("count", &total)])).small().color(WARNING_COLOR);
+    if ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_text(tr("parse.indicator_hint"))
+        .clicked()
+    {
+        app.show_parse_problems = true;
+    }
+}
+
+/// Log the parse problems of the last load under the parser category
+pub fn log_parse_diagnostics(app: &DemoLensApp, logger: &ReactiveEventLogger) {
+    let load_diagnostics = crate::ecs::get_load_diagnostics(&app.ecs_world);
+    for (file_name, diagnostics) in &load_diagnostics.0 {
+        for message in &diagnostics.messages {
+            logger.log_custom(LOG_TYPE_PARSER, &format!("{}: {}", file_name, message.text));
+        }
+        if diagnostics.truncated > 0 {
+            logger.log_custom(LOG_TYPE_PARSER, &format!("{}: {} more problems not shown", file_name, diagnostics.truncated));
+        }
+    }
+    let total = load_diagnostics.total();
+    if total > 0 {
+        logger.log_warning(&format!(
+            "{} parse warning{} in {} file{}; see the parser entries or the problems list",
+            total, plural(total), load_diagnostics.0.len(), plural(load_diagnostics.0.len())
+        ));
+    }
+}
+
+/// Every parse problem of the last load, grouped by file
+pub fn show_parse_problems_window(ctx: &egui::Context, app: &mut DemoLensApp) {
+    if !app.show_parse_problems {
+        return;
+    }
+    let load_diagnostics = crate::ecs::get_load_diagnostics(&app.ecs_world);
+
+    let mut open = true;
+    egui::Window::new(tr("parse.title"))
+        .id(egui::Id::new("parse_problems_window"))
+        .open(&mut open)
+        .default_width(480.0)
+        .show(ctx, |ui| {
+            if load_diagnostics.0.is_empty() {
+                ui.label(RichText::new(tr("parse.none")).color(Color32::GRAY));
+                return;
+            }
+            egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for (file_name, diagnostics) in &load_diagnostics.0 {
+                    egui::CollapsingHeader::new(RichText::new(format!("{} ({})", file_name, diagnostics.count())).color(badge_color(diagnostics)))
+                        .id_salt(("parse_problems", file_name))
+                        .default_open(load_diagnostics.0.len() == 1)
+                        .show(ui, |ui| {
+                            for message in &diagnostics.messages {
+                                let color = match message.severity {
+                                    DiagnosticSeverity::Warning => WARNING_COLOR,
+                                    DiagnosticSeverity::Error => ERROR_COLOR,
+                                };
+                                ui.label(RichText::new(&message.text).color(color));
+                            }
+                            if diagnostics.truncated > 0 {
+                                ui.label(RichText::new(tr_args("parse.more", &[("count", &diagnostics.truncated)])).italics().color(Color32::GRAY));
+                            }
+                        });
+                }
+            });
+        });
+    if !open {
+        app.show_parse_problems = false;
+    }
+}
@@ -0,0 +1,305 @@
+use egui::{Color32, Painter, Stroke};
+use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
+use egui_mobius_reactive::Dynamic;
+use nalgebra::{Matrix3, Point2};
+
+use crate::DemoLensApp;
+use crate::ecs::UnitsResource;
+use crate::project_manager::database::PickedPoint;
+
+const POINT_MARKER_RADIUS: f32 = 7.0;
+const POINT_MARKER_COLOR: Color32 = Color32::from_rgb(0, 200, 255);
+
+/// Probe points picked on the board for bring-up notes
+#[derive(Default)]
+pub struct PointPickerState {
+    /// Left-clicks in the viewport add points while active
+    pub active: bool,
+    pub points: Vec<PickedPoint>,
+    // Project the list was loaded from / is saved to
+    project_id: Option<String>,
+}
+
+/// Keep the point list in step with the open project
+/// Called every frame; loads the new project's points when the project changes
+pub fn sync_point_picker_project(app: &mut DemoLensApp) {
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let current_id = state.current_project.as_ref().map(|project| project.metadata.id.clone());
+    if current_id == app.point_picker.project_id {
+        return;
+    }
+
+    app.point_picker.points = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_picked_points(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load picked points: {}", e);
+            Vec::new()
+        }),
+        _ => Vec::new(),
+    };
+    app.point_picker.project_id = current_id;
+}
+
+/// Store the point list with the open project, if any
+fn save_points(app: &DemoLensApp) {
+    let (Some(project_id), Some(state)) = (&app.point_picker.project_id, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        if let Err(e) = database.save_picked_points(project_id, &app.point_picker.points) {
+            eprintln!("Failed to save picked points: {}", e);
+        }
+    }
+}
+
+/// Matrix from source gerber coordinates to view coordinates (rotation, mirroring, image transform)
+fn board_to_view_matrix(app: &mut DemoLensApp) -> Matrix3<f64> {
+    crate::ecs::get_board_view_transform(&mut app.ecs_world, &app.display_manager)
+        .map(|transform| transform.to_matrix())
+        .unwrap_or_else(Matrix3::identity)
+}
+
+/// Add a point at a view position (as reported by `screen_to_gerber_coords`)
+/// Snaps to the grid when enabled, same as the origin setter
+pub fn add_point_at(app: &mut DemoLensApp, view_pos: Point2<f64>) {
+    let snapped = if app.grid_settings.snap_enabled {
+        crate::display::snap_to_grid(view_pos, &app.grid_settings, app.display_manager.design_origin())
+    } else {
+        view_pos
+    };
+
+    let Some(view_to_board) = board_to_view_matrix(app).try_inverse() else {
+        return;
+    };
+    let position = view_to_board.transform_point(&snapped);
+    app.point_picker.points.push(PickedPoint {
+        x: position.x,
+        y: position.y,
+        label: String::new(),
+    });
+    save_points(app);
+
+    let board_to_view = board_to_view_matrix(app);
+    let relative = origin_relative(app, &board_to_view, app.point_picker.points.last().unwrap());
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    logger.log_info(&format!(
+        "Picked point {} at {}",
+        app.point_picker.points.len(),
+        format_position(units(app), relative)
+    ));
+}
+
+/// Position relative to the design origin, in view orientation (mm)
+fn origin_relative(app: &DemoLensApp, board_to_view: &Matrix3<f64>, point: &PickedPoint) -> Point2<f64> {
+    let view = board_to_view.transform_point(&Point2::new(point.x, point.y));
+    let design_offset = &app.display_manager.design_offset;
+    Point2::new(view.x - design_offset.x, view.y - design_offset.y)
+}
+
+fn units(app: &DemoLensApp) -> &UnitsResource {
+    app.ecs_world.get_resource::<UnitsResource>()
+        .expect("UnitsResource should exist")
+}
+
+fn to_display_units(units: &UnitsResource, value_mm: f64) -> f64 {
+    units.display_unit.from_mm(value_mm)
+}
+
+fn unit_suffix(units: &UnitsResource) -> &'static str {
+    units.unit_suffix()
+}
+
+fn format_position(units: &UnitsResource, position: Point2<f64>) -> String {
+    format!(
+        "({:.3}, {:.3}) {}",
+        to_display_units(units, position.x),
+        to_display_units(units, position.y),
+        unit_suffix(units)
+    )
+}
+
+/// Build CSV text with one row per point: index, X, Y and label
+pub fn points_to_csv(rows: &[(f64, f64, &str)], unit: &str) -> String {
+    let mut csv = format!("index,x_{unit},y_{unit},label\n");
+    for (i, (x, y, label)) in rows.iter().enumerate() {
+        let label = if label.contains([',', '"', '\n']) {
+            format!("\"{}\"", label.replace('"', "\"\""))
+        } else {
+            label.to_string()
+        };
+        csv.push_str(&format!("{},{:.4},{:.4},{}\n", i + 1, x, y, label));
+    }
+    csv
+}
+
+/// Toolbar toggle for the point picker
+pub fn render_point_picker_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let count = app.point_picker.points.len();
+    let text = if count > 0 { format!("📍 Pick Points ({})", count) } else { "📍 Pick Points".to_string() };
+    if ui.toggle_value(&mut app.point_picker.active, text)
+        .on_hover_text("Left-click the board to add probe points")
+        .changed()
+        && app.point_picker.active
+    {
+        // Clicks can only go to one tool at a time
+        app.ruler_active = false;
+        app.ruler_start = None;
+        app.ruler_end = None;
+        app.setting_origin_mode = false;
+        app.annotations.tool = None;
+    }
+}
+
+/// Draw each picked point as a small numbered circle
+pub fn render_picked_points(app: &mut DemoLensApp, painter: &Painter) {
+    if app.point_picker.points.is_empty() {
+        return;
+    }
+
+    let board_to_view = board_to_view_matrix(app);
+    for (i, point) in app.point_picker.points.iter().enumerate() {
+        let view = board_to_view.transform_point(&Point2::new(point.x, point.y));
+        let screen_pos = app.view_state.gerber_to_screen_coords(view);
+
+        painter.circle(
+            screen_pos,
+            POINT_MARKER_RADIUS,
+            Color32::from_black_alpha(160),
+            Stroke::new(1.5, POINT_MARKER_COLOR),
+        );
+        painter.text(
+            screen_pos,
+            egui::Align2::CENTER_CENTER,
+            (i + 1).to_string(),
+            egui::FontId::monospace(9.0),
+            POINT_MARKER_COLOR,
+        );
+    }
+}
+
+/// Panel listing the picked points with editable labels
+pub fn show_point_picker_panel<'a>(
+    ui: &mut egui::Ui,
+    app: &'a mut DemoLensApp,
+    logger_state: &'a Dynamic<ReactiveEventLoggerState>,
+    log_colors: &'a Dynamic<LogColors>,
+) {
+    let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
+
+    ui.heading("Picked Points");
+    ui.horizontal(|ui| {
+        render_point_picker_controls(ui, app);
+        if ui.add_enabled(!app.point_picker.points.is_empty(), egui::Button::new("📋 Copy all as CSV")).clicked() {
+            let board_to_view = board_to_view_matrix(app);
+            let units_resource = units(app);
+            let rows: Vec<(f64, f64, &str)> = app.point_picker.points.iter()
+                .map(|point| {
+                    let relative = origin_relative(app, &board_to_view, point);
+                    (
+                        to_display_units(units_resource, relative.x),
+                        to_display_units(units_resource, relative.y),
+                        point.label.as_str(),
+                    )
+                })
+                .collect();
+            ui.ctx().copy_text(points_to_csv(&rows, unit_suffix(units_resource)));
+            logger.log_info(&format!("Copied {} point(s) to the clipboard as CSV", rows.len()));
+        }
+        if ui.add_enabled(!app.point_picker.points.is_empty(), egui::Button::new("🗑 Clear")).clicked() {
+            app.point_picker.points.clear();
+            save_points(app);
+            logger.log_info("Cleared picked points");
+        }
+    });
+    ui.label(format!("Coordinates are relative to the design origin ({})", unit_suffix(units(app))));
+    ui.separator();
+
+    if app.point_picker.points.is_empty() {
+        ui.label("No points yet. Enable Pick Points and click on the board.");
+        return;
+    }
+
+    let board_to_view = board_to_view_matrix(app);
+    let positions: Vec<(f64, f64)> = app.point_picker.points.iter()
+        .map(|point| {
+            let relative = origin_relative(app, &board_to_view, point);
+            (to_display_units(units(app), relative.x), to_display_units(units(app), relative.y))
+        })
+        .collect();
+
+    let count = app.point_picker.points.len();
+    let mut move_up = None;
+    let mut move_down = None;
+    let mut remove = None;
+    let mut labels_changed = false;
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("picked_points_grid")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("#");
+                ui.strong("X");
+                ui.strong("Y");
+                ui.strong("Label");
+                ui.label("");
+                ui.end_row();
+
+                for (i, point) in app.point_picker.points.iter_mut().enumerate() {
+                    let (x, y) = positions[i];
+                    ui.label((i + 1).to_string());
+                    ui.monospace(format!("{:.3}", x));
+                    ui.monospace(format!("{:.3}", y));
+                    let response = ui.add(egui::TextEdit::singleline(&mut point.label).desired_width(140.0));
+                    if response.lost_focus() {
+                        labels_changed = true;
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(i > 0, egui::Button::new("⬆").small()).clicked() {
+                            move_up = Some(i);
+                        }
+                        if ui.add_enabled(i + 1 < count, egui::Button::new("⬇").small()).clicked() {
+                            move_down = Some(i);
+                        }
+                        if ui.small_button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+
+    let points = &mut app.point_picker.points;
+    let reordered = if let Some(i) = move_up {
+        points.swap(i, i - 1);
+        true
+    } else if let Some(i) = move_down {
+        points.swap(i, i + 1);
+        true
+    } else if let Some(i) = remove {
+        points.remove(i);
+        true
+    } else {
+        false
+    };
+
+    if reordered || labels_changed {
+        save_points(app);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_to_csv_quotes_labels() {
+        let csv = points_to_csv(&[(1.0, -2.5, "TP1"), (0.25, 3.0, "VCC, after \"R12\"")], "mm");
+        assert_eq!(
+            csv,
+            "index,x_mm,y_mm,label\n1,1.0000,-2.5000,TP1\n2,0.2500,3.0000,\"VCC, after \"\"R12\"\"\"\n"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::project_manager::post_gen_hook::{HOOK_VARIABLES, HookContext, HookEvent, HookOutcome, PostGenHookJob};
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(231, 76, 60);
+const TOAST_DURATION: Duration = Duration::from_secs(8);
+
+/// Running hook, the failure toast and the settings preview toggle
+#[derive(Default)]
+pub struct PostGenHookState {
+    pub job: Option<PostGenHookJob>,
+    toast: Option<(String, Instant)>,
+    show_preview: bool,
+}
+
+fn hook_context(app: &DemoLensApp, pcb_path: &Path, gerber_dir: &Path) -> HookContext {
+    let project_name = app.project_manager_state.as_ref()
+        .and_then(|state| state.current_project.as_ref())
+        .map(|project| project.metadata.name.as_str());
+    HookContext::new(gerber_dir, pcb_path, project_name)
+}
+
+/// Start the hook after a successful generation, when it is enabled
+pub fn run_after_generation(app: &mut DemoLensApp, pcb_path: &Path, gerber_dir: &Path, logger: &ReactiveEventLogger) {
+    let hook = &app.post_gen_hook;
+    if !hook.enabled || hook.command.trim().is_empty() {
+        return;
+    }
+    if app.post_gen_hook_state.job.is_some() {
+        logger.log_warning("Post-generation hook is still running from the last generation; not started again");
+        return;
+    }
+    let job = PostGenHookJob::spawn(hook, &hook_context(app, pcb_path, gerber_dir));
+    logger.log_info(&format!("Running post-generation hook: {}", job.command_line));
+    app.post_gen_hook_state.job = Some(job);
+}
+
+fn failure_message(outcome: &HookOutcome, timeout_secs: u64) -> Option<String> {
+    match outcome {
+        HookOutcome::Succeeded => None,
+        HookOutcome::Failed(Some(code)) => Some(format!("Post-generation hook failed with exit code {}", code)),
+        HookOutcome::Failed(None) => Some("Post-generation hook was terminated by a signal".to_string()),
+        HookOutcome::TimedOut => Some(format!("Post-generation hook timed out after {} s and was killed", timeout_secs)),
+        HookOutcome::Cancelled => None,
+        HookOutcome::Error(e) => Some(e.clone()),
+    }
+}
+
+/// Stream the hook output to the log and report how it ended
+/// Called every frame from the app so the hook runs with the settings closed
+pub fn poll_post_gen_hook(app: &mut DemoLensApp, ctx: &egui::Context) {
+    if let Some(job) = app.post_gen_hook_state.job.as_mut() {
+        ctx.request_repaint_after(Duration::from_millis(100));
+        let started = job.started;
+        let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+        for event in job.poll() {
+            match event {
+                HookEvent::Stdout(line) => logger.log_info(&format!("  hook: {}", line)),
+                HookEvent::Stderr(line) => logger.log_warning(&format!("  hook: {}", line)),
+                HookEvent::Finished(outcome) => {
+                    app.post_gen_hook_state.job = None;
+                    match failure_message(&outcome, app.post_gen_hook.timeout_secs) {
+                        Some(message) => {
+                            logger.log_error(&message);
+                            app.post_gen_hook_state.toast = Some((message, Instant::now()));
+                        }
+                        None if outcome == HookOutcome::Cancelled => logger.log_warning("Post-generation hook cancelled"),
+                        None => logger.log_info(&format!("Post-generation hook finished in {:.1} s", started.elapsed().as_secs_f32())),
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    show_failure_toast(app, ctx);
+}
+
+fn show_failure_toast(app: &mut DemoLensApp, ctx: &egui::Context) {
+    let Some((message, shown_at)) = &app.post_gen_hook_state.toast else {
+        return;
+    };
+    if shown_at.elapsed() > TOAST_DURATION {
+        app.post_gen_hook_state.toast = None;
+        return;
+    }
+    ctx.request_repaint_after(Duration::from_millis(250));
+
+    let mut dismissed = false;
+    egui::Area::new(egui::Id::new("post_gen_hook_toast"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -40.0))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("⚠ {}", message)).color(ERROR_COLOR));
+                    dismissed = ui.small_button("✖").clicked();
+                });
+                ui.label(RichText::new("See the event log for the hook output").small().color(Color32::GRAY));
+            });
+        });
+    if dismissed {
+        app.post_gen_hook_state.toast = None;
+    }
+}
+
+/// Paths of the open project for the preview; generated gerbers go next to the PCB
+fn preview_paths(app: &DemoLensApp) -> Option<(PathBuf, PathBuf)> {
+    let pcb_path = app.project_manager.get_pcb_path()?.clone();
+    let gerber_dir = app.project_manager.get_gerber_dir().cloned().unwrap_or_else(|| {
+        pcb_path.parent().unwrap_or(Path::new(".")).join("gerber_output")
+    });
+    Some((pcb_path, gerber_dir))
+}
+
+/// Hook settings: enable toggle, command template, timeout and a resolved preview
+pub fn show_post_gen_hook_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    ui.group(|ui| {
+        ui.label("Post-Generation Hook");
+        ui.checkbox(&mut app.post_gen_hook.enabled, "Run a command after gerbers are generated");
+
+        ui.add(
+            egui::TextEdit::multiline(&mut app.post_gen_hook.command)
+                .code_editor()
+                .desired_rows(2)
+                .desired_width(f32::INFINITY)
+                .hint_text("zip -r {project_name}_{timestamp}.zip ."),
+        );
+        ui.label(
+            RichText::new(format!(
+                "Variables: {}. Values are quoted for you; runs in the gerber directory.",
+                HOOK_VARIABLES.join(", ")
+            ))
+            .small()
+            .color(Color32::GRAY),
+        );
+
+        ui.horizontal(|ui| {
+            ui.label("Kill after");
+            ui.add(egui::DragValue::new(&mut app.post_gen_hook.timeout_secs).range(1..=3600).suffix(" s"));
+            ui.toggle_value(&mut app.post_gen_hook_state.show_preview, "Show resolved command");
+        });
+
+        if app.post_gen_hook_state.show_preview {
+            match preview_paths(app) {
+                Some((pcb_path, gerber_dir)) => {
+                    let context = hook_context(app, &pcb_path, &gerber_dir);
+                    let mut resolved = crate::project_manager::post_gen_hook::resolve_command(&app.post_gen_hook.command, &context);
+                    ui.add(egui::TextEdit::multiline(&mut resolved).code_editor().desired_rows(2).desired_width(f32::INFINITY).interactive(false));
+                    ui.label(RichText::new(format!("Working directory: {}", gerber_dir.display())).small().color(Color32::GRAY));
+                }
+                None => {
+                    ui.label(RichText::new("Select a PCB file to preview the command with its paths").color(Color32::GRAY));
+                }
+            }
+        }
+
+        if let Some(job) = &app.post_gen_hook_state.job {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(format!("Running for {} s", job.started.elapsed().as_secs()));
+                if ui.button("⏹ Kill").clicked() {
+                    job.cancel();
+                    logger.log_warning("Killing the post-generation hook");
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,119 @@
+use egui::{Color32, Painter, Pos2, Stroke, Vec2};
+
+use crate::DemoLensApp;
+use crate::ecs::{ApertureShape, DisplayUnit, PickedPrimitive, PrimitiveShape};
+use crate::ui::i18n::tr;
+
+/// How close (in screen pixels) the cursor must be to a primitive to inspect it
+const PICK_TOLERANCE_PX: f32 = 4.0;
+const SELECTION_COLOR: Color32 = Color32::from_rgb(0, 200, 255);
+
+/// Hovered and selected primitive of the gerber view
+#[derive(Default)]
+pub struct PrimitiveInspectState {
+    /// Cursor position and view (scale, translation) of the last hit test, with its result;
+    /// the test only reruns when one of them changes
+    hovered: Option<((Pos2, f32, Vec2), Option<PickedPrimitive>)>,
+    pub selected: Option<PickedPrimitive>,
+}
+
+fn pick_at(app: &mut DemoLensApp, screen_pos: Pos2) -> Option<PickedPrimitive> {
+    crate::ecs::pick_primitive_at_screen_pos(&mut app.ecs_world, &app.view_state, &app.display_manager, screen_pos, PICK_TOLERANCE_PX)
+}
+
+fn hovered_primitive(app: &mut DemoLensApp, screen_pos: Pos2) -> Option<PickedPrimitive> {
+    let key = (screen_pos, app.view_state.scale, app.view_state.translation);
+    if let Some((cached_key, picked)) = &app.primitive_inspect.hovered {
+        if *cached_key == key {
+            return picked.clone();
+        }
+    }
+    let picked = pick_at(app, screen_pos);
+    app.primitive_inspect.hovered = Some((key, picked.clone()));
+    picked
+}
+
+/// Property rows (label, value) of a picked primitive
+pub fn describe_primitive(picked: &PickedPrimitive, unit: DisplayUnit) -> Vec<(String, String)> {
+    let mut rows = Vec::new();
+    match &picked.primitive.shape {
+        PrimitiveShape::Segment { start, end, width } => {
+            rows.push((tr("inspect.kind").to_string(), tr("inspect.trace").to_string()));
+            rows.push((tr("inspect.width").to_string(), unit.format_mm(*width, 3)));
+            rows.push((tr("inspect.length").to_string(), unit.format_mm((end - start).norm(), 3)));
+        }
+        PrimitiveShape::Flash { width, height, aperture, .. } => {
+            rows.push((tr("inspect.kind").to_string(), tr("inspect.flash").to_string()));
+            let name = match aperture {
+                ApertureShape::Circle => tr("inspect.circle"),
+                ApertureShape::Rectangle => tr("inspect.rectangle"),
+                ApertureShape::Obround => tr("inspect.obround"),
+                ApertureShape::Polygon => tr("inspect.polygon"),
+                ApertureShape::Other => tr("inspect.macro"),
+            };
+            rows.push((tr("inspect.aperture").to_string(), name.to_string()));
+            let size = if matches!(aperture, ApertureShape::Circle | ApertureShape::Polygon) {
+                unit.format_mm(*width, 3)
+            } else {
+                format!("{} × {}", unit.format_mm(*width, 3), unit.format_mm(*height, 3))
+            };
+            rows.push((tr("inspect.size").to_string(), size));
+        }
+        PrimitiveShape::Region { points } => {
+            let (min, max) = picked.primitive.shape.bounds();
+            rows.push((tr("inspect.kind").to_string(), tr("inspect.region").to_string()));
+            rows.push((tr("inspect.vertices").to_string(), points.len().to_string()));
+            rows.push((tr("inspect.size").to_string(), format!("{} × {}", unit.format_mm(max.x - min.x, 3), unit.format_mm(max.y - min.y, 3))));
+        }
+    }
+    rows.push((tr("inspect.layer").to_string(), picked.layer_type.display_name()));
+    if let Some(net) = &picked.primitive.net {
+        rows.push((tr("inspect.net").to_string(), net.clone()));
+    }
+    rows
+}
+
+/// Tooltip with the properties of the primitive under the cursor; called while no tool is active
+pub fn show_hover_tooltip(ui: &egui::Ui, app: &mut DemoLensApp, response: &egui::Response, viewport: &egui::Rect) {
+    let Some(pointer) = ui.input(|i| i.pointer.hover_pos()).filter(|pos| viewport.contains(*pos)) else {
+        return;
+    };
+    if ui.input(|i| i.pointer.any_down()) {
+        return;
+    }
+    let Some(picked) = hovered_primitive(app, pointer) else {
+        return;
+    };
+    let rows = describe_primitive(&picked, app.display_unit);
+    response.clone().on_hover_ui_at_pointer(|ui| {
+        egui::Grid::new("primitive_inspect").num_columns(2).spacing([12.0, 2.0]).show(ui, |ui| {
+            for (label, value) in &rows {
+                ui.label(egui::RichText::new(label).color(Color32::GRAY));
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+    });
+}
+
+/// Select the primitive at a clicked position, or clear the selection when there is none
+pub fn select_at(app: &mut DemoLensApp, screen_pos: Pos2) {
+    app.primitive_inspect.selected = pick_at(app, screen_pos);
+}
+
+/// Outline of the selected primitive, drawn with its layer's transform
+pub fn draw_selection(app: &mut DemoLensApp, painter: &Painter) {
+    let Some(selected) = &app.primitive_inspect.selected else {
+        return;
+    };
+    let Some(matrix) = crate::ecs::get_layer_render_transform(&mut app.ecs_world, &app.display_manager, selected.layer_type)
+        .map(|transform| transform.to_matrix())
+    else {
+        return;
+    };
+    let points: Vec<Pos2> = crate::ecs::primitive_outline(&selected.primitive.shape)
+        .iter()
+        .map(|point| app.view_state.gerber_to_screen_coords(matrix.transform_point(point)))
+        .collect();
+    painter.add(egui::Shape::closed_line(points, Stroke::new(2.0, SELECTION_COLOR)));
+}
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 use crate::DemoLensApp;
+use crate::ui::i18n::{tr, tr_args};
 use crate::project_manager::ProjectManagerState;
+use crate::project_manager::templates::{templates_dir, BLANK_TEMPLATE_NAME};
+use crate::project_manager::archive::ARCHIVE_EXTENSION;
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::Dynamic;
 
@@ -15,13 +18,17 @@ pub fn show_project_manager_panel(
     
     // Initialize project manager state if not already done
     if app.project_manager_state.is_none() {
-        let mut state = ProjectManagerState::default();
+        let mut state = ProjectManagerState {
+            tag_filter: app.project_tag_filter.clone(),
+            ..Default::default()
+        };
         
         // Initialize database
         let db_path = app.config_path.join("projects.db");
         if let Err(e) = state.initialize_database(&db_path) {
             logger.log_error(&format!("Failed to initialize project database: {}", e));
         }
+        state.reload_templates(&templates_dir(&app.config_path));
         
         app.project_manager_state = Some(state);
     }
@@ -41,14 +48,16 @@ pub fn show_project_manager_panel(
             logger.log_error(&error);
         }
         
-        ui.heading("📁 Project Manager");
+        ui.heading(tr("manager.heading"));
         ui.separator();
         
         // Top controls
         ui.horizontal(|ui| {
             // Search
-            ui.label("🔍 Search:");
-            let search_changed = ui.text_edit_singleline(&mut manager_state.search_query).changed();
+            ui.label(tr("manager.search"));
+            let search_changed = ui.text_edit_singleline(&mut manager_state.search_query)
+                .on_hover_text(tr("manager.search_hint"))
+                .changed();
             
             if search_changed {
                 if let Err(e) = manager_state.search_projects(&manager_state.search_query.clone()) {
@@ -59,10 +68,23 @@ pub fn show_project_manager_panel(
             ui.separator();
             
             // Create new project button
-            if ui.button("➕ New Project").clicked() {
+            if ui.button(tr("manager.new")).clicked() {
                 manager_state.show_create_dialog = true;
             }
             
+            // Import a project archive from another machine
+            if ui.button(tr("manager.import")).on_hover_text(tr("manager.import_hint")).clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("CopperForge project", &[ARCHIVE_EXTENSION])
+                    .pick_file()
+                {
+                    match manager_state.import_archive(&path) {
+                        Ok(project_id) => logger.log_info(&format!("Imported {} as project {}", path.display(), project_id)),
+                        Err(e) => manager_state.last_error = Some(format!("Failed to import project: {}", e)),
+                    }
+                }
+            }
+            
             ui.separator();
             
             // Current project info
@@ -71,10 +93,10 @@ pub fn show_project_manager_panel(
                 .map(|p| p.metadata.name.clone());
             
             if let Some(ref project_name) = current_project_name {
-                ui.label(format!("📋 Current: {}", project_name));
+                ui.label(tr_args("manager.current", &[("name", project_name)]));
                 
                 // Save BOM to current project
-                if ui.button("💾 Save BOM").clicked() {
+                if ui.button(tr("project.save_bom")).clicked() {
                     if let Some(ref components) = bom_components {
                         if let Err(e) = manager_state.update_project_bom(components.clone()) {
                             manager_state.last_error = Some(format!("Failed to save BOM: {}", e));
@@ -84,17 +106,19 @@ pub fn show_project_manager_panel(
                     }
                 }
             } else {
-                ui.label("📋 No project loaded");
+                ui.label(tr("manager.no_project"));
             }
         });
         
+        show_tag_cloud(ui, manager_state);
+        
         ui.separator();
         
         // Project list
         ui.vertical(|ui| {
             if manager_state.project_list.is_empty() {
                 ui.centered_and_justified(|ui| {
-                    ui.label("No projects found. Create your first project!");
+                    ui.label(tr("manager.empty"));
                 });
             } else {
                 // Clone project list and current project id to avoid borrowing issues
@@ -110,12 +134,12 @@ pub fn show_project_manager_panel(
                     .column(egui_extras::Column::exact(200.0))  // Name
                     .column(egui_extras::Column::remainder())   // Description
                     .column(egui_extras::Column::exact(120.0))  // Last Modified
-                    .column(egui_extras::Column::exact(120.0))  // Actions
+                    .column(egui_extras::Column::exact(150.0))  // Actions
                     .header(20.0, |mut header| {
-                        header.col(|ui| { ui.strong("Project Name"); });
-                        header.col(|ui| { ui.strong("Description"); });
-                        header.col(|ui| { ui.strong("Last Modified"); });
-                        header.col(|ui| { ui.strong("Actions"); });
+                        header.col(|ui| { ui.strong(tr("manager.col_name")); });
+                        header.col(|ui| { ui.strong(tr("manager.col_description")); });
+                        header.col(|ui| { ui.strong(tr("manager.col_modified")); });
+                        header.col(|ui| { ui.strong(tr("manager.col_actions")); });
                     })
                     .body(|mut body| {
                         for project in &project_list {
@@ -133,6 +157,11 @@ pub fn show_project_manager_panel(
                                         egui::RichText::new(&project.name)
                                     };
                                     
+                                    if crate::ui::batch_drc::show_drc_badge(ui, manager_state.drc_summaries.get(&project.id)).clicked() {
+                                        ui.ctx().memory_mut(|mem| {
+                                            mem.data.insert_temp(egui::Id::new("load_project"), project.id.clone());
+                                        });
+                                    }
                                     ui.label(text);
                                 });
                                 
@@ -151,12 +180,19 @@ pub fn show_project_manager_panel(
                                 row.col(|ui| {
                                     ui.horizontal(|ui| {
                                         // Load project button
-                                        if ui.small_button("📂 Load").clicked() {
+                                        if ui.small_button(tr("manager.load")).clicked() {
                                             ui.ctx().memory_mut(|mem| {
                                                 mem.data.insert_temp(egui::Id::new("load_project"), project.id.clone());
                                             });
                                         }
                                         
+                                        // Export project archive button
+                                        if ui.small_button("📦").on_hover_text(tr("manager.export_hint")).clicked() {
+                                            ui.ctx().memory_mut(|mem| {
+                                                mem.data.insert_temp(egui::Id::new("export_project"), project.id.clone());
+                                            });
+                                        }
+                                        
                                         // Delete project button
                                         if ui.small_button("🗑️").clicked() {
                                             ui.ctx().memory_mut(|mem| {
@@ -178,6 +214,9 @@ pub fn show_project_manager_panel(
         let delete_project_id = ui.ctx().memory(|mem| {
             mem.data.get_temp::<String>(egui::Id::new("delete_project"))
         });
+        let export_project_id = ui.ctx().memory(|mem| {
+            mem.data.get_temp::<String>(egui::Id::new("export_project"))
+        });
         
         if let Some(project_id) = load_project_id {
             ui.ctx().memory_mut(|mem| {
@@ -188,7 +227,7 @@ pub fn show_project_manager_panel(
                 .iter()
                 .find(|p| p.id == project_id)
                 .map(|p| p.name.clone())
-                .unwrap_or_else(|| "Unknown".to_string());
+                .unwrap_or_else(|| tr("manager.unknown_project").to_string());
                 
             if let Err(e) = manager_state.load_project(&project_id) {
                 manager_state.last_error = Some(format!("Failed to load project: {}", e));
@@ -197,6 +236,28 @@ pub fn show_project_manager_panel(
             }
         }
         
+        if let Some(project_id) = export_project_id {
+            ui.ctx().memory_mut(|mem| {
+                mem.data.remove::<String>(egui::Id::new("export_project"));
+            });
+            
+            let file_name = manager_state.project_list
+                .iter()
+                .find(|p| p.id == project_id)
+                .map(|p| format!("{}.{}", p.name, ARCHIVE_EXTENSION))
+                .unwrap_or_else(|| format!("project.{}", ARCHIVE_EXTENSION));
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("CopperForge project", &[ARCHIVE_EXTENSION])
+                .set_file_name(&file_name)
+                .save_file()
+            {
+                match manager_state.export_archive(&project_id, &path) {
+                    Ok(()) => logger.log_info(&format!("Exported project to {}", path.display())),
+                    Err(e) => manager_state.last_error = Some(format!("Failed to export project: {}", e)),
+                }
+            }
+        }
+        
         if let Some(project_id) = delete_project_id {
             ui.ctx().memory_mut(|mem| {
                 mem.data.remove::<String>(egui::Id::new("delete_project"));
@@ -227,7 +288,7 @@ pub fn show_create_project_dialog(
     bom_components: Vec<crate::project_manager::bom::BomComponent>,
     logger: &ReactiveEventLogger,
 ) {
-    egui::Window::new("Create New Project")
+    egui::Window::new(tr("manager.create_title"))
         .id(egui::Id::new("create_project_dialog"))
         .collapsible(false)
         .resizable(false)
@@ -236,36 +297,43 @@ pub fn show_create_project_dialog(
         .max_size(egui::Vec2::new(500.0, 400.0))
         .show(ctx, |ui| {
             ui.vertical(|ui| {
-                ui.label("Project Name:");
+                ui.label(tr("manager.name"));
                 ui.text_edit_singleline(&mut manager_state.new_project_name);
                 
                 ui.add_space(5.0);
                 
-                ui.label("Description:");
+                ui.label(tr("project.description"));
                 ui.text_edit_multiline(&mut manager_state.new_project_description);
                 
                 ui.add_space(5.0);
                 
-                ui.label("Tags (comma-separated):");
+                ui.label(tr("manager.tags"));
                 ui.text_edit_singleline(&mut manager_state.new_project_tags);
                 
+                ui.add_space(5.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("project.template"));
+                    show_template_selector(ui, manager_state);
+                });
+                
                 ui.add_space(10.0);
                 
                 // PCB file selection
                 ui.horizontal(|ui| {
-                    ui.label("PCB File:");
+                    ui.label(tr("manager.pcb_file"));
                     
                     let pcb_file_text = if let Some(ref path) = manager_state.new_project_pcb_path {
                         path.file_name()
                             .map(|n| n.to_string_lossy().to_string())
-                            .unwrap_or_else(|| "Unknown file".to_string())
+                            .unwrap_or_else(|| tr("manager.unknown_file").to_string())
                     } else {
-                        "No PCB file selected".to_string()
+                        tr("project.no_pcb").to_string()
                     };
                     
                     ui.label(&pcb_file_text);
                     
-                    if ui.button("Browse...").clicked() {
+                    if ui.button(tr("manager.browse")).clicked() {
                         manager_state.show_pcb_file_dialog = true;
                     }
                 });
@@ -274,7 +342,7 @@ pub fn show_create_project_dialog(
                 if manager_state.show_pcb_file_dialog {
                     if let Some(path) = rfd::FileDialog::new()
                         .add_filter("KiCad PCB", &["kicad_pcb"])
-                        .set_title("Select PCB File")
+                        .set_title(tr("manager.pcb_title"))
                         .pick_file() 
                     {
                         manager_state.new_project_pcb_path = Some(path);
@@ -286,7 +354,7 @@ pub fn show_create_project_dialog(
                 
                 // Buttons
                 ui.horizontal(|ui| {
-                    if ui.button("Create").clicked() {
+                    if ui.button(tr("manager.create")).clicked() {
                         // Validate input
                         if manager_state.new_project_name.trim().is_empty() {
                             manager_state.last_error = Some("Project name cannot be empty".to_string());
@@ -302,16 +370,21 @@ pub fn show_create_project_dialog(
                         };
                         
                         // Parse tags
-                        let tags: Vec<String> = manager_state.new_project_tags
+                        let mut tags: Vec<String> = manager_state.new_project_tags
                             .split(',')
                             .map(|s| s.trim().to_string())
                             .filter(|s| !s.is_empty())
                             .collect();
                         
+                        let mut description = manager_state.new_project_description.clone();
+                        if let Some(template) = manager_state.get_selected_template() {
+                            template.apply_to_new_project(&mut description, &mut tags);
+                        }
+                        
                         // Create project
                         match manager_state.create_project(
                             manager_state.new_project_name.clone(),
-                            manager_state.new_project_description.clone(),
+                            description,
                             pcb_path,
                             tags,
                             bom_components.clone(),
@@ -326,7 +399,7 @@ pub fn show_create_project_dialog(
                         }
                     }
                     
-                    if ui.button("Cancel").clicked() {
+                    if ui.button(tr("manager.cancel")).clicked() {
                         manager_state.reset_create_dialog();
                     }
                 });
@@ -334,6 +407,72 @@ pub fn show_create_project_dialog(
         });
 }
 
+/// Template dropdown for the create-project dialog ("Blank" clears the selection)
+pub fn show_template_selector(ui: &mut egui::Ui, manager_state: &mut ProjectManagerState) {
+    let selected_text = manager_state.selected_template.clone()
+        .unwrap_or_else(|| BLANK_TEMPLATE_NAME.to_string());
+    
+    egui::ComboBox::from_id_salt("project_template_selector")
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut manager_state.selected_template, None, BLANK_TEMPLATE_NAME);
+            for template in &manager_state.templates {
+                ui.selectable_value(&mut manager_state.selected_template, Some(template.name.clone()), &template.name);
+            }
+        });
+}
+
+/// Clickable tag cloud filtering the project list; more common tags are drawn larger
+pub fn show_tag_cloud(ui: &mut egui::Ui, manager_state: &mut ProjectManagerState) {
+    // Selected tags no longer on any project stay listed so they can be deselected
+    let mut tags = manager_state.tag_counts.clone();
+    for selected in &manager_state.tag_filter.tags {
+        if !tags.iter().any(|(tag, _)| tag.eq_ignore_ascii_case(selected)) {
+            tags.push((selected.clone(), 0));
+        }
+    }
+    if tags.is_empty() {
+        return;
+    }
+    
+    let mut toggled = None;
+    let mut match_all = manager_state.tag_filter.match_all;
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr("manager.tag_cloud"));
+        for (tag, count) in &tags {
+            let selected = manager_state.tag_filter.tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+            let size = 12.0 + (*count).min(6) as f32;
+            if ui.selectable_label(selected, egui::RichText::new(format!("{} ({})", tag, count)).size(size)).clicked() {
+                toggled = Some(tag.clone());
+            }
+        }
+    });
+    
+    if !manager_state.tag_filter.tags.is_empty() {
+        ui.horizontal(|ui| {
+            ui.label(tr("manager.match"));
+            ui.radio_value(&mut match_all, false, tr("manager.any_tag"));
+            ui.radio_value(&mut match_all, true, tr("manager.all_tags"));
+            if ui.small_button(tr("manager.clear_tags")).clicked() {
+                if let Err(e) = manager_state.clear_tag_filter() {
+                    manager_state.last_error = Some(format!("Failed to filter projects: {}", e));
+                }
+            }
+        });
+    }
+    
+    let result = if let Some(tag) = toggled {
+        manager_state.toggle_tag_filter(&tag)
+    } else if match_all != manager_state.tag_filter.match_all {
+        manager_state.set_tag_match_all(match_all)
+    } else {
+        Ok(())
+    };
+    if let Err(e) = result {
+        manager_state.last_error = Some(format!("Failed to filter projects: {}", e));
+    }
+}
+
 /// Show delete confirmation dialog
 fn show_delete_confirmation_dialog(
     ctx: &egui::Context,
@@ -345,22 +484,23 @@ fn show_delete_confirmation_dialog(
         .iter()
         .find(|p| p.id == project_id)
         .map(|p| p.name.clone())
-        .unwrap_or_else(|| "Unknown".to_string());
+        .unwrap_or_else(|| tr("manager.unknown_project").to_string());
     
-    egui::Window::new("Delete Project")
+    egui::Window::new(tr("manager.delete_title"))
+        .id(egui::Id::new("delete_project_dialog"))
         .collapsible(false)
         .resizable(false)
         .movable(true)
         .default_pos(egui::Pos2::new(400.0, 300.0))
         .show(ctx, |ui| {
             ui.vertical(|ui| {
-                ui.label(format!("Are you sure you want to delete project '{}'?", project_name));
-                ui.label("This action cannot be undone.");
+                ui.label(tr_args("manager.delete_confirm", &[("name", &project_name)]));
+                ui.label(tr("manager.delete_warning"));
                 
                 ui.add_space(15.0);
                 
                 ui.horizontal(|ui| {
-                    if ui.button("🗑️ Delete").clicked() {
+                    if ui.button(tr("manager.delete")).clicked() {
                         match manager_state.delete_project(project_id) {
                             Ok(()) => {
                                 logger.log_info(&format!("Deleted project: {}", project_name));
@@ -373,7 +513,7 @@ fn show_delete_confirmation_dialog(
                         }
                     }
                     
-                    if ui.button("Cancel").clicked() {
+                    if ui.button(tr("manager.cancel")).clicked() {
                         manager_state.show_delete_confirmation = None;
                     }
                 });
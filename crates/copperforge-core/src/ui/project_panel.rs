@@ -1,6 +1,7 @@
 use crate::DemoLensApp;
 use crate::project::ProjectState;
 use crate::project_manager::ProjectManagerState;
+use crate::ui::i18n::{tr, tr_args};
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::Dynamic;
 use std::path::{Path, PathBuf};
@@ -14,19 +15,19 @@ pub fn show_project_panel<'a>(
 ) {
     let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
 
-    ui.heading("Project");
+    ui.heading(tr("project.heading"));
     ui.separator();
 
     // Show current state
     ui.group(|ui| {
-        ui.label("Current State:");
+        ui.label(tr("project.current_state"));
         let state_text = match &app.project_manager.state {
-            ProjectState::NoProject => "No project loaded",
-            ProjectState::PcbSelected { .. } => "PCB file selected",
-            ProjectState::GeneratingGerbers { .. } => "Generating gerbers...",
-            ProjectState::GerbersGenerated { .. } => "Gerbers generated",
-            ProjectState::LoadingGerbers { .. } => "Loading gerbers...",
-            ProjectState::Ready { .. } => "Project ready",
+            ProjectState::NoProject => tr("project.state.no_project"),
+            ProjectState::PcbSelected { .. } => tr("project.state.pcb_selected"),
+            ProjectState::GeneratingGerbers { .. } => tr("project.state.generating"),
+            ProjectState::GerbersGenerated { .. } => tr("project.state.generated"),
+            ProjectState::LoadingGerbers { .. } => tr("project.state.loading"),
+            ProjectState::Ready { .. } => tr("project.state.ready"),
         };
         ui.monospace(state_text);
     });
@@ -38,7 +39,7 @@ pub fn show_project_panel<'a>(
         mem.data.get_temp::<bool>(egui::Id::new("show_project_database")).unwrap_or(true)
     );
     
-    if ui.button(if show_database { "▼ Project Database" } else { "▶ Project Database" }).clicked() {
+    if ui.button(format!("{} {}", if show_database { "▼" } else { "▶" }, tr("project.database"))).clicked() {
         ui.ctx().memory_mut(|mem| {
             mem.data.insert_temp(egui::Id::new("show_project_database"), !show_database);
         });
@@ -52,19 +53,19 @@ pub fn show_project_panel<'a>(
 
     // Auto-generation settings
     ui.horizontal(|ui| {
-        ui.checkbox(&mut app.project_manager.auto_generate_on_startup, "Auto-generate on startup");
+        ui.checkbox(&mut app.project_manager.auto_generate_on_startup, tr("project.auto_generate"));
     });
     ui.horizontal(|ui| {
-        ui.checkbox(&mut app.project_manager.auto_reload_on_change, "Auto-reload on file change");
+        ui.checkbox(&mut app.project_manager.auto_reload_on_change, tr("project.auto_reload"));
     });
 
     ui.add_space(10.0);
 
     ui.horizontal(|ui| {
-        ui.label("KiCad PCB File:");
+        ui.label(tr("project.pcb_file"));
         
         // Add clear button to reset state
-        if ui.small_button("Clear").clicked() {
+        if ui.small_button(tr("project.clear")).clicked() {
             app.project_manager.state = ProjectState::NoProject;
             // Also clear current project in database state
             if let Some(ref mut manager_state) = app.project_manager_state {
@@ -96,7 +97,7 @@ pub fn show_project_panel<'a>(
         let response = ui.add(
             egui::TextEdit::singleline(&mut path_str)
                 .desired_width(ui.available_width() - 80.0)
-                .hint_text("Enter path to .kicad_pcb file...")
+                .hint_text(tr("project.path_hint"))
         );
         
         // Update path if user edited the text
@@ -111,7 +112,7 @@ pub fn show_project_panel<'a>(
             }
         }
 
-        if ui.button("Browse...").clicked() {
+        if ui.button(tr("ribbon.browse")).clicked() {
             app.project_manager.open_file_dialog();
         }
     });
@@ -122,18 +123,32 @@ pub fn show_project_panel<'a>(
         logger.log_info(&format!("Selected PCB file: {}", path_buf.display()));
     }
 
+    if ui.button(tr("project.import_odb"))
+        .on_hover_text(tr("project.import_odb_hint"))
+        .clicked()
+    {
+        if let Some(job_dir) = rfd::FileDialog::new()
+            .set_title(tr("project.import_odb_title"))
+            .pick_folder()
+        {
+            crate::ui::status_bar::defer_logged(app, tr("status.loading_gerbers"), move |app, logger| {
+                import_odb_into_viewer(app, &job_dir, logger)
+            });
+        }
+    }
+
     ui.add_space(10.0);
 
     // Show appropriate controls based on current state
     match &app.project_manager.state.clone() {
         ProjectState::NoProject => {
-            ui.label("No PCB file selected");
+            ui.label(tr("project.no_pcb"));
         },
         ProjectState::PcbSelected { pcb_path } => {
             show_pcb_info(ui, pcb_path);
             ui.add_space(10.0);
             
-            if ui.button("Generate Gerbers").clicked() {
+            if ui.button(tr("project.generate")).clicked() {
                 app.project_manager.state = ProjectState::GeneratingGerbers { pcb_path: pcb_path.clone() };
                 logger.log_info("Generating gerbers from PCB file...");
             }
@@ -142,29 +157,35 @@ pub fn show_project_panel<'a>(
             show_pcb_info(ui, pcb_path);
             ui.add_space(10.0);
             
-            ui.add_enabled(false, egui::Button::new("Generating..."));
+            ui.add_enabled(false, egui::Button::new(tr("project.generating")));
             
-            // Handle generation
-            if matches!(app.project_manager.state, ProjectState::GeneratingGerbers { .. }) {
-                if let Some(output_dir) = generate_gerbers_from_pcb(pcb_path, &logger) {
-                    app.project_manager.state = ProjectState::GerbersGenerated {
-                        pcb_path: pcb_path.clone(),
-                        gerber_dir: output_dir.clone(),
-                    };
-                } else {
-                    // Generation failed, go back to selected state
-                    app.project_manager.state = ProjectState::PcbSelected { pcb_path: pcb_path.clone() };
-                }
+            // Handle generation once the status bar shows it
+            if !crate::ui::status_bar::is_busy(app) {
+                let pcb_path = pcb_path.clone();
+                crate::ui::status_bar::defer_logged(app, tr("status.generating_gerbers"), move |app, logger| {
+                    if let Some(output_dir) = generate_gerbers_from_pcb(&pcb_path, logger) {
+                        crate::ui::post_gen_hook::run_after_generation(app, &pcb_path, &output_dir, logger);
+                        app.project_manager.state = ProjectState::GerbersGenerated {
+                            pcb_path,
+                            gerber_dir: output_dir,
+                        };
+                        Ok(())
+                    } else {
+                        // Generation failed, go back to selected state
+                        app.project_manager.state = ProjectState::PcbSelected { pcb_path };
+                        Err("kicad-cli could not generate the gerbers".to_string())
+                    }
+                });
             }
         },
         ProjectState::GerbersGenerated { pcb_path, gerber_dir } => {
             show_pcb_info(ui, pcb_path);
             ui.add_space(10.0);
             
-            ui.label(format!("Gerbers in: {}", gerber_dir.display()));
+            ui.label(tr_args("project.gerbers_in", &[("dir", &gerber_dir.display())]));
             ui.add_space(5.0);
             
-            if ui.button("Load Gerbers into Viewer").clicked() {
+            if ui.button(tr("project.load_gerbers")).clicked() {
                 app.project_manager.state = ProjectState::LoadingGerbers {
                     pcb_path: pcb_path.clone(),
                     gerber_dir: gerber_dir.clone(),
@@ -172,7 +193,7 @@ pub fn show_project_panel<'a>(
                 logger.log_info("Loading gerbers into viewer...");
             }
             
-            if ui.button("Regenerate Gerbers").clicked() {
+            if ui.button(tr("project.regenerate")).clicked() {
                 app.project_manager.state = ProjectState::GeneratingGerbers { pcb_path: pcb_path.clone() };
                             }
         },
@@ -180,66 +201,278 @@ pub fn show_project_panel<'a>(
             show_pcb_info(ui, pcb_path);
             ui.add_space(10.0);
             
-            ui.add_enabled(false, egui::Button::new("Loading..."));
+            ui.add_enabled(false, egui::Button::new(tr("project.loading")));
             
-            // Handle loading
-            if matches!(app.project_manager.state, ProjectState::LoadingGerbers { .. }) {
-                load_gerbers_into_viewer(app, gerber_dir, &logger);
-                let last_modified = std::fs::metadata(pcb_path)
-                    .and_then(|m| m.modified())
-                    .unwrap_or(std::time::SystemTime::now());
-                    
-                app.project_manager.state = ProjectState::Ready {
-                    pcb_path: pcb_path.clone(),
-                    gerber_dir: gerber_dir.clone(),
-                    last_modified,
-                };
+            // Handle loading once the status bar shows it
+            if !crate::ui::status_bar::is_busy(app) {
+                let (pcb_path, gerber_dir) = (pcb_path.clone(), gerber_dir.clone());
+                crate::ui::status_bar::defer_logged(app, tr("status.loading_gerbers"), move |app, logger| {
+                    let loaded = load_gerbers_into_viewer(app, &gerber_dir, logger);
+                    crate::ui::workspace_sync::apply_workspace_file(app, &pcb_path, logger);
+                    let last_modified = std::fs::metadata(&pcb_path)
+                        .and_then(|m| m.modified())
+                        .unwrap_or(std::time::SystemTime::now());
+                        
+                    app.project_manager.state = ProjectState::Ready {
+                        pcb_path,
+                        gerber_dir,
+                        last_modified,
+                    };
+                    loaded
+                });
             }
         },
         ProjectState::Ready { pcb_path, gerber_dir, last_modified } => {
             show_pcb_info(ui, pcb_path);
             ui.add_space(10.0);
             
-            ui.label("✓ Gerbers loaded and displayed");
+            ui.label(tr("project.loaded"));
             
             // Check if file has been modified
             if let Ok(metadata) = std::fs::metadata(pcb_path) {
                 if let Ok(modified) = metadata.modified() {
                     if &modified != last_modified {
-                        ui.colored_label(egui::Color32::YELLOW, "⚠ PCB file has been modified");
+                        ui.colored_label(egui::Color32::YELLOW, tr("project.modified"));
                     }
                 }
             }
             
             ui.add_space(5.0);
             
-            if ui.button("Reload Gerbers").clicked() {
+            if ui.button(tr("project.reload")).clicked() {
                 app.project_manager.state = ProjectState::LoadingGerbers {
                     pcb_path: pcb_path.clone(),
                     gerber_dir: gerber_dir.clone(),
                 };
                             }
             
-            if ui.button("Regenerate Gerbers").clicked() {
+            if ui.button(tr("project.regenerate")).clicked() {
                 app.project_manager.state = ProjectState::GeneratingGerbers { pcb_path: pcb_path.clone() };
                             }
+            
+            crate::ui::workspace_sync::show_workspace_controls(ui, app, pcb_path, &logger);
+            
+            ui.add_space(5.0);
+            
+            if ui.button(tr("project.export_transformed"))
+                .on_hover_text(tr("project.export_transformed_hint"))
+                .clicked()
+            {
+                if let Some(output_dir) = rfd::FileDialog::new()
+                    .set_title(tr("project.export_dir_title"))
+                    .pick_folder()
+                {
+                    let pcb_path = pcb_path.clone();
+                    crate::ui::status_bar::defer_logged(app, tr("status.exporting"), move |app, logger| {
+                        export_transformed_gerbers(app, &pcb_path, &output_dir, logger)
+                    });
+                }
+            }
+            
+            if app.ecs_world.resource::<crate::ecs::PanelSettings>().enabled
+                && ui.button(tr("project.export_panel"))
+                    .on_hover_text(tr("project.export_panel_hint"))
+                    .clicked()
+            {
+                if let Some(output_dir) = rfd::FileDialog::new()
+                    .set_title(tr("project.export_dir_title"))
+                    .pick_folder()
+                {
+                    let pcb_path = pcb_path.clone();
+                    crate::ui::status_bar::defer_logged(app, tr("status.exporting"), move |app, logger| {
+                        crate::ui::panel::export_panel_gerbers(app, &pcb_path, &output_dir, logger)
+                    });
+                }
+            }
+            
+            if ui.button(tr("project.export_odb"))
+                .on_hover_text(tr("project.export_odb_hint"))
+                .clicked()
+            {
+                if let Some(output_dir) = rfd::FileDialog::new()
+                    .set_title(tr("project.export_dir_title"))
+                    .pick_folder()
+                {
+                    let pcb_path = pcb_path.clone();
+                    crate::ui::status_bar::defer_logged(app, tr("status.exporting"), move |app, logger| {
+                        export_odb(app, &pcb_path, &output_dir, logger)
+                    });
+                }
+            }
+            
+            if ui.button(tr("project.export_ipc2581"))
+                .on_hover_text(tr("project.export_ipc2581_hint"))
+                .clicked()
+            {
+                let file_name = pcb_path.file_stem()
+                    .map(|stem| format!("{}.xml", stem.to_string_lossy()))
+                    .unwrap_or_else(|| "board.xml".to_string());
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title(tr("project.export_ipc2581_title"))
+                    .add_filter("IPC-2581", &["xml", "cvg"])
+                    .set_file_name(file_name)
+                    .save_file()
+                {
+                    crate::ui::status_bar::defer_logged(app, tr("status.exporting"), move |app, logger| {
+                        export_ipc2581(app, &path, logger)
+                    });
+                }
+            }
+            
+            if ui.button(tr("project.export_fab"))
+                .on_hover_text(tr("project.export_fab_hint"))
+                .clicked()
+            {
+                let file_name = pcb_path.file_stem()
+                    .map(|stem| format!("{}-fab.svg", stem.to_string_lossy()))
+                    .unwrap_or_else(|| "board-fab.svg".to_string());
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title(tr("project.export_fab_title"))
+                    .add_filter("SVG", &["svg"])
+                    .set_file_name(file_name)
+                    .save_file()
+                {
+                    crate::ui::status_bar::defer_logged(app, tr("status.exporting"), move |app, logger| {
+                        export_fab_drawing(app, &path, logger)
+                    });
+                }
+            }
+            
+            ui.add_space(10.0);
+            crate::ui::provenance::show_provenance_section(ui, app);
+            ui.add_space(10.0);
+            crate::ui::board_size::show_board_size_section(ui, app);
         },
     }
 }
 
+/// Write every visible layer with the current display transform applied
+fn export_transformed_gerbers(app: &mut DemoLensApp, pcb_path: &Path, output_dir: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Export, "export gerbers", logger)?;
+    
+    let mut layers = Vec::new();
+    for layer_type in crate::ecs::LayerType::all() {
+        if !crate::ecs::get_layer_visibility(&mut app.ecs_world, layer_type) {
+            continue;
+        }
+        let primitives = crate::ecs::get_layer_primitives(&mut app.ecs_world, layer_type);
+        if primitives.is_empty() {
+            continue;
+        }
+        if let Some(transform) = crate::ecs::get_layer_export_transform(&mut app.ecs_world, &app.display_manager, layer_type) {
+            layers.push((layer_type, primitives, transform));
+        }
+    }
+    
+    if layers.is_empty() {
+        let error = "No visible layers with exportable geometry".to_string();
+        logger.log_warning(&error);
+        return Err(error);
+    }
+    
+    let base_name = pcb_path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "board".to_string());
+    
+    match crate::export::gerber::export_transformed_layers(&layers, output_dir, &base_name) {
+        Ok((written, skipped)) => {
+            logger.log_info(&format!("Exported {} transformed gerber(s) to {}", written.len(), output_dir.display()));
+            if skipped > 0 {
+                logger.log_warning(&format!("{} flash(es) of undefined apertures could not be exported", skipped));
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let error = format!("Gerber export failed: {}", e);
+            logger.log_error(&error);
+            Err(error)
+        }
+    }
+}
+
+/// Write the loaded board as an ODB++ job named after the PCB file
+fn export_odb(app: &mut DemoLensApp, pcb_path: &Path, output_dir: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Export, "export ODB++", logger)?;
+    
+    let job_name = pcb_path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| "board".to_string());
+    
+    match crate::export::odb::export(&mut app.ecs_world, output_dir, &job_name) {
+        Ok(result) => {
+            logger.log_info(&format!("Exported ODB++ job with {} layer(s) to {}", result.layers, result.job_dir.display()));
+            if result.skipped_flashes > 0 {
+                logger.log_warning(&format!("{} macro aperture flash(es) could not be exported", result.skipped_flashes));
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let error = format!("ODB++ export failed: {}", e);
+            logger.log_error(&error);
+            Err(error)
+        }
+    }
+}
+
+/// Write the loaded design as a single IPC-2581 file
+fn export_ipc2581(app: &mut DemoLensApp, path: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Export, "export IPC-2581", logger)?;
+    
+    match crate::export::ipc2581::export(&mut app.ecs_world, path) {
+        Ok(result) => {
+            logger.log_info(&format!("Exported IPC-2581 with {} layer(s) and {} hole(s) to {}", result.layers, result.holes, path.display()));
+            if result.skipped_flashes > 0 {
+                logger.log_warning(&format!("{} macro aperture flash(es) could not be exported", result.skipped_flashes));
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let error = format!("IPC-2581 export failed: {}", e);
+            logger.log_error(&error);
+            Err(error)
+        }
+    }
+}
+
+/// Write the fab drawing of the loaded board, titled with the open project
+fn export_fab_drawing(app: &mut DemoLensApp, path: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
+    crate::ui::layer_set::check_before(app, crate::ecs::LayerSetOperation::Export, "export a fab drawing", logger)?;
+    
+    let metadata = app.project_manager_state.as_ref()
+        .and_then(|state| state.current_project.as_ref())
+        .map(|project| project.metadata.clone());
+    match crate::export::fab_drawing::export(&mut app.ecs_world, metadata.as_ref(), path) {
+        Ok(result) => {
+            logger.log_info(&format!(
+                "Exported fab drawing of the {:.2} × {:.2} mm board with {} hole(s) to {}",
+                result.width_mm, result.height_mm, result.holes, path.display()
+            ));
+            if result.inferred_outline {
+                logger.log_warning("No mechanical outline loaded - the drawing uses the bounding box of the layers");
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let error = format!("Fab drawing export failed: {}", e);
+            logger.log_error(&error);
+            Err(error)
+        }
+    }
+}
+
 fn show_pcb_info(ui: &mut egui::Ui, pcb_path: &Path) {
     ui.group(|ui| {
         ui.horizontal(|ui| {
-            ui.label("File:");
+            ui.label(tr("project.file"));
             ui.monospace(pcb_path.file_name()
                 .unwrap_or_default()
                 .to_string_lossy());
         });
         
         if pcb_path.exists() {
-            ui.label("✓ File exists");
+            ui.label(tr("project.file_exists"));
         } else {
-            ui.colored_label(egui::Color32::RED, "✗ File not found");
+            ui.colored_label(egui::Color32::RED, tr("project.file_missing"));
         }
     });
 }
@@ -317,6 +550,27 @@ fn generate_gerbers_from_pcb(pcb_path: &Path, logger: &ReactiveEventLogger) -> O
                 logger.log_info("Gerbers generated successfully!");
                 logger.log_info(&format!("Check output directory: {}", output_dir.display()));
                 
+                // Drill files feed the drill DRC; the gerbers are still usable without them
+                let mut drill_cmd = Command::new(&kicad_cli_path);
+                for (key, value) in cmd.get_envs() {
+                    if let Some(value) = value {
+                        drill_cmd.env(key, value);
+                    }
+                }
+                let drill_output = drill_cmd
+                    .arg("pcb")
+                    .arg("export")
+                    .arg("drill")
+                    .arg("--output")
+                    .arg(format!("{}/", output_dir.display()))
+                    .arg("--excellon-separate-th")
+                    .arg(pcb_path)
+                    .output();
+                match drill_output {
+                    Ok(result) if result.status.success() => logger.log_info("Drill files generated"),
+                    _ => logger.log_warning("Failed to generate drill files; drill DRC will be skipped"),
+                }
+                
                 // Log the generated files
                 if let Ok(entries) = std::fs::read_dir(&output_dir) {
                     for entry in entries.flatten() {
@@ -343,7 +597,7 @@ fn generate_gerbers_from_pcb(pcb_path: &Path, logger: &ReactiveEventLogger) -> O
     None
 }
 
-fn load_gerbers_into_viewer(app: &mut DemoLensApp, gerber_dir: &Path, logger: &ReactiveEventLogger) {
+pub(crate) fn load_gerbers_into_viewer(app: &mut DemoLensApp, gerber_dir: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
     // Clear all existing layers and unassigned gerbers first
     logger.log_info("Clearing existing gerber layers...");
     crate::ecs::clear_all_layers_system(&mut app.ecs_world);
@@ -358,6 +612,21 @@ fn load_gerbers_into_viewer(app: &mut DemoLensApp, gerber_dir: &Path, logger: &R
             if unassigned_count > 0 {
                 logger.log_warning(&format!("{} gerber files could not be automatically assigned", unassigned_count));
             }
+            if let Some(drill_data) = app.ecs_world.get_resource::<crate::ecs::DrillData>() {
+                if !drill_data.holes.is_empty() {
+                    logger.log_info(&format!(
+                        "Loaded {} drill holes from {}",
+                        drill_data.holes.len(),
+                        drill_data.filenames.join(", ")
+                    ));
+                }
+            }
+            
+            log_parse_cache_report(app, logger);
+            crate::ui::parse_problems::log_parse_diagnostics(app, logger);
+            crate::ui::layer_controls::run_registration_check(&mut app.ecs_world, logger);
+            crate::ui::layer_set::run_layer_set_check(app, logger);
+            crate::ui::provenance::log_provenance_warnings(app, logger);
             
             // Set loading status for UI
             if loaded_count > 0 {
@@ -365,33 +634,203 @@ fn load_gerbers_into_viewer(app: &mut DemoLensApp, gerber_dir: &Path, logger: &R
             } else if unassigned_count > 0 {
                 logger.log_warning(&format!("No layers were automatically detected. {} gerber files need manual assignment.", unassigned_count));
             } else {
-                logger.log_error("No gerber files were found");
+                let error = "No gerber files were found".to_string();
+                logger.log_error(&error);
+                return Err(error);
             }
+            Ok(())
         }
         Err(e) => {
-            logger.log_error(&format!("Failed to load gerbers: {}", e));
+            let error = format!("Failed to load gerbers: {}", e);
+            logger.log_error(&error);
+            Err(error)
+        }
+    }
+}
+
+/// Replace the loaded layers with an ODB++ job, logging what was skipped per layer
+fn import_odb_into_viewer(app: &mut DemoLensApp, job_dir: &Path, logger: &ReactiveEventLogger) -> Result<(), String> {
+    let result = match crate::import::odb::import(&mut app.ecs_world, job_dir) {
+        Ok(result) => result,
+        Err(e) => {
+            let error = format!("ODB++ import failed: {}", e);
+            logger.log_error(&error);
+            return Err(error);
+        }
+    };
+    for layer in &result.layers {
+        match (layer.layer_type, &layer.note) {
+            (Some(layer_type), _) => logger.log_info(&format!("{}: {} features as {}", layer.name, layer.primitives, layer_type.display_name())),
+            (None, None) => logger.log_info(&format!("{}: {} drill holes", layer.name, layer.drill_holes)),
+            (None, Some(note)) => logger.log_warning(&format!("{}: {}", layer.name, note)),
+        }
+        if !layer.unsupported.is_empty() {
+            let skipped: Vec<String> = layer.unsupported.iter().map(|(kind, count)| format!("{} × {}", count, kind)).collect();
+            logger.log_warning(&format!("{}: skipped unsupported records: {}", layer.name, skipped.join(", ")));
+        }
+    }
+
+    crate::ui::layer_controls::run_registration_check(&mut app.ecs_world, logger);
+    crate::ui::layer_set::run_layer_set_check(app, logger);
+    if result.loaded_layers() > 0 {
+        logger.log_info(&format!("Imported {} layers from ODB++ step {} in {}", result.loaded_layers(), result.step, job_dir.display()));
+        app.needs_initial_view = true;
+        Ok(())
+    } else {
+        let error = "The ODB++ job has no layers that could be imported".to_string();
+        logger.log_error(&error);
+        Err(error)
+    }
+}
+
+/// Log per layer whether the parse cache was used and how much time it saved
+fn log_parse_cache_report(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(report) = app.ecs_world.remove_resource::<crate::ecs::ParseCacheReport>() else {
+        return;
+    };
+    let mut total_saved = std::time::Duration::ZERO;
+    for (filename, outcome) in &report.layers {
+        match outcome {
+            crate::ecs::ParseCacheOutcome::Hit { saved } => {
+                total_saved += *saved;
+                logger.log_info(&format!("{}: loaded from parse cache, saved {} ms", filename, saved.as_millis()));
+            }
+            crate::ecs::ParseCacheOutcome::Miss { extract_time } => {
+                logger.log_info(&format!("{}: parsed in {} ms and cached", filename, extract_time.as_millis()));
+            }
+            crate::ecs::ParseCacheOutcome::WriteFailed(e) => logger.log_warning(&format!("{}: {}", filename, e)),
+        }
+    }
+    if !total_saved.is_zero() {
+        logger.log_info(&format!("Parse cache saved {} ms in total", total_saved.as_millis()));
+    }
+}
+
+/// How the current project's notes are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotesView {
+    Edit,
+    #[default]
+    Preview,
+    /// Editor and rendered preview side by side
+    Split,
+}
+
+/// Notes are written to the database this long after the last keystroke
+const NOTES_AUTOSAVE_DELAY_SECS: f64 = 3.0;
+
+fn save_notes(manager_state: &mut ProjectManagerState) {
+    if let Err(e) = manager_state.save_current_project() {
+        manager_state.last_error = Some(format!("Failed to save notes: {}", e));
+    }
+}
+
+/// Markdown notes of the current project; stored as raw text, rendered in preview
+/// Edits are saved a few seconds after the last keystroke, when the editor loses focus,
+/// and before another project is loaded (see `ProjectManagerState::load_project`)
+fn show_project_notes(ui: &mut egui::Ui, manager_state: &mut ProjectManagerState, view: &mut NotesView) {
+    let last_edit_id = egui::Id::new("project_notes_last_edit");
+    let now = ui.input(|i| i.time);
+    let Some(project) = manager_state.current_project.as_mut() else {
+        return;
+    };
+    
+    ui.separator();
+    let mut append_review = false;
+    ui.horizontal(|ui| {
+        ui.label(tr("notes.heading"));
+        append_review = ui.small_button(tr("notes.review"))
+            .on_hover_text(tr("notes.review_hint"))
+            .clicked();
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.selectable_value(view, NotesView::Split, tr("notes.split"));
+            ui.selectable_value(view, NotesView::Preview, tr("notes.preview"));
+            ui.selectable_value(view, NotesView::Edit, tr("notes.edit"));
+        });
+    });
+    let mut edited = false;
+    if append_review {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+        crate::ui::markdown::append_review_note(&mut project.notes, &timestamp);
+        if *view == NotesView::Preview {
+            *view = NotesView::Split;
         }
+        edited = true;
+    }
+    
+    let editor = |ui: &mut egui::Ui, notes: &mut String| {
+        ui.add(egui::TextEdit::multiline(notes)
+            .code_editor()
+            .desired_rows(10)
+            .desired_width(f32::INFINITY)
+            .hint_text(tr("notes.hint")))
+    };
+    let preview = |ui: &mut egui::Ui, notes: &str| {
+        egui::ScrollArea::vertical()
+            .id_salt("project_notes_preview")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                if notes.trim().is_empty() {
+                    crate::ui::markdown::show_empty_hint(ui, tr("notes.empty"));
+                } else {
+                    crate::ui::markdown::show_markdown(ui, notes);
+                }
+            });
+    };
+    
+    let response = match view {
+        NotesView::Edit => Some(editor(ui, &mut project.notes)),
+        NotesView::Preview => {
+            preview(ui, &project.notes);
+            None
+        }
+        NotesView::Split => ui.columns(2, |columns| {
+            let response = editor(&mut columns[0], &mut project.notes);
+            preview(&mut columns[1], &project.notes);
+            Some(response)
+        }),
+    };
+    
+    edited |= response.as_ref().is_some_and(|response| response.changed());
+    if edited {
+        manager_state.mark_dirty();
+        ui.data_mut(|data| data.insert_temp(last_edit_id, now));
+    }
+    // Write once editing pauses or is done rather than on every keystroke
+    let last_edit = ui.data(|data| data.get_temp::<f64>(last_edit_id));
+    if response.is_some_and(|response| response.lost_focus()) || last_edit.is_some_and(|at| now - at >= NOTES_AUTOSAVE_DELAY_SECS) {
+        ui.data_mut(|data| data.remove::<f64>(last_edit_id));
+        save_notes(manager_state);
+    } else if let Some(at) = last_edit {
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(NOTES_AUTOSAVE_DELAY_SECS - (now - at)));
     }
 }
 
 fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
     ui.group(|ui| {
-        ui.label("💾 Project Database");
+        ui.label(format!("💾 {}", tr("project.database")));
         ui.separator();
         
         // Initialize project manager state if not already done
         if app.project_manager_state.is_none() {
-            let mut state = ProjectManagerState::default();
+            let mut state = ProjectManagerState {
+                tag_filter: app.project_tag_filter.clone(),
+                ..Default::default()
+            };
             
             // Initialize database
             let db_path = app.config_path.join("projects.db");
             if let Err(e) = state.initialize_database(&db_path) {
                 logger.log_error(&format!("Failed to initialize project database: {}", e));
             }
+            state.reload_templates(&crate::project_manager::templates::templates_dir(&app.config_path));
             
             app.project_manager_state = Some(state);
         }
         
+        // Template to apply to the live app state once the manager borrow ends
+        let mut template_to_apply = None;
+        
         if let Some(ref mut manager_state) = app.project_manager_state {
             // Handle any errors
             if let Some(error) = manager_state.last_error.take() {
@@ -407,7 +846,7 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                 
                 if let Some(ref project_name) = current_project_name {
                     ui.vertical(|ui| {
-                        ui.label(format!("Current: {}", project_name));
+                        ui.label(tr_args("project.current", &[("name", project_name)]));
                         
                         // Enterprise feature: Show current project dates
                         if let Some(ref current_project) = manager_state.current_project {
@@ -428,7 +867,7 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                     });
                     
                     // Save BOM button
-                    if ui.button("💾 Save BOM").clicked() {
+                    if ui.button(tr("project.save_bom")).clicked() {
                         if let Some(ref bom_state) = app.bom_state {
                             let components = bom_state.components.lock().unwrap().clone();
                             if let Err(e) = manager_state.update_project_bom(components) {
@@ -439,11 +878,11 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                         }
                     }
                 } else {
-                    ui.label("No project loaded");
+                    ui.label(tr("project.state.no_project"));
                 }
                 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button("➕ New").clicked() {
+                    if ui.button(tr("project.new")).clicked() {
                         // Toggle create mode instead of showing modal
                         manager_state.show_create_dialog = !manager_state.show_create_dialog;
                     }
@@ -452,9 +891,11 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
             
             ui.add_space(5.0);
             
+            crate::ui::project_manager_panel::show_tag_cloud(ui, manager_state);
+            
             // Project list (scrollable)
             if !manager_state.project_list.is_empty() {
-                ui.label("Projects:");
+                ui.label(tr("project.list"));
                 
                 // Get current project ID without cloning entire list
                 let current_project_id = manager_state.current_project
@@ -481,6 +922,14 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                             egui::RichText::new(&project.name)
                         };
                         
+                        // Last batch DRC result; clicking it opens the project
+                        if crate::ui::batch_drc::show_drc_badge(ui, manager_state.drc_summaries.get(&project.id)).clicked() {
+                            ui.ctx().memory_mut(|mem| {
+                                mem.data.insert_temp(egui::Id::new("load_project_id"), project.id.clone());
+                                mem.data.insert_temp(egui::Id::new("load_project_name"), project.name.clone());
+                            });
+                        }
+                        
                         ui.vertical(|ui| {
                             ui.label(text);
                             
@@ -490,13 +939,13 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                                 
                                 // Created date
                                 let created_date = project.created_at.format("%Y-%m-%d %H:%M UTC").to_string();
-                                ui.label(egui::RichText::new(format!("📅 Created: {}", created_date))
+                                ui.label(egui::RichText::new(tr_args("project.created", &[("date", &created_date)]))
                                     .small()
                                     .color(egui::Color32::GRAY));
                                 
                                 // Last modified date
                                 let modified_date = project.last_modified.format("%Y-%m-%d %H:%M UTC").to_string();
-                                ui.label(egui::RichText::new(format!("🔄 Modified: {}", modified_date))
+                                ui.label(egui::RichText::new(tr_args("project.modified_at", &[("date", &modified_date)]))
                                     .small()
                                     .color(egui::Color32::GRAY));
                             });
@@ -504,7 +953,7 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                         
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             // Delete button
-                            if ui.small_button("🗑️").on_hover_text("Delete this project").clicked() {
+                            if ui.small_button("🗑️").on_hover_text(tr("project.delete_hint")).clicked() {
                                 ui.ctx().memory_mut(|mem| {
                                     mem.data.insert_temp(egui::Id::new("delete_project_id"), project.id.clone());
                                     mem.data.insert_temp(egui::Id::new("delete_project_name"), project.name.clone());
@@ -512,14 +961,14 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                             }
                             
                             // Edit button
-                            if ui.small_button("✏️").on_hover_text("Edit project details").clicked() {
+                            if ui.small_button("✏️").on_hover_text(tr("project.edit_hint")).clicked() {
                                 ui.ctx().memory_mut(|mem| {
                                     mem.data.insert_temp(egui::Id::new("edit_project_id"), project.id.clone());
                                 });
                             }
                             
                             // Load button
-                            if ui.small_button("📂").on_hover_text("Load this project").clicked() {
+                            if ui.small_button("📂").on_hover_text(tr("project.load_hint")).clicked() {
                                 ui.ctx().memory_mut(|mem| {
                                     mem.data.insert_temp(egui::Id::new("load_project_id"), project.id.clone());
                                     mem.data.insert_temp(egui::Id::new("load_project_name"), project.name.clone());
@@ -607,32 +1056,39 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                 }
             }
             
+            show_project_notes(ui, manager_state, &mut app.notes_view);
+            
             // Inline create project section
             if manager_state.show_create_dialog {
                 ui.separator();
                 ui.group(|ui| {
-                    ui.label("🆕 Create New Project");
+                    ui.label(tr("project.create_heading"));
                     ui.separator();
                     
                     ui.horizontal(|ui| {
-                        ui.label("Name:");
+                        ui.label(tr("project.name"));
                         ui.text_edit_singleline(&mut manager_state.new_project_name);
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Description:");
+                        ui.label(tr("project.description"));
                         ui.text_edit_singleline(&mut manager_state.new_project_description);
                     });
                     
                     ui.horizontal(|ui| {
-                        ui.label("Tags:");
+                        ui.label(tr("project.tags"));
                         ui.text_edit_singleline(&mut manager_state.new_project_tags);
                     });
                     
+                    ui.horizontal(|ui| {
+                        ui.label(tr("project.template"));
+                        crate::ui::project_manager_panel::show_template_selector(ui, manager_state);
+                    });
+                    
                     ui.add_space(5.0);
                     
                     ui.horizontal(|ui| {
-                        if ui.button("✅ Create").clicked() {
+                        if ui.button(tr("project.create")).clicked() {
                             if !manager_state.new_project_name.trim().is_empty() {
                                 // Get PCB file path
                                 if let Some(pcb_path) = match &app.project_manager.state {
@@ -646,12 +1102,18 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                                     _ => None,
                                 } {
                                     // Parse tags
-                                    let tags: Vec<String> = manager_state.new_project_tags
+                                    let mut tags: Vec<String> = manager_state.new_project_tags
                                         .split(',')
                                         .map(|s| s.trim().to_string())
                                         .filter(|s| !s.is_empty())
                                         .collect();
                                     
+                                    let mut description = manager_state.new_project_description.clone();
+                                    let template = manager_state.get_selected_template().cloned();
+                                    if let Some(ref template) = template {
+                                        template.apply_to_new_project(&mut description, &mut tags);
+                                    }
+                                    
                                     // Get BOM components
                                     let bom_components = if let Some(ref bom_state) = app.bom_state {
                                         bom_state.components.lock().unwrap().clone()
@@ -662,7 +1124,7 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                                     // Create project
                                     match manager_state.create_project(
                                         manager_state.new_project_name.clone(),
-                                        manager_state.new_project_description.clone(),
+                                        description,
                                         pcb_path,
                                         tags,
                                         bom_components,
@@ -670,6 +1132,7 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                                         Ok(project_id) => {
                                             logger.log_info(&format!("Created project: {} (ID: {})", manager_state.new_project_name, project_id));
                                             manager_state.reset_create_dialog();
+                                            template_to_apply = template;
                                         }
                                         Err(e) => {
                                             manager_state.last_error = Some(format!("Failed to create project: {}", e));
@@ -683,12 +1146,20 @@ fn show_project_database_section(ui: &mut egui::Ui, app: &mut DemoLensApp, logge
                             }
                         }
                         
-                        if ui.button("❌ Cancel").clicked() {
+                        if ui.button(tr("project.cancel")).clicked() {
                             manager_state.reset_create_dialog();
                         }
                     });
                 });
             }
         }
+        
+        if let Some(template) = template_to_apply {
+            app.apply_project_template(&template);
+            logger.log_info(&format!("Applied template '{}' (DRC rules, grid, layer colors)", template.name));
+        }
+        
+        ui.add_space(5.0);
+        crate::ui::batch_drc::show_batch_drc_controls(ui, app, logger);
     });
 }
\ No newline at end of file
@@ -0,0 +1,63 @@
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ecs::LayerProvenance;
+
+const WARNING_COLOR: Color32 = Color32::from_rgb(230, 126, 34);
+
+/// Log the provenance mismatches after a load
+pub fn log_provenance_warnings(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    for warning in crate::ecs::get_provenance_summary(&mut app.ecs_world).warnings {
+        logger.log_warning(&format!("⚠️  Provenance: {}", warning));
+    }
+}
+
+/// Plot time in local time when the file's timestamp parses, else as written
+fn format_created(provenance: &LayerProvenance) -> String {
+    match provenance.created_at() {
+        Some(created) => created.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => provenance.creation_date.clone().unwrap_or_else(|| "—".to_string()),
+    }
+}
+
+/// Generating software, version and plot time per loaded layer
+/// Layers from another tool version or plotted apart from the rest are highlighted
+pub fn show_provenance_section(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let summary = crate::ecs::get_provenance_summary(&mut app.ecs_world);
+    if summary.layers.is_empty() {
+        return;
+    }
+
+    ui.group(|ui| {
+        ui.label("🏷 Provenance");
+        ui.separator();
+
+        for warning in &summary.warnings {
+            ui.label(RichText::new(format!("⚠ {}", warning)).color(WARNING_COLOR));
+        }
+
+        egui::Grid::new("provenance_grid").num_columns(4).striped(true).spacing([12.0, 2.0]).show(ui, |ui| {
+            ui.strong("Layer");
+            ui.strong("Software");
+            ui.strong("Version");
+            ui.strong("Created");
+            ui.end_row();
+            for entry in &summary.layers {
+                let text = |text: String| if entry.mismatch { RichText::new(text).color(WARNING_COLOR) } else { RichText::new(text) };
+                let provenance = &entry.provenance;
+                let layer = ui.label(text(if entry.mismatch { format!("⚠ {}", entry.layer) } else { entry.layer.clone() }));
+                if let Some(file) = &entry.file {
+                    layer.on_hover_text(file);
+                }
+                let software = ui.label(text(provenance.software.clone().unwrap_or_else(|| "unknown".to_string())));
+                if let Some(comment) = &provenance.comment {
+                    software.on_hover_text(comment);
+                }
+                ui.label(text(provenance.version.clone().unwrap_or_else(|| "—".to_string())));
+                ui.label(text(format_created(provenance)));
+                ui.end_row();
+            }
+        });
+    });
+}
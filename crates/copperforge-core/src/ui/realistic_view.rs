@@ -0,0 +1,46 @@
+use egui::{Color32, RichText};
+
+use crate::DemoLensApp;
+use crate::display::{MaskColor, SurfaceFinish};
+
+/// "Realistic" toggle with surface finish and soldermask colour
+pub fn show_realistic_settings(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let settings = &mut app.display_manager.realistic;
+    ui.checkbox(&mut settings.enabled, "Realistic")
+        .on_hover_text("Show the shown side as fabricated: soldermask over copper, finished pads and white silkscreen");
+    ui.add_enabled_ui(settings.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Finish:");
+            egui::ComboBox::from_id_salt("realistic_finish")
+                .selected_text(settings.finish.label())
+                .show_ui(ui, |ui| {
+                    for finish in SurfaceFinish::ALL {
+                        ui.selectable_value(&mut settings.finish, finish, finish.label());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Mask:");
+            egui::ComboBox::from_id_salt("realistic_mask_color")
+                .selected_text(settings.mask_color.label())
+                .show_ui(ui, |ui| {
+                    for mask_color in MaskColor::ALL {
+                        ui.selectable_value(&mut settings.mask_color, mask_color, mask_color.label());
+                    }
+                });
+        });
+    });
+
+    if settings.enabled {
+        let reason = if app.display_manager.quadrant_view_enabled {
+            Some("quadrant view")
+        } else if app.revision_compare.enabled {
+            Some("revision compare")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            ui.label(RichText::new(format!("Layers are drawn flat while {} is on", reason)).small().color(Color32::GRAY));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::project::ProjectState;
+use crate::project_manager::recent::RecentProjects;
+use crate::ui::i18n::{tr, tr_args};
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(231, 76, 60);
+const PIN_COLOR: Color32 = Color32::from_rgb(241, 196, 15);
+
+/// Recent PCB files, the PCB last recorded and a picked entry whose file is gone
+#[derive(Default)]
+pub struct RecentProjectsState {
+    pub list: RecentProjects,
+    recorded: Option<PathBuf>,
+    missing: Option<PathBuf>,
+}
+
+impl RecentProjectsState {
+    pub fn load(config_dir: &Path) -> Self {
+        Self { list: RecentProjects::load(config_dir), ..Self::default() }
+    }
+}
+
+fn save(app: &DemoLensApp) {
+    if let Err(e) = app.recent_projects.list.save(&app.config_path) {
+        eprintln!("{}", e);
+    }
+}
+
+/// Add the selected PCB to the recent list when it changes, however it was chosen
+/// (Browse, the path field, a database project or this menu). Called every frame from the app
+pub fn track_recent_project(app: &mut DemoLensApp) {
+    let Some(pcb_path) = app.project_manager.get_pcb_path().cloned() else {
+        return;
+    };
+    if app.recent_projects.recorded.as_ref() == Some(&pcb_path) || !pcb_path.exists() {
+        return;
+    }
+    let name = app.project_manager_state.as_ref()
+        .and_then(|state| state.current_project.as_ref())
+        .filter(|project| project.metadata.pcb_file_path == pcb_path)
+        .map(|project| project.metadata.name.clone())
+        .or_else(|| pcb_path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    app.recent_projects.list.record(&pcb_path, &name, chrono::Utc::now());
+    app.recent_projects.recorded = Some(pcb_path);
+    save(app);
+}
+
+/// Select a recent PCB the way Browse does, so generation and loading follow the same states
+pub(crate) fn open_recent(app: &mut DemoLensApp, pcb_path: &Path, logger: &ReactiveEventLogger) {
+    if !pcb_path.exists() {
+        app.recent_projects.missing = Some(pcb_path.to_path_buf());
+        return;
+    }
+    app.recent_projects.missing = None;
+    app.project_manager.state = ProjectState::PcbSelected { pcb_path: pcb_path.to_path_buf() };
+    logger.log_info(&format!("Selected PCB file: {}", pcb_path.display()));
+}
+
+/// Dropdown of recent PCB files next to Browse, plus the error for an entry whose file is gone
+pub fn show_recent_menu(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let mut open = None;
+    let mut toggle_pin = None;
+    let mut clear = false;
+
+    let enabled = !app.recent_projects.list.entries.is_empty();
+    ui.add_enabled_ui(enabled, |ui| {
+        ui.menu_button(tr("recent.menu"), |ui| {
+            for entry in &app.recent_projects.list.entries {
+                ui.horizontal(|ui| {
+                    let pin = if entry.pinned { RichText::new("📌").color(PIN_COLOR) } else { RichText::new("📌").color(Color32::GRAY) };
+                    if ui.small_button(pin).on_hover_text(if entry.pinned { tr("recent.unpin") } else { tr("recent.pin") }).clicked() {
+                        toggle_pin = Some(entry.pcb_path.clone());
+                    }
+                    let opened = entry.last_opened.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+                    if ui.button(&entry.name)
+                        .on_hover_text(tr_args("recent.tooltip", &[("path", &entry.pcb_path.display()), ("time", &opened)]))
+                        .clicked()
+                    {
+                        open = Some(entry.pcb_path.clone());
+                        ui.close();
+                    }
+                });
+            }
+            ui.separator();
+            if ui.button(tr("recent.clear")).on_hover_text(tr("recent.clear_hint")).clicked() {
+                clear = true;
+                ui.close();
+            }
+        });
+    });
+
+    if let Some(path) = toggle_pin {
+        app.recent_projects.list.toggle_pin(&path);
+        save(app);
+    }
+    if clear {
+        app.recent_projects.list.clear_unpinned();
+        save(app);
+        logger.log_info("Cleared recent PCB files");
+    }
+    if let Some(path) = open {
+        open_recent(app, &path, logger);
+    }
+
+    if let Some(missing) = app.recent_projects.missing.clone() {
+        ui.label(RichText::new(tr_args("recent.missing", &[("path", &missing.display())])).color(ERROR_COLOR));
+        if ui.small_button(tr("recent.remove")).clicked() {
+            app.recent_projects.list.remove(&missing);
+            app.recent_projects.missing = None;
+            save(app);
+            logger.log_info(&format!("Removed {} from recent PCB files", missing.display()));
+        }
+        if ui.small_button("✖").on_hover_text(tr("recent.dismiss")).clicked() {
+            app.recent_projects.missing = None;
+        }
+    }
+}
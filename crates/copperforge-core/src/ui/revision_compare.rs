@@ -0,0 +1,256 @@
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use egui::{Color32, Painter};
+use egui_lens::ReactiveEventLogger;
+use gerber_viewer::{GerberLayer, GerberRenderer, RenderConfiguration};
+
+use crate::DemoLensApp;
+use crate::ui::i18n::{tr, tr_args};
+use crate::ecs::{LayerDetector, LayerType, TaggedPrimitive};
+use crate::export::diff::{
+    render_layer_diff_with_stats, LayerDiffStats, DIFF_ADDED_COLOR, DIFF_PIXELS_PER_MM, DIFF_REMOVED_COLOR,
+    DIFF_UNCHANGED_COLOR,
+};
+
+/// Tint used for revision B in the overlay compare
+const REVISION_B_COLOR: Color32 = Color32::from_rgb(255, 0, 255);
+
+/// Largest side of the diff preview in the window
+const DIFF_PREVIEW_MAX_SIZE: f32 = 600.0;
+
+/// One layer of the "B" revision, loaded outside the ECS world
+pub struct RevisionLayer {
+    pub layer_type: LayerType,
+    pub filename: String,
+    pub gerber: GerberLayer,
+    pub primitives: Vec<TaggedPrimitive>,
+}
+
+/// State for comparing the loaded board ("A") against another revision ("B")
+pub struct RevisionCompareState {
+    pub revision_dir: Option<PathBuf>,
+    pub layers: Vec<RevisionLayer>,
+    pub enabled: bool,
+    /// Draw revision B above the current layers instead of underneath
+    pub b_on_top: bool,
+    pub opacity: f32,
+    pub show_diff_window: bool,
+    pub diff_layer: LayerType,
+    diff_result: Option<(LayerType, egui::TextureHandle, LayerDiffStats)>,
+}
+
+impl Default for RevisionCompareState {
+    fn default() -> Self {
+        Self {
+            revision_dir: None,
+            layers: Vec::new(),
+            enabled: false,
+            b_on_top: true,
+            opacity: 0.5,
+            show_diff_window: false,
+            diff_layer: LayerType::Copper(1),
+            diff_result: None,
+        }
+    }
+}
+
+impl RevisionCompareState {
+    pub fn get_layer(&self, layer_type: LayerType) -> Option<&RevisionLayer> {
+        self.layers.iter().find(|layer| layer.layer_type == layer_type)
+    }
+
+    fn overlay_color(&self) -> Color32 {
+        REVISION_B_COLOR.gamma_multiply(self.opacity)
+    }
+}
+
+/// Load every detectable `*.gbr` in `dir` as revision B
+/// The first file detected for a layer type wins; undetected files are skipped
+pub fn load_revision_layers(dir: &Path) -> Result<Vec<RevisionLayer>, String> {
+    let detector = LayerDetector::new();
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("gbr"))
+        .collect();
+    paths.sort();
+
+    let mut layers: Vec<RevisionLayer> = Vec::new();
+    for path in paths {
+        let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
+        let Some(layer_type) = detector.detect_layer_type(&filename) else {
+            continue;
+        };
+        if layers.iter().any(|layer| layer.layer_type == layer_type) {
+            continue;
+        }
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(doc) = gerber_viewer::gerber_parser::parse(BufReader::new(content.as_bytes())) else {
+            continue;
+        };
+
+        layers.push(RevisionLayer {
+            layer_type,
+            filename,
+            gerber: GerberLayer::new(doc.into_commands()),
+            primitives: crate::ecs::extract_primitives(&content),
+        });
+    }
+
+    if layers.is_empty() {
+        return Err(format!("No recognised gerber layers in {}", dir.display()));
+    }
+    Ok(layers)
+}
+
+/// Toolbar controls for the revision compare mode
+pub fn render_revision_compare_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    if ui.button(tr("revision.compare")).on_hover_text(tr("revision.compare_hint")).clicked() {
+        if let Some(dir) = rfd::FileDialog::new().set_title(tr("revision.dir_title")).pick_folder() {
+            let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+            match load_revision_layers(&dir) {
+                Ok(layers) => {
+                    logger.log_info(&format!("Loaded {} revision B layer(s) from {}", layers.len(), dir.display()));
+                    let compare = &mut app.revision_compare;
+                    compare.layers = layers;
+                    compare.revision_dir = Some(dir);
+                    compare.enabled = true;
+                    compare.diff_result = None;
+                }
+                Err(e) => logger.log_error(&format!("Failed to load revision B: {}", e)),
+            }
+        }
+    }
+
+    let compare = &mut app.revision_compare;
+    if compare.layers.is_empty() {
+        return;
+    }
+
+    ui.checkbox(&mut compare.enabled, tr("revision.show_b"));
+    ui.add_enabled_ui(compare.enabled, |ui| {
+        ui.checkbox(&mut compare.b_on_top, tr("revision.b_on_top"))
+            .on_hover_text(tr("revision.b_on_top_hint"));
+        ui.add(egui::Slider::new(&mut compare.opacity, 0.1..=1.0).text(tr("revision.opacity")).fixed_decimals(1));
+    });
+    ui.toggle_value(&mut compare.show_diff_window, tr("revision.raster_diff"));
+}
+
+/// Draw the revision B layers with the transform of the matching current layer
+/// Layers hidden in the current board are not drawn for B either
+pub fn render_revision_overlay(app: &mut DemoLensApp, painter: &Painter) {
+    let compare = &app.revision_compare;
+    if !compare.enabled || compare.layers.is_empty() {
+        return;
+    }
+
+    let renderer = GerberRenderer::default();
+    let config = RenderConfiguration::default();
+    let color = compare.overlay_color();
+
+    for layer in &compare.layers {
+        if !crate::ecs::get_layer_visibility(&mut app.ecs_world, layer.layer_type) {
+            continue;
+        }
+        let Some(transform) = crate::ecs::get_layer_render_transform(&mut app.ecs_world, &app.display_manager, layer.layer_type) else {
+            continue;
+        };
+        renderer.paint_layer(painter, app.view_state, &layer.gerber, color, &config, &transform);
+    }
+}
+
+/// Window showing the rasterized added/removed copper of one layer
+pub fn show_raster_diff_window(ctx: &egui::Context, app: &mut DemoLensApp) {
+    if !app.revision_compare.show_diff_window {
+        return;
+    }
+
+    let mut open = true;
+    egui::Window::new(tr("revision.title"))
+        .id(egui::Id::new("revision_diff_window"))
+        .open(&mut open)
+        .default_size([480.0, 420.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("revision.layer"));
+                let compare = &mut app.revision_compare;
+                egui::ComboBox::from_id_salt("revision_diff_layer")
+                    .selected_text(compare.diff_layer.display_name())
+                    .show_ui(ui, |ui| {
+                        for layer in &compare.layers {
+                            ui.selectable_value(&mut compare.diff_layer, layer.layer_type, layer.layer_type.display_name());
+                        }
+                    });
+
+                if ui.button(tr("revision.compute")).clicked() {
+                    compute_diff(ui.ctx(), app);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.colored_label(to_color32(DIFF_ADDED_COLOR), tr("revision.added"));
+                ui.colored_label(to_color32(DIFF_REMOVED_COLOR), tr("revision.removed"));
+                ui.colored_label(to_color32(DIFF_UNCHANGED_COLOR), tr("revision.unchanged"));
+            });
+            ui.separator();
+
+            let Some((layer_type, texture, stats)) = &app.revision_compare.diff_result else {
+                ui.label(tr("revision.select_layer"));
+                return;
+            };
+
+            ui.label(layer_type.display_name());
+            if stats.is_identical() {
+                ui.label(tr("revision.no_changes"));
+            } else {
+                ui.label(tr_args("revision.areas", &[
+                    ("added", &format!("{:.3}", stats.added_area_mm2())),
+                    ("removed", &format!("{:.3}", stats.removed_area_mm2())),
+                ]));
+            }
+
+            let size = texture.size_vec2();
+            let scale = (DIFF_PREVIEW_MAX_SIZE / size.x.max(size.y)).min(1.0);
+            egui::ScrollArea::both().show(ui, |ui| {
+                ui.image((texture.id(), size * scale));
+            });
+        });
+
+    app.revision_compare.show_diff_window = open;
+}
+
+fn compute_diff(ctx: &egui::Context, app: &mut DemoLensApp) {
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    let layer_type = app.revision_compare.diff_layer;
+    let Some(revision_layer) = app.revision_compare.get_layer(layer_type) else {
+        logger.log_warning(&format!("Revision B has no {} layer", layer_type.display_name()));
+        return;
+    };
+
+    let current = crate::ecs::get_layer_primitives(&mut app.ecs_world, layer_type);
+    let (image, stats) = render_layer_diff_with_stats(&current, &revision_layer.primitives, DIFF_PIXELS_PER_MM);
+
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(
+        [image.width() as usize, image.height() as usize],
+        image.as_raw(),
+    );
+    let texture = ctx.load_texture("revision_diff", color_image, egui::TextureOptions::NEAREST);
+
+    logger.log_info(&format!(
+        "{} diff: +{:.3} mm² / -{:.3} mm²",
+        layer_type.display_name(),
+        stats.added_area_mm2(),
+        stats.removed_area_mm2()
+    ));
+    app.revision_compare.diff_result = Some((layer_type, texture, stats));
+}
+
+fn to_color32(color: image::Rgba<u8>) -> Color32 {
+    Color32::from_rgb(color[0], color[1], color[2])
+}
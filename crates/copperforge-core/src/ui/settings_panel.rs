@@ -1,9 +1,9 @@
 use crate::DemoLensApp;
-use crate::ecs::UnitsResource;
 use egui_lens::{ReactiveEventLogger, ReactiveEventLoggerState, LogColors};
 use egui_mobius_reactive::Dynamic;
 use chrono_tz::Tz;
 use chrono::Local;
+use crate::ui::i18n::{self, tr, tr_args};
 
 pub fn show_settings_panel<'a>(
     ui: &mut egui::Ui,
@@ -13,108 +13,70 @@ pub fn show_settings_panel<'a>(
 ) {
     let logger = ReactiveEventLogger::with_colors(logger_state, log_colors);
 
-    ui.heading("Application Settings");
+    ui.heading(tr("settings.heading"));
     ui.separator();
     
+    // Language Section
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(tr("settings.language"));
+            let previous = app.language;
+            egui::ComboBox::from_id_salt("ui_language")
+                .selected_text(app.language.native_name())
+                .show_ui(ui, |ui| {
+                    for language in i18n::Language::ALL {
+                        ui.selectable_value(&mut app.language, language, language.native_name());
+                    }
+                });
+            if app.language != previous {
+                i18n::set_language(app.language);
+                logger.log_info(&format!("Interface language set to {}", app.language.native_name()));
+            }
+        });
+        ui.label(egui::RichText::new(tr("settings.language_hint")).small().color(egui::Color32::GRAY));
+    });
+    
+    ui.add_space(20.0);
+    
     // Units Section
     ui.group(|ui| {
-        ui.label("Display Units");
+        ui.label(tr("settings.units"));
         ui.horizontal(|ui| {
-            ui.label("Global Units:");
-            
-            // Get current units from ECS
-            let _current_unit = if let Some(units_resource) = app.ecs_world.get_resource::<UnitsResource>() {
-                units_resource.display_unit
-            } else {
-                crate::ecs::DisplayUnit::Millimeters
-            };
+            ui.label(tr("settings.global_units"));
             
-            // Track if units changed
-            let mut units_changed = false;
-            let prev_units = app.global_units_mils;
+            // Pick up a change made through the U shortcut
+            app.sync_units_from_ecs();
+            let prev_unit = app.display_unit;
             
-            // Update legacy global_units_mils based on selection
-            if ui.selectable_value(&mut app.global_units_mils, false, "Millimeters (mm)").clicked() {
-                units_changed = true;
+            for unit in crate::ecs::DisplayUnit::CHOICES {
+                ui.selectable_value(&mut app.display_unit, unit, unit.name());
             }
-            if ui.selectable_value(&mut app.global_units_mils, true, "Mils (1/1000 inch)").clicked() {
-                units_changed = true;
-            }
-            
-            // Additional units options (disabled for now)
-            ui.add_enabled(false, egui::Button::new("Micrometers (µm)"));
-            ui.add_enabled(false, egui::Button::new("Nanometers (nm)"));
             
-            if units_changed || prev_units != app.global_units_mils {
+            if prev_unit != app.display_unit {
                 // Sync to ECS
                 app.sync_units_to_ecs();
-                
-                let units_name = if app.global_units_mils { "mils" } else { "mm" };
-                logger.log_info(&format!("Changed global units to {}", units_name));
+                logger.log_info(&format!("Changed global units to {}", app.display_unit.suffix()));
             }
         });
-        ui.label("Affects: Grid spacing, board dimensions, cursor position, zoom selection");
-        ui.label("Internal precision: 1 nanometer (integer-based like KiCad)");
+        ui.label(tr("settings.units_affect"));
+        ui.label(tr("settings.precision"));
     });
     
     ui.add_space(20.0);
     
     // Timezone Section
     ui.group(|ui| {
-        ui.label("Time & Localization");
-        ui.horizontal(|ui| {
-            ui.label("Timezone:");
-            
-            // Get current timezone name or use UTC as default
-            let current_tz_name = app.user_timezone.as_ref()
-                .map(|s| s.as_str())
-                .unwrap_or("UTC");
-            
-            egui::ComboBox::from_id_salt("timezone_selector")
-                .selected_text(current_tz_name)
-                .width(300.0)
-                .show_ui(ui, |ui| {
-                    // Common timezones first
-                    ui.label("Common Timezones:");
-                    for tz_name in &[
-                        "UTC",
-                        "US/Eastern", 
-                        "US/Central",
-                        "US/Mountain", 
-                        "US/Pacific",
-                        "Europe/London",
-                        "Europe/Paris",
-                        "Europe/Berlin",
-                        "Asia/Tokyo",
-                        "Asia/Shanghai",
-                        "Australia/Sydney",
-                    ] {
-                        if ui.selectable_value(&mut app.user_timezone, Some(tz_name.to_string()), *tz_name).clicked() {
-                            logger.log_info(&format!("Changed timezone to {}", tz_name));
-                        }
-                    }
-                    
-                    ui.separator();
-                    ui.label("All Timezones:");
-                    
-                    // All timezones
-                    for tz in chrono_tz::TZ_VARIANTS {
-                        let tz_name = tz.name();
-                        if ui.selectable_value(&mut app.user_timezone, Some(tz_name.to_string()), tz_name).clicked() {
-                            logger.log_info(&format!("Changed timezone to {}", tz_name));
-                        }
-                    }
-                });
-        });
+        ui.label(tr("settings.time"));
+        show_timezone_picker(ui, app, &logger);
         
         ui.add_space(10.0);
         
         // Clock format selection
         ui.horizontal(|ui| {
-            ui.label("Clock Format:");
+            ui.label(tr("settings.clock_format"));
             let prev_format = app.use_24_hour_clock;
-            ui.selectable_value(&mut app.use_24_hour_clock, true, "24-hour (13:30:45)");
-            ui.selectable_value(&mut app.use_24_hour_clock, false, "12-hour (1:30:45 PM)");
+            ui.selectable_value(&mut app.use_24_hour_clock, true, tr("settings.clock_24"));
+            ui.selectable_value(&mut app.use_24_hour_clock, false, tr("settings.clock_12"));
             
             if prev_format != app.use_24_hour_clock {
                 let format_name = if app.use_24_hour_clock { "24-hour" } else { "12-hour" };
@@ -125,35 +87,343 @@ pub fn show_settings_panel<'a>(
         // Show current time in selected timezone with chosen format
         let time_format = if app.use_24_hour_clock { "%Y-%m-%d %H:%M:%S %Z" } else { "%Y-%m-%d %I:%M:%S %p %Z" };
         
-        if let Some(tz_name) = &app.user_timezone {
-            if let Ok(tz) = tz_name.parse::<Tz>() {
+        match app.user_timezone.as_ref().and_then(|name| name.parse::<Tz>().ok()) {
+            Some(tz) => {
                 let now = Local::now().with_timezone(&tz);
-                ui.label(format!("Current time: {}", now.format(time_format)));
+                ui.label(tr_args("settings.current_time", &[("time", &now.format(time_format))]));
+            }
+            None => {
+                let now = Local::now();
+                ui.label(tr_args("settings.current_time", &[("time", &now.format(if app.use_24_hour_clock { "%Y-%m-%d %H:%M:%S" } else { "%Y-%m-%d %I:%M:%S %p" }))]));
+            }
+        }
+        // Keep the preview ticking while the settings are open
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs(1));
+    });
+    
+    ui.add_space(20.0);
+    
+    // Accessibility Section
+    ui.group(|ui| {
+        ui.label(tr("settings.accessibility"));
+        if ui.checkbox(&mut app.drc_marker_settings.high_contrast, tr("settings.high_contrast"))
+            .on_hover_text(tr("settings.high_contrast_hint"))
+            .changed()
+        {
+            let state = if app.drc_marker_settings.high_contrast { "enabled" } else { "disabled" };
+            logger.log_info(&format!("High-contrast DRC markers {}", state));
+        }
+        if ui.checkbox(&mut app.drc_marker_settings.show_labels, tr("settings.marker_labels"))
+            .on_hover_text(tr("settings.marker_labels_hint"))
+            .changed()
+        {
+            let state = if app.drc_marker_settings.show_labels { "shown" } else { "hidden" };
+            logger.log_info(&format!("DRC marker labels {}", state));
+        }
+    });
+    
+    ui.add_space(20.0);
+    
+    // Input Section
+    ui.group(|ui| {
+        ui.label(tr("settings.input"));
+        if ui.checkbox(&mut app.touch_gestures_enabled, tr("settings.gestures"))
+            .on_hover_text(tr("settings.gestures_hint"))
+            .changed()
+        {
+            let state = if app.touch_gestures_enabled { "enabled" } else { "disabled" };
+            logger.log_info(&format!("Touch gestures {}", state));
+        }
+        if ui.checkbox(&mut app.view_animations_enabled, tr("settings.animations"))
+            .on_hover_text(tr("settings.animations_hint"))
+            .changed()
+        {
+            if !app.view_animations_enabled {
+                app.view_animation.finish();
+            }
+            let state = if app.view_animations_enabled { "enabled" } else { "disabled" };
+            logger.log_info(&format!("View animations {}", state));
+        }
+    });
+    
+    ui.add_space(20.0);
+    
+    // Parse Cache Section
+    ui.group(|ui| {
+        ui.label(tr("settings.parse_cache"));
+        if ui.checkbox(&mut app.use_parse_cache, tr("settings.use_parse_cache"))
+            .on_hover_text(tr("settings.use_parse_cache_hint"))
+            .changed()
+        {
+            app.apply_parse_cache_setting();
+            let state = if app.use_parse_cache { "enabled" } else { "disabled" };
+            logger.log_info(&format!("Parse cache {}", state));
+        }
+        let cache = app.parse_cache();
+        ui.horizontal(|ui| {
+            ui.label(tr_args("settings.cache_size", &[("size", &format!("{:.1}", cache.size_on_disk() as f64 / (1024.0 * 1024.0)))]));
+            if ui.button(tr("settings.clear_cache")).clicked() {
+                match cache.clear() {
+                    Ok(removed) => logger.log_info(&format!("Cleared parse cache ({} file(s))", removed)),
+                    Err(e) => logger.log_error(&e),
+                }
+            }
+        });
+    });
+    
+    ui.add_space(20.0);
+    
+    // Rendering Section
+    ui.group(|ui| {
+        ui.label(tr("settings.rendering"));
+        let mut cache = app.ecs_world.resource_mut::<crate::ecs::LayerRenderCache>();
+        if ui.checkbox(&mut cache.enabled, tr("settings.render_cache"))
+            .on_hover_text(tr("settings.render_cache_hint"))
+            .changed()
+        {
+            cache.clear();
+            let state = if cache.enabled { "enabled" } else { "disabled" };
+            logger.log_info(&format!("Layer render cache {}", state));
+        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut cache.lod.enabled, tr("settings.lod"))
+                .on_hover_text(tr("settings.lod_hint"));
+            let threshold = ui.add_enabled(
+                cache.lod.enabled,
+                egui::DragValue::new(&mut cache.lod.threshold_px).range(0.5..=20.0).speed(0.1).suffix(" px"),
+            );
+            if threshold.changed() {
+                cache.clear();
             }
-        } else {
-            let now = Local::now();
-            ui.label(format!("Current time: {}", now.format(if app.use_24_hour_clock { "%Y-%m-%d %H:%M:%S" } else { "%Y-%m-%d %I:%M:%S %p" })));
+        });
+        let stats = cache.stats;
+        ui.label(egui::RichText::new(tr_args("settings.render_stats", &[
+            ("ms", &format!("{:.2}", stats.render_ms)),
+            ("hits", &stats.cache_hits),
+            ("layers", &stats.layers_drawn),
+            ("simplified", &stats.lod_layers),
+        ])).color(egui::Color32::GRAY));
+        ui.checkbox(&mut cache.show_overlay, tr("settings.render_overlay"))
+            .on_hover_text(tr("settings.render_overlay_hint"));
+    });
+    
+    ui.add_space(20.0);
+    
+    // Gerber File Watching Section
+    ui.group(|ui| {
+        ui.label(tr("settings.file_watching"));
+        if ui.checkbox(&mut app.watch_gerber_files, tr("settings.watch_files"))
+            .on_hover_text(tr("settings.watch_files_hint"))
+            .changed()
+        {
+            let state = if app.watch_gerber_files { "enabled" } else { "disabled" };
+            logger.log_info(&format!("Gerber file watching {}", state));
+        }
+    });
+    
+    ui.add_space(20.0);
+    
+    // Auto-save Section
+    ui.group(|ui| {
+        ui.label(tr("settings.autosave"));
+        ui.horizontal(|ui| {
+            ui.label(tr("settings.autosave_every"));
+            ui.add(egui::DragValue::new(&mut app.autosave_interval_secs)
+                .range(0..=3600)
+                .speed(5)
+                .suffix(" s"))
+                .on_hover_text(tr("settings.autosave_hint"));
+        });
+        if app.autosave_interval_secs == 0 {
+            ui.label(egui::RichText::new(tr("settings.autosave_off")).color(egui::Color32::GRAY));
         }
     });
     
     ui.add_space(20.0);
     
-    // Language Section (placeholder for future)
+    // Keyboard Shortcuts Section
+    crate::ui::key_bindings::show_key_bindings_section(ui, app, &logger);
+    
+    ui.add_space(20.0);
+    
+    // Post-Generation Hook Section
+    crate::ui::post_gen_hook::show_post_gen_hook_section(ui, app, &logger);
+    
+    ui.add_space(20.0);
+    
+    // Settings Transfer Section
+    ui.group(|ui| {
+        ui.label(tr("settings.file"));
+        ui.horizontal(|ui| {
+            if ui.button(tr("settings.export")).clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Settings", &["json"])
+                    .set_file_name("copperforge_settings.json")
+                    .save_file()
+                {
+                    match app.export_settings(&path) {
+                        Ok(()) => logger.log_info(&format!("Exported settings to {}", path.display())),
+                        Err(e) => logger.log_error(&format!("Settings export failed: {}", e)),
+                    }
+                }
+            }
+            if ui.button(tr("settings.import")).clicked() {
+                if let Some(path) = rfd::FileDialog::new().add_filter("Settings", &["json"]).pick_file() {
+                    match app.import_settings(&path) {
+                        Ok(report) => {
+                            if report.applied.is_empty() {
+                                logger.log_warning(&format!("No settings applied from {}", path.display()));
+                            } else {
+                                logger.log_info(&format!("Imported settings from {}: {}", path.display(), report.applied.join(", ")));
+                            }
+                            for (section, reason) in &report.skipped {
+                                logger.log_warning(&format!("Skipped {} settings: {}", section, reason));
+                            }
+                            ui.ctx().request_repaint();
+                        }
+                        Err(e) => logger.log_error(&format!("Settings import failed: {}", e)),
+                    }
+                }
+            }
+        });
+        ui.label(tr("settings.file_hint"));
+    });
+    
+    ui.add_space(20.0);
+    
+    // Project Templates Section
     ui.group(|ui| {
-        ui.label("Language");
+        ui.label(tr("settings.templates"));
         ui.horizontal(|ui| {
-            ui.label("Interface Language:");
+            let name_id = egui::Id::new("template_name_input");
+            let mut template_name = ui.data_mut(|data| data.get_temp::<String>(name_id).unwrap_or_default());
             
-            egui::ComboBox::from_id_salt("language_selector")
-                .selected_text("English")
-                .show_ui(ui, |ui| {
-                    let _ = ui.selectable_label(true, "English");
-                    ui.add_enabled(false, egui::Button::new("Français (coming soon)"));
-                    ui.add_enabled(false, egui::Button::new("Deutsch (coming soon)"));
-                    ui.add_enabled(false, egui::Button::new("中文 (coming soon)"));
-                    ui.add_enabled(false, egui::Button::new("日本語 (coming soon)"));
-                });
+            ui.label(tr("settings.template_name"));
+            ui.text_edit_singleline(&mut template_name);
+            
+            let can_save = !template_name.trim().is_empty();
+            if ui.add_enabled(can_save, egui::Button::new(tr("settings.save_template"))).clicked() {
+                let template = app.capture_project_template(template_name.trim());
+                let dir = crate::project_manager::templates::templates_dir(&app.config_path);
+                match template.save_to_dir(&dir) {
+                    Ok(path) => {
+                        logger.log_info(&format!("Saved project template '{}' to {}", template.name, path.display()));
+                        if let Some(ref mut manager_state) = app.project_manager_state {
+                            manager_state.reload_templates(&dir);
+                        }
+                        template_name.clear();
+                    }
+                    Err(e) => logger.log_error(&format!("Failed to save project template: {}", e)),
+                }
+            }
+            
+            ui.data_mut(|data| data.insert_temp(name_id, template_name));
         });
+        ui.label(tr("settings.template_hint"));
     });
     
-}
\ No newline at end of file
+}
+/// Zones offered above the full list
+const COMMON_TIMEZONES: [&str; 11] = [
+    "UTC",
+    "US/Eastern",
+    "US/Central",
+    "US/Mountain",
+    "US/Pacific",
+    "Europe/London",
+    "Europe/Paris",
+    "Europe/Berlin",
+    "Asia/Tokyo",
+    "Asia/Shanghai",
+    "Australia/Sydney",
+];
+
+/// IANA name of the system timezone, when the OS reports one chrono_tz knows
+pub fn detect_local_timezone() -> Option<String> {
+    iana_time_zone::get_timezone().ok().filter(|name| name.parse::<Tz>().is_ok())
+}
+
+/// Zones whose IANA name contains the search text, ignoring case; every zone for an empty search
+fn matching_timezones(search: &str) -> Vec<&'static str> {
+    let query = search.trim().to_lowercase();
+    chrono_tz::TZ_VARIANTS.iter()
+        .map(|tz| tz.name())
+        .filter(|name| query.is_empty() || name.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Searchable timezone combo box; "System" (None) follows the detected local zone
+fn show_timezone_picker(ui: &mut egui::Ui, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let detected = detect_local_timezone();
+    let system_label = match &detected {
+        Some(name) => tr_args("settings.system_timezone", &[("zone", name)]),
+        None => tr("settings.system_local_time").to_string(),
+    };
+    let invalid = app.user_timezone.as_ref().filter(|name| name.parse::<Tz>().is_err()).cloned();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("settings.timezone"));
+        
+        let selected_text = app.user_timezone.clone().unwrap_or_else(|| system_label.clone());
+        let search_id = egui::Id::new("timezone_search");
+        let mut search = ui.data_mut(|data| data.get_temp::<String>(search_id).unwrap_or_default());
+        let before = app.user_timezone.clone();
+        
+        egui::ComboBox::from_id_salt("timezone_selector")
+            .selected_text(selected_text)
+            .width(300.0)
+            .height(400.0)
+            .show_ui(ui, |ui| {
+                ui.add(egui::TextEdit::singleline(&mut search).hint_text(tr("settings.search_timezones"))).request_focus();
+                if search.trim().is_empty() {
+                    ui.selectable_value(&mut app.user_timezone, None, &system_label);
+                    ui.separator();
+                    ui.label(tr("settings.common_timezones"));
+                    for tz_name in COMMON_TIMEZONES {
+                        ui.selectable_value(&mut app.user_timezone, Some(tz_name.to_string()), tz_name);
+                    }
+                    ui.separator();
+                    ui.label(tr("settings.all_timezones"));
+                }
+                let zones = matching_timezones(&search);
+                for tz_name in &zones {
+                    ui.selectable_value(&mut app.user_timezone, Some(tz_name.to_string()), *tz_name);
+                }
+                if zones.is_empty() {
+                    ui.label(egui::RichText::new(tr("settings.no_timezone")).color(egui::Color32::GRAY));
+                }
+            });
+        
+        if app.user_timezone != before {
+            search.clear();
+            let name = app.user_timezone.clone().unwrap_or(system_label);
+            logger.log_info(&format!("Changed timezone to {}", name));
+        }
+        ui.data_mut(|data| data.insert_temp(search_id, search));
+    });
+    
+    if let Some(name) = invalid {
+        ui.label(egui::RichText::new(tr_args("settings.unknown_timezone", &[("zone", &name)]))
+            .color(egui::Color32::from_rgb(230, 160, 60)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timezone_search_and_presets() {
+        assert!(COMMON_TIMEZONES.iter().all(|name| name.parse::<Tz>().is_ok()));
+        assert_eq!(matching_timezones("  ").len(), chrono_tz::TZ_VARIANTS.len());
+
+        let berlin = matching_timezones(" europe/BERL ");
+        assert_eq!(berlin, vec!["Europe/Berlin"]);
+        assert!(matching_timezones("pacific").contains(&"US/Pacific"));
+        assert!(matching_timezones("Not/AZone").is_empty());
+
+        if let Some(name) = detect_local_timezone() {
+            assert!(name.parse::<Tz>().is_ok(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Status bar along the bottom of the window
+//!
+//! Shows what a long operation is doing. Background DRC runs report their
+//! progress directly. Work that still runs on the UI thread (gerber generation
+//! and loading, exports) is queued with `defer`: the bar paints its text and a
+//! spinner first, so the window never looks frozen without saying why.
+//! Operations queued while another waits run one after the other, and one that
+//! fails leaves its error in the bar instead of a finished notice.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use egui::{Color32, RichText};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ui::i18n::{tr, tr_args};
+
+/// Seconds the result of the last operation stays in the bar
+const NOTICE_SECS: f64 = 6.0;
+
+/// UI-thread operation; the error is shown in the bar when it fails
+type Operation = Box<dyn FnOnce(&mut DemoLensApp) -> Result<(), String>>;
+
+/// UI-thread operation waiting for its status text to be painted
+struct PendingOperation {
+    text: String,
+    operation: Operation,
+    /// Set once a frame showing `text` was drawn; the operation runs on the next one
+    shown: bool,
+}
+
+/// Result of the last finished operation
+struct Notice {
+    text: String,
+    failed: bool,
+    /// egui time it finished at
+    at: f64,
+}
+
+/// Queued operations and the notice of the last finished one
+#[derive(Default)]
+pub struct StatusBarState {
+    /// In the order they were queued; the first is shown and runs next
+    pending: VecDeque<PendingOperation>,
+    notice: Option<Notice>,
+}
+
+impl StatusBarState {
+    fn queue(&mut self, text: String, operation: Operation) {
+        self.pending.push_back(PendingOperation { text, operation, shown: false });
+    }
+
+    /// Note that the first operation's text is on screen
+    fn mark_shown(&mut self) {
+        if let Some(pending) = self.pending.front_mut() {
+            pending.shown = true;
+        }
+    }
+
+    /// The first operation, once a frame has shown its text
+    fn take_ready(&mut self) -> Option<PendingOperation> {
+        if self.pending.front().is_some_and(|pending| pending.shown) { self.pending.pop_front() } else { None }
+    }
+}
+
+/// Run `operation` on a later frame, after the status bar has shown `text`
+/// Operations queued before an earlier one ran wait for it, in order
+pub fn defer(app: &mut DemoLensApp, text: impl Into<String>, operation: impl FnOnce(&mut DemoLensApp) -> Result<(), String> + 'static) {
+    app.status_bar.queue(text.into(), Box::new(operation));
+}
+
+/// `defer` for an operation that logs its progress, including why it failed
+pub fn defer_logged(
+    app: &mut DemoLensApp,
+    text: impl Into<String>,
+    operation: impl FnOnce(&mut DemoLensApp, &ReactiveEventLogger) -> Result<(), String> + 'static,
+) {
+    defer(app, text, |app| {
+        let logger_state = app.logger_state.clone();
+        let log_colors = app.log_colors.clone();
+        operation(app, &ReactiveEventLogger::with_colors(&logger_state, &log_colors))
+    });
+}
+
+/// Whether an operation is queued or running
+pub fn is_busy(app: &DemoLensApp) -> bool {
+    !app.status_bar.pending.is_empty()
+}
+
+/// Notice for an operation that took `seconds`, and whether it failed
+fn finished_notice(text: &str, result: &Result<(), String>, seconds: f64) -> (String, bool) {
+    let operation = text.trim_end_matches('…');
+    match result {
+        Ok(()) => (tr_args("status.finished", &[("operation", &operation), ("seconds", &format!("{:.1}", seconds))]), false),
+        Err(error) => (tr_args("status.failed", &[("operation", &operation), ("error", error)]), true),
+    }
+}
+
+/// Run the first queued operation once its status text is on screen; called at the start of each frame
+pub fn run_deferred(app: &mut DemoLensApp, ctx: &egui::Context) {
+    let Some(pending) = app.status_bar.take_ready() else {
+        return;
+    };
+    let started = Instant::now();
+    let result = (pending.operation)(app);
+    let (text, failed) = finished_notice(&pending.text, &result, started.elapsed().as_secs_f64());
+    app.status_bar.notice = Some(Notice { text: crate::ui::i18n::localize_number(&text), failed, at: ctx.input(|i| i.time) });
+}
+
+/// Text and completed fraction of what is running, if anything
+fn current_activity(app: &DemoLensApp) -> Option<(String, Option<f32>)> {
+    if let Some(pending) = app.status_bar.pending.front() {
+        let waiting = app.status_bar.pending.len() - 1;
+        if waiting > 0 {
+            return Some((format!("{} {}", pending.text, tr_args("status.queued", &[("count", &waiting)])), None));
+        }
+        return Some((pending.text.clone(), None));
+    }
+    if let Some(job) = &app.drc_job {
+        let text = if job.is_cancelling() {
+            tr("status.drc_cancelling").to_string()
+        } else if job.current_layer.is_empty() {
+            tr("status.drc_running").to_string()
+        } else {
+            tr_args("status.drc_layer", &[("layer", &job.current_layer)])
+        };
+        return Some((text, Some(job.progress)));
+    }
+    if let Some(job) = &app.batch_drc_job {
+        let text = tr_args("status.batch_drc", &[("done", &job.done), ("total", &job.total), ("project", &job.current_project)]);
+        return Some((text, Some(job.done as f32 / job.total.max(1) as f32)));
+    }
+    None
+}
+
+/// Bottom panel with the current operation, or the result of the last one
+pub fn show_status_bar(ctx: &egui::Context, app: &mut DemoLensApp) {
+    let activity = current_activity(app);
+    app.status_bar.mark_shown();
+    if activity.is_some() {
+        ctx.request_repaint();
+    }
+
+    let now = ctx.input(|i| i.time);
+    egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            match &activity {
+                Some((text, fraction)) => {
+                    ui.spinner();
+                    if let Some(fraction) = fraction {
+                        ui.add(egui::ProgressBar::new(*fraction).show_percentage().desired_width(160.0));
+                    }
+                    ui.label(text);
+                }
+                None => match &app.status_bar.notice {
+                    Some(notice) if now - notice.at < NOTICE_SECS => {
+                        let color = if notice.failed { Color32::LIGHT_RED } else { Color32::LIGHT_GREEN };
+                        ui.label(RichText::new(&notice.text).color(color));
+                        ctx.request_repaint_after(std::time::Duration::from_secs_f64(NOTICE_SECS - (now - notice.at)));
+                    }
+                    _ => {
+                        ui.label(RichText::new(tr("status.ready")).color(Color32::GRAY));
+                    }
+                },
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations_queue_in_order_and_run_once_shown() {
+        let mut state = StatusBarState::default();
+        state.queue("Exporting…".to_string(), Box::new(|_| Ok(())));
+        state.queue("Loading gerbers…".to_string(), Box::new(|_| Err("no files".to_string())));
+        // Nothing runs before a frame has shown the first text
+        assert!(state.take_ready().is_none());
+
+        state.mark_shown();
+        assert_eq!(state.take_ready().map(|pending| pending.text).as_deref(), Some("Exporting…"));
+        // The second request was kept, and waits for its own text to be shown
+        assert_eq!(state.pending.len(), 1);
+        assert!(state.take_ready().is_none());
+        state.mark_shown();
+        assert_eq!(state.take_ready().map(|pending| pending.text).as_deref(), Some("Loading gerbers…"));
+        assert!(state.take_ready().is_none());
+    }
+
+    #[test]
+    fn test_failed_operations_are_reported_as_failures() {
+        let (text, failed) = finished_notice("Exporting…", &Ok(()), 1.25);
+        assert!(!failed && text.starts_with("Exporting ") && text.contains("1.2"), "{}", text);
+        let (text, failed) = finished_notice("Exporting…", &Err("disk full".to_string()), 1.25);
+        assert!(failed && text.contains("disk full") && !text.contains("1.2"), "{}", text);
+    }
+}
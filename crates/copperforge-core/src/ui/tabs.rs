@@ -1,6 +1,7 @@
 use crate::DemoLensApp;
 use crate::ui;
-use crate::ecs::{UnitsResource, mm_to_nm, nm_to_mm, mils_to_nm, nm_to_mils};
+use crate::ecs::{DisplayUnit, UnitsResource, nm_to_mm, mils_to_nm};
+use crate::ui::unit_value::{self, UnitValue};
 
 use eframe::emath::{Rect, Vec2};
 use eframe::epaint::Color32;
@@ -15,12 +16,26 @@ use gerber_viewer::{
 };
 use crate::drc_operations::types::Position;
 use crate::display::manager::ToPosition;
+use crate::ui::drc_markers::{draw_marker_label, draw_violation_marker, MarkerShape};
+use crate::ecs::OutlineReference;
+use crate::ui::i18n::{self, tr, tr_args};
 use nalgebra::Vector2;
 
 const MARKER_RADIUS: f32 = 6.0;
+const SWIPE_HANDLE_HALF_WIDTH: f32 = 6.0;
+const DRILL_VIOLATION_COLOR: Color32 = Color32::from_rgb(255, 140, 0);
+const DRC_REGION_COLOR: Color32 = Color32::from_rgb(255, 200, 60);
+const MASK_WARNING_COLOR: Color32 = Color32::from_rgb(200, 90, 255);
+const THERMAL_WARNING_COLOR: Color32 = Color32::from_rgb(255, 170, 40);
+/// How close (in screen pixels) the cursor must be to a feature for the ruler to snap to it
+const OBJECT_SNAP_RADIUS_PX: f32 = 10.0;
+const SNAP_INDICATOR_COLOR: Color32 = Color32::from_rgb(0, 255, 200);
+/// How close (in screen pixels) a click must be to the board outline in edge measure mode
+const EDGE_SNAP_RADIUS_PX: f32 = 15.0;
+const EDGE_PATH_COLOR: Color32 = Color32::from_rgb(255, 200, 0);
 
 /// Define the tabs for the DockArea
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TabKind {
     ViewSettings,
     DRC,
@@ -29,6 +44,10 @@ pub enum TabKind {
     Project,
     Settings,
     BOM,
+    Analysis,
+    Points,
+    View3D,
+    Annotations,
 }
 
 pub struct TabParams<'a> {
@@ -71,6 +90,10 @@ impl Tab {
             TabKind::Project => "Project".to_string(),
             TabKind::Settings => "Settings".to_string(),
             TabKind::BOM => "BOM".to_string(),
+            TabKind::Analysis => "Analysis".to_string(),
+            TabKind::Points => "Points".to_string(),
+            TabKind::View3D => "3D View".to_string(),
+            TabKind::Annotations => "Annotations".to_string(),
         }
     }
 
@@ -81,9 +104,33 @@ impl Tab {
                     let logger_state_clone = params.app.logger_state.clone();
                     let log_colors_clone = params.app.log_colors.clone();
                     
-                    ui.heading("Layer Controls");
+                    ui.heading(tr("view.layer_controls"));
                     ui.separator();
                     ui::show_layers_panel(ui, params.app, &logger_state_clone, &log_colors_clone);
+                    
+                    ui.add_space(20.0);
+                    
+                    ui.heading(tr("view.realistic"));
+                    ui.separator();
+                    ui::realistic_view::show_realistic_settings(ui, params.app);
+                    
+                    ui.add_space(20.0);
+                    
+                    ui.heading(tr("view.minimap"));
+                    ui.separator();
+                    ui::minimap::show_minimap_settings(ui, &mut params.app.minimap_settings);
+                    
+                    ui.add_space(20.0);
+                    
+                    ui.heading(tr("view.coordinate_readout"));
+                    ui.separator();
+                    ui::dro::show_dro_settings(ui, &mut params.app.dro_settings);
+                    
+                    ui.add_space(20.0);
+                    
+                    ui.heading(tr("view.panel"));
+                    ui.separator();
+                    ui::panel::show_panel_settings(ui, params.app);
                 });
             }
             TabKind::DRC => {
@@ -113,16 +160,42 @@ impl Tab {
                 let log_colors_clone = params.app.log_colors.clone();
                 ui::show_bom_panel(ui, params.app, &logger_state_clone, &log_colors_clone);
             }
+            TabKind::Analysis => {
+                let logger_state_clone = params.app.logger_state.clone();
+                let log_colors_clone = params.app.log_colors.clone();
+                ui::show_analysis_panel(ui, params.app, &logger_state_clone, &log_colors_clone);
+            }
+            TabKind::Points => {
+                let logger_state_clone = params.app.logger_state.clone();
+                let log_colors_clone = params.app.log_colors.clone();
+                ui::show_point_picker_panel(ui, params.app, &logger_state_clone, &log_colors_clone);
+            }
+            TabKind::View3D => {
+                ui::view_3d::show_3d_view(ui, params.app);
+            }
+            TabKind::Annotations => {
+                let logger_state_clone = params.app.logger_state.clone();
+                let log_colors_clone = params.app.log_colors.clone();
+                ui::show_annotations_panel(ui, params.app, &logger_state_clone, &log_colors_clone);
+            }
         }
     }
 
     fn render_gerber_view(&self, ui: &mut egui::Ui, app: &mut DemoLensApp) {
+        // Nothing loaded: offer ways to open a board instead of an empty canvas
+        if !crate::ecs::has_loaded_layers(&mut app.ecs_world) {
+            ui::empty_state::show_empty_state(ui, app);
+            return;
+        }
+        
         // Render top controls
         render_controls(ui, app);
         ui.separator();
+        ui::layer_set::show_layer_set_strip(ui, app);
         
         // Set up viewport and handle interactions
         let (viewport, response) = setup_viewport(ui, app);
+        handle_swipe_divider(ui, app, &viewport);
         handle_viewport_interactions(ui, app, &viewport, &response);
         
         // Render the gerber layers and overlays
@@ -139,6 +212,12 @@ fn render_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
             render_layer_controls(ui, app);
             ui.separator();
             render_transform_controls(ui, app);
+            ui.separator();
+            render_swipe_controls(ui, app);
+            ui.separator();
+            if ui.button("🧊 3D").on_hover_text("Open the 3D view").clicked() {
+                app.open_tab_request = Some(TabKind::View3D);
+            }
         });
         
         ui.add_space(4.0); // Small gap between rows
@@ -150,10 +229,140 @@ fn render_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
             render_ruler_controls(ui, app);
             ui.separator();
             render_grid_controls(ui, app);
+            ui.separator();
+            render_net_controls(ui, app);
+            ui.separator();
+            ui::point_picker::render_point_picker_controls(ui, app);
+            ui.separator();
+            ui::annotations::render_annotation_controls(ui, app);
+            ui.separator();
+            ui::revision_compare::render_revision_compare_controls(ui, app);
+            ui.separator();
+            ui::view_clipboard::render_copy_view_controls(ui, app);
         });
     });
 }
 
+/// Keep the quadrant layout in step with the open project
+/// Called every frame; loads the new project's layout (the built-in one if never changed) when the project changes
+pub fn sync_quadrant_layout_project(app: &mut DemoLensApp) {
+    let Some(state) = &app.project_manager_state else {
+        return;
+    };
+    let current_id = state.current_project.as_ref().map(|project| project.metadata.id.clone());
+    if current_id == app.display_manager.quadrant_layout_project {
+        return;
+    }
+
+    let layout = match (&current_id, &state.database) {
+        (Some(project_id), Some(database)) => database.load_quadrant_layout(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load quadrant layout: {}", e);
+            None
+        }),
+        _ => None,
+    };
+    app.display_manager.quadrant_layout = layout.unwrap_or_default();
+    app.display_manager.quadrant_layout_project = current_id;
+    crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+}
+
+/// Store the quadrant layout with the open project, if any
+fn save_quadrant_layout(app: &DemoLensApp) {
+    let (Some(project_id), Some(state)) = (&app.display_manager.quadrant_layout_project, &app.project_manager_state) else {
+        return;
+    };
+    if let Some(database) = &state.database {
+        if let Err(e) = database.save_quadrant_layout(project_id, &app.display_manager.quadrant_layout) {
+            eprintln!("Failed to save quadrant layout: {}", e);
+        }
+    }
+}
+
+/// Built-in layout or a grid of cells (2×2, row, column or custom) choosing the layer of each
+fn render_quadrant_layout_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    if ui.checkbox(&mut app.display_manager.quadrant_layout.custom, "Custom layout")
+        .on_hover_text("Choose the layer of each cell; off places copper, silkscreen and soldermask in the first three")
+        .changed()
+    {
+        crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+    }
+    let current_arrangement = app.display_manager.quadrant_layout.arrangement;
+    let mut arrangement = current_arrangement;
+    egui::ComboBox::from_id_salt("quadrant_arrangement")
+        .selected_text(arrangement.name())
+        .width(110.0)
+        .show_ui(ui, |ui| {
+            use crate::display::QuadrantArrangement;
+            let custom = match arrangement {
+                QuadrantArrangement::Grid { .. } => arrangement,
+                _ => QuadrantArrangement::Grid { columns: 3, rows: 2 },
+            };
+            for option in [QuadrantArrangement::Grid2x2, QuadrantArrangement::Row, QuadrantArrangement::Column, custom] {
+                let selected = std::mem::discriminant(&option) == std::mem::discriminant(&current_arrangement);
+                if ui.selectable_label(selected, option.name()).clicked() {
+                    arrangement = option;
+                }
+            }
+        })
+        .response
+        .on_hover_text("How the cells are placed; the built-in layout fills the first three with copper, silkscreen and soldermask");
+    if let crate::display::QuadrantArrangement::Grid { columns, rows } = &mut arrangement {
+        let max = crate::display::QuadrantArrangement::MAX_GRID;
+        ui.add(egui::DragValue::new(columns).range(1..=max).suffix(" col"));
+        ui.label("×");
+        ui.add(egui::DragValue::new(rows).range(1..=max).suffix(" row"));
+    }
+    if arrangement != current_arrangement {
+        app.display_manager.quadrant_layout.set_arrangement(arrangement);
+        crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+    }
+    
+    if !app.display_manager.quadrant_layout.custom {
+        return;
+    }
+    
+    let loaded: Vec<crate::ecs::LayerType> = crate::ecs::LayerType::all()
+        .into_iter()
+        .filter(|layer_type| crate::ecs::get_layer_by_type(&mut app.ecs_world, *layer_type).is_some())
+        .collect();
+    let mut changed = None;
+    let layout = &app.display_manager.quadrant_layout;
+    let columns = layout.arrangement.columns();
+    egui::Grid::new("quadrant_layout_grid").spacing([4.0, 2.0]).show(ui, |ui| {
+        for index in 0..layout.arrangement.cell_count() {
+            let current = layout.cell_layer(index);
+            let text = current.map_or("None".to_string(), |layer_type| layer_type.display_name());
+            egui::ComboBox::from_id_salt(("quadrant_layer", index))
+                .selected_text(text)
+                .width(120.0)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(current.is_none(), "None").clicked() {
+                        changed = Some((index, None));
+                    }
+                    for layer_type in &loaded {
+                        if ui.selectable_label(current == Some(*layer_type), layer_type.display_name()).clicked() {
+                            changed = Some((index, Some(*layer_type)));
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(layout.cell_name(index));
+            if index % columns == columns - 1 {
+                ui.end_row();
+            }
+        }
+    });
+    
+    if let Some((index, layer_type)) = changed {
+        app.display_manager.quadrant_layout.assign_cell(index, layer_type);
+        // A layer picked for a cell is meant to be seen, e.g. bottom copper while showing the top
+        if let Some(layer_type) = layer_type {
+            crate::ecs::set_layer_visibility(&mut app.ecs_world, layer_type, true);
+        }
+        crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+    }
+}
+
 fn render_quadrant_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
     if ui.checkbox(&mut app.display_manager.quadrant_view_enabled, "Quadrant View").clicked() {
         crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
@@ -164,91 +373,182 @@ fn render_quadrant_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
         ui.separator();
         ui.label("Spacing:");
         
-        // Get units from ECS
-        let units_resource = Tab::get_units(app);
-        
-        let (mut spacing_value, units_suffix, conversion_factor) = if units_resource.is_mils() {
-            let spacing_nm = mm_to_nm(app.display_manager.quadrant_offset_magnitude as f32);
-            (nm_to_mils(spacing_nm), "mils", 0.0254)
-        } else {
-            (app.display_manager.quadrant_offset_magnitude as f32, "mm", 1.0)
-        };
-        
-        let speed = if units_resource.is_mils() { 10.0 } else { 1.0 };
-        let max_range = if units_resource.is_mils() { 20000.0 } else { 500.0 };
-        
-        if ui.add(egui::DragValue::new(&mut spacing_value)
-            .suffix(units_suffix)
-            .speed(speed)
-            .range(0.0..=max_range))
-            .changed() 
+        let mut spacing_mm = app.display_manager.quadrant_offset_magnitude;
+        if ui.add(UnitValue::new(&mut spacing_mm, unit_value::display_unit(&app.ecs_world))
+            .range_mm(0.0..=500.0)
+            .speed_mm(0.5))
+            .changed()
         {
-            let spacing_mm = spacing_value * conversion_factor;
-            app.display_manager.set_quadrant_offset_magnitude(spacing_mm as f64);
+            app.display_manager.set_quadrant_offset_magnitude(spacing_mm);
             crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
         }
         
+        ui.separator();
+        let before = app.display_manager.quadrant_layout.clone();
+        render_quadrant_layout_controls(ui, app);
+        if app.display_manager.quadrant_layout != before {
+            save_quadrant_layout(app);
+        }
         ui.separator();
         
-        if ui.button("📷 Export Layers as PNG").clicked() {
-            let logger_state = app.logger_state.clone();
-            let log_colors = app.log_colors.clone();
-            let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
-            crate::ui::orientation_panel::export_quadrant_layers_to_png(app, &logger);
+        if ui.button("📷 Export Layers as PNG…").clicked() {
+            app.show_png_export_dialog = true;
         }
     }
 }
 
 fn render_layer_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
-    let flip_text = if app.display_manager.showing_top { "🔄 Flip to Bottom (F)" } else { "🔄 Flip to Top (F)" };
-    if ui.button(flip_text).clicked() {
-        app.display_manager.showing_top = !app.display_manager.showing_top;
-        
-        // Auto-toggle layer visibility based on flip state (using ECS)
-        for layer_type in crate::ecs::LayerType::all() {
-            let visible = match layer_type {
-                crate::ecs::LayerType::Copper(1) |
-                crate::ecs::LayerType::Silkscreen(crate::ecs::Side::Top) |
-                crate::ecs::LayerType::Soldermask(crate::ecs::Side::Top) |
-                crate::ecs::LayerType::Paste(crate::ecs::Side::Top) => {
-                    app.display_manager.showing_top
-                },
-                crate::ecs::LayerType::Copper(_) => {
-                    !app.display_manager.showing_top
-                },
-                crate::ecs::LayerType::Silkscreen(crate::ecs::Side::Bottom) |
-                crate::ecs::LayerType::Soldermask(crate::ecs::Side::Bottom) |
-                crate::ecs::LayerType::Paste(crate::ecs::Side::Bottom) => {
-                    !app.display_manager.showing_top
-                },
-                crate::ecs::LayerType::MechanicalOutline => {
-                    // Leave outline visibility unchanged, get current state from ECS
-                    crate::ecs::get_layer_visibility(&mut app.ecs_world, layer_type)
-                }
-            };
-            crate::ecs::set_layer_visibility(&mut app.ecs_world, layer_type, visible);
+    let shortcut = app.key_bindings.get(ui::key_bindings::ShortcutAction::FlipView);
+    let flip_text = if app.display_manager.showing_top { "🔄 Flip to Bottom" } else { "🔄 Flip to Top" };
+    if ui.button(format!("{} ({})", flip_text, shortcut)).clicked() {
+        app.flip_board_view();
+    }
+    
+    ui.checkbox(&mut app.display_manager.highlight_flashes, "Highlight pads/vias")
+        .on_hover_text("Outline flashed (D03) pads and vias on copper layers");
+}
+
+fn render_swipe_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let swipe = &mut app.display_manager.swipe_compare;
+    let toggled = ui.toggle_value(&mut swipe.enabled, "⇔ Swipe")
+        .on_hover_text("Compare two layers with a draggable divider")
+        .changed();
+    
+    if swipe.enabled {
+        for (label, layer) in [("A:", &mut swipe.layer_a), ("B:", &mut swipe.layer_b)] {
+            ui.label(label);
+            egui::ComboBox::from_id_salt(format!("swipe_layer_{}", label))
+                .selected_text(layer.display_name())
+                .width(140.0)
+                .show_ui(ui, |ui| {
+                    for layer_type in crate::ecs::LayerType::all() {
+                        ui.selectable_value(layer, layer_type, layer_type.display_name());
+                    }
+                });
         }
-        
-        crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+    }
+    
+    if toggled {
+        let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+        let swipe = &app.display_manager.swipe_compare;
+        if swipe.enabled {
+            logger.log_info(&format!(
+                "Swipe compare: {} | {}",
+                swipe.layer_a.display_name(),
+                swipe.layer_b.display_name()
+            ));
+        } else {
+            logger.log_info("Swipe compare off");
+        }
+    }
+}
+
+/// Drag handle for the swipe compare divider
+/// Registered after the viewport so it takes the drag instead of panning
+fn handle_swipe_divider(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect) {
+    if !app.display_manager.swipe_compare.enabled {
+        return;
+    }
+    
+    let divider_x = app.display_manager.swipe_compare.divider_x(viewport);
+    let handle_rect = Rect::from_min_max(
+        Pos2::new(divider_x - SWIPE_HANDLE_HALF_WIDTH, viewport.min.y),
+        Pos2::new(divider_x + SWIPE_HANDLE_HALF_WIDTH, viewport.max.y),
+    );
+    let response = ui.interact(handle_rect, ui.id().with("swipe_divider"), egui::Sense::drag());
+    
+    if response.hovered() || response.dragged() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+    }
+    if response.dragged() {
+        if let Some(pointer) = response.interact_pointer_pos() {
+            app.display_manager.swipe_compare.set_divider_x(pointer.x, viewport);
+        }
+    }
+}
+
+fn render_swipe_divider(app: &DemoLensApp, painter: &Painter, viewport: &Rect) {
+    let swipe = &app.display_manager.swipe_compare;
+    if !swipe.enabled {
+        return;
+    }
+    
+    let x = swipe.divider_x(viewport);
+    painter.line_segment(
+        [Pos2::new(x, viewport.min.y), Pos2::new(x, viewport.max.y)],
+        Stroke::new(2.0, Color32::WHITE),
+    );
+    painter.circle_filled(Pos2::new(x, viewport.center().y), SWIPE_HANDLE_HALF_WIDTH, Color32::WHITE);
+    
+    let font = egui::FontId::proportional(12.0);
+    painter.text(Pos2::new(x - 8.0, viewport.min.y + 8.0), egui::Align2::RIGHT_TOP, swipe.layer_a.display_name(), font.clone(), Color32::WHITE);
+    painter.text(Pos2::new(x + 8.0, viewport.min.y + 8.0), egui::Align2::LEFT_TOP, swipe.layer_b.display_name(), font, Color32::WHITE);
+}
+
+fn render_net_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let nets = crate::ecs::get_net_list(&app.ecs_world);
+    if nets.is_empty() {
+        return;
+    }
+    
+    ui.label(tr("view.net"));
+    let selected_text = app.highlighted_net
+        .and_then(|net_id| crate::ecs::get_net_name(&app.ecs_world, net_id))
+        .unwrap_or_else(|| tr("view.net_none").to_string());
+    
+    let previous = app.highlighted_net;
+    egui::ComboBox::from_id_salt("net_highlight_selector")
+        .selected_text(selected_text)
+        .width(160.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut app.highlighted_net, None, tr("view.net_none"));
+            for (net_id, name) in &nets {
+                ui.selectable_value(&mut app.highlighted_net, Some(*net_id), name);
+            }
+        });
+    
+    if app.highlighted_net != previous {
+        log_net_highlight(app);
+    }
+}
+
+fn log_net_highlight(app: &DemoLensApp) {
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    match app.highlighted_net.and_then(|net_id| crate::ecs::get_net_name(&app.ecs_world, net_id)) {
+        Some(name) => logger.log_info(&format!("Highlighting net {}", name)),
+        None => logger.log_info("Net highlight cleared"),
     }
 }
 
 fn render_transform_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
     // Rotate button
-    if ui.button("🔄 Rotate (R)").clicked() {
-        app.rotation_degrees = (app.rotation_degrees + 90.0) % 360.0;
-        
-        // Don't reset view - just mark coordinates as dirty to update rotation
-        // This keeps the view centered on the current origin
-        crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
-        
-        let logger_state = app.logger_state.clone();
-        let log_colors = app.log_colors.clone();
-        let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
-        logger.log_custom(
-            crate::project::constants::LOG_TYPE_ROTATION, 
-            &format!("Rotated to {:.0}°", app.rotation_degrees)
-        );
+    if ui.button(format!("🔄 Rotate ({})", app.key_bindings.get(ui::key_bindings::ShortcutAction::RotateView))).clicked() {
+        // Don't reset view - keeps the view centered on the current origin
+        app.animate_rotation_to(app.rotation_degrees + 90.0);
+        log_rotation(app);
+    }
+    
+    // Arbitrary angle entry with fine nudges for slightly rotated imports
+    if ui.small_button("−0.1°").on_hover_text(tr("view.rotate_minus_hint")).clicked() {
+        app.set_rotation_degrees(app.rotation_degrees - 0.1);
+        log_rotation(app);
+    }
+    let mut angle = app.rotation_degrees;
+    let angle_response = ui.add(
+        egui::DragValue::new(&mut angle)
+            .speed(0.1)
+            .max_decimals(2)
+            .suffix("°")
+    ).on_hover_text(tr("view.rotation_hint"));
+    if angle_response.changed() {
+        app.set_rotation_degrees(angle);
+    }
+    if angle_response.drag_stopped() || angle_response.lost_focus() {
+        log_rotation(app);
+    }
+    if ui.small_button("+0.1°").on_hover_text(tr("view.rotate_plus_hint")).clicked() {
+        app.set_rotation_degrees(app.rotation_degrees + 0.1);
+        log_rotation(app);
     }
     
     // ECS Rendering is now the default and only mode (gerber-viewer 0.2.0 compatible)
@@ -315,72 +615,110 @@ fn render_transform_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
             logger.log_info("Click on the PCB to set the origin");
         }
     }
+    
+    // Quick-set the origin to a point of the board outline
+    ui.menu_button("📐 Origin to outline", |ui| {
+        for reference in [OutlineReference::BottomLeft, OutlineReference::Center, OutlineReference::Centroid] {
+            if ui.button(format!("Origin = {}", reference.label())).clicked() {
+                set_origin_to_outline(app, reference);
+                ui.close();
+            }
+        }
+    });
+}
+
+/// Move the design origin to a reference point of the board outline, snapped to the grid when snapping is on
+fn set_origin_to_outline(app: &mut DemoLensApp, reference: OutlineReference) {
+    let logger_state = app.logger_state.clone();
+    let log_colors = app.log_colors.clone();
+    let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
+    let Some(point) = crate::ecs::get_outline_reference_point(&mut app.ecs_world, &app.display_manager, reference) else {
+        logger.log_warning("Setting the origin from the outline needs a closed board outline");
+        return;
+    };
+    let snapped = crate::display::snap_to_grid(point, &app.grid_settings, app.display_manager.design_origin());
+    
+    app.display_manager.design_offset = crate::display::VectorOffset { x: snapped.x, y: snapped.y };
+    app.setting_origin_mode = false;
+    app.origin_has_been_set = true;
+    app.needs_initial_view = true;
+    crate::ecs::mark_coordinates_dirty_ecs(&mut app.ecs_world);
+    
+    let snap_msg = if snapped != point { " (snapped to grid)" } else { "" };
+    logger.log_custom(
+        crate::project::constants::LOG_TYPE_DESIGN_OFFSET,
+        &format!("Set origin to {} at ({:.3}, {:.3}) mm{}", reference.label(), snapped.x, snapped.y, snap_msg),
+    );
+}
+
+fn log_rotation(app: &DemoLensApp) {
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    logger.log_custom(
+        crate::project::constants::LOG_TYPE_ROTATION,
+        &format!("Rotated to {:.1}°", app.rotation_degrees)
+    );
 }
 
 fn render_grid_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
-    ui.label("Grid:");
+    ui.label(tr("view.grid"));
     let grid_spacings_mils = [100.0, 50.0, 25.0, 10.0, 5.0, 2.0, 1.0];
     let grid_spacings_mm = [2.54, 1.27, 0.635, 0.254, 0.127, 0.0508, 0.0254];
     
-    let is_mils = {
-        let units_resource = Tab::get_units(app);
-        units_resource.is_mils()
-    };
-    
-    let spacings = if is_mils {
-        &grid_spacings_mils[..]
+    // Imperial units offer the mil presets, metric units the mm ones
+    let unit = unit_value::display_unit(&app.ecs_world);
+    let spacings: Vec<f32> = if unit.is_imperial() {
+        grid_spacings_mils.iter().map(|&mils| nm_to_mm(mils_to_nm(mils))).collect()
     } else {
-        &grid_spacings_mm[..]
+        grid_spacings_mm.to_vec()
     };
+    // Whole mils, or the mm presets to the µm
+    let preset_label = |spacing_mm: f32| unit.format_mm(spacing_mm as f64, if unit.is_imperial() { 1 } else { 3 });
     
     // Find current selection
-    let mut current_spacing_display = "Custom".to_string();
-    for &spacing in spacings {
-        let spacing_mm = if is_mils { 
-            nm_to_mm(mils_to_nm(spacing)) 
-        } else { 
-            spacing 
-        };
-        if (app.grid_settings.spacing_mm - spacing_mm).abs() < 0.001 {
-            current_spacing_display = if is_mils {
-                format!("{} mils", spacing as i32)
-            } else {
-                format!("{:.3} mm", spacing)
-            };
-            break;
-        }
-    }
+    let current_spacing_display = spacings.iter()
+        .find(|&&spacing_mm| (app.grid_settings.spacing_mm - spacing_mm).abs() < 0.001)
+        .map_or_else(|| tr("view.grid_custom").to_string(), |&spacing_mm| preset_label(spacing_mm));
     
     egui::ComboBox::from_label("")
         .selected_text(current_spacing_display)
         .show_ui(ui, |ui| {
-            for &spacing in spacings {
-                let spacing_mm = if is_mils { 
-                    nm_to_mm(mils_to_nm(spacing)) 
-                } else { 
-                    spacing 
-                };
-                let label = if is_mils {
-                    format!("{} mils", spacing as i32)
-                } else {
-                    format!("{:.3} mm", spacing)
-                };
+            for &spacing_mm in &spacings {
+                let label = preset_label(spacing_mm);
                 if ui.selectable_label(false, label).clicked() {
                     app.grid_settings.spacing_mm = spacing_mm;
                 }
             }
         });
     
+    // Custom spacing, then the same spacing in the other unit system
+    ui.add(UnitValue::new(&mut app.grid_settings.spacing_mm, unit).range_mm(0.025..=25.0))
+        .on_hover_text(tr("view.grid_custom_hint"));
+    let other_unit = format!(
+        "= {}",
+        (if unit.is_imperial() { DisplayUnit::Millimeters } else { DisplayUnit::Mils }).format_mm(app.grid_settings.spacing_mm as f64, 3)
+    );
+    ui.label(egui::RichText::new(other_unit).color(Color32::GRAY));
+    
+    egui::ComboBox::from_id_salt("grid_style")
+        .selected_text(app.grid_settings.style.name())
+        .show_ui(ui, |ui| {
+            for style in crate::display::GridStyle::ALL {
+                ui.selectable_value(&mut app.grid_settings.style, style, style.name());
+            }
+        });
+    
     ui.separator();
     
     // Grid dot size slider
-    ui.label("Dot Size:");
+    ui.label(tr("view.dot_size"));
     ui.add(egui::Slider::new(&mut app.grid_settings.dot_size, 0.5..=5.0).suffix("px"));
     
     ui.separator();
     
     // Enterprise feature: Snap to Grid
     ui.checkbox(&mut app.grid_settings.snap_enabled, "🧲 Snap to Grid");
+    ui.checkbox(&mut app.grid_settings.object_snap_enabled, "🎯 Snap to Objects")
+        .on_hover_text("Ruler and origin snap to trace endpoints and pad/hole centers near the cursor");
 }
 
 fn render_ruler_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
@@ -395,6 +733,20 @@ fn render_ruler_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
             app.ruler_end = None;
         }
     }
+    let edge_toggle = ui.toggle_value(&mut app.ruler_edge_mode, "⬚ Along Edge")
+        .on_hover_text("Measure along the board outline; clicks snap to the nearest edge");
+    if edge_toggle.changed() {
+        app.ruler_start = None;
+        app.ruler_end = None;
+        app.ruler_dragging = false;
+        app.latched_measurement_start = None;
+        app.latched_measurement_end = None;
+    }
+    ui::measurement_history::render_measurement_history_menu(ui, app);
+    if app.ruler_edge_mode {
+        render_edge_ruler_controls(ui, app);
+        return;
+    }
     
     // Show ruler measurement if active and both points set
     if app.ruler_active {
@@ -403,22 +755,13 @@ fn render_ruler_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
             let dy = end.y - start.y;
             let distance = (dx * dx + dy * dy).sqrt();
             
-            let units_resource = Tab::get_units(app);
-            if units_resource.is_mils() {
-                let distance_nm = mm_to_nm(distance as f32);
-                let distance_mils = nm_to_mils(distance_nm);
-                let dx_nm = mm_to_nm(dx.abs() as f32);
-                let dy_nm = mm_to_nm(dy.abs() as f32);
-                ui.label(format!("📏 Distance: {:.2} mils", distance_mils));
-                ui.label(format!("📐 ΔX: {:.3} mils, ΔY: {:.3} mils", nm_to_mils(dx_nm), nm_to_mils(dy_nm)));
-            } else {
-                ui.label(format!("📏 Distance: {:.3} mm", distance));
-                ui.label(format!("📐 ΔX: {:.3} mm, ΔY: {:.3} mm", dx.abs(), dy.abs()));
-            }
+            let unit = Tab::get_units(app).display_unit;
+            ui.label(distance_readout(&unit.format_mm(distance, 3)));
+            ui.label(delta_readout(&unit.format_mm(dx.abs(), 3), &unit.format_mm(dy.abs(), 3)));
         } else if app.ruler_start.is_some() {
-            ui.label("Click second point to complete measurement");
+            ui.label(tr("measure.second_point"));
         } else {
-            ui.label("Click first point to start measurement (or press M to toggle)");
+            ui.label(tr("measure.first_point"));
         }
     }
     // Show latched measurement if not in active measurement mode
@@ -427,28 +770,74 @@ fn render_ruler_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
         let dy = end.y - start.y;
         let distance = (dx * dx + dy * dy).sqrt();
         
-        let units_resource = Tab::get_units(app);
-        if units_resource.is_mils() {
-            let distance_nm = mm_to_nm(distance as f32);
-            let distance_mils = nm_to_mils(distance_nm);
-            let dx_nm = mm_to_nm(dx.abs() as f32);
-            let dy_nm = mm_to_nm(dy.abs() as f32);
-            let dx_mils = nm_to_mils(dx_nm);
-            let dy_mils = nm_to_mils(dy_nm);
-            ui.label(egui::RichText::new(format!("📏 Distance: {:.2} mils", distance_mils)).color(egui::Color32::LIGHT_GRAY));
-            // Use more precision for deltas to avoid showing 0.00 for small values
-            ui.label(egui::RichText::new(format!("📐 ΔX: {:.3} mils, ΔY: {:.3} mils", dx_mils, dy_mils)).color(egui::Color32::LIGHT_GRAY));
-        } else {
-            ui.label(egui::RichText::new(format!("📏 Distance: {:.3} mm", distance)).color(egui::Color32::LIGHT_GRAY));
-            ui.label(egui::RichText::new(format!("📐 ΔX: {:.3} mm, ΔY: {:.3} mm", dx.abs(), dy.abs())).color(egui::Color32::LIGHT_GRAY));
+        let unit = Tab::get_units(app).display_unit;
+        ui.label(egui::RichText::new(distance_readout(&unit.format_mm(distance, 3))).color(egui::Color32::LIGHT_GRAY));
+        ui.label(egui::RichText::new(delta_readout(&unit.format_mm(dx.abs(), 3), &unit.format_mm(dy.abs(), 3))).color(egui::Color32::LIGHT_GRAY));
+        ui.label(egui::RichText::new(tr("measure.previous")).color(egui::Color32::GRAY).italics());
+    }
+}
+
+fn distance_readout(distance: &str) -> String {
+    i18n::localize_number(&tr_args("measure.distance", &[("value", &distance)]))
+}
+
+fn delta_readout(dx: &str, dy: &str) -> String {
+    i18n::localize_number(&tr_args("measure.delta", &[("dx", &dx), ("dy", &dy)]))
+}
+
+/// Along-edge, other-way and chord lengths of an edge measurement
+fn edge_measurement_lines(app: &DemoLensApp, measurement: &crate::ecs::EdgeMeasurement) -> [String; 3] {
+    let unit = Tab::get_units(app).display_unit;
+    let flip = app.ruler_edge_flip;
+    let way = if measurement.forward == measurement.backward {
+        tr("measure.edge_either_way")
+    } else if flip {
+        tr("measure.edge_longer_way")
+    } else {
+        tr("measure.edge_shorter_way")
+    };
+    [
+        tr_args("measure.edge_along", &[("value", &unit.format_mm(measurement.along(flip), 3)), ("way", &way)]),
+        tr_args("measure.edge_other_way", &[("value", &unit.format_mm(measurement.other_way(flip), 3))]),
+        tr_args("measure.edge_chord", &[("value", &unit.format_mm(measurement.chord, 3))]),
+    ]
+}
+
+fn render_edge_ruler_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let Some(paths) = crate::ecs::get_outline_paths(&mut app.ecs_world, &app.display_manager) else {
+        ui.label(egui::RichText::new(tr("measure.edge_no_outline")).color(Color32::GRAY));
+        return;
+    };
+    let (start, end) = if app.ruler_active {
+        (app.ruler_start, app.ruler_end)
+    } else {
+        (app.latched_measurement_start, app.latched_measurement_end)
+    };
+    
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            if let Some(measurement) = paths.measure(start, end) {
+                for line in edge_measurement_lines(app, &measurement) {
+                    let text = egui::RichText::new(i18n::localize_number(&line));
+                    ui.label(if app.ruler_active { text } else { text.color(Color32::LIGHT_GRAY) });
+                }
+                ui.checkbox(&mut app.ruler_edge_flip, tr("measure.edge_flip"));
+            }
+            if !app.ruler_active {
+                ui.label(egui::RichText::new(tr("measure.previous")).color(Color32::GRAY).italics());
+            }
+        }
+        _ if !app.ruler_active => {}
+        (Some(_), None) => {
+            ui.label(tr("measure.edge_second_point"));
+        }
+        _ => {
+            ui.label(tr("measure.edge_first_point"));
         }
-        ui.label(egui::RichText::new("(Previous measurement - press M to start new)").color(egui::Color32::GRAY).italics());
     }
 }
 
 fn setup_viewport(ui: &mut egui::Ui, app: &mut DemoLensApp) -> (Rect, egui::Response) {
-    ui.ctx().request_repaint();
-    
     let available_size = ui.available_size();
     let size = egui::Vec2::new(
         available_size.x.max(100.0),
@@ -475,6 +864,11 @@ fn setup_viewport(ui: &mut egui::Ui, app: &mut DemoLensApp) -> (Rect, egui::Resp
 fn handle_viewport_interactions(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect, response: &egui::Response) {
     let mouse_pos_screen = ui.input(|i| i.pointer.hover_pos());
     
+    // Pinch zoom / two-finger pan; the other tools wait while fingers are down
+    if ui::gestures::handle_touch_gestures(ui, app, response) {
+        return;
+    }
+    
     // Handle zoom window
     handle_zoom_window(ui, app, viewport, mouse_pos_screen, response);
     
@@ -535,9 +929,9 @@ fn handle_viewport_interactions(ui: &mut egui::Ui, app: &mut DemoLensApp, viewpo
                 
                 // Draw preview text
                 let text = if app.ruler_start.is_some() && app.ruler_end.is_none() {
-                    "Click second point to complete measurement"
+                    tr("measure.second_point")
                 } else {
-                    "Click to start measurement"
+                    tr("measure.start")
                 };
                 painter.text(
                     mouse_pos + Vec2::new(20.0, -20.0),
@@ -549,21 +943,46 @@ fn handle_viewport_interactions(ui: &mut egui::Ui, app: &mut DemoLensApp, viewpo
             }
         }
         
+        // Show which feature a click would snap to
+        if app.ruler_active && app.ruler_edge_mode && !app.setting_origin_mode {
+            if let Some(mouse_pos) = mouse_pos_screen.filter(|pos| viewport.contains(*pos)) {
+                if let Some(edge_point) = edge_pick_point(app, mouse_pos) {
+                    let screen_pos = app.view_state.gerber_to_screen_coords(edge_point.point);
+                    draw_snap_indicator(ui.painter(), screen_pos, crate::ecs::SnapKind::Endpoint);
+                }
+            }
+        } else if app.ruler_active || app.setting_origin_mode {
+            if let Some(mouse_pos) = mouse_pos_screen.filter(|pos| viewport.contains(*pos)) {
+                if let (_, Some(snap)) = snapped_pick_point(app, mouse_pos) {
+                    let screen_pos = app.view_state.gerber_to_screen_coords(snap.position);
+                    draw_snap_indicator(ui.painter(), screen_pos, snap.kind);
+                }
+            }
+        }
+        
         // Handle professional ruler tool with right-click drag
         if app.ruler_active && !app.setting_origin_mode {
             handle_ruler_interaction(ui, app, response);
         }
         
+        // Properties of the trace, pad or region under the cursor
+        if !app.ruler_active && !app.setting_origin_mode && !app.point_picker.active && app.annotations.tool.is_none() {
+            ui::primitive_inspect::show_hover_tooltip(ui, app, response, viewport);
+        }
+        
+        // Remember the click for the DRO delta readout
+        if response.clicked() {
+            if let Some(click_pos) = response.interact_pointer_pos() {
+                let click_coords = app.view_state.screen_to_gerber_coords(click_pos);
+                ui::dro::record_click(app, click_coords);
+            }
+        }
+        
         // Handle origin setting
         if app.setting_origin_mode && response.clicked() {
-            if let Some(gerber_coords) = app.ui_state.cursor_gerber_coords {
-                // Enterprise feature: Apply snap to grid if enabled
-                let final_coords = if app.grid_settings.snap_enabled {
-                    let point = nalgebra::Point2::new(gerber_coords.x, gerber_coords.y);
-                    crate::display::snap_to_grid(point, &app.grid_settings)
-                } else {
-                    nalgebra::Point2::new(gerber_coords.x, gerber_coords.y)
-                };
+            if let Some(mouse_screen_pos) = ui.input(|i| i.pointer.hover_pos()) {
+                // Snap to geometry first, then to the grid if enabled
+                let (final_coords, object_snap) = snapped_pick_point(app, mouse_screen_pos);
                 
                 app.display_manager.design_offset = crate::display::VectorOffset {
                     x: final_coords.x,
@@ -581,9 +1000,38 @@ fn handle_viewport_interactions(ui: &mut egui::Ui, app: &mut DemoLensApp, viewpo
                 let logger_state = app.logger_state.clone();
                 let log_colors = app.log_colors.clone();
                 let logger = ReactiveEventLogger::with_colors(&logger_state, &log_colors);
-                let snap_msg = if app.grid_settings.snap_enabled { " (snapped to grid)" } else { "" };
+                let snap_msg = match object_snap.map(|snap| snap.kind) {
+                    Some(crate::ecs::SnapKind::Center) => " (snapped to center)",
+                    Some(crate::ecs::SnapKind::Endpoint) => " (snapped to endpoint)",
+                    None if app.grid_settings.snap_enabled => " (snapped to grid)",
+                    None => "",
+                };
                 logger.log_info(&format!("Set origin to ({:.2}, {:.2}) mm{} - view recentered", final_coords.x, final_coords.y, snap_msg));
             }
+        } else if app.point_picker.active && !app.ruler_active && response.clicked() {
+            if let Some(gerber_coords) = app.ui_state.cursor_gerber_coords {
+                ui::point_picker::add_point_at(app, nalgebra::Point2::new(gerber_coords.x, gerber_coords.y));
+            }
+        } else if app.annotations.tool.is_some() && !app.ruler_active && response.clicked() {
+            if let Some(gerber_coords) = app.ui_state.cursor_gerber_coords {
+                ui::annotations::handle_click(app, nalgebra::Point2::new(gerber_coords.x, gerber_coords.y));
+            }
+        } else if !app.ruler_active && response.clicked() {
+            // Click on a trace or pad to select it and highlight its net
+            if let Some(click_pos) = response.interact_pointer_pos() {
+                ui::primitive_inspect::select_at(app, click_pos);
+                let picked_net = crate::ecs::pick_net_at_screen_pos(
+                    &mut app.ecs_world,
+                    &app.view_state,
+                    &app.display_manager,
+                    click_pos,
+                    4.0,
+                );
+                if picked_net.is_some() && picked_net != app.highlighted_net {
+                    app.highlighted_net = picked_net;
+                    log_net_highlight(app);
+                }
+            }
         }
     }
 }
@@ -591,12 +1039,14 @@ fn handle_viewport_interactions(ui: &mut egui::Ui, app: &mut DemoLensApp, viewpo
 fn handle_zoom_window(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect, mouse_pos_screen: Option<Pos2>, response: &egui::Response) {
     let right_button = egui::PointerButton::Secondary;
     
-    // Start zoom window
-    if response.contains_pointer() {
+    // Start zoom window (only from a real mouse button, never a synthesized touch)
+    if response.contains_pointer() && !ui.input(|i| i.any_touches()) {
         if ui.input(|i| i.pointer.button_pressed(right_button)) {
             if let Some(pos) = mouse_pos_screen {
                 app.zoom_window_start = Some(pos);
                 app.zoom_window_dragging = true;
+                // Shift or the armed DRC region tool turn the drag into a region for a local DRC run
+                app.drc_region_drag = app.drc_region_armed || ui.input(|i| i.modifiers.shift);
             }
         }
     }
@@ -606,7 +1056,17 @@ fn handle_zoom_window(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect,
         if let (Some(start), Some(end)) = (app.zoom_window_start, ui.input(|i| i.pointer.hover_pos())) {
             let zoom_rect = Rect::from_two_pos(start, end);
             
-            if zoom_rect.width() > 10.0 && zoom_rect.height() > 10.0 {
+            if app.drc_region_drag {
+                if zoom_rect.width() > 10.0 && zoom_rect.height() > 10.0 {
+                    let region = crate::drc_operations::DrcRegion::from_corners(
+                        app.view_state.screen_to_gerber_coords(zoom_rect.min),
+                        app.view_state.screen_to_gerber_coords(zoom_rect.max),
+                    );
+                    app.drc_region_armed = false;
+                    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+                    ui::drc_panel::run_regional_drc(app, &logger, region);
+                }
+            } else if zoom_rect.width() > 10.0 && zoom_rect.height() > 10.0 {
                 let gerber_start = app.view_state.screen_to_gerber_coords(zoom_rect.min);
                 let gerber_end = app.view_state.screen_to_gerber_coords(zoom_rect.max);
                 
@@ -634,12 +1094,14 @@ fn handle_zoom_window(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect,
         
         app.zoom_window_dragging = false;
         app.zoom_window_start = None;
+        app.drc_region_drag = false;
     }
     
     // Cancel zoom window on escape
     if app.zoom_window_dragging && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
         app.zoom_window_dragging = false;
         app.zoom_window_start = None;
+        app.drc_region_drag = false;
     }
 }
 
@@ -648,7 +1110,12 @@ fn handle_mouse_wheel_zoom(ui: &mut egui::Ui, app: &mut DemoLensApp, _viewport:
         return;
     }
     
-    let scroll_delta = ui.input(|i| i.raw_scroll_delta.y);
+    // With gestures on, trackpad scrolling pans and pinch zooms, so only wheel notches zoom here
+    let scroll_delta = if app.touch_gestures_enabled {
+        ui.input(|i| ui::gestures::split_scroll_input(&i.events).0.y)
+    } else {
+        ui.input(|i| i.raw_scroll_delta.y)
+    };
     if scroll_delta == 0.0 {
         return;
     }
@@ -663,40 +1130,31 @@ fn handle_mouse_wheel_zoom(ui: &mut egui::Ui, app: &mut DemoLensApp, _viewport:
             1.0 / 1.1 // Zoom out
         };
         
-        // Get current scale and calculate new scale
-        let old_scale = app.view_state.scale;
-        let new_scale = (old_scale * zoom_factor).clamp(0.01, 100.0);
-        
-        // Calculate the gerber coordinates at the mouse position before scaling
-        let gerber_point = app.view_state.screen_to_gerber_coords(mouse_pos);
-        
-        // Update the scale
-        app.view_state.scale = new_scale;
-        
-        // Calculate the new screen position of the same gerber point
-        let new_screen_pos = app.view_state.gerber_to_screen_coords(gerber_point);
-        
-        // Adjust translation to keep the mouse cursor over the same gerber point
-        let translation_adjustment = mouse_pos - new_screen_pos;
-        app.view_state.translation += translation_adjustment;
-        
-        // Sync with ECS zoom resource
-        app.sync_zoom_to_ecs();
+        // Keep the mouse cursor over the same gerber point
+        ui::gestures::zoom_around_point(app, mouse_pos, zoom_factor);
     }
 }
 
 fn render_gerber_content(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Rect) {
     let painter = ui.painter_at(*viewport);
     painter.rect_filled(*viewport, 0.0, ui.visuals().extreme_bg_color);
+    app.view_clipboard.viewport = Some(*viewport);
+    app.view_clipboard.background = ui.visuals().extreme_bg_color;
     
     if app.needs_initial_view {
         app.reset_view(*viewport);
     }
     
+    // Pan to a violation clicked in the DRC panel before anything is drawn
+    let now = ui.input(|i| i.time);
+    ui::drc_violations::focus_pending_violation(app, viewport, now);
+    ui::assembly_checks::focus_pending_thermal_pad(app, viewport);
+    ui::annotations::focus_pending_annotation(app, viewport);
+    
     let painter = ui.painter().with_clip_rect(*viewport);
     
     // Draw grid
-    crate::display::draw_grid(&painter, viewport, &app.view_state, &app.grid_settings);
+    crate::display::draw_grid(&painter, viewport, &app.view_state, &app.grid_settings, app.display_manager.design_origin());
     
     // Draw quadrant axes
     if app.display_manager.quadrant_view_enabled {
@@ -707,13 +1165,59 @@ fn render_gerber_content(ui: &mut egui::Ui, app: &mut DemoLensApp, viewport: &Re
     draw_crosshair(&painter, app.ui_state.origin_screen_pos, Color32::BLUE);
     
     // Render layers using ECS system (gerber-viewer 0.2.0 compatible)
+    // Revision B is drawn before or after the current layers depending on the compare swap
+    if !app.revision_compare.b_on_top {
+        ui::revision_compare::render_revision_overlay(app, &painter);
+    }
     app.render_layers_ecs(&painter);
+    if app.revision_compare.b_on_top {
+        ui::revision_compare::render_revision_overlay(app, &painter);
+    }
+    
+    // Fade the newly shown side in after a flip
+    if let Some(veil) = app.view_animation.flip_veil() {
+        painter.rect_filled(*viewport, 0.0, ui.visuals().extreme_bg_color.gamma_multiply(veil));
+    }
+    
+    render_swipe_divider(app, &painter, viewport);
     
     // Render overlays
     render_overlays(app, &painter, viewport);
+    ui::drc_violations::draw_violation_pulse(app, &painter, now);
+    ui::revision_compare::show_raster_diff_window(ui.ctx(), app);
+    ui::orientation_panel::show_png_export_dialog(ui.ctx(), app);
+    
+    // Board overview, drawn after the layers and overlays so it sits above them
+    ui::minimap::show_minimap(ui, app, viewport);
+    ui::dro::show_dro(ui, app, viewport);
     
     // Render cursor info
     render_cursor_info(ui, app, &painter, viewport);
+    render_stats_overlay(ui, app, &painter, viewport);
+}
+
+/// Frame time and render cache statistics in the top left corner, when enabled in the settings
+fn render_stats_overlay(ui: &egui::Ui, app: &DemoLensApp, painter: &Painter, viewport: &Rect) {
+    let cache = app.ecs_world.resource::<crate::ecs::LayerRenderCache>();
+    if !cache.show_overlay {
+        return;
+    }
+    let stats = cache.stats;
+    let frame_ms = ui.input(|i| i.unstable_dt) * 1000.0;
+    let text = format!(
+        "frame {:.1} ms ({:.0} fps)\nlayers {:.2} ms\nmeshes emitted {}\ncache hits {}/{} ({} rescaled)",
+        frame_ms,
+        1000.0 / frame_ms.max(0.001),
+        stats.render_ms,
+        stats.meshes_emitted,
+        stats.cache_hits,
+        stats.layers_drawn,
+        stats.rescaled
+    );
+    let galley = painter.layout_no_wrap(text, egui::FontId::monospace(11.0), Color32::WHITE);
+    let pos = viewport.left_top() + Vec2::new(8.0, 8.0);
+    painter.rect_filled(Rect::from_min_size(pos, galley.size()).expand(4.0), 3.0, Color32::from_black_alpha(160));
+    painter.galley(pos, galley, Color32::WHITE);
 }
 
 
@@ -741,9 +1245,21 @@ fn render_overlays(app: &mut DemoLensApp, painter: &Painter, viewport: &Rect) {
     // Corner overlay shapes
     render_corner_overlays(app, painter);
     
+    // Drill size symbols
+    ui::drill_report::render_drill_symbols(app, painter);
+    
+    // Selected primitive, under the DRC markers
+    ui::primitive_inspect::draw_selection(app, painter);
+    
     // DRC violations
     render_drc_violations(app, painter);
     
+    // Picked probe points
+    ui::point_picker::render_picked_points(app, painter);
+    
+    // Review annotations
+    ui::annotations::render_annotations(app, painter);
+    
     // Board dimensions
     render_board_dimensions(app, painter, viewport);
     
@@ -753,45 +1269,31 @@ fn render_overlays(app: &mut DemoLensApp, painter: &Painter, viewport: &Rect) {
     // Custom measurement crosshair
     render_measurement_crosshair(app, painter);
     
+    // Region of the last regional DRC run
+    render_drc_region(app, painter);
+    
     // Zoom window
     render_zoom_window(app, painter);
 }
 
+/// Dashed outline of the regional DRC area, until the region is cleared
+fn render_drc_region(app: &mut DemoLensApp, painter: &Painter) {
+    let Some(region) = app.drc_manager.region else {
+        return;
+    };
+    let mut points: Vec<Pos2> = region.corners().iter().map(|corner| app.view_state.gerber_to_screen_coords(*corner)).collect();
+    points.push(points[0]);
+    painter.add(egui::Shape::dashed_line(&points, Stroke::new(1.5, DRC_REGION_COLOR), 8.0, 5.0));
+}
+
 fn render_corner_overlays(app: &mut DemoLensApp, painter: &Painter) {
     if !app.drc_manager.corner_overlay_shapes.is_empty() {
         let overlay_color = Color32::from_rgb(0, 255, 0);
         
         for shape in &app.drc_manager.corner_overlay_shapes {
-            let mut transformed_vertices = Vec::new();
-            
-            for point in &shape.points {
-                let mut vertex_pos = *point;
-                
-                // Apply rotation
-                if app.rotation_degrees != 0.0 {
-                    let rotation_radians = app.rotation_degrees.to_radians();
-                    let (sin_theta, cos_theta) = (rotation_radians.sin(), rotation_radians.cos());
-                    
-                    let rotated_x = vertex_pos.x * cos_theta as f64 - vertex_pos.y * sin_theta as f64;
-                    let rotated_y = vertex_pos.x * sin_theta as f64 + vertex_pos.y * cos_theta as f64;
-                    vertex_pos = Position { x: rotated_x, y: rotated_y };
-                }
-                
-                // Apply mirroring
-                if app.display_manager.mirroring.x {
-                    vertex_pos = vertex_pos.invert_x();
-                }
-                if app.display_manager.mirroring.y {
-                    vertex_pos = vertex_pos.invert_y();
-                }
-                
-                // Apply offsets
-                let origin = Vector2::from(app.display_manager.center_offset.clone()) - Vector2::from(app.display_manager.design_offset.clone());
-                vertex_pos = vertex_pos + origin.to_position();
-                
-                let vertex_screen = app.view_state.gerber_to_screen_coords(vertex_pos.to_point2());
-                transformed_vertices.push(vertex_screen);
-            }
+            let transformed_vertices: Vec<Pos2> = shape.points.iter()
+                .map(|point| board_screen_pos(app, point.x, point.y))
+                .collect();
             
             if transformed_vertices.len() >= 3 {
                 painter.add(egui::Shape::convex_polygon(
@@ -804,39 +1306,65 @@ fn render_corner_overlays(app: &mut DemoLensApp, painter: &Painter) {
     }
 }
 
+/// Screen position of a violation's marker in the current view
+pub(crate) fn violation_screen_pos(app: &DemoLensApp, violation: &crate::drc_operations::types::DrcViolation) -> Pos2 {
+    board_screen_pos(app, violation.x as f64, violation.y as f64)
+}
+
+/// Screen position of a point in gerber coordinates, through the same view transform as the layers
+pub(crate) fn board_screen_pos(app: &DemoLensApp, x: f64, y: f64) -> Pos2 {
+    let transform = crate::display::build_layer_transform(&app.display_manager, app.displayed_rotation_degrees(), None);
+    app.view_state.gerber_to_screen_coords(transform.apply_to_position(nalgebra::Point2::new(x, y)))
+}
+
 fn render_drc_violations(app: &mut DemoLensApp, painter: &Painter) {
-    for violation in &app.drc_manager.violations {
-        let violation_pos = Position::new(violation.x as f64, violation.y as f64);
-        let mut transformed_pos = violation_pos;
-        
-        // Apply rotation
-        if app.rotation_degrees != 0.0 {
-            let rotation_radians = app.rotation_degrees.to_radians();
-            let (sin_theta, cos_theta) = (rotation_radians.sin(), rotation_radians.cos());
-            let rotated_x = transformed_pos.x * cos_theta as f64 - transformed_pos.y * sin_theta as f64;
-            let rotated_y = transformed_pos.x * sin_theta as f64 + transformed_pos.y * cos_theta as f64;
-            transformed_pos = Position::new(rotated_x, rotated_y);
-        }
-        
-        // Apply mirroring
-        if app.display_manager.mirroring.x {
-            transformed_pos = transformed_pos.invert_x();
+    let marker_settings = app.drc_marker_settings;
+    
+    // Via tenting rings sized to the drill, under everything else
+    if app.assembly_checks.show_via_tenting {
+        for via in &app.assembly_checks.vias {
+            let screen_pos = board_screen_pos(app, via.position.x, via.position.y);
+            let radius = (via.drill as f32 / 2.0 * app.view_state.scale).max(3.0);
+            let color = crate::ui::assembly_checks::tenting_color(via.tenting);
+            painter.circle_stroke(screen_pos, radius, Stroke::new(2.0, color));
         }
-        if app.display_manager.mirroring.y {
-            transformed_pos = transformed_pos.invert_y();
+    }
+    
+    // Thermal relief candidates from the assembly checks, under everything else (T1, T2, ...)
+    for (i, pad) in app.assembly_checks.thermal_pads.iter().enumerate() {
+        let screen_pos = board_screen_pos(app, pad.position.x, pad.position.y);
+        let marker_size = marker_settings.marker_size(3.0 * app.view_state.scale.max(0.5));
+        draw_violation_marker(painter, screen_pos, marker_size, THERMAL_WARNING_COLOR, MarkerShape::Square, marker_settings.high_contrast);
+        if marker_settings.show_labels {
+            draw_marker_label(painter, screen_pos, marker_size, &format!("T{}", i + 1));
         }
-        
-        // Apply offsets
-        let origin = Vector2::from(app.display_manager.center_offset.clone()) - Vector2::from(app.display_manager.design_offset.clone());
-        transformed_pos = transformed_pos + origin.to_position();
-        
-        let screen_pos = app.view_state.gerber_to_screen_coords(transformed_pos.to_point2());
+    }
+    
+    // Mask warnings first so hard violations at the same spot stay on top
+    // Labels match the order of the mask warning list (M1, M2, ...) and the DRC log (1, 2, ...)
+    // Violations the user ignored keep their number but get no marker
+    let mask_warnings = app.drc_manager.mask_warnings.iter().enumerate().map(|(i, v)| (format!("M{}", i + 1), v));
+    let violations = app.drc_manager.active_violations().map(|(i, v)| ((i + 1).to_string(), v));
+    for (label, violation) in mask_warnings.chain(violations) {
+        let screen_pos = violation_screen_pos(app, violation);
         
         let base_size = 3.0;
-        let marker_size = base_size * app.view_state.scale.max(0.5);
-        let color = Color32::RED;
+        let marker_size = marker_settings.marker_size(base_size * app.view_state.scale.max(0.5));
+        
+        let (default_shape, color) = if crate::drc_operations::drill::is_drill_rule(&violation.rule_name) {
+            // Ring around the hole center so the hole itself stays visible
+            (MarkerShape::Ring, DRILL_VIOLATION_COLOR)
+        } else if crate::drc_operations::mask::is_mask_rule(&violation.rule_name) {
+            (MarkerShape::Cross, MASK_WARNING_COLOR)
+        } else {
+            (MarkerShape::Cross, Color32::RED)
+        };
+        let shape = if marker_settings.high_contrast { MarkerShape::for_rule(&violation.rule_name) } else { default_shape };
         
-        draw_violation_marker(painter, screen_pos, marker_size, color);
+        draw_violation_marker(painter, screen_pos, marker_size, color, shape, marker_settings.high_contrast);
+        if marker_settings.show_labels {
+            draw_marker_label(painter, screen_pos, marker_size, &label);
+        }
     }
 }
 
@@ -846,16 +1374,8 @@ fn render_board_dimensions(app: &mut DemoLensApp, painter: &Painter, viewport: &
         let width_mm = bbox.width();
         let height_mm = bbox.height();
         
-        let units_resource = Tab::get_units(app);
-        let dimension_text = if units_resource.is_mils() {
-            let width_nm = mm_to_nm(width_mm as f32);
-            let height_nm = mm_to_nm(height_mm as f32);
-            let width_mils = nm_to_mils(width_nm);
-            let height_mils = nm_to_mils(height_nm);
-            format!("{:.0} x {:.0} mils", width_mils, height_mils)
-        } else {
-            format!("{:.1} x {:.1} mm", width_mm, height_mm)
-        };
+        let unit = Tab::get_units(app).display_unit;
+        let dimension_text = format!("{} x {}", unit.format_number(width_mm, 1), unit.format_mm(height_mm, 1));
         
         let text_pos = viewport.max - Vec2::new(10.0, 50.0);
         painter.text(
@@ -872,16 +1392,17 @@ fn render_zoom_window(app: &mut DemoLensApp, painter: &Painter) {
     if app.zoom_window_dragging {
         if let (Some(start), Some(current)) = (app.zoom_window_start, painter.ctx().input(|i| i.pointer.hover_pos())) {
             let zoom_rect = Rect::from_two_pos(start, current);
+            let color = if app.drc_region_drag { DRC_REGION_COLOR } else { Color32::from_rgb(100, 150, 255) };
             
             // Draw semi-transparent fill
             painter.rect_filled(
                 zoom_rect,
                 0.0,
-                Color32::from_rgba_unmultiplied(100, 150, 255, 50)
+                color.gamma_multiply(0.2)
             );
             
             // Draw border
-            let stroke = Stroke::new(2.0, Color32::from_rgb(100, 150, 255));
+            let stroke = Stroke::new(2.0, color);
             painter.line_segment([zoom_rect.min, Pos2::new(zoom_rect.max.x, zoom_rect.min.y)], stroke);
             painter.line_segment([Pos2::new(zoom_rect.max.x, zoom_rect.min.y), zoom_rect.max], stroke);
             painter.line_segment([zoom_rect.max, Pos2::new(zoom_rect.min.x, zoom_rect.max.y)], stroke);
@@ -895,7 +1416,7 @@ fn render_zoom_window(app: &mut DemoLensApp, painter: &Painter) {
                           Pos2::new(zoom_rect.min.x, zoom_rect.max.y)];
             
             for corner in &corners {
-                painter.circle_filled(*corner, corner_size, Color32::from_rgb(100, 150, 255));
+                painter.circle_filled(*corner, corner_size, color);
             }
         }
     }
@@ -910,9 +1431,23 @@ fn render_ruler(app: &mut DemoLensApp, painter: &Painter) {
     else if app.latched_measurement_start.is_some() && app.latched_measurement_end.is_some() {
         render_ruler_measurement(app, painter, app.latched_measurement_start, app.latched_measurement_end, false);
     }
+    
+    // Measurement picked from the history, drawn like a latched one
+    if !app.ruler_edge_mode {
+        if let Some((start, end)) = ui::measurement_history::recalled_measurement(app) {
+            render_ruler_measurement(app, painter, Some(start), Some(end), false);
+        }
+    }
 }
 
 fn render_ruler_measurement(app: &mut DemoLensApp, painter: &Painter, start_opt: Option<nalgebra::Point2<f64>>, end_opt: Option<nalgebra::Point2<f64>>, is_active: bool) {
+    if app.ruler_edge_mode {
+        if let (Some(start), Some(end)) = (start_opt, end_opt) {
+            render_edge_measurement(app, painter, start, end, is_active);
+            return;
+        }
+    }
+    
     // Draw ruler points and line
     if let Some(start) = start_opt {
         let start_screen = app.view_state.gerber_to_screen_coords(start);
@@ -946,25 +1481,13 @@ fn render_ruler_measurement(app: &mut DemoLensApp, painter: &Painter, start_opt:
             let distance = (dx * dx + dy * dy).sqrt();
             
             // Create measurement text with dx/dy display
-            let units_resource = Tab::get_units(app);
-            let measurement_text = if units_resource.is_mils() {
-                let distance_nm = mm_to_nm(distance as f32);
-                let dx_nm = mm_to_nm(dx.abs() as f32);
-                let dy_nm = mm_to_nm(dy.abs() as f32);
-                format!(
-                    "{:.2} mils\nΔX: {:.2}\nΔY: {:.2}",
-                    nm_to_mils(distance_nm),
-                    nm_to_mils(dx_nm),
-                    nm_to_mils(dy_nm)
-                )
-            } else {
-                format!(
-                    "{:.3} mm\nΔX: {:.3}\nΔY: {:.3}",
-                    distance,
-                    dx,
-                    dy
-                )
-            };
+            let unit = Tab::get_units(app).display_unit;
+            let measurement_text = format!(
+                "{}\nΔX: {}\nΔY: {}",
+                unit.format_mm(distance, 3),
+                unit.format_number(dx, 3),
+                unit.format_number(dy, 3)
+            );
             
             // Position text near the end point (offset to avoid overlap)
             let text_offset = Vec2::new(20.0, -45.0);
@@ -1003,21 +1526,17 @@ fn handle_ruler_interaction(ui: &mut egui::Ui, app: &mut DemoLensApp, response:
     if !app.ruler_active {
         return;
     }
+    if app.ruler_edge_mode {
+        handle_edge_ruler_interaction(ui, app, response);
+        return;
+    }
     
     let mouse_pos = ui.input(|i| i.pointer.hover_pos());
     
     // In ruler mode, left-click to set measurement points
     if response.clicked() {
         if let Some(mouse_screen_pos) = mouse_pos {
-            let gerber_coords = app.view_state.screen_to_gerber_coords(mouse_screen_pos);
-            
-            // Apply snap to grid if enabled
-            let final_coords = if app.grid_settings.snap_enabled {
-                let point = nalgebra::Point2::new(gerber_coords.x, gerber_coords.y);
-                crate::display::snap_to_grid(point, &app.grid_settings)
-            } else {
-                nalgebra::Point2::new(gerber_coords.x, gerber_coords.y)
-            };
+            let (final_coords, _) = snapped_pick_point(app, mouse_screen_pos);
             
             if app.ruler_start.is_none() {
                 // First click - set start point
@@ -1028,6 +1547,9 @@ fn handle_ruler_interaction(ui: &mut egui::Ui, app: &mut DemoLensApp, response:
                 // Second click - set end point and complete measurement
                 app.ruler_end = Some(final_coords);
                 app.ruler_dragging = false;
+                if let Some(start) = app.ruler_start {
+                    ui::measurement_history::record_measurement(app, start, final_coords);
+                }
             } else {
                 // Third click - start new measurement
                 app.ruler_start = Some(final_coords);
@@ -1039,25 +1561,136 @@ fn handle_ruler_interaction(ui: &mut egui::Ui, app: &mut DemoLensApp, response:
     
     // Show live preview when dragging (after first click, before second click)
     if app.ruler_dragging && app.ruler_start.is_some() && mouse_pos.is_some() {
-        let mouse_screen_pos = mouse_pos.unwrap();
-        let gerber_coords = app.view_state.screen_to_gerber_coords(mouse_screen_pos);
-        
-        // Apply snap to grid if enabled
-        let final_coords = if app.grid_settings.snap_enabled {
-            let point = nalgebra::Point2::new(gerber_coords.x, gerber_coords.y);
-            crate::display::snap_to_grid(point, &app.grid_settings)
-        } else {
-            nalgebra::Point2::new(gerber_coords.x, gerber_coords.y)
-        };
+        let (final_coords, _) = snapped_pick_point(app, mouse_pos.unwrap());
         
         // Update live preview end point
         app.ruler_end = Some(final_coords);
     }
 }
 
+/// Highlight the measured stretch of outline, with the chord dashed and the lengths beside the end point
+fn render_edge_measurement(app: &mut DemoLensApp, painter: &Painter, start: nalgebra::Point2<f64>, end: nalgebra::Point2<f64>, is_active: bool) {
+    let Some(paths) = crate::ecs::get_outline_paths(&mut app.ecs_world, &app.display_manager) else {
+        return;
+    };
+    let Some(measurement) = paths.measure(start, end) else {
+        return;
+    };
+    let (path_color, point_color, text_color) = if is_active {
+        (EDGE_PATH_COLOR, Color32::RED, Color32::WHITE)
+    } else {
+        (EDGE_PATH_COLOR.gamma_multiply(0.5), Color32::GRAY, Color32::LIGHT_GRAY)
+    };
+    
+    let path: Vec<Pos2> = paths.measured_path(&measurement, app.ruler_edge_flip)
+        .into_iter()
+        .map(|point| app.view_state.gerber_to_screen_coords(point))
+        .collect();
+    painter.add(egui::Shape::line(path, Stroke::new(4.0, path_color)));
+    
+    let start_screen = app.view_state.gerber_to_screen_coords(measurement.start.point);
+    let end_screen = app.view_state.gerber_to_screen_coords(measurement.end.point);
+    painter.add(egui::Shape::dashed_line(&[start_screen, end_screen], Stroke::new(1.0, text_color), 6.0, 4.0));
+    for point in [start_screen, end_screen] {
+        painter.circle_filled(point, 4.0, point_color);
+        painter.circle_stroke(point, 6.0, Stroke::new(2.0, path_color));
+    }
+    
+    let text = edge_measurement_lines(app, &measurement).join("\n");
+    let text_pos = end_screen + Vec2::new(20.0, -45.0);
+    let galley = painter.layout_no_wrap(text, egui::FontId::monospace(16.0), text_color);
+    if is_active {
+        let background_rect = egui::Rect::from_min_size(text_pos - Vec2::new(6.0, 6.0), galley.size() + Vec2::new(12.0, 12.0));
+        painter.rect_filled(background_rect, 6.0, Color32::from_rgba_unmultiplied(0, 0, 0, 240));
+    }
+    painter.galley(text_pos, galley, text_color);
+}
+
+/// Outline point a click at `screen_pos` snaps to in edge measure mode
+/// While the second point is being placed only the loop of the first one counts
+fn edge_pick_point(app: &mut DemoLensApp, screen_pos: Pos2) -> Option<crate::ecs::EdgePoint> {
+    let paths = crate::ecs::get_outline_paths(&mut app.ecs_world, &app.display_manager)?;
+    let target = app.view_state.screen_to_gerber_coords(screen_pos);
+    let max_distance = (EDGE_SNAP_RADIUS_PX / app.view_state.scale) as f64;
+    let loop_index = app.ruler_start
+        .filter(|_| app.ruler_dragging)
+        .and_then(|start| paths.nearest(start, f64::INFINITY, None))
+        .map(|start| start.loop_index);
+    paths.nearest(target, max_distance, loop_index)
+}
+
+fn handle_edge_ruler_interaction(ui: &mut egui::Ui, app: &mut DemoLensApp, response: &egui::Response) {
+    let Some(mouse_pos) = ui.input(|i| i.pointer.hover_pos()) else {
+        return;
+    };
+    
+    // Clicks away from the outline are ignored
+    if response.clicked() {
+        if let Some(edge_point) = edge_pick_point(app, mouse_pos) {
+            if app.ruler_dragging && app.ruler_start.is_some() {
+                app.ruler_end = Some(edge_point.point);
+                app.ruler_dragging = false;
+            } else {
+                app.ruler_start = Some(edge_point.point);
+                app.ruler_end = None;
+                app.ruler_dragging = true;
+            }
+            return;
+        }
+    }
+    
+    // Live preview follows the cursor round the loop of the first point
+    if app.ruler_dragging {
+        if let Some(start) = app.ruler_start {
+            let target = app.view_state.screen_to_gerber_coords(mouse_pos);
+            if let Some(paths) = crate::ecs::get_outline_paths(&mut app.ecs_world, &app.display_manager) {
+                if let Some(measurement) = paths.measure(start, target) {
+                    app.ruler_end = Some(measurement.end.point);
+                }
+            }
+        }
+    }
+}
+
+/// View position a ruler or origin click at `screen_pos` resolves to
+/// Object snap wins over grid snap; the snap target is returned when one was hit
+fn snapped_pick_point(app: &mut DemoLensApp, screen_pos: Pos2) -> (nalgebra::Point2<f64>, Option<crate::ecs::SnapPoint>) {
+    if app.grid_settings.object_snap_enabled {
+        let snap = crate::ecs::pick_snap_point_at_screen_pos(
+            &mut app.ecs_world,
+            &app.view_state,
+            &app.display_manager,
+            screen_pos,
+            OBJECT_SNAP_RADIUS_PX,
+        );
+        if let Some(snap) = snap {
+            return (snap.position, Some(snap));
+        }
+    }
+    
+    let gerber_coords = app.view_state.screen_to_gerber_coords(screen_pos);
+    let point = nalgebra::Point2::new(gerber_coords.x, gerber_coords.y);
+    (crate::display::snap_to_grid(point, &app.grid_settings, app.display_manager.design_origin()), None)
+}
+
+/// Circle for an endpoint, cross for a pad or hole center
+fn draw_snap_indicator(painter: &Painter, center: Pos2, kind: crate::ecs::SnapKind) {
+    let stroke = Stroke::new(1.5, SNAP_INDICATOR_COLOR);
+    match kind {
+        crate::ecs::SnapKind::Endpoint => {
+            painter.circle_stroke(center, 6.0, stroke);
+        }
+        crate::ecs::SnapKind::Center => {
+            let size = 6.0;
+            painter.line_segment([center + Vec2::new(-size, -size), center + Vec2::new(size, size)], stroke);
+            painter.line_segment([center + Vec2::new(-size, size), center + Vec2::new(size, -size)], stroke);
+        }
+    }
+}
+
 fn render_cursor_info(ui: &mut egui::Ui, app: &mut DemoLensApp, painter: &Painter, viewport: &Rect) {
-    // Hide cursor coordinates when ruler mode is active
-    if app.ruler_active {
+    // Hide cursor coordinates when ruler mode is active or the user turned them off
+    if app.ruler_active || !app.dro_settings.follow_cursor {
         return;
     }
     
@@ -1074,16 +1707,13 @@ fn render_cursor_info(ui: &mut egui::Ui, app: &mut DemoLensApp, painter: &Painte
                 gerber_pos.y - app.display_manager.design_offset.y
             );
             
-            let units_resource = Tab::get_units(app);
-            let cursor_text = if units_resource.is_mils() {
-                let x_nm = mm_to_nm(adjusted_pos.x as f32);
-                let y_nm = mm_to_nm(adjusted_pos.y as f32);
-                let x_mils = nm_to_mils(x_nm);
-                let y_mils = nm_to_mils(y_nm);
-                format!("({:.0}, {:.0}) mils", x_mils, y_mils)
-            } else {
-                format!("({:.2}, {:.2}) mm", adjusted_pos.x, adjusted_pos.y)
-            };
+            let unit = Tab::get_units(app).display_unit;
+            let cursor_text = format!(
+                "({}, {}) {}",
+                unit.format_number(adjusted_pos.x, 2),
+                unit.format_number(adjusted_pos.y, 2),
+                unit.suffix()
+            );
             
             
             let text_offset = Vec2::new(15.0, -15.0);
@@ -1140,7 +1770,7 @@ fn render_cursor_info(ui: &mut egui::Ui, app: &mut DemoLensApp, painter: &Painte
     // Unit display
     let unit_toggle_pos = viewport.max - Vec2::new(10.0, 30.0);
     let units_resource = Tab::get_units(app);
-    let unit_text = if units_resource.is_mils() { "mils" } else { "mm" };
+    let unit_text = units_resource.display_unit.suffix();
     painter.text(
         unit_toggle_pos,
         egui::Align2::RIGHT_BOTTOM,
@@ -1252,23 +1882,6 @@ fn render_zoom_display(ui: &mut egui::Ui, app: &mut DemoLensApp) {
         ));
 }
 
-/// Draw a red X marker for DRC violations
-fn draw_violation_marker(painter: &Painter, center: Pos2, size: f32, color: Color32) {
-    let half_size = size / 2.0;
-    let stroke = Stroke::new(2.0, color);
-    
-    // Draw X shape
-    painter.line_segment([
-        Pos2::new(center.x - half_size, center.y - half_size),
-        Pos2::new(center.x + half_size, center.y + half_size)
-    ], stroke);
-    
-    painter.line_segment([
-        Pos2::new(center.x - half_size, center.y + half_size),
-        Pos2::new(center.x + half_size, center.y - half_size)
-    ], stroke);
-}
-
 /// Draw quadrant axes when quadrant view is enabled
 fn draw_quadrant_axes(painter: &Painter, viewport: &Rect, _view_state: &ViewState, center_screen_pos: Pos2) {
     let stroke = Stroke::new(2.0, Color32::from_rgba_unmultiplied(100, 100, 100, 150));
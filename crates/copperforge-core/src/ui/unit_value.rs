@@ -0,0 +1,117 @@
+//! Numeric entry for lengths stored in mm and shown in the user's units
+//!
+//! Values are converted for display and rounded to the entry resolution, and
+//! written back only when the user edits them. Switching units therefore never
+//! touches the stored value, and f32 storage error (6 mil reading back as
+//! 5.99999) is rounded away before it reaches the screen.
+
+use std::ops::RangeInclusive;
+
+use bevy_ecs::world::World;
+use egui::emath::Numeric;
+
+use crate::ecs::{DisplayUnit, UnitsResource};
+
+/// Display unit of the app (mm if the resource is missing)
+pub fn display_unit(world: &World) -> DisplayUnit {
+    world.get_resource::<UnitsResource>()
+        .map(|units| units.display_unit)
+        .unwrap_or(DisplayUnit::Millimeters)
+}
+
+/// Resolution lengths are entered and kept at, as decimals in mm (0.1 µm); other
+/// units get the matching number of decimals from `DisplayUnit::decimals_for`
+pub const ENTRY_MM_DECIMALS: usize = 4;
+
+fn default_speed(unit: DisplayUnit) -> f64 {
+    match unit {
+        DisplayUnit::Millimeters => 0.01,
+        DisplayUnit::Mils => 0.1,
+        DisplayUnit::Inches => 0.0001,
+        DisplayUnit::Micrometers => 1.0,
+        DisplayUnit::Nanometers => 100.0,
+    }
+}
+
+/// mm in display units, rounded to the entry resolution
+pub fn mm_to_display(mm: f64, unit: DisplayUnit) -> f64 {
+    let scale = 10f64.powi(unit.decimals_for(ENTRY_MM_DECIMALS) as i32);
+    (unit.from_mm(mm) * scale).round() / scale
+}
+
+pub fn display_to_mm(value: f64, unit: DisplayUnit) -> f64 {
+    unit.to_mm(value)
+}
+
+/// DragValue over a length stored in mm; range and speed are given in mm too
+pub struct UnitValue<'a, T: Numeric> {
+    value_mm: &'a mut T,
+    unit: DisplayUnit,
+    range_mm: RangeInclusive<f64>,
+    speed_mm: Option<f64>,
+}
+
+impl<'a, T: Numeric> UnitValue<'a, T> {
+    pub fn new(value_mm: &'a mut T, unit: DisplayUnit) -> Self {
+        Self {
+            value_mm,
+            unit,
+            range_mm: f64::NEG_INFINITY..=f64::INFINITY,
+            speed_mm: None,
+        }
+    }
+
+    pub fn range_mm(mut self, range_mm: RangeInclusive<f64>) -> Self {
+        self.range_mm = range_mm;
+        self
+    }
+
+    /// Change per dragged pixel; defaults to a step suited to the unit
+    pub fn speed_mm(mut self, speed_mm: f64) -> Self {
+        self.speed_mm = Some(speed_mm);
+        self
+    }
+}
+
+impl<T: Numeric> egui::Widget for UnitValue<'_, T> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let unit = self.unit;
+        let mut value = mm_to_display(self.value_mm.to_f64(), unit);
+        let speed = self.speed_mm.map_or(default_speed(unit), |speed_mm| speed_mm / unit.mm_per_unit());
+        let range = mm_to_display(*self.range_mm.start(), unit)..=mm_to_display(*self.range_mm.end(), unit);
+
+        let response = ui.add(egui::DragValue::new(&mut value)
+            .speed(speed)
+            .range(range)
+            .max_decimals(unit.decimals_for(ENTRY_MM_DECIMALS))
+            .suffix(format!(" {}", unit.suffix())));
+        if response.changed() {
+            *self.value_mm = T::from_f64(display_to_mm(value, unit));
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mil_values_survive_f32_storage_and_unit_toggles() {
+        // Entering 6 mil stores 0.1524 mm as f32
+        let stored = display_to_mm(6.0, DisplayUnit::Mils) as f32;
+        assert_eq!(mm_to_display(stored as f64, DisplayUnit::Mils), 6.0);
+        assert_eq!(mm_to_display(stored as f64, DisplayUnit::Millimeters), 0.1524);
+        assert_eq!(DisplayUnit::Mils.format_mm(stored as f64, 3), "6.00 mils");
+
+        // Re-entering the mm reading and switching back still gives 6 mil
+        let stored = display_to_mm(mm_to_display(stored as f64, DisplayUnit::Millimeters), DisplayUnit::Millimeters) as f32;
+        assert_eq!(mm_to_display(stored as f64, DisplayUnit::Mils), 6.0);
+
+        for half_mils in 1..=2000 {
+            let mils = half_mils as f64 / 2.0;
+            let stored = display_to_mm(mils, DisplayUnit::Mils) as f32;
+            assert_eq!(mm_to_display(stored as f64, DisplayUnit::Mils), mils);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use egui::{Color32, RichText, Sense};
+
+use crate::DemoLensApp;
+use crate::display::realistic::SUBSTRATE_COLOR;
+use crate::display::view3d::{self, Mesh3d, OrbitCamera};
+use crate::ecs::{LayerContentHash, LayerType, RenderProperties, Stackup, Visibility};
+
+const BACKGROUND_COLOR: Color32 = Color32::from_rgb(24, 26, 30);
+
+/// Mesh of one layer with what it was built from
+struct CachedMesh {
+    content_hash: u64,
+    z: f64,
+    mesh: Mesh3d,
+}
+
+/// Camera and per-layer meshes of the 3D tab
+/// Meshes are rebuilt only for layers whose content or height changed; hidden
+/// layers keep their mesh and are just not drawn
+pub struct View3dState {
+    pub camera: OrbitCamera,
+    layers: HashMap<LayerType, CachedMesh>,
+    board: Option<(u64, Stackup, Mesh3d)>,
+    needs_fit: bool,
+}
+
+impl Default for View3dState {
+    fn default() -> Self {
+        Self {
+            camera: OrbitCamera::default(),
+            layers: HashMap::new(),
+            board: None,
+            needs_fit: true,
+        }
+    }
+}
+
+impl View3dState {
+    /// Drop every mesh so the next frame regenerates them
+    pub fn rebuild(&mut self) {
+        self.layers.clear();
+        self.board = None;
+        self.needs_fit = true;
+    }
+
+    fn triangle_count(&self) -> usize {
+        self.layers.values().map(|cached| cached.mesh.triangles.len()).sum::<usize>()
+            + self.board.as_ref().map_or(0, |(_, _, mesh)| mesh.triangles.len())
+    }
+}
+
+/// Loaded layers: type, visible, color, content hash
+fn loaded_layers(world: &mut bevy_ecs::world::World) -> Vec<(LayerType, bool, Color32, u64)> {
+    let mut query = world.query::<(&crate::ecs::LayerInfo, &Visibility, &RenderProperties, &LayerContentHash)>();
+    let mut layers: Vec<_> = query.iter(world)
+        .map(|(info, visibility, render, hash)| (info.layer_type, visibility.visible, render.color, hash.0))
+        .collect();
+    layers.sort_by_key(|(layer_type, ..)| layer_type.z_order());
+    layers
+}
+
+/// Bring the cached meshes in line with the loaded layers
+fn update_meshes(app: &mut DemoLensApp, layers: &[(LayerType, bool, Color32, u64)], stackup: &Stackup) {
+    let inner_layers = layers.iter().filter(|(layer_type, ..)| layer_type.is_inner_copper()).count() as u8;
+    let state = &mut app.view_3d;
+    state.layers.retain(|layer_type, _| layers.iter().any(|(loaded, ..)| loaded == layer_type));
+
+    for &(layer_type, visible, _, content_hash) in layers {
+        if layer_type == LayerType::MechanicalOutline {
+            let board_current = state.board.as_ref()
+                .is_some_and(|(hash, built_with, _)| *hash == content_hash && built_with == stackup);
+            if !board_current {
+                let outline = crate::ecs::board_fill_polygon(&crate::ecs::get_layer_primitives(&mut app.ecs_world, layer_type));
+                state.board = Some((content_hash, *stackup, view3d::board_mesh(&outline, 0.0, stackup.board_thickness)));
+                state.needs_fit = true;
+            }
+            continue;
+        }
+
+        // Hidden layers are only built once they are shown
+        let z = stackup.layer_z(layer_type, inner_layers);
+        let current = state.layers.get(&layer_type)
+            .is_some_and(|cached| cached.content_hash == content_hash && cached.z == z);
+        if visible && !current {
+            let primitives = crate::ecs::get_layer_primitives(&mut app.ecs_world, layer_type);
+            let mesh = view3d::layer_mesh(&primitives, z, layer_type.is_bottom());
+            state.needs_fit |= state.layers.is_empty() && state.board.is_none();
+            state.layers.insert(layer_type, CachedMesh { content_hash, z, mesh });
+        }
+    }
+    if layers.iter().all(|(layer_type, ..)| *layer_type != LayerType::MechanicalOutline) {
+        state.board = None;
+    }
+}
+
+/// Bounds of everything built, for fitting the camera
+fn scene_bounds(state: &View3dState) -> Option<(nalgebra::Point3<f32>, nalgebra::Point3<f32>)> {
+    state.layers.values().map(|cached| &cached.mesh)
+        .chain(state.board.as_ref().map(|(_, _, mesh)| mesh))
+        .filter_map(Mesh3d::bounds)
+        .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.inf(&min_b), max_a.sup(&max_b)))
+}
+
+fn handle_camera_input(ui: &egui::Ui, response: &egui::Response, state: &mut View3dState, height: f32) {
+    if response.dragged_by(egui::PointerButton::Primary) {
+        let delta = response.drag_delta();
+        state.camera.orbit(delta.x * 0.01, delta.y * 0.01);
+    }
+    if response.dragged_by(egui::PointerButton::Secondary) || response.dragged_by(egui::PointerButton::Middle) {
+        state.camera.pan(response.drag_delta(), height);
+    }
+    if response.hovered() {
+        let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            state.camera.zoom((-scroll * 0.002).exp());
+        }
+    }
+    if response.double_clicked() {
+        state.needs_fit = true;
+    }
+}
+
+/// Paint the meshes back to front as one egui mesh
+fn paint_scene(painter: &egui::Painter, rect: egui::Rect, state: &View3dState, layers: &[(LayerType, bool, Color32, u64)]) {
+    let mut draw: Vec<(&Mesh3d, Color32)> = Vec::new();
+    let outline_visible = layers.iter().any(|(layer_type, visible, ..)| *layer_type == LayerType::MechanicalOutline && *visible);
+    if let Some((_, _, mesh)) = state.board.as_ref().filter(|_| outline_visible) {
+        draw.push((mesh, SUBSTRATE_COLOR));
+    }
+    for (layer_type, visible, color, _) in layers {
+        if let Some(cached) = state.layers.get(layer_type).filter(|_| *visible) {
+            draw.push((&cached.mesh, *color));
+        }
+    }
+
+    let mut triangles: Vec<(f32, [egui::Pos2; 3], Color32)> = Vec::new();
+    for (mesh, color) in draw {
+        let projected: Vec<Option<(egui::Pos2, f32)>> = mesh.positions.iter()
+            .map(|position| state.camera.project(*position, rect))
+            .collect();
+        for [a, b, c] in &mesh.triangles {
+            let (Some(pa), Some(pb), Some(pc)) = (projected[*a as usize], projected[*b as usize], projected[*c as usize]) else {
+                continue;
+            };
+            let (wa, wb, wc) = (mesh.positions[*a as usize], mesh.positions[*b as usize], mesh.positions[*c as usize]);
+            let normal = (wb - wa).cross(&(wc - wa));
+            triangles.push(((pa.1 + pb.1 + pc.1) / 3.0, [pa.0, pb.0, pc.0], view3d::shade(color, normal)));
+        }
+    }
+    triangles.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut mesh = egui::Mesh::default();
+    for (_, [a, b, c], color) in triangles {
+        let base = mesh.vertices.len() as u32;
+        mesh.colored_vertex(a, color);
+        mesh.colored_vertex(b, color);
+        mesh.colored_vertex(c, color);
+        mesh.add_triangle(base, base + 1, base + 2);
+    }
+    painter.add(egui::Shape::mesh(mesh));
+}
+
+/// 3D tab: toolbar and orbitable viewport of the loaded layers
+pub fn show_3d_view(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let layers = loaded_layers(&mut app.ecs_world);
+    let stackup = app.ecs_world.get_resource::<Stackup>().copied().unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        if ui.button("🔄 Rebuild 3D").on_hover_text("Regenerate every layer mesh, e.g. after reloading gerbers").clicked() {
+            app.view_3d.rebuild();
+        }
+        if ui.button("⛶ Fit").clicked() {
+            app.view_3d.needs_fit = true;
+        }
+        ui.separator();
+        ui.label(RichText::new(format!("{} triangles", app.view_3d.triangle_count())).color(Color32::GRAY));
+        ui.separator();
+        ui.label(RichText::new("Drag: orbit · Right/middle drag: pan · Scroll: zoom · Double-click: fit").small().color(Color32::GRAY));
+    });
+    ui.separator();
+
+    update_meshes(app, &layers, &stackup);
+    let state = &mut app.view_3d;
+
+    let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+    handle_camera_input(ui, &response, state, rect.height());
+    if state.needs_fit {
+        if let Some((min, max)) = scene_bounds(state) {
+            state.camera.fit(min, max);
+            state.needs_fit = false;
+        }
+    }
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, BACKGROUND_COLOR);
+    if layers.is_empty() {
+        painter.text(rect.center(), egui::Align2::CENTER_CENTER, "Load gerbers to see the 3D view", egui::FontId::proportional(16.0), Color32::GRAY);
+        return;
+    }
+    paint_scene(&painter, rect, state, &layers);
+}
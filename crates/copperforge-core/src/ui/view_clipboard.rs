@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use egui::{Color32, Rect};
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::ui::i18n::{tr, tr_args};
+use crate::ui::key_bindings::ShortcutAction;
+
+/// Give up on a screenshot the window backend never delivers
+const SCREENSHOT_TIMEOUT_SECS: f64 = 2.0;
+
+/// Tag on our screenshot request, to tell its result apart from other screenshots
+struct ViewScreenshot;
+
+/// Where a requested screenshot of the view goes
+#[derive(Debug, Clone, PartialEq)]
+enum ScreenshotTarget {
+    Clipboard,
+    /// PNG file chosen before the screenshot was taken
+    File(PathBuf),
+}
+
+impl ScreenshotTarget {
+    /// Action named in the log messages
+    fn action(&self) -> &'static str {
+        match self {
+            ScreenshotTarget::Clipboard => "Copy view",
+            ScreenshotTarget::File(_) => "Save view",
+        }
+    }
+}
+
+/// Gerber viewport of the last frame and a pending "Copy view" or "Save view" screenshot
+#[derive(Default)]
+pub struct ViewClipboardState {
+    /// Screen rect of the gerber view, updated every frame it is drawn
+    pub viewport: Option<Rect>,
+    /// Background the viewport was cleared with
+    pub background: Color32,
+    /// When the screenshot was requested, and what it is for
+    pending: Option<(f64, ScreenshotTarget)>,
+    /// Kept open so X11 and Wayland can still serve what was copied last
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl ViewClipboardState {
+    /// A screenshot is on its way, so the next frames end up in the image
+    pub fn capturing(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn clipboard(&mut self) -> Result<&mut arboard::Clipboard, String> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new().map_err(|e| format!("no clipboard available ({})", e))?);
+        }
+        Ok(self.clipboard.as_mut().expect("clipboard opened above"))
+    }
+
+    /// Put an image on the system clipboard
+    fn copy_image(&mut self, image: &egui::ColorImage) -> Result<(), String> {
+        let bytes: Vec<u8> = image.pixels.iter().flat_map(|pixel| pixel.to_array()).collect();
+        let data = arboard::ImageData { width: image.size[0], height: image.size[1], bytes: bytes.into() };
+        self.clipboard()?.set_image(data).map_err(|e| e.to_string())
+    }
+
+    /// Put text on the system clipboard
+    fn copy_text(&mut self, text: String) -> Result<(), String> {
+        self.clipboard()?.set_text(text).map_err(|e| e.to_string())
+    }
+}
+
+/// Ask the window for a screenshot; `poll_view_screenshot` hands the viewport part of it to `target`
+fn request_view_screenshot(ctx: &egui::Context, app: &mut DemoLensApp, target: ScreenshotTarget, logger: &ReactiveEventLogger) {
+    if app.view_clipboard.viewport.is_none() {
+        logger.log_error(&format!("{} failed: the gerber view is not open", target.action()));
+        return;
+    }
+    if app.view_clipboard.pending.is_some() {
+        return;
+    }
+    app.view_clipboard.pending = Some((ctx.input(|i| i.time), target));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::new(ViewScreenshot)));
+    ctx.request_repaint();
+}
+
+/// Copy the gerber view to the clipboard as an image
+pub fn request_view_image_copy(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    request_view_screenshot(ctx, app, ScreenshotTarget::Clipboard, logger);
+}
+
+/// Ask for a file name, then save the gerber view there as PNG
+pub fn request_view_image_save(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    if app.view_clipboard.viewport.is_none() {
+        logger.log_error("Save view failed: the gerber view is not open");
+        return;
+    }
+    if app.view_clipboard.pending.is_some() {
+        return;
+    }
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("PNG", &["png"])
+        .set_file_name("gerber_view.png")
+        .save_file()
+    else {
+        return;
+    };
+    request_view_screenshot(ctx, app, ScreenshotTarget::File(path), logger);
+}
+
+/// Crop a delivered screenshot to the gerber view and copy or save it
+/// The screenshot is the rendered frame, so grid, markers and overlays are included
+pub fn poll_view_screenshot(ctx: &egui::Context, app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some((requested_at, target)) = app.view_clipboard.pending.clone() else {
+        return;
+    };
+    let action = target.action();
+
+    let screenshot = ctx.input(|i| {
+        i.raw.events.iter().find_map(|event| match event {
+            egui::Event::Screenshot { user_data, image, .. }
+                if user_data.data.as_ref().is_some_and(|data| data.is::<ViewScreenshot>()) =>
+            {
+                Some(Arc::clone(image))
+            }
+            _ => None,
+        })
+    });
+
+    let Some(image) = screenshot else {
+        if ctx.input(|i| i.time) - requested_at > SCREENSHOT_TIMEOUT_SECS {
+            app.view_clipboard.pending = None;
+            logger.log_error(&format!("{} failed: the window system did not deliver a screenshot", action));
+        } else {
+            ctx.request_repaint();
+        }
+        return;
+    };
+
+    app.view_clipboard.pending = None;
+    let Some(viewport) = app.view_clipboard.viewport else {
+        logger.log_error(&format!("{} failed: the gerber view is not open", action));
+        return;
+    };
+    let region = image.region(&viewport, Some(ctx.pixels_per_point()));
+    let [width, height] = region.size;
+    if width == 0 || height == 0 {
+        logger.log_error(&format!("{} failed: the gerber view is empty", action));
+        return;
+    }
+    match target {
+        ScreenshotTarget::Clipboard => match app.view_clipboard.copy_image(&region) {
+            Ok(()) => logger.log_info(&format!("Copied view to clipboard ({} × {} px)", width, height)),
+            Err(e) => logger.log_error(&format!("Copy view failed: {}", e)),
+        },
+        ScreenshotTarget::File(path) => {
+            let pixels_per_mm = (app.view_state.scale * ctx.pixels_per_point()) as f64;
+            match crate::export::PngExporter::export_current_view(&region, &path, pixels_per_mm) {
+                Ok(()) => logger.log_info(&format!("Saved view to {} ({} × {} px)", path.display(), width, height)),
+                Err(e) => logger.log_error(&format!("Save view failed: {}", e)),
+            }
+        }
+    }
+}
+
+/// Copy the visible layers of the gerber view as SVG text
+pub fn copy_view_svg(app: &mut DemoLensApp, logger: &ReactiveEventLogger) {
+    let Some(viewport) = app.view_clipboard.viewport else {
+        logger.log_error("Copy view as SVG failed: the gerber view is not open");
+        return;
+    };
+    let annotations = app.annotations.exported();
+    match crate::export::svg::view_svg(
+        &mut app.ecs_world,
+        &app.display_manager,
+        &app.view_state,
+        &viewport,
+        app.view_clipboard.background,
+        &annotations,
+    ) {
+        Ok(svg) => {
+            let size = svg.len();
+            match app.view_clipboard.copy_text(svg) {
+                Ok(()) => logger.log_info(&format!("Copied view to clipboard as SVG ({} KB)", size.div_ceil(1024))),
+                Err(e) => logger.log_error(&format!("Copy view as SVG failed: {}", e)),
+            }
+        }
+        Err(e) => logger.log_error(&format!("Copy view as SVG failed: {}", e)),
+    }
+}
+
+/// "Copy view" menu in the gerber view toolbar
+pub fn render_copy_view_controls(ui: &mut egui::Ui, app: &mut DemoLensApp) {
+    let logger = ReactiveEventLogger::with_colors(&app.logger_state, &app.log_colors);
+    ui.menu_button(tr("view_copy.menu"), |ui| {
+        let shortcut = app.key_bindings.get(ShortcutAction::CopyView);
+        if ui.button(tr_args("view_copy.image", &[("shortcut", &shortcut)]))
+            .on_hover_text(tr("view_copy.image_hint"))
+            .clicked()
+        {
+            request_view_image_copy(ui.ctx(), app, &logger);
+            ui.close();
+        }
+        if ui.button(tr("view_copy.svg"))
+            .on_hover_text(tr("view_copy.svg_hint"))
+            .clicked()
+        {
+            copy_view_svg(app, &logger);
+            ui.close();
+        }
+        ui.separator();
+        let shortcut = app.key_bindings.get(ShortcutAction::SaveViewPng);
+        if ui.button(tr_args("view_copy.save_png", &[("shortcut", &shortcut)]))
+            .on_hover_text(tr("view_copy.save_png_hint"))
+            .clicked()
+        {
+            ui.close();
+            request_view_image_save(ui.ctx(), app, &logger);
+        }
+        ui.separator();
+        ui.checkbox(&mut app.annotations.include_in_exports, tr("view_copy.annotations"))
+            .on_hover_text(tr("view_copy.annotations_hint"));
+    });
+}
@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+
+use egui_lens::ReactiveEventLogger;
+
+use crate::DemoLensApp;
+use crate::project::workspace::{self, WorkspaceSettings};
+use crate::ui::i18n::{tr, tr_args};
+
+/// Workspace file waiting for the user to confirm it may be overwritten
+#[derive(Default)]
+pub struct WorkspaceSyncState {
+    confirm_overwrite: Option<PathBuf>,
+}
+
+/// Apply the workspace file beside `pcb_path`, if there is one; called after the gerbers are loaded
+pub fn apply_workspace_file(app: &mut DemoLensApp, pcb_path: &Path, logger: &ReactiveEventLogger) {
+    match WorkspaceSettings::load_for_pcb(pcb_path) {
+        Ok(Some(settings)) => {
+            let applied = app.apply_workspace_settings(settings);
+            logger.log_info(&format!(
+                "Applied workspace settings from {} ({})",
+                workspace::workspace_path(pcb_path).display(),
+                if applied.is_empty() { "nothing listed".to_string() } else { applied.join(", ") }
+            ));
+        }
+        Ok(None) => {}
+        Err(e) => logger.log_warning(&format!("Workspace settings not applied: {}", e)),
+    }
+}
+
+fn save_workspace_file(app: &mut DemoLensApp, pcb_path: &Path, logger: &ReactiveEventLogger) {
+    match app.workspace_settings().save_for_pcb(pcb_path) {
+        Ok(path) => logger.log_info(&format!("Saved workspace settings to {}", path.display())),
+        Err(e) => logger.log_error(&e),
+    }
+}
+
+/// "Save workspace settings" button for the loaded PCB; asks before replacing an existing file
+pub fn show_workspace_controls(ui: &mut egui::Ui, app: &mut DemoLensApp, pcb_path: &Path, logger: &ReactiveEventLogger) {
+    let path = workspace::workspace_path(pcb_path);
+    if ui.button(tr("workspace.save"))
+        .on_hover_text(tr_args("workspace.save_hint", &[("file", &workspace::WORKSPACE_FILE_NAME)]))
+        .clicked()
+    {
+        if path.exists() {
+            app.workspace_sync.confirm_overwrite = Some(path.clone());
+        } else {
+            save_workspace_file(app, pcb_path, logger);
+        }
+    }
+
+    let Some(existing) = app.workspace_sync.confirm_overwrite.clone() else {
+        return;
+    };
+    let mut overwrite = false;
+    let mut cancel = false;
+    egui::Window::new(tr("workspace.overwrite_title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ui.ctx(), |ui| {
+            ui.label(tr_args("workspace.overwrite_body", &[("path", &existing.display())]));
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                overwrite = ui.button(tr("workspace.overwrite")).clicked();
+                cancel = ui.button(tr("workspace.cancel")).clicked();
+            });
+        });
+    if overwrite {
+        app.workspace_sync.confirm_overwrite = None;
+        save_workspace_file(app, pcb_path, logger);
+    } else if cancel || existing != path {
+        app.workspace_sync.confirm_overwrite = None;
+    }
+}
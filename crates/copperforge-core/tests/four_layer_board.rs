@@ -0,0 +1,75 @@
+//! Inner copper layers on a small bundled four-layer board
+//!
+//! `tests/fixtures/four_layer` holds the copper and outline gerbers of a
+//! four-layer board in KiCad's conventions. In1.Cu is found by its file name;
+//! the second inner layer has a name no pattern knows and is placed by its
+//! X2 `FileFunction` attribute alone.
+
+use std::path::{Path, PathBuf};
+
+use copperforge_core::api::{self, LayerType};
+use copperforge_core::ecs::{LayerInfo, RenderProperties};
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/four_layer")
+}
+
+fn load_four_layer_board() -> bevy_ecs::world::World {
+    let mut world = api::setup_ecs_world();
+    let summary = api::load_gerber_directory(&mut world, &fixture_dir()).expect("fixture directory loads");
+    assert_eq!(summary.loaded_layers, 5);
+    assert_eq!(summary.unassigned_files, 0);
+    world
+}
+
+#[test]
+fn test_inner_layers_detected_between_top_and_bottom() {
+    let world = load_four_layer_board();
+    assert_eq!(api::loaded_layer_types(&world), vec![
+        LayerType::Copper(1),
+        LayerType::inner_copper(1),
+        LayerType::inner_copper(2),
+        LayerType::Copper(2),
+        LayerType::MechanicalOutline,
+    ]);
+
+    let file_name = |layer_type| {
+        let layer = api::layer(&world, layer_type).expect("layer is loaded");
+        layer.file_path.and_then(Path::file_name).and_then(|name| name.to_str()).expect("layer has a source file").to_string()
+    };
+    assert_eq!(api::detect_layer_type(&file_name(LayerType::inner_copper(1))), Some(LayerType::inner_copper(1)));
+    assert_eq!(api::detect_layer_type(&file_name(LayerType::inner_copper(2))), None);
+
+    let plane = api::layer(&world, LayerType::inner_copper(1)).expect("In1.Cu is loaded").stats();
+    assert_eq!((plane.traces, plane.flashes, plane.regions), (0, 1, 1));
+    let signal = api::layer(&world, LayerType::inner_copper(2)).expect("In2.Cu is loaded").stats();
+    assert_eq!((signal.traces, signal.flashes, signal.regions), (2, 2, 0));
+}
+
+#[test]
+fn test_inner_layers_visible_and_stacked() {
+    let mut world = load_four_layer_board();
+    assert!(api::layers(&world).iter().all(|layer| layer.visible));
+
+    // Top copper over In1 over In2 over bottom copper, each with its own colour
+    let mut query = world.query::<(&LayerInfo, &RenderProperties)>();
+    let z_order = |layer_type| {
+        query.iter(&world)
+            .find(|(info, _)| info.layer_type == layer_type)
+            .map(|(_, props)| props.z_order)
+            .expect("layer is loaded")
+    };
+    let stack: Vec<i32> = [LayerType::Copper(1), LayerType::inner_copper(1), LayerType::inner_copper(2), LayerType::Copper(2)]
+        .into_iter()
+        .map(z_order)
+        .collect();
+    assert!(stack.windows(2).all(|pair| pair[0] > pair[1]), "{:?}", stack);
+    let colors: Vec<[u8; 4]> = api::layers(&world).iter().filter(|layer| layer.layer_type.is_copper()).map(|layer| layer.color).collect();
+    assert!(colors.iter().enumerate().all(|(i, color)| !colors[..i].contains(color)), "{:?}", colors);
+
+    // Flipping the board hides top copper only; inner layers are not lumped with the bottom
+    for layer_type in [LayerType::inner_copper(1), LayerType::inner_copper(2)] {
+        assert!(layer_type.should_render(true) && layer_type.should_render(false));
+    }
+    assert!(!LayerType::Copper(1).should_render(false));
+}
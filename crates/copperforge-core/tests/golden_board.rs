@@ -0,0 +1,147 @@
+//! Load → transform → render pipeline on a small bundled board
+//!
+//! `tests/fixtures/demo_board` holds the gerbers and drill file of a two-layer
+//! demo board in KiCad's naming and attribute conventions. The tests check layer
+//! detection, board size and DRC results, and rasterize top copper with the PNG
+//! exporter against `tests/fixtures/golden`.
+//!
+//! After an intended rendering change, regenerate the reference images with
+//!
+//!     COPPERFORGE_UPDATE_GOLDENS=1 cargo test -p copperforge-core --test golden_board
+//!
+//! and review the new PNGs before committing them.
+
+use std::path::{Path, PathBuf};
+
+use copperforge_core::api::{self, DrcRules, LayerType, Side};
+use copperforge_core::display::DisplayManager;
+use copperforge_core::export::raster::primitives_bounds;
+use copperforge_core::export::{PngExporter, PngResolution, save_png_with_dpi};
+use image::RgbaImage;
+
+/// Exported images are 10 pixels per mm
+const GOLDEN_DPI: f32 = 254.0;
+/// Largest per-channel difference for a pixel to count as matching
+const CHANNEL_TOLERANCE: u8 = 16;
+/// Share of pixels allowed to differ, for edge pixels whose centre sits on a shape boundary
+const MAX_MISMATCH_FRACTION: f64 = 0.002;
+
+const UM: f64 = 0.001;
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/demo_board")
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/golden").join(name)
+}
+
+fn load_demo_board() -> bevy_ecs::world::World {
+    let mut world = api::setup_ecs_world();
+    let summary = api::load_gerber_directory(&mut world, &fixture_dir()).expect("fixture directory loads");
+    assert_eq!(summary.loaded_layers, 5);
+    assert_eq!(summary.unassigned_files, 0);
+    assert_eq!(summary.drill_holes, 3);
+    world
+}
+
+/// Pixels whose channels differ by more than the tolerance
+fn mismatched_pixels(actual: &RgbaImage, expected: &RgbaImage) -> usize {
+    actual.pixels()
+        .zip(expected.pixels())
+        .filter(|(a, b)| a.0.iter().zip(b.0.iter()).any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE))
+        .count()
+}
+
+/// Compare against the golden image, or rewrite it when COPPERFORGE_UPDATE_GOLDENS is set
+fn assert_matches_golden(actual: &RgbaImage, name: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("COPPERFORGE_UPDATE_GOLDENS").is_some() {
+        save_png_with_dpi(actual, &path, GOLDEN_DPI as f64).expect("golden image is written");
+        return;
+    }
+
+    let expected = image::open(&path)
+        .unwrap_or_else(|e| panic!("{} cannot be read ({}); set COPPERFORGE_UPDATE_GOLDENS=1 to create it", path.display(), e))
+        .to_rgba8();
+    let actual_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    if actual.dimensions() != expected.dimensions() {
+        let _ = actual.save(&actual_path);
+        panic!("{} is {:?}, rendered {:?} (saved to {})", name, expected.dimensions(), actual.dimensions(), actual_path.display());
+    }
+    let mismatched = mismatched_pixels(actual, &expected);
+    let allowed = (MAX_MISMATCH_FRACTION * (actual.width() * actual.height()) as f64) as usize;
+    if mismatched > allowed {
+        let _ = actual.save(&actual_path);
+        panic!("{} pixels differ from {} (at most {} allowed); rendering saved to {}", mismatched, name, allowed, actual_path.display());
+    }
+}
+
+#[test]
+fn test_layer_detection() {
+    let world = load_demo_board();
+    let mut expected = vec![
+        LayerType::Copper(1),
+        LayerType::Copper(2),
+        LayerType::Silkscreen(Side::Top),
+        LayerType::Soldermask(Side::Top),
+        LayerType::MechanicalOutline,
+    ];
+    let order = LayerType::all();
+    expected.sort_by_key(|layer_type| order.iter().position(|l| l == layer_type));
+    assert_eq!(api::loaded_layer_types(&world), expected);
+
+    for layer in api::layers(&world) {
+        let file_name = layer.file_path.and_then(Path::file_name).and_then(|name| name.to_str()).expect("layer has a source file");
+        assert_eq!(api::detect_layer_type(file_name), Some(layer.layer_type), "{}", file_name);
+    }
+    let top = api::layer(&world, LayerType::Copper(1)).expect("top copper is loaded");
+    let stats = top.stats();
+    assert_eq!((stats.traces, stats.flashes, stats.regions), (5, 5, 1));
+}
+
+#[test]
+fn test_combined_bounding_box() {
+    let world = load_demo_board();
+    // The outline is a 30 × 20 mm rectangle drawn with a 0.05 mm line
+    let primitives: Vec<_> = api::layers(&world).iter().flat_map(|layer| layer.primitives.iter().cloned()).collect();
+    let bounds = primitives_bounds(&primitives).expect("board has geometry");
+    assert!((bounds.width() - 30.05).abs() < UM, "width {}", bounds.width());
+    assert!((bounds.height() - 20.05).abs() < UM, "height {}", bounds.height());
+    assert!((bounds.min.x - 99.975).abs() < UM && (bounds.max.y + 49.975).abs() < UM, "bounds {:?}", bounds);
+}
+
+#[test]
+fn test_drc_violation_counts() {
+    let mut world = load_demo_board();
+    let results = api::run_drc(&mut world, &DrcRules::default()).expect("outline is loaded");
+    assert_eq!(results.violations.len(), 0, "{:#?}", results.violations);
+    assert_eq!(results.mask_warnings.len(), 0, "{:#?}", results.mask_warnings);
+
+    // The 0.3 mm via drill is the only hole under a 0.35 mm minimum
+    let strict = DrcRules { min_drill_diameter: 0.35, ..DrcRules::default() };
+    let results = api::run_drc(&mut world, &strict).expect("outline is loaded");
+    assert_eq!(results.violations.len(), 1, "{:#?}", results.violations);
+    assert!((results.violations[0].x - 115.0).abs() < 1e-3 && (results.violations[0].y + 65.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_top_copper_matches_golden_image() {
+    let mut world = load_demo_board();
+    let display_manager = DisplayManager::new();
+    let image = PngExporter::render_layer(&mut world, &display_manager, LayerType::Copper(1), PngResolution::Dpi(GOLDEN_DPI))
+        .expect("top copper renders");
+    assert_matches_golden(&image, "demo-F_Cu.png");
+
+    // Mirrored in X, the export is the same image flipped horizontally
+    let mut query = world.query::<&mut copperforge_core::ecs::Transform>();
+    for mut transform in query.iter_mut(&mut world) {
+        transform.mirroring.x = true;
+    }
+    let mirrored = PngExporter::render_layer(&mut world, &display_manager, LayerType::Copper(1), PngResolution::Dpi(GOLDEN_DPI))
+        .expect("mirrored top copper renders");
+    let flipped = image::imageops::flip_horizontal(&image);
+    assert_eq!(mirrored.dimensions(), flipped.dimensions());
+    let allowed = (MAX_MISMATCH_FRACTION * (image.width() * image.height()) as f64) as usize;
+    assert!(mismatched_pixels(&mirrored, &flipped) <= allowed);
+}
@@ -2,10 +2,15 @@ fn main() -> eframe::Result<()> {
     use copperforge_core::DemoLensApp;
     use copperforge_core::platform::parameters::gui::APPLICATION_NAME;
     
-    // Configure env_logger to filter out gerber_parser warnings
-    env_logger::Builder::from_default_env()
+    // Keep gerber_parser warnings out of the terminal; during a load they are
+    // captured per file and shown in the layer panel instead
+    let logger = env_logger::Builder::from_default_env()
         .filter_module("gerber_parser::parser", log::LevelFilter::Off)
-        .init();
+        .build();
+    let max_level = logger.filter().max(log::LevelFilter::Warn);
+    log::set_boxed_logger(Box::new(copperforge_core::ecs::ParserLogCapture::new(logger)))
+        .expect("logger is installed once");
+    log::set_max_level(max_level);
     eframe::run_native(
         APPLICATION_NAME,
         eframe::NativeOptions {